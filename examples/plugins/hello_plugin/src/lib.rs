@@ -4,10 +4,12 @@
 //! - Logging when commands are about to execute (pre_execute hook)
 //! - Logging when commands complete (post_execute hook)
 //! - Greeting the user on load
+//! - Providing a `greet` subcommand (`cmdrun greet [name]`)
 
 use ahash::AHashMap;
 use cmdrun::plugin::api::{
-    CommandResult, Plugin, PluginCapabilities, PluginContext, PluginMetadata,
+    CommandResult, CustomCommand, Plugin, PluginCapabilities, PluginContext, PluginMetadata,
+    PreExecuteDecision,
 };
 use cmdrun::error::Result;
 use std::any::Any;
@@ -32,7 +34,7 @@ impl Plugin for HelloPlugin {
                 pre_execute: true,
                 post_execute: true,
                 on_error: true,
-                custom_commands: false,
+                custom_commands: true,
                 config_modification: false,
             },
         }
@@ -56,7 +58,7 @@ impl Plugin for HelloPlugin {
         Ok(())
     }
 
-    fn pre_execute(&self, context: &mut PluginContext) -> Result<bool> {
+    fn pre_execute(&self, context: &mut PluginContext) -> Result<PreExecuteDecision> {
         println!(
             "🚀 [Hello Plugin] About to execute: {}",
             context.command_name
@@ -71,7 +73,7 @@ impl Plugin for HelloPlugin {
         }
 
         // Always continue with execution
-        Ok(true)
+        Ok(PreExecuteDecision::Continue)
     }
 
     fn post_execute(
@@ -111,6 +113,23 @@ impl Plugin for HelloPlugin {
         Ok(())
     }
 
+    fn commands(&self) -> Vec<CustomCommand> {
+        vec![CustomCommand::new(
+            "greet",
+            "Greet someone by name",
+            |args, _context| {
+                let name = args.first().map(String::as_str).unwrap_or("world");
+                Ok(CommandResult {
+                    exit_code: 0,
+                    stdout: format!("👋 Hello, {}!\n", name),
+                    stderr: String::new(),
+                    duration_ms: 0,
+                    metadata: AHashMap::new(),
+                })
+            },
+        )]
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -136,6 +155,15 @@ mod tests {
         assert!(metadata.capabilities.pre_execute);
         assert!(metadata.capabilities.post_execute);
         assert!(metadata.capabilities.on_error);
+        assert!(metadata.capabilities.custom_commands);
+    }
+
+    #[test]
+    fn test_greet_command() {
+        let plugin = HelloPlugin::default();
+        let commands = plugin.commands();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].name, "greet");
     }
 
     #[test]
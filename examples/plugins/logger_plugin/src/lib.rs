@@ -9,7 +9,7 @@ use ahash::AHashMap;
 use chrono::{DateTime, Utc};
 use cmdrun::error::Result;
 use cmdrun::plugin::api::{
-    CommandResult, Plugin, PluginCapabilities, PluginContext, PluginMetadata,
+    CommandResult, Plugin, PluginCapabilities, PluginContext, PluginMetadata, PreExecuteDecision,
 };
 use serde::{Deserialize, Serialize};
 use std::any::Any;
@@ -22,6 +22,10 @@ use std::path::PathBuf;
 struct LogEntry {
     timestamp: DateTime<Utc>,
     command_name: String,
+    /// Shell command that ran (variables expanded)
+    command: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    args: Vec<String>,
     exit_code: Option<i32>,
     duration_ms: Option<u64>,
     working_dir: String,
@@ -115,10 +119,12 @@ impl Plugin for LoggerPlugin {
         Ok(())
     }
 
-    fn pre_execute(&self, context: &mut PluginContext) -> Result<bool> {
+    fn pre_execute(&self, context: &mut PluginContext) -> Result<PreExecuteDecision> {
         let entry = LogEntry {
             timestamp: Utc::now(),
             command_name: context.command_name.clone(),
+            command: context.command_spec.clone(),
+            args: context.args.clone(),
             exit_code: None,
             duration_ms: None,
             working_dir: context.working_dir.clone(),
@@ -129,19 +135,22 @@ impl Plugin for LoggerPlugin {
 
         if self.log_level == "debug" || self.log_level == "trace" {
             println!(
-                "📝 [Logger] Pre-execute: {} at {}",
+                "📝 [Logger] Pre-execute: {} ({}) at {}",
                 context.command_name,
+                context.command_spec,
                 entry.timestamp.format("%Y-%m-%d %H:%M:%S")
             );
         }
 
-        Ok(true)
+        Ok(PreExecuteDecision::Continue)
     }
 
     fn post_execute(&self, context: &PluginContext, result: &mut CommandResult) -> Result<()> {
         let entry = LogEntry {
             timestamp: Utc::now(),
             command_name: context.command_name.clone(),
+            command: context.command_spec.clone(),
+            args: context.args.clone(),
             exit_code: Some(result.exit_code),
             duration_ms: Some(result.duration_ms),
             working_dir: context.working_dir.clone(),
@@ -174,6 +183,8 @@ impl Plugin for LoggerPlugin {
         let entry = LogEntry {
             timestamp: Utc::now(),
             command_name: context.command_name.clone(),
+            command: context.command_spec.clone(),
+            args: context.args.clone(),
             exit_code: None,
             duration_ms: None,
             working_dir: context.working_dir.clone(),
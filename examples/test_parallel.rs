@@ -5,7 +5,7 @@ use cmdrun::command::dependency::DependencyGraph;
 use cmdrun::command::executor::{CommandExecutor, ExecutionContext};
 use cmdrun::config::schema::{Command, CommandSpec, CommandsConfig, GlobalConfig, PluginsConfig};
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -51,13 +51,18 @@ async fn main() -> anyhow::Result<()> {
         } else {
             "bash".to_string()
         },
-        timeout: Some(10),
+        timeout: Some(Duration::from_secs(10)),
         strict: true,
         echo: true,
         color: true,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: cmdrun::command::executor::DEFAULT_KILL_GRACE_MS,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -118,6 +123,8 @@ fn create_test_config() -> CommandsConfig {
             description: "Root command".to_string(),
             cmd: CommandSpec::Single("echo root_executed".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec!["a".to_string(), "b".to_string()],
             platform: vec![],
@@ -125,8 +132,16 @@ fn create_test_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -137,6 +152,8 @@ fn create_test_config() -> CommandsConfig {
             description: "Command A".to_string(),
             cmd: CommandSpec::Single("echo command_a_executed".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec!["base".to_string()],
             platform: vec![],
@@ -144,8 +161,16 @@ fn create_test_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -156,6 +181,8 @@ fn create_test_config() -> CommandsConfig {
             description: "Command B".to_string(),
             cmd: CommandSpec::Single("echo command_b_executed".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec!["base".to_string()],
             platform: vec![],
@@ -163,8 +190,16 @@ fn create_test_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -175,6 +210,8 @@ fn create_test_config() -> CommandsConfig {
             description: "Base command".to_string(),
             cmd: CommandSpec::Single("echo base_executed".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -182,8 +219,16 @@ fn create_test_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
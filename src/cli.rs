@@ -1,7 +1,9 @@
 //! CLI argument definitions
 
+use crate::config::duration::parse_duration;
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Color output control
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -38,6 +40,12 @@ pub struct Cli {
     /// Use this option to specify which configuration file to use.
     /// This allows you to maintain multiple command sets for different
     /// purposes (work, personal, projects, environments, etc.)
+    /// Use `-` to read the configuration from stdin.
+    ///
+    /// Repeat the option to layer several files: they are merged in order,
+    /// later files replacing earlier commands and aliases of the same name
+    /// and adding to `[config.env]` key by key. Commands that edit the
+    /// configuration write to the last file.
     #[arg(
         short,
         long,
@@ -45,7 +53,7 @@ pub struct Cli {
         global = true,
         conflicts_with = "global"
     )]
-    pub config: Option<PathBuf>,
+    pub config: Vec<PathBuf>,
 
     /// Use only global configuration (skip local config search)
     ///
@@ -55,6 +63,14 @@ pub struct Cli {
     #[arg(short = 'G', long, global = true)]
     pub global: bool,
 
+    /// Look for the local config in the current directory only
+    ///
+    /// By default cmdrun walks up from the current directory to the
+    /// filesystem root and uses the first commands.toml (or
+    /// .cmdrun/commands.toml) it finds, like git does for `.git`.
+    #[arg(long, global = true)]
+    pub no_walk: bool,
+
     /// Control colored output
     ///
     /// Choose when to use colored output. The default is 'auto', which
@@ -66,6 +82,22 @@ pub struct Cli {
     #[arg(long, value_enum, default_value = "auto", global = true)]
     pub color: ColorChoice,
 
+    /// Skip command security validation for this run (DANGEROUS)
+    ///
+    /// Bypasses the shell injection checks normally applied before a command
+    /// is executed. `command_allowlist` is still enforced. Only use this for
+    /// trusted scripts; a warning is printed every time validation is skipped.
+    #[arg(long, global = true)]
+    pub allow_unsafe: bool,
+
+    /// Report failures as a JSON object on stderr
+    ///
+    /// The last line of stderr becomes
+    /// `{"error": ..., "kind": ..., "exit_code": ..., "command": ...}` so that
+    /// editors and other tools can parse failures. The exit code is unchanged.
+    #[arg(long, global = true)]
+    pub error_json: bool,
+
     /// Subcommand to execute
     #[command(subcommand)]
     pub command: Commands,
@@ -73,6 +105,14 @@ pub struct Cli {
     /// Verbose output (-v, -vv, -vvv for more verbosity)
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub verbose: u8,
+
+    /// Suppress cmdrun's own status lines
+    ///
+    /// Hides "Running:", "Completed in", command echo, group headers and
+    /// progress display. The command's own stdout/stderr, warnings and errors
+    /// are still printed.
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -81,11 +121,13 @@ pub enum Commands {
     ///
     /// Execute a command with automatic dependency resolution. Dependencies
     /// are executed in the correct order before the main command runs.
-    /// Use --parallel to execute independent dependencies concurrently.
+    /// Use --parallel to execute independent dependencies concurrently, or
+    /// --sequential to run them one at a time.
     ///
     /// Examples:
     ///   cmdrun run build
     ///   cmdrun run test --parallel
+    ///   cmdrun run test --sequential
     ///   cmdrun run deploy -- --env prod
     #[command(visible_alias = "r")]
     Run {
@@ -97,8 +139,175 @@ pub enum Commands {
         args: Vec<String>,
 
         /// Execute dependencies in parallel when possible
+        ///
+        /// Forces parallel execution even if the command sets `parallel = false`.
         #[arg(short, long)]
         parallel: bool,
+
+        /// Execute dependencies one at a time, in dependency order
+        ///
+        /// Forces serial execution even if the command sets `parallel = true`.
+        #[arg(long, conflicts_with = "parallel")]
+        sequential: bool,
+
+        /// Merge stderr into stdout as a single ordered stream (2>&1)
+        #[arg(long)]
+        merge_stderr: bool,
+
+        /// Automatically answer yes to confirmation prompts (`confirm = true`)
+        ///
+        /// Required when stdin is not a terminal. Skipped confirmations are
+        /// recorded in history.
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Write full trace-level logs of this run to a file (JSON lines)
+        ///
+        /// Console output keeps its normal verbosity.
+        #[arg(long, value_name = "PATH")]
+        log_file: Option<PathBuf>,
+
+        /// Also write the command's stdout/stderr to a file
+        ///
+        /// Output is appended after a header with the timestamp, command name
+        /// and arguments. ANSI color codes are stripped from the file.
+        #[arg(long, value_name = "PATH")]
+        log: Option<PathBuf>,
+
+        /// Save a JSON manifest of this run to a file
+        ///
+        /// Records the resolved command lines, environment, shell, platform,
+        /// cmdrun version, exit code, duration and output. Values of
+        /// environment variables matching `redact_patterns` are written as
+        /// `***REDACTED***`. The manifest is written even if the command fails.
+        #[arg(long, value_name = "PATH")]
+        capture: Option<PathBuf>,
+
+        /// Run the command (and its dependencies) in this directory
+        ///
+        /// Overrides the `working_dir` config setting for this invocation.
+        /// Relative paths are resolved against the current directory; a
+        /// command's own relative `working_dir` is resolved against this one.
+        #[arg(long, value_name = "DIR")]
+        cwd: Option<PathBuf>,
+
+        /// Run the command (and its dependencies) with this shell
+        ///
+        /// Overrides both the detected shell and the `shell` config setting
+        /// for this invocation. Accepts a name on PATH or a path.
+        #[arg(long, value_name = "NAME_OR_PATH")]
+        shell: Option<String>,
+
+        /// Print the resolved command line to stdout before executing it
+        ///
+        /// Shows the exact string handed to the shell after env and
+        /// positional interpolation, one line per step. Values of
+        /// environment variables matching `redact_patterns` are shown as
+        /// `***REDACTED***`.
+        #[arg(long)]
+        print_command: bool,
+
+        /// Only print the resolved command line; do not execute anything
+        ///
+        /// Implies --print-command. Hooks and confirmations are skipped.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Maximum number of commands run concurrently within a parallel group
+        ///
+        /// Overrides `max_parallel` in [config]. Defaults to the number of
+        /// logical CPUs.
+        #[arg(short, long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        jobs: Option<usize>,
+
+        /// Re-run the command whenever its watched files change
+        ///
+        /// Same as `cmdrun watch <name>`, using the command's `[commands.<name>.watch]`
+        /// settings (paths, patterns, exclude, debounce_ms).
+        #[arg(long)]
+        watch: bool,
+
+        /// Print a timing breakdown after the run
+        ///
+        /// Lists every executed command with its wall-clock duration and
+        /// whether it ran alongside others in a parallel group, slowest
+        /// first, followed by the critical path through the dependency graph.
+        #[arg(long)]
+        profile: bool,
+
+        /// Format of the --profile report
+        #[arg(long = "output", value_enum, value_name = "FORMAT", default_value_t = ProfileFormat::Text, requires = "profile")]
+        profile_format: ProfileFormat,
+
+        /// Timeout for this run (e.g. 500ms, 30s, 5m, 1h; a bare number is seconds)
+        ///
+        /// Overrides the command's `timeout` and the global `timeout` setting.
+        #[arg(short, long, value_name = "DURATION", value_parser = parse_duration)]
+        timeout: Option<Duration>,
+
+        /// Set an environment variable for this run (repeatable)
+        ///
+        /// Takes precedence over the global and command `env` (dependencies
+        /// included); positional arguments still win over `--env 1=...`.
+        #[arg(long = "env", value_name = "KEY=VALUE", value_parser = parse_env_override)]
+        env: Vec<(String, String)>,
+
+        /// Send a desktop notification when the run finishes
+        ///
+        /// Same as `notify_on_completion = true` for this run. Only runs that
+        /// take at least `notify_threshold_ms` are notified; requires a build
+        /// with the `notifications` feature.
+        #[arg(long)]
+        notify: bool,
+    },
+
+    /// Run an ad-hoc command through cmdrun's execution pipeline
+    ///
+    /// A single argument is used as the command line verbatim; several
+    /// arguments are shell-quoted and joined, so each stays one argument.
+    /// The result is executed like a defined
+    /// command: security validation, global env/shell/strict settings,
+    /// timeout and history recording (as `exec:<program>`) all apply.
+    ///
+    /// Examples:
+    ///   cmdrun exec -- cargo check
+    ///   cmdrun exec --timeout 10 -- ./scripts/smoke-test.sh
+    Exec {
+        /// Command and arguments to execute
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+
+        /// Timeout (e.g. 500ms, 30s, 5m; a bare number is seconds).
+        /// Defaults to the global timeout
+        #[arg(short, long, value_name = "DURATION", value_parser = parse_duration)]
+        timeout: Option<Duration>,
+    },
+
+    /// Run a command repeatedly and report timing statistics
+    ///
+    /// Executes the command (without its dependencies) `--runs` times after
+    /// `--warmup` discarded runs and prints min/max/mean/median/stddev of
+    /// the wall-clock durations. Bench runs are not recorded in history.
+    /// Stops at the first failing run unless --ignore-failures is given.
+    ///
+    /// Examples:
+    ///   cmdrun bench build
+    ///   cmdrun bench test --runs 20 --warmup 3
+    Bench {
+        /// Command name to benchmark
+        name: String,
+
+        /// Number of measured runs
+        #[arg(short, long, value_name = "N", default_value = "10", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        runs: usize,
+
+        /// Number of runs executed before measuring (discarded)
+        #[arg(short, long, value_name = "N", default_value = "0")]
+        warmup: usize,
+
+        /// Keep measuring when a run exits with a non-zero status
+        #[arg(long)]
+        ignore_failures: bool,
     },
 
     /// List available commands from configuration
@@ -109,12 +318,31 @@ pub enum Commands {
     /// Examples:
     ///   cmdrun list
     ///   cmdrun list --verbose
+    ///   cmdrun list --grouped
+    ///   cmdrun list --category build
     ///   cmdrun list --global
+    ///   cmdrun list --json-schema > cmdrun.schema.json
     #[command(visible_alias = "ls")]
     List {
         /// Show detailed information
         #[arg(short, long)]
         verbose: bool,
+
+        /// Group commands by category (uncategorized commands under "Other")
+        #[arg(long)]
+        grouped: bool,
+
+        /// Only list commands in this category ("Other" for uncategorized)
+        #[arg(long, value_name = "NAME")]
+        category: Option<String>,
+
+        /// Show full descriptions instead of truncating them to the terminal width
+        #[arg(long)]
+        no_truncate: bool,
+
+        /// Print the JSON Schema of commands.toml instead of listing commands
+        #[arg(long, conflicts_with_all = ["verbose", "grouped", "category", "no_truncate"])]
+        json_schema: bool,
     },
 
     /// Initialize a new commands.toml file in the current directory
@@ -127,6 +355,7 @@ pub enum Commands {
     ///   cmdrun init
     ///   cmdrun init --template rust-cli
     ///   cmdrun init --interactive
+    ///   cmdrun init --from package-json
     Init {
         /// Template to use (rust-cli, nodejs-web, python-data, react-app)
         #[arg(short, long)]
@@ -136,6 +365,10 @@ pub enum Commands {
         #[arg(short, long)]
         interactive: bool,
 
+        /// Import commands from package.json scripts or Makefile targets in the current directory
+        #[arg(long, value_name = "KIND", conflicts_with_all = ["template", "interactive"])]
+        from: Option<InitSource>,
+
         /// Output path (default: commands.toml)
         #[arg(short, long)]
         output: Option<PathBuf>,
@@ -148,11 +381,18 @@ pub enum Commands {
     /// - Invalid command definitions
     /// - Circular dependencies (with --check-cycles)
     /// - Missing dependencies
+    /// - Unknown keys (with --strict-schema)
+    /// - Commands defined in more than one merged config file
+    /// - Platform-specific commands missing a supported platform
     ///
     /// Examples:
     ///   cmdrun validate
     ///   cmdrun validate --verbose
     ///   cmdrun validate --check-cycles
+    ///   cmdrun validate --graph-only
+    ///   cmdrun validate --strict-schema
+    ///   cmdrun validate --strict
+    ///   cmdrun validate --fail-on-warning
     Validate {
         /// Path to configuration file
         #[arg(short, long)]
@@ -165,8 +405,53 @@ pub enum Commands {
         /// Check for circular dependencies
         #[arg(long)]
         check_cycles: bool,
+
+        /// Only check the dependency graph (missing dependencies and cycles)
+        #[arg(long)]
+        graph_only: bool,
+
+        /// Report unknown keys (e.g. misspelled fields) as errors
+        #[arg(long)]
+        strict_schema: bool,
+
+        /// Treat duplicate command definitions across config files as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Exit with a non-zero status if any warnings are reported
+        #[arg(long)]
+        fail_on_warning: bool,
+
+        /// List every validation rule with what it checks and whether it passed
+        #[arg(long)]
+        explain: bool,
     },
 
+    /// Diagnose common setup problems
+    ///
+    /// Checks the configuration file, shell detection, write access to the
+    /// history directory, enabled plugins, and optional external tools
+    /// (such as Graphviz `dot`). Exits with a non-zero status if any check
+    /// fails; warnings do not affect the exit status.
+    ///
+    /// Examples:
+    ///   cmdrun doctor
+    ///   cmdrun --config ./commands.toml doctor
+    Doctor,
+
+    /// Review every command for security issues without running anything
+    ///
+    /// Checks each command line (every platform variant and `when_shell`)
+    /// with strict security validation and reports what would be blocked
+    /// and why. Commands that set `allow_chaining`/`allow_subshells` or
+    /// dangerous environment variables such as `LD_PRELOAD` are flagged.
+    /// Exits with a non-zero status if any command would be blocked.
+    ///
+    /// Examples:
+    ///   cmdrun audit
+    ///   cmdrun --config ./cloned/commands.toml audit
+    Audit,
+
     /// Show dependency graph for commands
     ///
     /// Visualize command dependencies in various formats. The graph shows
@@ -204,10 +489,11 @@ pub enum Commands {
     ///   zsh:    cmdrun completion zsh > ~/.zsh/completions/_cmdrun
     ///   fish:   cmdrun completion fish > ~/.config/fish/completions/cmdrun.fish
     ///   pwsh:   cmdrun completion powershell > cmdrun.ps1
+    ///   nu:     cmdrun completion nushell | save -f ~/.config/nushell/cmdrun.nu
     Completion {
         /// Shell to generate completion for
         #[arg(value_enum)]
-        shell: clap_complete::Shell,
+        shell: CompletionShell,
     },
 
     /// Remove a command from the configuration
@@ -257,12 +543,18 @@ pub enum Commands {
 
     /// Open commands.toml in the default editor
     ///
-    /// Opens your configuration file in the editor specified by $EDITOR
-    /// environment variable. Falls back to sensible defaults if not set.
+    /// Opens your configuration file in the editor given by --editor, or by
+    /// the $VISUAL or $EDITOR environment variable (in that order). Falls
+    /// back to nano or vi (notepad on Windows) if none is set.
     ///
-    /// Example:
+    /// Examples:
     ///   cmdrun open
-    Open,
+    ///   cmdrun open --editor "code --wait"
+    Open {
+        /// Editor command to use (may include arguments)
+        #[arg(long, value_name = "CMD")]
+        editor: Option<String>,
+    },
 
     /// Edit an existing command interactively
     ///
@@ -285,28 +577,113 @@ pub enum Commands {
     /// Examples:
     ///   cmdrun info
     ///   cmdrun info build
+    ///   cmdrun info build --format toml
     Info {
         /// Command ID to show info for (optional - will prompt if not provided)
         id: Option<String>,
+
+        /// Print only the command's definition in this format (for pasting into another config)
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<DefinitionFormat>,
+    },
+
+    /// Show where a command is defined and what it runs
+    ///
+    /// Print the config file and line the effective definition comes from,
+    /// files it overrides, whether the name is an alias, and the command lines
+    /// for the current platform.
+    ///
+    /// Examples:
+    ///   cmdrun which build
+    ///   cmdrun which t1
+    Which {
+        /// Command or alias name
+        id: String,
     },
 
     /// Search commands by keyword
     ///
-    /// Search for commands matching a keyword in their ID, description,
-    /// tags, or category.
+    /// Search for commands matching keywords in their ID, description,
+    /// command text, tags, or category. Use --in to restrict the fields.
+    /// Multiple keywords must all match (use --any to match any of them);
+    /// a keyword prefixed with `-` excludes commands that match it. Options
+    /// go before the keywords.
     ///
     /// Examples:
     ///   cmdrun search test
     ///   cmdrun search docker
+    ///   cmdrun search --in cmd,tags docker
+    ///   cmdrun search docker -compose
+    ///   cmdrun search --any lint fmt
     Search {
-        /// Keyword to search for
-        keyword: String,
+        /// Keywords to search for (`-keyword` excludes matches)
+        #[arg(required = true, allow_hyphen_values = true)]
+        keywords: Vec<String>,
+
+        /// Match commands containing any keyword instead of all of them
+        #[arg(long)]
+        any: bool,
+
+        /// Fields to search (comma-separated; defaults to all fields)
+        #[arg(long = "in", value_name = "FIELDS", value_delimiter = ',')]
+        fields: Vec<SearchField>,
+    },
+
+    /// Generate a Markdown reference of all commands
+    ///
+    /// Produce a Markdown document with a table of contents and one section
+    /// per command (description, command, dependencies, tags, platforms).
+    ///
+    /// Examples:
+    ///   cmdrun docs
+    ///   cmdrun docs -o COMMANDS.md
+    Docs {
+        /// Output file path (prints to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Export commands as a Makefile or justfile
+    ///
+    /// Generate a build file so the commands can be run without cmdrun.
+    /// Dependencies become prerequisites; platform-specific commands use the
+    /// current platform's variant (Makefile) or platform attributes (justfile).
+    ///
+    /// Examples:
+    ///   cmdrun export --format makefile -o Makefile
+    ///   cmdrun export --format justfile
+    Export {
+        /// Output format
+        #[arg(long, value_enum)]
+        format: ExportFileFormat,
+
+        /// Output file path (prints to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 
     /// List command names for completion (internal use)
     #[command(hide = true)]
     CompletionList,
 
+    /// List `arg_completions` choices of a command's argument (internal use)
+    #[command(hide = true)]
+    CompleteArgs {
+        /// Command name
+        name: String,
+
+        /// Argument position (1 for the first argument)
+        position: usize,
+    },
+
+    /// List the tags used by commands for completion (internal use)
+    #[command(hide = true)]
+    CompletionTags,
+
+    /// List the categories used by commands for completion (internal use)
+    #[command(hide = true)]
+    CompletionCategories,
+
     /// Manage configuration settings (get/set/show configuration values)
     ///
     /// This subcommand allows you to view and modify settings within
@@ -339,7 +716,7 @@ pub enum Commands {
         #[arg(trailing_var_arg = true)]
         args: Vec<String>,
 
-        /// Paths to watch (default: current directory)
+        /// Paths to watch (default: the command's `watch.paths`, or the current directory)
         #[arg(short = 'p', long = "path", value_name = "PATH")]
         paths: Vec<PathBuf>,
 
@@ -351,9 +728,9 @@ pub enum Commands {
         #[arg(short = 'e', long = "exclude", value_name = "PATTERN")]
         exclude: Vec<String>,
 
-        /// Debounce delay in milliseconds (default: 500ms)
-        #[arg(short, long, value_name = "MS", default_value = "500")]
-        debounce: u64,
+        /// Debounce delay in milliseconds (default: the command's `watch.debounce_ms`, or 500ms)
+        #[arg(short, long, value_name = "MS")]
+        debounce: Option<u64>,
 
         /// Ignore .gitignore files
         #[arg(long)]
@@ -449,6 +826,11 @@ pub enum Commands {
         #[command(subcommand)]
         action: PluginAction,
     },
+
+    /// Custom subcommand provided by a plugin
+    #[cfg(feature = "plugin-system")]
+    #[command(external_subcommand)]
+    External(Vec<String>),
 }
 
 /// Configuration management actions
@@ -459,8 +841,9 @@ pub enum ConfigAction {
     /// Examples:
     ///   cmdrun config get language
     ///   cmdrun config get shell
+    ///   cmdrun config get commands.build.timeout
     Get {
-        /// Configuration key (e.g., language, shell, timeout)
+        /// Configuration key (e.g., language, config.env.FOO, commands.build.timeout)
         key: String,
     },
 
@@ -469,12 +852,19 @@ pub enum ConfigAction {
     /// Examples:
     ///   cmdrun config set language japanese
     ///   cmdrun config set shell zsh
+    ///   cmdrun config set commands.build.timeout 600
+    ///   cmdrun config set config.env.RUST_LOG debug
+    ///   cmdrun config set --append commands.build.tags ci
     Set {
-        /// Configuration key (e.g., language, shell, timeout)
+        /// Configuration key (e.g., language, config.env.FOO, commands.build.timeout)
         key: String,
 
         /// Value to set
         value: String,
+
+        /// Append the value to a list instead of replacing it
+        #[arg(long)]
+        append: bool,
     },
 
     /// Show all current configuration settings
@@ -541,6 +931,18 @@ pub enum EnvAction {
         /// Environment name (defaults to current)
         name: Option<String>,
     },
+
+    /// Compare the variables of two environments
+    ///
+    /// Examples:
+    ///   cmdrun env diff staging production
+    Diff {
+        /// Base environment
+        a: String,
+
+        /// Environment to compare against the base
+        b: String,
+    },
 }
 
 /// History management actions
@@ -552,6 +954,8 @@ pub enum HistoryAction {
     ///   cmdrun history list
     ///   cmdrun history list --limit 20
     ///   cmdrun history list --failed
+    ///   cmdrun history list --since 2h
+    ///   cmdrun history list --since 2024-01-01 --until 1w --failed
     List {
         /// Maximum number of entries to display
         #[arg(short, long, default_value = "50")]
@@ -565,9 +969,21 @@ pub enum HistoryAction {
         #[arg(short, long)]
         failed: bool,
 
+        /// Show only entries at or after this time (e.g. 2h, 3d, 1w, 2024-01-15)
+        #[arg(long, value_name = "DURATION_OR_DATE")]
+        since: Option<String>,
+
+        /// Show only entries at or before this time (e.g. 1d, 2024-01-15T12:00:00)
+        #[arg(long, value_name = "DURATION_OR_DATE")]
+        until: Option<String>,
+
         /// Show statistics summary
         #[arg(short, long)]
         stats: bool,
+
+        /// Also show the host and cmdrun version of each entry
+        #[arg(short, long)]
+        verbose: bool,
     },
 
     /// Search command history
@@ -595,11 +1011,33 @@ pub enum HistoryAction {
         force: bool,
     },
 
+    /// Delete old history entries
+    ///
+    /// Keeps only the newest entries and/or drops entries older than a given
+    /// age. Runs are also pruned automatically according to
+    /// `history_max_entries` and `history_max_age` in [config].
+    ///
+    /// Examples:
+    ///   cmdrun history prune --max-entries 500
+    ///   cmdrun history prune --older-than 30d
+    ///   cmdrun history prune --max-entries 100 --older-than 2024-01-01
+    #[command(group = clap::ArgGroup::new("limits").required(true).multiple(true))]
+    Prune {
+        /// Keep only this many of the newest entries
+        #[arg(long, value_name = "N", group = "limits")]
+        max_entries: Option<usize>,
+
+        /// Delete entries older than this (e.g. 12h, 30d, 4w, 2024-01-15)
+        #[arg(long, value_name = "AGE_OR_DATE", group = "limits")]
+        older_than: Option<String>,
+    },
+
     /// Export history to file
     ///
     /// Examples:
     ///   cmdrun history export --format json -o history.json
     ///   cmdrun history export --format csv --limit 100
+    ///   cmdrun history export --format yaml -o history.yaml
     Export {
         /// Export format
         #[arg(short, long, value_enum, default_value = "json")]
@@ -621,8 +1059,145 @@ pub enum HistoryAction {
     ///   - Success/failure rates
     ///   - Most frequently executed commands
     ///   - Average execution duration
+    ///   - Duration percentiles (p50/p90/p99)
+    ///   - Duration trend over recent runs
     ///   - Most frequent failure reasons
-    Stats,
+    ///
+    /// Examples:
+    ///   cmdrun history stats
+    ///   cmdrun history stats build --last 50
+    ///   cmdrun history stats --json
+    ///   cmdrun history stats --by-command --sort runs --top 10
+    Stats {
+        /// Only include runs of this command
+        command: Option<String>,
+
+        /// Number of most recent runs used for the trend
+        #[arg(short = 'n', long, default_value = "20")]
+        last: usize,
+
+        /// Output statistics as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Show a per-command table (runs, success rate, average and total duration)
+        #[arg(long, conflicts_with = "command")]
+        by_command: bool,
+
+        /// Order of the per-command table
+        #[arg(long, value_enum, default_value = "time", requires = "by_command")]
+        sort: StatsSort,
+
+        /// Only show the first N commands of the per-command table
+        #[arg(long, value_name = "N", requires = "by_command")]
+        top: Option<usize>,
+    },
+}
+
+/// Sort orders for `cmdrun history stats --by-command`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsSort {
+    /// Most runs first
+    Runs,
+    /// Most total time spent first
+    Time,
+    /// Most failures first
+    Failures,
+}
+
+/// Report formats for `cmdrun run --profile`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProfileFormat {
+    /// Human-readable table
+    Text,
+    /// JSON object
+    Json,
+}
+
+/// Build file formats generated by `cmdrun export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFileFormat {
+    /// GNU Make Makefile
+    Makefile,
+    /// justfile for the `just` command runner
+    Justfile,
+}
+
+/// Formats for `cmdrun info --format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DefinitionFormat {
+    /// `[commands.<id>]` table
+    Toml,
+    /// YAML mapping
+    #[value(alias = "yml")]
+    Yaml,
+    /// JSON object
+    Json,
+}
+
+/// Existing project files `cmdrun init --from` can import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InitSource {
+    /// npm `scripts` in package.json
+    PackageJson,
+    /// Targets in Makefile
+    Makefile,
+}
+
+/// Shells supported by `cmdrun completion`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    /// Bourne Again SHell
+    Bash,
+    /// Z SHell
+    Zsh,
+    /// Friendly Interactive SHell
+    Fish,
+    /// PowerShell
+    #[value(name = "powershell")]
+    PowerShell,
+    /// Elvish shell
+    Elvish,
+    /// Nushell
+    #[value(alias = "nu")]
+    Nushell,
+}
+
+/// Command fields searched by `cmdrun search`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SearchField {
+    /// Command ID
+    Name,
+    /// Description
+    Desc,
+    /// Command text (all steps and platform variants)
+    Cmd,
+    /// Tags
+    Tags,
+    /// Category
+    Category,
+}
+
+impl SearchField {
+    /// Every searchable field, in display order
+    pub const ALL: [SearchField; 5] = [
+        SearchField::Name,
+        SearchField::Desc,
+        SearchField::Cmd,
+        SearchField::Tags,
+        SearchField::Category,
+    ];
+
+    /// Label shown in the "Matched in" line (same as the `--in` value)
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchField::Name => "name",
+            SearchField::Desc => "desc",
+            SearchField::Cmd => "cmd",
+            SearchField::Tags => "tags",
+            SearchField::Category => "category",
+        }
+    }
 }
 
 /// Export format for history
@@ -632,6 +1207,11 @@ pub enum ExportFormat {
     Json,
     /// CSV format (spreadsheet-compatible, tabular data)
     Csv,
+    /// YAML format (human-readable, diff-friendly)
+    #[value(alias = "yml")]
+    Yaml,
+    /// TOML format (array of tables under `[[history]]`)
+    Toml,
 }
 
 /// Template management actions
@@ -660,6 +1240,25 @@ pub enum TemplateAction {
         /// Output path (default: commands.toml)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Set a template placeholder value (e.g. --set project_name=foo)
+        ///
+        /// Placeholders written as {{name}} in the template are replaced
+        /// with the given value. Missing values are prompted for interactively.
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+
+        /// Add the template's commands to an existing output file
+        ///
+        /// Commands that are already defined are kept and reported as
+        /// skipped; the rest of the file ([config], other commands,
+        /// comments) is left untouched.
+        #[arg(long)]
+        merge: bool,
+
+        /// With --merge, replace existing commands the template also defines
+        #[arg(long, requires = "merge")]
+        overwrite: bool,
     },
 
     /// List all available templates
@@ -696,11 +1295,48 @@ pub enum TemplateAction {
         output: PathBuf,
     },
 
-    /// Import a template from a file
+    /// Import a template from a file, URL, or git repository
     ///
     /// Import a template from a TOML file. The template will be validated
     /// and saved to ~/.cmdrun/templates/.
+    ///
+    /// Remote templates (--url, --git) are previewed and installed after
+    /// confirmation. They require the `template-download` feature.
+    ///
+    /// Examples:
+    ///   cmdrun template import ./my-template.toml
+    ///   cmdrun template import --url https://example.com/rust.toml
+    ///   cmdrun template import --git https://github.com/user/templates --path rust.toml
     Import {
+        /// Template file path
+        #[arg(required_unless_present_any = ["url", "git"], conflicts_with_all = ["url", "git"])]
+        file: Option<PathBuf>,
+
+        /// Download the template from an https URL
+        #[arg(long, value_name = "URL", conflicts_with = "git")]
+        url: Option<String>,
+
+        /// Clone a git repository and import a template file from it
+        #[arg(long, value_name = "REPO", requires = "path")]
+        git: Option<String>,
+
+        /// Path of the template file inside the git repository
+        #[arg(long, value_name = "FILE", requires = "git")]
+        path: Option<String>,
+
+        /// Import remote templates without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Validate a template file without importing it
+    ///
+    /// Run the same checks as import (metadata, command IDs and command
+    /// security) and report every problem found.
+    ///
+    /// Examples:
+    ///   cmdrun template validate shared-template.toml
+    Validate {
         /// Template file path
         file: PathBuf,
     },
@@ -730,6 +1366,7 @@ pub enum PluginAction {
     ///   cmdrun plugin list
     ///   cmdrun plugin list --enabled
     ///   cmdrun plugin list --verbose
+    ///   cmdrun plugin list --json
     List {
         /// Show only enabled plugins
         #[arg(short, long)]
@@ -738,6 +1375,10 @@ pub enum PluginAction {
         /// Show detailed information including hooks and metadata
         #[arg(short, long)]
         verbose: bool,
+
+        /// Output plugins as a JSON array (disabled plugins included)
+        #[arg(long, conflicts_with = "verbose")]
+        json: bool,
     },
 
     /// Show detailed plugin information
@@ -778,4 +1419,42 @@ pub enum PluginAction {
         /// Plugin name
         name: String,
     },
+
+    /// Install a plugin from a local file, directory, or URL
+    ///
+    /// Copy the plugin library into the managed plugins directory
+    /// (<config dir>/cmdrun/plugins), verify that it loads, and register
+    /// it in commands.toml. When given a directory, the library for the
+    /// current platform (.so, .dylib, or .dll) is picked automatically.
+    /// Downloading from http(s) URLs requires the `plugin-download` feature.
+    ///
+    /// Examples:
+    ///   cmdrun plugin install ./target/release/libmy_plugin.so
+    ///   cmdrun plugin install ./target/release
+    ///   cmdrun plugin install https://example.com/libmy_plugin.so
+    Install {
+        /// Plugin library path, directory, or http(s) URL
+        source: String,
+    },
+}
+
+/// Parse a `--env KEY=VALUE` argument
+fn parse_env_override(arg: &str) -> Result<(String, String), String> {
+    let (key, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", arg))?;
+
+    let valid_key = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_key {
+        return Err(format!(
+            "invalid variable name '{}' (use letters, digits and '_')",
+            key
+        ));
+    }
+
+    Ok((key.to_string(), value.to_string()))
 }
@@ -0,0 +1,188 @@
+//! 実行前確認
+//!
+//! `confirm = true` のコマンドに対する確認プロンプトを一元的に扱う
+
+use crate::config::schema::Command;
+use crate::config::Language;
+use crate::error::{ExecutionError, Result};
+use crate::i18n::{format_message, MessageKey};
+use colored::*;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::io::IsTerminal;
+
+/// 確認の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmOutcome {
+    /// 確認不要（`confirm = false`）
+    NotRequired,
+    /// ユーザーが確認した
+    Confirmed,
+    /// `--yes` により確認をスキップした
+    Skipped,
+}
+
+/// 確認方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmMode {
+    /// `--yes` 指定（自動確認）
+    AssumeYes,
+    /// 端末で対話的に確認
+    Interactive,
+    /// 非対話（標準入力が端末でない）
+    NonInteractive,
+}
+
+impl ConfirmMode {
+    /// `--yes` の指定と標準入力の状態から確認方法を決定
+    pub fn detect(assume_yes: bool) -> Self {
+        if assume_yes {
+            ConfirmMode::AssumeYes
+        } else if std::io::stdin().is_terminal() {
+            ConfirmMode::Interactive
+        } else {
+            ConfirmMode::NonInteractive
+        }
+    }
+}
+
+/// コマンド実行前の確認
+///
+/// 非対話環境では待機せずにエラーを返す。ユーザーが拒否した場合は
+/// `ExecutionError::Cancelled` を返す
+pub fn confirm_command(
+    name: &str,
+    command: &Command,
+    mode: ConfirmMode,
+    language: Language,
+) -> Result<ConfirmOutcome> {
+    if !command.confirm {
+        return Ok(ConfirmOutcome::NotRequired);
+    }
+
+    match mode {
+        ConfirmMode::AssumeYes => {
+            eprintln!(
+                "{} {}",
+                "⚠".yellow(),
+                format_message(MessageKey::WarningConfirmationSkipped, language, &[name])
+            );
+            Ok(ConfirmOutcome::Skipped)
+        }
+        ConfirmMode::NonInteractive => Err(ExecutionError::ConfirmationRequired(format_message(
+            MessageKey::ErrorConfirmationRequiresTerminal,
+            language,
+            &[name],
+        ))
+        .into()),
+        ConfirmMode::Interactive => {
+            let prompt = confirm_prompt(name, command, language);
+            let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(prompt)
+                .default(false)
+                .interact()
+                .map_err(|e| anyhow::anyhow!("Failed to read confirmation: {}", e))?;
+
+            if confirmed {
+                Ok(ConfirmOutcome::Confirmed)
+            } else {
+                Err(ExecutionError::Cancelled.into())
+            }
+        }
+    }
+}
+
+/// 確認プロンプトの文言（`confirm_message` が優先）
+fn confirm_prompt(name: &str, command: &Command, language: Language) -> String {
+    match &command.confirm_message {
+        Some(message) => message.clone(),
+        None => format_message(MessageKey::PromptConfirmRunCommand, language, &[name]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::CommandSpec;
+    use ahash::AHashMap;
+
+    fn test_command(confirm: bool, confirm_message: Option<&str>) -> Command {
+        Command {
+            description: "deploy".to_string(),
+            cmd: CommandSpec::Single("echo deploy".to_string()),
+            env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
+            working_dir: None,
+            deps: vec![],
+            platform: vec![],
+            tags: vec![],
+            timeout: None,
+            parallel: false,
+            confirm,
+            confirm_message: confirm_message.map(String::from),
+            allow_chaining: None,
+            allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_confirm_not_required() {
+        let command = test_command(false, None);
+        for mode in [
+            ConfirmMode::AssumeYes,
+            ConfirmMode::Interactive,
+            ConfirmMode::NonInteractive,
+        ] {
+            let outcome = confirm_command("deploy", &command, mode, Language::English).unwrap();
+            assert_eq!(outcome, ConfirmOutcome::NotRequired);
+        }
+    }
+
+    #[test]
+    fn test_confirm_assume_yes_skips() {
+        let command = test_command(true, None);
+        let outcome = confirm_command(
+            "deploy",
+            &command,
+            ConfirmMode::AssumeYes,
+            Language::English,
+        )
+        .unwrap();
+        assert_eq!(outcome, ConfirmOutcome::Skipped);
+    }
+
+    #[test]
+    fn test_confirm_non_interactive_refuses() {
+        let command = test_command(true, None);
+        let err = confirm_command(
+            "deploy",
+            &command,
+            ConfirmMode::NonInteractive,
+            Language::English,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--yes"));
+    }
+
+    #[test]
+    fn test_confirm_prompt_message() {
+        let command = test_command(true, None);
+        assert_eq!(
+            confirm_prompt("deploy", &command, Language::English),
+            "Run command 'deploy'?"
+        );
+
+        let command = test_command(true, Some("This wipes the production database!"));
+        assert_eq!(
+            confirm_prompt("deploy", &command, Language::English),
+            "This wipes the production database!"
+        );
+    }
+}
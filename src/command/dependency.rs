@@ -3,9 +3,11 @@
 //! コマンドの依存関係を解析し、並列実行可能なグループを特定
 
 use crate::config::schema::{Command, CommandsConfig};
-use crate::error::{ExecutionError, Result};
+use crate::error::{CmdrunError, ConfigError, ExecutionError, Result};
 use ahash::{AHashMap, AHashSet};
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
+use std::time::Duration;
 
 /// 依存関係グラフ
 #[derive(Debug)]
@@ -17,6 +19,13 @@ pub struct DependencyGraph<'a> {
     in_degree: AHashMap<&'a str, usize>,
     /// 全コマンド
     commands: &'a AHashMap<String, Command>,
+    /// ルートコマンド名ごとの解決結果キャッシュ
+    ///
+    /// グラフは設定を借用しているため、グラフの生存中に設定が変わることはない。
+    /// 設定を読み直した場合は新しいグラフを作成する（キャッシュも作り直される）
+    resolved: RefCell<AHashMap<&'a str, Vec<ExecutionGroup<'a>>>>,
+    /// 実際に解決処理を行った回数（キャッシュヒットは含まない）
+    resolutions: Cell<usize>,
 }
 
 /// 実行グループ（同じグループ内のコマンドは並列実行可能）
@@ -54,16 +63,47 @@ impl<'a> DependencyGraph<'a> {
             adjacency,
             in_degree,
             commands: &config.commands,
+            resolved: RefCell::new(AHashMap::new()),
+            resolutions: Cell::new(0),
         }
     }
 
     /// 特定のコマンドとその依存関係を解決し、実行順序を取得
-    pub fn resolve(&self, command_name: &'a str) -> Result<Vec<ExecutionGroup<'a>>> {
+    ///
+    /// 結果はルートコマンド名ごとにキャッシュされ、同じグラフで同じコマンドを
+    /// 再度解決した場合は計算を省略する。循環依存などで失敗した結果はキャッシュ
+    /// しないため、エラーは毎回検出される
+    pub fn resolve(&self, command_name: &str) -> Result<Vec<ExecutionGroup<'a>>> {
+        if let Some(groups) = self.resolved.borrow().get(command_name) {
+            return Ok(groups.clone());
+        }
+
+        // コマンドの存在確認（キャッシュのキーには設定側の名前を使う）
+        let Some((command_name, _)) = self.commands.get_key_value(command_name) else {
+            return Err(ExecutionError::CommandFailed {
+                command: command_name.to_string(),
+                code: 1,
+            }
+            .into());
+        };
+        let command_name = command_name.as_str();
+
         // 依存関係のサブグラフを抽出
         let subgraph = self.extract_subgraph(command_name)?;
 
-        // トポロジカルソート（Kahn's Algorithm）
-        self.topological_sort_groups(&subgraph)
+        // トポロジカルソート（Kahn's Algorithm）。循環があればここでエラーになる
+        let groups = self.topological_sort_groups(&subgraph)?;
+        self.resolutions.set(self.resolutions.get() + 1);
+
+        self.resolved
+            .borrow_mut()
+            .insert(command_name, groups.clone());
+        Ok(groups)
+    }
+
+    /// キャッシュされずに解決処理が行われた回数
+    pub fn resolution_count(&self) -> usize {
+        self.resolutions.get()
     }
 
     /// サブグラフ抽出（特定コマンドとその依存関係のみ）
@@ -167,61 +207,136 @@ impl<'a> DependencyGraph<'a> {
         // 循環依存チェック
         let total_processed: usize = groups.iter().map(|g| g.commands.len()).sum();
         if total_processed != subgraph.len() {
-            return Err(ExecutionError::CommandFailed {
-                command: "Circular dependency detected".to_string(),
-                code: 1,
-            }
-            .into());
+            let mut nodes: Vec<&'a str> = subgraph.iter().copied().collect();
+            nodes.sort_unstable();
+            let cycle = nodes
+                .into_iter()
+                .find_map(|node| self.find_cycle_from(node))
+                .unwrap_or_default();
+            return Err(Self::cycle_error(&cycle));
         }
 
         Ok(groups)
     }
 
     /// 依存関係の循環チェック
+    ///
+    /// 循環がある場合、循環を構成するコマンドを依存順に並べたパス
+    /// （例: `build → test → build`）をエラーに含める
     pub fn check_cycles(&self) -> Result<()> {
-        for name in self.commands.keys() {
-            let mut visited = AHashSet::new();
-            let mut rec_stack = AHashSet::new();
-
-            if self.has_cycle(name.as_str(), &mut visited, &mut rec_stack) {
-                return Err(ExecutionError::CommandFailed {
-                    command: format!("Circular dependency involving: {}", name),
-                    code: 1,
-                }
-                .into());
-            }
+        match self.find_cycle() {
+            Some(cycle) => Err(Self::cycle_error(&cycle)),
+            None => Ok(()),
         }
-        Ok(())
     }
 
-    /// DFS で循環を検出
-    fn has_cycle(
+    /// グラフ全体から循環を1つ探す（コマンド名順に探索するため結果は決定的）
+    pub fn find_cycle(&self) -> Option<Vec<&'a str>> {
+        let mut names: Vec<&'a str> = self.commands.keys().map(String::as_str).collect();
+        names.sort_unstable();
+
+        let mut visited = AHashSet::new();
+        names.into_iter().find_map(|name| {
+            let mut stack = Vec::new();
+            self.cycle_dfs(name, &mut visited, &mut stack)
+        })
+    }
+
+    /// 指定コマンドから到達できる循環を探す
+    ///
+    /// 戻り値は循環の開始コマンドで始まり、同じコマンドで終わる
+    pub fn find_cycle_from(&self, start: &str) -> Option<Vec<&'a str>> {
+        let (start, _) = self.commands.get_key_value(start)?;
+        let mut visited = AHashSet::new();
+        let mut stack = Vec::new();
+        self.cycle_dfs(start.as_str(), &mut visited, &mut stack)
+    }
+
+    /// DFS で循環を検出し、見つかった場合は循環部分のパスを返す
+    fn cycle_dfs(
         &self,
         node: &'a str,
         visited: &mut AHashSet<&'a str>,
-        rec_stack: &mut AHashSet<&'a str>,
-    ) -> bool {
-        if rec_stack.contains(node) {
-            return true;
+        stack: &mut Vec<&'a str>,
+    ) -> Option<Vec<&'a str>> {
+        // 探索中のパスに既に含まれている場合は循環
+        if let Some(pos) = stack.iter().position(|&n| n == node) {
+            let mut cycle = stack[pos..].to_vec();
+            cycle.push(node);
+            return Some(cycle);
+        }
+
+        if !visited.insert(node) {
+            return None;
+        }
+
+        stack.push(node);
+        if let Some(command) = self.commands.get(node) {
+            for dep in &command.deps {
+                if let Some(cycle) = self.cycle_dfs(dep.as_str(), visited, stack) {
+                    return Some(cycle);
+                }
+            }
         }
+        stack.pop();
+
+        None
+    }
 
-        if visited.contains(node) {
-            return false;
+    /// 実行時間が最長となる依存チェーン（クリティカルパス）を求める
+    ///
+    /// `durations` に含まれる（実際に実行された）コマンドのみを対象とする。
+    /// 戻り値は最初に実行されるコマンドから `root` までの順
+    pub fn critical_path(&self, root: &str, durations: &AHashMap<&str, Duration>) -> Vec<&'a str> {
+        let Some((root, _)) = self.commands.get_key_value(root) else {
+            return Vec::new();
+        };
+
+        let mut longest = AHashMap::new();
+        self.longest_chain(root.as_str(), durations, &mut longest);
+
+        let mut path = Vec::new();
+        let mut node = Some(root.as_str());
+        while let Some(current) = node {
+            path.push(current);
+            node = longest.get(current).and_then(|(_, next)| *next);
         }
+        path.reverse();
+        path
+    }
 
-        visited.insert(node);
-        rec_stack.insert(node);
+    /// `node` で終わる最長チェーンの所要時間（メモ化、直前のコマンドも記録）
+    fn longest_chain(
+        &self,
+        node: &'a str,
+        durations: &AHashMap<&str, Duration>,
+        longest: &mut AHashMap<&'a str, (Duration, Option<&'a str>)>,
+    ) -> Duration {
+        if let Some((total, _)) = longest.get(node) {
+            return *total;
+        }
 
+        let mut slowest_dep: Option<(&'a str, Duration)> = None;
         if let Some(command) = self.commands.get(node) {
             for dep in &command.deps {
-                if self.has_cycle(dep.as_str(), visited, rec_stack) {
-                    return true;
+                if !durations.contains_key(dep.as_str()) {
+                    continue;
+                }
+                let total = self.longest_chain(dep.as_str(), durations, longest);
+                if slowest_dep.map_or(true, |(_, slowest)| total > slowest) {
+                    slowest_dep = Some((dep.as_str(), total));
                 }
             }
         }
 
-        rec_stack.remove(node);
-        false
+        let total = durations.get(node).copied().unwrap_or_default()
+            + slowest_dep.map_or(Duration::ZERO, |(_, total)| total);
+        longest.insert(node, (total, slowest_dep.map(|(dep, _)| dep)));
+        total
+    }
+
+    fn cycle_error(cycle: &[&str]) -> CmdrunError {
+        ConfigError::CircularDependency(cycle.iter().map(|s| s.to_string()).collect()).into()
     }
 }
 
@@ -240,6 +355,8 @@ mod tests {
                 description: "Command A".to_string(),
                 cmd: CommandSpec::Single("echo a".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec!["b".to_string(), "c".to_string()],
                 platform: vec![],
@@ -247,8 +364,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -259,6 +384,8 @@ mod tests {
                 description: "Command B".to_string(),
                 cmd: CommandSpec::Single("echo b".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec!["d".to_string()],
                 platform: vec![],
@@ -266,8 +393,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -278,6 +413,8 @@ mod tests {
                 description: "Command C".to_string(),
                 cmd: CommandSpec::Single("echo c".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec!["d".to_string()],
                 platform: vec![],
@@ -285,8 +422,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -297,6 +442,8 @@ mod tests {
                 description: "Command D".to_string(),
                 cmd: CommandSpec::Single("echo d".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -304,8 +451,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -352,6 +507,80 @@ mod tests {
         assert!(graph.check_cycles().is_err());
     }
 
+    fn cycle_path(config: &CommandsConfig) -> Vec<String> {
+        match DependencyGraph::new(config).check_cycles() {
+            Err(crate::error::CmdrunError::Config(ConfigError::CircularDependency(path))) => path,
+            other => panic!("expected a circular dependency error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cycle_path_self_cycle() {
+        let mut config = create_test_config();
+        config
+            .commands
+            .get_mut("d")
+            .unwrap()
+            .deps
+            .push("d".to_string());
+
+        assert_eq!(cycle_path(&config), vec!["d", "d"]);
+    }
+
+    #[test]
+    fn test_cycle_path_two_nodes() {
+        let mut config = create_test_config();
+        // b -> d -> b
+        config
+            .commands
+            .get_mut("d")
+            .unwrap()
+            .deps
+            .push("b".to_string());
+
+        assert_eq!(cycle_path(&config), vec!["b", "d", "b"]);
+    }
+
+    #[test]
+    fn test_cycle_path_longer_cycle() {
+        let mut config = create_test_config();
+        // a -> b -> d -> a
+        config
+            .commands
+            .get_mut("d")
+            .unwrap()
+            .deps
+            .push("a".to_string());
+
+        let path = cycle_path(&config);
+        assert_eq!(path, vec!["a", "b", "d", "a"]);
+
+        let graph = DependencyGraph::new(&config);
+        let message = graph.resolve("a").unwrap_err().to_string();
+        assert!(message.contains("a → b → d → a"), "{}", message);
+    }
+
+    #[test]
+    fn test_critical_path() {
+        let config = create_test_config();
+        let graph = DependencyGraph::new(&config);
+        let ms = Duration::from_millis;
+
+        // a -> b, c; b -> d; c -> d  (c is slower than b)
+        let durations: AHashMap<&str, Duration> =
+            [("a", ms(10)), ("b", ms(20)), ("c", ms(50)), ("d", ms(5))]
+                .into_iter()
+                .collect();
+        assert_eq!(graph.critical_path("a", &durations), vec!["d", "c", "a"]);
+
+        // Commands that did not run are left out
+        let durations: AHashMap<&str, Duration> =
+            [("a", ms(10)), ("b", ms(20))].into_iter().collect();
+        assert_eq!(graph.critical_path("a", &durations), vec!["b", "a"]);
+
+        assert!(graph.critical_path("missing", &durations).is_empty());
+    }
+
     #[test]
     fn test_missing_dependency() {
         let mut config = create_test_config();
@@ -367,4 +596,41 @@ mod tests {
         let graph = DependencyGraph::new(&config);
         assert!(graph.resolve("a").is_err());
     }
+
+    #[test]
+    fn test_resolve_is_cached_per_root() {
+        let config = create_test_config();
+        let graph = DependencyGraph::new(&config);
+
+        let first = graph.resolve("a").unwrap();
+        let second = graph.resolve("a").unwrap();
+        assert_eq!(graph.resolution_count(), 1);
+        assert_eq!(
+            first.iter().map(|g| g.commands.len()).collect::<Vec<_>>(),
+            second.iter().map(|g| g.commands.len()).collect::<Vec<_>>()
+        );
+
+        // 依存関係を共有する別のルートはそれぞれ一度だけ計算される
+        for _ in 0..3 {
+            graph.resolve("b").unwrap();
+            graph.resolve("c").unwrap();
+        }
+        assert_eq!(graph.resolution_count(), 3);
+    }
+
+    #[test]
+    fn test_resolve_does_not_cache_cycles() {
+        let mut config = create_test_config();
+        config
+            .commands
+            .get_mut("d")
+            .unwrap()
+            .deps
+            .push("a".to_string());
+
+        let graph = DependencyGraph::new(&config);
+        assert!(graph.resolve("a").is_err());
+        assert!(graph.resolve("a").is_err());
+        assert_eq!(graph.resolution_count(), 0);
+    }
 }
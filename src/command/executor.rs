@@ -3,21 +3,32 @@
 //! 高性能・安全なコマンド実行機能を提供
 
 use crate::command::interpolation::InterpolationContext;
-use crate::config::schema::{Command, Platform};
-use crate::error::{ExecutionError, Result};
+use crate::config::schema::{Command, CommandSpec, Platform};
+use crate::error::{CmdrunError, ConfigError, ExecutionError, Result};
 use crate::i18n::{get_message, MessageKey};
-use crate::security::{CommandValidator, SensitiveEnv};
+use crate::output::capture::OutputLog;
+use crate::output::progress::{self, ParallelProgress, ProgressOutcome};
+use crate::output::status;
+use crate::plugin::PluginContext;
+use crate::security::validation::script_lines;
+use crate::security::{CommandValidator, EnvRedactor, SensitiveEnv};
 use ahash::AHashMap;
 use colored::*;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command as TokioCommand;
+use tokio::sync::{watch, Semaphore};
 use tokio::task::JoinSet;
 use tokio::time::timeout;
 use tracing::{debug, warn};
 
+/// タイムアウト時の SIGTERM から SIGKILL までのデフォルト猶予（ミリ秒）
+pub const DEFAULT_KILL_GRACE_MS: u64 = 2000;
+
 /// コマンド実行コンテキスト
 #[derive(Debug, Clone)]
 pub struct ExecutionContext {
@@ -27,8 +38,8 @@ pub struct ExecutionContext {
     pub env: AHashMap<String, String>,
     /// シェル
     pub shell: String,
-    /// タイムアウト（秒）
-    pub timeout: Option<u64>,
+    /// タイムアウト
+    pub timeout: Option<Duration>,
     /// 厳格モード
     pub strict: bool,
     /// コマンドエコー
@@ -41,6 +52,16 @@ pub struct ExecutionContext {
     pub allow_command_chaining: bool,
     /// サブシェルをグローバルで許可（デフォルト: false）
     pub allow_subshells: bool,
+    /// 実行を許可するベースコマンド（None の場合は制限なし）
+    pub command_allowlist: Option<Vec<String>>,
+    /// セキュリティ検証をスキップ（--allow-unsafe、デフォルト: false）
+    pub allow_unsafe: bool,
+    /// 標準エラー出力を標準出力に統合（2>&1 相当）
+    pub merge_stderr: bool,
+    /// タイムアウト時に SIGTERM を送ってから SIGKILL するまでの猶予（ミリ秒）
+    pub kill_grace_ms: u64,
+    /// 並列グループ内で同時に実行するコマンドの上限（None の場合は論理CPU数）
+    pub max_parallel: Option<usize>,
 }
 
 impl Default for ExecutionContext {
@@ -49,13 +70,177 @@ impl Default for ExecutionContext {
             working_dir: PathBuf::from("."),
             env: AHashMap::new(),
             shell: detect_shell(),
-            timeout: Some(300),
+            timeout: Some(Duration::from_secs(300)),
             strict: true,
             echo: true,
             color: true,
             language: crate::config::Language::default(),
             allow_command_chaining: false,
             allow_subshells: false,
+            command_allowlist: None,
+            allow_unsafe: false,
+            merge_stderr: false,
+            kill_grace_ms: DEFAULT_KILL_GRACE_MS,
+            max_parallel: None,
+        }
+    }
+}
+
+/// cmdrun が端末のフォアグラウンドで、標準入出力が端末に接続されているか
+///
+/// この場合は子プロセスのグループに端末を渡す。パイプ出力時（`| less` など）は
+/// 同じジョブの他のプロセスが端末を使うため渡さない
+#[cfg(unix)]
+fn in_terminal_foreground() -> bool {
+    // SAFETY: 状態を読み取るだけのシステムコール
+    unsafe {
+        libc::isatty(libc::STDIN_FILENO) == 1
+            && libc::isatty(libc::STDOUT_FILENO) == 1
+            && libc::tcgetpgrp(libc::STDIN_FILENO) == libc::getpgrp()
+    }
+}
+
+/// 端末のフォアグラウンドプロセスグループを `pgid` にする
+///
+/// バックグラウンドから呼んでも SIGTTOU で停止しないよう、呼び出し中はブロックする。
+/// async-signal-safe な関数のみ使うため `pre_exec` からも呼べる
+#[cfg(unix)]
+unsafe fn set_terminal_foreground(pgid: libc::pid_t) {
+    let mut block: libc::sigset_t = std::mem::zeroed();
+    let mut previous: libc::sigset_t = std::mem::zeroed();
+    libc::sigemptyset(&mut block);
+    libc::sigaddset(&mut block, libc::SIGTTOU);
+    libc::pthread_sigmask(libc::SIG_BLOCK, &block, &mut previous);
+    libc::tcsetpgrp(libc::STDIN_FILENO, pgid);
+    libc::pthread_sigmask(libc::SIG_SETMASK, &previous, std::ptr::null_mut());
+}
+
+/// 子プロセスに渡した端末を、破棄時に cmdrun のプロセスグループへ戻す
+#[cfg(unix)]
+struct TerminalHandoff;
+
+#[cfg(unix)]
+impl Drop for TerminalHandoff {
+    fn drop(&mut self) {
+        // SAFETY: 自身のプロセスグループを端末のフォアグラウンドに戻すだけ
+        unsafe { set_terminal_foreground(libc::getpgrp()) };
+    }
+}
+
+/// 出力の転送先（名前付きパイプ）
+///
+/// stdout/stderr の読み取りタスク間で共有する。書き込みに失敗した場合
+/// （読み手が切断した等）は以降の転送を停止する
+#[derive(Clone)]
+struct OutputSink {
+    path: Arc<str>,
+    file: Arc<Mutex<Option<std::fs::File>>>,
+}
+
+impl OutputSink {
+    /// パイプを書き込み用に開く
+    ///
+    /// FIFOは読み手がいないと開けないため、読み手が開くまで間隔を空けて再試行する。
+    /// `wait`（コマンドのタイムアウト）を過ぎても読み手がいなければエラー。中断要求でも打ち切る
+    async fn open(path: &str, wait: Option<Duration>, interrupt: &InterruptHandle) -> Result<Self> {
+        let mut interrupted = interrupt.0.subscribe();
+        let deadline = wait.map(|wait| Instant::now() + wait);
+        let mut delay = Duration::from_millis(10);
+
+        loop {
+            match Self::try_open(path) {
+                Ok(file) => {
+                    return Ok(Self {
+                        path: Arc::from(path),
+                        file: Arc::new(Mutex::new(Some(file))),
+                    })
+                }
+                Err(e) if Self::is_waiting_for_reader(&e) => {}
+                Err(e) => {
+                    return Err(ExecutionError::OutputPipeFailed {
+                        path: path.to_string(),
+                        source: e,
+                    }
+                    .into())
+                }
+            }
+
+            if *interrupted.borrow_and_update() {
+                return Err(ExecutionError::Interrupted.into());
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(ExecutionError::OutputPipeFailed {
+                    path: path.to_string(),
+                    source: std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        format!(
+                            "no reader on output_pipe within {:?}",
+                            wait.unwrap_or_default()
+                        ),
+                    ),
+                }
+                .into());
+            }
+
+            debug!("Waiting for a reader on output pipe '{}'", path);
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = interrupted.changed() => {}
+            }
+            delay = (delay * 2).min(Duration::from_millis(200));
+        }
+    }
+
+    /// 読み手を待たずに開く（Unix では O_NONBLOCK で開いた後、書き込みはブロッキングに戻す）
+    #[cfg(unix)]
+    fn try_open(path: &str) -> std::io::Result<std::fs::File> {
+        use std::os::unix::fs::OpenOptionsExt;
+        use std::os::unix::io::AsRawFd;
+
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(path)?;
+        // SAFETY: 開いたばかりの有効なファイルディスクリプタのフラグを変更するだけ
+        unsafe {
+            let fd = file.as_raw_fd();
+            let flags = libc::fcntl(fd, libc::F_GETFL);
+            if flags == -1 || libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        Ok(file)
+    }
+
+    #[cfg(not(unix))]
+    fn try_open(path: &str) -> std::io::Result<std::fs::File> {
+        std::fs::OpenOptions::new().write(true).open(path)
+    }
+
+    /// FIFOの読み手がまだいないことを示すエラーか
+    fn is_waiting_for_reader(error: &std::io::Error) -> bool {
+        #[cfg(unix)]
+        {
+            error.raw_os_error() == Some(libc::ENXIO)
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = error;
+            false
+        }
+    }
+
+    /// 1行を書き込む
+    fn write_line(&self, line: &str) {
+        let mut guard = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(file) = guard.as_mut() {
+            if let Err(e) = writeln!(file, "{}", line) {
+                warn!(
+                    "Failed to write to output pipe '{}', disabling it: {}",
+                    self.path, e
+                );
+                *guard = None;
+            }
         }
     }
 }
@@ -73,12 +258,54 @@ pub struct ExecutionResult {
     pub stderr: String,
     /// 成功したか
     pub success: bool,
+    /// 実行条件（`when` / `when_shell`）が偽のためスキップされたか
+    pub skipped: bool,
+}
+
+impl ExecutionResult {
+    /// 実行条件が偽でスキップされた結果（成功扱い）
+    pub fn skipped() -> Self {
+        Self {
+            exit_code: 0,
+            duration: Duration::ZERO,
+            stdout: String::new(),
+            stderr: String::new(),
+            success: true,
+            skipped: true,
+        }
+    }
+}
+
+/// 実行中のコマンドを中断するためのハンドル（Ctrl+C 用）
+///
+/// 中断すると、実行中の子プロセス（並列実行中のものを含む）はすべて終了させられ、
+/// 以降のコマンドは起動されずに [`ExecutionError::Interrupted`] で失敗する
+#[derive(Debug, Clone)]
+pub struct InterruptHandle(Arc<watch::Sender<bool>>);
+
+impl InterruptHandle {
+    /// 中断を要求
+    pub fn interrupt(&self) {
+        self.0.send_replace(true);
+    }
+
+    /// 中断が要求済みか
+    pub fn is_interrupted(&self) -> bool {
+        *self.0.borrow()
+    }
 }
 
 /// コマンドエグゼキューター
 pub struct CommandExecutor {
     context: ExecutionContext,
     sensitive_env: SensitiveEnv,
+    output_log: Option<OutputLog>,
+    print_redactor: Option<EnvRedactor>,
+    commands: Arc<AHashMap<String, Command>>,
+    args: Arc<[String]>,
+    interrupt: InterruptHandle,
+    /// 端末のフォアグラウンドを子プロセスに渡すか（並列タスクでは渡さない）
+    foreground: bool,
 }
 
 impl CommandExecutor {
@@ -87,11 +314,161 @@ impl CommandExecutor {
         Self {
             context,
             sensitive_env: SensitiveEnv::new(),
+            output_log: None,
+            print_redactor: None,
+            commands: Arc::new(AHashMap::new()),
+            args: Arc::from([]),
+            interrupt: InterruptHandle(Arc::new(watch::channel(false).0)),
+            foreground: true,
         }
     }
 
+    /// 実行中のコマンドを中断するためのハンドル
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interrupt.clone()
+    }
+
+    /// 出力キャプチャ先を設定（`run --log`）
+    ///
+    /// stdout/stderr の各行を端末表示に加えてログファイルにも書き込む
+    pub fn with_output_log(mut self, log: OutputLog) -> Self {
+        self.output_log = Some(log);
+        self
+    }
+
+    /// 展開後のコマンド文字列を実行前に標準出力へ表示（`run --print-command`）
+    ///
+    /// `redactor` の対象キーの値は `***REDACTED***` として表示する
+    pub fn with_print_commands(mut self, redactor: EnvRedactor) -> Self {
+        self.print_redactor = Some(redactor);
+        self
+    }
+
+    /// `when` で参照されるコマンド定義を設定
+    pub fn with_commands(mut self, commands: AHashMap<String, Command>) -> Self {
+        self.commands = Arc::new(commands);
+        self
+    }
+
+    /// 位置引数を設定（コマンド文字列の `{1}` / `{@}` / `{*}` を置換）
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        self.args = Arc::from(args);
+        self
+    }
+
+    /// シェルに渡すコマンド文字列を表示用に解決（実行はしない）
+    ///
+    /// 環境変数・位置引数を展開し、`redactor` の対象キーの値は伏せ字にする
+    pub fn resolve_printable_commands(
+        &self,
+        command: &Command,
+        redactor: &EnvRedactor,
+    ) -> Result<Vec<String>> {
+        let commands = self.resolve_commands(command)?;
+        self.interpolate_commands(&commands, command, Some(redactor))
+    }
+
+    /// プラグインフックに渡すコンテキストを構築（実行はしない）
+    ///
+    /// `command_spec` はシェルに渡す展開後のコマンド文字列（複数ステップは改行区切り）
+    pub fn plugin_context(
+        &self,
+        name: &str,
+        command: &Command,
+        args: &[String],
+    ) -> Result<PluginContext> {
+        let commands = self.resolve_commands(command)?;
+        let command_spec = self
+            .interpolate_commands(&commands, command, None)?
+            .join("\n");
+
+        let env = self.command_env(command)?;
+
+        Ok(PluginContext {
+            command_name: name.to_string(),
+            command: command.clone(),
+            command_spec,
+            args: args.to_vec(),
+            env,
+            working_dir: self.resolve_working_dir(command)?.display().to_string(),
+            data: AHashMap::new(),
+        })
+    }
+
     /// コマンド実行
+    ///
+    /// 実行条件（`when` / `when_shell`）が偽の場合は実行せず、スキップ結果を返す
     pub async fn execute(&self, command: &Command) -> Result<ExecutionResult> {
+        if !self.condition_holds(command).await? {
+            self.print_skipped(command);
+            return Ok(ExecutionResult::skipped());
+        }
+
+        self.execute_steps(command).await
+    }
+
+    /// 実行条件の評価（条件コマンドが終了コード0なら真）
+    ///
+    /// 条件コマンド自体の `when` は評価しない
+    async fn condition_holds(&self, command: &Command) -> Result<bool> {
+        if let Some(name) = &command.when {
+            let predicate = self
+                .commands
+                .get(name)
+                .ok_or_else(|| ConfigError::CommandNotFound(name.clone()))?;
+            debug!("Evaluating condition command '{}'", name);
+            match self.execute_steps(predicate).await {
+                Ok(_) => {}
+                Err(CmdrunError::Execution(ExecutionError::CommandFailed { .. })) => {
+                    return Ok(false)
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        if let Some(shell) = &command.when_shell {
+            debug!("Evaluating condition: {}", shell);
+            let condition =
+                self.interpolate_commands(std::slice::from_ref(shell), command, None)?;
+            let checked = self.validation_commands(std::slice::from_ref(shell), command)?;
+            let env = self.command_env(command)?;
+            let result = self
+                .execute_single_with_validator(
+                    &condition[0],
+                    &checked[0],
+                    &self.context.shell,
+                    &env,
+                    &self.resolve_working_dir(command)?,
+                    &self.build_validator_for_command(command),
+                    None,
+                )
+                .await?;
+            if !result.success {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// スキップ表示
+    fn print_skipped(&self, command: &Command) {
+        if self.context.color {
+            status::eprint(&format!(
+                "{} {}: skipped (condition false)",
+                "⏭".yellow().bold(),
+                command.description.bright_white()
+            ));
+        } else {
+            status::eprint(&format!(
+                "{}: skipped (condition false)",
+                command.description
+            ));
+        }
+    }
+
+    /// 条件を評価せずにコマンドの各ステップを実行
+    async fn execute_steps(&self, command: &Command) -> Result<ExecutionResult> {
         let start = Instant::now();
 
         // プラットフォームチェック
@@ -101,8 +478,7 @@ impl CommandExecutor {
         let commands = self.resolve_commands(command)?;
 
         // 環境変数マージ（コマンド固有の環境変数を追加）
-        let mut merged_env = self.context.env.clone();
-        merged_env.extend(command.env.clone());
+        let merged_env = self.command_env(command)?;
 
         // 危険な環境変数チェック
         use crate::security::validation::check_dangerous_env_vars;
@@ -116,17 +492,64 @@ impl CommandExecutor {
         }
 
         // 変数展開
-        let interpolated_commands = self.interpolate_commands(&commands, command)?;
+        let interpolated_commands = self.interpolate_commands(&commands, command, None)?;
+        let validation_commands = self.validation_commands(&commands, command)?;
+
+        // --print-command: シェルに渡す文字列を表示（機密値は伏せ字）
+        if let Some(redactor) = &self.print_redactor {
+            for line in self.interpolate_commands(&commands, command, Some(redactor))? {
+                println!("{}", line);
+            }
+        }
 
         // コマンド固有のvalidatorを構築（階層的制御）
         let command_validator = self.build_validator_for_command(command);
 
+        // --allow-unsafe 指定時は検証をスキップ（明示的な指定のみ）
+        if self.context.allow_unsafe {
+            self.warn_unsafe_execution(command);
+        }
+
+        // 作業ディレクトリ解決（コマンド固有の指定を優先）
+        let working_dir = self.resolve_working_dir(command)?;
+
+        // シェル解決（コマンド固有の指定を優先）
+        let shell = self.resolve_shell(command)?;
+
+        // 出力パイプを開く（全サブコマンドで共有）
+        let output_sink = match &command.output_pipe {
+            Some(path) => {
+                Some(OutputSink::open(path, self.context.timeout, &self.interrupt).await?)
+            }
+            None => None,
+        };
+
         // 実行
         let mut last_result = None;
-        for cmd in interpolated_commands {
-            let result = self
-                .execute_single_with_validator(&cmd, &merged_env, &command_validator)
-                .await?;
+        for (cmd, checked) in interpolated_commands.into_iter().zip(validation_commands) {
+            let result = if matches!(command.cmd, CommandSpec::Script { .. }) {
+                self.execute_script(
+                    &cmd,
+                    command,
+                    &shell,
+                    &merged_env,
+                    &working_dir,
+                    &command_validator,
+                    output_sink.as_ref(),
+                )
+                .await?
+            } else {
+                self.execute_single_with_validator(
+                    &cmd,
+                    &checked,
+                    &shell,
+                    &merged_env,
+                    &working_dir,
+                    &command_validator,
+                    output_sink.as_ref(),
+                )
+                .await?
+            };
             if !result.success {
                 return Err(ExecutionError::CommandFailed {
                     command: cmd,
@@ -144,6 +567,7 @@ impl CommandExecutor {
             stdout: String::new(),
             stderr: String::new(),
             success: true,
+            skipped: false,
         }))
     }
 
@@ -165,6 +589,55 @@ impl CommandExecutor {
         Ok(())
     }
 
+    /// 作業ディレクトリ解決
+    ///
+    /// `command.working_dir` が指定されていればそれを使い、相対パスは
+    /// コンテキストの作業ディレクトリ基準で解決する。存在しない場合はエラー
+    fn resolve_working_dir(&self, command: &Command) -> Result<PathBuf> {
+        let working_dir = match &command.working_dir {
+            Some(dir) if dir.is_absolute() => dir.clone(),
+            Some(dir) => self.context.working_dir.join(dir),
+            None => return Ok(self.context.working_dir.clone()),
+        };
+
+        if !working_dir.is_dir() {
+            return Err(ExecutionError::WorkingDirNotFound {
+                command: command.description.clone(),
+                path: working_dir.display().to_string(),
+            }
+            .into());
+        }
+
+        Ok(working_dir)
+    }
+
+    /// コマンドの実行時環境変数
+    ///
+    /// 優先度（低い順）: コンテキストの環境変数 < コマンドの `env_file` < コマンドの `env`。
+    /// `env_file` は実行のたびにここで読み込む
+    pub fn command_env(&self, command: &Command) -> Result<AHashMap<String, String>> {
+        let mut env = self.context.env.clone();
+        env.extend(
+            command
+                .env_file_vars()
+                .map_err(|e| anyhow::anyhow!("{} (command '{}')", e, command.description))?,
+        );
+        env.extend(command.env.clone());
+        Ok(env)
+    }
+
+    /// シェル解決
+    ///
+    /// `command.shell` が指定されていれば、PATH 上（またはパス指定）に存在することを確認して使う。
+    /// 未指定時はコンテキストのシェル
+    pub fn resolve_shell(&self, command: &Command) -> Result<String> {
+        match &command.shell {
+            Some(shell) if which::which(shell).is_ok() => Ok(shell.clone()),
+            Some(shell) => Err(ExecutionError::ShellNotFound(shell.clone()).into()),
+            None => Ok(self.context.shell.clone()),
+        }
+    }
+
     /// コマンド文字列解決
     fn resolve_commands(&self, command: &Command) -> Result<Vec<String>> {
         let platform = Platform::current();
@@ -177,16 +650,43 @@ impl CommandExecutor {
         })
     }
 
-    /// 変数展開
-    fn interpolate_commands(&self, commands: &[String], command: &Command) -> Result<Vec<String>> {
+    /// 変数展開（`redactor` 指定時は対象キーの値を伏せ字にして展開）
+    fn interpolate_commands(
+        &self,
+        commands: &[String],
+        command: &Command,
+        redactor: Option<&EnvRedactor>,
+    ) -> Result<Vec<String>> {
+        self.interpolate_with_args(commands, command, redactor, self.args.to_vec())
+    }
+
+    /// セキュリティ検証用の変数展開
+    ///
+    /// `{N}`・`{@}`・`{*}` の値はクォートして渡すため、引数の内容ではなくテンプレートを検証する。
+    /// 引数は無害な語に置き換える（`${1}` はクォートされないので実際の値のまま）
+    fn validation_commands(&self, commands: &[String], command: &Command) -> Result<Vec<String>> {
+        let stand_ins = vec!["arg".to_string(); self.args.len()];
+        self.interpolate_with_args(commands, command, None, stand_ins)
+    }
+
+    fn interpolate_with_args(
+        &self,
+        commands: &[String],
+        command: &Command,
+        redactor: Option<&EnvRedactor>,
+        args: Vec<String>,
+    ) -> Result<Vec<String>> {
         // 環境変数マージ
-        let mut env = self.context.env.clone();
-        env.extend(command.env.clone());
+        let env = self.command_env(command)?;
 
         // 展開コンテキスト作成
-        let ctx = InterpolationContext::new(self.context.strict)
+        let mut ctx = InterpolationContext::new(self.context.strict)
             .with_env_map(env)
+            .with_args(args)
             .merge_system_env();
+        if let Some(redactor) = redactor {
+            ctx = ctx.redact_values(redactor);
+        }
 
         // 各コマンドを展開
         commands
@@ -196,7 +696,7 @@ impl CommandExecutor {
     }
 
     /// コマンド固有のvalidatorを構築（階層的制御）
-    fn build_validator_for_command(&self, command: &Command) -> CommandValidator {
+    pub(crate) fn build_validator_for_command(&self, command: &Command) -> CommandValidator {
         // 1. コマンド個別設定（最優先）
         let allow_chaining = if let Some(allow) = command.allow_chaining {
             allow
@@ -242,70 +742,82 @@ impl CommandExecutor {
             validator = validator.allow_subshells();
         }
 
+        // 許可リストモード（連結されたセグメントごとに適用）
+        if let Some(allowlist) = &self.context.command_allowlist {
+            validator = validator.with_allowlist(allowlist.clone());
+        }
+
         validator
     }
 
     /// 単一コマンド実行（validator指定版）
+    ///
+    /// `checked` は検証対象（引数プレースホルダーを置き換えた形、[`Self::validation_commands`]）
+    #[allow(clippy::too_many_arguments)]
     async fn execute_single_with_validator(
         &self,
         command: &str,
+        checked: &str,
+        shell: &str,
         env: &AHashMap<String, String>,
+        working_dir: &Path,
         validator: &CommandValidator,
+        output_sink: Option<&OutputSink>,
     ) -> Result<ExecutionResult> {
-        let start = Instant::now();
-
-        // セキュリティ検証
-        let validation_result = validator.validate(command);
+        // セキュリティ検証（--allow-unsafe 指定時も許可リストは適用）
+        let validation_result = if self.context.allow_unsafe {
+            validator.validate_allowlist(checked)
+        } else {
+            validator.validate(checked)
+        };
         if !validation_result.is_safe() {
-            if let Some(err) = validation_result.error() {
-                warn!("Command validation failed: {}", err);
-
-                // コマンド連結が拒否された場合、代替方法を提示
-                use crate::security::validation::ValidationError;
-                if let ValidationError::DangerousMetacharacters(chars) = err {
-                    if chars.contains("'&'") || chars.contains("'|'") || chars.contains("';'") {
-                        use crate::i18n::{get_message, MessageKey};
-                        eprintln!();
-                        eprintln!(
-                            "{}",
-                            get_message(
-                                MessageKey::HintCommandChainingAlternatives,
-                                self.context.language
-                            )
-                        );
-                        eprintln!(
-                            "{}",
-                            get_message(
-                                MessageKey::HintCommandArrayRecommended,
-                                self.context.language
-                            )
-                        );
-                        eprintln!();
-                        eprintln!(
-                            "{}",
-                            get_message(
-                                MessageKey::HintEnableChainingForCommand,
-                                self.context.language
-                            )
-                        );
-                        eprintln!();
-                        eprintln!(
-                            "{}",
-                            get_message(
-                                MessageKey::HintEnableChainingGlobally,
-                                self.context.language
-                            )
-                        );
-                        eprintln!();
-                    }
-                }
+            warn!("Command validation failed: {}", validation_result.reasons());
+
+            // コマンド連結が拒否された場合、代替方法を提示
+            use crate::security::validation::ViolationKind;
+            if validation_result.has(ViolationKind::CommandChaining)
+                || validation_result.has(ViolationKind::Pipe)
+            {
+                use crate::i18n::{get_message, MessageKey};
+                eprintln!();
+                eprintln!(
+                    "{}",
+                    get_message(
+                        MessageKey::HintCommandChainingAlternatives,
+                        self.context.language
+                    )
+                );
+                eprintln!(
+                    "{}",
+                    get_message(
+                        MessageKey::HintCommandArrayRecommended,
+                        self.context.language
+                    )
+                );
+                eprintln!();
+                eprintln!(
+                    "{}",
+                    get_message(
+                        MessageKey::HintEnableChainingForCommand,
+                        self.context.language
+                    )
+                );
+                eprintln!();
+                eprintln!(
+                    "{}",
+                    get_message(
+                        MessageKey::HintEnableChainingGlobally,
+                        self.context.language
+                    )
+                );
+                eprintln!();
+            }
 
-                return Err(ExecutionError::CommandFailed {
-                    command: command.to_string(),
-                    code: 1,
-                }
-                .into());
+            return Err(ExecutionError::ValidationFailed {
+                command: command.to_string(),
+                reason: validation_result.reasons(),
             }
+            .into());
         }
 
         // コマンドエコー
@@ -317,7 +829,87 @@ impl CommandExecutor {
         self.warn_shell_builtin(command, self.context.language);
 
         // シェルコマンド構築
-        let (shell, args) = self.build_shell_command(command);
+        let (shell, args) = Self::build_shell_command(shell, command);
+
+        self.spawn_and_wait(command, &shell, &args, env, working_dir, output_sink)
+            .await
+    }
+
+    /// スクリプトファイル実行
+    ///
+    /// ファイルは実行のたびに読み込み、空行・コメント行以外の各行をコマンドとして検証してから
+    /// 検証した内容をシェルに渡す（`bash -c <content> <path> <args>...`）。スクリプトは任意の処理を含められ、
+    /// 行単位の検証では制御構文等を完全には判定できないため、インラインのコマンドより危険度が高い
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_script(
+        &self,
+        path: &str,
+        command: &Command,
+        shell: &str,
+        env: &AHashMap<String, String>,
+        working_dir: &Path,
+        validator: &CommandValidator,
+        output_sink: Option<&OutputSink>,
+    ) -> Result<ExecutionResult> {
+        // 相対パスは定義元の設定ファイルのディレクトリ基準（設定には書き戻さない）
+        let script_path = command
+            .script_path(path, self.context.strict)
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid script file (command '{}'): {}",
+                    command.description,
+                    e
+                )
+            })?;
+        let script = tokio::fs::read_to_string(&script_path).await.map_err(|e| {
+            ExecutionError::ScriptReadFailed {
+                command: command.description.clone(),
+                path: script_path.display().to_string(),
+                source: e,
+            }
+        })?;
+
+        // セキュリティ検証（--allow-unsafe 指定時も許可リストは適用）
+        for (line_no, line) in script_lines(&script) {
+            let result = if self.context.allow_unsafe {
+                validator.validate_allowlist(line)
+            } else {
+                validator.validate(line)
+            };
+            if !result.is_safe() {
+                warn!("Script validation failed: {}", result.reasons());
+                return Err(ExecutionError::ValidationFailed {
+                    command: format!("{}:{}: {}", path, line_no, line),
+                    reason: result.reasons(),
+                }
+                .into());
+            }
+        }
+
+        // コマンドエコー
+        if self.context.echo {
+            self.print_command(path);
+        }
+
+        let script_path = script_path.display().to_string();
+        let (shell, args) = Self::build_script_command(shell, &script_path, &script, &self.args);
+        self.spawn_and_wait(path, &shell, &args, env, working_dir, output_sink)
+            .await
+    }
+
+    /// プロセスを起動して終了を待つ
+    ///
+    /// `command` はログ・エラー表示用
+    async fn spawn_and_wait(
+        &self,
+        command: &str,
+        shell: &str,
+        args: &[String],
+        env: &AHashMap<String, String>,
+        working_dir: &Path,
+        output_sink: Option<&OutputSink>,
+    ) -> Result<ExecutionResult> {
+        let start = Instant::now();
 
         // 環境変数のログ出力（機密情報マスキング）
         if self.context.echo && !env.is_empty() {
@@ -325,95 +917,249 @@ impl CommandExecutor {
             debug!("Environment variables: {:?}", masked_env);
         }
 
-        // プロセス起動
-        let mut child = TokioCommand::new(&shell)
-            .args(&args)
-            .current_dir(&self.context.working_dir)
+        // 中断要求後は新しいプロセスを起動しない
+        let mut interrupted = self.interrupt.0.subscribe();
+        if *interrupted.borrow_and_update() {
+            return Err(ExecutionError::Interrupted.into());
+        }
+
+        // プロセス起動（待機中のタスクが破棄された場合も子プロセスを残さない）
+        let mut process = TokioCommand::new(shell);
+        process
+            .args(args)
+            .current_dir(working_dir)
             .envs(env)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| ExecutionError::SpawnFailed {
+            .kill_on_drop(true);
+
+        // Unix では新しいプロセスグループで起動し、タイムアウト・中断時はグループごと終了させる
+        // （`sleep 10; echo done` のような複合コマンドの孫プロセスを残さない）
+        #[cfg(unix)]
+        let foreground = self.foreground && in_terminal_foreground();
+        #[cfg(unix)]
+        {
+            process.process_group(0);
+            if foreground {
+                // exec 前に端末を受け取るので、入力を読んでも SIGTTIN で停止しない
+                // SAFETY: fork 後の子プロセスでは async-signal-safe な関数のみ呼ぶ
+                unsafe {
+                    process.pre_exec(|| {
+                        libc::setpgid(0, 0);
+                        set_terminal_foreground(libc::getpid());
+                        Ok(())
+                    });
+                }
+            }
+        }
+
+        // stderr統合時は同一パイプを stdout/stderr に割り当てる（2>&1 相当）
+        let merged_reader = if self.context.merge_stderr {
+            let spawn_err = |e| ExecutionError::SpawnFailed {
                 command: command.to_string(),
                 source: e,
-            })?;
+            };
+            let (reader, writer) = os_pipe::pipe().map_err(spawn_err)?;
+            let writer_err = writer.try_clone().map_err(spawn_err)?;
+            process.stdout(writer).stderr(writer_err);
+            Some(reader)
+        } else {
+            process.stdout(Stdio::piped()).stderr(Stdio::piped());
+            None
+        };
 
-        // 標準出力・エラー出力をキャプチャ
-        let stdout = child.stdout.take().unwrap();
-        let stderr = child.stderr.take().unwrap();
+        let mut child = process.spawn().map_err(|e| ExecutionError::SpawnFailed {
+            command: command.to_string(),
+            source: e,
+        })?;
 
-        let stdout_reader = BufReader::new(stdout);
-        let stderr_reader = BufReader::new(stderr);
+        // 親プロセス側の書き込み端を閉じる（EOF検出のため）
+        drop(process);
 
-        // 非同期出力読み取り
-        let stdout_handle = tokio::spawn(Self::read_output(stdout_reader, self.context.color));
-        let stderr_handle = tokio::spawn(Self::read_output(stderr_reader, self.context.color));
+        // 終了後（タイムアウト・中断を含む）に端末を cmdrun に戻す
+        #[cfg(unix)]
+        let _terminal = foreground.then_some(TerminalHandoff);
 
-        // タイムアウト付きプロセス待機
-        let status = if let Some(timeout_secs) = self.context.timeout {
-            match timeout(Duration::from_secs(timeout_secs), child.wait()).await {
-                Ok(result) => result.map_err(|e| ExecutionError::SpawnFailed {
+        debug!("Process started (pid {:?}): {}", child.id(), command);
+
+        // 非同期出力読み取り
+        let stdout_handle = match merged_reader {
+            Some(reader) => {
+                let sink = output_sink.cloned();
+                let log = self.output_log.clone();
+                tokio::task::spawn_blocking(move || Self::read_merged_output(reader, sink, log))
+            }
+            None => {
+                let stdout = child.stdout.take().unwrap();
+                tokio::spawn(Self::read_output(
+                    BufReader::new(stdout),
+                    self.context.color,
+                    output_sink.cloned(),
+                    self.output_log.clone(),
+                ))
+            }
+        };
+        let stderr_handle = child.stderr.take().map(|stderr| {
+            tokio::spawn(Self::read_output(
+                BufReader::new(stderr),
+                self.context.color,
+                output_sink.cloned(),
+                self.output_log.clone(),
+            ))
+        });
+
+        // タイムアウト・中断付きプロセス待機
+        let wait = async {
+            match self.context.timeout {
+                Some(limit) => timeout(limit, child.wait()).await.map_err(|_| limit),
+                None => Ok(child.wait().await),
+            }
+        };
+        let waited = tokio::select! {
+            waited = wait => Some(waited),
+            _ = interrupted.wait_for(|interrupted| *interrupted) => None,
+        };
+        let status = match waited {
+            Some(Ok(result)) => result.map_err(|e| ExecutionError::SpawnFailed {
+                command: command.to_string(),
+                source: e,
+            })?,
+            Some(Err(limit)) => {
+                // タイムアウト時は終了を要求し、猶予期間後も残っていればキル
+                self.terminate(&mut child).await;
+                return Err(ExecutionError::Timeout {
                     command: command.to_string(),
-                    source: e,
-                })?,
-                Err(_) => {
-                    // タイムアウト時はプロセスをキル
-                    let _ = child.kill().await;
-                    return Err(ExecutionError::Timeout {
-                        command: command.to_string(),
-                        timeout: timeout_secs,
-                    }
-                    .into());
+                    timeout: limit,
                 }
+                .into());
+            }
+            None => {
+                debug!("Interrupted, terminating process {:?}", child.id());
+                self.terminate(&mut child).await;
+                return Err(ExecutionError::Interrupted.into());
             }
-        } else {
-            child
-                .wait()
-                .await
-                .map_err(|e| ExecutionError::SpawnFailed {
-                    command: command.to_string(),
-                    source: e,
-                })?
         };
 
+        // 端末の Ctrl+C は子のプロセスグループにだけ届くため、SIGINT による終了は中断として扱う
+        #[cfg(unix)]
+        if foreground {
+            use std::os::unix::process::ExitStatusExt;
+            if status.signal() == Some(libc::SIGINT) {
+                self.interrupt.interrupt();
+            }
+        }
+
         // 出力取得
         let stdout_output = stdout_handle.await.unwrap();
-        let stderr_output = stderr_handle.await.unwrap();
+        let stderr_output = match stderr_handle {
+            Some(handle) => handle.await.unwrap(),
+            None => String::new(),
+        };
 
         let duration = start.elapsed();
         let exit_code = status.code().unwrap_or(-1);
 
+        debug!(
+            "Process exited with code {} after {:?}: {}",
+            exit_code, duration, command
+        );
+
         Ok(ExecutionResult {
             exit_code,
             duration,
             stdout: stdout_output,
             stderr: stderr_output,
             success: status.success(),
+            skipped: false,
         })
     }
 
-    /// シェルコマンド構築
-    fn build_shell_command(&self, command: &str) -> (String, Vec<String>) {
-        let shell = &self.context.shell;
+    /// 子プロセスを終了させる
+    ///
+    /// Unix では子のプロセスグループ全体（孫プロセスを含む）に SIGTERM を送り、
+    /// `kill_grace_ms` 以内にグループが空にならなければグループごと SIGKILL。
+    /// リーダーが先に終了しても孫プロセスが残ることがあるため、猶予後は必ず SIGKILL を送る。
+    /// Windows では従来どおり即座に強制終了する
+    async fn terminate(&self, child: &mut tokio::process::Child) {
+        #[cfg(unix)]
+        if let Some(pid) = child.id() {
+            let pgid = pid as libc::pid_t;
+            debug!("Sending SIGTERM to process group {}", pgid);
+            // SAFETY: 子はまだ回収していないプロセスグループのリーダーのため、
+            // グループ ID が別のグループに再利用されていない
+            let sent = unsafe { libc::killpg(pgid, libc::SIGTERM) } == 0;
+            if sent {
+                let grace = Duration::from_millis(self.context.kill_grace_ms);
+                let deadline = Instant::now() + grace;
+                let _ = timeout(grace, child.wait()).await;
+                // リーダーの終了後もグループに残ったプロセスを猶予期間まで待つ
+                // SAFETY: シグナル 0 は存在確認のみ。メンバーが残っている間はグループ ID は再利用されない
+                while Instant::now() < deadline && unsafe { libc::killpg(pgid, 0) } == 0 {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                debug!("Killing process group {} after {:?}", pgid, grace);
+                // SAFETY: 同上。グループが空の場合は ESRCH になるだけなので結果は無視する
+                unsafe { libc::killpg(pgid, libc::SIGKILL) };
+            }
+        }
 
-        if cfg!(windows) {
+        let _ = child.kill().await;
+    }
+
+    /// シェルコマンド構築
+    fn build_shell_command(shell: &str, command: &str) -> (String, Vec<String>) {
+        if cfg!(windows) && !crate::platform::shell::uses_dash_c(shell) {
             if shell.contains("pwsh") || shell.contains("powershell") {
                 (
-                    shell.clone(),
+                    shell.to_string(),
                     vec!["-Command".to_string(), command.to_string()],
                 )
             } else {
                 // cmd.exe
-                (shell.clone(), vec!["/C".to_string(), command.to_string()])
+                (
+                    shell.to_string(),
+                    vec!["/C".to_string(), command.to_string()],
+                )
             }
         } else {
-            // Unix系
-            (shell.clone(), vec!["-c".to_string(), command.to_string()])
+            // Unix系（および Windows 上の elvish / xonsh）
+            (
+                shell.to_string(),
+                vec!["-c".to_string(), command.to_string()],
+            )
         }
     }
 
+    /// スクリプトファイル実行用のシェルコマンド構築
+    ///
+    /// 検証済みの内容を `-c` で渡し、検証後にファイルが書き換えられても検証した内容を実行する。
+    /// `$0` はスクリプトのパス、`$1` 以降は引数になる（標準入力はスクリプトが使える）。
+    /// Windows の cmd.exe / PowerShell は内容を渡せないためファイルを指定する
+    fn build_script_command(
+        shell: &str,
+        path: &str,
+        script: &str,
+        args: &[String],
+    ) -> (String, Vec<String>) {
+        let mut shell_args = if cfg!(windows) && !crate::platform::shell::uses_dash_c(shell) {
+            if shell.contains("pwsh") || shell.contains("powershell") {
+                vec!["-File".to_string(), path.to_string()]
+            } else {
+                // cmd.exe
+                vec!["/C".to_string(), path.to_string()]
+            }
+        } else {
+            vec!["-c".to_string(), script.to_string(), path.to_string()]
+        };
+        shell_args.extend(args.iter().cloned());
+        (shell.to_string(), shell_args)
+    }
+
     /// 出力読み取り（リアルタイム表示）
-    async fn read_output<R>(reader: BufReader<R>, _color: bool) -> String
+    async fn read_output<R>(
+        reader: BufReader<R>,
+        _color: bool,
+        sink: Option<OutputSink>,
+        log: Option<OutputLog>,
+    ) -> String
     where
         R: tokio::io::AsyncRead + Unpin,
     {
@@ -421,7 +1167,56 @@ impl CommandExecutor {
         let mut lines = reader.lines();
 
         while let Ok(Some(line)) = lines.next_line().await {
-            println!("{}", line);
+            progress::print_line(&line);
+            if let Some(sink) = &sink {
+                sink.write_line(&line);
+            }
+            if let Some(log) = &log {
+                log.write_line(&line);
+            }
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// 検証スキップ警告を表示
+    fn warn_unsafe_execution(&self, command: &Command) {
+        warn!(
+            "Security validation disabled (--allow-unsafe) for command '{}'",
+            command.description
+        );
+        let msg = if self.context.command_allowlist.is_some() {
+            "⚠️  WARNING: --allow-unsafe is set, command validation is DISABLED (command_allowlist still applies)"
+        } else {
+            "⚠️  WARNING: --allow-unsafe is set, command validation is DISABLED"
+        };
+        if self.context.color {
+            eprintln!("{}", msg.red().bold());
+        } else {
+            eprintln!("{}", msg);
+        }
+    }
+
+    /// 統合出力読み取り（stdout + stderr、書き込み順を保持）
+    fn read_merged_output(
+        reader: os_pipe::PipeReader,
+        sink: Option<OutputSink>,
+        log: Option<OutputLog>,
+    ) -> String {
+        use std::io::BufRead;
+
+        let mut output = String::new();
+        for line in std::io::BufReader::new(reader).lines() {
+            let Ok(line) = line else { break };
+            progress::print_line(&line);
+            if let Some(sink) = &sink {
+                sink.write_line(&line);
+            }
+            if let Some(log) = &log {
+                log.write_line(&line);
+            }
             output.push_str(&line);
             output.push('\n');
         }
@@ -432,42 +1227,95 @@ impl CommandExecutor {
     /// コマンド表示
     fn print_command(&self, command: &str) {
         if self.context.color {
-            eprintln!("{} {}", "→".cyan().bold(), command.bright_white());
+            status::eprint(&format!("{} {}", "→".cyan().bold(), command.bright_white()));
         } else {
-            eprintln!("→ {}", command);
+            status::eprint(&format!("→ {}", command));
         }
     }
 
     /// 複数コマンドを並列実行
     pub async fn execute_parallel(&self, commands: &[&Command]) -> Result<Vec<ExecutionResult>> {
+        self.run_parallel(commands, None).await
+    }
+
+    /// 進捗を表示しながら複数コマンドを並列実行
+    ///
+    /// `names` は `commands` と同じ順序の表示名
+    pub async fn execute_parallel_with_progress(
+        &self,
+        commands: &[&Command],
+        names: &[&str],
+        progress: &ParallelProgress,
+    ) -> Result<Vec<ExecutionResult>> {
+        self.run_parallel(commands, Some((names, progress))).await
+    }
+
+    async fn run_parallel(
+        &self,
+        commands: &[&Command],
+        progress: Option<(&[&str], &ParallelProgress)>,
+    ) -> Result<Vec<ExecutionResult>> {
         if commands.is_empty() {
             return Ok(Vec::new());
         }
 
         if self.context.color {
-            eprintln!(
+            status::eprint(&format!(
                 "{} {} commands in parallel",
                 "⚡".yellow().bold(),
                 commands.len()
-            );
+            ));
         }
 
+        // 同時実行数をセマフォで制限
+        let semaphore = Arc::new(Semaphore::new(self.parallel_limit()));
         let mut set = JoinSet::new();
 
         // 各コマンドを並列タスクとして起動
-        for command in commands {
+        for (idx, command) in commands.iter().enumerate() {
             let executor = self.clone_for_task();
             let cmd = (*command).clone();
-
-            set.spawn(async move { executor.execute(&cmd).await });
+            let semaphore = Arc::clone(&semaphore);
+            let progress = progress.map(|(names, progress)| {
+                let name = names.get(idx).copied().unwrap_or(&command.description);
+                (name.to_string(), progress.clone())
+            });
+
+            set.spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("parallel semaphore is never closed");
+                if let Some((name, progress)) = &progress {
+                    progress.command_started(name);
+                }
+                let result = executor.execute(&cmd).await;
+                if let Some((name, progress)) = &progress {
+                    let outcome = match &result {
+                        Ok(result) if result.skipped => ProgressOutcome::Skipped,
+                        Ok(result) if result.success => ProgressOutcome::Succeeded,
+                        Ok(result) => ProgressOutcome::Failed(result.exit_code),
+                        Err(_) => ProgressOutcome::Aborted,
+                    };
+                    progress.command_finished(name, outcome);
+                }
+                (idx, result)
+            });
         }
 
-        // 全タスクの完了を待機
-        let mut results = Vec::new();
+        // 全タスクの完了を待機（結果は元の順序で返す）
+        let mut results: Vec<Option<ExecutionResult>> = std::iter::repeat_with(|| None)
+            .take(commands.len())
+            .collect();
+        let mut interrupted = None;
         while let Some(result) = set.join_next().await {
             match result {
-                Ok(Ok(exec_result)) => results.push(exec_result),
-                Ok(Err(e)) => return Err(e),
+                Ok((idx, Ok(exec_result))) => results[idx] = Some(exec_result),
+                // 中断時は他のタスクが子プロセスを終了させるのを待つ
+                Ok((_, Err(e))) if self.interrupt.is_interrupted() => {
+                    interrupted.get_or_insert(e);
+                }
+                Ok((_, Err(e))) => return Err(e),
                 Err(e) => {
                     return Err(ExecutionError::CommandFailed {
                         command: format!("Parallel task failed: {}", e),
@@ -478,7 +1326,23 @@ impl CommandExecutor {
             }
         }
 
-        Ok(results)
+        if let Some(e) = interrupted {
+            return Err(e);
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// 並列グループの同時実行数の上限
+    fn parallel_limit(&self) -> usize {
+        self.context
+            .max_parallel
+            .unwrap_or_else(|| {
+                std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1)
+            })
+            .max(1)
     }
 
     /// タスク用のクローンを作成
@@ -486,6 +1350,12 @@ impl CommandExecutor {
         Self {
             context: self.context.clone(),
             sensitive_env: SensitiveEnv::new(),
+            output_log: self.output_log.clone(),
+            print_redactor: self.print_redactor.clone(),
+            commands: Arc::clone(&self.commands),
+            args: Arc::clone(&self.args),
+            interrupt: self.interrupt.clone(),
+            foreground: false,
         }
     }
 
@@ -648,6 +1518,8 @@ mod tests {
             description: "test".to_string(),
             cmd: CommandSpec::Single("echo hello".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -655,8 +1527,16 @@ mod tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor.execute(&command).await;
@@ -677,6 +1557,8 @@ mod tests {
             description: "test".to_string(),
             cmd: CommandSpec::Single("echo ${TEST_VAR}".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -684,8 +1566,16 @@ mod tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor.execute(&command).await.unwrap();
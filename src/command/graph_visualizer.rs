@@ -387,6 +387,8 @@ mod tests {
                 description: "Build the project".to_string(),
                 cmd: CommandSpec::Single("cargo build".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec!["lint".to_string(), "test".to_string()],
                 platform: vec![],
@@ -394,8 +396,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -405,6 +415,8 @@ mod tests {
                 description: "Run linter".to_string(),
                 cmd: CommandSpec::Single("cargo clippy".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -412,8 +424,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -423,6 +443,8 @@ mod tests {
                 description: "Run tests".to_string(),
                 cmd: CommandSpec::Single("cargo test".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -430,8 +452,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -631,6 +661,8 @@ mod tests {
                 description: "Deploy to production".to_string(),
                 cmd: CommandSpec::Single("deploy.sh".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec!["build".to_string(), "test".to_string()],
                 platform: vec![],
@@ -638,8 +670,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -649,6 +689,8 @@ mod tests {
                 description: "Build project".to_string(),
                 cmd: CommandSpec::Single("cargo build".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec!["lint".to_string()],
                 platform: vec![],
@@ -656,8 +698,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -667,6 +717,8 @@ mod tests {
                 description: "Run tests".to_string(),
                 cmd: CommandSpec::Single("cargo test".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec!["lint".to_string()],
                 platform: vec![],
@@ -674,8 +726,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -685,6 +745,8 @@ mod tests {
                 description: "Run linter".to_string(),
                 cmd: CommandSpec::Single("cargo clippy".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -692,8 +754,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -742,6 +812,8 @@ mod tests {
                 description: "Standalone command".to_string(),
                 cmd: CommandSpec::Single("echo hello".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -749,8 +821,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -779,6 +859,8 @@ mod tests {
                 description: "Test \"quotes\" and <brackets> and & symbols".to_string(),
                 cmd: CommandSpec::Single("echo test".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -786,8 +868,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -0,0 +1,264 @@
+//! グローバルフック実行
+//!
+//! `[hooks]` に定義されたフックを `cmdrun run` の前後で実行する
+//!
+//! 実行順序:
+//! 1. `before_all`（一度だけ）
+//! 2. 各コマンドごとに `pre_run` → `before_each` → コマンド別 `pre_run`
+//! 3. コマンド本体
+//! 4. 各コマンドごとにコマンド別 `post_run` → `after_each` → `post_run`
+//! 5. `after_all`（一度だけ）
+//!
+//! `after_*` はコマンドが失敗しても実行される（finally 相当）
+
+use crate::command::executor::{CommandExecutor, ExecutionContext};
+use crate::config::schema::{Command, CommandHooks, CommandSpec, CommandsConfig, Hooks};
+use crate::error::{CmdrunError, ExecutionError, Result};
+use ahash::AHashMap;
+use tracing::debug;
+
+/// フックに渡す、対象コマンド名の環境変数
+pub const HOOK_COMMAND_ENV: &str = "CMDRUN_COMMAND";
+
+/// フック実行器
+pub struct HookRunner<'a> {
+    hooks: &'a Hooks,
+    /// `hooks_enabled = false` の場合は `None`
+    executor: Option<CommandExecutor>,
+}
+
+impl<'a> HookRunner<'a> {
+    /// 設定と実行コンテキストからフック実行器を作成
+    ///
+    /// フックはコマンド本体と同じセキュリティ検証を受け、
+    /// タイムアウトはグローバル設定の `timeout` を使う
+    pub fn new(config: &'a CommandsConfig, context: &ExecutionContext) -> Self {
+        let executor = config.config.hooks_enabled.then(|| {
+            CommandExecutor::new(ExecutionContext {
+                timeout: Some(config.config.timeout),
+                ..context.clone()
+            })
+        });
+
+        Self {
+            hooks: &config.hooks,
+            executor,
+        }
+    }
+
+    /// `before_all` を実行（失敗した時点で中断）
+    pub async fn before_all(&self) -> Result<()> {
+        let hooks = stage_hooks("before_all", &self.hooks.before_all);
+        self.run(&hooks, None, false).await
+    }
+
+    /// `after_all` を実行（失敗しても残りのフックを実行）
+    pub async fn after_all(&self) -> Result<()> {
+        let hooks = stage_hooks("after_all", &self.hooks.after_all);
+        self.run(&hooks, None, true).await
+    }
+
+    /// 各コマンドの実行前フックを実行（失敗した時点で中断）
+    pub async fn before_each(&self, names: &[&str]) -> Result<()> {
+        for name in names {
+            let mut hooks: Vec<(&str, &str)> = self
+                .hooks
+                .pre_run
+                .iter()
+                .map(|hook| ("pre_run", hook.as_str()))
+                .collect();
+            hooks.extend(stage_hooks("before_each", &self.hooks.before_each));
+            if let Some(pre_run) = self.command_hooks(name).and_then(|h| h.pre_run.as_deref()) {
+                hooks.push(("pre_run", pre_run));
+            }
+
+            self.run(&hooks, Some(name), false).await?;
+        }
+        Ok(())
+    }
+
+    /// 各コマンドの実行後フックを実行（失敗しても残りのフックを実行し、最初のエラーを返す）
+    pub async fn after_each(&self, names: &[&str]) -> Result<()> {
+        let mut first_error = None;
+        for name in names {
+            let mut hooks: Vec<(&str, &str)> = self
+                .command_hooks(name)
+                .and_then(|h| h.post_run.as_deref())
+                .map(|hook| ("post_run", hook))
+                .into_iter()
+                .collect();
+            hooks.extend(stage_hooks("after_each", &self.hooks.after_each));
+            if let Some(post_run) = self.hooks.post_run.as_deref() {
+                hooks.push(("post_run", post_run));
+            }
+
+            if let Err(e) = self.run(&hooks, Some(name), true).await {
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn command_hooks(&self, name: &str) -> Option<&CommandHooks> {
+        self.hooks.commands.get(name)
+    }
+
+    /// フック一覧を順に実行
+    ///
+    /// `keep_going` が true の場合は失敗しても残りを実行し、最初のエラーを返す
+    async fn run(
+        &self,
+        hooks: &[(&str, &str)],
+        command_name: Option<&str>,
+        keep_going: bool,
+    ) -> Result<()> {
+        let Some(executor) = &self.executor else {
+            return Ok(());
+        };
+
+        let mut first_error = None;
+        for (stage, hook) in hooks {
+            if let Err(e) = run_hook(executor, stage, hook, command_name).await {
+                if !keep_going {
+                    return Err(e);
+                }
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+/// ステージ名とフックの組を作成
+fn stage_hooks<'h>(stage: &'static str, hooks: &'h [String]) -> Vec<(&'static str, &'h str)> {
+    hooks.iter().map(|hook| (stage, hook.as_str())).collect()
+}
+
+/// フックを1つ実行
+async fn run_hook(
+    executor: &CommandExecutor,
+    stage: &str,
+    hook: &str,
+    command_name: Option<&str>,
+) -> Result<()> {
+    debug!("Running {} hook: {}", stage, hook);
+
+    let mut env = AHashMap::new();
+    if let Some(name) = command_name {
+        env.insert(HOOK_COMMAND_ENV.to_string(), name.to_string());
+    }
+
+    let command = Command {
+        description: format!("{} hook", stage),
+        cmd: CommandSpec::Single(hook.to_string()),
+        env,
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        deps: vec![],
+        platform: vec![],
+        tags: vec![],
+        timeout: None,
+        parallel: false,
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    match executor.execute(&command).await {
+        Ok(_) => Ok(()),
+        Err(CmdrunError::Execution(ExecutionError::CommandFailed { code, .. })) => {
+            Err(ExecutionError::HookFailed {
+                stage: stage.to_string(),
+                command: hook.to_string(),
+                code,
+            }
+            .into())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(hooks: Hooks) -> CommandsConfig {
+        CommandsConfig {
+            hooks,
+            ..Default::default()
+        }
+    }
+
+    fn test_context(dir: &TempDir) -> ExecutionContext {
+        ExecutionContext {
+            working_dir: dir.path().to_path_buf(),
+            echo: false,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_before_each_stops_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = test_config(Hooks {
+            before_each: vec!["false".to_string(), "touch second".to_string()],
+            ..Default::default()
+        });
+        let runner = HookRunner::new(&config, &test_context(&temp_dir));
+
+        let err = runner.before_each(&["build"]).await.unwrap_err();
+        assert!(err.to_string().contains("before_each hook failed"));
+        assert!(!temp_dir.path().join("second").exists());
+    }
+
+    #[tokio::test]
+    async fn test_after_each_runs_all_hooks_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut hooks = Hooks {
+            after_each: vec!["false".to_string(), "touch after_each".to_string()],
+            post_run: Some("touch post_run".to_string()),
+            ..Default::default()
+        };
+        hooks.commands.insert(
+            "build".to_string(),
+            CommandHooks {
+                pre_run: None,
+                post_run: Some("touch command_post_run".to_string()),
+            },
+        );
+        let config = test_config(hooks);
+        let runner = HookRunner::new(&config, &test_context(&temp_dir));
+
+        let err = runner.after_each(&["build"]).await.unwrap_err();
+        assert!(err.to_string().contains("after_each hook failed"));
+        for file in ["command_post_run", "after_each", "post_run"] {
+            assert!(temp_dir.path().join(file).exists(), "{} was not run", file);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hooks_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(Hooks {
+            before_all: vec!["touch before_all".to_string()],
+            after_all: vec!["false".to_string()],
+            ..Default::default()
+        });
+        config.config.hooks_enabled = false;
+        let runner = HookRunner::new(&config, &test_context(&temp_dir));
+
+        runner.before_all().await.unwrap();
+        runner.after_all().await.unwrap();
+        assert!(!temp_dir.path().join("before_all").exists());
+    }
+}
@@ -7,8 +7,15 @@
 //! - ${VAR:-default} - デフォルト値
 //! - ${VAR:?error_message} - 必須変数
 //! - ${VAR:+value_if_set} - 設定時置換
+//! - {1}, {2} - 位置引数（シェル用にクォート）
+//! - {@} - 全引数（1引数ずつクォートして空白区切り）
+//! - {*} - 全引数（空白で連結して1つにクォート）
+//!
+//! シングルクォート内の `{N}` は置換しない（`grep -E '[0-9]{4}'` など）。
+//! クォート外でそのまま書く場合は `{{4}}` とエスケープする
 
 use crate::error::{InterpolationError, Result};
+use crate::security::{EnvRedactor, REDACTED};
 use ahash::AHashMap;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -22,6 +29,21 @@ const MAX_EXPANSION_LENGTH: usize = 10_240; // 10KB
 static VAR_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*|[0-9]+)(:[?+\-])?([^}]*)?\}").unwrap());
 
+/// 引数プレースホルダーパターン（{1}, {@}, {*}、先頭一致）
+static ARG_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\{([1-9][0-9]*|@|\*)\}").unwrap());
+
+/// エスケープされた引数プレースホルダー（`{{1}}` → `{1}`、先頭一致）
+static ESCAPED_ARG_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\{(\{(?:[1-9][0-9]*|@|\*)\})\}").unwrap());
+
+/// 引数プレースホルダー走査中のクォート状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quote {
+    None,
+    Single,
+    Double,
+}
+
 /// 変数コンテキスト
 #[derive(Debug, Clone)]
 pub struct InterpolationContext {
@@ -31,6 +53,8 @@ pub struct InterpolationContext {
     strict: bool,
     /// 最大展開深度（再帰防止）
     max_depth: usize,
+    /// 位置引数（`{1}` / `{@}` / `{*}` の置換に使用）
+    args: Vec<String>,
 }
 
 impl InterpolationContext {
@@ -40,9 +64,16 @@ impl InterpolationContext {
             env_vars: AHashMap::new(),
             strict,
             max_depth: 10,
+            args: Vec::new(),
         }
     }
 
+    /// 位置引数を設定
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
+    }
+
     /// 環境変数を追加
     pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.env_vars.insert(key.into(), value.into());
@@ -63,6 +94,16 @@ impl InterpolationContext {
         self
     }
 
+    /// 伏せ字対象キーの値を `***REDACTED***` に置き換える（表示用の展開で使用）
+    pub fn redact_values(mut self, redactor: &EnvRedactor) -> Self {
+        for (key, value) in self.env_vars.iter_mut() {
+            if redactor.is_redacted(key) {
+                *value = REDACTED.to_string();
+            }
+        }
+        self
+    }
+
     /// 変数展開実行
     pub fn interpolate(&self, input: &str) -> Result<String> {
         self.interpolate_with_depth(input, 0)
@@ -81,6 +122,7 @@ impl InterpolationContext {
 
         let mut result = String::with_capacity(input.len());
         let mut last_end = 0;
+        let mut quote = Quote::None;
 
         for cap in VAR_PATTERN.captures_iter(input) {
             let full_match = cap.get(0).unwrap();
@@ -89,7 +131,12 @@ impl InterpolationContext {
             let operand = cap.get(3).map(|m| m.as_str()).unwrap_or("");
 
             // マッチ前の部分を追加
-            result.push_str(&input[last_end..full_match.start()]);
+            self.push_literal(
+                &mut result,
+                &input[last_end..full_match.start()],
+                depth,
+                &mut quote,
+            )?;
 
             // 変数値を取得
             let value = self.resolve_variable(var_name, operator, operand)?;
@@ -106,10 +153,91 @@ impl InterpolationContext {
         }
 
         // 残りの部分を追加
-        result.push_str(&input[last_end..]);
+        self.push_literal(&mut result, &input[last_end..], depth, &mut quote)?;
         Ok(result)
     }
 
+    /// `${...}` 以外の部分を追加（最上位のみ引数プレースホルダーを置換）
+    ///
+    /// 変数の値に含まれる `{1}` 等は置換しない。シングルクォート内はそのまま残し、
+    /// `{{1}}` はエスケープとして `{1}` にする。`quote` は `${...}` をまたいで引き継ぐ
+    fn push_literal(
+        &self,
+        result: &mut String,
+        literal: &str,
+        depth: usize,
+        quote: &mut Quote,
+    ) -> Result<()> {
+        if depth > 0 {
+            result.push_str(literal);
+            return Ok(());
+        }
+
+        let mut rest = literal;
+        while let Some(ch) = rest.chars().next() {
+            if *quote != Quote::Single {
+                if let Some(cap) = ESCAPED_ARG_PATTERN.captures(rest) {
+                    result.push_str(&cap[1]);
+                    rest = &rest[cap[0].len()..];
+                    continue;
+                }
+                if let Some(cap) = ARG_PATTERN.captures(rest) {
+                    result.push_str(&self.resolve_placeholder(&cap[1])?);
+                    rest = &rest[cap[0].len()..];
+                    continue;
+                }
+            }
+
+            *quote = match (ch, *quote) {
+                ('\'', Quote::None) => Quote::Single,
+                ('\'', Quote::Single) => Quote::None,
+                ('"', Quote::None) => Quote::Double,
+                ('"', Quote::Double) => Quote::None,
+                (_, current) => current,
+            };
+            result.push(ch);
+            rest = &rest[ch.len_utf8()..];
+
+            // シングルクォート外のバックスラッシュは次の1文字をそのまま扱う
+            if ch == '\\' && *quote != Quote::Single {
+                if let Some(next) = rest.chars().next() {
+                    result.push(next);
+                    rest = &rest[next.len_utf8()..];
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 引数プレースホルダーの値（シェル用にクォート済み）
+    fn resolve_placeholder(&self, placeholder: &str) -> Result<String> {
+        match placeholder {
+            "@" => Ok(self
+                .args
+                .iter()
+                .map(|arg| shell_words::quote(arg).into_owned())
+                .collect::<Vec<_>>()
+                .join(" ")),
+            "*" if self.args.is_empty() => Ok(String::new()),
+            "*" => Ok(shell_words::quote(&self.args.join(" ")).into_owned()),
+            index => {
+                // ARG_PATTERN により 1 以上の数値
+                let index: usize = index.parse().map_err(|_| {
+                    InterpolationError::InvalidSyntax(format!("{{{}}}", placeholder))
+                })?;
+                match self.args.get(index - 1) {
+                    Some(arg) => Ok(shell_words::quote(arg).into_owned()),
+                    None if self.strict => Err(InterpolationError::MissingArgument {
+                        index,
+                        given: self.args.len(),
+                    }
+                    .into()),
+                    None => Ok(String::new()),
+                }
+            }
+        }
+    }
+
     /// 変数値解決
     fn resolve_variable(
         &self,
@@ -331,6 +459,113 @@ mod tests {
         assert_eq!(result, "default");
     }
 
+    fn args_ctx(strict: bool, args: &[&str]) -> InterpolationContext {
+        InterpolationContext::new(strict).with_args(args.iter().map(|a| a.to_string()).collect())
+    }
+
+    #[test]
+    fn test_index_placeholder() {
+        let ctx = args_ctx(true, &["input.png", "out.webp"]);
+
+        let result = ctx.interpolate("sharp -i {1} -o {2}").unwrap();
+        assert_eq!(result, "sharp -i input.png -o out.webp");
+    }
+
+    #[test]
+    fn test_all_args_placeholder() {
+        let ctx = args_ctx(true, &["a", "b c", "d"]);
+
+        assert_eq!(ctx.interpolate("echo {@}").unwrap(), "echo a 'b c' d");
+        assert_eq!(ctx.interpolate("echo {*}").unwrap(), "echo 'a b c d'");
+
+        // 引数なしは空
+        let empty = args_ctx(true, &[]);
+        assert_eq!(empty.interpolate("echo {@}{*}").unwrap(), "echo ");
+    }
+
+    #[test]
+    fn test_placeholder_quotes_shell_syntax() {
+        let ctx = args_ctx(true, &["it's", "a; rm -rf /", "$(whoami)"]);
+
+        assert_eq!(ctx.interpolate("echo {1}").unwrap(), r"echo 'it'\''s'");
+        assert_eq!(ctx.interpolate("echo {2}").unwrap(), "echo 'a; rm -rf /'");
+        // `$(...)` はシェルにもcmdrunにも展開されない
+        assert_eq!(ctx.interpolate("echo {3}").unwrap(), "echo '$(whoami)'");
+    }
+
+    #[test]
+    fn test_missing_placeholder_argument() {
+        let result = args_ctx(true, &["one"]).interpolate("echo {1} {2}");
+        assert!(matches!(
+            result,
+            Err(crate::error::CmdrunError::Interpolation(
+                InterpolationError::MissingArgument { index: 2, given: 1 }
+            ))
+        ));
+
+        let result = args_ctx(false, &["one"])
+            .interpolate("echo {1} {2}")
+            .unwrap();
+        assert_eq!(result, "echo one ");
+    }
+
+    #[test]
+    fn test_placeholder_not_substituted_in_variable_values() {
+        let mut env = AHashMap::new();
+        env.insert("TEMPLATE".to_string(), "{1}".to_string());
+        let ctx = args_ctx(true, &["arg"]).with_env_map(env);
+
+        assert_eq!(ctx.interpolate("${TEMPLATE} {1}").unwrap(), "{1} arg");
+        // `{0}` や `{name}`、ブレース展開はそのまま
+        assert_eq!(
+            ctx.interpolate("echo {0} {name} {1..3} {}").unwrap(),
+            "echo {0} {name} {1..3} {}"
+        );
+    }
+
+    #[test]
+    fn test_placeholder_ignored_in_single_quotes() {
+        for strict in [true, false] {
+            let ctx = args_ctx(strict, &[]);
+            assert_eq!(
+                ctx.interpolate("echo 2024-01-15 | grep -oE '[0-9]{4}'")
+                    .unwrap(),
+                "echo 2024-01-15 | grep -oE '[0-9]{4}'"
+            );
+            assert_eq!(
+                ctx.interpolate("echo 'match a{2} here'").unwrap(),
+                "echo 'match a{2} here'"
+            );
+        }
+
+        // ダブルクォート内とクォートを閉じた後は置換する
+        let ctx = args_ctx(true, &["x y"]);
+        assert_eq!(
+            ctx.interpolate("echo '{1}' \"{1}\" {1}").unwrap(),
+            "echo '{1}' \"'x y'\" 'x y'"
+        );
+        // `${...}` をまたいでもクォート状態を引き継ぐ
+        assert_eq!(
+            ctx.interpolate("echo '${HOME:-h} {1}'").unwrap(),
+            "echo 'h {1}'"
+        );
+    }
+
+    #[test]
+    fn test_escaped_placeholder() {
+        let ctx = args_ctx(true, &[]);
+        assert_eq!(
+            ctx.interpolate("grep -E [0-9]{{4}} {{@}}").unwrap(),
+            "grep -E [0-9]{4} {@}"
+        );
+        assert_eq!(
+            args_ctx(true, &["a"])
+                .interpolate("echo {{1}}={1} \\'{1}")
+                .unwrap(),
+            "echo {1}=a \\'a"
+        );
+    }
+
     #[test]
     fn test_no_interpolation() {
         let ctx = InterpolationContext::new(false);
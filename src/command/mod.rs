@@ -1,4 +1,6 @@
+pub mod confirm;
 pub mod dependency;
 pub mod executor;
 pub mod graph_visualizer;
+pub mod hooks;
 pub mod interpolation;
@@ -5,13 +5,16 @@
 use anyhow::{bail, Context, Result};
 use colored::*;
 use dialoguer::{theme::ColorfulTheme, Input, Select};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use std::fs;
 use std::path::PathBuf;
 use toml_edit::{value, Array, DocumentMut, InlineTable, Item, Table};
 
+use crate::config::loader::save_config_atomic;
 use crate::config::{ConfigLoader, Language};
 use crate::i18n::{get_message, MessageKey};
-use crate::security::validation::CommandValidator;
+use crate::security::validation::{CommandValidator, ValidationResult};
 
 /// Handle the add command
 pub async fn handle_add(
@@ -31,9 +34,6 @@ pub async fn handle_add(
     let config = config_loader.load().await.unwrap_or_default();
     let lang = config.config.language;
 
-    // Interactive prompts with navigation support
-    let theme = ColorfulTheme::default();
-
     // If all arguments provided, skip interactive mode
     if let (Some(ref id_val), Some(ref command_val), Some(ref description_val)) =
         (&id, &command, &description)
@@ -60,61 +60,113 @@ pub async fn handle_add(
         .await;
     }
 
-    // Interactive mode with back navigation
-    let mut input_id = id.unwrap_or_default();
-    let mut input_command = command.unwrap_or_default();
-    let mut input_description = description.unwrap_or_default();
+    let mut prompt = DialoguerPrompt::default();
+    interactive_add(
+        &mut prompt,
+        AddInput {
+            id: id.unwrap_or_default(),
+            command: command.unwrap_or_default(),
+            description: description.unwrap_or_default(),
+            category,
+            tags,
+        },
+        lang,
+        config_path,
+    )
+    .await
+}
 
-    // Error messages for validation (static lifetime required by dialoguer)
-    let err_empty_id = get_message(MessageKey::ErrorEmptyCommandId, lang);
-    let err_empty_cmd = get_message(MessageKey::ErrorEmptyCommand, lang);
-    let err_empty_desc = get_message(MessageKey::ErrorEmptyDescription, lang);
+/// Source of answers for the interactive add flow
+///
+/// The CLI uses [`DialoguerPrompt`]; tests drive the flow with scripted answers.
+pub trait AddPrompt {
+    /// Ask for a line of text, pre-filled with `default`
+    fn input(&mut self, prompt: &str, default: &str) -> Result<String>;
+
+    /// Ask the user to pick one of `items`, returning its index
+    fn select(&mut self, prompt: &str, items: &[&str], default: usize) -> Result<usize>;
+}
+
+/// Terminal prompts backed by dialoguer
+#[derive(Default)]
+pub struct DialoguerPrompt {
+    theme: ColorfulTheme,
+}
+
+impl AddPrompt for DialoguerPrompt {
+    fn input(&mut self, prompt: &str, default: &str) -> Result<String> {
+        Ok(Input::with_theme(&self.theme)
+            .with_prompt(prompt)
+            .default(default.to_string())
+            .interact_text()?)
+    }
+
+    fn select(&mut self, prompt: &str, items: &[&str], default: usize) -> Result<usize> {
+        Ok(Select::with_theme(&self.theme)
+            .with_prompt(prompt)
+            .items(items)
+            .default(default)
+            .interact()?)
+    }
+}
+
+/// Values collected for a new command
+struct AddInput {
+    id: String,
+    command: String,
+    description: String,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+}
 
+/// Interactive add flow with back navigation
+///
+/// The command is checked by the security validator as soon as it is entered,
+/// so a rejected command can be corrected instead of failing on write.
+async fn interactive_add(
+    prompt: &mut dyn AddPrompt,
+    mut input: AddInput,
+    lang: Language,
+    config_path: Option<PathBuf>,
+) -> Result<()> {
     loop {
         println!();
         println!("{}", "=== Add New Command ===".bright_cyan().bold());
         println!();
 
         // Step 1: Command ID
-        input_id = Input::with_theme(&theme)
-            .with_prompt(get_message(MessageKey::PromptCommandId, lang))
-            .default(input_id.clone())
-            .validate_with(move |input: &String| -> Result<(), &str> {
-                if input.trim().is_empty() {
-                    Err(err_empty_id)
-                } else {
-                    Ok(())
-                }
-            })
-            .interact_text()?;
-
-        // Step 2: Command
-        input_command = Input::with_theme(&theme)
-            .with_prompt(get_message(MessageKey::PromptCommand, lang))
-            .default(input_command.clone())
-            .validate_with(move |input: &String| -> Result<(), &str> {
-                if input.trim().is_empty() {
-                    Err(err_empty_cmd)
-                } else {
-                    Ok(())
-                }
-            })
-            .interact_text()?;
+        input.id = prompt_non_empty(
+            prompt,
+            get_message(MessageKey::PromptCommandId, lang),
+            &input.id,
+            get_message(MessageKey::ErrorEmptyCommandId, lang),
+        )?;
+
+        // Step 2: Command (re-prompted until it passes validation)
+        loop {
+            input.command = prompt_non_empty(
+                prompt,
+                get_message(MessageKey::PromptCommand, lang),
+                &input.command,
+                get_message(MessageKey::ErrorEmptyCommand, lang),
+            )?;
+
+            let validation_result = validate_command(&input.command);
+            if validation_result.is_safe() {
+                break;
+            }
+            print_violations(&validation_result, lang);
+        }
 
         // Step 3: Description
-        input_description = Input::with_theme(&theme)
-            .with_prompt(get_message(MessageKey::PromptDescription, lang))
-            .default(input_description.clone())
-            .validate_with(move |input: &String| -> Result<(), &str> {
-                if input.trim().is_empty() {
-                    Err(err_empty_desc)
-                } else {
-                    Ok(())
-                }
-            })
-            .interact_text()?;
-
-        // Preview
+        input.description = prompt_non_empty(
+            prompt,
+            get_message(MessageKey::PromptDescription, lang),
+            &input.description,
+            get_message(MessageKey::ErrorEmptyDescription, lang),
+        )?;
+
+        // Preview of the TOML that will be written
         println!();
         println!(
             "{}",
@@ -122,44 +174,30 @@ pub async fn handle_add(
                 .bright_cyan()
                 .bold()
         );
-        println!(
-            "  {}: {}",
-            get_message(MessageKey::LabelId, lang),
-            input_id.green()
-        );
-        println!(
-            "  {}: {}",
-            get_message(MessageKey::LabelCommand, lang),
-            input_command.yellow()
-        );
-        println!(
-            "  {}: {}",
-            get_message(MessageKey::LabelDescription, lang),
-            input_description.white()
-        );
+        println!();
+        for line in preview_toml(&input).lines() {
+            println!("  {}", highlight_toml_line(line));
+        }
         println!();
 
         // Confirmation with options
-        let options = vec![
+        let options = [
             get_message(MessageKey::OptionYesAdd, lang),
             get_message(MessageKey::OptionNoEdit, lang),
             get_message(MessageKey::OptionCancel, lang),
         ];
-        let selection = Select::with_theme(&theme)
-            .with_prompt(get_message(MessageKey::PromptWhatToDo, lang))
-            .items(&options)
-            .default(0)
-            .interact()?;
+        let selection =
+            prompt.select(get_message(MessageKey::PromptWhatToDo, lang), &options, 0)?;
 
         match selection {
             0 => {
                 // Add command
                 return add_command_to_config(
-                    input_id,
-                    input_command,
-                    input_description,
-                    category,
-                    tags,
+                    input.id,
+                    input.command,
+                    input.description,
+                    input.category,
+                    input.tags,
                     lang,
                     config_path,
                 )
@@ -179,6 +217,98 @@ pub async fn handle_add(
     }
 }
 
+/// Prompt until a non-blank answer is given
+fn prompt_non_empty(
+    prompt: &mut dyn AddPrompt,
+    message: &str,
+    default: &str,
+    empty_error: &str,
+) -> Result<String> {
+    loop {
+        let answer = prompt.input(message, default)?;
+        if !answer.trim().is_empty() {
+            return Ok(answer);
+        }
+        eprintln!("{} {}", "✗".red().bold(), empty_error.red());
+    }
+}
+
+/// Security check applied to added commands
+fn validate_command(command: &str) -> ValidationResult {
+    CommandValidator::new().validate(command)
+}
+
+/// Show each violation with its kind
+fn print_violations(result: &ValidationResult, lang: Language) {
+    eprintln!();
+    eprintln!(
+        "{} {}",
+        "✗".red().bold(),
+        get_message(MessageKey::ErrorSecurityValidationFailed, lang)
+            .red()
+            .bold()
+    );
+    for violation in &result.violations {
+        eprintln!(
+            "  {} {}",
+            format!("[{}]", violation.kind).yellow(),
+            violation
+        );
+    }
+    eprintln!();
+}
+
+/// Inline table stored under `[commands]` for a new command
+fn command_entry(input: &AddInput) -> InlineTable {
+    let mut command_table = InlineTable::new();
+    command_table.insert(
+        "description",
+        value(input.description.clone()).into_value().unwrap(),
+    );
+    command_table.insert("cmd", value(input.command.clone()).into_value().unwrap());
+
+    // Add optional fields
+    if let Some(cat) = &input.category {
+        command_table.insert("category", value(cat.clone()).into_value().unwrap());
+    }
+
+    if let Some(tag_list) = &input.tags {
+        let mut tag_array = Array::new();
+        for tag in tag_list {
+            tag_array.push(tag.clone());
+        }
+        command_table.insert("tags", value(tag_array).into_value().unwrap());
+    }
+
+    command_table
+}
+
+/// TOML block that the new command will add to the config file
+fn preview_toml(input: &AddInput) -> String {
+    let mut commands = Table::new();
+    commands.insert(&input.id, Item::Value(command_entry(input).into()));
+
+    let mut doc = DocumentMut::new();
+    doc["commands"] = Item::Table(commands);
+    doc.to_string()
+}
+
+/// Minimal TOML highlighting: table headers, keys and string values
+fn highlight_toml_line(line: &str) -> String {
+    static STRING: Lazy<Regex> = Lazy::new(|| Regex::new(r#""(?:[^"\\]|\\.)*""#).unwrap());
+
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('[') {
+        return line.cyan().bold().to_string();
+    }
+
+    let Some((key, rest)) = line.split_once(" = ") else {
+        return line.to_string();
+    };
+    let rest = STRING.replace_all(rest, |caps: &regex::Captures| caps[0].yellow().to_string());
+    format!("{} = {}", key.green().bold(), rest)
+}
+
 /// Add command to TOML configuration
 async fn add_command_to_config(
     id: String,
@@ -205,16 +335,13 @@ async fn add_command_to_config(
     );
 
     // Security validation: Check for dangerous shell metacharacters
-    let validator = CommandValidator::new();
-    let validation_result = validator.validate(&command);
+    let validation_result = validate_command(&command);
     if !validation_result.is_safe() {
-        if let Some(err) = validation_result.error() {
-            bail!(
-                "{}: {}",
-                get_message(MessageKey::ErrorSecurityValidationFailed, lang),
-                err
-            );
-        }
+        bail!(
+            "{}: {}",
+            get_message(MessageKey::ErrorSecurityValidationFailed, lang),
+            validation_result.reasons()
+        );
     }
 
     // Read existing TOML file
@@ -234,22 +361,13 @@ async fn add_command_to_config(
     }
 
     // Create new command entry
-    let mut command_table = InlineTable::new();
-    command_table.insert("description", value(description).into_value().unwrap());
-    command_table.insert("cmd", value(command).into_value().unwrap());
-
-    // Add optional fields
-    if let Some(cat) = category {
-        command_table.insert("category", value(cat).into_value().unwrap());
-    }
-
-    if let Some(tag_list) = tags {
-        let mut tag_array = Array::new();
-        for tag in tag_list {
-            tag_array.push(tag);
-        }
-        command_table.insert("tags", value(tag_array).into_value().unwrap());
-    }
+    let command_table = command_entry(&AddInput {
+        id: id.clone(),
+        command,
+        description,
+        category,
+        tags,
+    });
 
     // Ensure [commands] table exists
     if !doc.contains_key("commands") {
@@ -258,9 +376,16 @@ async fn add_command_to_config(
         doc["commands"] = Item::Table(commands);
     }
 
-    // Add the new command to the commands table
+    // Add the new command to the commands table, matching the file's style:
+    // a `[commands.<id>]` table if the existing commands use them, otherwise inline
     if let Some(commands_table) = doc["commands"].as_table_mut() {
-        commands_table.insert(&id, Item::Value(command_table.into()));
+        let uses_subtables = commands_table.iter().any(|(_, item)| item.is_table());
+        let entry = if uses_subtables {
+            Item::Table(command_table.into_table())
+        } else {
+            Item::Value(command_table.into())
+        };
+        commands_table.insert(&id, entry);
     } else {
         bail!(
             "{}",
@@ -269,8 +394,7 @@ async fn add_command_to_config(
     }
 
     // Write back to file
-    fs::write(&config_path, doc.to_string())
-        .with_context(|| format!("Failed to write config file: {}", config_path.display()))?;
+    save_config_atomic(&config_path, &doc.to_string())?;
 
     println!(
         "{} {} '{}'",
@@ -708,6 +832,133 @@ existing = { description = "Existing command", cmd = "echo existing" }
         assert!(err.contains("parse") || err.contains("TOML") || err.contains("Failed"));
     }
 
+    /// Answers prompts from a script and records what was asked
+    #[derive(Default)]
+    struct ScriptedPrompt {
+        inputs: std::collections::VecDeque<&'static str>,
+        selections: std::collections::VecDeque<usize>,
+        asked: Vec<String>,
+    }
+
+    impl ScriptedPrompt {
+        fn new(inputs: &[&'static str], selections: &[usize]) -> Self {
+            Self {
+                inputs: inputs.iter().copied().collect(),
+                selections: selections.iter().copied().collect(),
+                asked: Vec::new(),
+            }
+        }
+    }
+
+    impl AddPrompt for ScriptedPrompt {
+        fn input(&mut self, prompt: &str, _default: &str) -> Result<String> {
+            self.asked.push(prompt.to_string());
+            self.inputs
+                .pop_front()
+                .map(str::to_string)
+                .context("prompt script exhausted")
+        }
+
+        fn select(&mut self, prompt: &str, _items: &[&str], _default: usize) -> Result<usize> {
+            self.asked.push(prompt.to_string());
+            self.selections
+                .pop_front()
+                .context("prompt script exhausted")
+        }
+    }
+
+    fn empty_input() -> AddInput {
+        AddInput {
+            id: String::new(),
+            command: String::new(),
+            description: String::new(),
+            category: None,
+            tags: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interactive_add_reprompts_dangerous_command() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+        fs::write(&path, "[commands]\n").unwrap();
+
+        let mut prompt = ScriptedPrompt::new(
+            &["deploy", "echo $(whoami)", "./deploy.sh", "Deploy the app"],
+            &[0],
+        );
+        interactive_add(
+            &mut prompt,
+            empty_input(),
+            Language::English,
+            Some(path.clone()),
+        )
+        .await
+        .unwrap();
+
+        let command_prompt = get_message(MessageKey::PromptCommand, Language::English);
+        assert_eq!(
+            prompt.asked.iter().filter(|p| *p == command_prompt).count(),
+            2,
+            "{:?}",
+            prompt.asked
+        );
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("./deploy.sh"), "{}", content);
+        assert!(!content.contains("whoami"), "{}", content);
+    }
+
+    #[tokio::test]
+    async fn test_interactive_add_cancel_writes_nothing() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+        fs::write(&path, "[commands]\n").unwrap();
+
+        let mut prompt = ScriptedPrompt::new(&["", "lint", "cargo clippy", "Lint"], &[2]);
+        interactive_add(
+            &mut prompt,
+            empty_input(),
+            Language::English,
+            Some(path.clone()),
+        )
+        .await
+        .unwrap();
+
+        // Blank ID was asked again
+        let id_prompt = get_message(MessageKey::PromptCommandId, Language::English);
+        assert_eq!(prompt.asked.iter().filter(|p| *p == id_prompt).count(), 2);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[commands]\n");
+    }
+
+    #[test]
+    fn test_preview_toml_matches_written_entry() {
+        let input = AddInput {
+            id: "build".to_string(),
+            command: "cargo build".to_string(),
+            description: "Build".to_string(),
+            category: None,
+            tags: Some(vec!["rust".to_string()]),
+        };
+
+        let preview = preview_toml(&input);
+        assert!(preview.starts_with("[commands]\n"), "{}", preview);
+        assert!(
+            preview.contains(
+                r#"build = { description = "Build", cmd = "cargo build", tags = ["rust"] }"#
+            ),
+            "{}",
+            preview
+        );
+
+        // Highlighting only adds color codes
+        let line = r#"build = { cmd = "x" }"#;
+        assert_eq!(
+            crate::output::capture::strip_ansi(&highlight_toml_line(line)),
+            line
+        );
+    }
+
     #[tokio::test]
     async fn test_add_command_with_nonexistent_file() {
         let temp_dir = TempDir::new().unwrap();
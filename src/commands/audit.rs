@@ -0,0 +1,368 @@
+//! Audit command - review a configuration's commands without running them
+
+use crate::config::loader::ConfigLoader;
+use crate::config::schema::{Command, CommandSpec, CommandsConfig};
+use crate::security::validation::{
+    check_dangerous_env_vars, script_lines, CommandValidator, Violation,
+};
+use ahash::AHashMap;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// A command line that strict validation would reject
+#[derive(Debug, Clone)]
+pub struct BlockedStep {
+    /// Where the line comes from (`cmd`, `cmd[1]`, `cmd.windows`, `when_shell`)
+    pub source: String,
+    pub line: String,
+    pub violations: Vec<Violation>,
+}
+
+/// Audit result for a single command
+#[derive(Debug, Clone)]
+pub struct CommandAudit {
+    pub name: String,
+    pub blocked: Vec<BlockedStep>,
+    /// Settings that loosen security checks, such as `allow_chaining = true`
+    pub flags: Vec<String>,
+}
+
+impl CommandAudit {
+    pub fn is_blocked(&self) -> bool {
+        !self.blocked.is_empty()
+    }
+}
+
+/// Collected results of the audit
+#[derive(Debug, Default)]
+pub struct AuditReport {
+    /// Commands in name order
+    pub commands: Vec<CommandAudit>,
+    /// Findings for `[config]` itself
+    pub global_flags: Vec<String>,
+}
+
+impl AuditReport {
+    /// Number of commands strict validation would reject
+    pub fn blocked_count(&self) -> usize {
+        self.commands.iter().filter(|c| c.is_blocked()).count()
+    }
+
+    /// Find a command's result by name
+    pub fn get(&self, name: &str) -> Option<&CommandAudit> {
+        self.commands.iter().find(|c| c.name == name)
+    }
+}
+
+/// Handle audit command
+///
+/// Nothing is executed. Fails if any command would be blocked in strict mode.
+pub async fn handle_audit(global_only: bool, config_paths: Vec<PathBuf>) -> Result<()> {
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+    let config = config_loader.load().await?;
+
+    let report = audit_config(&config);
+    print_report(&report);
+
+    let blocked = report.blocked_count();
+    if blocked > 0 {
+        anyhow::bail!("{} command(s) would be blocked in strict mode", blocked);
+    }
+
+    Ok(())
+}
+
+/// Audit every command in the configuration
+///
+/// Each command line, including every platform variant and `when_shell`, is
+/// checked with strict validation regardless of `allow_chaining` /
+/// `allow_subshells`, which are reported as flags instead.
+pub fn audit_config(config: &CommandsConfig) -> AuditReport {
+    let mut validator = CommandValidator::new().allow_variable_expansion();
+    if let Some(allowlist) = &config.config.command_allowlist {
+        validator = validator.with_allowlist(allowlist.clone());
+    }
+
+    let mut names: Vec<&String> = config.commands.keys().collect();
+    names.sort();
+
+    let commands = names
+        .into_iter()
+        .map(|name| audit_command(name, &config.commands[name], &validator))
+        .collect();
+
+    let mut global_flags = Vec::new();
+    if config.config.allow_command_chaining {
+        global_flags.push("allow_command_chaining = true".to_string());
+    }
+    if config.config.allow_subshells {
+        global_flags.push("allow_subshells = true".to_string());
+    }
+    global_flags.extend(dangerous_env_flags(&config.config.env));
+
+    AuditReport {
+        commands,
+        global_flags,
+    }
+}
+
+fn audit_command(name: &str, command: &Command, validator: &CommandValidator) -> CommandAudit {
+    let blocked = command_lines(command)
+        .into_iter()
+        .filter_map(|(source, line)| {
+            let result = validator.validate(&line);
+            (!result.is_safe()).then_some(BlockedStep {
+                source,
+                line,
+                violations: result.violations,
+            })
+        })
+        .collect();
+
+    let mut flags = Vec::new();
+    if command.allow_chaining == Some(true) {
+        flags.push("allow_chaining = true".to_string());
+    }
+    if command.allow_subshells == Some(true) {
+        flags.push("allow_subshells = true".to_string());
+    }
+    if let CommandSpec::Script { file } = &command.cmd {
+        flags.push(format!("runs script file {}", file));
+    }
+    flags.extend(dangerous_env_flags(&command.env));
+
+    CommandAudit {
+        name: name.to_string(),
+        blocked,
+        flags,
+    }
+}
+
+/// Every shell line the command can run, labelled with where it comes from
+fn command_lines(command: &Command) -> Vec<(String, String)> {
+    let mut lines = match &command.cmd {
+        CommandSpec::Single(cmd) => vec![("cmd".to_string(), cmd.clone())],
+        CommandSpec::Multiple(cmds) => cmds
+            .iter()
+            .enumerate()
+            .map(|(i, cmd)| (format!("cmd[{}]", i), cmd.clone()))
+            .collect(),
+        CommandSpec::Platform(platform_cmds) => [
+            ("unix", &platform_cmds.unix),
+            ("linux", &platform_cmds.linux),
+            ("macos", &platform_cmds.macos),
+            ("windows", &platform_cmds.windows),
+        ]
+        .into_iter()
+        .filter_map(|(platform, cmd)| {
+            cmd.as_ref()
+                .map(|c| (format!("cmd.{}", platform), c.clone()))
+        })
+        .collect(),
+        // An unreadable script is reported when the command runs
+        CommandSpec::Script { file } => command
+            .script_path(file, false)
+            .and_then(|path| Ok(std::fs::read_to_string(path)?))
+            .map(|script| {
+                script_lines(&script)
+                    .map(|(line_no, line)| (format!("cmd.file:{}", line_no), line.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    if let Some(condition) = &command.when_shell {
+        lines.push(("when_shell".to_string(), condition.clone()));
+    }
+
+    lines
+}
+
+fn dangerous_env_flags(env: &AHashMap<String, String>) -> Vec<String> {
+    let mut vars = check_dangerous_env_vars(env);
+    vars.sort();
+    vars.into_iter()
+        .map(|var| format!("sets dangerous environment variable {}", var))
+        .collect()
+}
+
+fn print_report(report: &AuditReport) {
+    println!();
+    println!(
+        "{} ({} command(s), strict mode)",
+        "cmdrun audit".cyan().bold(),
+        report.commands.len()
+    );
+    println!();
+
+    if !report.global_flags.is_empty() {
+        println!("{} {}", "⚠".yellow().bold(), "[config]".yellow());
+        for flag in &report.global_flags {
+            println!("    {}", flag);
+        }
+    }
+
+    for command in &report.commands {
+        if command.is_blocked() {
+            println!("{} {}", "✗".red().bold(), command.name.red());
+        } else if !command.flags.is_empty() {
+            println!("{} {}", "⚠".yellow().bold(), command.name.yellow());
+        } else {
+            println!("{} {}", "✓".green().bold(), command.name);
+            continue;
+        }
+
+        for step in &command.blocked {
+            println!("    {} {}: {}", "blocked".red(), step.source, step.line);
+            for violation in &step.violations {
+                println!("      - {}", violation);
+            }
+        }
+        for flag in &command.flags {
+            println!("    {}", flag);
+        }
+    }
+
+    println!();
+    let blocked = report.blocked_count();
+    if blocked > 0 {
+        println!(
+            "{} {} command(s) would be blocked in strict mode",
+            "✗".red().bold(),
+            blocked
+        );
+    } else {
+        println!(
+            "{} No command would be blocked in strict mode",
+            "✓".green().bold()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::validation::ViolationKind;
+
+    const MIXED: &str = r#"
+[config]
+allow_subshells = true
+
+[config.env]
+PATH = "/opt/tools/bin"
+
+[commands.build]
+description = "Build"
+cmd = "cargo build --release"
+
+[commands.deploy]
+description = "Deploy"
+cmd = ["cargo build", "scp target/app host:/srv && ssh host restart"]
+allow_chaining = true
+
+[commands.open]
+description = "Open"
+cmd = { unix = "xdg-open .", windows = "start . | more" }
+
+[commands.preload]
+description = "Preloaded"
+cmd = "./run.sh"
+when_shell = "test -f .ready; true"
+
+[commands.preload.env]
+LD_PRELOAD = "/tmp/hook.so"
+"#;
+
+    fn audit(toml: &str) -> AuditReport {
+        audit_config(&toml::from_str(toml).unwrap())
+    }
+
+    #[test]
+    fn test_audit_mixed_config() {
+        let report = audit(MIXED);
+
+        let names: Vec<&str> = report.commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "deploy", "open", "preload"]);
+        assert_eq!(report.blocked_count(), 3);
+
+        let build = report.get("build").unwrap();
+        assert!(!build.is_blocked());
+        assert!(build.flags.is_empty());
+
+        // Blocked in strict mode even though `allow_chaining` would allow it at run time
+        let deploy = report.get("deploy").unwrap();
+        assert_eq!(deploy.blocked.len(), 1);
+        assert_eq!(deploy.blocked[0].source, "cmd[1]");
+        assert_eq!(
+            deploy.blocked[0].violations[0].kind,
+            ViolationKind::CommandChaining
+        );
+        assert_eq!(deploy.flags, vec!["allow_chaining = true"]);
+
+        // Every platform variant is audited, not just the current one
+        let open = report.get("open").unwrap();
+        assert_eq!(open.blocked.len(), 1);
+        assert_eq!(open.blocked[0].source, "cmd.windows");
+        assert_eq!(open.blocked[0].violations[0].kind, ViolationKind::Pipe);
+
+        let preload = report.get("preload").unwrap();
+        assert_eq!(preload.blocked[0].source, "when_shell");
+        assert_eq!(
+            preload.flags,
+            vec!["sets dangerous environment variable LD_PRELOAD"]
+        );
+
+        assert_eq!(
+            report.global_flags,
+            vec![
+                "allow_subshells = true",
+                "sets dangerous environment variable PATH"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_audit_clean_config() {
+        let report = audit(
+            r#"
+[commands.test]
+description = "Test"
+cmd = "cargo test ${1:-}"
+"#,
+        );
+
+        assert_eq!(report.blocked_count(), 0);
+        assert!(report.global_flags.is_empty());
+        assert!(report.get("test").unwrap().flags.is_empty());
+    }
+
+    #[test]
+    fn test_audit_applies_allowlist() {
+        let report = audit(
+            r#"
+[config]
+command_allowlist = ["cargo"]
+
+[commands.build]
+cmd = "cargo build"
+
+[commands.fetch]
+cmd = "curl -O https://example.com/file"
+"#,
+        );
+
+        assert!(!report.get("build").unwrap().is_blocked());
+        let fetch = report.get("fetch").unwrap();
+        assert_eq!(
+            fetch.blocked[0].violations[0].kind,
+            ViolationKind::NotAllowlisted
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Bench command - run a command repeatedly and report timing statistics
+
+use crate::command::executor::{CommandExecutor, ExecutionContext};
+use crate::config::loader::ConfigLoader;
+use crate::config::schema::Command;
+use crate::error::{CmdrunError, ExecutionError};
+use crate::platform::shell::detect_shell;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Options for `cmdrun bench`
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Number of measured runs
+    pub runs: usize,
+    /// Number of discarded runs before measuring
+    pub warmup: usize,
+    /// Keep measuring when a run exits with a non-zero status
+    pub ignore_failures: bool,
+}
+
+/// Durations of the measured runs
+#[derive(Debug, Clone, Default)]
+pub struct BenchReport {
+    /// Wall-clock duration of each measured run, in run order
+    pub samples: Vec<Duration>,
+    /// Number of measured runs that failed (only with `ignore_failures`)
+    pub failures: usize,
+}
+
+/// Summary statistics of the measured runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchStats {
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    /// Sample standard deviation (zero for a single run)
+    pub stddev: Duration,
+}
+
+impl BenchReport {
+    /// Compute statistics, or `None` without samples
+    pub fn stats(&self) -> Option<BenchStats> {
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+
+        let count = sorted.len();
+        let min = *sorted.first()?;
+        let max = sorted[count - 1];
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / count as f64;
+        let median = if count % 2 == 0 {
+            (secs[count / 2 - 1] + secs[count / 2]) / 2.0
+        } else {
+            secs[count / 2]
+        };
+        let variance = if count > 1 {
+            secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (count - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(BenchStats {
+            min,
+            max,
+            mean: Duration::from_secs_f64(mean),
+            median: Duration::from_secs_f64(median),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Handle bench command
+///
+/// Only the command itself is measured; dependencies are not run. Bench runs
+/// are not recorded in history.
+pub async fn handle_bench(
+    name: &str,
+    options: BenchOptions,
+    global_only: bool,
+    config_paths: Vec<PathBuf>,
+) -> Result<()> {
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+    let config = config_loader.load_with_environment().await?;
+
+    let command = config
+        .commands
+        .get(name)
+        .ok_or_else(|| anyhow::anyhow!("Command not found: {}", name))?;
+
+    let ctx = ExecutionContext {
+        working_dir: config.config.working_dir.clone(),
+        env: config.config.resolved_env()?,
+        shell: detect_shell()
+            .map(|s| s.name)
+            .unwrap_or_else(|_| config.config.shell.clone()),
+        timeout: command.timeout.or(Some(config.config.timeout)),
+        strict: config.config.strict_mode,
+        echo: false,
+        color: true,
+        language: config.config.language,
+        allow_command_chaining: config.config.allow_command_chaining,
+        allow_subshells: config.config.allow_subshells,
+        command_allowlist: config.config.command_allowlist.clone(),
+        allow_unsafe: false,
+        merge_stderr: config.config.merge_stderr,
+        kill_grace_ms: config.config.kill_grace_ms,
+        max_parallel: config.config.max_parallel,
+    };
+    let executor = CommandExecutor::new(ctx).with_commands(config.commands.clone());
+
+    println!(
+        "{} {} ({} run(s), {} warmup)",
+        "Benchmarking:".cyan().bold(),
+        name.bright_white(),
+        options.runs,
+        options.warmup
+    );
+
+    let report = bench_command(&executor, command, &options).await?;
+    print_report(&report);
+
+    Ok(())
+}
+
+/// Run `command` `warmup + runs` times and collect the measured durations
+///
+/// A failing run aborts the benchmark unless `ignore_failures` is set, in
+/// which case its duration is still measured and counted in `failures`.
+pub async fn bench_command(
+    executor: &CommandExecutor,
+    command: &Command,
+    options: &BenchOptions,
+) -> Result<BenchReport> {
+    let mut report = BenchReport::default();
+
+    for run in 0..options.warmup + options.runs {
+        let measured = run >= options.warmup;
+
+        let start = Instant::now();
+        let failed = match executor.execute(command).await {
+            Ok(_) => false,
+            Err(CmdrunError::Execution(ExecutionError::CommandFailed { code, .. }))
+                if options.ignore_failures =>
+            {
+                tracing::debug!("Bench run {} failed with exit code {}", run + 1, code);
+                true
+            }
+            Err(e @ CmdrunError::Execution(ExecutionError::CommandFailed { code, .. })) => {
+                let phase = if measured { "run" } else { "warmup run" };
+                return Err(anyhow::Error::new(e).context(format!(
+                    "Bench {} {} failed with exit code {} (use --ignore-failures to continue)",
+                    phase,
+                    run + 1,
+                    code
+                )));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let elapsed = start.elapsed();
+
+        if measured {
+            report.samples.push(elapsed);
+            if failed {
+                report.failures += 1;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+fn print_report(report: &BenchReport) {
+    let Some(stats) = report.stats() else {
+        return;
+    };
+
+    println!();
+    println!(
+        "  {} {} ± {}",
+        "Time (mean ± σ):".bright_white(),
+        format_time(stats.mean).green().bold(),
+        format_time(stats.stddev)
+    );
+    println!(
+        "  {} {}  ({} … {})",
+        "Median:".bright_white(),
+        format_time(stats.median),
+        format_time(stats.min).cyan(),
+        format_time(stats.max).magenta()
+    );
+    println!("  {} {}", "Runs:".bright_white(), report.samples.len());
+
+    if report.failures > 0 {
+        println!(
+            "  {} {} run(s) exited with a non-zero status",
+            "⚠".yellow().bold(),
+            report.failures
+        );
+    }
+}
+
+/// Format a duration with a unit suited to its size
+fn format_time(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.3} s", secs)
+    } else {
+        format!("{:.1} ms", secs * 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(cmd: &str) -> Command {
+        toml::from_str(&format!("cmd = {:?}", cmd)).unwrap()
+    }
+
+    fn executor() -> CommandExecutor {
+        CommandExecutor::new(ExecutionContext {
+            echo: false,
+            ..Default::default()
+        })
+    }
+
+    fn options(runs: usize, warmup: usize, ignore_failures: bool) -> BenchOptions {
+        BenchOptions {
+            runs,
+            warmup,
+            ignore_failures,
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let report = BenchReport {
+            samples: [40, 10, 30, 20].map(Duration::from_millis).to_vec(),
+            failures: 0,
+        };
+
+        let stats = report.stats().unwrap();
+        assert_eq!(stats.min, Duration::from_millis(10));
+        assert_eq!(stats.max, Duration::from_millis(40));
+        assert_eq!(stats.mean, Duration::from_millis(25));
+        assert_eq!(stats.median, Duration::from_millis(25));
+        // sqrt(((15² + 5²) * 2) / 3) ms
+        assert_eq!(stats.stddev.as_micros(), 12_909);
+
+        assert!(BenchReport::default().stats().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bench_echo_collects_each_run() {
+        let report = bench_command(&executor(), &command("echo bench"), &options(3, 2, false))
+            .await
+            .unwrap();
+
+        assert_eq!(report.samples.len(), 3);
+        assert_eq!(report.failures, 0);
+        let stats = report.stats().unwrap();
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+    }
+
+    #[tokio::test]
+    async fn test_bench_failure_aborts_unless_ignored() {
+        let error = bench_command(&executor(), &command("exit 3"), &options(3, 0, false))
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("Bench run 1 failed"),
+            "{}",
+            error
+        );
+
+        let report = bench_command(&executor(), &command("exit 3"), &options(3, 0, true))
+            .await
+            .unwrap();
+        assert_eq!(report.samples.len(), 3);
+        assert_eq!(report.failures, 3);
+    }
+}
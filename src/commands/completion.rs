@@ -1,24 +1,39 @@
 //! Completion command implementation
 
-use crate::cli::Cli;
+use crate::cli::{Cli, CompletionShell};
 use crate::config::Language;
 use crate::i18n::{get_message, MessageKey};
-use clap::CommandFactory;
+use clap::{ArgAction, CommandFactory};
 use clap_complete::{generate, Shell};
 use colored::Colorize;
+use std::fmt::Write as _;
 use std::io::{self, Write};
 
+/// Subcommands whose first positional argument is a command name
+const COMMAND_NAME_SUBCOMMANDS: &[&str] = &["run", "info"];
+
 /// Handle completion command
-pub fn handle_completion(shell: Shell, language: Language) {
+pub fn handle_completion(shell: CompletionShell, language: Language) {
     let mut cmd = Cli::command();
-    let mut buf = Vec::new();
-    generate(shell, &mut cmd, "cmdrun", &mut buf);
 
-    // Write generated completion
-    io::stdout().write_all(&buf).unwrap();
+    match clap_shell(shell) {
+        Some(clap_shell) => {
+            let mut buf = Vec::new();
+            generate(clap_shell, &mut cmd, "cmdrun", &mut buf);
+
+            // Write generated completion
+            io::stdout().write_all(&buf).unwrap();
+        }
+        None => {
+            // clap_complete has no generator for this shell
+            print!("{}", generate_nushell(&cmd));
+        }
+    }
 
     // Add custom completion functions for command descriptions
-    add_custom_completions(shell);
+    if let Some(script) = custom_completions(shell) {
+        println!("{}", script);
+    }
 
     // Print installation instructions to stderr (won't interfere with eval)
     eprintln!();
@@ -31,29 +46,53 @@ pub fn handle_completion(shell: Shell, language: Language) {
     print_installation_instructions(shell);
 }
 
-/// Add custom completion functions for command descriptions
-fn add_custom_completions(shell: Shell) {
+/// Map to the clap_complete generator, if one exists
+fn clap_shell(shell: CompletionShell) -> Option<Shell> {
     match shell {
-        Shell::Bash => {
-            println!(
-                r#"
+        CompletionShell::Bash => Some(Shell::Bash),
+        CompletionShell::Zsh => Some(Shell::Zsh),
+        CompletionShell::Fish => Some(Shell::Fish),
+        CompletionShell::PowerShell => Some(Shell::PowerShell),
+        CompletionShell::Elvish => Some(Shell::Elvish),
+        CompletionShell::Nushell => None,
+    }
+}
+
+/// Custom completion functions for command names with descriptions
+///
+/// `cmdrun completion-list` prints `name:description` lines with colons in
+/// either part escaped as `\:`; each script splits on the first unescaped
+/// colon.
+fn custom_completions(shell: CompletionShell) -> Option<&'static str> {
+    match shell {
+        CompletionShell::Bash => Some(BASH_COMPLETIONS),
+        CompletionShell::Zsh => Some(ZSH_COMPLETIONS),
+        CompletionShell::Fish => Some(FISH_COMPLETIONS),
+        // Nushell completers are part of the generated extern definitions
+        _ => None,
+    }
+}
+
+const BASH_COMPLETIONS: &str = r#"
 # Custom completion for 'cmdrun run' and 'cmdrun info' with descriptions
-_cmdrun_complete_commands() {{
+_cmdrun_complete_commands() {
     local IFS=$'\n'
     local suggestions=($(cmdrun completion-list 2>/dev/null))
 
-    if [ "${{#suggestions[@]}}" == "0" ]; then
+    if [ "${#suggestions[@]}" == "0" ]; then
         return 1
     fi
 
-    # Parse "name:description" format
+    # Parse "name:description" format (split on the first unescaped colon)
+    local re='^((\\.|[^:\\])*):(.*)$'
     local names=()
     local descs=()
     COMPREPLY=()
 
-    for suggestion in "${{suggestions[@]}}"; do
-        local name="${{suggestion%%:*}}"
-        local desc="${{suggestion#*:}}"
+    for suggestion in "${suggestions[@]}"; do
+        [[ $suggestion =~ $re ]] || continue
+        local name="${BASH_REMATCH[1]//\\:/:}"
+        local desc="${BASH_REMATCH[3]//\\:/:}"
         names+=("$name")
         descs+=("$desc")
         # Add name only to COMPREPLY (Bash doesn't natively support descriptions)
@@ -63,15 +102,55 @@ _cmdrun_complete_commands() {{
     # Try to display descriptions using bash-completion 2.11+ feature
     # This may not work on all systems
     if declare -F _comp_cmd_complete__descriptions &>/dev/null; then
-        _comp_cmd_complete__descriptions "${{names[@]}}" -- "${{descs[@]}}"
+        _comp_cmd_complete__descriptions "${names[@]}" -- "${descs[@]}"
+    fi
+
+    return 0
+}
+
+# Complete arguments of 'cmdrun run <name>' from the command's arg_completions
+_cmdrun_complete_args() {
+    local name="" position=1 word i
+    for ((i = 2; i < COMP_CWORD; i++)); do
+        word="${COMP_WORDS[i]}"
+        [[ $word == -* ]] && continue
+        if [[ -z $name ]]; then
+            name="$word"
+        else
+            ((position++))
+        fi
+    done
+    [[ -n $name ]] || return 1
+
+    local IFS=$'\n'
+    local choices=($(cmdrun complete-args "$name" "$position" 2>/dev/null))
+    if [ "${#choices[@]}" == "0" ]; then
+        return 1
+    fi
+
+    COMPREPLY=($(compgen -W "${choices[*]}" -- "${COMP_WORDS[COMP_CWORD]}"))
+    return 0
+}
+
+# Complete tag or category values from the config ($1: completion-tags or completion-categories)
+_cmdrun_complete_values() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prefix=""
+    # Comma-separated lists: complete the last item only
+    [[ $cur == *,* ]] && prefix="${cur%,*},"
+
+    local IFS=$'\n'
+    local values=($(cmdrun "$1" 2>/dev/null))
+    if [ "${#values[@]}" == "0" ]; then
+        return 1
     fi
 
+    COMPREPLY=($(compgen -P "$prefix" -W "${values[*]}" -- "${cur##*,}"))
     return 0
-}}
+}
 
 # Intercept and wrap the clap-generated _cmdrun function
 # This must be done after clap generates the completion function
-_cmdrun_wrap_completion() {{
+_cmdrun_wrap_completion() {
     # Check if _cmdrun exists
     if ! declare -F _cmdrun > /dev/null 2>&1; then
         return 1
@@ -81,31 +160,44 @@ _cmdrun_wrap_completion() {{
     eval "$(declare -f _cmdrun | sed '1s/^_cmdrun/_cmdrun_original/')"
 
     # Redefine _cmdrun with our hook
-    _cmdrun() {{
+    _cmdrun() {
         # Check if completing the name argument for 'run' or 'info'
         if [[ $COMP_CWORD -eq 2 ]]; then
-            case "${{COMP_WORDS[1]}}" in
+            case "${COMP_WORDS[1]}" in
                 run|r|info|i)
                     _cmdrun_complete_commands && return 0
                     ;;
             esac
+        elif [[ $COMP_CWORD -gt 2 ]]; then
+            case "${COMP_WORDS[1]}" in
+                run|r)
+                    _cmdrun_complete_args && return 0
+                    ;;
+            esac
         fi
 
+        # Tags and categories that exist in the config
+        case "${COMP_WORDS[1]} ${COMP_WORDS[COMP_CWORD-1]}" in
+            "add --tags"|"add -t")
+                _cmdrun_complete_values completion-tags && return 0
+                ;;
+            "add --category"|"add -C"|"list --category"|"ls --category")
+                _cmdrun_complete_values completion-categories && return 0
+                ;;
+        esac
+
         # Fall back to original completion
         _cmdrun_original "$@"
-    }}
-}}
+    }
+}
 
 # Execute the wrapper after this script loads
 _cmdrun_wrap_completion
-"#
-            );
-        }
-        Shell::Zsh => {
-            println!(
-                r#"
+"#;
+
+const ZSH_COMPLETIONS: &str = r#"
 # Custom completion for 'cmdrun run' and 'cmdrun info' with descriptions
-_cmdrun_commands_with_desc() {{
+_cmdrun_commands_with_desc() {
     local -a commands
     local line
 
@@ -115,58 +207,216 @@ _cmdrun_commands_with_desc() {{
     done < <(cmdrun completion-list 2>/dev/null)
 
     # If no commands found, return failure
-    if (( ${{#commands}} == 0 )); then
+    if (( ${#commands} == 0 )); then
         return 1
     fi
 
     # Use _describe (simple and reliable)
     _describe 'available commands' commands
-}}
+}
+
+# Complete arguments of 'cmdrun run <name>' from the command's arg_completions
+_cmdrun_arg_choices() {
+    local -a args choices
+
+    # Words after 'run' without options: the command name, then its arguments
+    args=(${${words[3,CURRENT-1]}:#-*})
+    (( ${#args} )) || return 1
+
+    choices=(${(f)"$(cmdrun complete-args ${args[1]} ${#args} 2>/dev/null)"})
+    (( ${#choices} )) || return 1
+
+    compadd -a choices
+}
+
+# Complete tag or category values from the config ($1: completion-tags or completion-categories)
+_cmdrun_config_values() {
+    local -a values
+    values=(${(f)"$(cmdrun $1 2>/dev/null)"})
+    (( ${#values} )) || return 1
+
+    # Comma-separated lists: complete the last item only
+    compset -P '*,'
+    compadd -a values
+}
 
 # Wrap the original _cmdrun function to inject custom completion
 # Save the original function
-functions[_cmdrun_original]=${{functions[_cmdrun]}}
+functions[_cmdrun_original]=${functions[_cmdrun]}
 
 # Redefine _cmdrun with custom logic
-_cmdrun() {{
+_cmdrun() {
     # Check if we're completing 'run' or 'info' subcommand's name argument
-    if [[ ${{words[2]}} == "run" || ${{words[2]}} == "info" || ${{words[2]}} == "r" || ${{words[2]}} == "i" ]]; then
+    if [[ ${words[2]} == "run" || ${words[2]} == "info" || ${words[2]} == "r" || ${words[2]} == "i" ]]; then
         if [[ $CURRENT == 3 ]]; then
             _cmdrun_commands_with_desc && return 0
         fi
     fi
 
+    # Arguments of 'run <name>' with configured choices
+    if [[ ${words[2]} == "run" || ${words[2]} == "r" ]] && (( CURRENT > 3 )); then
+        _cmdrun_arg_choices && return 0
+    fi
+
+    # Tags and categories that exist in the config
+    case "${words[2]} ${words[CURRENT-1]}" in
+        "add --tags"|"add -t")
+            _cmdrun_config_values completion-tags && return 0
+            ;;
+        "add --category"|"add -C"|"list --category"|"ls --category")
+            _cmdrun_config_values completion-categories && return 0
+            ;;
+    esac
+
     # Otherwise, use the original completion
     _cmdrun_original "$@"
-}}
+}
 
 # Configure completion style for cmdrun to show menu immediately
 zstyle ':completion:*:*:cmdrun:*' menu yes select
-"#
-            );
-        }
-        Shell::Fish => {
-            println!(
-                r#"
+"#;
+
+const FISH_COMPLETIONS: &str = r#"
 # Custom completion for 'cmdrun run' and 'cmdrun info' with descriptions
-complete -c cmdrun -n "__fish_seen_subcommand_from run; and not __fish_seen_subcommand_from (cmdrun completion-list 2>/dev/null | string replace -r ':.*' '')" -f -a "
-(cmdrun completion-list 2>/dev/null | string replace ':' \t)"
+# Convert "name:description" to "name<TAB>description" (split on the first unescaped colon)
+function __cmdrun_command_list
+    cmdrun completion-list 2>/dev/null | string replace -r '^((?:\\\\.|[^:\\\\])*):' '$1'\t | string replace -a '\\:' ':'
+end
 
-complete -c cmdrun -n "__fish_seen_subcommand_from info; and not __fish_seen_subcommand_from (cmdrun completion-list 2>/dev/null | string replace -r ':.*' '')" -f -a "
-(cmdrun completion-list 2>/dev/null | string replace ':' \t)"
-"#
-            );
+# True while the command name has not been given yet
+function __cmdrun_needs_command_name
+    set -l args (string match -v -- '-*' (commandline -opc))
+    test (count $args) -eq 2
+end
+
+# Choices for the argument being completed, from the command's arg_completions
+function __cmdrun_arg_choices
+    set -l args (string match -v -- '-*' (commandline -opc))
+    test (count $args) -ge 3; or return
+    cmdrun complete-args $args[3] (math (count $args) - 2) 2>/dev/null
+end
+
+complete -c cmdrun -n '__fish_seen_subcommand_from run r; and __cmdrun_needs_command_name' -f -a '(__cmdrun_command_list)'
+complete -c cmdrun -n '__fish_seen_subcommand_from info i; and __cmdrun_needs_command_name' -f -a '(__cmdrun_command_list)'
+complete -c cmdrun -n '__fish_seen_subcommand_from run r; and not __cmdrun_needs_command_name' -a '(__cmdrun_arg_choices)'
+
+# Tags and categories that exist in the config
+complete -c cmdrun -n '__fish_seen_subcommand_from add' -s t -l tags -x -a '(cmdrun completion-tags 2>/dev/null)'
+complete -c cmdrun -n '__fish_seen_subcommand_from add' -s C -l category -x -a '(cmdrun completion-categories 2>/dev/null)'
+complete -c cmdrun -n '__fish_seen_subcommand_from list ls' -l category -x -a '(cmdrun completion-categories 2>/dev/null)'
+"#;
+
+const NUSHELL_COMPLETER: &str = r#"# Command names with descriptions from `cmdrun completion-list`
+# ("name:description", colons in either part escaped as "\:")
+def "nu-complete cmdrun commands" [] {
+    do -i { ^cmdrun completion-list }
+    | lines
+    | parse --regex '^(?P<value>(?:\\.|[^:\\])*):(?P<description>.*)$'
+    | update value {|row| $row.value | str replace --all '\:' ':' }
+    | update description {|row| $row.description | str replace --all '\:' ':' }
+}
+
+# Tags and categories used by the configured commands
+def "nu-complete cmdrun tags" [] {
+    do -i { ^cmdrun completion-tags } | lines
+}
+
+def "nu-complete cmdrun categories" [] {
+    do -i { ^cmdrun completion-categories } | lines
+}
+"#;
+
+/// Generate a Nushell completion script (`export extern` definitions)
+///
+/// One definition is emitted per visible subcommand; global options are
+/// repeated on every subcommand so they are accepted anywhere.
+pub fn generate_nushell(cmd: &clap::Command) -> String {
+    let mut out = String::from(NUSHELL_COMPLETER);
+    let globals: Vec<&clap::Arg> = cmd.get_arguments().filter(|a| a.is_global_set()).collect();
+    write_nushell_extern(&mut out, cmd, cmd.get_name(), &globals);
+    out
+}
+
+fn write_nushell_extern(out: &mut String, cmd: &clap::Command, path: &str, globals: &[&clap::Arg]) {
+    out.push('\n');
+    if let Some(about) = cmd.get_about() {
+        let _ = writeln!(out, "# {}", first_line(&about.to_string()));
+    }
+    let _ = writeln!(out, "export extern \"{}\" [", path);
+
+    let completes_command_name = path
+        .strip_prefix("cmdrun ")
+        .is_some_and(|sub| COMMAND_NAME_SUBCOMMANDS.contains(&sub));
+    let mut first_positional = true;
+
+    let own_args = cmd.get_arguments().filter(|a| !a.is_global_set());
+    for arg in globals.iter().copied().chain(own_args) {
+        if arg.is_hide_set() {
+            continue;
         }
-        _ => {
-            // Other shells don't have good support for descriptions in completions
+
+        let param = if arg.is_positional() {
+            let name = arg.get_id().as_str().replace('-', "_");
+            let completer = if completes_command_name && first_positional {
+                "@\"nu-complete cmdrun commands\""
+            } else {
+                ""
+            };
+            first_positional = false;
+
+            if matches!(arg.get_action(), ArgAction::Append) {
+                format!("...{}: string{}", name, completer)
+            } else if arg.is_required_set() {
+                format!("{}: string{}", name, completer)
+            } else {
+                format!("{}?: string{}", name, completer)
+            }
+        } else {
+            let flag = match (arg.get_long(), arg.get_short()) {
+                (Some(long), Some(short)) => format!("--{}(-{})", long, short),
+                (Some(long), None) => format!("--{}", long),
+                (None, Some(short)) => format!("-{}", short),
+                (None, None) => continue,
+            };
+            if matches!(arg.get_action(), ArgAction::Set | ArgAction::Append) {
+                let completer = match arg.get_long() {
+                    Some("tags") => "@\"nu-complete cmdrun tags\"",
+                    Some("category") => "@\"nu-complete cmdrun categories\"",
+                    _ => "",
+                };
+                format!("{}: string{}", flag, completer)
+            } else {
+                flag
+            }
+        };
+
+        match arg.get_help() {
+            Some(help) => {
+                let _ = writeln!(out, "    {} # {}", param, first_line(&help.to_string()));
+            }
+            None => {
+                let _ = writeln!(out, "    {}", param);
+            }
+        }
+    }
+    out.push_str("]\n");
+
+    for sub in cmd.get_subcommands() {
+        if sub.is_hide_set() {
+            continue;
         }
+        write_nushell_extern(out, sub, &format!("{} {}", path, sub.get_name()), globals);
     }
 }
 
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default().trim()
+}
+
 /// Print shell-specific installation instructions
-fn print_installation_instructions(shell: Shell) {
+fn print_installation_instructions(shell: CompletionShell) {
     match shell {
-        Shell::Bash => {
+        CompletionShell::Bash => {
             eprintln!();
             eprintln!("  Add to your {}:", "~/.bashrc".green());
             eprintln!("    {}", "eval \"$(cmdrun completion bash)\"".dimmed());
@@ -177,7 +427,7 @@ fn print_installation_instructions(shell: Shell) {
                 "cmdrun completion bash > /etc/bash_completion.d/cmdrun".dimmed()
             );
         }
-        Shell::Zsh => {
+        CompletionShell::Zsh => {
             eprintln!();
             eprintln!("  Add to your {}:", "~/.zshrc".green());
             eprintln!("    {}", "eval \"$(cmdrun completion zsh)\"".dimmed());
@@ -188,7 +438,7 @@ fn print_installation_instructions(shell: Shell) {
                 "cmdrun completion zsh > \"${fpath[1]}/_cmdrun\"".dimmed()
             );
         }
-        Shell::Fish => {
+        CompletionShell::Fish => {
             eprintln!();
             eprintln!("  Save to Fish completion directory:");
             eprintln!(
@@ -196,7 +446,7 @@ fn print_installation_instructions(shell: Shell) {
                 "cmdrun completion fish > ~/.config/fish/completions/cmdrun.fish".dimmed()
             );
         }
-        Shell::PowerShell => {
+        CompletionShell::PowerShell => {
             eprintln!();
             eprintln!("  Add to your PowerShell profile:");
             eprintln!(
@@ -211,14 +461,22 @@ fn print_installation_instructions(shell: Shell) {
             );
             eprintln!("    {}", ". ./cmdrun.ps1".dimmed());
         }
-        Shell::Elvish => {
+        CompletionShell::Elvish => {
             eprintln!();
             eprintln!("  Add to your {}:", "~/.elvish/rc.elv".green());
             eprintln!("    {}", "eval (cmdrun completion elvish)".dimmed());
         }
-        _ => {
+        CompletionShell::Nushell => {
             eprintln!();
-            eprintln!("  See your shell's documentation for completion setup.");
+            eprintln!(
+                "  Save the definitions and source them from your {}:",
+                "config.nu".green()
+            );
+            eprintln!(
+                "    {}",
+                "cmdrun completion nushell | save -f ~/.config/nushell/cmdrun.nu".dimmed()
+            );
+            eprintln!("    {}", "source ~/.config/nushell/cmdrun.nu".dimmed());
         }
     }
 
@@ -232,6 +490,20 @@ fn print_installation_instructions(shell: Shell) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::{Arg, Command};
+
+    /// Compare against a golden file; set `UPDATE_GOLDEN=1` to rewrite it
+    fn assert_golden(name: &str, actual: &str) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/completion")
+            .join(name);
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, actual).unwrap();
+        }
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+        assert_eq!(actual, expected, "golden file mismatch: {}", path.display());
+    }
 
     #[test]
     fn test_generate_completion() {
@@ -263,4 +535,71 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_fish_custom_completions_golden() {
+        assert_golden(
+            "cmdrun_custom.fish",
+            custom_completions(CompletionShell::Fish).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_nushell_golden() {
+        let cmd = Command::new("cmdrun")
+            .about("Test command runner")
+            .arg(
+                Arg::new("verbose")
+                    .short('v')
+                    .long("verbose")
+                    .action(ArgAction::Count)
+                    .global(true)
+                    .help("Increase verbosity"),
+            )
+            .subcommand(
+                Command::new("run")
+                    .about("Run a command\n\nLong description is dropped")
+                    .arg(Arg::new("name").required(true).help("Command name"))
+                    .arg(
+                        Arg::new("args")
+                            .action(ArgAction::Append)
+                            .help("Additional arguments"),
+                    )
+                    .arg(
+                        Arg::new("parallel")
+                            .short('p')
+                            .long("parallel")
+                            .action(ArgAction::SetTrue)
+                            .help("Run dependencies in parallel"),
+                    ),
+            )
+            .subcommand(
+                Command::new("history").about("Manage history").subcommand(
+                    Command::new("list").arg(
+                        Arg::new("limit")
+                            .short('l')
+                            .long("limit")
+                            .action(ArgAction::Set)
+                            .help("Number of entries"),
+                    ),
+                ),
+            )
+            .subcommand(Command::new("completion-list").hide(true));
+
+        assert_golden("test_cli.nu", &generate_nushell(&cmd));
+    }
+
+    #[test]
+    fn test_nushell_for_cli() {
+        let script = generate_nushell(&Cli::command());
+        assert!(script.contains("export extern \"cmdrun run\" ["));
+        assert!(script.contains("name: string@\"nu-complete cmdrun commands\""));
+        assert!(script.contains("export extern \"cmdrun history list\" ["));
+        assert!(!script.contains("completion-list\" ["));
+        assert!(script.contains("--tags(-t): string@\"nu-complete cmdrun tags\""));
+        assert!(script.contains("--category: string@\"nu-complete cmdrun categories\""));
+        // Global options are accepted on subcommands
+        let run = &script[script.find("export extern \"cmdrun run\"").unwrap()..];
+        assert!(run[..run.find("\n]").unwrap()].contains("--config(-c): string"));
+    }
 }
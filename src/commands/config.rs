@@ -6,10 +6,74 @@ use std::fs;
 use std::path::PathBuf;
 use toml_edit::DocumentMut;
 
-use crate::config::{loader::ConfigLoader, Language};
+use crate::config::duration::format_duration;
+use crate::config::loader::{save_config_atomic, ConfigLoader};
+use crate::config::{schema::CommandsConfig, Language};
+use crate::error::config_not_found;
 use crate::i18n::{get_message, MessageKey};
+use crate::security::validation::CommandValidator;
+use crate::security::{EnvRedactor, REDACTED};
+
+/// Fields of the `[config]` section that `config get/set` accept
+const GLOBAL_KEYS: &[&str] = &[
+    "shell",
+    "strict_mode",
+    "parallel",
+    "timeout",
+    "working_dir",
+    "language",
+    "env",
+    "env_file",
+    "typo_detection",
+    "typo_threshold",
+    "auto_correct",
+    "allow_command_chaining",
+    "allow_subshells",
+    "command_allowlist",
+    "redact_patterns",
+    "merge_stderr",
+    "kill_grace_ms",
+    "max_parallel",
+    "hooks_enabled",
+    "history_max_entries",
+    "history_max_age",
+    "notify_on_completion",
+    "notify_threshold_ms",
+];
+
+/// Fields of a `[commands.<name>]` table that `config get/set` accept
+const COMMAND_KEYS: &[&str] = &[
+    "description",
+    "cmd",
+    "env",
+    "env_file",
+    "working_dir",
+    "deps",
+    "platform",
+    "tags",
+    "timeout",
+    "parallel",
+    "confirm",
+    "confirm_message",
+    "allow_chaining",
+    "allow_subshells",
+    "output_pipe",
+    "category",
+    "watch",
+    "when",
+    "when_shell",
+    "shell",
+    "arg_completions",
+];
+
+/// Map-valued fields whose entries can be addressed as `<field>.<KEY>`
+const MAP_KEYS: &[&str] = &["env"];
 
 /// Get a configuration value
+///
+/// `key` is either a `[config]` field (`timeout`) or a dotted path such as
+/// `config.env.FOO` or `commands.build.timeout`. Prints nothing when the key
+/// is valid but not set.
 pub async fn handle_get(key: &str, config_path: Option<PathBuf>) -> Result<()> {
     let config_loader = if let Some(path) = config_path {
         ConfigLoader::with_path(path)?
@@ -19,33 +83,52 @@ pub async fn handle_get(key: &str, config_path: Option<PathBuf>) -> Result<()> {
     let config = config_loader.load().await?;
     let lang = config.config.language;
 
-    match key {
-        "language" => {
-            let lang_str = match config.config.language {
-                crate::config::schema::Language::English => "english",
-                crate::config::schema::Language::Japanese => "japanese",
-                crate::config::schema::Language::ChineseSimplified => "chinese_simplified",
-                crate::config::schema::Language::ChineseTraditional => "chinese_traditional",
-            };
-            println!("{}", lang_str);
-        }
-        "shell" => println!("{}", config.config.shell),
-        "timeout" => println!("{}", config.config.timeout),
-        "strict_mode" => println!("{}", config.config.strict_mode),
-        "parallel" => println!("{}", config.config.parallel),
-        "working_dir" => println!("{}", config.config.working_dir.display()),
-        _ => anyhow::bail!(
+    let path = parse_key(key, lang)?;
+    if path[0] == "commands" && !config.commands.contains_key(&path[1]) {
+        anyhow::bail!(
             "{}: {}",
-            get_message(MessageKey::ErrorUnknownConfigKey, lang),
-            key
-        ),
+            get_message(MessageKey::ErrorCommandNotFound, lang),
+            path[1]
+        );
+    }
+
+    let root = toml::Value::try_from(&config)?;
+    let found = path
+        .iter()
+        .try_fold(&root, |value, segment| value.get(segment.as_str()));
+    match found {
+        Some(toml::Value::String(s)) => println!("{}", s),
+        Some(value) => println!("{}", value),
+        None => {}
     }
 
     Ok(())
 }
 
 /// Set a configuration value
+///
+/// Accepts the same keys as [`handle_get`]. The value is written with the
+/// type the schema expects (e.g. `60` as an integer, `true` as a boolean,
+/// `["a", "b"]` as a list) and the file keeps its existing formatting.
 pub async fn handle_set(key: &str, value: &str, config_file_path: Option<PathBuf>) -> Result<()> {
+    set_value(key, value, config_file_path, false).await
+}
+
+/// Append a value to a list-valued configuration key (`config set --append`)
+pub async fn handle_append(
+    key: &str,
+    value: &str,
+    config_file_path: Option<PathBuf>,
+) -> Result<()> {
+    set_value(key, value, config_file_path, true).await
+}
+
+async fn set_value(
+    key: &str,
+    value: &str,
+    config_file_path: Option<PathBuf>,
+    append: bool,
+) -> Result<()> {
     // Load config first to get language setting
     let temp_loader = if let Some(ref path) = config_file_path {
         ConfigLoader::with_path(path)?
@@ -61,60 +144,226 @@ pub async fn handle_set(key: &str, value: &str, config_file_path: Option<PathBuf
         get_config_path()?
     };
     let content = fs::read_to_string(&config_path)?;
-    let mut doc = content.parse::<DocumentMut>()?;
-
-    match key {
-        "language" => {
-            // Validate language value
-            let valid_languages = [
-                "english",
-                "japanese",
-                "chinese_simplified",
-                "chinese_traditional",
-            ];
-            if !valid_languages.contains(&value) {
-                anyhow::bail!("{}", get_message(MessageKey::ErrorInvalidLanguage, lang));
-            }
-            doc["config"]["language"] = toml_edit::value(value);
-        }
-        "shell" => {
-            doc["config"]["shell"] = toml_edit::value(value);
-        }
-        "timeout" => {
-            let timeout: u64 = value.parse()?;
-            doc["config"]["timeout"] = toml_edit::value(timeout as i64);
+    let doc = content.parse::<DocumentMut>()?;
+
+    let path = parse_key(key, lang)?;
+    if path == ["config", "language"] {
+        // Validate language value
+        let valid_languages = [
+            "english",
+            "japanese",
+            "chinese_simplified",
+            "chinese_traditional",
+            "spanish",
+            "german",
+        ];
+        if !valid_languages.contains(&value) {
+            anyhow::bail!("{}", get_message(MessageKey::ErrorInvalidLanguage, lang));
         }
-        "strict_mode" => {
-            let strict: bool = value.parse()?;
-            doc["config"]["strict_mode"] = toml_edit::value(strict);
+    }
+    if path[0] == "commands"
+        && doc
+            .get("commands")
+            .and_then(|commands| commands.get(&path[1]))
+            .is_none()
+    {
+        anyhow::bail!(
+            "{}: {}",
+            get_message(MessageKey::ErrorCommandNotFound, lang),
+            path[1]
+        );
+    }
+
+    // Only check the result against the schema if the file was valid to begin with,
+    // so that `config set` can still be used to repair a broken file
+    let check_schema = is_valid_config(&doc);
+
+    // Try the value as a TOML literal first, then as a plain string
+    let mut candidates = Vec::new();
+    if let Ok(literal) = value.parse::<toml_edit::Value>() {
+        candidates.push(literal);
+    }
+    candidates.push(toml_edit::Value::from(value));
+
+    let invalid_value = || {
+        anyhow::anyhow!(
+            "{}: {} = {}",
+            get_message(MessageKey::ErrorInvalidConfigValue, lang),
+            key,
+            value
+        )
+    };
+    let mut updated = None;
+    for candidate in candidates {
+        let mut attempt = doc.clone();
+        if !write_value(&mut attempt, &path, candidate.clone(), append) {
+            return Err(invalid_value());
         }
-        "parallel" => {
-            let parallel: bool = value.parse()?;
-            doc["config"]["parallel"] = toml_edit::value(parallel);
+        if !check_schema || is_valid_config(&attempt) {
+            updated = Some((attempt, candidate));
+            break;
         }
-        "working_dir" => {
-            doc["config"]["working_dir"] = toml_edit::value(value);
+    }
+    let (doc, written) = updated.ok_or_else(invalid_value)?;
+
+    // Commands set this way pass the same security validation as `cmdrun add`
+    if path.len() == 3 && path[0] == "commands" && path[2] == "cmd" {
+        let validator = CommandValidator::new();
+        for command in string_values(&written) {
+            let validation_result = validator.validate(command);
+            if !validation_result.is_safe() {
+                anyhow::bail!(
+                    "{}: {}",
+                    get_message(MessageKey::ErrorSecurityValidationFailed, lang),
+                    validation_result.reasons()
+                );
+            }
         }
-        _ => anyhow::bail!(
-            "{}: {}",
-            get_message(MessageKey::ErrorUnknownConfigKey, lang),
-            key
-        ),
     }
 
-    fs::write(&config_path, doc.to_string())?;
+    save_config_atomic(&config_path, &doc.to_string())?;
 
     println!(
-        "{} {} {} = {}",
+        "{} {} {} {} {}",
         "✓".green().bold(),
         get_message(MessageKey::ConfigSet, lang),
         key.cyan(),
+        if append { "+=" } else { "=" },
         value.bright_white()
     );
 
     Ok(())
 }
 
+/// Resolve a `config get/set` key into a path in the TOML document
+///
+/// - `timeout` → `config.timeout`
+/// - `config.env.FOO`
+/// - `commands.<name>.<field>` / `commands.<name>.env.FOO` (`<name>` may contain dots)
+fn parse_key(key: &str, lang: Language) -> Result<Vec<String>> {
+    let unknown = || {
+        anyhow::anyhow!(
+            "{}: {}",
+            get_message(MessageKey::ErrorUnknownConfigKey, lang),
+            key
+        )
+    };
+
+    let segments: Vec<&str> = key.split('.').collect();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return Err(unknown());
+    }
+
+    let (section, fields, known): (Vec<&str>, &[&str], &[&str]) = match segments.as_slice() {
+        [field] => (vec!["config"], std::slice::from_ref(field), GLOBAL_KEYS),
+        ["config", fields @ ..] => (vec!["config"], fields, GLOBAL_KEYS),
+        ["commands", rest @ ..] if rest.len() >= 2 => {
+            // The command name is everything between `commands.` and the field
+            let field_len = if rest.len() >= 3 && MAP_KEYS.contains(&rest[rest.len() - 2]) {
+                2
+            } else {
+                1
+            };
+            let (name, fields) = rest.split_at(rest.len() - field_len);
+            let name = name.join(".");
+            let path = ["commands", name.as_str()]
+                .into_iter()
+                .chain(fields.iter().copied())
+                .map(str::to_string)
+                .collect();
+            return match fields {
+                [field] if COMMAND_KEYS.contains(field) => Ok(path),
+                [map, _] if MAP_KEYS.contains(map) => Ok(path),
+                _ => Err(unknown()),
+            };
+        }
+        _ => return Err(unknown()),
+    };
+
+    let valid = match fields {
+        [field] => known.contains(field),
+        [map, _] => MAP_KEYS.contains(map),
+        _ => false,
+    };
+    if !valid {
+        return Err(unknown());
+    }
+
+    Ok(section
+        .into_iter()
+        .chain(fields.iter().copied())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Write `value` at `path`, creating missing tables
+///
+/// Replacing a value keeps its comments and spacing. With `append`, the value
+/// is pushed onto the list at `path` (created if missing). Returns `false` if
+/// the path runs into a non-table value or `append` targets a non-list.
+fn write_value(
+    doc: &mut DocumentMut,
+    path: &[String],
+    mut value: toml_edit::Value,
+    append: bool,
+) -> bool {
+    let Some((last, parents)) = path.split_last() else {
+        return false;
+    };
+
+    let mut table: &mut dyn toml_edit::TableLike = doc.as_table_mut();
+    for segment in parents {
+        if table.get(segment).is_none() {
+            table.insert(segment, toml_edit::Item::Table(toml_edit::Table::new()));
+        }
+        match table
+            .get_mut(segment)
+            .and_then(toml_edit::Item::as_table_like_mut)
+        {
+            Some(child) => table = child,
+            None => return false,
+        }
+    }
+
+    value.decor_mut().clear();
+    match (table.get_mut(last), append) {
+        (Some(item), true) => match item.as_array_mut() {
+            Some(array) => array.push(value),
+            None => return false,
+        },
+        (None, true) => {
+            let array: toml_edit::Array = std::iter::once(value).collect();
+            table.insert(last, toml_edit::value(array));
+        }
+        (Some(toml_edit::Item::Value(old)), false) => {
+            let decor = old.decor().clone();
+            *old = value;
+            *old.decor_mut() = decor;
+        }
+        (_, false) => {
+            table.insert(last, toml_edit::Item::Value(value));
+        }
+    }
+
+    true
+}
+
+/// Every string in `value`, including those nested in arrays and inline tables
+fn string_values(value: &toml_edit::Value) -> Vec<&str> {
+    match value {
+        toml_edit::Value::String(s) => vec![s.value().as_str()],
+        toml_edit::Value::Array(array) => array.iter().flat_map(string_values).collect(),
+        toml_edit::Value::InlineTable(table) => {
+            table.iter().flat_map(|(_, v)| string_values(v)).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn is_valid_config(doc: &DocumentMut) -> bool {
+    toml::from_str::<CommandsConfig>(&doc.to_string()).is_ok()
+}
+
 /// Show all configuration settings
 pub async fn handle_show(config_path: Option<PathBuf>) -> Result<()> {
     let config_loader = if let Some(path) = config_path {
@@ -130,6 +379,8 @@ pub async fn handle_show(config_path: Option<PathBuf>) -> Result<()> {
         crate::config::schema::Language::Japanese => "japanese",
         crate::config::schema::Language::ChineseSimplified => "chinese_simplified",
         crate::config::schema::Language::ChineseTraditional => "chinese_traditional",
+        crate::config::schema::Language::Spanish => "spanish",
+        crate::config::schema::Language::German => "german",
     };
 
     println!(
@@ -152,7 +403,7 @@ pub async fn handle_show(config_path: Option<PathBuf>) -> Result<()> {
     println!(
         "  {} {}",
         format!("{}:", get_message(MessageKey::LabelTimeout, lang)).dimmed(),
-        config.config.timeout
+        format_duration(config.config.timeout)
     );
     println!(
         "  {} {}",
@@ -180,7 +431,13 @@ pub async fn handle_show(config_path: Option<PathBuf>) -> Result<()> {
             )
             .dimmed()
         );
+        let redactor = EnvRedactor::new(&config.config.redact_patterns).unwrap_or_default();
         for (key, value) in &config.config.env {
+            let value = if redactor.is_redacted(key) {
+                REDACTED
+            } else {
+                value
+            };
             println!("    {} = {}", key.cyan(), value);
         }
     }
@@ -219,8 +476,8 @@ fn get_config_path() -> Result<PathBuf> {
         }
     }
 
-    anyhow::bail!(
-        "{}",
-        get_message(MessageKey::ErrorNoConfigFileFound, Language::English)
-    )
+    Err(config_not_found(
+        std::env::current_dir().unwrap_or_default(),
+        get_message(MessageKey::ErrorNoConfigFileFound, Language::English),
+    ))
 }
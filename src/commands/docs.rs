@@ -0,0 +1,225 @@
+//! Docs command - Generate a Markdown reference of all commands
+
+use crate::config::loader::ConfigLoader;
+use crate::config::schema::{Command, CommandSpec, CommandsConfig, Platform};
+use anyhow::{Context, Result};
+use colored::*;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+/// Generate Markdown documentation for the loaded commands
+///
+/// Prints to stdout unless `output` is given.
+pub async fn handle_docs(
+    output: Option<PathBuf>,
+    global_only: bool,
+    config_paths: Vec<PathBuf>,
+) -> Result<()> {
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+    let config = config_loader.load().await?;
+
+    let markdown = generate_markdown(&config);
+
+    if let Some(path) = output {
+        std::fs::write(&path, markdown)
+            .with_context(|| format!("Failed to write to {}", path.display()))?;
+
+        println!(
+            "{} Generated command reference: {}",
+            "✓".green().bold(),
+            path.display().to_string().bright_white()
+        );
+    } else {
+        print!("{}", markdown);
+    }
+
+    Ok(())
+}
+
+/// Render the command reference as Markdown
+///
+/// Commands are listed alphabetically, with a table of contents linking to
+/// one section per command.
+pub fn generate_markdown(config: &CommandsConfig) -> String {
+    let mut names: Vec<&String> = config.commands.keys().collect();
+    names.sort();
+
+    let mut out = String::from("# Commands\n\n");
+
+    if names.is_empty() {
+        out.push_str("No commands defined.\n");
+        return out;
+    }
+
+    out.push_str("## Table of Contents\n\n");
+    for name in &names {
+        let command = &config.commands[*name];
+        let _ = write!(out, "- [`{}`](#{})", name, anchor(name));
+        if !command.description.is_empty() {
+            let _ = write!(out, " - {}", command.description);
+        }
+        out.push('\n');
+    }
+    out.push('\n');
+
+    for name in &names {
+        write_command_section(&mut out, name, &config.commands[*name]);
+    }
+
+    if !config.aliases.is_empty() {
+        let mut aliases: Vec<(&String, &String)> = config.aliases.iter().collect();
+        aliases.sort();
+
+        out.push_str("## Aliases\n\n");
+        out.push_str("| Alias | Command |\n");
+        out.push_str("|-------|---------|\n");
+        for (alias, target) in aliases {
+            let _ = writeln!(out, "| `{}` | [`{}`](#{}) |", alias, target, anchor(target));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Write the section for a single command
+fn write_command_section(out: &mut String, name: &str, command: &Command) {
+    let _ = writeln!(out, "## `{}`\n", name);
+
+    if !command.description.is_empty() {
+        let _ = writeln!(out, "{}\n", command.description);
+    }
+
+    out.push_str("**Command:**\n\n");
+    match &command.cmd {
+        CommandSpec::Single(cmd) => {
+            let _ = writeln!(out, "```sh\n{}\n```\n", cmd);
+        }
+        CommandSpec::Multiple(cmds) => {
+            let _ = writeln!(out, "```sh\n{}\n```\n", cmds.join("\n"));
+        }
+        CommandSpec::Platform(platform_cmds) => {
+            for (label, cmd) in [
+                ("Unix", &platform_cmds.unix),
+                ("Linux", &platform_cmds.linux),
+                ("macOS", &platform_cmds.macos),
+                ("Windows", &platform_cmds.windows),
+            ] {
+                if let Some(cmd) = cmd {
+                    let _ = writeln!(out, "- {}: `{}`", label, cmd);
+                }
+            }
+            out.push('\n');
+        }
+        CommandSpec::Script { file } => {
+            let _ = writeln!(out, "Script file: `{}`\n", file);
+        }
+    }
+
+    if !command.deps.is_empty() {
+        let deps: Vec<String> = command
+            .deps
+            .iter()
+            .map(|dep| format!("[`{}`](#{})", dep, anchor(dep)))
+            .collect();
+        let _ = writeln!(out, "**Dependencies:** {}\n", deps.join(", "));
+    }
+
+    if !command.tags.is_empty() {
+        let tags: Vec<String> = command.tags.iter().map(|t| format!("`{}`", t)).collect();
+        let _ = writeln!(out, "**Tags:** {}\n", tags.join(", "));
+    }
+
+    if !command.platform.is_empty() {
+        let platforms: Vec<&str> = command.platform.iter().map(platform_name).collect();
+        let _ = writeln!(out, "**Platforms:** {}\n", platforms.join(", "));
+    }
+}
+
+/// GitHub-style heading anchor for a command name
+fn anchor(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+pub(crate) fn platform_name(platform: &Platform) -> &'static str {
+    match platform {
+        Platform::Unix => "Unix",
+        Platform::Linux => "Linux",
+        Platform::Macos => "macOS",
+        Platform::Windows => "Windows",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_config(toml: &str) -> CommandsConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_generate_markdown_sections() {
+        let config = parse_config(
+            r#"
+[aliases]
+b = "build"
+
+[commands.test]
+description = "Run tests"
+cmd = "cargo test"
+tags = ["ci"]
+
+[commands.build]
+description = "Build project"
+cmd = ["cargo fmt --check", "cargo build --release"]
+deps = ["test"]
+platform = ["linux", "macos"]
+
+[commands."docs:serve"]
+description = "Serve docs"
+cmd = { unix = "mdbook serve", windows = "mdbook.exe serve" }
+"#,
+        );
+
+        let markdown = generate_markdown(&config);
+
+        // Table of contents, sorted by name
+        assert!(markdown.contains("## Table of Contents"));
+        assert!(markdown.contains("- [`build`](#build) - Build project"));
+        assert!(markdown.contains("- [`docs:serve`](#docsserve) - Serve docs"));
+        let build = markdown.find("## `build`").unwrap();
+        let docs = markdown.find("## `docs:serve`").unwrap();
+        let test = markdown.find("## `test`").unwrap();
+        assert!(build < docs && docs < test);
+
+        // Per-command sections with description and command string
+        assert!(markdown.contains("## `test`\n\nRun tests\n"));
+        assert!(markdown.contains("```sh\ncargo test\n```"));
+        assert!(markdown.contains("```sh\ncargo fmt --check\ncargo build --release\n```"));
+        assert!(markdown.contains("- Unix: `mdbook serve`"));
+        assert!(markdown.contains("**Dependencies:** [`test`](#test)"));
+        assert!(markdown.contains("**Tags:** `ci`"));
+        assert!(markdown.contains("**Platforms:** Linux, macOS"));
+
+        // Aliases table
+        assert!(markdown.contains("| `b` | [`build`](#build) |"));
+    }
+
+    #[test]
+    fn test_generate_markdown_empty() {
+        let config = parse_config("[commands]\n");
+        let markdown = generate_markdown(&config);
+        assert!(markdown.contains("No commands defined."));
+        assert!(!markdown.contains("Table of Contents"));
+    }
+}
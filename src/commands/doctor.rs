@@ -0,0 +1,366 @@
+//! Doctor command - diagnose common environment problems
+
+use crate::config::loader::ConfigLoader;
+use crate::config::schema::CommandsConfig;
+use crate::history::HistoryStorage;
+use crate::platform::shell::detect_shell;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Outcome of a single diagnostic check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// A single diagnostic check result
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Collected results of all checks
+#[derive(Debug, Default)]
+pub struct DoctorReport {
+    pub checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    /// Number of checks with the given status
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.count(CheckStatus::Fail) > 0
+    }
+
+    /// Find a check by name
+    pub fn get(&self, name: &str) -> Option<&Check> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+
+    fn push(&mut self, check: Check) {
+        self.checks.push(check);
+    }
+}
+
+/// Handle doctor command
+///
+/// Prints a checklist and fails if any check failed.
+pub async fn handle_doctor(global_only: bool, config_paths: Vec<PathBuf>) -> Result<()> {
+    let report = run_checks(global_only, config_paths).await;
+    print_report(&report);
+
+    let failed = report.count(CheckStatus::Fail);
+    if failed > 0 {
+        anyhow::bail!("{} doctor check(s) failed", failed);
+    }
+
+    Ok(())
+}
+
+/// Run every diagnostic check
+pub async fn run_checks(global_only: bool, config_paths: Vec<PathBuf>) -> DoctorReport {
+    let mut report = DoctorReport::default();
+
+    let config = check_config(&mut report, global_only, config_paths).await;
+    report.push(check_shell());
+    report.push(match HistoryStorage::default_db_path() {
+        Ok(db_path) => check_history_dir(&db_path),
+        Err(e) => Check::new("History directory", CheckStatus::Fail, format!("{:#}", e)),
+    });
+    if let Some(config) = &config {
+        check_plugins(&mut report, config);
+    }
+    report.push(check_tool(
+        "dot",
+        "Graphviz (dot)",
+        "needed to render `cmdrun graph --format dot` output",
+    ));
+
+    report
+}
+
+/// Config file existence and parseability
+async fn check_config(
+    report: &mut DoctorReport,
+    global_only: bool,
+    config_paths: Vec<PathBuf>,
+) -> Option<CommandsConfig> {
+    const NAME: &str = "Configuration";
+
+    let loader = if !config_paths.is_empty() {
+        match ConfigLoader::with_paths(config_paths) {
+            Ok(loader) => loader,
+            Err(e) => {
+                report.push(Check::new(NAME, CheckStatus::Fail, format!("{:#}", e)));
+                return None;
+            }
+        }
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+
+    match loader.load_with_paths().await {
+        Ok(loaded) => {
+            let files: Vec<String> = loaded
+                .paths()
+                .into_iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            report.push(Check::new(
+                NAME,
+                CheckStatus::Pass,
+                format!(
+                    "{} command(s) loaded from {}",
+                    loaded.config.commands.len(),
+                    files.join(", ")
+                ),
+            ));
+            Some(loaded.config)
+        }
+        Err(e) => {
+            report.push(Check::new(
+                NAME,
+                CheckStatus::Fail,
+                format!("{:#} (run `cmdrun init` to create one)", e),
+            ));
+            None
+        }
+    }
+}
+
+/// Shell detectability via `platform::shell`
+fn check_shell() -> Check {
+    match detect_shell() {
+        Ok(shell) => Check::new(
+            "Shell",
+            CheckStatus::Pass,
+            format!("{} ({})", shell.name, shell.path.display()),
+        ),
+        Err(e) => Check::new("Shell", CheckStatus::Fail, format!("{:#}", e)),
+    }
+}
+
+/// Write access to the directory holding the history database
+fn check_history_dir(db_path: &Path) -> Check {
+    const NAME: &str = "History directory";
+
+    let Some(dir) = db_path.parent() else {
+        return Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("Invalid history path: {}", db_path.display()),
+        );
+    };
+
+    let probe = dir.join(".cmdrun-doctor-probe");
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&probe, b""))
+        .and_then(|_| std::fs::remove_file(&probe));
+
+    match result {
+        Ok(()) => Check::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("{} is writable", dir.display()),
+        ),
+        Err(e) => Check::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} is not writable: {}", dir.display(), e),
+        ),
+    }
+}
+
+/// Loadability of each enabled plugin
+#[cfg(feature = "plugin-system")]
+fn check_plugins(report: &mut DoctorReport, config: &CommandsConfig) {
+    use crate::plugin::PluginManager;
+
+    let mut plugins: Vec<_> = config
+        .plugins
+        .plugins
+        .iter()
+        .filter(|(_, plugin)| plugin.enabled)
+        .collect();
+    plugins.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (name, plugin) in plugins {
+        let check_name = format!("Plugin '{}'", name);
+        let mut manager = PluginManager::new();
+        report.push(match manager.load_plugin(&plugin.path, &plugin.config) {
+            Ok(()) => Check::new(check_name, CheckStatus::Pass, plugin.path.clone()),
+            Err(e) => Check::new(check_name, CheckStatus::Fail, e.to_string()),
+        });
+    }
+}
+
+#[cfg(not(feature = "plugin-system"))]
+fn check_plugins(report: &mut DoctorReport, config: &CommandsConfig) {
+    let enabled = config
+        .plugins
+        .plugins
+        .values()
+        .filter(|p| p.enabled)
+        .count();
+    if enabled > 0 {
+        report.push(Check::new(
+            "Plugins",
+            CheckStatus::Warn,
+            format!(
+                "{} plugin(s) configured but cmdrun was built without the `plugin-system` feature",
+                enabled
+            ),
+        ));
+    }
+}
+
+/// Presence of an optional external tool
+fn check_tool(program: &str, name: &str, purpose: &str) -> Check {
+    match which::which(program) {
+        Ok(path) => Check::new(name, CheckStatus::Pass, path.display().to_string()),
+        Err(_) => Check::new(
+            name,
+            CheckStatus::Warn,
+            format!("`{}` not found in PATH; {}", program, purpose),
+        ),
+    }
+}
+
+fn print_report(report: &DoctorReport) {
+    let width = report
+        .checks
+        .iter()
+        .map(|c| c.name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    println!();
+    println!("{}", "cmdrun doctor".cyan().bold());
+    println!();
+    for check in &report.checks {
+        let (mark, name) = match check.status {
+            CheckStatus::Pass => ("✓".green().bold(), check.name.normal()),
+            CheckStatus::Warn => ("⚠".yellow().bold(), check.name.yellow()),
+            CheckStatus::Fail => ("✗".red().bold(), check.name.red().bold()),
+        };
+        let padding = " ".repeat(width - check.name.chars().count());
+        println!("  {} {}{}  {}", mark, name, padding, check.detail.dimmed());
+    }
+
+    println!();
+    println!(
+        "{} passed, {} warning(s), {} failed",
+        report.count(CheckStatus::Pass).to_string().green(),
+        report.count(CheckStatus::Warn).to_string().yellow(),
+        report.count(CheckStatus::Fail).to_string().red()
+    );
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_history_dir_writable() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("cmdrun").join("history.db");
+
+        let check = check_history_dir(&db_path);
+        assert_eq!(check.status, CheckStatus::Pass, "{}", check.detail);
+        assert!(!temp_dir.path().join("cmdrun/.cmdrun-doctor-probe").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_history_dir_not_writable() {
+        let temp_dir = TempDir::new().unwrap();
+        // A regular file where the directory should be
+        let blocker = temp_dir.path().join("cmdrun");
+        std::fs::write(&blocker, "").unwrap();
+
+        let check = check_history_dir(&blocker.join("history.db"));
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains("not writable"), "{}", check.detail);
+    }
+
+    #[test]
+    fn test_check_tool() {
+        assert_eq!(
+            check_tool("cmdrun-no-such-tool", "Tool", "optional").status,
+            CheckStatus::Warn
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            check_tool("sh", "Tool", "optional").status,
+            CheckStatus::Pass
+        );
+    }
+
+    #[cfg(feature = "plugin-system")]
+    #[test]
+    fn test_check_plugins_reports_unloadable_plugin() {
+        let config: CommandsConfig = toml::from_str(
+            r#"
+[commands]
+
+[plugins.broken]
+path = "/nonexistent/libbroken.so"
+
+[plugins.off]
+path = "/nonexistent/liboff.so"
+enabled = false
+"#,
+        )
+        .unwrap();
+
+        let mut report = DoctorReport::default();
+        check_plugins(&mut report, &config);
+
+        assert_eq!(report.checks.len(), 1);
+        let check = report.get("Plugin 'broken'").unwrap();
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains("not found"), "{}", check.detail);
+    }
+
+    #[tokio::test]
+    async fn test_check_config_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut report = DoctorReport::default();
+
+        let config = check_config(
+            &mut report,
+            false,
+            vec![temp_dir.path().join("missing.toml")],
+        )
+        .await;
+
+        assert!(config.is_none());
+        assert_eq!(
+            report.get("Configuration").unwrap().status,
+            CheckStatus::Fail
+        );
+        assert!(report.has_failures());
+    }
+}
@@ -1,7 +1,9 @@
 //! Edit command - Edit existing command interactively
 
+use crate::config::loader::{save_config_atomic, ConfigLoader};
 use crate::config::schema::{Command, CommandSpec, CommandsConfig};
-use crate::config::{loader::ConfigLoader, Language};
+use crate::config::Language;
+use crate::error::config_not_found;
 use crate::i18n::{get_message, MessageKey};
 use anyhow::{Context, Result};
 use colored::*;
@@ -121,7 +123,21 @@ pub async fn handle_edit(command_id: Option<String>, config_path: Option<PathBuf
     };
 
     // Save to configuration file
-    save_edited_command(&id, updated_command, config_path).await?;
+    save_edited_command(&id, updated_command, config_path.clone()).await?;
+
+    // Show what actually changed in the saved file
+    let saved = load_command(&id, config_path).await?;
+    let diff = command_diff(&id, command, &saved)?;
+    println!();
+    if diff
+        .iter()
+        .all(|line| matches!(line, DiffLine::Unchanged(_)))
+    {
+        println!("{}", get_message(MessageKey::EditNoChanges, lang).dimmed());
+        return Ok(());
+    }
+    print_diff(&diff);
+    println!();
 
     println!(
         "{} {} '{}'",
@@ -133,6 +149,81 @@ pub async fn handle_edit(command_id: Option<String>, config_path: Option<PathBuf
     Ok(())
 }
 
+/// A line of a command diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Unchanged(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Diff the `[commands.<id>]` block of `before` against `after`
+pub fn command_diff(id: &str, before: &Command, after: &Command) -> Result<Vec<DiffLine>> {
+    Ok(diff_lines(
+        &command_block(id, before)?,
+        &command_block(id, after)?,
+    ))
+}
+
+/// Serialize a command the way it appears in commands.toml
+fn command_block(id: &str, command: &Command) -> Result<String> {
+    let mut commands = toml::Table::new();
+    commands.insert(
+        id.to_string(),
+        toml::Value::try_from(command).context("Failed to serialize command")?,
+    );
+    let mut root = toml::Table::new();
+    root.insert("commands".to_string(), toml::Value::Table(commands));
+    toml::to_string_pretty(&root).context("Failed to serialize command")
+}
+
+/// Line diff based on the longest common subsequence
+fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j]: length of the LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Unchanged(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    lines.extend(new[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    lines
+}
+
+fn print_diff(diff: &[DiffLine]) {
+    for line in diff {
+        match line {
+            DiffLine::Unchanged(text) => println!("  {}", text.dimmed()),
+            DiffLine::Removed(text) => println!("{}", format!("- {}", text).red()),
+            DiffLine::Added(text) => println!("{}", format!("+ {}", text).green()),
+        }
+    }
+}
+
 /// Select a command interactively from the list
 fn select_command_interactive(
     config: &CommandsConfig,
@@ -142,12 +233,20 @@ fn select_command_interactive(
         anyhow::bail!("{}", get_message(MessageKey::NoCommandsFound, lang));
     }
 
+    // Most-run commands first
+    let frequency = crate::history::load_frequency_map();
     let mut commands: Vec<_> = config.commands.iter().collect();
-    commands.sort_by_key(|(name, _)| *name);
+    crate::history::sort_by_frequency(&mut commands, &frequency);
 
     let items: Vec<String> = commands
         .iter()
-        .map(|(name, cmd)| format!("{} - {}", name.green().bold(), cmd.description))
+        .map(|(name, cmd)| {
+            let runs = match frequency.get(*name) {
+                Some(count) => format!(" ({} runs)", count).dimmed().to_string(),
+                None => String::new(),
+            };
+            format!("{} - {}{}", name.green().bold(), cmd.description, runs)
+        })
         .collect();
 
     let theme = ColorfulTheme::default();
@@ -186,6 +285,7 @@ fn format_command_spec(spec: &CommandSpec) -> String {
         CommandSpec::Single(cmd) => cmd.clone(),
         CommandSpec::Multiple(cmds) => cmds.join(" && "),
         CommandSpec::Platform(_) => "[Platform-specific]".to_string(),
+        CommandSpec::Script { file } => format!("[Script: {}]", file),
     }
 }
 
@@ -217,15 +317,28 @@ async fn save_edited_command(
     let toml_content =
         toml::to_string_pretty(&config).context("Failed to serialize configuration")?;
 
-    tokio::fs::write(&config_file_path, toml_content)
-        .await
-        .context("Failed to write configuration file")?;
+    save_config_atomic(&config_file_path, &toml_content)?;
 
     info!("Updated command '{}' in {}", id, config_file_path.display());
 
     Ok(())
 }
 
+/// Reload a command from the configuration after it was saved
+async fn load_command(id: &str, config_path: Option<PathBuf>) -> Result<Command> {
+    let config_loader = if let Some(path) = config_path {
+        ConfigLoader::with_path(path)?
+    } else {
+        ConfigLoader::new()
+    };
+    let mut config = config_loader.load().await?;
+
+    config
+        .commands
+        .remove(id)
+        .with_context(|| format!("Command '{}' missing after saving", id))
+}
+
 /// Find the configuration file
 async fn find_config_file() -> Result<PathBuf> {
     const CONFIG_FILENAMES: &[&str] = &["commands.toml", ".cmdrun.toml", "cmdrun.toml"];
@@ -257,10 +370,10 @@ async fn find_config_file() -> Result<PathBuf> {
         }
     }
 
-    anyhow::bail!(
-        "{}",
-        get_message(MessageKey::ErrorNoConfigFileFound, Language::English)
-    )
+    Err(config_not_found(
+        std::env::current_dir().unwrap_or_default(),
+        get_message(MessageKey::ErrorNoConfigFileFound, Language::English),
+    ))
 }
 
 #[cfg(test)]
@@ -276,4 +389,148 @@ mod tests {
             CommandSpec::Multiple(vec!["echo hello".to_string(), "echo world".to_string()]);
         assert_eq!(format_command_spec(&multiple), "echo hello && echo world");
     }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc", "a\nc\nd");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Unchanged("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Unchanged("c".to_string()),
+                DiffLine::Added("d".to_string()),
+            ]
+        );
+
+        assert!(diff_lines("a\nb", "a\nb")
+            .iter()
+            .all(|line| matches!(line, DiffLine::Unchanged(_))));
+    }
+
+    #[tokio::test]
+    async fn test_edit_description_shows_diff() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+        std::fs::write(
+            &config_path,
+            r#"
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#,
+        )
+        .unwrap();
+
+        let before = load_command("build", Some(config_path.clone()))
+            .await
+            .unwrap();
+        let edited = Command {
+            description: "Build in release mode".to_string(),
+            ..before.clone()
+        };
+        save_edited_command("build", edited, Some(config_path.clone()))
+            .await
+            .unwrap();
+
+        let after = load_command("build", Some(config_path)).await.unwrap();
+        let diff = command_diff("build", &before, &after).unwrap();
+
+        let changed: Vec<&DiffLine> = diff
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Unchanged(_)))
+            .collect();
+        assert_eq!(
+            changed,
+            vec![
+                &DiffLine::Removed(r#"description = "Build""#.to_string()),
+                &DiffLine::Added(r#"description = "Build in release mode""#.to_string()),
+            ]
+        );
+        assert!(diff.contains(&DiffLine::Unchanged("[commands.build]".to_string())));
+
+        let unchanged = command_diff("build", &after, &after).unwrap();
+        assert!(unchanged
+            .iter()
+            .all(|line| matches!(line, DiffLine::Unchanged(_))));
+    }
+
+    #[tokio::test]
+    async fn test_edit_does_not_inline_env_file_values() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+        std::fs::write(temp_dir.path().join(".env"), "API_TOKEN=secret-value\n").unwrap();
+        std::fs::write(
+            temp_dir.path().join("deploy.env"),
+            "DEPLOY_KEY=deploy-secret\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &config_path,
+            r#"
+[config]
+env_file = ".env"
+
+[commands.deploy]
+description = "Deploy"
+cmd = "echo deploy"
+env_file = "deploy.env"
+"#,
+        )
+        .unwrap();
+
+        let before = load_command("deploy", Some(config_path.clone()))
+            .await
+            .unwrap();
+        let edited = Command {
+            description: "Deploy to production".to_string(),
+            ..before
+        };
+        save_edited_command("deploy", edited, Some(config_path.clone()))
+            .await
+            .unwrap();
+
+        let saved = std::fs::read_to_string(&config_path).unwrap();
+        assert!(saved.contains("Deploy to production"), "{}", saved);
+        assert!(saved.contains(r#"env_file = ".env""#), "{}", saved);
+        assert!(saved.contains(r#"env_file = "deploy.env""#), "{}", saved);
+        assert!(!saved.contains("secret-value"), "{}", saved);
+        assert!(!saved.contains("deploy-secret"), "{}", saved);
+        assert!(!saved.contains("source_dir"), "{}", saved);
+    }
+
+    #[tokio::test]
+    async fn test_edit_keeps_script_path_as_written() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+        std::fs::write(
+            &config_path,
+            r#"
+[commands.deploy]
+description = "Deploy"
+cmd = { file = "scripts/deploy.sh" }
+"#,
+        )
+        .unwrap();
+
+        let before = load_command("deploy", Some(config_path.clone()))
+            .await
+            .unwrap();
+        let edited = Command {
+            description: "Deploy to production".to_string(),
+            ..before
+        };
+        save_edited_command("deploy", edited, Some(config_path.clone()))
+            .await
+            .unwrap();
+
+        let saved = std::fs::read_to_string(&config_path).unwrap();
+        assert!(saved.contains("Deploy to production"), "{}", saved);
+        assert!(saved.contains(r#"file = "scripts/deploy.sh""#), "{}", saved);
+        assert!(
+            !saved.contains(&*temp_dir.path().to_string_lossy()),
+            "{}",
+            saved
+        );
+    }
 }
@@ -2,11 +2,15 @@
 //!
 //! 環境切り替えと環境変数管理コマンド
 
-use crate::config::environment::EnvironmentManager;
+use crate::config::environment::{EnvironmentConfig, EnvironmentManager};
+use crate::config::loader::ConfigLoader;
 use crate::config::Language;
 use crate::i18n::{get_message, MessageKey};
+use crate::security::{EnvRedactor, REDACTED};
+use ahash::AHashMap;
 use anyhow::{Context, Result};
 use colored::Colorize;
+use std::path::PathBuf;
 
 /// `cmdrun env use <env>` - 環境切り替え
 pub async fn handle_use(env_name: String) -> Result<()> {
@@ -168,14 +172,31 @@ pub async fn handle_info(env_name: Option<String>, language: Language) -> Result
     } else if let Some(env) = config.environments.get(&target_env) {
         println!("  {}: {}", "Description".dimmed(), env.description);
 
-        if !env.variables.is_empty() {
+        let chain = config.inheritance_chain(&target_env)?;
+        if chain.len() > 1 {
+            println!("  {}: {}", "Extends".dimmed(), chain[1..].join(" -> "));
+        }
+
+        let variables = config.resolve_variables(&target_env)?;
+        if !variables.is_empty() {
             println!();
             println!(
                 "  {}:",
                 get_message(MessageKey::EnvEnvironmentVariables, language).bold()
             );
-            for (key, value) in &env.variables {
-                println!("    {} = {}", key.cyan(), value.yellow());
+            let mut keys: Vec<&String> = variables.keys().collect();
+            keys.sort();
+            for key in keys {
+                if env.variables.contains_key(key) {
+                    println!("    {} = {}", key.cyan(), variables[key].yellow());
+                } else {
+                    println!(
+                        "    {} = {} {}",
+                        key.cyan(),
+                        variables[key].yellow(),
+                        "(inherited)".dimmed()
+                    );
+                }
             }
         }
 
@@ -232,6 +253,146 @@ pub async fn handle_info(env_name: Option<String>, language: Language) -> Result
     Ok(())
 }
 
+/// 環境変数の差分（`a` → `b`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariableChange {
+    /// `b` にのみ存在
+    Added { key: String, value: String },
+    /// `a` にのみ存在
+    Removed { key: String, value: String },
+    /// 両方に存在し値が異なる
+    Changed {
+        key: String,
+        old: String,
+        new: String,
+    },
+}
+
+/// 2つの環境変数マップの差分をキー順で返す
+pub fn diff_variables(
+    a: &AHashMap<String, String>,
+    b: &AHashMap<String, String>,
+) -> Vec<VariableChange> {
+    let mut keys: Vec<&String> = a
+        .keys()
+        .chain(b.keys().filter(|k| !a.contains_key(*k)))
+        .collect();
+    keys.sort();
+
+    keys.into_iter()
+        .filter_map(|key| match (a.get(key), b.get(key)) {
+            (None, Some(value)) => Some(VariableChange::Added {
+                key: key.clone(),
+                value: value.clone(),
+            }),
+            (Some(value), None) => Some(VariableChange::Removed {
+                key: key.clone(),
+                value: value.clone(),
+            }),
+            (Some(old), Some(new)) if old != new => Some(VariableChange::Changed {
+                key: key.clone(),
+                old: old.clone(),
+                new: new.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 2つの環境の環境変数を比較
+pub async fn diff_environments(
+    manager: &EnvironmentManager,
+    a: &str,
+    b: &str,
+    language: Language,
+) -> Result<Vec<VariableChange>> {
+    let config = manager.load_environment_config().await?;
+    let a_vars = environment_variables(&config, a, language)?;
+    let b_vars = environment_variables(&config, b, language)?;
+    Ok(diff_variables(&a_vars, &b_vars))
+}
+
+/// 環境の環境変数を継承を解決して取得（`default` は変数なし）
+fn environment_variables(
+    config: &EnvironmentConfig,
+    name: &str,
+    language: Language,
+) -> Result<AHashMap<String, String>> {
+    if name != "default" && !config.environments.contains_key(name) {
+        anyhow::bail!(
+            "{}: '{}'",
+            get_message(MessageKey::EnvErrorNotFound, language),
+            name
+        );
+    }
+    config.resolve_variables(name)
+}
+
+/// 差分を1行に整形（機密キーの値は伏せ字）
+fn format_change(change: &VariableChange, redactor: &EnvRedactor) -> String {
+    let shown = |key: &str, value: &str| {
+        if redactor.is_redacted(key) {
+            REDACTED.to_string()
+        } else {
+            value.to_string()
+        }
+    };
+
+    match change {
+        VariableChange::Added { key, value } => format!("+ {}={}", key, shown(key, value)),
+        VariableChange::Removed { key, value } => format!("- {}={}", key, shown(key, value)),
+        VariableChange::Changed { key, old, new } => {
+            format!("~ {}: {} → {}", key, shown(key, old), shown(key, new))
+        }
+    }
+}
+
+/// `cmdrun env diff <a> <b>` - 環境間の環境変数の差分表示
+///
+/// `[config] redact_patterns` に一致するキーの値も伏せ字にする
+pub async fn handle_diff(
+    a: String,
+    b: String,
+    language: Language,
+    global_only: bool,
+    config_paths: Vec<PathBuf>,
+) -> Result<()> {
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+    let config = config_loader.load().await.unwrap_or_default();
+    let redactor = EnvRedactor::new(&config.config.redact_patterns).unwrap_or_default();
+
+    let manager = EnvironmentManager::default_instance()
+        .context("Failed to initialize environment manager")?;
+
+    let changes = diff_environments(&manager, &a, &b, language).await?;
+
+    println!("{}", format!("Environment diff: {} → {}", a, b).bold());
+    println!();
+
+    if changes.is_empty() {
+        println!("  {}", "No differences".dimmed());
+        return Ok(());
+    }
+
+    for change in &changes {
+        let line = format_change(change, &redactor);
+        let line = match change {
+            VariableChange::Added { .. } => line.green(),
+            VariableChange::Removed { .. } => line.red(),
+            VariableChange::Changed { .. } => line.yellow(),
+        };
+        println!("  {}", line);
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,6 +411,109 @@ mod tests {
         (temp_dir, manager)
     }
 
+    #[tokio::test]
+    async fn test_diff_environments() {
+        let (_temp_dir, manager) = setup_test_env().await;
+
+        for name in ["staging", "production"] {
+            manager
+                .create_environment(name.to_string(), name.to_string())
+                .await
+                .unwrap();
+        }
+        for (key, value) in [
+            ("API_URL", "https://staging"),
+            ("DEBUG", "1"),
+            ("REGION", "eu"),
+        ] {
+            manager
+                .set_variable("staging", key.to_string(), value.to_string())
+                .await
+                .unwrap();
+        }
+        for (key, value) in [
+            ("API_URL", "https://prod"),
+            ("REGION", "eu"),
+            ("REPLICAS", "3"),
+        ] {
+            manager
+                .set_variable("production", key.to_string(), value.to_string())
+                .await
+                .unwrap();
+        }
+
+        let changes = diff_environments(&manager, "staging", "production", Language::English)
+            .await
+            .unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                VariableChange::Changed {
+                    key: "API_URL".to_string(),
+                    old: "https://staging".to_string(),
+                    new: "https://prod".to_string(),
+                },
+                VariableChange::Removed {
+                    key: "DEBUG".to_string(),
+                    value: "1".to_string(),
+                },
+                VariableChange::Added {
+                    key: "REPLICAS".to_string(),
+                    value: "3".to_string(),
+                },
+            ]
+        );
+
+        // default has no variables, so everything in staging is an addition
+        let changes = diff_environments(&manager, "default", "staging", Language::English)
+            .await
+            .unwrap();
+        assert_eq!(changes.len(), 3);
+        assert!(changes
+            .iter()
+            .all(|c| matches!(c, VariableChange::Added { .. })));
+    }
+
+    #[test]
+    fn test_format_change_redacts_secrets() {
+        let redactor = EnvRedactor::default();
+
+        let changed = VariableChange::Changed {
+            key: "API_TOKEN".to_string(),
+            old: "old-secret".to_string(),
+            new: "new-secret".to_string(),
+        };
+        assert_eq!(
+            format_change(&changed, &redactor),
+            "~ API_TOKEN: ***REDACTED*** → ***REDACTED***"
+        );
+
+        let added = VariableChange::Added {
+            key: "REGION".to_string(),
+            value: "eu".to_string(),
+        };
+        assert_eq!(format_change(&added, &redactor), "+ REGION=eu");
+
+        let removed = VariableChange::Removed {
+            key: "DB_PASSWORD".to_string(),
+            value: "hunter2".to_string(),
+        };
+        assert_eq!(
+            format_change(&removed, &redactor),
+            "- DB_PASSWORD=***REDACTED***"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_environments_not_found() {
+        let (_temp_dir, manager) = setup_test_env().await;
+
+        let err = diff_environments(&manager, "default", "missing", Language::English)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Environment not found: 'missing'");
+    }
+
     #[tokio::test]
     async fn test_create_environment() {
         let (_temp_dir, manager) = setup_test_env().await;
@@ -0,0 +1,385 @@
+//! Export command - Generate a Makefile or justfile from the loaded commands
+
+use crate::cli::ExportFileFormat;
+use crate::commands::docs::platform_name;
+use crate::config::loader::ConfigLoader;
+use crate::config::schema::{Command, CommandSpec, CommandsConfig, Platform, PlatformCommands};
+use anyhow::{Context, Result};
+use colored::*;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+const HEADER: &str = "# Generated by `cmdrun export` from commands.toml; edit that file instead.\n";
+
+/// Generate a Makefile or justfile for the loaded commands
+///
+/// Prints to stdout unless `output` is given.
+pub async fn handle_export(
+    format: ExportFileFormat,
+    output: Option<PathBuf>,
+    global_only: bool,
+    config_paths: Vec<PathBuf>,
+) -> Result<()> {
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+    let config = config_loader.load().await?;
+
+    let content = match format {
+        ExportFileFormat::Makefile => generate_makefile(&config, Platform::current()),
+        ExportFileFormat::Justfile => generate_justfile(&config),
+    };
+
+    if let Some(path) = output {
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write to {}", path.display()))?;
+
+        println!(
+            "{} Exported {} command(s): {}",
+            "✓".green().bold(),
+            config.commands.len(),
+            path.display().to_string().bright_white()
+        );
+    } else {
+        print!("{}", content);
+    }
+
+    Ok(())
+}
+
+/// Render the commands as a Makefile
+///
+/// Every command becomes a phony target whose prerequisites are its `deps`.
+/// Platform-specific commands use the variant for `platform`, with a note
+/// listing every platform the command defines.
+pub fn generate_makefile(config: &CommandsConfig, platform: Platform) -> String {
+    let names = sorted_names(config);
+    let mut out = String::from(HEADER);
+
+    if names.is_empty() {
+        return out;
+    }
+
+    let targets: Vec<String> = names.iter().map(|n| target_name(n)).collect();
+    let _ = writeln!(out, "\n.PHONY: {}", targets.join(" "));
+
+    for name in names {
+        let command = &config.commands[name];
+        out.push('\n');
+        write_description(&mut out, command);
+
+        if let CommandSpec::Platform(platform_cmds) = &command.cmd {
+            let _ = writeln!(
+                out,
+                "# Platform-specific command; generated for {} ({})",
+                platform_name(&platform),
+                platform_variants(platform_cmds)
+            );
+        }
+
+        let _ = writeln!(out, "{}:{}", target_name(name), prerequisites(command));
+        let steps = match &command.cmd {
+            CommandSpec::Script { file } => Some(vec![script_step(config, command, file)]),
+            spec => spec.resolve_for_platform(&platform),
+        };
+        match steps {
+            Some(steps) => {
+                for line in steps.iter().flat_map(|step| recipe_lines(step)) {
+                    let _ = writeln!(out, "\t{}", line.replace('$', "$$"));
+                }
+            }
+            None => {
+                let _ = writeln!(
+                    out,
+                    "\t@echo \"{}: not available on {}\" >&2; exit 1",
+                    name,
+                    platform_name(&platform)
+                );
+            }
+        }
+    }
+
+    out
+}
+
+/// Render the commands as a justfile
+///
+/// Platform-specific commands become one recipe per variant, selected with
+/// just's `[unix]`/`[linux]`/`[macos]`/`[windows]` attributes.
+pub fn generate_justfile(config: &CommandsConfig) -> String {
+    let names = sorted_names(config);
+    let mut out = String::from(HEADER);
+
+    for name in names {
+        let command = &config.commands[name];
+        let header = format!("{}:{}", target_name(name), prerequisites(command));
+
+        match &command.cmd {
+            CommandSpec::Single(cmd) => {
+                out.push('\n');
+                write_description(&mut out, command);
+                write_just_recipe(&mut out, &header, std::slice::from_ref(cmd));
+            }
+            CommandSpec::Multiple(cmds) => {
+                out.push('\n');
+                write_description(&mut out, command);
+                write_just_recipe(&mut out, &header, cmds);
+            }
+            CommandSpec::Script { file } => {
+                out.push('\n');
+                write_description(&mut out, command);
+                write_just_recipe(&mut out, &header, &[script_step(config, command, file)]);
+            }
+            CommandSpec::Platform(platform_cmds) => {
+                for (attribute, cmd) in [
+                    ("linux", &platform_cmds.linux),
+                    ("macos", &platform_cmds.macos),
+                    ("windows", &platform_cmds.windows),
+                ] {
+                    if let Some(cmd) = cmd {
+                        out.push('\n');
+                        write_description(&mut out, command);
+                        let _ = writeln!(out, "[{}]", attribute);
+                        write_just_recipe(&mut out, &header, std::slice::from_ref(cmd));
+                    }
+                }
+                // `unix` covers whichever of linux/macos has no variant of its own
+                if let Some(cmd) = &platform_cmds.unix {
+                    let uncovered: Vec<&str> = [
+                        ("linux", &platform_cmds.linux),
+                        ("macos", &platform_cmds.macos),
+                    ]
+                    .into_iter()
+                    .filter(|(_, variant)| variant.is_none())
+                    .map(|(attribute, _)| attribute)
+                    .collect();
+                    if !uncovered.is_empty() {
+                        out.push('\n');
+                        write_description(&mut out, command);
+                        let attribute = if uncovered.len() == 2 {
+                            "unix"
+                        } else {
+                            uncovered[0]
+                        };
+                        let _ = writeln!(out, "[{}]", attribute);
+                        write_just_recipe(&mut out, &header, std::slice::from_ref(cmd));
+                    }
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn write_just_recipe(out: &mut String, header: &str, steps: &[String]) {
+    let _ = writeln!(out, "{}", header);
+    for line in steps.iter().flat_map(|step| recipe_lines(step)) {
+        // `{{` starts an interpolation in just
+        let _ = writeln!(out, "    {}", line.replace("{{", "{{{{"));
+    }
+}
+
+/// Recipe lines for a (possibly multi-line) step
+///
+/// Each line becomes its own indented recipe line; a trailing `\` still
+/// continues onto the next one. Blank lines would end the recipe and are dropped.
+fn recipe_lines(step: &str) -> impl Iterator<Item = &str> {
+    step.lines().map(str::trim).filter(|line| !line.is_empty())
+}
+
+/// Step running a script file with the shell cmdrun would use
+fn script_step(config: &CommandsConfig, command: &Command, file: &str) -> String {
+    let shell = command.shell.as_deref().unwrap_or(&config.config.shell);
+    format!("{} {}", shell, shell_words::quote(file))
+}
+
+fn write_description(out: &mut String, command: &Command) {
+    for line in command.description.lines() {
+        let _ = writeln!(out, "# {}", line);
+    }
+}
+
+fn sorted_names(config: &CommandsConfig) -> Vec<&String> {
+    let mut names: Vec<&String> = config.commands.keys().collect();
+    names.sort();
+    names
+}
+
+/// ` dep1 dep2` (empty when the command has no dependencies)
+fn prerequisites(command: &Command) -> String {
+    command
+        .deps
+        .iter()
+        .map(|dep| format!(" {}", target_name(dep)))
+        .collect()
+}
+
+/// Target/recipe name; `:` and other characters Make and just reject become `-`
+fn target_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Comma-separated list of the platforms a platform-specific command defines
+fn platform_variants(platform_cmds: &PlatformCommands) -> String {
+    [
+        (Platform::Unix, &platform_cmds.unix),
+        (Platform::Linux, &platform_cmds.linux),
+        (Platform::Macos, &platform_cmds.macos),
+        (Platform::Windows, &platform_cmds.windows),
+    ]
+    .iter()
+    .filter(|(_, cmd)| cmd.is_some())
+    .map(|(platform, _)| platform_name(platform))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_config(toml: &str) -> CommandsConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    const FIXTURE: &str = r#"
+[commands.build]
+description = "Build project"
+cmd = "cargo build --release"
+deps = ["lint"]
+
+[commands.lint]
+description = "Lint"
+cmd = ["cargo fmt --check", "cargo clippy -- -D warnings"]
+
+[commands.test]
+description = "Run tests"
+cmd = "cargo test ${1:-}"
+deps = ["build", "lint"]
+"#;
+
+    #[test]
+    fn test_generate_makefile_snapshot() {
+        let makefile = generate_makefile(&parse_config(FIXTURE), Platform::Linux);
+        assert_eq!(
+            makefile,
+            "# Generated by `cmdrun export` from commands.toml; edit that file instead.\n\
+             \n\
+             .PHONY: build lint test\n\
+             \n\
+             # Build project\n\
+             build: lint\n\
+             \tcargo build --release\n\
+             \n\
+             # Lint\n\
+             lint:\n\
+             \tcargo fmt --check\n\
+             \tcargo clippy -- -D warnings\n\
+             \n\
+             # Run tests\n\
+             test: build lint\n\
+             \tcargo test $${1:-}\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_justfile_snapshot() {
+        let justfile = generate_justfile(&parse_config(FIXTURE));
+        assert_eq!(
+            justfile,
+            "# Generated by `cmdrun export` from commands.toml; edit that file instead.\n\
+             \n\
+             # Build project\n\
+             build: lint\n    \
+             cargo build --release\n\
+             \n\
+             # Lint\n\
+             lint:\n    \
+             cargo fmt --check\n    \
+             cargo clippy -- -D warnings\n\
+             \n\
+             # Run tests\n\
+             test: build lint\n    \
+             cargo test ${1:-}\n"
+        );
+    }
+
+    const MULTILINE_FIXTURE: &str = r#"
+[commands.ci]
+description = "CI"
+cmd = """
+cargo fmt --check
+
+  cargo build \\
+    --release
+"""
+"#;
+
+    #[test]
+    fn test_multiline_step_snapshot() {
+        let config = parse_config(MULTILINE_FIXTURE);
+        assert_eq!(
+            generate_makefile(&config, Platform::Linux),
+            "# Generated by `cmdrun export` from commands.toml; edit that file instead.\n\
+             \n\
+             .PHONY: ci\n\
+             \n\
+             # CI\n\
+             ci:\n\
+             \tcargo fmt --check\n\
+             \tcargo build \\\n\
+             \t--release\n"
+        );
+        assert_eq!(
+            generate_justfile(&config),
+            "# Generated by `cmdrun export` from commands.toml; edit that file instead.\n\
+             \n\
+             # CI\n\
+             ci:\n    \
+             cargo fmt --check\n    \
+             cargo build \\\n    \
+             --release\n"
+        );
+    }
+
+    #[test]
+    fn test_platform_specific_commands() {
+        let config = parse_config(
+            r#"
+[commands."docs:serve"]
+description = "Serve docs"
+cmd = { unix = "mdbook serve", windows = "mdbook.exe serve" }
+
+[commands.open]
+description = "Open"
+cmd = { macos = "open ." }
+"#,
+        );
+
+        let makefile = generate_makefile(&config, Platform::Linux);
+        assert!(makefile.contains(
+            "# Platform-specific command; generated for Linux (Unix, Windows)\n\
+             docs-serve:\n\
+             \tmdbook serve\n"
+        ));
+        assert!(makefile.contains("open:\n\t@echo \"open: not available on Linux\" >&2; exit 1\n"));
+
+        let justfile = generate_justfile(&config);
+        assert!(justfile.contains("[windows]\ndocs-serve:\n    mdbook.exe serve\n"));
+        assert!(justfile.contains("[unix]\ndocs-serve:\n    mdbook serve\n"));
+        assert!(justfile.contains("[macos]\nopen:\n    open .\n"));
+    }
+}
@@ -1,20 +1,34 @@
 //! History command implementation
 //!
-//! Provides command history display, search, clear, and export functionality.
+//! Provides command history display, search, clear, prune, and export functionality.
 
+use crate::cli::StatsSort;
 use crate::config::Language;
-use crate::history::{HistoryEntry, HistoryStorage};
+use crate::history::storage::format_duration_ms;
+use crate::history::{
+    parse_time_bound, CommandBreakdown, HistoryEntry, HistoryFilter, HistoryStats, HistoryStorage,
+    Trend,
+};
 use crate::i18n::{get_message, MessageKey};
+use crate::output::status;
+use crate::security::EnvRedactor;
+use ahash::AHashMap;
 use anyhow::{Context, Result};
 use colored::*;
 use std::path::PathBuf;
 
 /// Handle the history list command
+///
+/// `verbose` also shows the host and cmdrun version each entry was recorded with.
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_history(
     limit: Option<usize>,
     offset: Option<usize>,
     show_failed_only: bool,
+    since: Option<&str>,
+    until: Option<&str>,
     show_stats: bool,
+    verbose: bool,
     language: Language,
 ) -> Result<()> {
     let storage = HistoryStorage::new().context("Failed to open history database")?;
@@ -24,18 +38,25 @@ pub async fn handle_history(
         return Ok(());
     }
 
-    let entries = if show_failed_only {
-        // Get all entries and filter failed ones
-        storage
-            .list(None, None)?
-            .into_iter()
-            .filter(|e| !e.success)
-            .take(limit.unwrap_or(50))
-            .collect::<Vec<_>>()
-    } else {
-        storage.list(limit, offset)?
+    // Relative durations are counted back from the same instant for both bounds
+    let now = chrono::Utc::now();
+    let parse_bound = |value: Option<&str>, flag: &str| {
+        value
+            .map(|v| {
+                parse_time_bound(v, now)
+                    .map(|time| time.timestamp_millis())
+                    .map_err(|e| anyhow::anyhow!("Invalid {} value: {}", flag, e))
+            })
+            .transpose()
+    };
+    let filter = HistoryFilter {
+        since: parse_bound(since, "--since")?,
+        until: parse_bound(until, "--until")?,
+        failed_only: show_failed_only,
     };
 
+    let entries = storage.list_filtered(&filter, limit, offset)?;
+
     if entries.is_empty() {
         println!(
             "{}",
@@ -49,7 +70,7 @@ pub async fn handle_history(
 
     let count = entries.len();
     for entry in entries {
-        display_entry(&entry, language);
+        display_entry(&entry, verbose, language);
     }
 
     println!();
@@ -92,7 +113,7 @@ pub async fn handle_history_search(
     println!();
 
     for entry in results {
-        display_entry(&entry, language);
+        display_entry(&entry, false, language);
     }
 
     Ok(())
@@ -123,6 +144,25 @@ pub async fn handle_history_clear(force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Delete entries beyond the newest `max_entries` and entries older than `older_than`
+///
+/// `older_than` accepts the same values as `history list --since`.
+pub async fn handle_history_prune(
+    max_entries: Option<usize>,
+    older_than: Option<&str>,
+) -> Result<()> {
+    let cutoff = older_than
+        .map(|value| parse_time_bound(value, chrono::Utc::now()))
+        .transpose()?;
+
+    let mut storage = HistoryStorage::new().context("Failed to open history database")?;
+    let count = storage.prune(max_entries, cutoff)?;
+
+    println!("{} Pruned {} history entries", "✓".green().bold(), count);
+
+    Ok(())
+}
+
 /// Handle the history export command
 pub async fn handle_history_export(
     format: ExportFormat,
@@ -134,6 +174,8 @@ pub async fn handle_history_export(
     let data = match format {
         ExportFormat::Json => storage.export_json(limit)?,
         ExportFormat::Csv => storage.export_csv(limit)?,
+        ExportFormat::Yaml => storage.export_yaml(limit)?,
+        ExportFormat::Toml => storage.export_toml(limit)?,
     };
 
     if let Some(path) = output {
@@ -166,18 +208,18 @@ pub async fn handle_retry(id: Option<i64>) -> Result<()> {
             .ok_or_else(|| anyhow::anyhow!("No failed commands in history"))?
     };
 
-    println!(
+    status::print(&format!(
         "{} Retrying command: {}",
         "🔄".bright_white(),
         entry.command.bright_white()
-    );
-    println!("  {} {}", "ID:".dimmed(), entry.id);
-    println!(
+    ));
+    status::print(&format!("  {} {}", "ID:".dimmed(), entry.id));
+    status::print(&format!(
         "  {} {}",
         "Original run:".dimmed(),
         entry.start_time_as_datetime()
-    );
-    println!();
+    ));
+    status::print("");
 
     // Import the necessary modules for re-execution
     use crate::command::executor::{CommandExecutor, ExecutionContext};
@@ -204,7 +246,7 @@ pub async fn handle_retry(id: Option<i64>) -> Result<()> {
     };
 
     // Create execution context
-    let mut env = config.config.env.clone();
+    let mut env = config.config.resolved_env()?;
     for (idx, arg) in args.iter().enumerate() {
         env.insert((idx + 1).to_string(), arg.clone());
     }
@@ -222,17 +264,24 @@ pub async fn handle_retry(id: Option<i64>) -> Result<()> {
         language: config.config.language,
         allow_command_chaining: config.config.allow_command_chaining,
         allow_subshells: config.config.allow_subshells,
+        command_allowlist: config.config.command_allowlist.clone(),
+        allow_unsafe: false,
+        merge_stderr: config.config.merge_stderr,
+        kill_grace_ms: config.config.kill_grace_ms,
+        max_parallel: config.config.max_parallel,
     };
 
-    let executor = CommandExecutor::new(ctx);
+    let executor = CommandExecutor::new(ctx)
+        .with_commands(config.commands.clone())
+        .with_args(args);
     let result = executor.execute(command).await?;
 
     if result.success {
-        println!(
+        status::print(&format!(
             "{} Completed in {:.2}s",
             "✓".green().bold(),
             result.duration.as_secs_f64()
-        );
+        ));
     } else {
         anyhow::bail!(
             "{} {}",
@@ -249,20 +298,20 @@ pub async fn handle_retry(id: Option<i64>) -> Result<()> {
 pub enum ExportFormat {
     Json,
     Csv,
+    Yaml,
+    Toml,
 }
 
 /// Display a single history entry
-fn display_entry(entry: &HistoryEntry, language: Language) {
-    let status_icon = if entry.success {
-        "✓".green()
-    } else {
-        "✗".red()
-    };
-
-    let status_text = if entry.success {
-        "success".green()
+fn display_entry(entry: &HistoryEntry, verbose: bool, language: Language) {
+    let (status_icon, status_text) = if entry.skipped {
+        ("⏭".yellow(), "skipped".yellow())
+    } else if entry.success {
+        ("✓".green(), "success".green())
+    } else if entry.is_interrupted() {
+        ("⏹".yellow(), "interrupted".yellow())
     } else {
-        "failed".red()
+        ("✗".red(), "failed".red())
     };
 
     println!(
@@ -300,13 +349,157 @@ fn display_entry(entry: &HistoryEntry, language: Language) {
         );
     }
 
+    if let Some(env) = entry.environment.as_deref().and_then(format_environment) {
+        println!("  {} {}", "Env:".dimmed(), env);
+    }
+
+    if entry.confirm_skipped {
+        println!("  {} skipped (--yes)", "Confirm:".dimmed());
+    }
+
+    // Entries recorded by earlier versions have neither field
+    if verbose {
+        if !entry.hostname.is_empty() {
+            println!("  {} {}", "Host:".dimmed(), entry.hostname);
+        }
+        if !entry.cmdrun_version.is_empty() {
+            println!("  {} {}", "cmdrun:".dimmed(), entry.cmdrun_version);
+        }
+    }
+
     println!();
 }
 
+/// Format a recorded environment for display, redacting sensitive values
+///
+/// Values are redacted at record time; this re-applies the default patterns so
+/// entries recorded without filtering never print secrets either.
+fn format_environment(environment: &str) -> Option<String> {
+    let env: AHashMap<String, String> = serde_json::from_str(environment).ok()?;
+    if env.is_empty() {
+        return None;
+    }
+
+    let redacted = EnvRedactor::default().redact(&env);
+    Some(
+        redacted
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// Handle the history stats command
+///
+/// `command` restricts the statistics to a single command, `last` sets how
+/// many recent runs the duration trend is computed from.
+pub async fn handle_history_stats(
+    command: Option<String>,
+    last: usize,
+    json: bool,
+    language: Language,
+) -> Result<()> {
+    let storage = HistoryStorage::new().context("Failed to open history database")?;
+    let stats = storage.get_stats_filtered(command.as_deref(), last)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print_stats(&stats, language);
+    }
+
+    Ok(())
+}
+
+/// Handle `history stats --by-command`
+///
+/// Prints one row per command sorted by `sort`, limited to the first `top`
+/// rows when given.
+pub async fn handle_history_breakdown(
+    sort: StatsSort,
+    top: Option<usize>,
+    json: bool,
+    language: Language,
+) -> Result<()> {
+    let storage = HistoryStorage::new().context("Failed to open history database")?;
+    let mut rows = storage.get_command_breakdown()?;
+    sort_breakdown(&mut rows, sort);
+    if let Some(top) = top {
+        rows.truncate(top);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else if rows.is_empty() {
+        println!(
+            "{}",
+            get_message(MessageKey::HistoryNoEntriesFound, language).yellow()
+        );
+    } else {
+        print_breakdown(&rows);
+    }
+
+    Ok(())
+}
+
+/// Sort per-command rows, largest first (ties by command name)
+pub fn sort_breakdown(rows: &mut [CommandBreakdown], sort: StatsSort) {
+    rows.sort_by(|a, b| {
+        let order = match sort {
+            StatsSort::Runs => b.runs.cmp(&a.runs),
+            StatsSort::Time => b.total_duration_ms.total_cmp(&a.total_duration_ms),
+            StatsSort::Failures => b.failed.cmp(&a.failed),
+        };
+        order.then_with(|| a.command.cmp(&b.command))
+    });
+}
+
+fn print_breakdown(rows: &[CommandBreakdown]) {
+    let width = rows
+        .iter()
+        .map(|row| row.command.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Command".len());
+
+    println!(
+        "{}",
+        format!(
+            "{:<width$}  {:>6}  {:>7}  {:>8}  {:>9}  {:>8}",
+            "Command", "Runs", "Success", "Failures", "Avg", "Total"
+        )
+        .bright_white()
+        .bold()
+    );
+    for row in rows {
+        let rate = format!("{:>7}", format!("{:.1}%", row.success_rate()));
+        let rate = if row.failed == 0 {
+            rate.green()
+        } else {
+            rate.yellow()
+        };
+        println!(
+            "{}  {:>6}  {}  {:>8}  {:>9}  {:>8}",
+            format!("{:<width$}", row.command).cyan(),
+            row.runs,
+            rate,
+            row.failed,
+            format_duration_ms(row.avg_duration_ms),
+            format_duration_ms(Some(row.total_duration_ms))
+        );
+    }
+}
+
 /// Display history statistics
 fn display_stats(storage: &HistoryStorage, language: Language) -> Result<()> {
     let stats = storage.get_stats()?;
+    print_stats(&stats, language);
+    Ok(())
+}
 
+/// Print history statistics in human-readable form
+fn print_stats(stats: &HistoryStats, language: Language) {
     println!("{}", "History Statistics".cyan().bold());
     println!();
 
@@ -327,8 +520,28 @@ fn display_stats(storage: &HistoryStorage, language: Language) -> Result<()> {
         "Avg duration:".bright_white(),
         stats.avg_duration_string()
     );
+    println!(
+        "  {} p50 {} / p90 {} / p99 {}",
+        "Percentiles:".bright_white(),
+        format_duration_ms(stats.p50_duration_ms),
+        format_duration_ms(stats.p90_duration_ms),
+        format_duration_ms(stats.p99_duration_ms)
+    );
 
-    Ok(())
+    if let (Some(slope), Some(trend)) = (stats.trend_ms_per_run, stats.trend()) {
+        let direction = match trend {
+            Trend::Slower => "↑ slower".red(),
+            Trend::Faster => "↓ faster".green(),
+            Trend::Stable => "→ stable".dimmed(),
+        };
+        println!(
+            "  {} {} ({:+.1}ms/run over last {} runs)",
+            "Trend:".bright_white(),
+            direction,
+            slope,
+            stats.trend_window
+        );
+    }
 }
 
 #[cfg(test)]
@@ -358,6 +571,10 @@ mod tests {
             success: true,
             working_dir: Some("/tmp".to_string()),
             environment: None,
+            confirm_skipped: false,
+            skipped: false,
+            hostname: String::new(),
+            cmdrun_version: String::new(),
         };
         storage.add(&entry1).unwrap();
 
@@ -371,6 +588,10 @@ mod tests {
             success: false,
             working_dir: Some("/tmp".to_string()),
             environment: None,
+            confirm_skipped: false,
+            skipped: false,
+            hostname: String::new(),
+            cmdrun_version: String::new(),
         };
         storage.add(&entry2).unwrap();
 
@@ -384,6 +605,10 @@ mod tests {
             success: true,
             working_dir: Some("/tmp".to_string()),
             environment: None,
+            confirm_skipped: false,
+            skipped: false,
+            hostname: String::new(),
+            cmdrun_version: String::new(),
         };
         storage.add(&entry3).unwrap();
 
@@ -405,7 +630,17 @@ mod tests {
         let _storage = HistoryStorage::new().unwrap();
 
         // Should succeed without errors even with empty history
-        let result = handle_history(None, None, false, false, Language::English).await;
+        let result = handle_history(
+            None,
+            None,
+            false,
+            None,
+            None,
+            false,
+            false,
+            Language::English,
+        )
+        .await;
         assert!(result.is_ok());
     }
 
@@ -414,7 +649,17 @@ mod tests {
         let (_storage, _temp_dir) = create_test_storage();
 
         // Should succeed with limit parameter
-        let result = handle_history(Some(2), None, false, false, Language::English).await;
+        let result = handle_history(
+            Some(2),
+            None,
+            false,
+            None,
+            None,
+            false,
+            false,
+            Language::English,
+        )
+        .await;
         assert!(result.is_ok());
     }
 
@@ -423,7 +668,17 @@ mod tests {
         let (_storage, _temp_dir) = create_test_storage();
 
         // Should filter and show only failed commands
-        let result = handle_history(None, None, true, false, Language::English).await;
+        let result = handle_history(
+            None,
+            None,
+            true,
+            None,
+            None,
+            false,
+            false,
+            Language::English,
+        )
+        .await;
         assert!(result.is_ok());
     }
 
@@ -432,7 +687,72 @@ mod tests {
         let (_storage, _temp_dir) = create_test_storage();
 
         // Should display statistics
-        let result = handle_history(None, None, false, true, Language::English).await;
+        let result = handle_history(
+            None,
+            None,
+            false,
+            None,
+            None,
+            true,
+            false,
+            Language::English,
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_handle_history_stats_json() {
+        let (_storage, _temp_dir) = create_test_storage();
+
+        let result = handle_history_stats(None, 20, true, Language::English).await;
+        assert!(result.is_ok());
+        let result =
+            handle_history_stats(Some("build".to_string()), 5, false, Language::English).await;
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_sort_breakdown() {
+        let row =
+            |command: &str, runs: usize, failed: usize, total_duration_ms: f64| CommandBreakdown {
+                command: command.to_string(),
+                runs,
+                successful: runs - failed,
+                failed,
+                avg_duration_ms: Some(total_duration_ms / runs as f64),
+                total_duration_ms,
+            };
+        let mut rows = vec![
+            row("build", 3, 1, 6_000.0),
+            row("lint", 5, 0, 500.0),
+            row("test", 4, 2, 9_000.0),
+            row("fmt", 5, 0, 250.0),
+        ];
+        let names = |rows: &[CommandBreakdown]| -> Vec<String> {
+            rows.iter().map(|r| r.command.clone()).collect()
+        };
+
+        sort_breakdown(&mut rows, StatsSort::Time);
+        assert_eq!(names(&rows), ["test", "build", "lint", "fmt"]);
+
+        // Ties fall back to the command name
+        sort_breakdown(&mut rows, StatsSort::Runs);
+        assert_eq!(names(&rows), ["fmt", "lint", "test", "build"]);
+
+        sort_breakdown(&mut rows, StatsSort::Failures);
+        assert_eq!(names(&rows), ["test", "build", "fmt", "lint"]);
+    }
+
+    #[tokio::test]
+    async fn test_handle_history_breakdown() {
+        let (_storage, _temp_dir) = create_test_storage();
+
+        let result =
+            handle_history_breakdown(StatsSort::Runs, Some(1), true, Language::English).await;
+        assert!(result.is_ok());
+        let result =
+            handle_history_breakdown(StatsSort::Time, None, false, Language::English).await;
         assert!(result.is_ok());
     }
 
@@ -514,10 +834,24 @@ mod tests {
             args: None,
             working_dir: Some("/tmp".to_string()),
             environment: None,
+            confirm_skipped: false,
+            skipped: false,
+            hostname: String::new(),
+            cmdrun_version: String::new(),
         };
 
         // Should not panic when displaying entry
-        display_entry(&entry, Language::English);
+        display_entry(&entry, true, Language::English);
+    }
+
+    #[test]
+    fn test_format_environment_redacts_secrets() {
+        let env = r#"{"GITHUB_TOKEN":"ghp_supersecret123","CI":"true"}"#;
+        let formatted = format_environment(env).unwrap();
+
+        assert_eq!(formatted, "CI=true GITHUB_TOKEN=***REDACTED***");
+        assert!(format_environment("{}").is_none());
+        assert!(format_environment("not json").is_none());
     }
 
     #[test]
@@ -534,10 +868,14 @@ mod tests {
             args: Some(r#"["arg1", "arg2"]"#.to_string()),
             working_dir: Some("/tmp".to_string()),
             environment: None,
+            confirm_skipped: false,
+            skipped: false,
+            hostname: String::new(),
+            cmdrun_version: String::new(),
         };
 
         // Should not panic when displaying failed entry
-        display_entry(&entry, Language::English);
+        display_entry(&entry, true, Language::English);
     }
 
     #[test]
@@ -579,7 +917,17 @@ mod tests {
         let (_storage, _temp_dir) = create_test_storage();
 
         // Test with offset parameter (covers line 33)
-        let result = handle_history(Some(10), Some(1), false, false, Language::English).await;
+        let result = handle_history(
+            Some(10),
+            Some(1),
+            false,
+            None,
+            None,
+            false,
+            false,
+            Language::English,
+        )
+        .await;
         assert!(result.is_ok());
     }
 }
@@ -1,33 +1,37 @@
 //! Info command - Show detailed command information
 
+use crate::cli::DefinitionFormat;
 use crate::config::loader::ConfigLoader;
-use crate::config::schema::{CommandSpec, CommandsConfig};
+use crate::config::schema::{Command, CommandSpec, CommandsConfig};
 use crate::i18n::{get_message, MessageKey};
+use crate::plugin::PluginManager;
+use crate::security::{EnvRedactor, REDACTED};
 use anyhow::Result;
 use colored::*;
 use std::io::{self, Write};
 use std::path::PathBuf;
 
 /// Show detailed information about a command
+///
+/// With `format`, prints only the command's definition in that format.
 pub async fn handle_info(
     command_id: Option<String>,
+    format: Option<DefinitionFormat>,
     global_only: bool,
-    config_path: Option<PathBuf>,
+    config_paths: Vec<PathBuf>,
 ) -> Result<()> {
-    let config_loader = if let Some(path) = config_path {
-        ConfigLoader::with_path(path)?
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
     } else if global_only {
         ConfigLoader::global_only()
     } else {
         ConfigLoader::new()
     };
-    let loaded = config_loader.load_with_paths().await?;
+    let mut loaded = config_loader.load_with_paths().await?;
+    PluginManager::load_for_config(&mut loaded.config)?;
     let config = &loaded.config;
     let lang = config.config.language;
 
-    // Load history for statistics
-    let history_storage = crate::history::HistoryStorage::new().ok();
-
     // Get command ID (from argument or interactive selection)
     let id = if let Some(id) = command_id {
         id
@@ -40,6 +44,14 @@ pub async fn handle_info(
         anyhow::anyhow!("{}", get_message(MessageKey::ErrorCommandNotFound, lang))
     })?;
 
+    if let Some(format) = format {
+        print!("{}", format_definition(&id, command, format)?);
+        return Ok(());
+    }
+
+    // Load history for statistics
+    let history_storage = crate::history::HistoryStorage::new().ok();
+
     // Display detailed information
     println!(
         "{} {}",
@@ -91,6 +103,9 @@ pub async fn handle_info(
                 println!("  {} {}", "Windows:".dimmed(), windows.bright_white());
             }
         }
+        CommandSpec::Script { file } => {
+            println!("  {} {}", "Script:".dimmed(), file.bright_white());
+        }
     }
     println!();
 
@@ -143,7 +158,13 @@ pub async fn handle_info(
             .white()
             .bold()
         );
+        let redactor = EnvRedactor::new(&config.config.redact_patterns).unwrap_or_default();
         for (key, value) in &command.env {
+            let value = if redactor.is_redacted(key) {
+                REDACTED
+            } else {
+                value
+            };
             println!("  {} = {}", key.yellow(), value.bright_white());
         }
         println!();
@@ -168,9 +189,9 @@ pub async fn handle_info(
     );
     if let Some(timeout) = command.timeout {
         println!(
-            "  {} {}s",
+            "  {} {}",
             format!("{}:", get_message(MessageKey::LabelTimeout, lang)).dimmed(),
-            timeout
+            crate::config::duration::format_duration(timeout)
         );
     }
     println!();
@@ -299,6 +320,21 @@ struct CommandStatistics {
 }
 
 /// Get execution statistics for a command from history
+/// Serialize a command's definition
+///
+/// TOML is wrapped in a `[commands.<id>]` table so it can be pasted into
+/// a commands.toml as is.
+pub fn format_definition(id: &str, command: &Command, format: DefinitionFormat) -> Result<String> {
+    Ok(match format {
+        DefinitionFormat::Toml => {
+            let commands = std::collections::BTreeMap::from([(id, command)]);
+            toml::to_string(&std::collections::BTreeMap::from([("commands", commands)]))?
+        }
+        DefinitionFormat::Yaml => serde_yaml::to_string(command)?,
+        DefinitionFormat::Json => format!("{}\n", serde_json::to_string_pretty(command)?),
+    })
+}
+
 async fn get_command_statistics(
     storage: &crate::history::HistoryStorage,
     command_name: &str,
@@ -377,15 +413,18 @@ fn select_command_interactive(
     );
     println!();
 
+    // Most-run commands first
+    let frequency = crate::history::load_frequency_map();
     let mut commands: Vec<_> = config.commands.iter().collect();
-    commands.sort_by_key(|(name, _)| *name);
+    crate::history::sort_by_frequency(&mut commands, &frequency);
 
     for (idx, (name, cmd)) in commands.iter().enumerate() {
         println!(
-            "  {}. {} - {}",
+            "  {}. {} - {}{}",
             (idx + 1).to_string().yellow(),
             name.green().bold(),
-            cmd.description
+            cmd.description,
+            format_run_count(frequency.get(*name).copied())
         );
     }
 
@@ -414,6 +453,14 @@ fn select_command_interactive(
     Ok(commands[selection - 1].0.clone())
 }
 
+/// Dimmed run count suffix for the command picker
+fn format_run_count(count: Option<u64>) -> String {
+    match count {
+        Some(count) => format!(" ({} runs)", count).dimmed().to_string(),
+        None => String::new(),
+    }
+}
+
 /// Format boolean value with colored output
 fn format_bool(value: bool, lang: crate::config::schema::Language) -> ColoredString {
     if value {
@@ -427,6 +474,71 @@ fn format_bool(value: bool, lang: crate::config::schema::Language) -> ColoredStr
 mod tests {
     use super::*;
 
+    fn parse_command(toml: &str) -> Command {
+        toml::from_str(toml).unwrap()
+    }
+
+    const DEFINITION: &str = r#"
+description = "Serve docs"
+cmd = ["mdbook build", "mdbook serve --port ${PORT}"]
+deps = ["build"]
+timeout = "90s"
+confirm = true
+
+[env]
+PORT = "3000"
+"#;
+
+    #[test]
+    fn test_format_definition_toml_round_trip() {
+        let command = parse_command(DEFINITION);
+        let output = format_definition("docs:serve", &command, DefinitionFormat::Toml).unwrap();
+        assert!(
+            output.starts_with("[commands.\"docs:serve\"]\n"),
+            "{}",
+            output
+        );
+
+        let config: CommandsConfig = toml::from_str(&output).unwrap();
+        let parsed = &config.commands["docs:serve"];
+        assert_eq!(parsed.description, command.description);
+        assert_eq!(
+            parsed
+                .cmd
+                .resolve_for_platform(&crate::config::Platform::current()),
+            command
+                .cmd
+                .resolve_for_platform(&crate::config::Platform::current())
+        );
+        assert_eq!(parsed.deps, command.deps);
+        assert_eq!(parsed.env, command.env);
+        assert_eq!(parsed.timeout, command.timeout);
+        assert!(parsed.confirm);
+
+        // Serializing the parsed command again gives the same text
+        assert_eq!(
+            format_definition("docs:serve", parsed, DefinitionFormat::Toml).unwrap(),
+            output
+        );
+    }
+
+    #[test]
+    fn test_format_definition_yaml_and_json() {
+        let command = parse_command(DEFINITION);
+
+        let yaml = format_definition("docs:serve", &command, DefinitionFormat::Yaml).unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(value["description"], "Serve docs");
+        assert_eq!(value["cmd"][1], "mdbook serve --port ${PORT}");
+
+        let json = format_definition("docs:serve", &command, DefinitionFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["description"], "Serve docs");
+        assert_eq!(value["cmd"][0], "mdbook build");
+        // Unset optional fields are omitted rather than written as null
+        assert!(value.get("working_dir").is_none(), "{}", json);
+    }
+
     #[test]
     fn test_format_bool() {
         use crate::config::schema::Language;
@@ -5,6 +5,7 @@ use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Select};
 use std::path::{Path, PathBuf};
 
+use crate::cli::InitSource;
 use crate::config::Language;
 use crate::i18n::{get_message, MessageKey};
 
@@ -142,6 +143,8 @@ pub async fn handle_init(
         Language::Japanese => "日本語",
         Language::ChineseSimplified => "简体中文",
         Language::ChineseTraditional => "繁體中文",
+        Language::Spanish => "Español",
+        Language::German => "Deutsch",
     };
     println!(
         "  {} {}",
@@ -167,19 +170,26 @@ fn select_language_interactive() -> Result<Language> {
     );
     println!();
 
-    let items = vec!["English", "日本語 (Japanese)"];
+    let languages = [
+        Language::English,
+        Language::Japanese,
+        Language::Spanish,
+        Language::German,
+    ];
+    let items = [
+        get_message(MessageKey::OptionEnglish, Language::English),
+        get_message(MessageKey::OptionJapanese, Language::English),
+        get_message(MessageKey::OptionSpanish, Language::English),
+        get_message(MessageKey::OptionGerman, Language::English),
+    ];
 
     let selection = Select::with_theme(&ColorfulTheme::default())
-        .items(&items)
+        .items(items)
         .default(0)
         .interact()
         .context("Failed to read language selection")?;
 
-    Ok(match selection {
-        0 => Language::English,
-        1 => Language::Japanese,
-        _ => Language::English,
-    })
+    Ok(languages.get(selection).copied().unwrap_or_default())
 }
 
 /// Interactive template selection
@@ -212,6 +222,8 @@ fn inject_language_setting(content: &str, language: Language) -> String {
         Language::Japanese => "japanese",
         Language::ChineseSimplified => "chinese_simplified",
         Language::ChineseTraditional => "chinese_traditional",
+        Language::Spanish => "spanish",
+        Language::German => "german",
     };
 
     // Find the [config] section and add language setting
@@ -252,6 +264,11 @@ fn print_next_steps(output_path: &Path, language: Language) {
         Language::Japanese => format!("{} を編集してコマンドを定義", output_path.display()),
         Language::ChineseSimplified => format!("编辑 {} 来定义您的命令", output_path.display()),
         Language::ChineseTraditional => format!("編輯 {} 來定義您的命令", output_path.display()),
+        Language::Spanish => format!("Edite {} para definir sus comandos", output_path.display()),
+        Language::German => format!(
+            "Bearbeiten Sie {}, um Ihre Befehle zu definieren",
+            output_path.display()
+        ),
     };
 
     let step2_msg = match language {
@@ -259,6 +276,8 @@ fn print_next_steps(output_path: &Path, language: Language) {
         Language::Japanese => "cmdrun list で利用可能なコマンド一覧を表示",
         Language::ChineseSimplified => "运行 cmdrun list 列出可用命令",
         Language::ChineseTraditional => "執行 cmdrun list 列出可用命令",
+        Language::Spanish => "Ejecute cmdrun list para listar los comandos disponibles",
+        Language::German => "Führen Sie cmdrun list aus, um verfügbare Befehle aufzulisten",
     };
 
     let step3_msg = match language {
@@ -266,6 +285,8 @@ fn print_next_steps(output_path: &Path, language: Language) {
         Language::Japanese => "cmdrun run <名前> でコマンドを実行",
         Language::ChineseSimplified => "运行 cmdrun run <名称> 执行命令",
         Language::ChineseTraditional => "執行 cmdrun run <名稱> 執行命令",
+        Language::Spanish => "Ejecute cmdrun run <nombre> para ejecutar un comando",
+        Language::German => "Führen Sie cmdrun run <Name> aus, um einen Befehl auszuführen",
     };
 
     println!("  {} {}", "1.".bright_white().bold(), step1_msg.yellow());
@@ -279,6 +300,8 @@ fn print_next_steps(output_path: &Path, language: Language) {
         Language::Japanese => "コマンド例:",
         Language::ChineseSimplified => "示例命令：",
         Language::ChineseTraditional => "範例命令：",
+        Language::Spanish => "Comandos de ejemplo:",
+        Language::German => "Beispielbefehle:",
     };
 
     println!("{}", example_label.dimmed());
@@ -288,6 +311,261 @@ fn print_next_steps(output_path: &Path, language: Language) {
     println!();
 }
 
+/// A command converted from an existing project file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedCommand {
+    pub name: String,
+    pub description: String,
+    /// Steps run in order (a single step is written as a plain string)
+    pub cmd: Vec<String>,
+    pub deps: Vec<String>,
+}
+
+/// Handle `init --from`: convert package.json scripts or Makefile targets
+pub async fn handle_init_from(source: InitSource, output: Option<PathBuf>) -> Result<()> {
+    let output_path = output.unwrap_or_else(|| PathBuf::from("commands.toml"));
+    init_from_file(source, Path::new(source_file_name(source)), &output_path)
+}
+
+fn source_file_name(source: InitSource) -> &'static str {
+    match source {
+        InitSource::PackageJson => "package.json",
+        InitSource::Makefile => "Makefile",
+    }
+}
+
+fn init_from_file(source: InitSource, input: &Path, output_path: &Path) -> Result<()> {
+    if output_path.exists() {
+        anyhow::bail!(
+            "{}",
+            get_message(MessageKey::ErrorFileAlreadyExists, Language::English)
+        );
+    }
+
+    let content = std::fs::read_to_string(input)
+        .with_context(|| format!("Failed to read {}", input.display()))?;
+    let commands = match source {
+        InitSource::PackageJson => import_package_json(&content)
+            .with_context(|| format!("Failed to parse {}", input.display()))?,
+        InitSource::Makefile => import_makefile(&content),
+    };
+    if commands.is_empty() {
+        anyhow::bail!("No commands found in {}", input.display());
+    }
+
+    std::fs::write(output_path, render_imported_commands(&commands))
+        .with_context(|| format!("Failed to write to {}", output_path.display()))?;
+
+    println!();
+    println!(
+        "{} {} {}",
+        "✓".green().bold(),
+        get_message(MessageKey::InitCreated, Language::English).green(),
+        output_path.display().to_string().bright_white().bold()
+    );
+    println!(
+        "  Imported {} command(s) from {}",
+        commands.len().to_string().cyan().bold(),
+        input.display()
+    );
+    println!();
+    print_next_steps(output_path, Language::English);
+
+    Ok(())
+}
+
+/// Convert package.json `scripts` into commands
+///
+/// `pre<name>` becomes a dependency of `<name>`, and `post<name>` depends on
+/// `<name>`, mirroring npm's lifecycle order.
+pub fn import_package_json(content: &str) -> Result<Vec<ImportedCommand>> {
+    let package: serde_json::Value = serde_json::from_str(content)?;
+    let Some(scripts) = package.get("scripts").and_then(|s| s.as_object()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut commands: Vec<ImportedCommand> = scripts
+        .iter()
+        .filter_map(|(name, script)| {
+            let script = script.as_str()?.trim();
+            (!script.is_empty()).then(|| ImportedCommand {
+                name: sanitize_command_name(name),
+                description: format!("npm script '{}'", name),
+                cmd: split_script(script),
+                deps: Vec::new(),
+            })
+        })
+        .collect();
+
+    let names: Vec<String> = commands.iter().map(|c| c.name.clone()).collect();
+    let has = |name: &str| names.iter().any(|n| n == name);
+    for command in &mut commands {
+        let pre = format!("pre{}", command.name);
+        if has(&pre) {
+            command.deps.push(pre);
+        }
+        if let Some(main) = command.name.strip_prefix("post") {
+            if has(main) {
+                command.deps.push(main.to_string());
+            }
+        }
+    }
+
+    Ok(commands)
+}
+
+/// Convert Makefile targets and their recipes into commands
+///
+/// Prerequisites that are themselves targets become `deps`; file
+/// prerequisites, special targets (`.PHONY`), pattern rules and variable
+/// assignments are ignored.
+pub fn import_makefile(content: &str) -> Vec<ImportedCommand> {
+    let mut commands: Vec<ImportedCommand> = Vec::new();
+    // Indices into `commands` of the targets the current recipe belongs to
+    let mut current: Vec<usize> = Vec::new();
+
+    for line in join_continuations(content) {
+        if let Some(recipe) = line.strip_prefix('\t') {
+            let recipe = recipe.trim_start_matches(['@', '-', '+']).trim();
+            if recipe.is_empty() || recipe.starts_with('#') {
+                continue;
+            }
+            let recipe = recipe.replace("$$", "$");
+            for &idx in &current {
+                commands[idx].cmd.push(recipe.clone());
+            }
+            continue;
+        }
+
+        current.clear();
+        let line = line.split('#').next().unwrap_or("").trim_end();
+        let Some((targets, prereqs)) = line.split_once(':') else {
+            continue;
+        };
+        // `VAR := value`, `VAR ::= value`, `target: VAR = value` and `::` rules
+        if targets.contains('=') || prereqs.contains('=') || prereqs.starts_with(':') {
+            continue;
+        }
+        let prereqs = prereqs.split(';').next().unwrap_or("");
+        let prereqs: Vec<String> = prereqs.split_whitespace().map(str::to_string).collect();
+
+        for target in targets.split_whitespace() {
+            if target.starts_with('.') || target.contains('%') || target.contains('$') {
+                continue;
+            }
+            let name = sanitize_command_name(target);
+            let idx = match commands.iter().position(|c| c.name == name) {
+                Some(idx) => idx,
+                None => {
+                    commands.push(ImportedCommand {
+                        name,
+                        description: format!("make target '{}'", target),
+                        cmd: Vec::new(),
+                        deps: Vec::new(),
+                    });
+                    commands.len() - 1
+                }
+            };
+            commands[idx].deps.extend(prereqs.iter().cloned());
+            current.push(idx);
+        }
+    }
+
+    // Only prerequisites defined as targets are commands
+    let names: Vec<String> = commands.iter().map(|c| c.name.clone()).collect();
+    for command in &mut commands {
+        let mut deps = Vec::new();
+        for dep in command.deps.drain(..).map(|d| sanitize_command_name(&d)) {
+            if names.contains(&dep) && !deps.contains(&dep) {
+                deps.push(dep);
+            }
+        }
+        command.deps = deps;
+        // Aggregate targets such as `all: build test` have no recipe of their own
+        if command.cmd.is_empty() {
+            command.cmd.push(format!("echo {}", command.name));
+        }
+    }
+
+    commands
+}
+
+/// Join backslash-continued lines, keeping the leading tab of recipe lines
+fn join_continuations(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut pending = String::new();
+    for line in content.lines() {
+        match line.strip_suffix('\\') {
+            Some(head) => {
+                pending.push_str(head.trim_end());
+                pending.push(' ');
+            }
+            None => {
+                if pending.is_empty() {
+                    lines.push(line.to_string());
+                } else {
+                    pending.push_str(line.trim_start());
+                    lines.push(std::mem::take(&mut pending));
+                }
+            }
+        }
+    }
+    if !pending.is_empty() {
+        lines.push(pending);
+    }
+    lines
+}
+
+/// Split `a && b` into separate steps so the default security policy accepts it
+fn split_script(script: &str) -> Vec<String> {
+    if script.contains(['\'', '"', '|', ';']) {
+        return vec![script.to_string()];
+    }
+    script
+        .split("&&")
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Replace characters not allowed in command names with `-`
+fn sanitize_command_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == ':' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Render imported commands as a commands.toml document
+fn render_imported_commands(commands: &[ImportedCommand]) -> String {
+    use toml_edit::{value, Array, DocumentMut, Item, Table};
+
+    let mut table = Table::new();
+    table.set_implicit(true);
+    for command in commands {
+        let mut entry = Table::new();
+        entry["description"] = value(command.description.as_str());
+        entry["cmd"] = match command.cmd.as_slice() {
+            [single] => value(single.as_str()),
+            steps => value(steps.iter().map(String::as_str).collect::<Array>()),
+        };
+        if !command.deps.is_empty() {
+            entry["deps"] = value(command.deps.iter().map(String::as_str).collect::<Array>());
+        }
+        table.insert(&command.name, Item::Table(entry));
+    }
+
+    let mut doc = DocumentMut::new();
+    doc["commands"] = Item::Table(table);
+    doc.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -702,4 +980,106 @@ cmd = "echo test"
             assert!(display.contains(" - "));
         }
     }
+
+    const PACKAGE_JSON: &str = r#"{
+  "name": "app",
+  "scripts": {
+    "prebuild": "rimraf dist",
+    "build": "tsc -p . && vite build",
+    "postbuild": "node scripts/report.js",
+    "test": "vitest run",
+    "lint:fix": "eslint --fix src",
+    "empty": ""
+  }
+}"#;
+
+    #[test]
+    fn test_import_package_json() {
+        let commands = import_package_json(PACKAGE_JSON).unwrap();
+        let get = |name: &str| commands.iter().find(|c| c.name == name).unwrap();
+
+        assert_eq!(commands.len(), 5, "{:?}", commands);
+        assert_eq!(get("build").cmd, vec!["tsc -p .", "vite build"]);
+        assert_eq!(get("build").description, "npm script 'build'");
+        assert_eq!(get("test").cmd, vec!["vitest run"]);
+        assert_eq!(get("lint:fix").cmd, vec!["eslint --fix src"]);
+        assert!(commands.iter().all(|c| c.name != "empty"));
+    }
+
+    #[test]
+    fn test_import_package_json_pre_post_deps() {
+        let commands = import_package_json(PACKAGE_JSON).unwrap();
+        let get = |name: &str| commands.iter().find(|c| c.name == name).unwrap();
+
+        assert_eq!(get("build").deps, vec!["prebuild"]);
+        assert_eq!(get("postbuild").deps, vec!["build"]);
+        assert!(get("prebuild").deps.is_empty());
+        assert!(get("test").deps.is_empty());
+    }
+
+    #[test]
+    fn test_import_package_json_without_scripts() {
+        assert!(import_package_json(r#"{"name": "app"}"#)
+            .unwrap()
+            .is_empty());
+        assert!(import_package_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_import_makefile() {
+        let makefile = "CC := gcc\n\
+                        .PHONY: all build test\n\
+                        \n\
+                        all: build test\n\
+                        \n\
+                        build: main.o\n\
+                        \t@echo building\n\
+                        \tcargo build \\\n\
+                        \t  --release\n\
+                        \n\
+                        # run the tests\n\
+                        test: build\n\
+                        \t-cargo test\n\
+                        \techo $$HOME\n\
+                        \n\
+                        %.o: %.c\n\
+                        \tgcc -c $<\n";
+
+        let commands = import_makefile(makefile);
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["all", "build", "test"]);
+
+        assert_eq!(commands[0].deps, vec!["build", "test"]);
+        assert_eq!(commands[0].cmd, vec!["echo all"]);
+        assert_eq!(commands[1].deps, Vec::<String>::new());
+        assert_eq!(
+            commands[1].cmd,
+            vec!["echo building", "cargo build --release"]
+        );
+        assert_eq!(commands[2].deps, vec!["build"]);
+        assert_eq!(commands[2].cmd, vec!["cargo test", "echo $HOME"]);
+        assert_eq!(commands[2].description, "make target 'test'");
+    }
+
+    #[test]
+    fn test_init_from_package_json_writes_valid_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("package.json");
+        let output = temp_dir.path().join("commands.toml");
+        std::fs::write(&input, PACKAGE_JSON).unwrap();
+
+        init_from_file(InitSource::PackageJson, &input, &output).unwrap();
+
+        let content = std::fs::read_to_string(&output).unwrap();
+        let config: crate::config::schema::CommandsConfig = toml::from_str(&content).unwrap();
+        crate::config::validation::ConfigValidator::new(&config)
+            .validate()
+            .unwrap();
+        assert_eq!(config.commands.len(), 5);
+        assert_eq!(config.commands["build"].deps, vec!["prebuild"]);
+        assert_eq!(config.commands["postbuild"].deps, vec!["build"]);
+
+        // Refuses to overwrite
+        assert!(init_from_file(InitSource::PackageJson, &input, &output).is_err());
+    }
 }
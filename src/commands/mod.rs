@@ -1,10 +1,15 @@
 //! Command implementations
 
 pub mod add;
+pub mod audit;
+pub mod bench;
 pub mod completion;
 pub mod config;
+pub mod docs;
+pub mod doctor;
 pub mod edit;
 pub mod env;
+pub mod export;
 pub mod history;
 pub mod info;
 pub mod init;
@@ -15,33 +20,42 @@ pub mod search;
 pub mod template;
 pub mod validate;
 pub mod watch;
+pub mod which;
 
 // Re-export command handlers
 pub use add::handle_add;
+pub use audit::handle_audit;
+pub use bench::{handle_bench, BenchOptions};
 pub use completion::handle_completion;
-pub use config::{handle_get, handle_set, handle_show};
+pub use config::{handle_append, handle_get, handle_set, handle_show};
+pub use docs::handle_docs;
+pub use doctor::handle_doctor;
 pub use edit::handle_edit;
 pub use env::{
-    handle_create, handle_current, handle_info as handle_env_info, handle_list as handle_env_list,
-    handle_set as handle_env_set, handle_use,
+    handle_create, handle_current, handle_diff as handle_env_diff, handle_info as handle_env_info,
+    handle_list as handle_env_list, handle_set as handle_env_set, handle_use,
 };
+pub use export::handle_export;
 pub use history::{
-    handle_history, handle_history_clear, handle_history_export, handle_history_search,
-    handle_retry, ExportFormat,
+    handle_history, handle_history_breakdown, handle_history_clear, handle_history_export,
+    handle_history_prune, handle_history_search, handle_history_stats, handle_retry, ExportFormat,
 };
 pub use info::handle_info;
-pub use init::handle_init;
+pub use init::{handle_init, handle_init_from};
 pub use open::handle_open;
 pub use remove::handle_remove;
 pub use search::handle_search;
 pub use template::{
-    handle_template_add, handle_template_export, handle_template_import, handle_template_list,
-    handle_template_remove, handle_template_use,
+    handle_template_add, handle_template_export, handle_template_import,
+    handle_template_import_remote, handle_template_list, handle_template_remove,
+    handle_template_use, handle_template_validate,
 };
 pub use validate::handle_validate;
-pub use watch::handle_watch;
+pub use watch::{handle_watch, WatchOptions};
+pub use which::handle_which;
 
 #[cfg(feature = "plugin-system")]
 pub use plugin::{
-    handle_plugin_disable, handle_plugin_enable, handle_plugin_info, handle_plugin_list,
+    handle_plugin_disable, handle_plugin_enable, handle_plugin_info, handle_plugin_install,
+    handle_plugin_list,
 };
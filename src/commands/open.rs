@@ -1,16 +1,19 @@
 //! Open command - Opens commands.toml in the default editor
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use colored::*;
 use std::path::PathBuf;
 use std::process::Command;
 use tracing::{debug, info};
 
 use crate::config::Language;
+use crate::error::config_not_found;
 use crate::i18n::{get_message, MessageKey};
 
-/// Open the configuration file in the default editor
-pub async fn handle_open(config_file_path: Option<PathBuf>) -> Result<()> {
+/// Open the configuration file in an editor
+///
+/// `editor` takes precedence over `$VISUAL` and `$EDITOR`.
+pub async fn handle_open(config_file_path: Option<PathBuf>, editor: Option<String>) -> Result<()> {
     let config_path = if let Some(path) = config_file_path {
         path
     } else {
@@ -24,7 +27,7 @@ pub async fn handle_open(config_file_path: Option<PathBuf>) -> Result<()> {
         config_path.display().to_string().bright_white()
     );
 
-    open_in_editor(&config_path)?;
+    open_in_editor(&config_path, editor)?;
 
     Ok(())
 }
@@ -51,10 +54,10 @@ async fn find_config_file() -> Result<PathBuf> {
         }
     }
 
-    anyhow::bail!(
-        "{}",
-        get_message(MessageKey::ErrorNoConfigFileFound, Language::English)
-    )
+    Err(config_not_found(
+        std::env::current_dir().unwrap_or_default(),
+        get_message(MessageKey::ErrorNoConfigFileFound, Language::English),
+    ))
 }
 
 /// Search upwards from a directory for config files
@@ -82,52 +85,93 @@ async fn search_upwards(
     Ok(None)
 }
 
-/// Open file in the appropriate editor
-fn open_in_editor(path: &std::path::Path) -> Result<()> {
-    // Try different editor commands in order of preference
-    let editors = if cfg!(target_os = "macos") {
-        vec![
-            ("open", vec![path.to_str().unwrap()]),
-            ("code", vec![path.to_str().unwrap()]),
-            ("vim", vec![path.to_str().unwrap()]),
-        ]
-    } else if cfg!(target_os = "windows") {
-        vec![
-            ("code", vec![path.to_str().unwrap()]),
-            ("notepad", vec![path.to_str().unwrap()]),
-        ]
-    } else {
-        vec![
-            ("xdg-open", vec![path.to_str().unwrap()]),
-            ("code", vec![path.to_str().unwrap()]),
-            ("vim", vec![path.to_str().unwrap()]),
-            ("nano", vec![path.to_str().unwrap()]),
-        ]
-    };
-
-    for (cmd, args) in editors {
-        if let Ok(which_result) = which::which(cmd) {
-            debug!("Using editor: {} at {:?}", cmd, which_result);
+/// Editors tried, in order, when no editor is configured
+#[cfg(windows)]
+const DEFAULT_EDITORS: &[&str] = &["notepad"];
+#[cfg(not(windows))]
+const DEFAULT_EDITORS: &[&str] = &["nano", "vi"];
+
+/// Editor command from `--editor`, then `$VISUAL`, then `$EDITOR`
+///
+/// Blank values are skipped; `None` means the platform defaults apply.
+fn configured_editor(
+    explicit: Option<String>,
+    visual: Option<String>,
+    editor: Option<String>,
+) -> Option<String> {
+    [explicit, visual, editor]
+        .into_iter()
+        .flatten()
+        .find(|e| !e.trim().is_empty())
+}
 
-            let status = Command::new(cmd)
-                .args(&args)
-                .status()
-                .with_context(|| format!("Failed to execute editor: {}", cmd))?;
+/// Open file in the configured editor, or the first platform default found
+fn open_in_editor(path: &std::path::Path, editor: Option<String>) -> Result<()> {
+    let lang = Language::English;
+    let editor = match configured_editor(
+        editor,
+        std::env::var("VISUAL").ok(),
+        std::env::var("EDITOR").ok(),
+    ) {
+        Some(editor) => editor,
+        None => DEFAULT_EDITORS
+            .iter()
+            .find(|e| which::which(e).is_ok())
+            .map(|e| e.to_string())
+            .ok_or_else(|| {
+                anyhow!(
+                    "{}: {}",
+                    get_message(MessageKey::ErrorEditorNotFound, lang),
+                    DEFAULT_EDITORS.join(", ")
+                )
+            })?,
+    };
 
-            if status.success() {
-                println!("{} Opened in {}", "✓".green().bold(), cmd.bright_white());
-                return Ok(());
-            }
-        }
+    // The editor may carry its own arguments (e.g. `code --wait`)
+    let words = shell_words::split(&editor).with_context(|| {
+        format!(
+            "{}: {}",
+            get_message(MessageKey::ErrorFailedToOpenEditor, lang),
+            editor
+        )
+    })?;
+    let Some((program, args)) = words.split_first() else {
+        bail!(
+            "{}: {}",
+            get_message(MessageKey::ErrorEditorNotFound, lang),
+            editor
+        );
+    };
+    let program_path = which::which(program).map_err(|_| {
+        anyhow!(
+            "{}: {}",
+            get_message(MessageKey::ErrorEditorNotFound, lang),
+            program
+        )
+    })?;
+    debug!("Using editor: {} at {:?}", editor, program_path);
+
+    let status = Command::new(&program_path)
+        .args(args)
+        .arg(path)
+        .status()
+        .with_context(|| {
+            format!(
+                "{}: {}",
+                get_message(MessageKey::ErrorFailedToOpenEditor, lang),
+                editor
+            )
+        })?;
+    if !status.success() {
+        bail!(
+            "{}: {} ({})",
+            get_message(MessageKey::ErrorFailedToOpenEditor, lang),
+            editor,
+            status
+        );
     }
 
-    // Fallback: just print the path
-    println!(
-        "{} Please manually open: {}",
-        "⚠".yellow().bold(),
-        path.display().to_string().bright_white()
-    );
-
+    println!("{} Opened in {}", "✓".green().bold(), editor.bright_white());
     Ok(())
 }
 
@@ -168,4 +212,23 @@ mod tests {
         assert!(result.is_some());
         assert_eq!(result.unwrap(), config_path);
     }
+
+    #[test]
+    fn test_configured_editor_precedence() {
+        let some = |s: &str| Some(s.to_string());
+
+        assert_eq!(
+            configured_editor(some("code --wait"), some("vim"), some("nano")),
+            some("code --wait")
+        );
+        assert_eq!(
+            configured_editor(None, some("vim"), some("nano")),
+            some("vim")
+        );
+        assert_eq!(
+            configured_editor(None, some(" "), some("nano")),
+            some("nano")
+        );
+        assert_eq!(configured_editor(some(""), None, None), None);
+    }
 }
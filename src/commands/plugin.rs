@@ -1,6 +1,6 @@
 //! Plugin management command
 //!
-//! Manages cmdrun plugins: list, enable, disable, info.
+//! Manages cmdrun plugins: list, enable, disable, info, install.
 
 #[cfg(feature = "plugin-system")]
 use crate::config::loader::ConfigLoader;
@@ -11,22 +11,69 @@ use crate::error::Result;
 #[cfg(feature = "plugin-system")]
 use crate::i18n::{get_message, MessageKey};
 #[cfg(feature = "plugin-system")]
-use crate::plugin::PluginManager;
+use crate::plugin::{PluginCapabilities, PluginConfig, PluginManager};
+#[cfg(feature = "plugin-system")]
+use ahash::AHashMap;
 #[cfg(feature = "plugin-system")]
 use colored::Colorize;
 #[cfg(feature = "plugin-system")]
 use std::path::PathBuf;
 
+/// A plugin entry of `plugin list --json`
+#[cfg(feature = "plugin-system")]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PluginListEntry {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub enabled: bool,
+    pub capabilities: PluginCapabilities,
+    /// Library path from the `[plugins.<name>]` entry
+    pub path: Option<String>,
+    pub min_cmdrun_version: Option<String>,
+}
+
+/// Build the `plugin list --json` entries for the loaded plugins, in name order
+///
+/// Disabled plugins are included with `enabled: false` unless `only_enabled`
+/// is set.
+#[cfg(feature = "plugin-system")]
+pub fn plugin_list_entries(
+    manager: &PluginManager,
+    plugins: &AHashMap<String, PluginConfig>,
+    only_enabled: bool,
+) -> Vec<PluginListEntry> {
+    let mut entries: Vec<PluginListEntry> = manager
+        .list_plugins_with_status()
+        .into_iter()
+        .filter(|(_, enabled)| *enabled || !only_enabled)
+        .map(|(metadata, enabled)| PluginListEntry {
+            path: plugins.get(&metadata.name).map(|c| c.path.clone()),
+            name: metadata.name,
+            version: metadata.version,
+            description: metadata.description,
+            enabled,
+            capabilities: metadata.capabilities,
+            min_cmdrun_version: metadata.min_cmdrun_version,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
 /// List all installed plugins
+///
+/// With `json`, prints a JSON array of [`PluginListEntry`] instead.
 #[cfg(feature = "plugin-system")]
 pub async fn handle_plugin_list(
     only_enabled: bool,
     verbose: bool,
-    config_path: Option<PathBuf>,
+    json: bool,
+    config_paths: Vec<PathBuf>,
     language: Language,
 ) -> Result<()> {
-    let loader = if let Some(path) = config_path {
-        ConfigLoader::with_path(path)?
+    let loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
     } else {
         ConfigLoader::new()
     };
@@ -35,6 +82,15 @@ pub async fn handle_plugin_list(
     let mut manager = PluginManager::new();
     manager.load_plugins(&config.plugins.plugins)?;
 
+    if json {
+        let entries = plugin_list_entries(&manager, &config.plugins.plugins, only_enabled);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).map_err(anyhow::Error::from)?
+        );
+        return Ok(());
+    }
+
     let plugins = manager.list_plugins();
 
     if plugins.is_empty() {
@@ -118,11 +174,11 @@ pub async fn handle_plugin_list(
 #[cfg(feature = "plugin-system")]
 pub async fn handle_plugin_info(
     name: &str,
-    config_path: Option<PathBuf>,
+    config_paths: Vec<PathBuf>,
     language: Language,
 ) -> Result<()> {
-    let loader = if let Some(path) = config_path {
-        ConfigLoader::with_path(path)?
+    let loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
     } else {
         ConfigLoader::new()
     };
@@ -262,3 +318,562 @@ pub async fn handle_plugin_disable(name: &str, config_path: Option<PathBuf>) ->
 
     Ok(())
 }
+
+/// Library extensions accepted by `plugin install`
+#[cfg(feature = "plugin-system")]
+const PLUGIN_EXTENSIONS: &[&str] = &["so", "dylib", "dll"];
+
+/// Install a plugin and register it in the configuration
+///
+/// The library is copied into the managed plugins directory and loaded once
+/// to read its metadata. The copy is removed again if loading or writing the
+/// configuration fails.
+#[cfg(feature = "plugin-system")]
+pub async fn handle_plugin_install(source: &str, config_path: Option<PathBuf>) -> Result<()> {
+    let config_file = match config_path {
+        Some(path) => path,
+        None => ConfigLoader::new()
+            .load_with_paths()
+            .await?
+            .local_path
+            .ok_or_else(|| anyhow::anyhow!("No commands.toml found to register the plugin in"))?,
+    };
+
+    let plugins_dir = plugins_dir()?;
+    std::fs::create_dir_all(&plugins_dir)?;
+
+    let installed = fetch_plugin(source, &plugins_dir)?;
+
+    if let Err(e) = register_plugin(&installed, &config_file) {
+        if let Err(remove_err) = std::fs::remove_file(&installed) {
+            tracing::warn!(
+                "Failed to remove {} during rollback: {}",
+                installed.display(),
+                remove_err
+            );
+        }
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Managed plugins directory (`<config dir>/cmdrun/plugins`)
+#[cfg(feature = "plugin-system")]
+fn plugins_dir() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Cannot determine configuration directory"))?;
+    Ok(config_dir.join("cmdrun").join("plugins"))
+}
+
+/// Copy or download the plugin library into `plugins_dir`
+///
+/// Returns the path of the installed library.
+#[cfg(feature = "plugin-system")]
+fn fetch_plugin(source: &str, plugins_dir: &std::path::Path) -> Result<PathBuf> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        return download_plugin(source, plugins_dir);
+    }
+
+    let library = resolve_library_path(std::path::Path::new(source))?;
+    let file_name = library
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid plugin path: {}", library.display()))?;
+    let destination = plugins_dir.join(file_name);
+
+    if destination.exists() {
+        return Err(anyhow::anyhow!(
+            "Plugin library already installed: {}",
+            destination.display()
+        )
+        .into());
+    }
+
+    std::fs::copy(&library, &destination)?;
+    Ok(destination)
+}
+
+/// Maximum size of a downloaded plugin library in bytes
+#[cfg(feature = "plugin-download")]
+const MAX_PLUGIN_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Time limit for the whole plugin download, including the body
+#[cfg(feature = "plugin-download")]
+const PLUGIN_DOWNLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Download a plugin library over http(s) into `plugins_dir`
+#[cfg(feature = "plugin-download")]
+fn download_plugin(url: &str, plugins_dir: &std::path::Path) -> Result<PathBuf> {
+    download_plugin_with_limits(url, plugins_dir, PLUGIN_DOWNLOAD_TIMEOUT, MAX_PLUGIN_SIZE)
+}
+
+#[cfg(feature = "plugin-download")]
+fn download_plugin_with_limits(
+    url: &str,
+    plugins_dir: &std::path::Path,
+    timeout: std::time::Duration,
+    max_size: u64,
+) -> Result<PathBuf> {
+    use std::io::Read;
+
+    let file_name = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|u| u.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Cannot determine file name from URL: {}", url))?;
+    check_extension(std::path::Path::new(file_name))?;
+
+    let destination = plugins_dir.join(file_name);
+    if destination.exists() {
+        return Err(anyhow::anyhow!(
+            "Plugin library already installed: {}",
+            destination.display()
+        )
+        .into());
+    }
+
+    println!("Downloading {}", url.cyan());
+    let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+    let response = agent
+        .get(url)
+        .call()
+        .map_err(|e| anyhow::anyhow!("Failed to download plugin: {}", e))?;
+
+    // Read one byte past the limit to detect oversized libraries
+    let mut file = std::fs::File::create(&destination)?;
+    let copied = std::io::copy(&mut response.into_reader().take(max_size + 1), &mut file);
+    let failure = match copied {
+        Ok(size) if size > max_size => Some(anyhow::anyhow!(
+            "Plugin library exceeds the {} byte limit: {}",
+            max_size,
+            url
+        )),
+        Ok(_) => None,
+        Err(e) => Some(anyhow::anyhow!("Failed to download plugin: {}", e)),
+    };
+    if let Some(e) = failure {
+        drop(file);
+        let _ = std::fs::remove_file(&destination);
+        return Err(e.into());
+    }
+
+    Ok(destination)
+}
+
+#[cfg(all(feature = "plugin-system", not(feature = "plugin-download")))]
+fn download_plugin(url: &str, _plugins_dir: &std::path::Path) -> Result<PathBuf> {
+    Err(anyhow::anyhow!(
+        "Installing plugins from a URL requires the `plugin-download` feature: {}",
+        url
+    )
+    .into())
+}
+
+/// Resolve a plugin library from a file or directory path
+///
+/// For a directory, the single library with the current platform's extension
+/// is selected.
+#[cfg(feature = "plugin-system")]
+fn resolve_library_path(path: &std::path::Path) -> Result<PathBuf> {
+    if !path.exists() {
+        return Err(crate::error::CmdrunError::PluginLoad(format!(
+            "Plugin file not found: {}",
+            path.display()
+        )));
+    }
+
+    if path.is_file() {
+        check_extension(path)?;
+        return Ok(path.to_path_buf());
+    }
+
+    let extension = std::env::consts::DLL_EXTENSION;
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(path)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && p.extension().and_then(|e| e.to_str()) == Some(extension))
+        .collect();
+    candidates.sort();
+
+    match candidates.len() {
+        0 => Err(anyhow::anyhow!(
+            "No .{} plugin library found in {}",
+            extension,
+            path.display()
+        )
+        .into()),
+        1 => Ok(candidates.remove(0)),
+        _ => Err(anyhow::anyhow!(
+            "Multiple .{} libraries found in {}, specify one: {}",
+            extension,
+            path.display(),
+            candidates
+                .iter()
+                .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .into()),
+    }
+}
+
+/// Reject files that are not dynamic libraries
+#[cfg(feature = "plugin-system")]
+fn check_extension(path: &std::path::Path) -> Result<()> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if PLUGIN_EXTENSIONS.contains(&extension) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Not a plugin library (expected .so, .dylib, or .dll): {}",
+            path.display()
+        )
+        .into())
+    }
+}
+
+/// Load the installed library, print its metadata, and add it to the config
+#[cfg(feature = "plugin-system")]
+fn register_plugin(library: &std::path::Path, config_file: &std::path::Path) -> Result<()> {
+    let metadata = read_plugin_metadata(library)?;
+    print_installed_metadata(&metadata);
+
+    let content = std::fs::read_to_string(config_file)?;
+    let updated = add_plugin_entry(&content, &metadata.name, library)?;
+
+    // Write through a temporary file so a failed write leaves the config intact
+    let temp_file = config_file.with_extension("toml.tmp");
+    if let Err(e) =
+        std::fs::write(&temp_file, updated).and_then(|_| std::fs::rename(&temp_file, config_file))
+    {
+        let _ = std::fs::remove_file(&temp_file);
+        return Err(e.into());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Plugin '{}' installed and registered in {}",
+            metadata.name,
+            config_file.display()
+        )
+        .green()
+    );
+
+    Ok(())
+}
+
+/// Load a plugin library once to read its metadata
+#[cfg(feature = "plugin-system")]
+fn read_plugin_metadata(library: &std::path::Path) -> Result<crate::plugin::PluginMetadata> {
+    use crate::plugin::loader::PluginLoader;
+
+    PluginLoader::validate(library)?;
+
+    let mut loader = PluginLoader::new();
+    // The plugin instance must be dropped before its library is unloaded
+    let metadata = unsafe { loader.load(library)? }.metadata();
+
+    Ok(metadata)
+}
+
+/// Print metadata of a freshly installed plugin
+#[cfg(feature = "plugin-system")]
+fn print_installed_metadata(metadata: &crate::plugin::PluginMetadata) {
+    println!("{}", "Plugin Information".bold());
+    println!();
+    println!("Name: {}", metadata.name.bold());
+    println!("Version: {}", metadata.version);
+    println!("Description: {}", metadata.description);
+
+    if !metadata.authors.is_empty() {
+        println!("Authors: {}", metadata.authors.join(", "));
+    }
+
+    if let Some(license) = &metadata.license {
+        println!("License: {}", license);
+    }
+
+    println!();
+}
+
+/// Add a `[plugins.<name>]` entry to a commands.toml document
+#[cfg(feature = "plugin-system")]
+fn add_plugin_entry(content: &str, name: &str, library: &std::path::Path) -> Result<String> {
+    use toml_edit::{value, DocumentMut, Item, Table};
+
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .map_err(|e| anyhow::anyhow!("Failed to parse TOML file: {}", e))?;
+
+    if !doc.contains_key("plugins") {
+        let mut plugins = Table::new();
+        plugins.set_implicit(true);
+        doc["plugins"] = Item::Table(plugins);
+    }
+
+    let plugins = doc["plugins"]
+        .as_table_mut()
+        .ok_or_else(|| anyhow::anyhow!("[plugins] in config is not a table"))?;
+
+    if plugins.contains_key(name) {
+        return Err(crate::error::CmdrunError::PluginError {
+            plugin: name.to_string(),
+            message: "Plugin already registered in config".to_string(),
+        });
+    }
+
+    let mut entry = Table::new();
+    entry.insert("path", value(library.to_string_lossy().into_owned()));
+    entry.insert("enabled", value(true));
+    plugins.insert(name, Item::Table(entry));
+
+    Ok(doc.to_string())
+}
+
+#[cfg(all(test, feature = "plugin-system"))]
+mod tests {
+    use super::*;
+    use crate::plugin::{Plugin, PluginMetadata};
+    use tempfile::TempDir;
+
+    struct NamedPlugin(&'static str);
+
+    impl Plugin for NamedPlugin {
+        fn metadata(&self) -> PluginMetadata {
+            PluginMetadata {
+                name: self.0.to_string(),
+                version: "0.2.0".to_string(),
+                description: format!("{} plugin", self.0),
+                authors: vec![],
+                license: None,
+                homepage: None,
+                min_cmdrun_version: Some("1.0.0".to_string()),
+                capabilities: PluginCapabilities {
+                    pre_execute: true,
+                    ..Default::default()
+                },
+            }
+        }
+
+        fn on_load(&mut self, _config: &AHashMap<String, String>) -> Result<()> {
+            Ok(())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_plugin_list_entries_json_includes_disabled() {
+        let manager = PluginManager::new();
+        for name in ["notify", "audit"] {
+            manager
+                .registry()
+                .register(Box::new(NamedPlugin(name)))
+                .unwrap();
+        }
+        manager.disable_plugin("audit").unwrap();
+
+        let plugins: crate::config::schema::CommandsConfig = toml::from_str(
+            r#"
+[plugins.notify]
+path = "/plugins/libnotify.so"
+
+[plugins.audit]
+path = "/plugins/libaudit.so"
+enabled = false
+"#,
+        )
+        .unwrap();
+        let plugins = plugins.plugins.plugins;
+
+        let entries = plugin_list_entries(&manager, &plugins, false);
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {
+                    "name": "audit",
+                    "version": "0.2.0",
+                    "description": "audit plugin",
+                    "enabled": false,
+                    "capabilities": {
+                        "pre_execute": true,
+                        "post_execute": false,
+                        "on_error": false,
+                        "custom_commands": false,
+                        "config_modification": false
+                    },
+                    "path": "/plugins/libaudit.so",
+                    "min_cmdrun_version": "1.0.0"
+                },
+                {
+                    "name": "notify",
+                    "version": "0.2.0",
+                    "description": "notify plugin",
+                    "enabled": true,
+                    "capabilities": {
+                        "pre_execute": true,
+                        "post_execute": false,
+                        "on_error": false,
+                        "custom_commands": false,
+                        "config_modification": false
+                    },
+                    "path": "/plugins/libnotify.so",
+                    "min_cmdrun_version": "1.0.0"
+                }
+            ])
+        );
+
+        let enabled = plugin_list_entries(&manager, &plugins, true);
+        assert_eq!(enabled.len(), 1);
+        assert_eq!(enabled[0].name, "notify");
+    }
+
+    #[test]
+    fn test_resolve_library_path_picks_platform_library() {
+        let temp_dir = TempDir::new().unwrap();
+        let library = temp_dir
+            .path()
+            .join(format!("libdemo.{}", std::env::consts::DLL_EXTENSION));
+        std::fs::write(&library, b"").unwrap();
+        std::fs::write(temp_dir.path().join("README.md"), b"").unwrap();
+
+        assert_eq!(resolve_library_path(temp_dir.path()).unwrap(), library);
+    }
+
+    #[test]
+    fn test_resolve_library_path_rejects_ambiguous_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["liba", "libb"] {
+            std::fs::write(
+                temp_dir
+                    .path()
+                    .join(format!("{}.{}", name, std::env::consts::DLL_EXTENSION)),
+                b"",
+            )
+            .unwrap();
+        }
+
+        assert!(resolve_library_path(temp_dir.path()).is_err());
+        assert!(resolve_library_path(&temp_dir.path().join("missing.so")).is_err());
+    }
+
+    #[test]
+    fn test_resolve_library_path_rejects_non_library() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("plugin.txt");
+        std::fs::write(&file, b"").unwrap();
+
+        assert!(resolve_library_path(&file).is_err());
+    }
+
+    #[test]
+    fn test_add_plugin_entry() {
+        let content = "[commands.build]\ncmd = \"cargo build\"\n";
+        let updated =
+            add_plugin_entry(content, "demo", std::path::Path::new("/plugins/libdemo.so")).unwrap();
+
+        let config: crate::config::schema::CommandsConfig = toml::from_str(&updated).unwrap();
+        let plugin = config.plugins.plugins.get("demo").unwrap();
+        assert_eq!(plugin.path, "/plugins/libdemo.so");
+        assert!(plugin.enabled);
+        assert!(config.commands.contains_key("build"));
+        assert!(updated.contains("[plugins.demo]"));
+        assert!(!updated.contains("[plugins]\n"));
+
+        assert!(add_plugin_entry(&updated, "demo", std::path::Path::new("/x.so")).is_err());
+    }
+
+    #[test]
+    fn test_register_invalid_library_leaves_config_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("commands.toml");
+        std::fs::write(&config_file, "[commands]\n").unwrap();
+        let library = temp_dir.path().join("libbroken.so");
+        std::fs::write(&library, b"not a library").unwrap();
+
+        assert!(register_plugin(&library, &config_file).is_err());
+        assert_eq!(
+            std::fs::read_to_string(&config_file).unwrap(),
+            "[commands]\n"
+        );
+    }
+
+    /// Serve a single HTTP response with `body` and return the library URL
+    #[cfg(feature = "plugin-download")]
+    fn serve_library_once(body: Vec<u8>) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let header = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(header.as_bytes());
+                let _ = stream.write_all(&body);
+            }
+        });
+        format!(
+            "http://{}/libdemo.{}",
+            addr,
+            std::env::consts::DLL_EXTENSION
+        )
+    }
+
+    #[cfg(feature = "plugin-download")]
+    #[test]
+    fn test_download_plugin_enforces_size_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let url = serve_library_once(vec![0u8; 2048]);
+
+        let err = download_plugin_with_limits(
+            &url,
+            temp_dir.path(),
+            std::time::Duration::from_secs(5),
+            1024,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("byte limit"), "{}", err);
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[cfg(feature = "plugin-download")]
+    #[test]
+    fn test_download_plugin_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/libdemo.{}",
+            listener.local_addr().unwrap(),
+            std::env::consts::DLL_EXTENSION
+        );
+        // Accept the connection but never answer
+        let handle = std::thread::spawn(move || {
+            let _stream = listener.accept();
+            std::thread::sleep(std::time::Duration::from_secs(2));
+        });
+
+        let err = download_plugin_with_limits(
+            &url,
+            temp_dir.path(),
+            std::time::Duration::from_millis(200),
+            1024,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Failed to download"), "{}", err);
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+        drop(handle);
+    }
+}
@@ -1,12 +1,15 @@
 //! Command removal functionality
 
-use crate::config::{loader::ConfigLoader, Language};
+use crate::config::loader::{save_config_atomic, ConfigLoader};
+use crate::config::Language;
+use crate::error::config_not_found;
 use crate::i18n::{get_message, MessageKey};
 use anyhow::{Context, Result};
 use colored::*;
 use std::io::{self, Write};
 use std::path::PathBuf;
 use tokio::fs;
+use toml_edit::{DocumentMut, Item};
 
 /// Handle the `remove` subcommand
 pub async fn handle_remove(id: String, force: bool, config_path: Option<PathBuf>) -> Result<()> {
@@ -72,6 +75,13 @@ pub async fn handle_remove(id: String, force: bool, config_path: Option<PathBuf>
                 get_message(MessageKey::RemovePlatformSpecific, lang)
             );
         }
+        crate::config::schema::CommandSpec::Script { file } => {
+            println!(
+                "  {} {}",
+                format!("{}:", get_message(MessageKey::LabelCommand, lang)).dimmed(),
+                file
+            );
+        }
     }
 
     // Show dependencies if any
@@ -119,27 +129,39 @@ pub async fn handle_remove(id: String, force: bool, config_path: Option<PathBuf>
         find_config_file().await?
     };
 
-    // Create backup
-    let backup_path = create_backup(&config_file_path).await?;
-    println!(
-        "{} {}: {}",
-        "✓".green().bold(),
-        get_message(MessageKey::LabelBackupCreated, lang),
-        backup_path.display()
-    );
-
-    // Remove command from config
-    let mut updated_config = config;
-    updated_config.commands.remove(&id);
-
-    // Serialize back to TOML
-    let toml_string = toml::to_string_pretty(&updated_config)
-        .context("Failed to serialize configuration to TOML")?;
-
-    // Write updated config
-    fs::write(&config_file_path, toml_string)
+    // Parse the file itself (preserves comments and formatting)
+    let toml_content = fs::read_to_string(&config_file_path)
         .await
-        .context("Failed to write configuration file")?;
+        .with_context(|| format!("Failed to read config file: {}", config_file_path.display()))?;
+    let mut doc = toml_content
+        .parse::<DocumentMut>()
+        .context("Failed to parse TOML file")?;
+
+    // Remove command from the document; it may only be defined in another
+    // (e.g. global) config file
+    let removed = doc
+        .get_mut("commands")
+        .and_then(Item::as_table_like_mut)
+        .and_then(|commands| commands.remove(&id))
+        .is_some();
+    if !removed {
+        anyhow::bail!(
+            "{}: {} ({})",
+            get_message(MessageKey::ErrorCommandNotFound, lang),
+            id,
+            config_file_path.display()
+        );
+    }
+
+    // Write updated config (backs up the original first)
+    if let Some(backup_path) = save_config_atomic(&config_file_path, &doc.to_string())? {
+        println!(
+            "{} {}: {}",
+            "✓".green().bold(),
+            get_message(MessageKey::LabelBackupCreated, lang),
+            backup_path.display()
+        );
+    }
 
     println!(
         "{} {} '{}'",
@@ -194,35 +216,10 @@ async fn find_config_file() -> Result<PathBuf> {
         }
     }
 
-    anyhow::bail!(
-        "{}",
-        get_message(MessageKey::ErrorNoConfigFileFound, Language::English)
-    )
-}
-
-/// Create a backup of the configuration file
-async fn create_backup(config_path: &PathBuf) -> Result<PathBuf> {
-    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-    let backup_filename = format!(
-        "{}.backup.{}",
-        config_path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("commands.toml"),
-        timestamp
-    );
-
-    let backup_path = if let Some(parent) = config_path.parent() {
-        parent.join(backup_filename)
-    } else {
-        PathBuf::from(backup_filename)
-    };
-
-    fs::copy(config_path, &backup_path)
-        .await
-        .context("Failed to create backup")?;
-
-    Ok(backup_path)
+    Err(config_not_found(
+        std::env::current_dir().unwrap_or_default(),
+        get_message(MessageKey::ErrorNoConfigFileFound, Language::English),
+    ))
 }
 
 #[cfg(test)]
@@ -290,40 +287,4 @@ cmd = "cargo test"
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
-
-    #[tokio::test]
-    async fn test_backup_creation() {
-        let temp_dir = TempDir::new().unwrap();
-        let config_path = temp_dir.path().join("commands.toml");
-
-        let toml_content = r#"
-[config]
-shell = "bash"
-
-[commands.test]
-description = "Run tests"
-cmd = "cargo test"
-"#;
-
-        let mut file = tokio::fs::File::create(&config_path).await.unwrap();
-        file.write_all(toml_content.as_bytes()).await.unwrap();
-        file.sync_all().await.unwrap(); // Ensure data is written to disk
-
-        // Create backup
-        let backup_path = create_backup(&config_path).await.unwrap();
-
-        // Verify backup exists
-        assert!(backup_path.exists());
-        assert!(backup_path
-            .file_name()
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .contains("backup"));
-
-        // Verify backup content matches original
-        let original_content = tokio::fs::read_to_string(&config_path).await.unwrap();
-        let backup_content = tokio::fs::read_to_string(&backup_path).await.unwrap();
-        assert_eq!(original_content, backup_content);
-    }
 }
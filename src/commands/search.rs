@@ -1,20 +1,74 @@
 //! Search command - Search commands by keyword
 
+use crate::cli::SearchField;
 use crate::config::loader::ConfigLoader;
-use crate::config::schema::CommandSpec;
+use crate::config::schema::{Command, CommandSpec, CommandsConfig};
 use crate::i18n::{get_message, MessageKey};
-use anyhow::Result;
+use anyhow::{bail, Result};
 use colored::*;
 use std::path::PathBuf;
 
-/// Search commands by keyword (case-insensitive)
+/// A command matching the search keywords
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub name: String,
+    pub description: String,
+    /// Fields a (non-negated) keyword was found in
+    pub matched_in: Vec<SearchField>,
+}
+
+/// Parsed search keywords
+///
+/// Keywords are matched case-insensitively. A keyword prefixed with `-`
+/// excludes commands matching it; a lone `-` is an ordinary keyword and a
+/// lone `--` is ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Keywords a command must match (all of them, or any with `any`)
+    pub include: Vec<String>,
+    /// Keywords a command must not match
+    pub exclude: Vec<String>,
+    /// Match any included keyword instead of all of them
+    pub any: bool,
+}
+
+impl SearchQuery {
+    /// Parse command-line keywords
+    pub fn parse(keywords: &[String], any: bool) -> Self {
+        let mut query = Self {
+            any,
+            ..Self::default()
+        };
+        for keyword in keywords.iter().filter(|k| k.as_str() != "--") {
+            match keyword.strip_prefix('-') {
+                Some(negated) if !negated.is_empty() => query.exclude.push(negated.to_lowercase()),
+                _ => query.include.push(keyword.to_lowercase()),
+            }
+        }
+        query
+    }
+}
+
+/// Search commands by keywords (case-insensitive)
+///
+/// `fields` restricts which fields are searched; empty means all fields.
 pub async fn handle_search(
-    keyword: String,
+    keywords: &[String],
+    any: bool,
+    fields: &[SearchField],
     global_only: bool,
-    config_path: Option<PathBuf>,
+    config_paths: Vec<PathBuf>,
 ) -> Result<()> {
-    let config_loader = if let Some(path) = config_path {
-        ConfigLoader::with_path(path)?
+    // Keywords take hyphenated values, so options after them end up here
+    if let Some(option) = keywords.iter().find(|k| k.starts_with("--") && k.len() > 2) {
+        bail!(
+            "Unexpected option '{}' after the search keywords; put options before the keywords",
+            option
+        );
+    }
+
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
     } else if global_only {
         ConfigLoader::global_only()
     } else {
@@ -22,8 +76,7 @@ pub async fn handle_search(
     };
     let config = config_loader.load().await?;
     let lang = config.config.language;
-
-    let keyword_lower = keyword.to_lowercase();
+    let keyword = keywords.join(" ");
 
     println!(
         "{}: '{}'",
@@ -34,65 +87,8 @@ pub async fn handle_search(
     );
     println!();
 
-    let mut results = Vec::new();
-
-    // Search through all commands
-    for (name, cmd) in &config.commands {
-        let mut matched = false;
-        let mut match_locations = Vec::new();
-
-        // Search in command ID
-        if name.to_lowercase().contains(&keyword_lower) {
-            matched = true;
-            match_locations.push("id");
-        }
-
-        // Search in description
-        if cmd.description.to_lowercase().contains(&keyword_lower) {
-            matched = true;
-            match_locations.push("description");
-        }
-
-        // Search in command text
-        let command_text = match &cmd.cmd {
-            CommandSpec::Single(c) => c.clone(),
-            CommandSpec::Multiple(cmds) => cmds.join(" "),
-            CommandSpec::Platform(p) => {
-                let mut parts = Vec::new();
-                if let Some(unix) = &p.unix {
-                    parts.push(unix.clone());
-                }
-                if let Some(linux) = &p.linux {
-                    parts.push(linux.clone());
-                }
-                if let Some(macos) = &p.macos {
-                    parts.push(macos.clone());
-                }
-                if let Some(windows) = &p.windows {
-                    parts.push(windows.clone());
-                }
-                parts.join(" ")
-            }
-        };
-
-        if command_text.to_lowercase().contains(&keyword_lower) {
-            matched = true;
-            match_locations.push("command");
-        }
-
-        // Search in tags
-        for tag in &cmd.tags {
-            if tag.to_lowercase().contains(&keyword_lower) {
-                matched = true;
-                match_locations.push("tags");
-                break;
-            }
-        }
-
-        if matched {
-            results.push((name.clone(), cmd.description.clone(), match_locations));
-        }
-    }
+    let query = SearchQuery::parse(keywords, any);
+    let results = search_commands(&config, &query, fields);
 
     // Display results
     if results.is_empty() {
@@ -114,16 +110,22 @@ pub async fn handle_search(
     );
     println!();
 
-    // Sort results alphabetically
-    results.sort_by(|a, b| a.0.cmp(&b.0));
-
-    for (name, description, locations) in results {
-        println!("  {} {} - {}", "•".blue(), name.green().bold(), description);
+    for result in results {
         println!(
-            "    {} {}",
-            get_message(MessageKey::SearchMatchedIn, lang).dimmed(),
-            locations.join(", ").dimmed()
+            "  {} {} - {}",
+            "•".blue(),
+            result.name.green().bold(),
+            result.description
         );
+        // Nothing to show when only excluding keywords were given
+        if !result.matched_in.is_empty() {
+            let locations: Vec<&str> = result.matched_in.iter().map(|f| f.label()).collect();
+            println!(
+                "    {} {}",
+                get_message(MessageKey::SearchMatchedIn, lang).dimmed(),
+                locations.join(", ").dimmed()
+            );
+        }
         println!();
     }
 
@@ -136,6 +138,96 @@ pub async fn handle_search(
     Ok(())
 }
 
+/// Find commands matching `query` within `fields`
+///
+/// A command matches when its included keywords are found (each in some
+/// field, or any one of them with `query.any`) and no excluded keyword is
+/// found in any field. Results are sorted by command name. Empty `fields`
+/// searches all fields.
+pub fn search_commands(
+    config: &CommandsConfig,
+    query: &SearchQuery,
+    fields: &[SearchField],
+) -> Vec<SearchMatch> {
+    let fields = if fields.is_empty() {
+        &SearchField::ALL[..]
+    } else {
+        fields
+    };
+    let searched: Vec<SearchField> = SearchField::ALL
+        .into_iter()
+        .filter(|field| fields.contains(field))
+        .collect();
+
+    let mut results: Vec<SearchMatch> = config
+        .commands
+        .iter()
+        .filter_map(|(name, cmd)| {
+            let matches = |keyword: &str| {
+                searched
+                    .iter()
+                    .any(|field| field_matches(*field, name, cmd, keyword))
+            };
+
+            let included = if query.any {
+                query.include.is_empty() || query.include.iter().any(|k| matches(k))
+            } else {
+                query.include.iter().all(|k| matches(k))
+            };
+            if !included || query.exclude.iter().any(|k| matches(k)) {
+                return None;
+            }
+
+            let matched_in: Vec<SearchField> = searched
+                .iter()
+                .copied()
+                .filter(|field| {
+                    query
+                        .include
+                        .iter()
+                        .any(|k| field_matches(*field, name, cmd, k))
+                })
+                .collect();
+
+            Some(SearchMatch {
+                name: name.clone(),
+                description: cmd.description.clone(),
+                matched_in,
+            })
+        })
+        .collect();
+
+    // Sort results alphabetically
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    results
+}
+
+fn field_matches(field: SearchField, name: &str, cmd: &Command, keyword_lower: &str) -> bool {
+    let contains = |text: &str| text.to_lowercase().contains(keyword_lower);
+
+    match field {
+        SearchField::Name => contains(name),
+        SearchField::Desc => contains(&cmd.description),
+        SearchField::Cmd => command_text(&cmd.cmd).iter().any(|c| contains(c)),
+        SearchField::Tags => cmd.tags.iter().any(|tag| contains(tag)),
+        SearchField::Category => cmd.category.as_deref().is_some_and(contains),
+    }
+}
+
+/// Every command string in the spec (all steps and platform variants)
+fn command_text(spec: &CommandSpec) -> Vec<&str> {
+    match spec {
+        CommandSpec::Single(c) => vec![c.as_str()],
+        CommandSpec::Multiple(cmds) => cmds.iter().map(String::as_str).collect(),
+        CommandSpec::Platform(p) => [&p.unix, &p.linux, &p.macos, &p.windows]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect(),
+        CommandSpec::Script { file } => vec![file.as_str()],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,7 +260,7 @@ cmd = "cargo test"
 "#;
         fs::write(&path, config).unwrap();
 
-        let result = handle_search("build".to_string(), false, Some(path)).await;
+        let result = handle_search(&["build".to_string()], false, &[], false, vec![path]).await;
         assert!(result.is_ok());
     }
 
@@ -184,7 +276,8 @@ cmd = "kubectl apply"
 "#;
         fs::write(&path, config).unwrap();
 
-        let result = handle_search("production".to_string(), false, Some(path)).await;
+        let result =
+            handle_search(&["production".to_string()], false, &[], false, vec![path]).await;
         assert!(result.is_ok());
     }
 
@@ -200,7 +293,8 @@ cmd = "echo hello"
 "#;
         fs::write(&path, config).unwrap();
 
-        let result = handle_search("nonexistent".to_string(), false, Some(path)).await;
+        let result =
+            handle_search(&["nonexistent".to_string()], false, &[], false, vec![path]).await;
         assert!(result.is_ok());
     }
 
@@ -217,7 +311,7 @@ tags = ["docker", "container", "build"]
 "#;
         fs::write(&path, config).unwrap();
 
-        let result = handle_search("docker".to_string(), false, Some(path)).await;
+        let result = handle_search(&["docker".to_string()], false, &[], false, vec![path]).await;
         assert!(result.is_ok());
     }
 
@@ -234,7 +328,7 @@ cmd = "npm run dev"
         fs::write(&path, config).unwrap();
 
         // Search with uppercase should still match
-        let result = handle_search("FRONTEND".to_string(), false, Some(path)).await;
+        let result = handle_search(&["FRONTEND".to_string()], false, &[], false, vec![path]).await;
         assert!(result.is_ok());
     }
 
@@ -257,7 +351,7 @@ windows = "dir"
 "#;
         fs::write(&path, config).unwrap();
 
-        let result = handle_search("step".to_string(), false, Some(path)).await;
+        let result = handle_search(&["step".to_string()], false, &[], false, vec![path]).await;
         assert!(result.is_ok());
     }
 
@@ -265,7 +359,7 @@ windows = "dir"
     async fn test_search_with_default_config_path() {
         // Test ConfigLoader::new() path (line 15)
         // This will fail if default config doesn't exist, but covers the code path
-        let result = handle_search("test".to_string(), false, None).await;
+        let result = handle_search(&["test".to_string()], false, &[], false, vec![]).await;
         // Result may be Ok or Err depending on default config existence
         // We just want to execute the ConfigLoader::new() code path
         let _ = result;
@@ -294,7 +388,7 @@ cmd = "echo middle"
 
         // Search for "Test" - should match all three commands
         // This covers line 112: results.sort_by(|a, b| a.0.cmp(&b.0));
-        let result = handle_search("Test".to_string(), false, Some(path)).await;
+        let result = handle_search(&["Test".to_string()], false, &[], false, vec![path]).await;
         assert!(result.is_ok());
     }
 
@@ -302,9 +396,188 @@ cmd = "echo middle"
     async fn test_search_with_global_only_mode() {
         // Test ConfigLoader::global_only() path (lines 18-19)
         // This covers the global_only=true branch
-        let result = handle_search("test".to_string(), true, None).await;
+        let result = handle_search(&["test".to_string()], false, &[], true, vec![]).await;
         // Result may be Ok or Err depending on global config existence
         // We just want to execute the ConfigLoader::global_only() code path
         let _ = result;
     }
+
+    fn search_config(toml: &str) -> CommandsConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn query(keywords: &[&str]) -> SearchQuery {
+        let keywords: Vec<String> = keywords.iter().map(|k| k.to_string()).collect();
+        SearchQuery::parse(&keywords, false)
+    }
+
+    fn names(results: &[SearchMatch]) -> Vec<&str> {
+        results.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    fn docker_config() -> CommandsConfig {
+        search_config(
+            r#"
+[commands.docker-build]
+description = "Build the image"
+cmd = "docker build -t app ."
+tags = ["docker"]
+
+[commands.docker-up]
+description = "Start services"
+cmd = "docker compose up"
+tags = ["docker", "compose"]
+
+[commands.fmt]
+description = "Format code"
+cmd = "cargo fmt"
+"#,
+        )
+    }
+
+    #[test]
+    fn test_search_query_parse() {
+        let keywords: Vec<String> = ["Docker", "--", "-Compose", "-"]
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        let query = SearchQuery::parse(&keywords, true);
+        assert_eq!(query.include, vec!["docker", "-"]);
+        assert_eq!(query.exclude, vec!["compose"]);
+        assert!(query.any);
+    }
+
+    #[test]
+    fn test_search_commands_all_keywords_must_match() {
+        let config = docker_config();
+
+        let results = search_commands(&config, &query(&["docker", "compose"]), &[]);
+        assert_eq!(names(&results), vec!["docker-up"]);
+
+        // Each keyword may match in a different field
+        let results = search_commands(&config, &query(&["image", "docker"]), &[]);
+        assert_eq!(names(&results), vec!["docker-build"]);
+        assert_eq!(
+            results[0].matched_in,
+            vec![
+                SearchField::Name,
+                SearchField::Desc,
+                SearchField::Cmd,
+                SearchField::Tags
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_commands_any_keyword() {
+        let config = docker_config();
+        let keywords = vec!["compose".to_string(), "fmt".to_string()];
+
+        let results = search_commands(&config, &SearchQuery::parse(&keywords, true), &[]);
+        assert_eq!(names(&results), vec!["docker-up", "fmt"]);
+
+        let results = search_commands(&config, &SearchQuery::parse(&keywords, false), &[]);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_search_commands_negation() {
+        let config = docker_config();
+
+        let results = search_commands(&config, &query(&["docker", "-compose"]), &[]);
+        assert_eq!(names(&results), vec!["docker-build"]);
+
+        // Exclusions apply only to the searched fields
+        let results = search_commands(
+            &config,
+            &query(&["docker", "-compose"]),
+            &[SearchField::Name],
+        );
+        assert_eq!(names(&results), vec!["docker-build", "docker-up"]);
+
+        // Only exclusions: every other command matches
+        let results = search_commands(&config, &query(&["-docker"]), &[]);
+        assert_eq!(names(&results), vec!["fmt"]);
+        assert!(results[0].matched_in.is_empty());
+    }
+
+    #[test]
+    fn test_search_commands_match_in_cmd_only() {
+        let config = search_config(
+            r#"
+[commands.image]
+description = "Build the image"
+cmd = ["cargo build", "docker build -t app ."]
+tags = ["release"]
+"#,
+        );
+
+        let results = search_commands(&config, &query(&["DOCKER"]), &[]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "image");
+        assert_eq!(results[0].matched_in, vec![SearchField::Cmd]);
+        assert_eq!(results[0].matched_in[0].label(), "cmd");
+    }
+
+    #[test]
+    fn test_search_commands_match_in_tags_only() {
+        let config = search_config(
+            r#"
+[commands.up]
+description = "Start services"
+cmd = "podman compose up"
+tags = ["Containers"]
+"#,
+        );
+
+        let results = search_commands(&config, &query(&["container"]), &[]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].matched_in, vec![SearchField::Tags]);
+        assert_eq!(results[0].matched_in[0].label(), "tags");
+    }
+
+    #[test]
+    fn test_search_commands_restricted_fields() {
+        let config = search_config(
+            r#"
+[commands.docker-up]
+description = "Start docker services"
+cmd = "docker compose up"
+category = "docker"
+
+[commands.lint]
+description = "Lint"
+cmd = "hadolint Dockerfile"
+"#,
+        );
+
+        let results = search_commands(&config, &query(&["docker"]), &[SearchField::Cmd]);
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["docker-up", "lint"]);
+        assert!(results
+            .iter()
+            .all(|r| r.matched_in == vec![SearchField::Cmd]));
+
+        let results = search_commands(
+            &config,
+            &query(&["docker"]),
+            &[SearchField::Name, SearchField::Desc],
+        );
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].matched_in,
+            vec![SearchField::Name, SearchField::Desc]
+        );
+
+        let results = search_commands(&config, &query(&["docker"]), &[]);
+        assert_eq!(
+            results[0].matched_in,
+            vec![
+                SearchField::Name,
+                SearchField::Desc,
+                SearchField::Cmd,
+                SearchField::Category
+            ]
+        );
+    }
 }
@@ -3,11 +3,17 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm, Select};
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use toml_edit::{DocumentMut, Item, Table};
 
-use crate::config::{loader::ConfigLoader, Language};
+use ahash::AHashMap;
+
+use crate::config::loader::{save_config_atomic, ConfigLoader};
+use crate::config::schema::{Command, CommandSpec};
+use crate::config::Language;
 use crate::i18n::{get_message, MessageKey};
-use crate::template::TemplateManager;
+use crate::template::{RemoteSource, TemplateManager, UserTemplate};
 
 /// Handle template add command
 pub async fn handle_template_add(name: Option<String>, config_path: Option<PathBuf>) -> Result<()> {
@@ -73,20 +79,37 @@ pub async fn handle_template_add(name: Option<String>, config_path: Option<PathB
 }
 
 /// Handle template use command
-pub async fn handle_template_use(name: String, output: Option<PathBuf>) -> Result<()> {
+///
+/// `set` holds `KEY=VALUE` overrides for template placeholders. Placeholders
+/// without an override are prompted for when stdin is a terminal.
+///
+/// With `merge`, only the template's commands are added to an existing
+/// output file (see [`merge_template_commands`]); without it the file is
+/// replaced after confirmation.
+pub async fn handle_template_use(
+    name: String,
+    output: Option<PathBuf>,
+    set: Vec<String>,
+    merge: bool,
+    overwrite: bool,
+) -> Result<()> {
     let manager = TemplateManager::new()?;
 
-    // Load template
-    let template = manager
-        .load(&name)
-        .with_context(|| format!("Failed to load template '{}'", name))?;
+    let values = parse_placeholder_values(&set)?;
+    let interactive = std::io::stdin().is_terminal();
 
-    // Convert to CommandsConfig
-    let config = template.to_commands_config();
+    // Load template and fill in placeholders
+    let config = manager
+        .apply(&name, &values, interactive)
+        .with_context(|| format!("Failed to load template '{}'", name))?;
 
     // Determine output path
     let output_path = output.unwrap_or_else(|| PathBuf::from("commands.toml"));
 
+    if merge && output_path.exists() {
+        return merge_template_into(&name, &config.commands, &output_path, overwrite);
+    }
+
     // Check if file exists
     if output_path.exists() {
         let confirm = Confirm::with_theme(&ColorfulTheme::default())
@@ -108,8 +131,7 @@ pub async fn handle_template_use(name: String, output: Option<PathBuf>) -> Resul
         toml::to_string_pretty(&config).context("Failed to serialize configuration to TOML")?;
 
     // Write to file
-    std::fs::write(&output_path, toml_content)
-        .with_context(|| format!("Failed to write to {}", output_path.display()))?;
+    save_config_atomic(&output_path, &toml_content)?;
 
     println!();
     println!(
@@ -128,6 +150,139 @@ pub async fn handle_template_use(name: String, output: Option<PathBuf>) -> Resul
     Ok(())
 }
 
+/// Commands affected by merging a template into an existing config
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// Commands that were not defined before
+    pub added: Vec<String>,
+    /// Existing commands replaced by the template's definition (`--overwrite`)
+    pub replaced: Vec<String>,
+    /// Existing commands kept because the template defines them too
+    pub skipped: Vec<String>,
+}
+
+fn merge_template_into(
+    name: &str,
+    commands: &AHashMap<String, Command>,
+    output_path: &std::path::Path,
+    overwrite: bool,
+) -> Result<()> {
+    let content = std::fs::read_to_string(output_path)
+        .with_context(|| format!("Failed to read config file: {}", output_path.display()))?;
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse TOML file: {}", output_path.display()))?;
+
+    let report = merge_template_commands(&mut doc, commands, overwrite)?;
+    if !report.added.is_empty() || !report.replaced.is_empty() {
+        save_config_atomic(output_path, &doc.to_string())?;
+    }
+
+    println!();
+    println!(
+        "{} Merged template '{}' into {}",
+        "✓".green().bold(),
+        name.cyan().bold(),
+        output_path.display().to_string().bright_white().bold()
+    );
+    for (label, names) in [
+        ("Added:", &report.added),
+        ("Replaced:", &report.replaced),
+        ("Skipped (already defined):", &report.skipped),
+    ] {
+        if !names.is_empty() {
+            println!("  {} {}", label.dimmed(), names.join(", "));
+        }
+    }
+    if !report.skipped.is_empty() {
+        println!(
+            "  {}",
+            "Use --overwrite to replace existing commands".dimmed()
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Add the template's `commands` to the `[commands]` table of `doc`
+///
+/// Commands already in `doc` are kept unless `overwrite` is set. Everything
+/// else in the document (`[config]`, other commands, comments) is left as is.
+/// New entries follow the file's style like `cmdrun add`: `[commands.<id>]`
+/// tables if the existing commands use them, otherwise inline tables.
+pub fn merge_template_commands(
+    doc: &mut DocumentMut,
+    commands: &AHashMap<String, Command>,
+    overwrite: bool,
+) -> Result<MergeReport> {
+    if !doc.contains_key("commands") {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        doc["commands"] = Item::Table(table);
+    }
+    let commands_table = doc["commands"]
+        .as_table_mut()
+        .context("`commands` in the existing config is not a table")?;
+
+    // An empty [commands] gets tables, as written by `template use` itself
+    let uses_subtables =
+        commands_table.is_empty() || commands_table.iter().any(|(_, item)| item.is_table());
+
+    let mut names: Vec<&String> = commands.keys().collect();
+    names.sort();
+
+    let mut report = MergeReport::default();
+    for id in names {
+        let exists = commands_table.contains_key(id);
+        if exists && !overwrite {
+            report.skipped.push(id.clone());
+            continue;
+        }
+
+        let entry = toml::to_string(&commands[id])
+            .with_context(|| format!("Failed to serialize command '{}'", id))?
+            .parse::<DocumentMut>()
+            .context("Failed to parse serialized command")?
+            .into_table()
+            .into_inline_table();
+        let entry = if uses_subtables {
+            Item::Table(entry.into_table())
+        } else {
+            Item::Value(entry.into())
+        };
+        commands_table.insert(id, entry);
+
+        if exists {
+            report.replaced.push(id.clone());
+        } else {
+            report.added.push(id.clone());
+        }
+    }
+
+    Ok(report)
+}
+
+/// Parse `KEY=VALUE` placeholder overrides
+fn parse_placeholder_values(set: &[String]) -> Result<AHashMap<String, String>> {
+    let mut values = AHashMap::new();
+
+    for entry in set {
+        let (key, value) = entry.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("Invalid --set value '{}': expected KEY=VALUE", entry)
+        })?;
+
+        let key = key.trim();
+        if key.is_empty() {
+            anyhow::bail!("Invalid --set value '{}': empty key", entry);
+        }
+
+        values.insert(key.to_string(), value.to_string());
+    }
+
+    Ok(values)
+}
+
 /// Handle template list command
 pub async fn handle_template_list(verbose: bool, language: Language) -> Result<()> {
     let manager = TemplateManager::new()?;
@@ -289,6 +444,155 @@ pub async fn handle_template_import(file: PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Handle template import from a URL or git repository
+///
+/// The fetched template is validated and previewed, then installed after
+/// confirmation (skipped with `yes`).
+pub async fn handle_template_import_remote(source: RemoteSource, yes: bool) -> Result<()> {
+    let manager = TemplateManager::new()?;
+
+    println!("Fetching {}", source.to_string().cyan());
+    let content = source.fetch()?;
+    let (template, problems) = manager.check_content(&content, &source.to_string())?;
+
+    print_template_preview(&template);
+
+    if !problems.is_empty() {
+        println!(
+            "{} {} problem(s) found in {}",
+            "✗".red().bold(),
+            problems.len(),
+            source.to_string().bright_white().bold()
+        );
+        for problem in &problems {
+            println!("  {} {}", "•".red(), problem);
+        }
+        println!();
+        anyhow::bail!("Template validation failed: {}", source);
+    }
+
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Refusing to import a remote template without confirmation (use --yes)");
+        }
+
+        let confirm = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Import template '{}'?", template.template.name))
+            .default(false)
+            .interact()?;
+
+        if !confirm {
+            println!("{}", get_message(MessageKey::Cancelled, Language::English));
+            return Ok(());
+        }
+    }
+
+    manager
+        .install(&template)
+        .with_context(|| format!("Failed to import template from {}", source))?;
+
+    println!();
+    println!(
+        "{} Template '{}' imported successfully",
+        "✓".green().bold(),
+        template.template.name.cyan().bold()
+    );
+    println!("  {} {}", "From:".dimmed(), source.to_string().dimmed());
+    println!();
+
+    Ok(())
+}
+
+/// Print template metadata and the commands it defines
+fn print_template_preview(template: &UserTemplate) {
+    println!();
+    println!(
+        "{} {}",
+        template.template.name.cyan().bold(),
+        format!("v{}", template.template.version).dimmed()
+    );
+    println!("  {}", template.template.description);
+    if let Some(author) = &template.template.author {
+        println!("  {} {}", "Author:".dimmed(), author);
+    }
+
+    let mut ids: Vec<&String> = template.commands.keys().collect();
+    ids.sort();
+    println!();
+    println!("  {}", "Commands:".bold());
+    for id in ids {
+        let command = &template.commands[id];
+        println!("    {} - {}", id.green(), command.description);
+        match &command.cmd {
+            CommandSpec::Single(cmd) => println!("      {}", cmd.dimmed()),
+            CommandSpec::Multiple(cmds) => {
+                for cmd in cmds {
+                    println!("      {}", cmd.dimmed());
+                }
+            }
+            CommandSpec::Platform(platform_cmds) => {
+                let variants = [
+                    ("unix", &platform_cmds.unix),
+                    ("linux", &platform_cmds.linux),
+                    ("macos", &platform_cmds.macos),
+                    ("windows", &platform_cmds.windows),
+                ];
+                for (platform, cmd) in variants {
+                    if let Some(cmd) = cmd {
+                        println!(
+                            "      {} {}",
+                            format!("[{}]", platform).dimmed(),
+                            cmd.dimmed()
+                        );
+                    }
+                }
+            }
+            CommandSpec::Script { file } => {
+                println!("      {} {}", "[script]".dimmed(), file.dimmed())
+            }
+        }
+    }
+    println!();
+}
+
+/// Handle template validate command
+pub async fn handle_template_validate(file: PathBuf) -> Result<()> {
+    let manager = TemplateManager::new()?;
+
+    let (template, problems) = manager
+        .check_file(&file)
+        .with_context(|| format!("Failed to validate template {}", file.display()))?;
+
+    println!();
+    if problems.is_empty() {
+        println!(
+            "{} Template '{}' is valid",
+            "✓".green().bold(),
+            template.template.name.cyan().bold()
+        );
+        println!(
+            "  {} {}",
+            "Commands:".dimmed(),
+            template.commands.len().to_string().dimmed()
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "{} {} problem(s) found in {}",
+        "✗".red().bold(),
+        problems.len(),
+        file.display().to_string().bright_white().bold()
+    );
+    for problem in &problems {
+        println!("  {} {}", "•".red(), problem);
+    }
+    println!();
+
+    anyhow::bail!("Template validation failed: {}", file.display())
+}
+
 /// Handle interactive template selection
 pub async fn select_template_interactive(language: Language) -> Result<String> {
     let manager = TemplateManager::new()?;
@@ -337,7 +641,14 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let output = temp_dir.path().join("commands.toml");
 
-        let result = handle_template_use("rust-cli".to_string(), Some(output.clone())).await;
+        let result = handle_template_use(
+            "rust-cli".to_string(),
+            Some(output.clone()),
+            Vec::new(),
+            false,
+            false,
+        )
+        .await;
         assert!(result.is_ok(), "Template use failed: {:?}", result.err());
         assert!(output.exists(), "Output file should exist");
 
@@ -346,6 +657,103 @@ mod tests {
         assert!(content.contains("[commands.build]"));
     }
 
+    fn template_commands() -> AHashMap<String, Command> {
+        toml::from_str::<crate::config::schema::CommandsConfig>(
+            r#"
+[commands.build]
+description = "Build (template)"
+cmd = "cargo build --release"
+
+[commands.test]
+description = "Test"
+cmd = "cargo test"
+
+[commands.test.env]
+RUST_BACKTRACE = "1"
+"#,
+        )
+        .unwrap()
+        .commands
+    }
+
+    const EXISTING: &str = r#"# my project
+[config]
+shell = "zsh"
+
+[commands.build]
+description = "Build (mine)"
+cmd = "make"
+
+[commands.deploy]
+cmd = "./deploy.sh"
+"#;
+
+    fn parse_merged(doc: &DocumentMut) -> crate::config::schema::CommandsConfig {
+        toml::from_str(&doc.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_merge_template_into_empty_config() {
+        let mut doc = DocumentMut::new();
+        let report = merge_template_commands(&mut doc, &template_commands(), false).unwrap();
+
+        assert_eq!(report.added, vec!["build", "test"]);
+        assert!(report.skipped.is_empty() && report.replaced.is_empty());
+
+        let merged = parse_merged(&doc);
+        assert_eq!(merged.commands.len(), 2);
+        assert_eq!(
+            merged.commands["test"].env.get("RUST_BACKTRACE"),
+            Some(&"1".to_string())
+        );
+        assert!(doc.to_string().contains("[commands.test]"));
+    }
+
+    #[test]
+    fn test_merge_template_skips_conflicts() {
+        let mut doc = EXISTING.parse::<DocumentMut>().unwrap();
+        let report = merge_template_commands(&mut doc, &template_commands(), false).unwrap();
+
+        assert_eq!(report.added, vec!["test"]);
+        assert_eq!(report.skipped, vec!["build"]);
+        assert!(report.replaced.is_empty());
+
+        let content = doc.to_string();
+        assert!(content.starts_with("# my project\n[config]\nshell = \"zsh\""));
+        let merged = parse_merged(&doc);
+        assert_eq!(merged.config.shell, "zsh");
+        assert_eq!(merged.commands["build"].description, "Build (mine)");
+        assert!(merged.commands.contains_key("deploy"));
+        assert!(merged.commands.contains_key("test"));
+    }
+
+    #[test]
+    fn test_merge_template_overwrite_replaces_conflicts() {
+        let mut doc = EXISTING.parse::<DocumentMut>().unwrap();
+        let report = merge_template_commands(&mut doc, &template_commands(), true).unwrap();
+
+        assert_eq!(report.added, vec!["test"]);
+        assert_eq!(report.replaced, vec!["build"]);
+        assert!(report.skipped.is_empty());
+
+        let merged = parse_merged(&doc);
+        assert_eq!(merged.commands["build"].description, "Build (template)");
+        assert!(merged.commands.contains_key("deploy"));
+        assert_eq!(merged.config.shell, "zsh");
+    }
+
+    #[test]
+    fn test_merge_template_matches_inline_style() {
+        let mut doc = "[commands]\nfmt = { cmd = \"cargo fmt\" }\n"
+            .parse::<DocumentMut>()
+            .unwrap();
+        merge_template_commands(&mut doc, &template_commands(), false).unwrap();
+
+        let content = doc.to_string();
+        assert!(!content.contains("[commands.build]"), "{}", content);
+        assert_eq!(parse_merged(&doc).commands.len(), 3);
+    }
+
     #[tokio::test]
     async fn test_handle_template_list() {
         let result = handle_template_list(false, Language::English).await;
@@ -361,4 +769,16 @@ mod tests {
         assert!(result.is_ok());
         assert!(output.exists());
     }
+
+    #[test]
+    fn test_parse_placeholder_values() {
+        let values =
+            parse_placeholder_values(&["project_name=foo".to_string(), "cmd=a=b".to_string()])
+                .unwrap();
+        assert_eq!(values.get("project_name"), Some(&"foo".to_string()));
+        assert_eq!(values.get("cmd"), Some(&"a=b".to_string()));
+
+        assert!(parse_placeholder_values(&["novalue".to_string()]).is_err());
+        assert!(parse_placeholder_values(&["=foo".to_string()]).is_err());
+    }
 }
@@ -1,11 +1,19 @@
 //! Validate command implementation
 
+use crate::command::dependency::DependencyGraph;
+use crate::command::executor::{CommandExecutor, ExecutionContext};
 use crate::config::loader::ConfigLoader;
-use crate::config::validation::{ConfigValidator, ValidationError};
+use crate::config::schema::{CommandSpec, CommandsConfig};
+use crate::config::validation::{
+    find_duplicate_commands, find_uncovered_platforms, find_unknown_keys, ConfigValidator,
+    ValidationError,
+};
 use crate::i18n::{get_message, MessageKey};
+use crate::plugin::PluginManager;
+use crate::security::validation::script_lines;
 use anyhow::{Context, Result};
 use colored::Colorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Validation report
 #[derive(Debug)]
@@ -88,23 +96,238 @@ impl Default for ValidationReport {
     }
 }
 
+/// A check listed by `cmdrun validate --explain`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationRule {
+    CommandIds,
+    Dependencies,
+    CircularDependencies,
+    AliasTargets,
+    CommandLines,
+    Security,
+    PlatformCoverage,
+}
+
+impl ValidationRule {
+    /// Every rule, in the order they are explained
+    pub const ALL: [ValidationRule; 7] = [
+        ValidationRule::CommandIds,
+        ValidationRule::Dependencies,
+        ValidationRule::CircularDependencies,
+        ValidationRule::AliasTargets,
+        ValidationRule::CommandLines,
+        ValidationRule::Security,
+        ValidationRule::PlatformCoverage,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ValidationRule::CommandIds => "command-ids",
+            ValidationRule::Dependencies => "dependencies",
+            ValidationRule::CircularDependencies => "circular-dependencies",
+            ValidationRule::AliasTargets => "alias-targets",
+            ValidationRule::CommandLines => "command-lines",
+            ValidationRule::Security => "security",
+            ValidationRule::PlatformCoverage => "platform-coverage",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ValidationRule::CommandIds => "Command IDs use only letters, digits, '-', '_' and ':'",
+            ValidationRule::Dependencies => {
+                "Every dependency and `when` predicate names a defined command"
+            }
+            ValidationRule::CircularDependencies => {
+                "No command depends on itself through its dependencies"
+            }
+            ValidationRule::AliasTargets => "Every alias points to a defined command",
+            ValidationRule::CommandLines => {
+                "Commands have a non-empty command line for this platform"
+            }
+            ValidationRule::Security => {
+                "Command lines pass the security checks run before execution"
+            }
+            ValidationRule::PlatformCoverage => {
+                "Platform-specific commands have a variant for every supported platform"
+            }
+        }
+    }
+
+    /// Whether problems found by this rule are only warnings
+    fn is_advisory(self) -> bool {
+        matches!(
+            self,
+            ValidationRule::Security | ValidationRule::PlatformCoverage
+        )
+    }
+}
+
+/// Result of one rule for `--explain`
+#[derive(Debug, Clone)]
+pub struct RuleResult {
+    pub rule: ValidationRule,
+    /// Problems found; empty if the rule passed
+    pub problems: Vec<String>,
+}
+
+impl RuleResult {
+    pub fn passed(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Run every validation rule on its own
+///
+/// Unlike [`ConfigValidator::validate`], a failing rule does not stop the
+/// rules after it.
+pub fn explain_rules(config: &CommandsConfig) -> Vec<RuleResult> {
+    let validator = ConfigValidator::new(config);
+    let first_error =
+        |result: Result<()>| result.err().map(|e| e.to_string()).into_iter().collect();
+
+    ValidationRule::ALL
+        .into_iter()
+        .map(|rule| {
+            let problems = match rule {
+                ValidationRule::CommandIds => first_error(validator.validate_command_names()),
+                ValidationRule::Dependencies => first_error(validator.validate_dependencies()),
+                ValidationRule::CircularDependencies => first_error(validator.validate_no_cycles()),
+                ValidationRule::AliasTargets => first_error(validator.validate_aliases()),
+                ValidationRule::CommandLines => first_error(validator.validate_commands()),
+                ValidationRule::Security => check_security(config),
+                ValidationRule::PlatformCoverage => find_uncovered_platforms(config)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            };
+            RuleResult { rule, problems }
+        })
+        .collect()
+}
+
+/// Check command lines the way the executor does before running them,
+/// honouring `strict_mode`, `allow_chaining` / `allow_subshells` and the allowlist
+fn check_security(config: &CommandsConfig) -> Vec<String> {
+    let executor = CommandExecutor::new(ExecutionContext {
+        strict: config.config.strict_mode,
+        allow_command_chaining: config.config.allow_command_chaining,
+        allow_subshells: config.config.allow_subshells,
+        command_allowlist: config.config.command_allowlist.clone(),
+        ..Default::default()
+    });
+
+    let mut names: Vec<&String> = config.commands.keys().collect();
+    names.sort();
+
+    let mut problems = Vec::new();
+    for name in names {
+        let command = &config.commands[name];
+        let validator = executor.build_validator_for_command(command);
+        let script;
+        let lines: Vec<&str> = match &command.cmd {
+            CommandSpec::Single(cmd) => vec![cmd],
+            CommandSpec::Multiple(cmds) => cmds.iter().map(String::as_str).collect(),
+            CommandSpec::Platform(platform_cmds) => [
+                &platform_cmds.unix,
+                &platform_cmds.linux,
+                &platform_cmds.macos,
+                &platform_cmds.windows,
+            ]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect(),
+            CommandSpec::Script { file } => match command
+                .script_path(file, config.config.strict_mode)
+                .and_then(|path| Ok(std::fs::read_to_string(path)?))
+            {
+                Ok(content) => {
+                    script = content;
+                    script_lines(&script).map(|(_, line)| line).collect()
+                }
+                Err(e) => {
+                    problems.push(format!(
+                        "Command '{}' script file cannot be read: {} ({})",
+                        name, file, e
+                    ));
+                    continue;
+                }
+            },
+        };
+        for line in lines {
+            let result = validator.validate(line);
+            if !result.is_safe() {
+                problems.push(format!(
+                    "Command '{}' would be blocked: {} ({})",
+                    name,
+                    line,
+                    result.reasons()
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+fn print_explain(results: &[RuleResult]) {
+    println!();
+    println!("{}", "Validation rules:".cyan().bold());
+
+    let width = ValidationRule::ALL
+        .iter()
+        .map(|rule| rule.name().len())
+        .max()
+        .unwrap_or(0);
+    for result in results {
+        let mark = if result.passed() {
+            "✓".green()
+        } else if result.rule.is_advisory() {
+            "⚠".yellow()
+        } else {
+            "✗".red()
+        };
+        println!(
+            "  {} {}  {}",
+            mark,
+            format!("{:<width$}", result.rule.name()).bold(),
+            result.rule.description().dimmed()
+        );
+        for problem in &result.problems {
+            println!("      {}", problem);
+        }
+    }
+}
+
 /// Handle validate command
+///
+/// With `explain`, every rule is listed with what it checks and whether it
+/// passed, in addition to the usual report.
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_validate(
-    path: Option<PathBuf>,
+    paths: Vec<PathBuf>,
     verbose: bool,
     check_cycles: bool,
+    graph_only: bool,
+    strict_schema: bool,
+    strict: bool,
+    fail_on_warning: bool,
+    explain: bool,
 ) -> Result<()> {
     // Load configuration first to get language setting
-    let config_loader = if let Some(p) = &path {
-        ConfigLoader::with_path(p.clone())?
+    let config_loader = if !paths.is_empty() {
+        ConfigLoader::with_paths(paths.clone())?
     } else {
         ConfigLoader::new()
     };
 
-    let config = config_loader
-        .load()
+    let mut loaded = config_loader
+        .load_with_environment_paths()
         .await
         .context("Failed to load configuration")?;
+    let mut config = std::mem::take(&mut loaded.config);
+    PluginManager::load_for_config(&mut config)?;
 
     let lang = config.config.language;
 
@@ -119,17 +342,65 @@ pub async fn handle_validate(
     );
     println!();
 
-    let config_path = path.unwrap_or_else(|| PathBuf::from("commands.toml"));
+    let config_files = if paths.is_empty() {
+        "commands.toml".to_string()
+    } else {
+        paths
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
     println!(
         "{} {} {}",
         "✓".green(),
         get_message(MessageKey::ValidateLoadedConfigFrom, lang),
-        config_path.display()
+        config_files
     );
 
+    if explain {
+        print_explain(&explain_rules(&config));
+    }
+
     // Create validation report
     let mut report = ValidationReport::new();
 
+    // Strict schema: report keys that serde would otherwise silently ignore
+    if strict_schema {
+        for config_file in loaded.paths() {
+            check_unknown_keys(config_file, &mut report)?;
+        }
+    }
+
+    // Commands silently overridden by a later config file
+    for duplicate in find_duplicate_commands(&loaded.command_sources) {
+        if strict {
+            report.add_error(duplicate.to_string());
+        } else {
+            report.add_warning(duplicate.to_string());
+        }
+    }
+
+    // Graph-only mode: check dependency existence and cycles, skip content validation
+    if graph_only {
+        println!();
+        println!(
+            "{}",
+            format!(
+                "{}...",
+                get_message(MessageKey::ValidateBuildingDependencyGraph, lang)
+            )
+            .cyan()
+        );
+
+        check_dependency_graph(&config, &mut report);
+        if !report.has_errors() {
+            report
+                .add_info(get_message(MessageKey::ValidateDependencyGraphBuilt, lang).to_string());
+        }
+        return print_summary(&report, &config, verbose, fail_on_warning, lang);
+    }
+
     // Create validator
     let validator = ConfigValidator::new(&config);
 
@@ -148,6 +419,11 @@ pub async fn handle_validate(
         }
     }
 
+    // Platform-specific commands with no variant for a supported platform
+    for uncovered in find_uncovered_platforms(&config) {
+        report.add_warning(uncovered.to_string());
+    }
+
     // Check circular dependencies if requested
     if check_cycles {
         println!();
@@ -160,9 +436,15 @@ pub async fn handle_validate(
             .cyan()
         );
 
+        let graph = DependencyGraph::new(&config);
         for (name, _) in &config.commands {
             if let Err(e) = validator.compute_execution_order(std::slice::from_ref(name)) {
-                report.add_error(format!("Circular dependency in '{}': {}", name, e));
+                // Show the commands forming the loop when the graph can find it
+                let detail = graph
+                    .find_cycle_from(name)
+                    .map(|cycle| cycle.join(" → "))
+                    .unwrap_or_else(|| e.to_string());
+                report.add_error(format!("Circular dependency in '{}': {}", name, detail));
             } else if verbose {
                 report.add_info(format!(
                     "✓ {} '{}'",
@@ -215,7 +497,10 @@ pub async fn handle_validate(
         );
 
         for (alias, target) in &config.aliases {
-            if config.commands.contains_key(target) {
+            let exists = config
+                .alias_command(alias)
+                .is_some_and(|command| config.commands.contains_key(&command));
+            if exists {
                 println!("  {} {} -> {}", "✓".green(), alias, target);
             } else {
                 report.add_error(format!(
@@ -267,7 +552,59 @@ pub async fn handle_validate(
         }
     }
 
-    // Print report
+    print_summary(&report, &config, verbose, fail_on_warning, lang)
+}
+
+/// Check that every dependency resolves and the dependency graph has no cycles
+fn check_dependency_graph(config: &CommandsConfig, report: &mut ValidationReport) {
+    let mut names: Vec<&String> = config.commands.keys().collect();
+    names.sort();
+
+    for name in names {
+        for dep in &config.commands[name].deps {
+            let resolved = config.alias_command(dep).unwrap_or_else(|| dep.clone());
+            if !config.commands.contains_key(&resolved) {
+                report.add_error(
+                    ValidationError::CommandNotFound {
+                        command: dep.clone(),
+                        referenced_by: name.clone(),
+                    }
+                    .to_string(),
+                );
+            }
+        }
+    }
+
+    if let Err(e) = DependencyGraph::new(config).check_cycles() {
+        report.add_error(match e {
+            crate::error::CmdrunError::Config(err) => err.to_string(),
+            other => other.to_string(),
+        });
+    }
+}
+
+/// Report unknown keys in a configuration file as errors
+fn check_unknown_keys(config_file: &Path, report: &mut ValidationReport) -> Result<()> {
+    let content = std::fs::read_to_string(config_file)
+        .with_context(|| format!("Failed to read {}", config_file.display()))?;
+
+    for err in find_unknown_keys(&content)? {
+        report.add_error(format!("{}: {}", config_file.display(), err));
+    }
+
+    Ok(())
+}
+
+/// Print the validation report and summary, failing if any errors were found
+///
+/// With `fail_on_warning`, warnings fail the validation as well.
+fn print_summary(
+    report: &ValidationReport,
+    config: &CommandsConfig,
+    verbose: bool,
+    fail_on_warning: bool,
+    lang: crate::config::schema::Language,
+) -> Result<()> {
     report.print(verbose, lang);
 
     // Print summary
@@ -280,6 +617,14 @@ pub async fn handle_validate(
             report.errors.len()
         );
         anyhow::bail!("{}", get_message(MessageKey::ValidationFailed, lang));
+    } else if fail_on_warning && !report.warnings.is_empty() {
+        println!(
+            "{} {} {} warning(s) (--fail-on-warning)",
+            "✗".red().bold(),
+            get_message(MessageKey::ValidateFailedWithErrors, lang),
+            report.warnings.len()
+        );
+        anyhow::bail!("{}", get_message(MessageKey::ValidationFailed, lang));
     } else {
         println!(
             "{} {} ({} {}, {} {})",
@@ -322,4 +667,51 @@ mod tests {
         assert_eq!(report.warnings.len(), 1);
         assert_eq!(report.info.len(), 1);
     }
+
+    #[test]
+    fn test_explain_rules() {
+        let config: CommandsConfig = toml::from_str(
+            r#"
+[commands.build]
+cmd = "cargo build"
+deps = ["fmt"]
+
+[commands.fmt]
+cmd = "cargo fmt"
+
+[commands.clean]
+cmd = "rm -rf target; echo cleaned"
+
+[aliases]
+b = "build"
+t = "test"
+"#,
+        )
+        .unwrap();
+
+        let results = explain_rules(&config);
+        let names: Vec<&str> = results.iter().map(|r| r.rule.name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "command-ids",
+                "dependencies",
+                "circular-dependencies",
+                "alias-targets",
+                "command-lines",
+                "security",
+                "platform-coverage",
+            ]
+        );
+
+        let failed: Vec<&str> = results
+            .iter()
+            .filter(|r| !r.passed())
+            .map(|r| r.rule.name())
+            .collect();
+        assert_eq!(failed, vec!["alias-targets", "security"]);
+        let security = &results[5];
+        assert_eq!(security.problems.len(), 1);
+        assert!(security.problems[0].starts_with("Command 'clean' would be blocked"));
+    }
 }
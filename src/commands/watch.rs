@@ -3,33 +3,78 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 
 use crate::command::executor::ExecutionContext;
 use crate::config::loader::ConfigLoader;
+use crate::config::schema::WatchSpec;
 use crate::i18n::{get_message, MessageKey};
 use crate::platform::shell::detect_shell;
+use crate::plugin::PluginManager;
 use crate::watch::{WatchConfig, WatchPattern, WatchRunner};
 
+/// Debounce delay used when neither the command line nor the command's
+/// `watch` spec sets one
+pub const DEFAULT_DEBOUNCE_MS: u64 = 500;
+
+/// Watch settings from the command line
+///
+/// Unset fields fall back to the command's `watch` spec.
+#[derive(Debug, Clone, Default)]
+pub struct WatchOptions {
+    pub paths: Vec<PathBuf>,
+    pub patterns: Vec<String>,
+    pub exclude: Vec<String>,
+    pub debounce_ms: Option<u64>,
+    pub ignore_gitignore: bool,
+    pub no_recursive: bool,
+}
+
+impl WatchOptions {
+    /// Fill unset fields from a command's `watch` spec
+    ///
+    /// Relative spec paths are resolved against `working_dir`; exclude
+    /// patterns from both sources are combined.
+    pub fn merge_spec(mut self, spec: &WatchSpec, working_dir: &Path) -> Self {
+        if self.paths.is_empty() {
+            self.paths = spec.paths.iter().map(|p| working_dir.join(p)).collect();
+        }
+        if self.patterns.is_empty() {
+            self.patterns = spec.patterns.clone();
+        }
+        for pattern in &spec.exclude {
+            if !self.exclude.contains(pattern) {
+                self.exclude.push(pattern.clone());
+            }
+        }
+        self.debounce_ms = self.debounce_ms.or(spec.debounce_ms);
+        self
+    }
+}
+
 /// Handle the watch command
 ///
 /// Monitors files and automatically executes commands when changes are detected.
 /// Supports glob patterns, debouncing, and gitignore integration.
-#[allow(clippy::too_many_arguments)]
 pub async fn handle_watch(
     command: String,
     args: Vec<String>,
-    paths: Vec<PathBuf>,
-    patterns: Vec<String>,
-    exclude: Vec<String>,
-    debounce_ms: u64,
-    ignore_gitignore: bool,
-    no_recursive: bool,
+    options: WatchOptions,
+    global_only: bool,
+    config_paths: Vec<PathBuf>,
 ) -> Result<()> {
     // Load cmdrun configuration
-    let config_loader = ConfigLoader::new();
-    let cmdrun_config = config_loader.load().await?;
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+    let mut cmdrun_config = config_loader.load().await?;
+    // Plugins may add or modify commands (and their watch specs)
+    PluginManager::load_for_config(&mut cmdrun_config)?;
     let lang = cmdrun_config.config.language;
 
     // Validate that the command exists in the configuration
@@ -46,32 +91,39 @@ pub async fn handle_watch(
         )
     })?;
 
+    // Command-line settings take precedence over the command's watch spec
+    let options = match &cmd_def.watch {
+        Some(spec) => options.merge_spec(spec, &cmdrun_config.config.working_dir),
+        None => options,
+    };
+    let debounce_ms = options.debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS);
+
     // Display watch configuration
     display_watch_info(
         &command,
         &args,
-        &paths,
-        &patterns,
-        &exclude,
+        &options.paths,
+        &options.patterns,
+        &options.exclude,
         debounce_ms,
         lang,
     );
 
     // Get base path (current directory if no paths specified)
-    let base_path = get_base_path(&paths)?;
+    let base_path = get_base_path(&options.paths)?;
 
     // Build watch configuration
     let watch_config = build_watch_config(
-        paths,
-        patterns,
-        exclude,
+        options.paths,
+        options.patterns,
+        options.exclude,
         debounce_ms,
-        ignore_gitignore,
-        no_recursive,
+        options.ignore_gitignore,
+        options.no_recursive,
     )?;
 
     // Create execution context for the command
-    let mut env = cmdrun_config.config.env.clone();
+    let mut env = cmdrun_config.config.resolved_env()?;
 
     // Add positional arguments as environment variables
     for (idx, arg) in args.iter().enumerate() {
@@ -91,6 +143,11 @@ pub async fn handle_watch(
         language: lang,
         allow_command_chaining: cmdrun_config.config.allow_command_chaining,
         allow_subshells: cmdrun_config.config.allow_subshells,
+        command_allowlist: cmdrun_config.config.command_allowlist.clone(),
+        allow_unsafe: false,
+        merge_stderr: cmdrun_config.config.merge_stderr,
+        kill_grace_ms: cmdrun_config.config.kill_grace_ms,
+        max_parallel: cmdrun_config.config.max_parallel,
     };
 
     // Create and run the watch runner with cmdrun integration
@@ -101,7 +158,9 @@ pub async fn handle_watch(
         exec_ctx,
         &base_path,
     )
-    .context("Failed to create watch runner")?;
+    .context("Failed to create watch runner")?
+    .with_commands(cmdrun_config.commands.clone())
+    .with_args(args.to_vec());
 
     // Set up signal handler
     let mut shutdown_rx = setup_signal_handler().await?;
@@ -428,4 +487,40 @@ mod tests {
         let config = config.unwrap();
         assert_eq!(config.debounce_ms, 1000);
     }
+
+    #[test]
+    fn test_watch_options_merge_spec() {
+        let spec = WatchSpec {
+            paths: vec![PathBuf::from("src"), PathBuf::from("/abs/dir")],
+            patterns: vec!["**/*.rs".to_string()],
+            exclude: vec!["**/gen/**".to_string()],
+            debounce_ms: Some(200),
+        };
+
+        let merged = WatchOptions::default().merge_spec(&spec, Path::new("/project"));
+        assert_eq!(
+            merged.paths,
+            vec![PathBuf::from("/project/src"), PathBuf::from("/abs/dir")]
+        );
+        assert_eq!(merged.patterns, vec!["**/*.rs".to_string()]);
+        assert_eq!(merged.exclude, vec!["**/gen/**".to_string()]);
+        assert_eq!(merged.debounce_ms, Some(200));
+
+        // Command-line values win; excludes are combined
+        let cli = WatchOptions {
+            paths: vec![PathBuf::from("tests")],
+            patterns: vec!["**/*.toml".to_string()],
+            exclude: vec!["**/tmp/**".to_string()],
+            debounce_ms: Some(1000),
+            ..Default::default()
+        };
+        let merged = cli.merge_spec(&spec, Path::new("/project"));
+        assert_eq!(merged.paths, vec![PathBuf::from("tests")]);
+        assert_eq!(merged.patterns, vec!["**/*.toml".to_string()]);
+        assert_eq!(
+            merged.exclude,
+            vec!["**/tmp/**".to_string(), "**/gen/**".to_string()]
+        );
+        assert_eq!(merged.debounce_ms, Some(1000));
+    }
 }
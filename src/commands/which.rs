@@ -0,0 +1,366 @@
+//! Which command - show where a command is defined and what it runs
+
+use crate::config::loader::{ConfigLoader, LoadedConfig};
+use crate::config::schema::{AliasTarget, Platform};
+use crate::error::{CmdrunError, ConfigError};
+use crate::utils::typo_detector::{TypoDetector, TypoDetectorConfig};
+use anyhow::Result;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Where a command or alias is defined
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefinitionSite {
+    pub file: PathBuf,
+    /// 1-based line of the definition, if it could be found in the file
+    pub line: Option<usize>,
+    /// The table holding the definition (`[commands.build]`, `[commands]`, `[aliases]`)
+    pub table: Option<String>,
+}
+
+/// Alias resolved by `which`
+#[derive(Debug, Clone)]
+pub struct AliasInfo {
+    pub name: String,
+    /// Alias target as written (`test -- --exact {1}`)
+    pub target: String,
+    pub site: Option<DefinitionSite>,
+}
+
+/// Result of looking up a command
+#[derive(Debug, Clone)]
+pub struct WhichReport {
+    /// Command that would run
+    pub name: String,
+    /// Set when the looked-up name is an alias
+    pub alias: Option<AliasInfo>,
+    /// File the effective definition comes from
+    pub site: DefinitionSite,
+    /// Files with an earlier definition that this one overrides, in merge order
+    pub overridden: Vec<PathBuf>,
+    pub platform: Platform,
+    /// Command lines for the current platform (`None` if there is no variant for it)
+    pub resolved: Option<Vec<String>>,
+}
+
+/// Handle which command
+pub async fn handle_which(id: &str, global_only: bool, config_paths: Vec<PathBuf>) -> Result<()> {
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+    let loaded = config_loader.load_with_environment_paths().await?;
+
+    let report = which(&loaded, id)?;
+    print_report(&report);
+
+    Ok(())
+}
+
+/// Look up `id` the way `cmdrun run` resolves it
+///
+/// Defined commands take precedence over aliases of the same name. Unknown
+/// names fail with typo suggestions when `typo_detection` is enabled.
+pub fn which(loaded: &LoadedConfig, id: &str) -> Result<WhichReport> {
+    let config = &loaded.config;
+
+    let (name, alias) = if config.commands.contains_key(id) {
+        (id.to_string(), None)
+    } else if let Some(target) = config.aliases.get(id) {
+        let command = AliasTarget::parse(target)
+            .map_err(|e| anyhow::anyhow!("Alias '{}': {}", id, e))?
+            .command;
+        let alias = AliasInfo {
+            name: id.to_string(),
+            target: target.clone(),
+            site: alias_site(loaded, id),
+        };
+        (command, Some(alias))
+    } else {
+        return Err(not_found(loaded, id));
+    };
+
+    let command = config
+        .commands
+        .get(&name)
+        .ok_or_else(|| not_found(loaded, &name))?;
+
+    let mut sources = loaded
+        .command_sources
+        .get(&name)
+        .cloned()
+        .unwrap_or_default();
+    let file = sources.pop().unwrap_or_default();
+    let (line, table) = read_definition(&file, "commands", &name).unzip();
+
+    let platform = Platform::current();
+    let resolved = command.cmd.resolve_for_platform(&platform);
+
+    Ok(WhichReport {
+        name,
+        alias,
+        site: DefinitionSite { file, line, table },
+        overridden: sources,
+        platform,
+        resolved,
+    })
+}
+
+/// Find the alias in the loaded files, starting with the one merged last
+fn alias_site(loaded: &LoadedConfig, name: &str) -> Option<DefinitionSite> {
+    loaded.paths().into_iter().rev().find_map(|file| {
+        read_definition(file, "aliases", name).map(|(line, table)| DefinitionSite {
+            file: file.to_path_buf(),
+            line: Some(line),
+            table: Some(table),
+        })
+    })
+}
+
+fn read_definition(file: &Path, table: &str, name: &str) -> Option<(usize, String)> {
+    let content = std::fs::read_to_string(file).ok()?;
+    find_definition(&content, table, name)
+}
+
+/// Locate `name` under `[table]` in TOML source
+///
+/// Recognises both `[table.name]` headers and `name = ...` keys inside
+/// `[table]`. Returns the 1-based line and the table as written.
+pub fn find_definition(content: &str, table: &str, name: &str) -> Option<(usize, String)> {
+    let mut current: Option<&str> = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if let Some(header) = line
+            .strip_prefix('[')
+            .filter(|rest| !rest.starts_with('['))
+            .and_then(|rest| rest.split(']').next())
+        {
+            let header = header.trim();
+            let mut parts = header.splitn(2, '.');
+            if parts.next().map(str::trim) == Some(table)
+                && parts
+                    .next()
+                    .is_some_and(|rest| key_matches(first_key(rest), name))
+            {
+                return Some((idx + 1, format!("[{}]", header)));
+            }
+            current = Some(header);
+            continue;
+        }
+
+        if current == Some(table) {
+            if let Some((key, _)) = line.split_once('=') {
+                if key_matches(first_key(key), name) {
+                    return Some((idx + 1, format!("[{}]", table)));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// First segment of a dotted key (`build.env` → `build`, `"a.b".cmd` → `"a.b"`)
+fn first_key(key: &str) -> &str {
+    let key = key.trim();
+    match key.chars().next() {
+        Some(quote @ ('"' | '\'')) => key[1..].find(quote).map_or(key, |end| &key[..end + 2]),
+        _ => key.split('.').next().unwrap_or(key).trim(),
+    }
+}
+
+fn key_matches(key: &str, name: &str) -> bool {
+    let key = key.trim();
+    key == name
+        || key
+            .strip_prefix('"')
+            .and_then(|k| k.strip_suffix('"'))
+            .or_else(|| key.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')))
+            == Some(name)
+}
+
+fn not_found(loaded: &LoadedConfig, id: &str) -> anyhow::Error {
+    let error = anyhow::Error::new(CmdrunError::from(ConfigError::CommandNotFound(
+        id.to_string(),
+    )));
+
+    let config = &loaded.config.config;
+    if !config.typo_detection {
+        return error;
+    }
+
+    let mut available: Vec<&str> = loaded
+        .config
+        .commands
+        .keys()
+        .chain(loaded.config.aliases.keys())
+        .map(String::as_str)
+        .collect();
+    available.sort();
+
+    let detector = TypoDetector::with_config(TypoDetectorConfig {
+        threshold: config.typo_threshold,
+        max_suggestions: 5,
+    });
+    let suggestions: Vec<&str> = detector
+        .suggest(id, &available)
+        .into_iter()
+        .map(|(suggestion, _)| suggestion)
+        .collect();
+    if suggestions.is_empty() {
+        return error;
+    }
+
+    error.context(format!(
+        "Command not found: {} (did you mean: {}?)",
+        id,
+        suggestions.join(", ")
+    ))
+}
+
+fn print_report(report: &WhichReport) {
+    if let Some(alias) = &report.alias {
+        println!(
+            "{} {} {}",
+            alias.name.cyan().bold(),
+            "is an alias for".dimmed(),
+            alias.target.bright_white()
+        );
+        if let Some(site) = &alias.site {
+            println!("  {} {}", "Defined in:".dimmed(), format_site(site));
+        }
+        println!();
+    }
+
+    println!("{}", report.name.cyan().bold());
+    println!("  {} {}", "Defined in:".dimmed(), format_site(&report.site));
+    for file in report.overridden.iter().rev() {
+        println!("  {} {}", "Overrides: ".dimmed(), file.display());
+    }
+
+    let platform = format!("{:?}", report.platform).to_lowercase();
+    match &report.resolved {
+        Some(lines) => {
+            println!("  {} ({}):", "Runs".dimmed(), platform);
+            for line in lines {
+                println!("    {}", line.bright_white());
+            }
+        }
+        None => println!(
+            "  {} {}",
+            "Runs:".dimmed(),
+            format!("nothing (no variant for {})", platform).yellow()
+        ),
+    }
+}
+
+fn format_site(site: &DefinitionSite) -> String {
+    let mut text = site.file.display().to_string();
+    if let Some(line) = site.line {
+        text.push_str(&format!(":{}", line));
+    }
+    if let Some(table) = &site.table {
+        text.push_str(&format!(" {}", table));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[config]
+shell = "bash"
+
+[commands]
+fmt = { cmd = "cargo fmt" }
+"quoted.name" = { cmd = "true" }
+
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+
+[commands.build.env]
+RUST_LOG = "info"
+
+[aliases]
+b = "build"
+"#;
+
+    #[test]
+    fn test_find_definition() {
+        assert_eq!(
+            find_definition(CONFIG, "commands", "build"),
+            Some((9, "[commands.build]".to_string()))
+        );
+        assert_eq!(
+            find_definition(CONFIG, "commands", "fmt"),
+            Some((6, "[commands]".to_string()))
+        );
+        assert_eq!(
+            find_definition(CONFIG, "commands", "quoted.name"),
+            Some((7, "[commands]".to_string()))
+        );
+        assert_eq!(
+            find_definition(CONFIG, "aliases", "b"),
+            Some((17, "[aliases]".to_string()))
+        );
+
+        // `shell` under [config] and `b` as a command are not definitions
+        assert_eq!(find_definition(CONFIG, "commands", "shell"), None);
+        assert_eq!(find_definition(CONFIG, "commands", "b"), None);
+    }
+
+    fn loaded(dir: &Path) -> LoadedConfig {
+        let file = dir.join("commands.toml");
+        std::fs::write(&file, CONFIG).unwrap();
+
+        let config = toml::from_str(CONFIG).unwrap();
+        let mut loaded = LoadedConfig {
+            config,
+            global_path: None,
+            local_path: Some(file.clone()),
+            base_paths: Vec::new(),
+            environment_path: None,
+            command_sources: Default::default(),
+        };
+        for name in ["fmt", "quoted.name", "build"] {
+            loaded
+                .command_sources
+                .insert(name.to_string(), vec![file.clone()]);
+        }
+        loaded
+    }
+
+    #[test]
+    fn test_which_alias_resolves_to_command() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let report = which(&loaded(temp_dir.path()), "b").unwrap();
+
+        assert_eq!(report.name, "build");
+        let alias = report.alias.unwrap();
+        assert_eq!(alias.target, "build");
+        assert_eq!(alias.site.unwrap().line, Some(17));
+        assert_eq!(report.site.line, Some(9));
+        assert_eq!(report.resolved, Some(vec!["cargo build".to_string()]));
+    }
+
+    #[test]
+    fn test_which_unknown_command_suggests_names() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let error = which(&loaded(temp_dir.path()), "bulid").unwrap_err();
+
+        assert!(
+            error.to_string().contains("did you mean: build?"),
+            "{}",
+            error
+        );
+        assert_eq!(crate::error::process_exit_code(&error), 3);
+    }
+}
@@ -0,0 +1,249 @@
+//! Dotenv file support
+//!
+//! `env_file` で指定された `.env` ファイル（`KEY=VALUE` 形式）の読み込み
+
+use ahash::AHashMap;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// `env_file` を読み込む
+///
+/// 相対パスは `base_dir` 基準で解決する。末尾が `?` のパスは任意扱いで、
+/// ファイルが存在しなければ `None` を返す
+pub fn load_env_file(spec: &str, base_dir: &Path) -> Result<Option<AHashMap<String, String>>> {
+    let (path, optional) = match spec.strip_suffix('?') {
+        Some(path) => (path, true),
+        None => (spec, false),
+    };
+    let path = resolve_path(path, base_dir);
+
+    if !path.is_file() {
+        if optional {
+            debug!("Optional env_file not found: {}", path.display());
+            return Ok(None);
+        }
+        anyhow::bail!(
+            "env_file not found: {} (append '?' to make it optional)",
+            path.display()
+        );
+    }
+
+    // 呼び出し元で context に埋もれないよう、原因をメッセージに含める
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read env_file {}: {}", path.display(), e))?;
+    let vars = parse(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse env_file {}: {}", path.display(), e))?;
+
+    debug!("Loaded {} variables from {}", vars.len(), path.display());
+    Ok(Some(vars))
+}
+
+/// 環境変数ファイルのパスを解決
+fn resolve_path(path: &str, base_dir: &Path) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base_dir.join(path)
+    }
+}
+
+/// dotenv 形式の内容をパース
+///
+/// - 空行と `#` で始まる行は無視
+/// - 先頭の `export ` は省略可能
+/// - `"..."` はエスケープ（`\n`, `\t`, `\"`, `\\` など）を展開し、複数行に渡ってよい
+/// - `'...'` は内容をそのまま扱う
+/// - クォートなしの値は空白に続く `#` 以降をコメントとして除去
+pub fn parse(content: &str) -> Result<AHashMap<String, String>> {
+    let mut vars = AHashMap::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((idx, line)) = lines.next() {
+        let line_no = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line
+            .strip_prefix("export ")
+            .map(str::trim_start)
+            .unwrap_or(line);
+
+        let (key, rest) = line
+            .split_once('=')
+            .with_context(|| format!("line {}: expected KEY=VALUE", line_no))?;
+        let key = key.trim();
+        if !is_valid_key(key) {
+            anyhow::bail!("line {}: invalid variable name '{}'", line_no, key);
+        }
+
+        let rest = rest.trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let mut raw = rest[1..].to_string();
+                loop {
+                    if let Some(end) = find_closing_quote(&raw, quote) {
+                        let trailing = raw[end + 1..].trim();
+                        if !trailing.is_empty() && !trailing.starts_with('#') {
+                            anyhow::bail!(
+                                "line {}: unexpected characters after closing quote",
+                                line_no
+                            );
+                        }
+                        raw.truncate(end);
+                        break;
+                    }
+                    match lines.next() {
+                        Some((_, next)) => {
+                            raw.push('\n');
+                            raw.push_str(next);
+                        }
+                        None => anyhow::bail!("line {}: unterminated quoted value", line_no),
+                    }
+                }
+                if quote == '"' {
+                    unescape(&raw)
+                } else {
+                    raw
+                }
+            }
+            _ => strip_inline_comment(rest).trim_end().to_string(),
+        };
+
+        vars.insert(key.to_string(), value);
+    }
+
+    Ok(vars)
+}
+
+/// 変数名として有効か（英字または `_` で始まり、英数字・`_`・`.` のみ）
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// 閉じクォートの位置を探す（ダブルクォート内ではバックスラッシュでエスケープ可能）
+fn find_closing_quote(s: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && quote == '"' {
+            escaped = true;
+        } else if c == quote {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// ダブルクォート内のエスケープシーケンスを展開
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(c @ ('"' | '\\' | '$')) => out.push(c),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// クォートなしの値から行末コメントを除去
+fn strip_inline_comment(value: &str) -> &str {
+    let mut prev_whitespace = false;
+    for (i, c) in value.char_indices() {
+        if c == '#' && prev_whitespace {
+            return &value[..i];
+        }
+        prev_whitespace = c.is_whitespace();
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_basic_and_comments() {
+        let vars = parse(
+            r#"
+# database settings
+DB_HOST=localhost
+export DB_PORT = 5432
+EMPTY=
+URL=http://example.com/#anchor # trailing comment
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(vars["DB_HOST"], "localhost");
+        assert_eq!(vars["DB_PORT"], "5432");
+        assert_eq!(vars["EMPTY"], "");
+        assert_eq!(vars["URL"], "http://example.com/#anchor");
+        assert_eq!(vars.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_quoted_values() {
+        let vars = parse(
+            r#"
+DOUBLE="hello # not a comment"
+ESCAPED="line1\nline2 \"quoted\" \\ end"
+SINGLE='raw \n $HOME # kept'
+SPACES="  padded  "  # comment after quote
+MULTI="first
+second"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(vars["DOUBLE"], "hello # not a comment");
+        assert_eq!(vars["ESCAPED"], "line1\nline2 \"quoted\" \\ end");
+        assert_eq!(vars["SINGLE"], "raw \\n $HOME # kept");
+        assert_eq!(vars["SPACES"], "  padded  ");
+        assert_eq!(vars["MULTI"], "first\nsecond");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("NO_EQUALS_SIGN").is_err());
+        assert!(parse("1BAD=value").is_err());
+        assert!(parse("KEY=\"unterminated").is_err());
+        assert!(parse("KEY=\"value\" garbage").is_err());
+    }
+
+    #[test]
+    fn test_load_env_file_optional_and_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(".env"), "TOKEN=abc\n").unwrap();
+
+        let vars = load_env_file(".env", temp_dir.path()).unwrap().unwrap();
+        assert_eq!(vars["TOKEN"], "abc");
+
+        assert!(load_env_file(".env.local?", temp_dir.path())
+            .unwrap()
+            .is_none());
+
+        let err = load_env_file(".env.local", temp_dir.path()).unwrap_err();
+        assert!(err.to_string().contains("env_file not found"));
+    }
+}
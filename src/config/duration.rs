@@ -0,0 +1,193 @@
+//! 時間指定（タイムアウト等）のパースとシリアライズ
+//!
+//! `"500ms"` / `"30s"` / `"5m"` / `"1h"` の文字列と、秒数として扱う整数（従来の形式）を受け付ける
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serializer};
+use std::fmt;
+use std::time::Duration;
+use thiserror::Error;
+
+/// 時間指定のパースエラー
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid duration '{0}' (expected e.g. 500ms, 30s, 5m, 1h or a number of seconds)")]
+pub struct ParseDurationError(String);
+
+/// 時間指定をパース
+///
+/// 単位なしの数値は秒として扱う
+pub fn parse_duration(input: &str) -> Result<Duration, ParseDurationError> {
+    let error = || ParseDurationError(input.to_string());
+
+    let trimmed = input.trim();
+    let split = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let (digits, unit) = trimmed.split_at(split);
+    let value: u64 = digits.parse().map_err(|_| error())?;
+
+    let millis = match unit.trim_start() {
+        "ms" => Some(value),
+        "" | "s" => value.checked_mul(1_000),
+        "m" => value.checked_mul(60_000),
+        "h" => value.checked_mul(3_600_000),
+        _ => None,
+    };
+    millis.map(Duration::from_millis).ok_or_else(error)
+}
+
+/// 表示用の時間指定（秒単位で割り切れる場合は `"30s"`、それ以外は `"500ms"`）
+///
+/// [`parse_duration`] で元の値に戻せる
+pub fn format_duration(duration: Duration) -> String {
+    if duration.subsec_nanos() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+/// `#[serde(with = "crate::config::duration")]` 用
+///
+/// 秒単位で割り切れる値は従来どおり整数（秒）として書き出す
+pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    if duration.subsec_nanos() == 0 {
+        serializer.serialize_u64(duration.as_secs())
+    } else {
+        serializer.serialize_str(&format_duration(*duration))
+    }
+}
+
+/// `#[serde(with = "crate::config::duration")]` 用
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    DurationValue::deserialize(deserializer).map(|value| value.0)
+}
+
+/// `Option<Duration>` 用（`#[serde(with = "crate::config::duration::option")]`）
+pub mod option {
+    use super::DurationValue;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => super::serialize(duration, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Option::<DurationValue>::deserialize(deserializer).map(|value| value.map(|v| v.0))
+    }
+}
+
+struct DurationValue(Duration);
+
+impl<'de> Deserialize<'de> for DurationValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DurationVisitor;
+
+        impl Visitor<'_> for DurationVisitor {
+            type Value = DurationValue;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a number of seconds or a duration like \"30s\"")
+            }
+
+            fn visit_u64<E: de::Error>(self, secs: u64) -> Result<Self::Value, E> {
+                Ok(DurationValue(Duration::from_secs(secs)))
+            }
+
+            fn visit_i64<E: de::Error>(self, secs: i64) -> Result<Self::Value, E> {
+                u64::try_from(secs)
+                    .map(|secs| DurationValue(Duration::from_secs(secs)))
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(secs), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                parse_duration(value).map(DurationValue).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(DurationVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Timeouts {
+        #[serde(with = "crate::config::duration")]
+        timeout: Duration,
+        #[serde(default, with = "crate::config::duration::option")]
+        optional: Option<Duration>,
+    }
+
+    #[test]
+    fn test_parse_each_unit() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("45"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_duration(" 10 s "), Ok(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for input in [
+            "",
+            "s",
+            "-5s",
+            "1.5s",
+            "10 minutes",
+            "5d",
+            "18446744073709551615h",
+        ] {
+            assert!(parse_duration(input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_strings_and_legacy_integers() {
+        let parsed: Timeouts = toml::from_str("timeout = \"500ms\"\noptional = \"2m\"").unwrap();
+        assert_eq!(parsed.timeout, Duration::from_millis(500));
+        assert_eq!(parsed.optional, Some(Duration::from_secs(120)));
+
+        // 単位なしの整数は秒
+        let parsed: Timeouts = toml::from_str("timeout = 30").unwrap();
+        assert_eq!(parsed.timeout, Duration::from_secs(30));
+        assert_eq!(parsed.optional, None);
+
+        assert!(toml::from_str::<Timeouts>("timeout = -1").is_err());
+        assert!(toml::from_str::<Timeouts>("timeout = \"soon\"").is_err());
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let timeouts = Timeouts {
+            timeout: Duration::from_secs(300),
+            optional: Some(Duration::from_millis(1500)),
+        };
+        let serialized = toml::to_string(&timeouts).unwrap();
+        assert_eq!(serialized, "timeout = 300\noptional = \"1500ms\"\n");
+
+        let parsed: Timeouts = toml::from_str(&serialized).unwrap();
+        assert_eq!(parsed.timeout, timeouts.timeout);
+        assert_eq!(parsed.optional, timeouts.optional);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(30)), "30s");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1500ms");
+    }
+}
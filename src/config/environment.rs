@@ -2,7 +2,7 @@
 //!
 //! 環境別設定の管理と切り替え機能
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -34,6 +34,67 @@ impl Default for EnvironmentConfig {
     }
 }
 
+impl EnvironmentConfig {
+    /// 継承チェーンを取得（自身から祖先の順、`default` は含まない）
+    ///
+    /// 親が存在しない場合や継承が循環している場合はエラー
+    pub fn inheritance_chain<'a>(&'a self, env_name: &'a str) -> Result<Vec<&'a str>> {
+        let mut chain = Vec::new();
+        let mut visited = AHashSet::new();
+        let mut current = Some(env_name);
+
+        while let Some(name) = current {
+            if name == "default" {
+                break;
+            }
+            if !visited.insert(name) {
+                chain.push(name);
+                anyhow::bail!("Circular environment inheritance: {}", chain.join(" -> "));
+            }
+
+            let env = match self.environments.get(name) {
+                Some(env) => env,
+                None if chain.is_empty() => anyhow::bail!(
+                    "{}: '{}'",
+                    get_message(MessageKey::EnvErrorNotFound, Language::English),
+                    name
+                ),
+                None => anyhow::bail!(
+                    "Environment '{}' extends unknown environment '{}'",
+                    chain[chain.len() - 1],
+                    name
+                ),
+            };
+
+            chain.push(name);
+            current = env.extends.as_deref();
+        }
+
+        Ok(chain)
+    }
+
+    /// 継承を解決した環境変数を取得（子の値が親の値を上書き）
+    pub fn resolve_variables(&self, env_name: &str) -> Result<AHashMap<String, String>> {
+        let chain = self.inheritance_chain(env_name)?;
+
+        let mut variables = AHashMap::new();
+        for name in chain.iter().rev() {
+            variables.extend(self.environments[*name].variables.clone());
+        }
+        Ok(variables)
+    }
+
+    /// すべての環境の継承（親の存在と循環がないこと）を検証
+    pub fn validate_inheritance(&self) -> Result<()> {
+        let mut names: Vec<&String> = self.environments.keys().collect();
+        names.sort();
+        for name in names {
+            self.inheritance_chain(name)?;
+        }
+        Ok(())
+    }
+}
+
 /// 環境定義
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Environment {
@@ -41,6 +102,10 @@ pub struct Environment {
     #[serde(default)]
     pub description: String,
 
+    /// 継承元の環境名（環境変数を引き継ぎ、同じキーは上書きする）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+
     /// 環境変数
     #[serde(default)]
     pub variables: AHashMap<String, String>,
@@ -58,6 +123,7 @@ impl Default for Environment {
     fn default() -> Self {
         Self {
             description: String::new(),
+            extends: None,
             variables: AHashMap::new(),
             config_file: None,
             env_file: None,
@@ -118,6 +184,13 @@ impl EnvironmentManager {
                 .clone()
                 .try_into()
                 .context("Failed to parse environment section")?;
+            env_config.validate_inheritance().map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid environment config {}: {}",
+                    config_path.display(),
+                    e
+                )
+            })?;
             Ok(env_config)
         } else {
             // 後方互換性のため、デフォルトで空の設定を返す
@@ -213,7 +286,7 @@ impl EnvironmentManager {
         if overlay.config.shell != "bash" && overlay.config.shell != "pwsh" {
             base.config.shell = overlay.config.shell;
         }
-        if overlay.config.timeout != 300 {
+        if overlay.config.timeout != crate::config::schema::default_timeout() {
             base.config.timeout = overlay.config.timeout;
         }
         base.config.parallel |= overlay.config.parallel;
@@ -221,6 +294,12 @@ impl EnvironmentManager {
         base
     }
 
+    /// 継承を解決した環境の環境変数を取得
+    pub async fn resolve_variables(&self, env_name: &str) -> Result<AHashMap<String, String>> {
+        let config = self.load_environment_config().await?;
+        config.resolve_variables(env_name)
+    }
+
     /// 現在の環境名を取得
     pub async fn get_current_environment(&self) -> Result<String> {
         let config = self.load_environment_config().await?;
@@ -307,6 +386,7 @@ impl EnvironmentManager {
             name.clone(),
             Environment {
                 description,
+                extends: None,
                 variables: AHashMap::new(),
                 config_file: None,
                 env_file: None,
@@ -418,4 +498,107 @@ mod tests {
         assert!(envs.iter().any(|(name, _)| name == "dev"));
         assert!(envs.iter().any(|(name, _)| name == "staging"));
     }
+
+    fn test_environment(extends: Option<&str>, variables: &[(&str, &str)]) -> Environment {
+        Environment {
+            extends: extends.map(str::to_string),
+            variables: variables
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn inheritance_config() -> EnvironmentConfig {
+        let mut config = EnvironmentConfig::default();
+        config.environments.insert(
+            "base".to_string(),
+            test_environment(None, &[("REGION", "eu"), ("LOG_LEVEL", "info")]),
+        );
+        config.environments.insert(
+            "staging".to_string(),
+            test_environment(
+                Some("base"),
+                &[("API_URL", "https://staging"), ("LOG_LEVEL", "debug")],
+            ),
+        );
+        config.environments.insert(
+            "prod".to_string(),
+            test_environment(Some("staging"), &[("API_URL", "https://prod")]),
+        );
+        config
+    }
+
+    #[test]
+    fn test_single_level_inheritance() {
+        let config = inheritance_config();
+
+        let vars = config.resolve_variables("staging").unwrap();
+        assert_eq!(vars.len(), 3);
+        assert_eq!(vars["REGION"], "eu");
+        assert_eq!(vars["LOG_LEVEL"], "debug");
+        assert_eq!(vars["API_URL"], "https://staging");
+    }
+
+    #[test]
+    fn test_multi_level_inheritance() {
+        let config = inheritance_config();
+
+        assert_eq!(
+            config.inheritance_chain("prod").unwrap(),
+            vec!["prod", "staging", "base"]
+        );
+        let vars = config.resolve_variables("prod").unwrap();
+        assert_eq!(vars["REGION"], "eu");
+        assert_eq!(vars["LOG_LEVEL"], "debug");
+        assert_eq!(vars["API_URL"], "https://prod");
+        assert!(config.validate_inheritance().is_ok());
+    }
+
+    #[test]
+    fn test_inheritance_errors() {
+        let mut config = inheritance_config();
+        config
+            .environments
+            .insert("qa".to_string(), test_environment(Some("missing"), &[]));
+        let err = config.resolve_variables("qa").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Environment 'qa' extends unknown environment 'missing'"
+        );
+
+        config.environments.remove("qa");
+        config.environments.get_mut("base").unwrap().extends = Some("prod".to_string());
+        let err = config.validate_inheritance().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Circular environment inheritance: base -> prod -> staging -> base"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_rejects_inheritance_cycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join(".cmdrun");
+        fs::create_dir_all(&config_dir).await.unwrap();
+        fs::write(
+            config_dir.join("config.toml"),
+            r#"
+[environment.environments.a]
+extends = "b"
+
+[environment.environments.b]
+extends = "a"
+"#,
+        )
+        .await
+        .unwrap();
+
+        let manager = EnvironmentManager::new(config_dir);
+        let err = manager.load_environment_config().await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Circular environment inheritance: a -> b -> a"));
+    }
 }
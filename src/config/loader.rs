@@ -2,11 +2,17 @@
 //!
 //! TOML 設定ファイルの読み込みと階層的なマージ処理
 
+use crate::config::paths::{expand_path, expand_path_buf};
 use crate::config::schema::CommandsConfig;
+use crate::config::validation::ConfigValidator;
 use crate::config::Language;
+use crate::error::config_not_found;
 use crate::i18n::{get_message, MessageKey};
+use ahash::{AHashMap, AHashSet};
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 use tokio::fs;
 use tracing::{debug, info};
 
@@ -17,54 +23,168 @@ pub struct LoadedConfig {
     pub config: CommandsConfig,
     /// グローバル設定ファイルパス
     pub global_path: Option<PathBuf>,
-    /// ローカル設定ファイルパス
+    /// ローカル設定ファイルパス（`--config` を複数指定した場合は最後のファイル）
     pub local_path: Option<PathBuf>,
+    /// `local_path` より前にマージした明示的な設定ファイル（指定順）
+    pub base_paths: Vec<PathBuf>,
+    /// 環境別設定ファイルパス（`load_with_environment_paths` のみ）
+    pub environment_path: Option<PathBuf>,
+    /// コマンド名ごとの定義元ファイル（マージ順、最後の要素が採用されたファイル）
+    pub command_sources: AHashMap<String, Vec<PathBuf>>,
+}
+
+impl LoadedConfig {
+    /// 読み込んだ設定ファイル（マージ順）
+    pub fn paths(&self) -> Vec<&Path> {
+        self.global_path
+            .iter()
+            .chain(&self.base_paths)
+            .chain(&self.local_path)
+            .chain(&self.environment_path)
+            .map(PathBuf::as_path)
+            .collect()
+    }
+
+    /// 読み込んだ設定ファイルのコマンド定義元を記録
+    fn record_sources(&mut self, config: &CommandsConfig, path: &Path) {
+        for name in config.commands.keys() {
+            self.command_sources
+                .entry(name.clone())
+                .or_default()
+                .push(path.to_path_buf());
+        }
+    }
 }
 
 /// 設定ファイル名（優先順位順）
 const CONFIG_FILENAMES: &[&str] = &["commands.toml", ".cmdrun.toml", "cmdrun.toml"];
 
+/// プロジェクト用ディレクトリ内の設定ファイル（`.cmdrun/commands.toml`）
+const PROJECT_DIR_NAME: &str = ".cmdrun";
+
+/// 親ディレクトリを遡って設定ファイルを探すか（`--no-walk` で無効化）
+static WALK_PARENTS: AtomicBool = AtomicBool::new(true);
+
+/// `ConfigLoader::new` が親ディレクトリを遡って探索するかを設定
+pub fn set_walk_parents(enabled: bool) {
+    WALK_PARENTS.store(enabled, Ordering::Relaxed);
+}
+
+/// 標準入力から設定を読み込むことを示すパス（`--config -`）
+pub const STDIN_PATH: &str = "-";
+
+/// 標準入力から読み込んだ設定内容
+///
+/// 標準入力は一度しか読めないため、同じプロセス内の複数のローダーで共有する
+static STDIN_CONFIG: OnceLock<Result<String, String>> = OnceLock::new();
+
+fn read_stdin_config() -> Result<&'static str> {
+    STDIN_CONFIG
+        .get_or_init(|| {
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)
+                .map(|_| content)
+                .map_err(|e| e.to_string())
+        })
+        .as_deref()
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "{}: stdin: {}",
+                get_message(MessageKey::ErrorFailedToReadConfig, Language::English),
+                e
+            )
+        })
+}
+
 /// 設定ファイルローダー
 #[derive(Debug, Clone)]
 pub struct ConfigLoader {
-    /// 明示的に指定された設定ファイルパス
-    explicit_path: Option<PathBuf>,
+    /// 明示的に指定された設定ファイルパス（マージ順）
+    explicit_paths: Vec<PathBuf>,
     /// 実際に使用されたグローバル設定ファイルパス
     pub loaded_global_path: Option<PathBuf>,
     /// 実際に使用されたローカル設定ファイルパス
     pub loaded_local_path: Option<PathBuf>,
     /// グローバル設定のみを使用するフラグ
     global_only: bool,
+    /// ローカル設定を親ディレクトリまで遡って探すか
+    walk_parents: bool,
 }
 
 impl ConfigLoader {
     /// 新しいローダーを作成
     pub fn new() -> Self {
         Self {
-            explicit_path: None,
+            explicit_paths: Vec::new(),
             loaded_global_path: None,
             loaded_local_path: None,
             global_only: false,
+            walk_parents: WALK_PARENTS.load(Ordering::Relaxed),
         }
     }
 
     /// グローバル設定のみを使用するローダーを作成
     pub fn global_only() -> Self {
         Self {
-            explicit_path: None,
+            explicit_paths: Vec::new(),
             loaded_global_path: None,
             loaded_local_path: None,
             global_only: true,
+            walk_parents: WALK_PARENTS.load(Ordering::Relaxed),
         }
     }
 
+    /// ローカル設定をカレントディレクトリでのみ探す
+    pub fn no_walk(mut self) -> Self {
+        self.walk_parents = false;
+        self
+    }
+
     /// 明示的なパスを指定してローダーを作成
+    ///
+    /// `-` の場合は標準入力から読み込む（環境別設定はマージしない）
     pub fn with_path<P: Into<PathBuf>>(path: P) -> Result<Self> {
-        let path = path.into();
+        Self::with_paths([path])
+    }
+
+    /// 複数の設定ファイルを順にマージするローダーを作成
+    ///
+    /// 後のファイルが優先される。同名のコマンドやエイリアスは定義ごと置き換え、
+    /// `[config.env]` はキー単位でマージする（グローバル設定はマージしない）
+    pub fn with_paths<I, P>(paths: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        let explicit_paths = paths
+            .into_iter()
+            .map(|path| Self::resolve_explicit_path(path.into()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            explicit_paths,
+            loaded_global_path: None,
+            loaded_local_path: None,
+            global_only: false,
+            walk_parents: WALK_PARENTS.load(Ordering::Relaxed),
+        })
+    }
+
+    /// 明示的に指定されたパスを正規化
+    fn resolve_explicit_path(path: PathBuf) -> Result<PathBuf> {
+        if path.as_os_str() == STDIN_PATH {
+            return Ok(path);
+        }
 
         // パストラバーサル対策：パスを正規化
-        let canonical_path = std::fs::canonicalize(&path)
-            .with_context(|| format!("Invalid config path: {}", path.display()))?;
+        let canonical_path = std::fs::canonicalize(&path).map_err(|e| {
+            let message = format!("Invalid config path: {}", path.display());
+            if e.kind() == std::io::ErrorKind::NotFound {
+                config_not_found(path.clone(), message)
+            } else {
+                anyhow::Error::new(e).context(message)
+            }
+        })?;
 
         // セキュリティ警告：プロジェクト外のパス
         if let Ok(current_dir) = std::env::current_dir() {
@@ -76,12 +196,7 @@ impl ConfigLoader {
             }
         }
 
-        Ok(Self {
-            explicit_path: Some(canonical_path),
-            loaded_global_path: None,
-            loaded_local_path: None,
-            global_only: false,
-        })
+        Ok(canonical_path)
     }
 
     /// 設定ファイルを読み込む（グローバル + ローカルのマージ）
@@ -95,59 +210,90 @@ impl ConfigLoader {
     }
 
     /// 設定ファイルを読み込み、パス情報も返す
+    ///
+    /// マージ後の設定でエイリアスのターゲットが存在するか検証する
     pub async fn load_with_paths(&self) -> Result<LoadedConfig> {
-        if let Some(path) = &self.explicit_path {
-            debug!("Using explicitly specified config: {}", path.display());
-            let config = self.load_from_path(path).await?;
-            return Ok(LoadedConfig {
-                config,
-                global_path: None,
-                local_path: Some(path.clone()),
-            });
+        let loaded = self.load_unvalidated().await?;
+        ConfigValidator::new(&loaded.config).validate_aliases()?;
+        Ok(loaded)
+    }
+
+    /// 設定ファイルを読み込む（検証なし）
+    async fn load_unvalidated(&self) -> Result<LoadedConfig> {
+        let mut loaded = LoadedConfig {
+            config: CommandsConfig::default(),
+            global_path: None,
+            local_path: None,
+            base_paths: Vec::new(),
+            environment_path: None,
+            command_sources: AHashMap::new(),
+        };
+
+        if let Some((last, base)) = self.explicit_paths.split_last() {
+            let mut merged: Option<CommandsConfig> = None;
+            for path in &self.explicit_paths {
+                debug!("Using explicitly specified config: {}", path.display());
+                let config = self.load_from_path(path).await?;
+                loaded.record_sources(&config, path);
+                merged = Some(match merged {
+                    Some(previous) => previous.merge_with(config),
+                    None => config,
+                });
+            }
+            loaded.config = merged.unwrap_or_default();
+            loaded.base_paths = base.to_vec();
+            loaded.local_path = Some(last.clone());
+            return Ok(loaded);
         }
 
         // グローバル設定（任意）
-        let (global_config, global_path) = match self.find_global_config().await {
+        let global_config = match self.find_global_config().await {
             Some(path) => {
                 info!("Loading global config: {}", path.display());
                 let config = self.load_from_path(&path).await?;
-                (Some(config), Some(path))
+                loaded.record_sources(&config, &path);
+                loaded.global_path = Some(path);
+                Some(config)
             }
             None => {
                 debug!("No global config found");
-                (None, None)
+                None
             }
         };
 
         // グローバルのみモードの場合、ローカル設定を探さない
         if self.global_only {
-            let config = global_config.context("Global configuration file not found")?;
-            return Ok(LoadedConfig {
-                config,
-                global_path,
-                local_path: None,
-            });
+            loaded.config = match global_config {
+                Some(config) => config,
+                None => {
+                    let global_dir = dirs::config_dir().unwrap_or_default().join("cmdrun");
+                    return Err(config_not_found(
+                        global_dir,
+                        "Global configuration file not found",
+                    ));
+                }
+            };
+            return Ok(loaded);
         }
 
         // ローカル設定（必須）
         let local_path = self.find_local_config().await?;
         info!("Loading local config: {}", local_path.display());
         let local_config = self.load_from_path(&local_path).await?;
+        loaded.record_sources(&local_config, &local_path);
+        loaded.local_path = Some(local_path);
 
         // マージ（ローカルが優先）
-        let merged_config = match global_config {
+        loaded.config = match global_config {
             Some(global) => {
                 debug!("Merging global and local configurations");
                 global.merge_with(local_config)
             }
             None => local_config,
         };
+        self.anchor_working_dir(&mut loaded);
 
-        Ok(LoadedConfig {
-            config: merged_config,
-            global_path,
-            local_path: Some(local_path),
-        })
+        Ok(loaded)
     }
 
     /// 環境を考慮して設定ファイルを読み込む
@@ -157,10 +303,22 @@ impl ConfigLoader {
     /// 2. ローカル設定（必須）
     /// 3. 環境別設定（任意、最優先）
     pub async fn load_with_environment(&self) -> Result<CommandsConfig> {
+        let loaded = self.load_with_environment_paths().await?;
+        Ok(loaded.config)
+    }
+
+    /// 環境を考慮して設定ファイルを読み込み、パス情報も返す
+    ///
+    /// 標準入力から読み込む場合は環境別設定をマージしない
+    pub async fn load_with_environment_paths(&self) -> Result<LoadedConfig> {
         use crate::config::environment::EnvironmentManager;
 
         // 基本設定を読み込み
-        let mut config = self.load().await?;
+        let mut loaded = self.load_with_paths().await?;
+        if self.reads_stdin() {
+            debug!("Config read from stdin; skipping environment-specific config");
+            return Ok(loaded);
+        }
 
         // 現在の環境を取得
         let env_manager = EnvironmentManager::default_instance()
@@ -170,26 +328,34 @@ impl ConfigLoader {
 
         // デフォルト環境以外の場合、環境別設定をマージ
         if current_env != "default" {
-            if let Some(env_config) = self.load_environment_config(&current_env).await? {
+            if let Some((env_config, env_path)) = self.load_environment_config(&current_env).await?
+            {
                 info!("Merging environment-specific config: {}", current_env);
-                config = config.merge_with(env_config);
-
-                // 環境固有の環境変数を追加
-                if let Ok(env_config_data) = env_manager.load_environment_config().await {
-                    if let Some(env) = env_config_data.environments.get(&current_env) {
-                        config.config.env.extend(env.variables.clone());
-                    }
+                loaded.record_sources(&env_config, &env_path);
+                loaded.environment_path = Some(env_path);
+                loaded.config = std::mem::take(&mut loaded.config).merge_with(env_config);
+                self.anchor_working_dir(&mut loaded);
+
+                // 環境固有の環境変数を追加（継承元の変数を含む）
+                if let Ok(variables) = env_manager.resolve_variables(&current_env).await {
+                    loaded.config.config.env.extend(variables);
                 }
             }
         }
 
-        Ok(config)
+        Ok(loaded)
     }
 
     /// 環境別設定ファイルを読み込む
-    async fn load_environment_config(&self, env_name: &str) -> Result<Option<CommandsConfig>> {
-        // 現在のディレクトリで環境別設定を探す
-        let current_dir = std::env::current_dir().context("Failed to get current directory")?;
+    async fn load_environment_config(
+        &self,
+        env_name: &str,
+    ) -> Result<Option<(CommandsConfig, PathBuf)>> {
+        // 見つかったプロジェクトのディレクトリ（なければカレントディレクトリ）で環境別設定を探す
+        let current_dir = match self.loaded_project_dir().await {
+            Some(dir) => dir,
+            None => std::env::current_dir().context("Failed to get current directory")?,
+        };
 
         // commands.{env}.toml の形式
         let env_filename = format!("commands.{}.toml", env_name);
@@ -198,7 +364,7 @@ impl ConfigLoader {
         if env_path.exists() && env_path.is_file() {
             info!("Loading environment config: {}", env_path.display());
             let config = self.load_from_path(&env_path).await?;
-            return Ok(Some(config));
+            return Ok(Some((config, env_path)));
         }
 
         // .cmdrun/config.{env}.toml も探す
@@ -208,13 +374,69 @@ impl ConfigLoader {
         if cmdrun_env_path.exists() && cmdrun_env_path.is_file() {
             info!("Loading environment config: {}", cmdrun_env_path.display());
             let config = self.load_from_path(&cmdrun_env_path).await?;
-            return Ok(Some(config));
+            return Ok(Some((config, cmdrun_env_path)));
         }
 
         debug!("No environment-specific config found for: {}", env_name);
         Ok(None)
     }
 
+    /// 標準入力から設定を読み込むか（`--config -`）
+    pub fn reads_stdin(&self) -> bool {
+        self.explicit_paths
+            .iter()
+            .any(|path| path.as_os_str() == STDIN_PATH)
+    }
+
+    /// ローカル設定ファイル（または明示的なパス）が存在するか
+    pub async fn has_local_config(&self) -> bool {
+        if !self.explicit_paths.is_empty() {
+            return true;
+        }
+        if self.global_only {
+            return false;
+        }
+        match std::env::current_dir() {
+            Ok(dir) => matches!(self.search_local(&dir).await, Ok(Some(_))),
+            Err(_) => false,
+        }
+    }
+
+    /// 探索で見つかったローカル設定のプロジェクトディレクトリ
+    ///
+    /// 明示的なパスやグローバルのみの場合は `None`
+    async fn loaded_project_dir(&self) -> Option<PathBuf> {
+        if !self.explicit_paths.is_empty() || self.global_only {
+            return None;
+        }
+        let current_dir = std::env::current_dir().ok()?;
+        let path = self.search_local(&current_dir).await.ok()??;
+        Some(project_dir(&path).to_path_buf())
+    }
+
+    /// 相対パスの `working_dir` を見つかったローカル設定のプロジェクトディレクトリ基準にする
+    ///
+    /// サブディレクトリから実行しても、コマンドは設定ファイルのあるディレクトリで実行される。
+    /// 明示的なパスの場合は従来どおりカレントディレクトリ基準
+    fn anchor_working_dir(&self, loaded: &mut LoadedConfig) {
+        if !self.explicit_paths.is_empty() {
+            return;
+        }
+        let Some(local_path) = &loaded.local_path else {
+            return;
+        };
+        let working_dir = &mut loaded.config.config.working_dir;
+        if working_dir.is_absolute() {
+            return;
+        }
+        let base = project_dir(local_path);
+        *working_dir = if working_dir.as_os_str() == "." {
+            base.to_path_buf()
+        } else {
+            base.join(&*working_dir)
+        };
+    }
+
     /// グローバル設定ファイルを検索
     async fn find_global_config(&self) -> Option<PathBuf> {
         let global_dir = dirs::config_dir()?.join("cmdrun");
@@ -225,15 +447,24 @@ impl ConfigLoader {
     async fn find_local_config(&self) -> Result<PathBuf> {
         let current_dir = std::env::current_dir().context("Failed to get current directory")?;
 
-        if let Some(path) = self.search_upwards(&current_dir).await? {
+        if let Some(path) = self.search_local(&current_dir).await? {
             return Ok(path);
         }
 
-        anyhow::bail!(
-            "{}. Searched for: {}",
-            get_message(MessageKey::ErrorLocalConfigNotFound, Language::English),
-            CONFIG_FILENAMES.join(", ")
-        )
+        Err(config_not_found(
+            current_dir,
+            format!(
+                "{}. Searched for: {}, {}/commands.toml{}",
+                get_message(MessageKey::ErrorLocalConfigNotFound, Language::English),
+                CONFIG_FILENAMES.join(", "),
+                PROJECT_DIR_NAME,
+                if self.walk_parents {
+                    " (in this and parent directories)"
+                } else {
+                    " (in this directory only)"
+                }
+            ),
+        ))
     }
 
     /// 設定ファイルを探索（後方互換）
@@ -242,7 +473,18 @@ impl ConfigLoader {
         self.find_local_config().await
     }
 
+    /// ローカル設定を探索（`walk_parents` が無効ならカレントディレクトリのみ）
+    async fn search_local(&self, start_dir: &Path) -> Result<Option<PathBuf>> {
+        if self.walk_parents {
+            self.search_upwards(start_dir).await
+        } else {
+            self.check_directory(start_dir).await
+        }
+    }
+
     /// ディレクトリから上位へ向かって設定ファイルを探索
+    ///
+    /// `.git` と同様に、最初に見つかった設定ファイルを使う（ファイルシステムのルートまで）
     async fn search_upwards(&self, start_dir: &Path) -> Result<Option<PathBuf>> {
         let mut current = start_dir.to_path_buf();
 
@@ -269,26 +511,54 @@ impl ConfigLoader {
                 return Ok(Some(path));
             }
         }
+
+        let path = dir.join(PROJECT_DIR_NAME).join("commands.toml");
+        if path.is_file() {
+            debug!("Found config file: {}", path.display());
+            return Ok(Some(path));
+        }
+
         Ok(None)
     }
 
     /// 指定されたパスから設定ファイルを読み込む
+    ///
+    /// `-` は標準入力（`env_file` の相対パスはカレントディレクトリ基準）
     async fn load_from_path(&self, path: &Path) -> Result<CommandsConfig> {
-        let content = fs::read_to_string(path).await.with_context(|| {
+        let content = if path.as_os_str() == STDIN_PATH {
+            read_stdin_config()?.to_string()
+        } else {
+            fs::read_to_string(path).await.with_context(|| {
+                format!(
+                    "{}: {}",
+                    get_message(MessageKey::ErrorFailedToReadConfig, Language::English),
+                    path.display()
+                )
+            })?
+        };
+
+        let mut config: CommandsConfig = toml::from_str(&content).with_context(|| {
             format!(
                 "{}: {}",
-                get_message(MessageKey::ErrorFailedToReadConfig, Language::English),
-                path.display()
+                get_message(MessageKey::ErrorFailedToParseConfig, Language::English),
+                if path.as_os_str() == STDIN_PATH {
+                    "stdin".to_string()
+                } else {
+                    path.display().to_string()
+                }
             )
         })?;
+        config.config.specified_keys = Some(specified_config_keys(&content));
 
-        let config: CommandsConfig = toml::from_str(&content).with_context(|| {
+        Self::expand_paths(&mut config).with_context(|| {
             format!(
                 "{}: {}",
                 get_message(MessageKey::ErrorFailedToParseConfig, Language::English),
                 path.display()
             )
         })?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        Self::set_source_dir(&mut config, base_dir);
 
         debug!(
             "Loaded {} commands, {} aliases",
@@ -299,6 +569,44 @@ impl ConfigLoader {
         Ok(config)
     }
 
+    /// `working_dir`・`env_file` の `~`・`$VAR`・`${VAR}` を展開
+    ///
+    /// 未定義の変数は `strict_mode` ならエラー、そうでなければ空文字列になる
+    fn expand_paths(config: &mut CommandsConfig) -> Result<()> {
+        let strict = config.config.strict_mode;
+
+        let global = &mut config.config;
+        global.working_dir =
+            expand_path_buf(&global.working_dir, strict).context("Invalid working_dir")?;
+        if let Some(spec) = &mut global.env_file {
+            *spec = expand_path(spec, strict).context("Invalid env_file")?;
+        }
+
+        for (name, command) in config.commands.iter_mut() {
+            if let Some(dir) = &mut command.working_dir {
+                *dir = expand_path_buf(dir, strict)
+                    .with_context(|| format!("Invalid working_dir (command '{}')", name))?;
+            }
+            if let Some(spec) = &mut command.env_file {
+                *spec = expand_path(spec, strict)
+                    .with_context(|| format!("Invalid env_file (command '{}')", name))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 定義元の設定ファイルのディレクトリを記録
+    ///
+    /// `env_file` はロード時には読み込まず、実行時にこのディレクトリ基準で読み込む
+    /// （`edit` で設定を保存しても dotenv の値が書き込まれないようにするため）
+    fn set_source_dir(config: &mut CommandsConfig, base_dir: &Path) {
+        config.config.source_dir = Some(base_dir.to_path_buf());
+        for command in config.commands.values_mut() {
+            command.source_dir = Some(base_dir.to_path_buf());
+        }
+    }
+
     /// 複数の設定ファイルをマージして読み込む（将来の機能拡張用）
     #[allow(dead_code)]
     async fn load_with_merge(&self, paths: &[PathBuf]) -> Result<CommandsConfig> {
@@ -331,20 +639,17 @@ impl ConfigLoader {
         if overlay.config.shell != "bash" && overlay.config.shell != "pwsh" {
             base.config.shell = overlay.config.shell;
         }
-        if overlay.config.timeout != 300 {
+        if overlay.config.timeout != crate::config::schema::default_timeout() {
             base.config.timeout = overlay.config.timeout;
         }
         base.config.parallel |= overlay.config.parallel;
         base.config.env.extend(overlay.config.env);
 
+        // overlay 側で無効化されていればフックを無効にする
+        base.config.hooks_enabled &= overlay.config.hooks_enabled;
+
         // フックをマージ
-        if overlay.hooks.pre_run.is_some() {
-            base.hooks.pre_run = overlay.hooks.pre_run;
-        }
-        if overlay.hooks.post_run.is_some() {
-            base.hooks.post_run = overlay.hooks.post_run;
-        }
-        base.hooks.commands.extend(overlay.hooks.commands);
+        base.hooks = base.hooks.merge_with(overlay.hooks);
 
         base
     }
@@ -356,6 +661,99 @@ impl Default for ConfigLoader {
     }
 }
 
+/// 設定ファイルが属するプロジェクトのディレクトリ
+///
+/// `.cmdrun/commands.toml` の場合は `.cmdrun` の親ディレクトリ
+fn project_dir(config_path: &Path) -> &Path {
+    let dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    if dir.file_name().is_some_and(|name| name == PROJECT_DIR_NAME) {
+        dir.parent().unwrap_or(dir)
+    } else {
+        dir
+    }
+}
+
+/// 設定ファイルの `[config]` テーブルに書かれたキー
+///
+/// 重ねた設定ファイルで省略されたキーが既定値で上書きされないようにするために使う
+fn specified_config_keys(content: &str) -> AHashSet<String> {
+    match toml::from_str::<toml::Table>(content) {
+        Ok(table) => match table.get("config") {
+            Some(toml::Value::Table(config)) => config.keys().cloned().collect(),
+            _ => AHashSet::new(),
+        },
+        Err(_) => AHashSet::new(),
+    }
+}
+
+/// 設定ファイルを安全に書き換える
+///
+/// 既存のファイルを `<ファイル名>.bak` にコピーしてから、同じディレクトリの一時ファイルに
+/// 書き込んでリネームで置き換える。書き込み途中で中断されても設定ファイルが途中までの
+/// 内容になることはない。リネームに失敗した場合はバックアップから復元する。
+///
+/// シンボリックリンクの場合はリンク先を書き換え、バックアップもリンク先の隣に作る。
+///
+/// 作成したバックアップのパスを返す（ファイルが存在しなかった場合は `None`）
+pub fn save_config_atomic(path: &Path, contents: &str) -> Result<Option<PathBuf>> {
+    // リンク自体を通常のファイルで置き換えない
+    let resolved;
+    let path = match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => {
+            resolved = std::fs::canonicalize(path)
+                .with_context(|| format!("Failed to resolve symlink: {}", path.display()))?;
+            resolved.as_path()
+        }
+        _ => path,
+    };
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("commands.toml");
+
+    let backup_path = if path.exists() {
+        let backup_path = path.with_file_name(format!("{}.bak", file_name));
+        std::fs::copy(path, &backup_path)
+            .with_context(|| format!("Failed to create backup: {}", backup_path.display()))?;
+        debug!("Backed up {} to {}", path.display(), backup_path.display());
+        Some(backup_path)
+    } else {
+        None
+    };
+
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+    let written = write_synced(&temp_path, contents).and_then(|()| {
+        // 元のファイルのパーミッションを引き継ぐ
+        if let Ok(metadata) = std::fs::metadata(path) {
+            std::fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        Ok(())
+    });
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e).with_context(|| format!("Failed to write config file: {}", path.display()));
+    }
+
+    if let Err(e) = std::fs::rename(&temp_path, path) {
+        let _ = std::fs::remove_file(&temp_path);
+        if let Some(backup_path) = &backup_path {
+            let _ = std::fs::copy(backup_path, path);
+        }
+        return Err(e).with_context(|| format!("Failed to write config file: {}", path.display()));
+    }
+
+    Ok(backup_path)
+}
+
+fn write_synced(path: &Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,6 +786,165 @@ cmd = "cargo test"
         assert!(config.commands.contains_key("test"));
     }
 
+    #[tokio::test]
+    async fn test_load_env_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+
+        std::fs::write(
+            temp_dir.path().join(".env"),
+            "# global secrets\nSHARED=from_global_file\nGLOBAL_ONLY=\"global # value\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("deploy.env"),
+            "SHARED=from_command_file\nDEPLOY_TOKEN='abc'\nOVERRIDDEN=file\n",
+        )
+        .unwrap();
+
+        let toml_content = r#"
+[config]
+env_file = ".env"
+env = { SHARED = "from_global_env" }
+
+[commands.deploy]
+cmd = "echo deploy"
+env_file = "deploy.env"
+env = { OVERRIDDEN = "command_env" }
+
+[commands.optional]
+cmd = "echo optional"
+env_file = "missing.env?"
+"#;
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        let loader = ConfigLoader::with_path(&config_path).unwrap();
+        let config = loader.load().await.unwrap();
+
+        // dotenv values are not merged into the loaded config
+        assert_eq!(config.config.env.len(), 1);
+        assert_eq!(config.commands["deploy"].env.len(), 1);
+
+        // global env > global env_file
+        let global = config.config.resolved_env().unwrap();
+        assert_eq!(global["SHARED"], "from_global_env");
+        assert_eq!(global["GLOBAL_ONLY"], "global # value");
+
+        // command env_file is read relative to the config file
+        let deploy = config.commands["deploy"].env_file_vars().unwrap();
+        assert_eq!(deploy["SHARED"], "from_command_file");
+        assert_eq!(deploy["DEPLOY_TOKEN"], "abc");
+        assert_eq!(deploy["OVERRIDDEN"], "file");
+
+        assert!(config.commands["optional"]
+            .env_file_vars()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_env_file_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+
+        let toml_content = r#"
+[commands.deploy]
+cmd = "echo deploy"
+env_file = "missing.env"
+"#;
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        // A missing env_file only fails the command that uses it
+        let loader = ConfigLoader::with_path(&config_path).unwrap();
+        let config = loader.load().await.unwrap();
+        let err = config.commands["deploy"].env_file_vars().unwrap_err();
+        assert!(err.to_string().contains("env_file not found"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_script_file_relative_to_config_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+
+        let toml_content = r#"
+[commands.deploy]
+cmd = { file = "scripts/deploy.sh" }
+"#;
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        let loader = ConfigLoader::with_path(&config_path).unwrap();
+        let config = loader.load().await.unwrap();
+
+        // 設定にはそのまま残し（edit で書き戻しても変わらない）、実行時に解決する
+        let deploy = &config.commands["deploy"];
+        match &deploy.cmd {
+            crate::config::schema::CommandSpec::Script { file } => {
+                assert_eq!(file, "scripts/deploy.sh");
+                assert_eq!(
+                    deploy.script_path(file, true).unwrap(),
+                    config_path.parent().unwrap().join("scripts/deploy.sh")
+                );
+            }
+            other => panic!("unexpected spec: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_expands_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+
+        let toml_content = r#"
+[config]
+working_dir = "~/project"
+
+[commands.build]
+cmd = "make"
+working_dir = "$HOME/src"
+env_file = "${HOME}/build.env?"
+"#;
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        let config = ConfigLoader::with_path(&config_path)
+            .unwrap()
+            .load()
+            .await
+            .unwrap();
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(
+            config.config.working_dir,
+            dirs::home_dir().unwrap().join("project")
+        );
+        let build = &config.commands["build"];
+        assert_eq!(
+            build.working_dir.as_deref(),
+            Some(Path::new(&format!("{}/src", home)))
+        );
+        assert_eq!(
+            build.env_file.as_deref(),
+            Some(format!("{}/build.env?", home).as_str())
+        );
+
+        // Undefined variables fail in strict mode (the default)
+        std::fs::write(
+            &config_path,
+            "[commands.build]\ncmd = \"make\"\nworking_dir = \"$CMDRUN_UNDEFINED_TEST_VAR/x\"\n",
+        )
+        .unwrap();
+        let err = ConfigLoader::with_path(&config_path)
+            .unwrap()
+            .load()
+            .await
+            .unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(
+            message.contains("Undefined variable 'CMDRUN_UNDEFINED_TEST_VAR'"),
+            "{}",
+            message
+        );
+        assert!(message.contains("command 'build'"), "{}", message);
+    }
+
     #[tokio::test]
     async fn test_config_not_found() {
         // Path validation now happens in with_path, so we expect it to fail there
@@ -395,6 +952,18 @@ cmd = "cargo test"
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_with_path_stdin() {
+        let loader = ConfigLoader::with_path(STDIN_PATH).unwrap();
+        assert!(loader.reads_stdin());
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+        std::fs::write(&config_path, "").unwrap();
+        assert!(!ConfigLoader::with_path(&config_path).unwrap().reads_stdin());
+        assert!(!ConfigLoader::new().reads_stdin());
+    }
+
     #[tokio::test]
     async fn test_invalid_toml() {
         let temp_dir = TempDir::new().unwrap();
@@ -419,6 +988,131 @@ cmd = "cargo test"
         );
     }
 
+    #[tokio::test]
+    async fn test_with_paths_layers_configs_in_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join("base.toml");
+        let local_path = temp_dir.path().join("local.toml");
+        fs::write(
+            &base_path,
+            r#"
+[config.env]
+SHARED = "base"
+REGION = "eu"
+
+[commands.build]
+description = "Shared build"
+cmd = "cargo build"
+deps = ["fmt"]
+
+[commands.fmt]
+cmd = "cargo fmt"
+"#,
+        )
+        .await
+        .unwrap();
+        fs::write(
+            &local_path,
+            r#"
+[config.env]
+SHARED = "local"
+
+[commands.build]
+cmd = "cargo build --release"
+"#,
+        )
+        .await
+        .unwrap();
+
+        let loader = ConfigLoader::with_paths([&base_path, &local_path]).unwrap();
+        let loaded = loader.load_with_paths().await.unwrap();
+        let config = &loaded.config;
+
+        // 後のファイルのコマンドが定義ごと置き換える（deps は引き継がない）
+        let build = &config.commands["build"];
+        assert!(
+            matches!(&build.cmd, crate::config::schema::CommandSpec::Single(cmd) if cmd == "cargo build --release")
+        );
+        assert!(build.deps.is_empty());
+        assert!(config.commands.contains_key("fmt"));
+
+        // env はキー単位でマージ
+        assert_eq!(config.config.env["SHARED"], "local");
+        assert_eq!(config.config.env["REGION"], "eu");
+
+        let base_path = base_path.canonicalize().unwrap();
+        let local_path = local_path.canonicalize().unwrap();
+        assert_eq!(
+            loaded.command_sources["build"],
+            vec![base_path.clone(), local_path.clone()]
+        );
+        assert_eq!(loaded.local_path.as_ref(), Some(&local_path));
+        assert_eq!(
+            loaded.paths(),
+            vec![base_path.as_path(), local_path.as_path()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_paths_keeps_config_omitted_by_later_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join("base.toml");
+        let local_path = temp_dir.path().join("local.toml");
+        fs::write(
+            &base_path,
+            r#"
+[config]
+shell = "zsh"
+timeout = 90
+strict_mode = false
+merge_stderr = true
+
+[commands.build]
+cmd = "cargo build"
+"#,
+        )
+        .await
+        .unwrap();
+        fs::write(
+            &local_path,
+            r#"
+[commands.lint]
+cmd = "cargo clippy"
+"#,
+        )
+        .await
+        .unwrap();
+
+        let loader = ConfigLoader::with_paths([&base_path, &local_path]).unwrap();
+        let config = loader.load().await.unwrap();
+
+        // [config] のないファイルは前のファイルの設定を既定値で上書きしない
+        assert_eq!(config.config.shell, "zsh");
+        assert_eq!(config.config.timeout, std::time::Duration::from_secs(90));
+        assert!(!config.config.strict_mode);
+        assert!(config.config.merge_stderr);
+        assert!(config.commands.contains_key("build"));
+        assert!(config.commands.contains_key("lint"));
+
+        // 明示したキーだけが上書きされる
+        fs::write(
+            &local_path,
+            r#"
+[config]
+timeout = 10
+
+[commands.lint]
+cmd = "cargo clippy"
+"#,
+        )
+        .await
+        .unwrap();
+        let config = loader.load().await.unwrap();
+        assert_eq!(config.config.shell, "zsh");
+        assert_eq!(config.config.timeout, std::time::Duration::from_secs(10));
+        assert!(!config.config.strict_mode);
+    }
+
     #[tokio::test]
     async fn test_merge_configs() {
         let loader = ConfigLoader::new();
@@ -426,7 +1120,7 @@ cmd = "cargo test"
         let base = CommandsConfig {
             config: crate::config::schema::GlobalConfig {
                 shell: "bash".to_string(),
-                timeout: 100,
+                timeout: std::time::Duration::from_secs(100),
                 ..Default::default()
             },
             commands: {
@@ -437,6 +1131,8 @@ cmd = "cargo test"
                         description: "Test".to_string(),
                         cmd: crate::config::schema::CommandSpec::Single("echo test".to_string()),
                         env: Default::default(),
+                        env_file: None,
+                        source_dir: None,
                         working_dir: None,
                         deps: vec![],
                         platform: vec![],
@@ -444,8 +1140,16 @@ cmd = "cargo test"
                         timeout: None,
                         parallel: false,
                         confirm: false,
+                        confirm_message: None,
                         allow_chaining: None,
                         allow_subshells: None,
+                        output_pipe: None,
+                        category: None,
+                        watch: None,
+                        when: None,
+                        when_shell: None,
+                        shell: None,
+                        arg_completions: vec![],
                     },
                 );
                 map
@@ -458,7 +1162,7 @@ cmd = "cargo test"
         let overlay = CommandsConfig {
             config: crate::config::schema::GlobalConfig {
                 shell: "zsh".to_string(),
-                timeout: 200,
+                timeout: std::time::Duration::from_secs(200),
                 ..Default::default()
             },
             commands: {
@@ -469,6 +1173,8 @@ cmd = "cargo test"
                         description: "Build".to_string(),
                         cmd: crate::config::schema::CommandSpec::Single("cargo build".to_string()),
                         env: Default::default(),
+                        env_file: None,
+                        source_dir: None,
                         working_dir: None,
                         deps: vec![],
                         platform: vec![],
@@ -476,8 +1182,16 @@ cmd = "cargo test"
                         timeout: None,
                         parallel: false,
                         confirm: false,
+                        confirm_message: None,
                         allow_chaining: None,
                         allow_subshells: None,
+                        output_pipe: None,
+                        category: None,
+                        watch: None,
+                        when: None,
+                        when_shell: None,
+                        shell: None,
+                        arg_completions: vec![],
                     },
                 );
                 map
@@ -490,11 +1204,100 @@ cmd = "cargo test"
         let merged = loader.merge_configs(base, overlay);
 
         assert_eq!(merged.config.shell, "zsh");
-        assert_eq!(merged.config.timeout, 200);
+        assert_eq!(merged.config.timeout, std::time::Duration::from_secs(200));
         assert_eq!(merged.commands.len(), 2);
         assert!(merged.commands.contains_key("test"));
         assert!(merged.commands.contains_key("build"));
     }
+
+    #[test]
+    fn test_save_config_atomic_backs_up_and_replaces() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+        let original = "[commands.build]\ndescription = \"Build\"\ncmd = \"cargo build\"\n";
+        std::fs::write(&config_path, original).unwrap();
+
+        let updated = format!(
+            "{}\n[commands.test]\ndescription = \"Test\"\ncmd = \"cargo test\"\n",
+            original
+        );
+        let backup = save_config_atomic(&config_path, &updated).unwrap();
+
+        let backup = backup.expect("existing file should be backed up");
+        assert_eq!(backup, temp_dir.path().join("commands.toml.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), original);
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), updated);
+
+        // 一時ファイルは残らない
+        let entries: Vec<_> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries.len(), 2, "{:?}", entries);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_config_atomic_follows_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let dotfiles = temp_dir.path().join("dotfiles");
+        std::fs::create_dir(&dotfiles).unwrap();
+        let target = dotfiles.join("commands.toml");
+        std::fs::write(&target, "[commands]\n").unwrap();
+        let link = temp_dir.path().join("commands.toml");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let backup = save_config_atomic(&link, "[config]\n").unwrap();
+
+        assert!(std::fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "[config]\n");
+        let backup = backup.expect("existing file should be backed up");
+        assert_eq!(
+            backup,
+            target
+                .canonicalize()
+                .unwrap()
+                .with_file_name("commands.toml.bak")
+        );
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "[commands]\n");
+        assert!(!temp_dir.path().join("commands.toml.bak").exists());
+    }
+
+    #[test]
+    fn test_save_config_atomic_new_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+
+        let backup = save_config_atomic(&config_path, "[commands]\n").unwrap();
+
+        assert!(backup.is_none());
+        assert_eq!(
+            std::fs::read_to_string(&config_path).unwrap(),
+            "[commands]\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_config_atomic_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("commands.toml");
+        std::fs::write(&config_path, "[commands]\n").unwrap();
+        std::fs::set_permissions(&config_path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        save_config_atomic(&config_path, "[config]\n").unwrap();
+
+        let mode = std::fs::metadata(&config_path)
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
 }
 
 #[cfg(test)]
@@ -553,4 +1356,46 @@ cmd = "echo local"
         println!("Global: {:?}", global_path);
         println!("Local: {:?}", local_path);
     }
+
+    #[tokio::test]
+    async fn test_search_local_walks_parents_unless_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join(".cmdrun")).await.unwrap();
+        let config_path = root.join(".cmdrun/commands.toml");
+        fs::write(&config_path, "[commands]\n").await.unwrap();
+        let nested = root.join("a/b");
+        fs::create_dir_all(&nested).await.unwrap();
+
+        let loader = ConfigLoader::new();
+        assert_eq!(
+            loader.search_local(&nested).await.unwrap(),
+            Some(config_path.clone())
+        );
+        assert_eq!(project_dir(&config_path), root);
+
+        let loader = ConfigLoader::new().no_walk();
+        assert_eq!(loader.search_local(&nested).await.unwrap(), None);
+        assert_eq!(loader.search_local(root).await.unwrap(), Some(config_path));
+    }
+
+    #[test]
+    fn test_anchor_working_dir() {
+        let mut loaded = LoadedConfig {
+            config: toml::from_str("[config]\nworking_dir = \"sub\"\n").unwrap(),
+            global_path: None,
+            local_path: Some(PathBuf::from("/repo/commands.toml")),
+            base_paths: Vec::new(),
+            environment_path: None,
+            command_sources: AHashMap::new(),
+        };
+        let loader = ConfigLoader::new();
+        loader.anchor_working_dir(&mut loaded);
+        assert_eq!(loaded.config.config.working_dir, Path::new("/repo/sub"));
+
+        loaded.config.config.working_dir = PathBuf::from(".");
+        loaded.local_path = Some(PathBuf::from("/repo/.cmdrun/commands.toml"));
+        loader.anchor_working_dir(&mut loaded);
+        assert_eq!(loaded.config.config.working_dir, Path::new("/repo"));
+    }
 }
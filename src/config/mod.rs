@@ -1,6 +1,10 @@
+pub mod dotenv;
+pub mod duration;
 pub mod environment;
 pub mod loader;
+pub mod paths;
 pub mod schema;
+pub mod schema_json;
 pub mod validation;
 
 pub use environment::{Environment, EnvironmentConfig, EnvironmentManager};
@@ -0,0 +1,157 @@
+//! Path expansion
+//!
+//! `working_dir` と `env_file` に書かれた先頭の `~` と `$VAR` / `${VAR}` を
+//! 読み込み時にホストの環境変数で展開する（シェルには任せない）
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// パス文字列を展開
+///
+/// 厳格モードでは未定義の変数をエラーにし、それ以外では空文字列に置き換える
+pub fn expand_path(path: &str, strict: bool) -> Result<String> {
+    expand_with(
+        path,
+        strict,
+        |name| std::env::var(name).ok(),
+        dirs::home_dir,
+    )
+}
+
+/// `PathBuf` 版（UTF-8 でないパスはそのまま返す）
+pub fn expand_path_buf(path: &Path, strict: bool) -> Result<PathBuf> {
+    match path.to_str() {
+        Some(s) => expand_path(s, strict).map(PathBuf::from),
+        None => Ok(path.to_path_buf()),
+    }
+}
+
+/// 変数の参照先とホームディレクトリを指定して展開
+fn expand_with(
+    path: &str,
+    strict: bool,
+    lookup: impl Fn(&str) -> Option<String>,
+    home: impl FnOnce() -> Option<PathBuf>,
+) -> Result<String> {
+    let mut result = String::with_capacity(path.len());
+
+    // 先頭の `~` / `~/...` のみ展開（`~user` は対象外）
+    let mut rest = path;
+    if let Some(tail) = path.strip_prefix('~') {
+        if tail.is_empty() || tail.starts_with('/') || tail.starts_with('\\') {
+            let home = home().context("Cannot expand '~': home directory not found")?;
+            result.push_str(&home.to_string_lossy());
+            rest = tail;
+        }
+    }
+
+    let mut chars = rest.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+
+        let name = match chars.peek() {
+            Some((_, '{')) => {
+                let start = idx + 2;
+                let end = rest[start..]
+                    .find('}')
+                    .map(|len| start + len)
+                    .with_context(|| format!("Unclosed '${{' in path: {}", path))?;
+                let name = &rest[start..end];
+                if !is_valid_name(name) {
+                    anyhow::bail!("Invalid variable name '{}' in path: {}", name, path);
+                }
+                while chars.next_if(|(i, _)| *i <= end).is_some() {}
+                name
+            }
+            Some((_, next)) if next.is_ascii_alphabetic() || *next == '_' => {
+                let start = idx + 1;
+                let mut end = start;
+                while let Some((i, c)) =
+                    chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                {
+                    end = i + c.len_utf8();
+                }
+                &rest[start..end]
+            }
+            // `$` の後に変数名がなければ文字として扱う
+            _ => {
+                result.push('$');
+                continue;
+            }
+        };
+
+        match lookup(name) {
+            Some(value) => result.push_str(&value),
+            None if strict => {
+                anyhow::bail!("Undefined variable '{}' in path: {}", name, path)
+            }
+            None => {}
+        }
+    }
+
+    Ok(result)
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(path: &str, strict: bool) -> Result<String> {
+        expand_with(
+            path,
+            strict,
+            |name| match name {
+                "HOME" => Some("/home/me".to_string()),
+                "PROJECT" => Some("app".to_string()),
+                _ => None,
+            },
+            || Some(PathBuf::from("/home/me")),
+        )
+    }
+
+    #[test]
+    fn test_expand_tilde() {
+        assert_eq!(expand("~", true).unwrap(), "/home/me");
+        assert_eq!(expand("~/project", true).unwrap(), "/home/me/project");
+        // `~` elsewhere and `~user` are left alone
+        assert_eq!(expand("~other/x", true).unwrap(), "~other/x");
+        assert_eq!(expand("a/~/b", true).unwrap(), "a/~/b");
+    }
+
+    #[test]
+    fn test_expand_variables() {
+        assert_eq!(expand("$HOME/project", true).unwrap(), "/home/me/project");
+        assert_eq!(
+            expand("${HOME}/src/${PROJECT}", true).unwrap(),
+            "/home/me/src/app"
+        );
+        assert_eq!(expand("$PROJECT-dev", true).unwrap(), "app-dev");
+        assert_eq!(expand("cost$/x", true).unwrap(), "cost$/x");
+        assert_eq!(expand("plain/path", true).unwrap(), "plain/path");
+    }
+
+    #[test]
+    fn test_undefined_variable_errors_in_strict_mode() {
+        let err = expand("$MISSING/dir", true).unwrap_err();
+        assert!(
+            err.to_string().contains("Undefined variable 'MISSING'"),
+            "{}",
+            err
+        );
+        assert_eq!(expand("$MISSING/dir", false).unwrap(), "/dir");
+
+        assert!(expand("${HOME", true).is_err());
+        assert!(expand("${1X}", true).is_err());
+    }
+}
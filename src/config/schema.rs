@@ -2,9 +2,12 @@
 //!
 //! Serde を使用した型安全な設定デシリアライゼーション
 
-use ahash::AHashMap;
+use crate::config::dotenv::load_env_file;
+use crate::config::paths::expand_path;
+use ahash::{AHashMap, AHashSet};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// commands.toml のルート構造
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -43,6 +46,14 @@ impl Default for CommandsConfig {
 }
 
 impl CommandsConfig {
+    /// エイリアスが指すコマンド名を取得（引数付きエイリアスはコマンド名部分のみ）
+    pub fn alias_command(&self, alias: &str) -> Option<String> {
+        self.aliases
+            .get(alias)
+            .and_then(|target| AliasTarget::parse(target).ok())
+            .map(|target| target.command)
+    }
+
     /// Merge configurations (overlay takes precedence)
     pub fn merge_with(self, overlay: Self) -> Self {
         Self {
@@ -63,6 +74,109 @@ impl CommandsConfig {
     }
 }
 
+/// エイリアスのターゲット
+///
+/// `test-one = "test -- --exact {1}"` のようにコマンド名の後に引数を続けられる。
+/// `{N}` は呼び出し時の N 番目の引数で置換され、残りの引数は末尾に追加される
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasTarget {
+    /// 実行するコマンド名
+    pub command: String,
+    /// コマンドに渡す固定引数（プレースホルダーを含む）
+    pub args: Vec<String>,
+}
+
+impl AliasTarget {
+    /// ターゲット文字列を解析
+    pub fn parse(target: &str) -> Result<Self, String> {
+        let mut tokens = shell_words::split(target)
+            .map_err(|e| format!("invalid alias target '{}': {}", target, e))?
+            .into_iter();
+        let command = tokens
+            .next()
+            .ok_or_else(|| "alias target cannot be empty".to_string())?;
+
+        // `cmdrun run test -- --exact` と同様に先頭の `--` は区切りとして扱う
+        let mut args: Vec<String> = tokens.collect();
+        if args.first().map(String::as_str) == Some("--") {
+            args.remove(0);
+        }
+
+        Ok(Self { command, args })
+    }
+
+    /// 呼び出し時の引数を展開してコマンドに渡す引数列を組み立てる
+    pub fn expand(&self, user_args: &[String]) -> Result<Vec<String>, String> {
+        let consumed = self
+            .args
+            .iter()
+            .flat_map(|arg| placeholders(arg))
+            .max()
+            .unwrap_or(0);
+        if user_args.len() < consumed {
+            return Err(format!(
+                "expects at least {} argument(s), got {}",
+                consumed,
+                user_args.len()
+            ));
+        }
+
+        let mut expanded: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| substitute_placeholders(arg, user_args))
+            .collect();
+        expanded.extend_from_slice(&user_args[consumed..]);
+
+        Ok(expanded)
+    }
+}
+
+/// 文字列中の `{N}`（N >= 1）プレースホルダーの番号を列挙
+fn placeholders(arg: &str) -> Vec<usize> {
+    let mut found = Vec::new();
+    let mut rest = arg;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(end) = rest.find('}') {
+            if let Ok(n @ 1..) = rest[..end].parse::<usize>() {
+                found.push(n);
+            }
+        }
+    }
+    found
+}
+
+/// `{N}` を呼び出し時の N 番目の引数で置換
+///
+/// 1回の走査で置換するため、引数の値に含まれる `{N}` は展開しない
+fn substitute_placeholders(arg: &str, user_args: &[String]) -> String {
+    let mut result = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let placeholder = after
+            .find('}')
+            .and_then(|end| match after[..end].parse::<usize>() {
+                Ok(n @ 1..) => Some((n, end)),
+                _ => None,
+            });
+        match placeholder {
+            Some((n, end)) => {
+                result.push_str(&user_args[n - 1]);
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
 /// 言語設定
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -76,6 +190,10 @@ pub enum Language {
     ChineseSimplified,
     /// 繁體中文
     ChineseTraditional,
+    /// スペイン語
+    Spanish,
+    /// ドイツ語
+    German,
 }
 
 /// グローバル設定
@@ -93,9 +211,9 @@ pub struct GlobalConfig {
     #[serde(default)]
     pub parallel: bool,
 
-    /// タイムアウト（秒）
-    #[serde(default = "default_timeout")]
-    pub timeout: u64,
+    /// タイムアウト（`"30s"` などの時間指定、整数は秒）
+    #[serde(default = "default_timeout", with = "crate::config::duration")]
+    pub timeout: Duration,
 
     /// 作業ディレクトリ
     #[serde(default = "default_working_dir")]
@@ -109,6 +227,20 @@ pub struct GlobalConfig {
     #[serde(default)]
     pub env: AHashMap<String, String>,
 
+    /// グローバル環境変数を読み込む dotenv ファイル（末尾 `?` で任意扱い）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<String>,
+
+    /// `env_file` を定義した設定ファイルのディレクトリ（ロード時に設定、保存しない）
+    #[serde(skip)]
+    pub source_dir: Option<PathBuf>,
+
+    /// 設定ファイルの `[config]` に明示的に書かれたキー（ロード時に設定、保存しない）
+    ///
+    /// `None` はすべてのキーが指定されたものとして扱う
+    #[serde(skip)]
+    pub specified_keys: Option<AHashSet<String>>,
+
     /// Typo検出の有効化
     #[serde(default = "default_true")]
     pub typo_detection: bool,
@@ -133,6 +265,57 @@ pub struct GlobalConfig {
     /// 用途: grep -E '(pattern)', (cd /tmp && make)
     #[serde(default)]
     pub allow_subshells: bool,
+
+    /// 実行を許可するベースコマンドの一覧（許可リストモード）
+    /// 設定時は先頭トークンがこの一覧にないコマンドを拒否する
+    /// コマンド連結を許可した場合は各セグメントごとに適用される
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_allowlist: Option<Vec<String>>,
+
+    /// 履歴で値を伏せ字（***REDACTED***）にする環境変数キーのパターン（glob形式）
+    /// 指定するとデフォルトのパターン一覧を置き換える
+    #[serde(default = "default_redact_patterns")]
+    pub redact_patterns: Vec<String>,
+
+    /// 標準エラー出力を標準出力に統合（2>&1 相当）
+    /// デフォルト: false（別々にキャプチャ）
+    #[serde(default)]
+    pub merge_stderr: bool,
+
+    /// タイムアウト時に SIGTERM を送ってから強制終了（SIGKILL）するまでの猶予（ミリ秒）
+    /// デフォルト: 2000
+    #[serde(default = "default_kill_grace_ms")]
+    pub kill_grace_ms: u64,
+
+    /// 並列実行グループ内で同時に実行するコマンドの上限
+    /// デフォルト: 論理CPU数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel: Option<usize>,
+
+    /// `[hooks]` のフック実行を有効化（マスタースイッチ）
+    /// デフォルト: true
+    #[serde(default = "default_true")]
+    pub hooks_enabled: bool,
+
+    /// 履歴に保持する最大件数（超えた分は古い順に削除）
+    /// デフォルト: 1000
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_max_entries: Option<usize>,
+
+    /// 履歴の保持期間（`"30d"` / `"12h"` など、これより古い履歴は削除）
+    /// デフォルト: なし（期間では削除しない）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_max_age: Option<String>,
+
+    /// `cmdrun run` の完了時にデスクトップ通知を送る（`run --notify` と同じ）
+    /// デフォルト: false
+    #[serde(default)]
+    pub notify_on_completion: bool,
+
+    /// 通知するのは実行時間がこの値（ミリ秒）以上の場合のみ
+    /// デフォルト: 10000
+    #[serde(default = "default_notify_threshold_ms")]
+    pub notify_threshold_ms: u64,
 }
 
 impl Default for GlobalConfig {
@@ -145,39 +328,163 @@ impl Default for GlobalConfig {
             working_dir: default_working_dir(),
             language: Language::default(),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
+            specified_keys: None,
             typo_detection: true,
             typo_threshold: default_typo_threshold(),
             auto_correct: false,
             allow_command_chaining: false,
             allow_subshells: false,
+            command_allowlist: None,
+            redact_patterns: default_redact_patterns(),
+            merge_stderr: false,
+            kill_grace_ms: default_kill_grace_ms(),
+            max_parallel: None,
+            hooks_enabled: true,
+            history_max_entries: None,
+            history_max_age: None,
+            notify_on_completion: false,
+            notify_threshold_ms: default_notify_threshold_ms(),
         }
     }
 }
 
 impl GlobalConfig {
+    /// `env_file` の値に `env` を重ねたグローバル環境変数
+    ///
+    /// `env_file` は設定ファイルには展開せず、実行時にここで読み込む
+    pub fn resolved_env(&self) -> anyhow::Result<AHashMap<String, String>> {
+        let mut env = match &self.env_file {
+            Some(spec) => load_env_file(spec, base_dir(&self.source_dir))?.unwrap_or_default(),
+            None => AHashMap::new(),
+        };
+        env.extend(self.env.clone());
+        Ok(env)
+    }
+
     /// Merge global configs (overlay takes precedence, env vars are combined)
-    pub fn merge_with(self, overlay: Self) -> Self {
+    pub fn merge_with(self, mut overlay: Self) -> Self {
+        // 上書き側で省略されたキーは既定値ではなく元の値を残す
+        let keys = overlay.specified_keys.take();
+        let specified = |key: &str| keys.as_ref().map_or(true, |keys| keys.contains(key));
         Self {
-            shell: overlay.shell,
-            strict_mode: overlay.strict_mode,
-            parallel: overlay.parallel,
-            timeout: overlay.timeout,
-            working_dir: overlay.working_dir,
-            language: overlay.language,
+            shell: if specified("shell") {
+                overlay.shell
+            } else {
+                self.shell
+            },
+            strict_mode: if specified("strict_mode") {
+                overlay.strict_mode
+            } else {
+                self.strict_mode
+            },
+            parallel: if specified("parallel") {
+                overlay.parallel
+            } else {
+                self.parallel
+            },
+            timeout: if specified("timeout") {
+                overlay.timeout
+            } else {
+                self.timeout
+            },
+            working_dir: if specified("working_dir") {
+                overlay.working_dir
+            } else {
+                self.working_dir
+            },
+            language: if specified("language") {
+                overlay.language
+            } else {
+                self.language
+            },
             env: {
                 let mut merged = self.env;
                 merged.extend(overlay.env);
                 merged
             },
-            typo_detection: overlay.typo_detection,
-            typo_threshold: overlay.typo_threshold,
-            auto_correct: overlay.auto_correct,
-            allow_command_chaining: overlay.allow_command_chaining,
-            allow_subshells: overlay.allow_subshells,
+            source_dir: if overlay.env_file.is_some() {
+                overlay.source_dir
+            } else {
+                self.source_dir
+            },
+            env_file: overlay.env_file.or(self.env_file),
+            specified_keys: match (self.specified_keys, &keys) {
+                (Some(mut merged), Some(keys)) => {
+                    merged.extend(keys.iter().cloned());
+                    Some(merged)
+                }
+                _ => None,
+            },
+            typo_detection: if specified("typo_detection") {
+                overlay.typo_detection
+            } else {
+                self.typo_detection
+            },
+            typo_threshold: if specified("typo_threshold") {
+                overlay.typo_threshold
+            } else {
+                self.typo_threshold
+            },
+            auto_correct: if specified("auto_correct") {
+                overlay.auto_correct
+            } else {
+                self.auto_correct
+            },
+            allow_command_chaining: if specified("allow_command_chaining") {
+                overlay.allow_command_chaining
+            } else {
+                self.allow_command_chaining
+            },
+            allow_subshells: if specified("allow_subshells") {
+                overlay.allow_subshells
+            } else {
+                self.allow_subshells
+            },
+            command_allowlist: overlay.command_allowlist.or(self.command_allowlist),
+            redact_patterns: if specified("redact_patterns") {
+                overlay.redact_patterns
+            } else {
+                self.redact_patterns
+            },
+            merge_stderr: if specified("merge_stderr") {
+                overlay.merge_stderr
+            } else {
+                self.merge_stderr
+            },
+            kill_grace_ms: if specified("kill_grace_ms") {
+                overlay.kill_grace_ms
+            } else {
+                self.kill_grace_ms
+            },
+            max_parallel: overlay.max_parallel.or(self.max_parallel),
+            hooks_enabled: if specified("hooks_enabled") {
+                overlay.hooks_enabled
+            } else {
+                self.hooks_enabled
+            },
+            history_max_entries: overlay.history_max_entries.or(self.history_max_entries),
+            history_max_age: overlay.history_max_age.or(self.history_max_age),
+            notify_on_completion: if specified("notify_on_completion") {
+                overlay.notify_on_completion
+            } else {
+                self.notify_on_completion
+            },
+            notify_threshold_ms: if specified("notify_threshold_ms") {
+                overlay.notify_threshold_ms
+            } else {
+                self.notify_threshold_ms
+            },
         }
     }
 }
 
+/// 相対パスの基準ディレクトリ（定義元が不明な場合はカレントディレクトリ）
+fn base_dir(source_dir: &Option<PathBuf>) -> &Path {
+    source_dir.as_deref().unwrap_or_else(|| Path::new("."))
+}
+
 /// コマンド定義
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Command {
@@ -193,8 +500,18 @@ pub struct Command {
     #[serde(default)]
     pub env: AHashMap<String, String>,
 
+    /// 環境変数を読み込む dotenv ファイル（末尾 `?` で任意扱い）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<String>,
+
+    /// 定義元の設定ファイルのディレクトリ（ロード時に設定、保存しない）
+    ///
+    /// `env_file` とスクリプトファイルの相対パスは実行時にこのディレクトリ基準で解決する
+    #[serde(skip)]
+    pub source_dir: Option<PathBuf>,
+
     /// 作業ディレクトリ
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub working_dir: Option<PathBuf>,
 
     /// 依存コマンド
@@ -209,9 +526,13 @@ pub struct Command {
     #[serde(default)]
     pub tags: Vec<String>,
 
-    /// タイムアウト
-    #[serde(default)]
-    pub timeout: Option<u64>,
+    /// タイムアウト（`"30s"` などの時間指定、整数は秒）
+    #[serde(
+        default,
+        with = "crate::config::duration::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout: Option<Duration>,
 
     /// 並列実行可能
     #[serde(default)]
@@ -221,19 +542,73 @@ pub struct Command {
     #[serde(default)]
     pub confirm: bool,
 
+    /// 確認プロンプトに表示するメッセージ（未指定時はデフォルトの文言）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm_message: Option<String>,
+
     /// コマンド連結（&&, ||, ;）を許可
     /// デフォルト: None（グローバル設定に従う）
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allow_chaining: Option<bool>,
 
     /// サブシェル（()）を許可
     /// デフォルト: None（グローバル設定に従う）
     /// 用途: grep -E '(pattern)', (cd /tmp && make)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allow_subshells: Option<bool>,
+
+    /// 出力の転送先（Unix: 名前付きFIFO、Windows: 名前付きパイプ）
+    /// 端末への出力に加えて、stdout/stderr の各行をこのパイプにも書き込む
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_pipe: Option<String>,
+
+    /// カテゴリ（`list --grouped` でのグループ分けに使用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+
+    /// ファイル監視設定（`cmdrun run --watch` / `cmdrun watch` で使用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watch: Option<WatchSpec>,
+
+    /// 実行条件: 指定したコマンドが成功（終了コード0）した場合のみ実行
+    /// 失敗した場合は実行せずにスキップ（成功扱い）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+
+    /// 実行条件（インラインのシェルコマンド版）
+    /// `when` と併用した場合は両方が成功した場合のみ実行
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when_shell: Option<String>,
+
+    /// このコマンドだけに使うシェル（`python3` など、`-c` でコマンド文字列を渡す）
+    /// 未指定時は `config.shell`（または `--shell`）のシェルを使う
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+
+    /// 位置引数ごとの補完候補（`[["staging", "prod"]]` なら第1引数の候補）
+    /// シェル補完が `cmdrun complete-args` 経由で使う
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arg_completions: Vec<Vec<String>>,
 }
 
 impl Command {
+    /// `env_file` から読み込む環境変数（`env` より優先度が低い）
+    pub fn env_file_vars(&self) -> anyhow::Result<AHashMap<String, String>> {
+        match &self.env_file {
+            Some(spec) => Ok(load_env_file(spec, base_dir(&self.source_dir))?.unwrap_or_default()),
+            None => Ok(AHashMap::new()),
+        }
+    }
+
+    /// スクリプトファイル（`cmd = { file = "..." }`）の実行時のパス
+    ///
+    /// `~`・`$VAR`・`${VAR}` を展開し、相対パスは定義元の設定ファイルのディレクトリ基準で解決する。
+    /// 解決したパスは設定には保存しない
+    pub fn script_path(&self, file: &str, strict: bool) -> anyhow::Result<PathBuf> {
+        let file = expand_path(file, strict)?;
+        Ok(base_dir(&self.source_dir).join(file))
+    }
+
     /// コマンドの安全性を検証
     pub fn validate(&self) -> Result<(), String> {
         use crate::security::validation::CommandValidator;
@@ -275,13 +650,18 @@ impl Command {
                     }
                 }
             }
+            // スクリプトの内容は実行時に読み込んで検証する
+            CommandSpec::Script { .. } => {}
         }
         Ok(())
     }
 }
 
 /// コマンド仕様（文字列、配列、プラットフォーム別）
+///
+/// シリアライズ時は `deserialize_command` と同じ形（タグなし）で出力する
 #[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
 pub enum CommandSpec {
     /// 単一コマンド
     Single(String),
@@ -291,24 +671,52 @@ pub enum CommandSpec {
 
     /// プラットフォーム別コマンド
     Platform(PlatformCommands),
+
+    /// 外部スクリプトファイル（`cmd = { file = "scripts/deploy.sh" }`）
+    ///
+    /// 相対パスは読み込み時に設定ファイルのディレクトリ基準で解決され、
+    /// 実行時に内容を検証してからシェルにファイルとして渡される
+    Script { file: String },
 }
 
 /// プラットフォーム別コマンド定義
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PlatformCommands {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unix: Option<String>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub linux: Option<String>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub macos: Option<String>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub windows: Option<String>,
 }
 
+/// コマンド単位のファイル監視設定
+///
+/// `cmdrun watch` のコマンドラインで指定しなかった項目に使われる
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct WatchSpec {
+    /// 監視するパス（相対パスは作業ディレクトリ基準、デフォルト: 作業ディレクトリ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<PathBuf>,
+
+    /// 監視するファイルのglobパターン（デフォルト: すべて）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+
+    /// 除外するglobパターン
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+
+    /// デバウンス時間（ミリ秒、デフォルト: 500）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debounce_ms: Option<u64>,
+}
+
 /// プラットフォーム種類
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -330,6 +738,22 @@ pub struct Hooks {
     #[serde(default)]
     pub post_run: Option<String>,
 
+    /// `cmdrun run` の最初に一度だけ実行
+    #[serde(default)]
+    pub before_all: Vec<String>,
+
+    /// `cmdrun run` の最後に一度だけ実行（失敗時も実行）
+    #[serde(default)]
+    pub after_all: Vec<String>,
+
+    /// 各コマンドの実行前
+    #[serde(default)]
+    pub before_each: Vec<String>,
+
+    /// 各コマンドの実行後（失敗時も実行）
+    #[serde(default)]
+    pub after_each: Vec<String>,
+
     /// コマンド別フック
     #[serde(default)]
     pub commands: AHashMap<String, CommandHooks>,
@@ -341,6 +765,10 @@ impl Hooks {
         Self {
             pre_run: overlay.pre_run.or(self.pre_run),
             post_run: overlay.post_run.or(self.post_run),
+            before_all: non_empty_or(overlay.before_all, self.before_all),
+            after_all: non_empty_or(overlay.after_all, self.after_all),
+            before_each: non_empty_or(overlay.before_each, self.before_each),
+            after_each: non_empty_or(overlay.after_each, self.after_each),
             commands: {
                 let mut merged = self.commands;
                 merged.extend(overlay.commands);
@@ -350,6 +778,15 @@ impl Hooks {
     }
 }
 
+/// overlay が空でなければ overlay、空なら base を使う
+fn non_empty_or(overlay: Vec<String>, base: Vec<String>) -> Vec<String> {
+    if overlay.is_empty() {
+        base
+    } else {
+        overlay
+    }
+}
+
 /// コマンド別フック
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CommandHooks {
@@ -404,7 +841,9 @@ where
         type Value = CommandSpec;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("a string, array of strings, or platform-specific commands")
+            formatter.write_str(
+                "a string, array of strings, platform-specific commands, or a script file",
+            )
         }
 
         fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
@@ -429,9 +868,31 @@ where
         where
             A: de::MapAccess<'de>,
         {
-            let platform_commands =
-                PlatformCommands::deserialize(de::value::MapAccessDeserializer::new(map))?;
-            Ok(CommandSpec::Platform(platform_commands))
+            #[derive(Deserialize)]
+            struct CommandTable {
+                #[serde(default)]
+                file: Option<String>,
+                #[serde(flatten)]
+                platform: PlatformCommands,
+            }
+
+            let table = CommandTable::deserialize(de::value::MapAccessDeserializer::new(map))?;
+            match table.file {
+                Some(file) => {
+                    let p = &table.platform;
+                    if p.unix.is_some()
+                        || p.linux.is_some()
+                        || p.macos.is_some()
+                        || p.windows.is_some()
+                    {
+                        return Err(de::Error::custom(
+                            "`file` cannot be combined with platform-specific commands",
+                        ));
+                    }
+                    Ok(CommandSpec::Script { file })
+                }
+                None => Ok(CommandSpec::Platform(table.platform)),
+            }
         }
     }
 
@@ -454,8 +915,16 @@ fn default_true() -> bool {
     true
 }
 
-fn default_timeout() -> u64 {
-    300
+pub(crate) fn default_timeout() -> Duration {
+    Duration::from_secs(300)
+}
+
+fn default_kill_grace_ms() -> u64 {
+    crate::command::executor::DEFAULT_KILL_GRACE_MS
+}
+
+fn default_notify_threshold_ms() -> u64 {
+    10_000
 }
 
 fn default_working_dir() -> PathBuf {
@@ -466,6 +935,13 @@ fn default_typo_threshold() -> usize {
     2
 }
 
+fn default_redact_patterns() -> Vec<String> {
+    crate::security::DEFAULT_REDACT_PATTERNS
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
 // ===================================================================
 // ユーティリティメソッド
 // ===================================================================
@@ -485,6 +961,8 @@ impl CommandSpec {
                 };
                 cmd.map(|c| vec![c.clone()])
             }
+            // 実行時はファイルの内容ではなくパスをシェルに渡す
+            CommandSpec::Script { file } => Some(vec![file.clone()]),
         }
     }
 }
@@ -551,6 +1029,98 @@ mod tests {
         assert!(matches!(cmd.cmd, CommandSpec::Platform(_)));
     }
 
+    #[test]
+    fn test_deserialize_script_file() {
+        let toml = r#"
+            description = "test"
+            cmd = { file = "scripts/deploy.sh" }
+        "#;
+        let cmd: Command = toml::from_str(toml).unwrap();
+        match cmd.cmd {
+            CommandSpec::Script { file } => assert_eq!(file, "scripts/deploy.sh"),
+            other => panic!("unexpected spec: {:?}", other),
+        }
+
+        let toml = r#"
+            description = "test"
+            cmd = { file = "deploy.sh", unix = "ls" }
+        "#;
+        let err = toml::from_str::<Command>(toml).unwrap_err();
+        assert!(
+            err.to_string().contains("`file` cannot be combined"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_alias_target_without_args() {
+        let target = AliasTarget::parse("test").unwrap();
+        assert_eq!(target.command, "test");
+        assert!(target.args.is_empty());
+
+        let args = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(target.expand(&args).unwrap(), args);
+    }
+
+    #[test]
+    fn test_alias_target_with_fixed_flags() {
+        let target = AliasTarget::parse("test -- --release --features 'a b'").unwrap();
+        assert_eq!(target.command, "test");
+        assert_eq!(target.args, vec!["--release", "--features", "a b"]);
+
+        let expanded = target.expand(&["extra".to_string()]).unwrap();
+        assert_eq!(expanded, vec!["--release", "--features", "a b", "extra"]);
+    }
+
+    #[test]
+    fn test_alias_target_placeholders() {
+        let target = AliasTarget::parse("test -- --exact {1} --log=out-{2}.txt").unwrap();
+
+        let args: Vec<String> = ["foo", "bar", "rest"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            target.expand(&args).unwrap(),
+            vec!["--exact", "foo", "--log=out-bar.txt", "rest"]
+        );
+
+        let err = target.expand(&["foo".to_string()]).unwrap_err();
+        assert_eq!(err, "expects at least 2 argument(s), got 1");
+
+        // `{0}` や数値以外はプレースホルダーとして扱わない
+        let literal = AliasTarget::parse("test {0} {name}").unwrap();
+        assert_eq!(literal.expand(&[]).unwrap(), vec!["{0}", "{name}"]);
+    }
+
+    #[test]
+    fn test_alias_target_does_not_reexpand_argument_values() {
+        let target = AliasTarget::parse("deploy {1}:{2}").unwrap();
+
+        let args: Vec<String> = ["{2}", "prod"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(target.expand(&args).unwrap(), vec!["{2}:prod"]);
+    }
+
+    #[test]
+    fn test_deserialize_language() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            language: Language,
+        }
+
+        let parse = |value: &str| {
+            toml::from_str::<Wrapper>(&format!("language = \"{}\"", value)).map(|w| w.language)
+        };
+        assert_eq!(parse("spanish").unwrap(), Language::Spanish);
+        assert_eq!(parse("german").unwrap(), Language::German);
+        assert_eq!(
+            parse("chinese_traditional").unwrap(),
+            Language::ChineseTraditional
+        );
+        assert!(parse("klingon").is_err());
+    }
+
     #[test]
     fn test_platform_detection() {
         let platform = Platform::current();
@@ -561,4 +1131,21 @@ mod tests {
         #[cfg(target_os = "windows")]
         assert_eq!(platform, Platform::Windows);
     }
+
+    #[test]
+    fn test_command_allowlist_merge() {
+        let base: GlobalConfig = toml::from_str(r#"command_allowlist = ["cargo", "npm"]"#).unwrap();
+        assert_eq!(
+            base.command_allowlist.as_deref(),
+            Some(&["cargo".to_string(), "npm".to_string()][..])
+        );
+
+        // オーバーレイで未指定の場合はベースの許可リストを維持
+        let merged = base.clone().merge_with(GlobalConfig::default());
+        assert_eq!(merged.command_allowlist, base.command_allowlist);
+
+        let overlay: GlobalConfig = toml::from_str(r#"command_allowlist = ["make"]"#).unwrap();
+        let merged = base.merge_with(overlay);
+        assert_eq!(merged.command_allowlist, Some(vec!["make".to_string()]));
+    }
 }
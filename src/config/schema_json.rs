@@ -0,0 +1,334 @@
+//! 設定ファイルの JSON Schema（`cmdrun list --json-schema` 用）
+//!
+//! エディタ（taplo / Even Better TOML など）での補完・検証に使う。
+//! スキーマは [`crate::config::schema`] の serde 定義に合わせて手書きしており、
+//! フィールドの過不足はテストで検出する
+
+use serde_json::{json, Value};
+
+/// JSON Schema の方言
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// `commands.toml` の JSON Schema（draft 2020-12）を生成
+pub fn config_json_schema() -> Value {
+    json!({
+        "$schema": SCHEMA_DIALECT,
+        "title": "cmdrun configuration",
+        "description": "Configuration file for cmdrun (commands.toml)",
+        "type": "object",
+        "properties": {
+            "config": { "$ref": "#/$defs/GlobalConfig" },
+            "commands": {
+                "description": "Commands keyed by name",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/$defs/Command" }
+            },
+            "aliases": {
+                "description": "Aliases mapping a name to a command, optionally followed by arguments ({1}, {2}, ... are replaced by call arguments)",
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            },
+            "hooks": { "$ref": "#/$defs/Hooks" },
+            "plugins": { "$ref": "#/$defs/Plugins" }
+        },
+        "$defs": {
+            "Duration": {
+                "description": "Duration such as \"500ms\", \"30s\", \"5m\" or \"1h\"; a plain integer is a number of seconds",
+                "oneOf": [
+                    { "type": "integer", "minimum": 0 },
+                    { "type": "string", "pattern": "^\\s*\\d+\\s*(ms|s|m|h)?\\s*$" }
+                ]
+            },
+            "Language": {
+                "type": "string",
+                "enum": [
+                    "english",
+                    "japanese",
+                    "chinese_simplified",
+                    "chinese_traditional",
+                    "spanish",
+                    "german"
+                ]
+            },
+            "Platform": {
+                "type": "string",
+                "enum": ["unix", "linux", "macos", "windows"]
+            },
+            "StringMap": {
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            },
+            "StringList": {
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "GlobalConfig": global_config_definition(),
+            "CommandSpec": {
+                "description": "A command line, a list run in order, per-platform commands, or a script file",
+                "oneOf": [
+                    { "type": "string" },
+                    { "$ref": "#/$defs/StringList" },
+                    { "$ref": "#/$defs/PlatformCommands" },
+                    { "$ref": "#/$defs/ScriptFile" }
+                ]
+            },
+            "PlatformCommands": {
+                "type": "object",
+                "properties": {
+                    "unix": { "type": "string" },
+                    "linux": { "type": "string" },
+                    "macos": { "type": "string" },
+                    "windows": { "type": "string" }
+                },
+                "not": { "required": ["file"] },
+                "additionalProperties": true
+            },
+            "ScriptFile": {
+                "description": "Script file run with the shell (relative to the config file's directory)",
+                "type": "object",
+                "properties": {
+                    "file": { "type": "string" }
+                },
+                "required": ["file"],
+                "additionalProperties": false
+            },
+            "WatchSpec": {
+                "description": "Default settings for `cmdrun watch <command>`",
+                "type": "object",
+                "properties": {
+                    "paths": { "$ref": "#/$defs/StringList" },
+                    "patterns": { "$ref": "#/$defs/StringList" },
+                    "exclude": { "$ref": "#/$defs/StringList" },
+                    "debounce_ms": { "type": "integer", "minimum": 0 }
+                },
+                "additionalProperties": true
+            },
+            "Command": command_definition(),
+            "CommandHooks": {
+                "type": "object",
+                "properties": {
+                    "pre_run": { "type": "string" },
+                    "post_run": { "type": "string" }
+                },
+                "additionalProperties": true
+            },
+            "Hooks": {
+                "type": "object",
+                "properties": {
+                    "pre_run": { "type": "string" },
+                    "post_run": { "type": "string" },
+                    "before_all": { "$ref": "#/$defs/StringList" },
+                    "after_all": { "$ref": "#/$defs/StringList" },
+                    "before_each": { "$ref": "#/$defs/StringList" },
+                    "after_each": { "$ref": "#/$defs/StringList" },
+                    "commands": {
+                        "description": "Hooks for individual commands",
+                        "type": "object",
+                        "additionalProperties": { "$ref": "#/$defs/CommandHooks" }
+                    }
+                },
+                "additionalProperties": true
+            },
+            "PluginConfig": {
+                "type": "object",
+                "required": ["path"],
+                "properties": {
+                    "path": { "description": "Plugin library path", "type": "string" },
+                    "enabled": { "type": "boolean", "default": true },
+                    "config": { "$ref": "#/$defs/StringMap", "description": "Plugin-specific settings" },
+                    "hook_timeout": { "description": "Maximum duration of a single hook call in seconds", "type": "integer", "minimum": 0 },
+                    "priority": { "description": "Load and hook order (lower first)", "type": "integer", "default": 0 },
+                    "after": { "$ref": "#/$defs/StringList", "description": "Plugins that must be loaded before this one" },
+                    "target_tags": { "$ref": "#/$defs/StringList", "description": "Only run hooks for commands with one of these tags" }
+                },
+                "additionalProperties": true
+            },
+            "Plugins": {
+                "description": "Plugins keyed by name",
+                "type": "object",
+                "properties": {
+                    "enabled": { "$ref": "#/$defs/StringList" }
+                },
+                "additionalProperties": { "$ref": "#/$defs/PluginConfig" }
+            }
+        }
+    })
+}
+
+/// `[config]` テーブルのスキーマ
+fn global_config_definition() -> Value {
+    json!({
+        "description": "Global settings",
+        "type": "object",
+        "properties": {
+            "shell": { "description": "Shell used to run commands", "type": "string" },
+            "strict_mode": { "description": "Strict mode", "type": "boolean", "default": true },
+            "parallel": { "description": "Run dependencies in parallel by default", "type": "boolean", "default": false },
+            "timeout": { "$ref": "#/$defs/Duration", "description": "Default timeout for every command" },
+            "working_dir": { "description": "Default working directory", "type": "string", "default": "." },
+            "language": { "$ref": "#/$defs/Language", "default": "english" },
+            "env": { "$ref": "#/$defs/StringMap", "description": "Environment variables for every command" },
+            "env_file": { "description": "dotenv file to load (a trailing `?` makes it optional)", "type": "string" },
+            "typo_detection": { "description": "Suggest similar command names on typos", "type": "boolean", "default": true },
+            "typo_threshold": { "description": "Maximum edit distance for typo suggestions", "type": "integer", "minimum": 0, "default": 2 },
+            "auto_correct": { "description": "Auto-correct typos (reserved)", "type": "boolean", "default": false },
+            "allow_command_chaining": { "description": "Allow `&&`, `||` and `;` in commands", "type": "boolean", "default": false },
+            "allow_subshells": { "description": "Allow subshells `(...)` in commands", "type": "boolean", "default": false },
+            "command_allowlist": { "$ref": "#/$defs/StringList", "description": "Base commands allowed to run (allowlist mode)" },
+            "redact_patterns": { "$ref": "#/$defs/StringList", "description": "Glob patterns of environment variable names redacted in history" },
+            "merge_stderr": { "description": "Merge stderr into stdout", "type": "boolean", "default": false },
+            "kill_grace_ms": { "description": "Milliseconds between SIGTERM and SIGKILL when a command times out", "type": "integer", "minimum": 0, "default": 2000 },
+            "max_parallel": { "description": "Maximum number of commands run at once in a parallel group (default: number of CPUs)", "type": "integer", "minimum": 1 },
+            "hooks_enabled": { "description": "Master switch for [hooks]", "type": "boolean", "default": true },
+            "history_max_entries": { "description": "Maximum number of history entries kept (default: 1000)", "type": "integer", "minimum": 0 },
+            "history_max_age": { "description": "Delete history entries older than this, e.g. \"30d\" (s, m, h, d or w)", "type": "string", "pattern": "^\\s*\\d+[smhdw]\\s*$" },
+            "notify_on_completion": { "description": "Send a desktop notification when `cmdrun run` finishes", "type": "boolean", "default": false },
+            "notify_threshold_ms": { "description": "Only notify runs that took at least this many milliseconds", "type": "integer", "minimum": 0, "default": 10000 }
+        },
+        "additionalProperties": true
+    })
+}
+
+/// `[commands.<name>]` テーブルのスキーマ
+fn command_definition() -> Value {
+    json!({
+        "type": "object",
+        "required": ["cmd"],
+        "properties": {
+            "description": { "type": "string" },
+            "cmd": { "$ref": "#/$defs/CommandSpec" },
+            "env": { "$ref": "#/$defs/StringMap" },
+            "env_file": { "description": "dotenv file to load (a trailing `?` makes it optional)", "type": "string" },
+            "working_dir": { "type": "string" },
+            "deps": { "$ref": "#/$defs/StringList", "description": "Commands to run first" },
+            "platform": {
+                "description": "Platforms the command supports",
+                "type": "array",
+                "items": { "$ref": "#/$defs/Platform" }
+            },
+            "tags": { "$ref": "#/$defs/StringList" },
+            "timeout": { "$ref": "#/$defs/Duration" },
+            "parallel": { "description": "Run dependencies in parallel", "type": "boolean", "default": false },
+            "confirm": { "description": "Ask for confirmation before running", "type": "boolean", "default": false },
+            "confirm_message": { "type": "string" },
+            "allow_chaining": { "description": "Override `config.allow_command_chaining`", "type": "boolean" },
+            "allow_subshells": { "description": "Override `config.allow_subshells`", "type": "boolean" },
+            "output_pipe": { "description": "Named pipe (FIFO on Unix) that also receives every output line", "type": "string" },
+            "category": { "type": "string" },
+            "watch": { "$ref": "#/$defs/WatchSpec" },
+            "when": { "description": "Command that must succeed for this command to run (skipped otherwise)", "type": "string" },
+            "when_shell": { "description": "Inline shell condition that must succeed for this command to run", "type": "string" },
+            "shell": { "description": "Shell or interpreter for this command only, e.g. \"python3\" (overrides `config.shell`)", "type": "string" },
+            "arg_completions": {
+                "description": "Shell completion candidates per positional argument (the first list is for argument 1)",
+                "type": "array",
+                "items": { "$ref": "#/$defs/StringList" }
+            }
+        },
+        "additionalProperties": true
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::{
+        CommandHooks, CommandsConfig, GlobalConfig, Hooks, PlatformCommands, WatchSpec,
+    };
+    use crate::config::validation::struct_fields;
+    use crate::config::Command;
+    use serde::de::DeserializeOwned;
+
+    fn properties(schema: &Value, pointer: &str) -> Vec<String> {
+        let mut keys: Vec<String> = schema
+            .pointer(pointer)
+            .and_then(Value::as_object)
+            .unwrap_or_else(|| panic!("missing {}", pointer))
+            .keys()
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    fn fields<T: DeserializeOwned>() -> Vec<String> {
+        let mut fields: Vec<String> = struct_fields::<T>().iter().map(|f| f.to_string()).collect();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn test_schema_is_valid_json_with_top_level_properties() {
+        let schema = config_json_schema();
+        let text = serde_json::to_string_pretty(&schema).unwrap();
+        let parsed: Value = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(parsed["$schema"], SCHEMA_DIALECT);
+        assert!(parsed["properties"]["commands"].is_object());
+        assert!(parsed["properties"]["config"].is_object());
+    }
+
+    #[test]
+    fn test_schema_covers_every_field() {
+        let schema = config_json_schema();
+
+        assert_eq!(
+            properties(&schema, "/properties"),
+            fields::<CommandsConfig>()
+        );
+        assert_eq!(
+            properties(&schema, "/$defs/GlobalConfig/properties"),
+            fields::<GlobalConfig>()
+        );
+        assert_eq!(
+            properties(&schema, "/$defs/Command/properties"),
+            fields::<Command>()
+        );
+        assert_eq!(
+            properties(&schema, "/$defs/PlatformCommands/properties"),
+            fields::<PlatformCommands>()
+        );
+        assert_eq!(
+            properties(&schema, "/$defs/WatchSpec/properties"),
+            fields::<WatchSpec>()
+        );
+        assert_eq!(
+            properties(&schema, "/$defs/Hooks/properties"),
+            fields::<Hooks>()
+        );
+        assert_eq!(
+            properties(&schema, "/$defs/CommandHooks/properties"),
+            fields::<CommandHooks>()
+        );
+        assert_eq!(
+            properties(&schema, "/$defs/PluginConfig/properties"),
+            fields::<crate::plugin::PluginConfig>()
+        );
+    }
+
+    #[test]
+    fn test_every_ref_resolves() {
+        fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(target)) = map.get("$ref") {
+                        refs.push(target);
+                    }
+                    map.values().for_each(|v| collect_refs(v, refs));
+                }
+                Value::Array(items) => items.iter().for_each(|v| collect_refs(v, refs)),
+                _ => {}
+            }
+        }
+
+        let schema = config_json_schema();
+        let mut refs = Vec::new();
+        collect_refs(&schema, &mut refs);
+
+        assert!(!refs.is_empty());
+        for target in refs {
+            let pointer = target.strip_prefix('#').unwrap();
+            assert!(schema.pointer(pointer).is_some(), "unresolved {}", target);
+        }
+    }
+}
@@ -2,19 +2,25 @@
 //!
 //! 設定の妥当性検証と循環依存検出
 
-use crate::config::schema::{CommandsConfig, Platform};
+use crate::config::schema::{
+    AliasTarget, Command, CommandHooks, CommandSpec, CommandsConfig, GlobalConfig, Hooks, Platform,
+    PlatformCommands,
+};
 use crate::config::Language;
 use crate::i18n::{get_message, MessageKey};
+use crate::utils::typo_detector::TypoDetector;
 use ahash::{AHashMap, AHashSet};
 use anyhow::Result;
+use serde::de::{DeserializeOwned, Visitor};
 use std::collections::VecDeque;
+use std::path::PathBuf;
 use tracing::{debug, warn};
 
 /// 設定検証エラー
 #[derive(Debug, thiserror::Error)]
 pub enum ValidationError {
-    #[error("Circular dependency detected: {0}")]
-    CircularDependency(String),
+    #[error("Circular dependency detected: {}", .0.join(" → "))]
+    CircularDependency(Vec<String>),
 
     #[error("Command not found: {command} (referenced by {referenced_by})")]
     CommandNotFound {
@@ -37,6 +43,30 @@ pub enum ValidationError {
 
     #[error("Empty command specification: {0}")]
     EmptyCommand(String),
+
+    #[error("Unknown key '{key}' in {table}{hint}")]
+    UnknownKey {
+        key: String,
+        table: String,
+        /// 綴り間違いの候補（例: " (did you mean 'timeout'?)"）
+        hint: String,
+    },
+
+    #[error("Command '{name}' is defined in multiple files: {files} (using {winner})")]
+    DuplicateCommand {
+        name: String,
+        /// 定義元ファイル（マージ順、カンマ区切り）
+        files: String,
+        /// 採用されたファイル
+        winner: String,
+    },
+
+    #[error("Command '{command}' has no platform-specific cmd for {platforms}")]
+    UncoveredPlatform {
+        command: String,
+        /// 実装のないプラットフォーム（カンマ区切り）
+        platforms: String,
+    },
 }
 
 /// 設定検証器
@@ -61,6 +91,7 @@ impl<'a> ConfigValidator<'a> {
 
         self.validate_command_names()?;
         self.validate_dependencies()?;
+        self.validate_no_cycles()?;
         self.validate_aliases()?;
         self.validate_platforms()?;
         self.validate_commands()?;
@@ -70,7 +101,7 @@ impl<'a> ConfigValidator<'a> {
     }
 
     /// コマンド名の妥当性検証
-    fn validate_command_names(&self) -> Result<()> {
+    pub fn validate_command_names(&self) -> Result<()> {
         for name in self.config.commands.keys() {
             if name.is_empty() {
                 return Err(ValidationError::InvalidCommandName(name.clone()).into());
@@ -88,15 +119,15 @@ impl<'a> ConfigValidator<'a> {
         Ok(())
     }
 
-    /// 依存関係の検証（循環依存検出）
-    fn validate_dependencies(&self) -> Result<()> {
+    /// 依存コマンド・実行条件コマンドの存在確認
+    pub fn validate_dependencies(&self) -> Result<()> {
         for (name, command) in &self.config.commands {
             // 依存コマンドの存在確認
             for dep in &command.deps {
                 // エイリアスを解決
                 let resolved_dep = self.resolve_alias(dep);
 
-                if !self.config.commands.contains_key(resolved_dep) {
+                if !self.config.commands.contains_key(&resolved_dep) {
                     return Err(ValidationError::CommandNotFound {
                         command: dep.clone(),
                         referenced_by: name.clone(),
@@ -105,7 +136,24 @@ impl<'a> ConfigValidator<'a> {
                 }
             }
 
-            // 循環依存検出
+            // 実行条件コマンドの存在確認
+            if let Some(predicate) = &command.when {
+                if !self.config.commands.contains_key(predicate) {
+                    return Err(ValidationError::CommandNotFound {
+                        command: predicate.clone(),
+                        referenced_by: name.clone(),
+                    }
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 循環依存の検出
+    pub fn validate_no_cycles(&self) -> Result<()> {
+        for name in self.config.commands.keys() {
             self.check_circular_dependency(name)?;
         }
 
@@ -127,10 +175,10 @@ impl<'a> ConfigValidator<'a> {
         visited: &mut AHashSet<String>,
         path: &mut Vec<String>,
     ) -> Result<()> {
-        // 現在のパスに既に含まれている場合は循環
-        if path.contains(&current.to_string()) {
-            path.push(current.to_string());
-            let cycle = path.join(" -> ");
+        // 現在のパスに既に含まれている場合は循環（循環部分のみを報告）
+        if let Some(pos) = path.iter().position(|p| p == current) {
+            let mut cycle = path[pos..].to_vec();
+            cycle.push(current.to_string());
             return Err(ValidationError::CircularDependency(cycle).into());
         }
 
@@ -146,7 +194,7 @@ impl<'a> ConfigValidator<'a> {
         if let Some(command) = self.config.commands.get(current) {
             for dep in &command.deps {
                 let resolved_dep = self.resolve_alias(dep);
-                self.dfs_check(resolved_dep, visited, path)?;
+                self.dfs_check(&resolved_dep, visited, path)?;
             }
         }
 
@@ -155,13 +203,16 @@ impl<'a> ConfigValidator<'a> {
     }
 
     /// エイリアスを検証
-    fn validate_aliases(&self) -> Result<()> {
+    pub fn validate_aliases(&self) -> Result<()> {
         for (alias, target) in &self.config.aliases {
+            let parsed = AliasTarget::parse(target)
+                .map_err(|e| anyhow::anyhow!("Invalid alias '{}': {}", alias, e))?;
+
             // エイリアスターゲットの存在確認
-            if !self.config.commands.contains_key(target) {
+            if !self.config.commands.contains_key(&parsed.command) {
                 return Err(ValidationError::AliasTargetNotFound {
                     alias: alias.clone(),
-                    target: target.clone(),
+                    target: parsed.command,
                 }
                 .into());
             }
@@ -193,7 +244,7 @@ impl<'a> ConfigValidator<'a> {
     }
 
     /// コマンド仕様の検証
-    fn validate_commands(&self) -> Result<()> {
+    pub fn validate_commands(&self) -> Result<()> {
         for (name, command) in &self.config.commands {
             // プラットフォーム別コマンドの場合、現在のプラットフォームで実行可能か確認
             if let Some(cmds) = command.cmd.resolve_for_platform(&self.current_platform) {
@@ -211,16 +262,11 @@ impl<'a> ConfigValidator<'a> {
         Ok(())
     }
 
-    /// エイリアスを解決
-    fn resolve_alias<'b>(&self, name: &'b str) -> &'b str
-    where
-        'a: 'b,
-    {
+    /// エイリアスを解決（引数付きエイリアスはコマンド名のみ）
+    fn resolve_alias(&self, name: &str) -> String {
         self.config
-            .aliases
-            .get(name)
-            .map(|s| s.as_str())
-            .unwrap_or(name)
+            .alias_command(name)
+            .unwrap_or_else(|| name.to_string())
     }
 
     /// 依存グラフを構築（トポロジカルソート）
@@ -334,10 +380,200 @@ impl Default for DependencyGraph {
     }
 }
 
+/// 複数の設定ファイルで定義されているコマンドを検出する
+///
+/// `sources` はコマンド名ごとの定義元ファイル（マージ順）。後のファイルの定義が採用される
+pub fn find_duplicate_commands(sources: &AHashMap<String, Vec<PathBuf>>) -> Vec<ValidationError> {
+    let mut duplicates: Vec<(&String, &Vec<PathBuf>)> = sources
+        .iter()
+        .filter(|(_, files)| files.len() > 1)
+        .collect();
+    duplicates.sort_by_key(|(name, _)| *name);
+
+    duplicates
+        .into_iter()
+        .map(|(name, files)| ValidationError::DuplicateCommand {
+            name: name.clone(),
+            files: files
+                .iter()
+                .map(|f| f.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            winner: files[files.len() - 1].display().to_string(),
+        })
+        .collect()
+}
+
+/// プラットフォーム別の `cmd` で実装のないプラットフォームを検出する
+///
+/// `platform` で宣言したプラットフォーム（未指定時は Linux / macOS / Windows）のうち、
+/// `cmd = { ... }` に該当する指定（`unix` は Linux と macOS を含む）がないものを報告する
+pub fn find_uncovered_platforms(config: &CommandsConfig) -> Vec<ValidationError> {
+    let mut names: Vec<&String> = config.commands.keys().collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let command = &config.commands[name];
+            if !matches!(command.cmd, CommandSpec::Platform(_)) {
+                return None;
+            }
+
+            let uncovered: Vec<String> = required_platforms(&command.platform)
+                .into_iter()
+                .filter(|platform| command.cmd.resolve_for_platform(platform).is_none())
+                .map(|platform| format!("{:?}", platform))
+                .collect();
+            (!uncovered.is_empty()).then(|| ValidationError::UncoveredPlatform {
+                command: name.clone(),
+                platforms: uncovered.join(", "),
+            })
+        })
+        .collect()
+}
+
+/// 実装が必要なプラットフォーム（`Unix` は Linux と macOS に展開）
+fn required_platforms(declared: &[Platform]) -> Vec<Platform> {
+    let all = [Platform::Linux, Platform::Macos, Platform::Windows];
+    if declared.is_empty() {
+        return all.to_vec();
+    }
+
+    all.into_iter()
+        .filter(|platform| platform.is_supported(declared))
+        .collect()
+}
+
+/// 設定ファイル内の未知のキーを検出する（`validate --strict-schema` 用）
+///
+/// serde は未知のフィールドを黙って無視するため、生の TOML テーブルを
+/// スキーマのフィールド名と突き合わせて検出する。
+/// 通常の読み込みは前方互換性のため従来どおり未知のキーを許容する
+pub fn find_unknown_keys(content: &str) -> Result<Vec<ValidationError>> {
+    let root: toml::Table = toml::from_str(content)?;
+    let mut errors = Vec::new();
+
+    check_table::<CommandsConfig>(&root, "", &mut errors);
+
+    if let Some(toml::Value::Table(config)) = root.get("config") {
+        check_table::<GlobalConfig>(config, "config", &mut errors);
+    }
+
+    if let Some(toml::Value::Table(commands)) = root.get("commands") {
+        for (name, value) in commands {
+            let Some(command) = value.as_table() else {
+                continue;
+            };
+            let path = format!("commands.{}", name);
+            check_table::<Command>(command, &path, &mut errors);
+
+            if let Some(toml::Value::Table(cmd)) = command.get("cmd") {
+                check_table::<PlatformCommands>(cmd, &format!("{}.cmd", path), &mut errors);
+            }
+        }
+    }
+
+    if let Some(toml::Value::Table(hooks)) = root.get("hooks") {
+        check_table::<Hooks>(hooks, "hooks", &mut errors);
+
+        if let Some(toml::Value::Table(commands)) = hooks.get("commands") {
+            for (name, value) in commands {
+                if let Some(command_hooks) = value.as_table() {
+                    let path = format!("hooks.commands.{}", name);
+                    check_table::<CommandHooks>(command_hooks, &path, &mut errors);
+                }
+            }
+        }
+    }
+
+    if let Some(toml::Value::Table(plugins)) = root.get("plugins") {
+        for (name, value) in plugins {
+            if let Some(plugin) = value.as_table() {
+                let path = format!("plugins.{}", name);
+                check_table::<crate::plugin::PluginConfig>(plugin, &path, &mut errors);
+            }
+        }
+    }
+
+    Ok(errors)
+}
+
+/// テーブルのキーを `T` のフィールド名と比較し、未知のキーを記録
+fn check_table<T: DeserializeOwned>(
+    table: &toml::Table,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let fields = struct_fields::<T>();
+    // flatten などでフィールド一覧が取れない構造体は検査しない
+    if fields.is_empty() {
+        return;
+    }
+
+    let detector = TypoDetector::with_threshold(2);
+    for key in table.keys() {
+        if fields.contains(&key.as_str()) {
+            continue;
+        }
+
+        let hint = detector
+            .suggest(key, fields)
+            .first()
+            .map(|(field, _)| format!(" (did you mean '{}'?)", field))
+            .unwrap_or_default();
+        let table = if path.is_empty() {
+            "top-level table".to_string()
+        } else {
+            format!("[{}]", path)
+        };
+
+        errors.push(ValidationError::UnknownKey {
+            key: key.clone(),
+            table,
+            hint,
+        });
+    }
+}
+
+/// serde の derive が `deserialize_struct` に渡すフィールド名一覧を取得
+pub(crate) fn struct_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldCapture(&mut fields));
+    fields
+}
+
+/// フィールド名を捕捉するだけの Deserializer（値の読み込みは常に失敗する）
+struct FieldCapture<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> serde::Deserializer<'de> for FieldCapture<'_> {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(serde::de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(serde::de::Error::custom("fields captured"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::schema::{Command, CommandSpec, GlobalConfig, Hooks};
+    use crate::config::schema::CommandSpec;
 
     fn create_test_config() -> CommandsConfig {
         let mut commands = AHashMap::new();
@@ -348,6 +584,8 @@ mod tests {
                 description: "Build".to_string(),
                 cmd: CommandSpec::Single("cargo build".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -355,8 +593,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -366,6 +612,8 @@ mod tests {
                 description: "Test".to_string(),
                 cmd: CommandSpec::Single("cargo test".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec!["build".to_string()],
                 platform: vec![],
@@ -373,8 +621,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -435,6 +691,21 @@ mod tests {
             .contains("Command not found"));
     }
 
+    #[test]
+    fn test_missing_when_command() {
+        let mut config = create_test_config();
+
+        config.commands.get_mut("test").unwrap().when = Some("nonexistent".to_string());
+
+        let validator = ConfigValidator::new(&config);
+        let result = validator.validate();
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Command not found"));
+    }
+
     #[test]
     fn test_topological_sort() {
         let config = create_test_config();
@@ -459,6 +730,23 @@ mod tests {
         assert!(validator.validate().is_ok());
     }
 
+    #[test]
+    fn test_alias_with_args_resolution() {
+        let mut config = create_test_config();
+        config
+            .aliases
+            .insert("t1".to_string(), "test -- --exact {1}".to_string());
+        assert!(ConfigValidator::new(&config).validate().is_ok());
+
+        config
+            .aliases
+            .insert("bad".to_string(), "missing -- {1}".to_string());
+        let err = ConfigValidator::new(&config)
+            .validate_aliases()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Alias target not found: bad -> missing");
+    }
+
     #[test]
     fn test_invalid_command_name() {
         let mut config = create_test_config();
@@ -468,6 +756,8 @@ mod tests {
                 description: "Invalid".to_string(),
                 cmd: CommandSpec::Single("echo test".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -475,8 +765,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -484,4 +782,170 @@ mod tests {
         let result = validator.validate();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_unknown_top_level_key() {
+        let errors = find_unknown_keys(
+            r#"
+[config]
+shell = "bash"
+
+[comands.build]
+cmd = "cargo build"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "Unknown key 'comands' in top-level table (did you mean 'commands'?)"
+        );
+    }
+
+    #[test]
+    fn test_find_duplicate_commands() {
+        let mut sources = AHashMap::new();
+        sources.insert(
+            "build".to_string(),
+            vec![
+                PathBuf::from("/home/user/.config/cmdrun/commands.toml"),
+                PathBuf::from("/project/commands.toml"),
+            ],
+        );
+        sources.insert(
+            "test".to_string(),
+            vec![PathBuf::from("/project/commands.toml")],
+        );
+
+        let duplicates = find_duplicate_commands(&sources);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(
+            duplicates[0].to_string(),
+            "Command 'build' is defined in multiple files: \
+             /home/user/.config/cmdrun/commands.toml, /project/commands.toml \
+             (using /project/commands.toml)"
+        );
+    }
+
+    #[test]
+    fn test_platform_spec_fully_covered() {
+        let config: CommandsConfig = toml::from_str(
+            r#"
+[commands.open]
+cmd = { unix = "xdg-open .", windows = "explorer ." }
+
+[commands.notify]
+cmd = { macos = "osascript -e 'beep'" }
+platform = ["macos"]
+
+[commands.build]
+cmd = "cargo build"
+"#,
+        )
+        .unwrap();
+
+        assert!(find_uncovered_platforms(&config).is_empty());
+    }
+
+    #[test]
+    fn test_platform_spec_missing_windows() {
+        let config: CommandsConfig = toml::from_str(
+            r#"
+[commands.open]
+cmd = { linux = "xdg-open .", macos = "open ." }
+
+[commands.install]
+cmd = { linux = "apt install jq" }
+platform = ["unix"]
+"#,
+        )
+        .unwrap();
+
+        let messages: Vec<String> = find_uncovered_platforms(&config)
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Command 'install' has no platform-specific cmd for Macos",
+                "Command 'open' has no platform-specific cmd for Windows",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_command_key() {
+        let errors = find_unknown_keys(
+            r#"
+[config]
+shel = "bash"
+
+[commands.build]
+cmd = "cargo build"
+timout = 30
+
+[commands.deploy.cmd]
+unix = "./deploy.sh"
+linx = "./deploy.sh"
+"#,
+        )
+        .unwrap();
+
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Unknown key 'shel' in [config] (did you mean 'shell'?)",
+                "Unknown key 'timout' in [commands.build] (did you mean 'timeout'?)",
+                "Unknown key 'linx' in [commands.deploy.cmd] (did you mean 'linux'?)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_known_keys_pass_strict_schema() {
+        let errors = find_unknown_keys(
+            r#"
+[config]
+shell = "bash"
+timeout = 60
+language = "german"
+
+[config.env]
+ANY_NAME = "ok"
+
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+deps = []
+timeout = 30
+confirm = true
+confirm_message = "Really?"
+
+[commands.build.env]
+RUST_LOG = "debug"
+
+[aliases]
+b = "build"
+
+[hooks]
+pre_run = "echo start"
+
+[hooks.commands.build]
+post_run = "echo done"
+
+[plugins]
+enabled = ["logger"]
+
+[plugins.logger]
+path = "plugins/logger.so"
+hook_timeout = 5
+"#,
+        )
+        .unwrap();
+
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! 包括的で情報豊富なエラーハンドリングを提供
 
+use serde::Serialize;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -56,8 +57,8 @@ pub enum ConfigError {
     #[error("Command not found: {0}")]
     CommandNotFound(String),
 
-    #[error("Circular dependency detected: {0}")]
-    CircularDependency(String),
+    #[error("Circular dependency detected: {}", .0.join(" → "))]
+    CircularDependency(Vec<String>),
 
     #[error("Missing required field: {field} in {context}")]
     MissingField { field: String, context: String },
@@ -75,8 +76,11 @@ pub enum ExecutionError {
     #[error("Command failed with exit code {code}: {command}")]
     CommandFailed { command: String, code: i32 },
 
-    #[error("Command timed out after {timeout}s: {command}")]
-    Timeout { command: String, timeout: u64 },
+    #[error("Command timed out after {}: {command}", crate::config::duration::format_duration(*.timeout))]
+    Timeout {
+        command: String,
+        timeout: std::time::Duration,
+    },
 
     #[error("Shell not found: {0}")]
     ShellNotFound(String),
@@ -88,11 +92,55 @@ pub enum ExecutionError {
         source: std::io::Error,
     },
 
+    /// セキュリティ検証で拒否された
+    #[error("Command rejected by security validation: {command}\nReason: {reason}")]
+    ValidationFailed { command: String, reason: String },
+
+    #[error("Working directory not found: {path} (command: {command})")]
+    WorkingDirNotFound { command: String, path: String },
+
+    #[error("Failed to read script file: {path} (command: {command})\nReason: {source}")]
+    ScriptReadFailed {
+        command: String,
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to open output pipe: {path}\nReason: {source}")]
+    OutputPipeFailed {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error("User cancelled execution")]
     Cancelled,
 
+    #[error("Interrupted")]
+    Interrupted,
+
+    /// 確認が必要だが非対話環境のため実行できない（メッセージはローカライズ済み）
+    #[error("{0}")]
+    ConfirmationRequired(String),
+
     #[error("Dependency failed: {dependency} required by {command}")]
     DependencyFailed { dependency: String, command: String },
+
+    #[error("{stage} hook failed with exit code {code}: {command}")]
+    HookFailed {
+        stage: String,
+        command: String,
+        code: i32,
+    },
+
+    /// プラグインの pre_execute が実行を拒否した
+    #[error("Command '{command}' blocked by {plugin} plugin: {reason}")]
+    BlockedByPlugin {
+        command: String,
+        plugin: String,
+        reason: String,
+    },
 }
 
 /// 変数展開エラー
@@ -112,6 +160,253 @@ pub enum InterpolationError {
 
     #[error("Variable expansion result too large: {0} bytes (max 10KB)")]
     ExpansionTooLarge(usize),
+
+    #[error("Missing positional argument {{{index}}}: {given} argument(s) given")]
+    MissingArgument { index: usize, given: usize },
+}
+
+impl CmdrunError {
+    /// 機械可読なエラー種別（`--error-json` 出力用、安定した識別子）
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Config(e) => e.kind(),
+            Self::Execution(e) => e.kind(),
+            Self::Interpolation(e) => e.kind(),
+            Self::PluginError { .. } => "PluginError",
+            Self::PluginLoad(_) => "PluginLoadFailed",
+            Self::Io(_) => "Io",
+            Self::Other(_) => "Other",
+        }
+    }
+
+    /// 失敗したコマンドの終了コード（該当する場合のみ）
+    pub fn command_exit_code(&self) -> Option<i32> {
+        match self {
+            Self::Execution(e) => e.command_exit_code(),
+            _ => None,
+        }
+    }
+
+    /// cmdrun プロセスの終了コード（[`exit_code`] 参照）
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Config(e) => e.exit_code(),
+            Self::Execution(e) => e.exit_code(),
+            _ => exit_code::FAILURE,
+        }
+    }
+}
+
+impl ConfigError {
+    /// 機械可読なエラー種別
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::FileNotFound(_) => "ConfigFileNotFound",
+            Self::ParseError { .. } => "ConfigParseError",
+            Self::InvalidConfig(_) => "InvalidConfig",
+            Self::CommandNotFound(_) => "CommandNotFound",
+            Self::CircularDependency(_) => "CircularDependency",
+            Self::MissingField { .. } => "MissingField",
+            Self::InvalidShell(_) => "InvalidShell",
+            Self::UnsupportedPlatform { .. } => "UnsupportedPlatform",
+        }
+    }
+
+    /// cmdrun プロセスの終了コード
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::FileNotFound(_) => exit_code::CONFIG_NOT_FOUND,
+            Self::CommandNotFound(_) => exit_code::COMMAND_NOT_FOUND,
+            _ => exit_code::FAILURE,
+        }
+    }
+}
+
+impl ExecutionError {
+    /// 機械可読なエラー種別
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::CommandFailed { .. } => "CommandExecutionFailed",
+            Self::Timeout { .. } => "CommandTimeout",
+            Self::ShellNotFound(_) => "ShellNotFound",
+            Self::ValidationFailed { .. } => "ValidationFailed",
+            Self::SpawnFailed { .. } => "SpawnFailed",
+            Self::WorkingDirNotFound { .. } => "WorkingDirNotFound",
+            Self::ScriptReadFailed { .. } => "ScriptReadFailed",
+            Self::OutputPipeFailed { .. } => "OutputPipeFailed",
+            Self::Cancelled => "Cancelled",
+            Self::Interrupted => "Interrupted",
+            Self::ConfirmationRequired(_) => "ConfirmationRequired",
+            Self::DependencyFailed { .. } => "DependencyFailed",
+            Self::HookFailed { .. } => "HookFailed",
+            Self::BlockedByPlugin { .. } => "BlockedByPlugin",
+        }
+    }
+
+    /// 失敗したコマンドの終了コード（該当する場合のみ）
+    pub fn command_exit_code(&self) -> Option<i32> {
+        match self {
+            Self::CommandFailed { code, .. } | Self::HookFailed { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// cmdrun プロセスの終了コード
+    ///
+    /// コマンド自体の失敗はそのコマンドの終了コードを引き継ぐ
+    /// （シグナル終了等で有効な値がない場合は 1）
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Timeout { .. } => exit_code::TIMEOUT,
+            Self::Interrupted => exit_code::INTERRUPTED,
+            Self::ValidationFailed { .. } => exit_code::VALIDATION_FAILED,
+            Self::CommandFailed { code, .. } | Self::HookFailed { code, .. }
+                if (1..=255).contains(code) =>
+            {
+                *code
+            }
+            _ => exit_code::FAILURE,
+        }
+    }
+}
+
+impl InterpolationError {
+    /// 機械可読なエラー種別
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::UndefinedVariable(_) => "UndefinedVariable",
+            Self::RequiredVariableNotSet(_) => "RequiredVariableNotSet",
+            Self::InvalidSyntax(_) => "InvalidVariableSyntax",
+            Self::RecursiveExpansion(_) => "RecursiveExpansion",
+            Self::ExpansionTooLarge(_) => "ExpansionTooLarge",
+            Self::MissingArgument { .. } => "MissingArgument",
+        }
+    }
+}
+
+/// cmdrun プロセスの終了コード
+///
+/// コマンド自体が失敗した場合は、そのコマンドの終了コードで終了する
+pub mod exit_code {
+    /// 一般エラー
+    pub const FAILURE: i32 = 1;
+    /// 設定ファイルが見つからない
+    pub const CONFIG_NOT_FOUND: i32 = 2;
+    /// コマンドが見つからない
+    pub const COMMAND_NOT_FOUND: i32 = 3;
+    /// セキュリティ検証で拒否された
+    pub const VALIDATION_FAILED: i32 = 4;
+    /// タイムアウト（GNU `timeout` と同じ）
+    pub const TIMEOUT: i32 = 124;
+    /// Ctrl+C で中断された（128 + SIGINT）
+    pub const INTERRUPTED: i32 = 130;
+}
+
+/// エラーチェーン中で最初に見つかった型付きエラー
+#[derive(Debug, Clone, Copy)]
+enum TypedError<'a> {
+    Cmdrun(&'a CmdrunError),
+    Config(&'a ConfigError),
+    Execution(&'a ExecutionError),
+    Interpolation(&'a InterpolationError),
+}
+
+impl<'a> TypedError<'a> {
+    fn find(err: &'a anyhow::Error) -> Option<Self> {
+        err.chain().find_map(|cause| {
+            if let Some(e) = cause.downcast_ref::<CmdrunError>() {
+                Some(Self::Cmdrun(e))
+            } else if let Some(e) = cause.downcast_ref::<ConfigError>() {
+                Some(Self::Config(e))
+            } else if let Some(e) = cause.downcast_ref::<ExecutionError>() {
+                Some(Self::Execution(e))
+            } else {
+                cause
+                    .downcast_ref::<InterpolationError>()
+                    .map(Self::Interpolation)
+            }
+        })
+    }
+
+    fn kind(self) -> &'static str {
+        match self {
+            Self::Cmdrun(e) => e.kind(),
+            Self::Config(e) => e.kind(),
+            Self::Execution(e) => e.kind(),
+            Self::Interpolation(e) => e.kind(),
+        }
+    }
+
+    fn command_exit_code(self) -> Option<i32> {
+        match self {
+            Self::Cmdrun(e) => e.command_exit_code(),
+            Self::Execution(e) => e.command_exit_code(),
+            _ => None,
+        }
+    }
+
+    fn exit_code(self) -> i32 {
+        match self {
+            Self::Cmdrun(e) => e.exit_code(),
+            Self::Config(e) => e.exit_code(),
+            Self::Execution(e) => e.exit_code(),
+            Self::Interpolation(_) => exit_code::FAILURE,
+        }
+    }
+}
+
+/// 「設定ファイルが見つからない」エラーを作成（終了コード 2）
+///
+/// `message` がそのまま表示され、`path` は型付きエラーとしてチェーンに残る
+pub fn config_not_found<M>(path: impl Into<PathBuf>, message: M) -> anyhow::Error
+where
+    M: std::fmt::Display + Send + Sync + 'static,
+{
+    anyhow::Error::new(CmdrunError::from(ConfigError::FileNotFound(path.into()))).context(message)
+}
+
+/// anyhow エラーに対応するプロセス終了コード
+///
+/// チェーン中の型付きエラーから判定し、見つからなければ 1
+pub fn process_exit_code(err: &anyhow::Error) -> i32 {
+    TypedError::find(err).map_or(exit_code::FAILURE, TypedError::exit_code)
+}
+
+/// 構造化エラー出力（`--error-json`）
+///
+/// エディタ等のツールがエラーメッセージを解析せずに失敗内容を判別できるようにする
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorReport {
+    /// 表示用メッセージ（通常出力の `Error:` の後と同じ）
+    pub error: String,
+    /// エラー種別（[`CmdrunError::kind`]、型付きエラーでない場合は `"Other"`）
+    pub kind: String,
+    /// 失敗したコマンドの終了コード
+    pub exit_code: Option<i32>,
+    /// 実行しようとしたコマンド名
+    pub command: Option<String>,
+}
+
+impl ErrorReport {
+    /// エラーチェーンから型付きエラーを探してレポートを作成
+    pub fn new(err: &anyhow::Error, command: Option<&str>) -> Self {
+        let typed = TypedError::find(err);
+        let kind = typed.map_or("Other", TypedError::kind);
+        let exit_code = typed.and_then(TypedError::command_exit_code);
+
+        Self {
+            error: err.to_string(),
+            kind: kind.to_string(),
+            exit_code,
+            command: command.map(str::to_string),
+        }
+    }
+
+    /// 1行のJSONにシリアライズ
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|_| format!(r#"{{"error":{:?},"kind":"Other"}}"#, self.error))
+    }
 }
 
 /// Result型のエイリアス
@@ -136,4 +431,111 @@ mod tests {
         };
         assert!(exec_err.to_string().contains("Failed to spawn process"));
     }
+
+    #[test]
+    fn test_error_kind() {
+        let err = CmdrunError::from(ConfigError::CommandNotFound("build".to_string()));
+        assert_eq!(err.kind(), "CommandNotFound");
+        assert_eq!(err.command_exit_code(), None);
+
+        let err = CmdrunError::from(ExecutionError::CommandFailed {
+            command: "false".to_string(),
+            code: 2,
+        });
+        assert_eq!(err.kind(), "CommandExecutionFailed");
+        assert_eq!(err.command_exit_code(), Some(2));
+    }
+
+    #[test]
+    fn test_process_exit_code() {
+        let cases = [
+            (
+                CmdrunError::from(ConfigError::FileNotFound(PathBuf::from("commands.toml"))),
+                exit_code::CONFIG_NOT_FOUND,
+            ),
+            (
+                ConfigError::CommandNotFound("build".to_string()).into(),
+                exit_code::COMMAND_NOT_FOUND,
+            ),
+            (
+                ExecutionError::ValidationFailed {
+                    command: "rm -rf /".to_string(),
+                    reason: "dangerous".to_string(),
+                }
+                .into(),
+                exit_code::VALIDATION_FAILED,
+            ),
+            (
+                ExecutionError::Timeout {
+                    command: "sleep 10".to_string(),
+                    timeout: std::time::Duration::from_secs(1),
+                }
+                .into(),
+                exit_code::TIMEOUT,
+            ),
+            (
+                ExecutionError::CommandFailed {
+                    command: "false".to_string(),
+                    code: 42,
+                }
+                .into(),
+                42,
+            ),
+            (
+                // シグナル終了（有効な終了コードなし）
+                ExecutionError::CommandFailed {
+                    command: "sleep 10".to_string(),
+                    code: -1,
+                }
+                .into(),
+                exit_code::FAILURE,
+            ),
+            (
+                ConfigError::InvalidConfig("bad".to_string()).into(),
+                exit_code::FAILURE,
+            ),
+        ];
+
+        for (err, expected) in cases {
+            let message = err.to_string();
+            let err = anyhow::Error::new(err).context("wrapped");
+            assert_eq!(process_exit_code(&err), expected, "{}", message);
+        }
+        assert_eq!(
+            process_exit_code(&anyhow::anyhow!("untyped")),
+            exit_code::FAILURE
+        );
+    }
+
+    #[test]
+    fn test_error_report_finds_typed_error_behind_context() {
+        let err = anyhow::Error::new(CmdrunError::from(ExecutionError::CommandFailed {
+            command: "false".to_string(),
+            code: 2,
+        }))
+        .context("Command failed with exit code 2");
+
+        let report = ErrorReport::new(&err, Some("build"));
+        assert_eq!(
+            report,
+            ErrorReport {
+                error: "Command failed with exit code 2".to_string(),
+                kind: "CommandExecutionFailed".to_string(),
+                exit_code: Some(2),
+                command: Some("build".to_string()),
+            }
+        );
+        assert_eq!(
+            report.to_json(),
+            r#"{"error":"Command failed with exit code 2","kind":"CommandExecutionFailed","exit_code":2,"command":"build"}"#
+        );
+    }
+
+    #[test]
+    fn test_error_report_untyped_error() {
+        let report = ErrorReport::new(&anyhow::anyhow!("something broke"), None);
+        assert_eq!(report.kind, "Other");
+        assert_eq!(report.exit_code, None);
+        assert_eq!(report.command, None);
+    }
 }
@@ -7,4 +7,8 @@ pub mod recorder;
 pub mod storage;
 
 pub use recorder::HistoryRecorder;
-pub use storage::{CommandStats, HistoryEntry, HistoryStorage};
+pub use storage::{
+    load_frequency_map, parse_age, parse_time_bound, sort_by_frequency, CommandBreakdown,
+    CommandStats, HistoryEntry, HistoryFilter, HistoryStats, HistoryStorage, Trend,
+    DEFAULT_TREND_WINDOW,
+};
@@ -3,33 +3,24 @@
 //! Integrates with the command executor to automatically record
 //! execution history.
 
-use super::storage::{HistoryEntry, HistoryStorage};
+use super::storage::{parse_age, HistoryEntry, HistoryStorage};
 use ahash::AHashMap;
 use anyhow::Result;
 use chrono::Utc;
 use serde_json;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::env;
 
 use crate::config::Language;
 use crate::i18n::{get_message, MessageKey};
-
-/// Sensitive environment variable patterns to exclude from history
-const SENSITIVE_ENV_PATTERNS: &[&str] = &[
-    "KEY",
-    "SECRET",
-    "TOKEN",
-    "PASSWORD",
-    "PASS",
-    "API",
-    "AUTH",
-    "CREDENTIAL",
-];
+use crate::security::EnvRedactor;
 
 /// History recorder that tracks command executions
 pub struct HistoryRecorder {
     storage: HistoryStorage,
     filter_sensitive: bool,
+    redactor: EnvRedactor,
+    confirm_skipped: bool,
 }
 
 impl HistoryRecorder {
@@ -38,6 +29,8 @@ impl HistoryRecorder {
         Ok(Self {
             storage: HistoryStorage::new()?,
             filter_sensitive: true,
+            redactor: EnvRedactor::default(),
+            confirm_skipped: false,
         })
     }
 
@@ -46,6 +39,8 @@ impl HistoryRecorder {
         Self {
             storage,
             filter_sensitive: true,
+            redactor: EnvRedactor::default(),
+            confirm_skipped: false,
         }
     }
 
@@ -54,6 +49,38 @@ impl HistoryRecorder {
         self.filter_sensitive = filter;
     }
 
+    /// Mark subsequent records as having skipped confirmation (`--yes`)
+    pub fn set_confirm_skipped(&mut self, skipped: bool) {
+        self.confirm_skipped = skipped;
+    }
+
+    /// Replace the key patterns whose values are recorded as `***REDACTED***`
+    ///
+    /// Patterns are case-insensitive globs such as `*_TOKEN`.
+    pub fn set_redact_patterns<S: AsRef<str>>(&mut self, patterns: &[S]) -> Result<()> {
+        self.redactor = EnvRedactor::new(patterns.iter().map(AsRef::as_ref))
+            .map_err(|e| anyhow::anyhow!("Invalid redact pattern: {}", e))?;
+        Ok(())
+    }
+
+    /// Apply `history_max_entries` / `history_max_age` from `[config]`
+    ///
+    /// Entries beyond the limits are pruned whenever a run is recorded.
+    /// `max_entries` defaults to 1000 and entries are kept regardless of age
+    /// when `max_age` is not set.
+    pub fn set_retention(
+        &mut self,
+        max_entries: Option<usize>,
+        max_age: Option<&str>,
+    ) -> Result<()> {
+        if let Some(max_entries) = max_entries {
+            self.storage.set_max_entries(max_entries);
+        }
+        self.storage
+            .set_max_age(max_age.map(parse_age).transpose()?);
+        Ok(())
+    }
+
     /// Record the start of a command execution
     ///
     /// Returns an execution ID that should be used with `record_completion`
@@ -83,6 +110,10 @@ impl HistoryRecorder {
             } else {
                 Some(self.serialize_env(env)?)
             },
+            confirm_skipped: self.confirm_skipped,
+            skipped: false,
+            hostname: current_hostname(),
+            cmdrun_version: env!("CARGO_PKG_VERSION").to_string(),
         };
 
         self.storage.add(&entry)
@@ -125,6 +156,49 @@ impl HistoryRecorder {
         duration_ms: i64,
         exit_code: i32,
         success: bool,
+    ) -> Result<i64> {
+        self.record_entry(command, args, env, duration_ms, exit_code, success, false)
+    }
+
+    /// Record a command skipped because its `when` condition was false
+    pub fn record_skipped(
+        &mut self,
+        command: &str,
+        args: &[String],
+        env: &AHashMap<String, String>,
+    ) -> Result<i64> {
+        self.record_entry(command, args, env, 0, 0, true, true)
+    }
+
+    /// Record a command stopped with Ctrl+C
+    pub fn record_interrupted(
+        &mut self,
+        command: &str,
+        args: &[String],
+        env: &AHashMap<String, String>,
+        duration_ms: i64,
+    ) -> Result<i64> {
+        self.record_entry(
+            command,
+            args,
+            env,
+            duration_ms,
+            crate::error::exit_code::INTERRUPTED,
+            false,
+            false,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn record_entry(
+        &mut self,
+        command: &str,
+        args: &[String],
+        env: &AHashMap<String, String>,
+        duration_ms: i64,
+        exit_code: i32,
+        success: bool,
+        skipped: bool,
     ) -> Result<i64> {
         let entry = HistoryEntry {
             id: 0,
@@ -146,6 +220,10 @@ impl HistoryRecorder {
             } else {
                 Some(self.serialize_env(env)?)
             },
+            confirm_skipped: self.confirm_skipped,
+            skipped,
+            hostname: current_hostname(),
+            cmdrun_version: env!("CARGO_PKG_VERSION").to_string(),
         };
 
         self.storage.add(&entry)
@@ -161,29 +239,20 @@ impl HistoryRecorder {
         &mut self.storage
     }
 
-    /// Serialize environment variables, filtering sensitive data
+    /// Serialize environment variables, redacting sensitive values
     fn serialize_env(&self, env: &AHashMap<String, String>) -> Result<String> {
-        let filtered = if self.filter_sensitive {
-            env.iter()
-                .filter(|(k, _)| !self.is_sensitive_key(k))
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect::<HashMap<_, _>>()
+        if self.filter_sensitive {
+            Ok(serde_json::to_string(&self.redactor.redact(env))?)
         } else {
-            env.iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect::<HashMap<_, _>>()
-        };
-
-        Ok(serde_json::to_string(&filtered)?)
+            let plain = env.iter().collect::<BTreeMap<_, _>>();
+            Ok(serde_json::to_string(&plain)?)
+        }
     }
+}
 
-    /// Check if an environment variable name is sensitive
-    fn is_sensitive_key(&self, key: &str) -> bool {
-        let key_upper = key.to_uppercase();
-        SENSITIVE_ENV_PATTERNS
-            .iter()
-            .any(|pattern| key_upper.contains(pattern))
-    }
+/// Name of the machine the command runs on
+fn current_hostname() -> String {
+    gethostname::gethostname().to_string_lossy().into_owned()
 }
 
 impl Default for HistoryRecorder {
@@ -195,6 +264,7 @@ impl Default for HistoryRecorder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
     use tempfile::TempDir;
 
     fn create_test_recorder() -> HistoryRecorder {
@@ -232,15 +302,117 @@ mod tests {
         assert_eq!(entry.duration_ms, Some(1500));
     }
 
+    #[test]
+    fn test_record_includes_host_and_version() {
+        let mut recorder = create_test_recorder();
+        let env = AHashMap::new();
+
+        recorder.record("build", &[], &env, 10, 0, true).unwrap();
+
+        let entry = recorder.storage().get_last().unwrap().unwrap();
+        assert_eq!(entry.hostname, current_hostname());
+        assert!(!entry.hostname.is_empty());
+        assert_eq!(entry.cmdrun_version, env!("CARGO_PKG_VERSION"));
+
+        let json = recorder.storage().export_json(None).unwrap();
+        let exported: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(exported[0]["hostname"], entry.hostname.as_str());
+        assert_eq!(exported[0]["cmdrun_version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_record_confirm_skipped() {
+        let mut recorder = create_test_recorder();
+        let env = AHashMap::new();
+
+        recorder.set_confirm_skipped(true);
+        recorder.record("deploy", &[], &env, 10, 0, true).unwrap();
+
+        let entry = recorder.storage().get_last().unwrap().unwrap();
+        assert_eq!(entry.command, "deploy");
+        assert!(entry.confirm_skipped);
+    }
+
+    #[test]
+    fn test_record_skipped() {
+        let mut recorder = create_test_recorder();
+        let env = AHashMap::new();
+
+        recorder.record_skipped("deploy", &[], &env).unwrap();
+
+        let entry = recorder.storage().get_last().unwrap().unwrap();
+        assert!(entry.skipped);
+        assert!(entry.success);
+        assert_eq!(entry.status(), "skipped");
+    }
+
+    #[test]
+    fn test_record_interrupted() {
+        let mut recorder = create_test_recorder();
+        let env = AHashMap::new();
+
+        recorder
+            .record_interrupted("serve", &[], &env, 2500)
+            .unwrap();
+
+        let entry = recorder.storage().get_last().unwrap().unwrap();
+        assert!(!entry.success);
+        assert_eq!(entry.exit_code, Some(130));
+        assert_eq!(entry.duration_ms, Some(2500));
+        assert_eq!(entry.status(), "interrupted");
+    }
+
+    #[test]
+    fn test_record_applies_retention() {
+        let mut recorder = create_test_recorder();
+        let env = AHashMap::new();
+
+        // An entry from before the retention window
+        recorder
+            .storage_mut()
+            .add(&HistoryEntry {
+                id: 0,
+                command: "stale".to_string(),
+                args: None,
+                start_time: Utc::now().timestamp_millis() - 90 * 86_400_000,
+                duration_ms: Some(10),
+                exit_code: Some(0),
+                success: true,
+                working_dir: None,
+                environment: None,
+                confirm_skipped: false,
+                skipped: false,
+                hostname: String::new(),
+                cmdrun_version: String::new(),
+            })
+            .unwrap();
+
+        recorder.set_retention(Some(2), Some("30d")).unwrap();
+        for name in ["build", "test", "lint"] {
+            recorder.record(name, &[], &env, 10, 0, true).unwrap();
+        }
+
+        let commands: Vec<String> = recorder
+            .storage()
+            .list(None, None)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.command)
+            .collect();
+        assert_eq!(commands.len(), 2);
+        assert!(!commands.contains(&"stale".to_string()));
+        assert!(recorder.set_retention(None, Some("soon")).is_err());
+    }
+
     #[test]
     fn test_sensitive_filtering() {
         let recorder = create_test_recorder();
 
-        assert!(recorder.is_sensitive_key("API_KEY"));
-        assert!(recorder.is_sensitive_key("SECRET_TOKEN"));
-        assert!(recorder.is_sensitive_key("PASSWORD"));
-        assert!(!recorder.is_sensitive_key("PATH"));
-        assert!(!recorder.is_sensitive_key("HOME"));
+        assert!(recorder.redactor.is_redacted("API_KEY"));
+        assert!(recorder.redactor.is_redacted("SECRET_TOKEN"));
+        assert!(recorder.redactor.is_redacted("PASSWORD"));
+        assert!(!recorder.redactor.is_redacted("PATH"));
+        assert!(!recorder.redactor.is_redacted("HOME"));
     }
 
     #[test]
@@ -254,9 +426,35 @@ mod tests {
         let serialized = recorder.serialize_env(&env).unwrap();
         let deserialized: HashMap<String, String> = serde_json::from_str(&serialized).unwrap();
 
-        assert!(deserialized.contains_key("PATH"));
-        assert!(deserialized.contains_key("HOME"));
-        assert!(!deserialized.contains_key("API_KEY"));
+        assert_eq!(deserialized["PATH"], "/usr/bin");
+        assert_eq!(deserialized["HOME"], "/home/user");
+        assert_eq!(deserialized["API_KEY"], crate::security::REDACTED);
+        assert!(!serialized.contains("secret"));
+    }
+
+    #[test]
+    fn test_custom_redact_patterns() {
+        let mut recorder = create_test_recorder();
+        recorder.set_redact_patterns(&["internal_*"]).unwrap();
+
+        assert!(recorder.redactor.is_redacted("INTERNAL_URL"));
+        assert!(!recorder.redactor.is_redacted("API_KEY"));
+        assert!(recorder.set_redact_patterns(&["[invalid"]).is_err());
+    }
+
+    #[test]
+    fn test_token_never_in_exported_json() {
+        let mut recorder = create_test_recorder();
+        let mut env = AHashMap::new();
+        env.insert("GITHUB_TOKEN".to_string(), "ghp_supersecret123".to_string());
+        env.insert("CI".to_string(), "true".to_string());
+
+        recorder.record("deploy", &[], &env, 10, 0, true).unwrap();
+
+        let exported = recorder.storage().export_json(None).unwrap();
+        assert!(!exported.contains("ghp_supersecret123"));
+        assert!(exported.contains("GITHUB_TOKEN"));
+        assert!(exported.contains("***REDACTED***"));
     }
 
     #[test]
@@ -3,6 +3,7 @@
 //! Provides persistent storage for command execution history with
 //! efficient querying and export capabilities.
 
+use ahash::AHashMap;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, OptionalExtension};
@@ -15,8 +16,22 @@ use crate::i18n::{get_message, MessageKey};
 /// Default maximum number of history entries to retain
 const DEFAULT_MAX_HISTORY: usize = 1000;
 
+/// Default number of most recent runs used for the duration trend
+pub const DEFAULT_TREND_WINDOW: usize = 20;
+
+/// Filter applied when listing history entries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HistoryFilter {
+    /// Only entries started at or after this time (Unix timestamp in milliseconds)
+    pub since: Option<i64>,
+    /// Only entries started at or before this time (Unix timestamp in milliseconds)
+    pub until: Option<i64>,
+    /// Only failed entries
+    pub failed_only: bool,
+}
+
 /// Command execution history entry
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HistoryEntry {
     /// Unique entry ID
     pub id: i64,
@@ -34,8 +49,20 @@ pub struct HistoryEntry {
     pub success: bool,
     /// Working directory at execution time
     pub working_dir: Option<String>,
-    /// Environment variables (serialized as JSON, sensitive data filtered)
+    /// Environment variables (serialized as JSON, sensitive values redacted)
     pub environment: Option<String>,
+    /// Whether a confirmation prompt was skipped with `--yes`
+    #[serde(default)]
+    pub confirm_skipped: bool,
+    /// Whether the command was skipped because its `when` condition was false
+    #[serde(default)]
+    pub skipped: bool,
+    /// Host the command ran on (empty for entries recorded by earlier versions)
+    #[serde(default)]
+    pub hostname: String,
+    /// cmdrun version that recorded the entry (empty for entries recorded by earlier versions)
+    #[serde(default)]
+    pub cmdrun_version: String,
 }
 
 impl HistoryEntry {
@@ -55,18 +82,28 @@ impl HistoryEntry {
 
     /// Get status string
     pub fn status(&self) -> &str {
-        if self.success {
+        if self.skipped {
+            "skipped"
+        } else if self.success {
             "success"
+        } else if self.is_interrupted() {
+            "interrupted"
         } else {
             "failed"
         }
     }
+
+    /// Whether the run was stopped with Ctrl+C
+    pub fn is_interrupted(&self) -> bool {
+        !self.success && self.exit_code == Some(crate::error::exit_code::INTERRUPTED)
+    }
 }
 
 /// SQLite-based history storage
 pub struct HistoryStorage {
     conn: Connection,
     max_entries: usize,
+    max_age: Option<chrono::Duration>,
 }
 
 impl HistoryStorage {
@@ -96,6 +133,7 @@ impl HistoryStorage {
         let mut storage = Self {
             conn,
             max_entries: DEFAULT_MAX_HISTORY,
+            max_age: None,
         };
 
         storage.initialize_schema()?;
@@ -103,7 +141,7 @@ impl HistoryStorage {
     }
 
     /// Get the default database path
-    fn default_db_path() -> Result<PathBuf> {
+    pub fn default_db_path() -> Result<PathBuf> {
         let data_dir = dirs::data_local_dir().ok_or_else(|| {
             anyhow::anyhow!(
                 "{}",
@@ -131,6 +169,10 @@ impl HistoryStorage {
                 success BOOLEAN NOT NULL,
                 working_dir TEXT,
                 environment TEXT,
+                confirm_skipped BOOLEAN NOT NULL DEFAULT 0,
+                skipped BOOLEAN NOT NULL DEFAULT 0,
+                hostname TEXT NOT NULL DEFAULT '',
+                cmdrun_version TEXT NOT NULL DEFAULT '',
                 created_at INTEGER DEFAULT (strftime('%s', 'now') * 1000)
             )
             "#,
@@ -153,6 +195,28 @@ impl HistoryStorage {
             [],
         )?;
 
+        // Databases created by earlier versions lack the newer columns
+        for (column, definition) in [
+            ("confirm_skipped", "BOOLEAN NOT NULL DEFAULT 0"),
+            ("skipped", "BOOLEAN NOT NULL DEFAULT 0"),
+            ("hostname", "TEXT NOT NULL DEFAULT ''"),
+            ("cmdrun_version", "TEXT NOT NULL DEFAULT ''"),
+        ] {
+            let exists = self
+                .conn
+                .prepare("SELECT 1 FROM pragma_table_info('command_history') WHERE name = ?1")?
+                .exists([column])?;
+            if !exists {
+                self.conn.execute(
+                    &format!(
+                        "ALTER TABLE command_history ADD COLUMN {} {}",
+                        column, definition
+                    ),
+                    [],
+                )?;
+            }
+        }
+
         Ok(())
     }
 
@@ -161,13 +225,18 @@ impl HistoryStorage {
         self.max_entries = max;
     }
 
+    /// Set the maximum age of retained history entries (`None` keeps them regardless of age)
+    pub fn set_max_age(&mut self, max_age: Option<chrono::Duration>) {
+        self.max_age = max_age;
+    }
+
     /// Add a new history entry
     pub fn add(&mut self, entry: &HistoryEntry) -> Result<i64> {
         let id = self.conn.execute(
             r#"
             INSERT INTO command_history
-                (command, args, start_time, duration_ms, exit_code, success, working_dir, environment)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                (command, args, start_time, duration_ms, exit_code, success, working_dir, environment, confirm_skipped, skipped, hostname, cmdrun_version)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
             "#,
             params![
                 entry.command,
@@ -178,11 +247,15 @@ impl HistoryStorage {
                 entry.success,
                 entry.working_dir,
                 entry.environment,
+                entry.confirm_skipped,
+                entry.skipped,
+                entry.hostname,
+                entry.cmdrun_version,
             ],
         )?;
 
-        // Enforce max entries limit
-        self.cleanup_old_entries()?;
+        // Enforce the retention limits
+        self.auto_prune()?;
 
         Ok(id as i64)
     }
@@ -191,7 +264,7 @@ impl HistoryStorage {
     pub fn get_last(&self) -> Result<Option<HistoryEntry>> {
         self.conn
             .query_row(
-                "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment
+                "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment, confirm_skipped, skipped, hostname, cmdrun_version
                  FROM command_history
                  ORDER BY start_time DESC
                  LIMIT 1",
@@ -206,7 +279,7 @@ impl HistoryStorage {
     pub fn get_last_failed(&self) -> Result<Option<HistoryEntry>> {
         self.conn
             .query_row(
-                "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment
+                "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment, confirm_skipped, skipped, hostname, cmdrun_version
                  FROM command_history
                  WHERE success = 0
                  ORDER BY start_time DESC
@@ -222,7 +295,7 @@ impl HistoryStorage {
     pub fn get_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         self.conn
             .query_row(
-                "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment
+                "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment, confirm_skipped, skipped, hostname, cmdrun_version
                  FROM command_history
                  WHERE id = ?1",
                 [id],
@@ -238,7 +311,7 @@ impl HistoryStorage {
         let offset = offset.unwrap_or(0);
 
         let mut stmt = self.conn.prepare(
-            "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment
+            "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment, confirm_skipped, skipped, hostname, cmdrun_version
              FROM command_history
              ORDER BY start_time DESC
              LIMIT ?1 OFFSET ?2",
@@ -251,6 +324,45 @@ impl HistoryStorage {
         Ok(entries)
     }
 
+    /// List history entries matching `filter`, newest first
+    ///
+    /// The filter is applied before `limit`/`offset`, so pagination works on
+    /// the filtered set.
+    pub fn list_filtered(
+        &self,
+        filter: &HistoryFilter,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<Vec<HistoryEntry>> {
+        let limit = limit.unwrap_or(50);
+        let offset = offset.unwrap_or(0);
+
+        let mut stmt = self.conn.prepare(
+            "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment, confirm_skipped, skipped, hostname, cmdrun_version
+             FROM command_history
+             WHERE (?3 IS NULL OR start_time >= ?3)
+               AND (?4 IS NULL OR start_time <= ?4)
+               AND (?5 = 0 OR success = 0)
+             ORDER BY start_time DESC
+             LIMIT ?1 OFFSET ?2",
+        )?;
+
+        let entries = stmt
+            .query_map(
+                params![
+                    limit,
+                    offset,
+                    filter.since,
+                    filter.until,
+                    filter.failed_only
+                ],
+                Self::row_to_entry,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
     /// Search history entries by command name or arguments
     pub fn search(&self, query: &str, limit: Option<usize>) -> Result<Vec<HistoryEntry>> {
         let limit = limit.unwrap_or(50);
@@ -265,7 +377,7 @@ impl HistoryStorage {
         let search_pattern = format!("%{}%", escaped_query);
 
         let mut stmt = self.conn.prepare(
-            "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment
+            "SELECT id, command, args, start_time, duration_ms, exit_code, success, working_dir, environment, confirm_skipped, skipped, hostname, cmdrun_version
              FROM command_history
              WHERE command LIKE ?1 ESCAPE '\\' OR args LIKE ?1 ESCAPE '\\'
              ORDER BY start_time DESC
@@ -281,40 +393,100 @@ impl HistoryStorage {
 
     /// Get history statistics
     pub fn get_stats(&self) -> Result<HistoryStats> {
-        let total: i64 =
-            self.conn
-                .query_row("SELECT COUNT(*) FROM command_history", [], |row| row.get(0))?;
+        self.get_stats_filtered(None, DEFAULT_TREND_WINDOW)
+    }
+
+    /// Get history statistics, optionally restricted to one command
+    ///
+    /// The duration trend is computed over the last `trend_window` runs.
+    pub fn get_stats_filtered(
+        &self,
+        command: Option<&str>,
+        trend_window: usize,
+    ) -> Result<HistoryStats> {
+        let total: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM command_history WHERE (?1 IS NULL OR command = ?1)",
+            [command],
+            |row| row.get(0),
+        )?;
 
         let successful: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM command_history WHERE success = 1",
-            [],
+            "SELECT COUNT(*) FROM command_history WHERE success = 1 AND (?1 IS NULL OR command = ?1)",
+            [command],
             |row| row.get(0),
         )?;
 
         let failed: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM command_history WHERE success = 0",
-            [],
+            "SELECT COUNT(*) FROM command_history WHERE success = 0 AND (?1 IS NULL OR command = ?1)",
+            [command],
             |row| row.get(0),
         )?;
 
         let avg_duration: Option<f64> = self
             .conn
             .query_row(
-                "SELECT AVG(duration_ms) FROM command_history WHERE duration_ms IS NOT NULL",
-                [],
+                "SELECT AVG(duration_ms) FROM command_history
+                 WHERE duration_ms IS NOT NULL AND (?1 IS NULL OR command = ?1)",
+                [command],
                 |row| row.get(0),
             )
             .optional()?
             .flatten();
 
+        let mut stmt = self.conn.prepare(
+            "SELECT duration_ms FROM command_history
+             WHERE duration_ms IS NOT NULL AND (?1 IS NULL OR command = ?1)
+             ORDER BY start_time DESC, id DESC",
+        )?;
+        // Most recent first
+        let durations = stmt
+            .query_map([command], |row| row.get::<_, i64>(0))?
+            .map(|d| d.map(|d| d as f64))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Trend is computed in chronological order (oldest first)
+        let mut recent: Vec<f64> = durations.iter().take(trend_window).copied().collect();
+        recent.reverse();
+
+        let mut sorted = durations;
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
         Ok(HistoryStats {
             total: total as usize,
             successful: successful as usize,
             failed: failed as usize,
             avg_duration_ms: avg_duration,
+            p50_duration_ms: percentile(&sorted, 50.0),
+            p90_duration_ms: percentile(&sorted, 90.0),
+            p99_duration_ms: percentile(&sorted, 99.0),
+            trend_ms_per_run: linear_trend(&recent),
+            trend_window: recent.len(),
         })
     }
 
+    /// Get run totals for every command, ordered by command name
+    pub fn get_command_breakdown(&self) -> Result<Vec<CommandBreakdown>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT command, COUNT(*), SUM(CASE WHEN success = 1 THEN 1 ELSE 0 END),
+                    AVG(duration_ms), COALESCE(SUM(duration_ms), 0)
+             FROM command_history GROUP BY command ORDER BY command",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let runs: i64 = row.get(1)?;
+            let successful: i64 = row.get(2)?;
+            Ok(CommandBreakdown {
+                command: row.get(0)?,
+                runs: runs as usize,
+                successful: successful as usize,
+                failed: (runs - successful) as usize,
+                avg_duration_ms: row.get(3)?,
+                total_duration_ms: row.get::<_, i64>(4)? as f64,
+            })
+        })?;
+
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
     /// Get statistics for a specific command
     pub fn get_command_stats(&self, command_name: &str) -> Result<CommandStats> {
         let total_count: i64 = self.conn.query_row(
@@ -338,6 +510,55 @@ impl HistoryStorage {
         })
     }
 
+    /// Number of recorded runs per command name
+    pub fn frequency_map(&self) -> Result<AHashMap<String, u64>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT command, COUNT(*) FROM command_history GROUP BY command")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+
+        let mut map = AHashMap::new();
+        for row in rows {
+            let (command, count) = row?;
+            map.insert(command, count as u64);
+        }
+        Ok(map)
+    }
+
+    /// Delete entries beyond the newest `max_entries` and entries started before `older_than`
+    ///
+    /// Returns the number of deleted entries.
+    pub fn prune(
+        &mut self,
+        max_entries: Option<usize>,
+        older_than: Option<DateTime<Utc>>,
+    ) -> Result<usize> {
+        let mut deleted = 0;
+
+        if let Some(cutoff) = older_than {
+            deleted += self.conn.execute(
+                "DELETE FROM command_history WHERE start_time < ?1",
+                [cutoff.timestamp_millis()],
+            )?;
+        }
+
+        if let Some(max_entries) = max_entries {
+            deleted += self.conn.execute(
+                "DELETE FROM command_history
+                 WHERE id NOT IN (
+                     SELECT id FROM command_history
+                     ORDER BY start_time DESC, id DESC
+                     LIMIT ?1
+                 )",
+                [max_entries as i64],
+            )?;
+        }
+
+        Ok(deleted)
+    }
+
     /// Clear all history entries
     pub fn clear(&mut self) -> Result<usize> {
         let count = self.conn.execute("DELETE FROM command_history", [])?;
@@ -355,6 +576,26 @@ impl HistoryStorage {
         serde_json::to_string_pretty(&entries).context("Failed to serialize history to JSON")
     }
 
+    /// Export history to YAML format (a sequence of entries)
+    pub fn export_yaml(&self, limit: Option<usize>) -> Result<String> {
+        let entries = self.list(limit, None)?;
+        serde_yaml::to_string(&entries).context("Failed to serialize history to YAML")
+    }
+
+    /// Export history to TOML format (an array of tables under `[[history]]`)
+    ///
+    /// TOML has no null value, so fields that are `None` are omitted.
+    pub fn export_toml(&self, limit: Option<usize>) -> Result<String> {
+        #[derive(Serialize)]
+        struct TomlExport<'a> {
+            history: &'a [HistoryEntry],
+        }
+
+        let entries = self.list(limit, None)?;
+        toml::to_string(&TomlExport { history: &entries })
+            .context("Failed to serialize history to TOML")
+    }
+
     /// Export history to CSV format
     pub fn export_csv(&self, limit: Option<usize>) -> Result<String> {
         let entries = self.list(limit, None)?;
@@ -386,23 +627,25 @@ impl HistoryStorage {
         Ok(csv)
     }
 
-    /// Cleanup old entries to enforce max_entries limit
-    fn cleanup_old_entries(&mut self) -> Result<()> {
-        let count: i64 =
-            self.conn
-                .query_row("SELECT COUNT(*) FROM command_history", [], |row| row.get(0))?;
+    /// Apply the configured retention limits
+    ///
+    /// Runs after every insert, so it only deletes when a limit is actually
+    /// exceeded; both checks are answered from the count and the start time index.
+    fn auto_prune(&mut self) -> Result<()> {
+        let (count, oldest): (i64, Option<i64>) = self.conn.query_row(
+            "SELECT COUNT(*), MIN(start_time) FROM command_history",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
 
-        if count > self.max_entries as i64 {
-            let to_delete = count - self.max_entries as i64;
-            self.conn.execute(
-                "DELETE FROM command_history
-                 WHERE id IN (
-                     SELECT id FROM command_history
-                     ORDER BY start_time ASC, id ASC
-                     LIMIT ?1
-                 )",
-                [to_delete],
-            )?;
+        let cutoff = self
+            .max_age
+            .and_then(|age| Utc::now().checked_sub_signed(age));
+        let too_old = matches!((oldest, cutoff), (Some(oldest), Some(cutoff)) if oldest < cutoff.timestamp_millis());
+        let too_many = count > self.max_entries as i64;
+
+        if too_old || too_many {
+            self.prune(Some(self.max_entries), cutoff)?;
         }
 
         Ok(())
@@ -420,6 +663,10 @@ impl HistoryStorage {
             success: row.get(6)?,
             working_dir: row.get(7)?,
             environment: row.get(8)?,
+            confirm_skipped: row.get(9)?,
+            skipped: row.get(10)?,
+            hostname: row.get(11)?,
+            cmdrun_version: row.get(12)?,
         })
     }
 
@@ -454,6 +701,24 @@ pub struct HistoryStats {
     pub successful: usize,
     pub failed: usize,
     pub avg_duration_ms: Option<f64>,
+    /// Median duration
+    pub p50_duration_ms: Option<f64>,
+    /// 90th percentile duration
+    pub p90_duration_ms: Option<f64>,
+    /// 99th percentile duration
+    pub p99_duration_ms: Option<f64>,
+    /// Least-squares slope of duration over recent runs (ms per run, positive = slower)
+    pub trend_ms_per_run: Option<f64>,
+    /// Number of runs the trend was computed from
+    pub trend_window: usize,
+}
+
+/// Duration trend direction over recent runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Slower,
+    Faster,
+    Stable,
 }
 
 /// Command-specific statistics
@@ -463,6 +728,29 @@ pub struct CommandStats {
     pub last_run_at: Option<String>,
 }
 
+/// Run totals of one command (`history stats --by-command`)
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandBreakdown {
+    pub command: String,
+    pub runs: usize,
+    pub successful: usize,
+    pub failed: usize,
+    pub avg_duration_ms: Option<f64>,
+    /// Sum of the recorded durations
+    pub total_duration_ms: f64,
+}
+
+impl CommandBreakdown {
+    /// Get success rate as a percentage
+    pub fn success_rate(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            (self.successful as f64 / self.runs as f64) * 100.0
+        }
+    }
+}
+
 impl HistoryStats {
     /// Get success rate as a percentage
     pub fn success_rate(&self) -> f64 {
@@ -475,14 +763,164 @@ impl HistoryStats {
 
     /// Get average duration as a formatted string
     pub fn avg_duration_string(&self) -> String {
-        match self.avg_duration_ms {
-            Some(ms) if ms < 1000.0 => format!("{:.0}ms", ms),
-            Some(ms) => format!("{:.2}s", ms / 1000.0),
-            None => "N/A".to_string(),
+        format_duration_ms(self.avg_duration_ms)
+    }
+
+    /// Classify the duration trend
+    ///
+    /// The trend is `Stable` when the projected change over the window stays
+    /// within 5% of the average duration.
+    pub fn trend(&self) -> Option<Trend> {
+        let slope = self.trend_ms_per_run?;
+        let change = slope * self.trend_window as f64;
+        let threshold = self.avg_duration_ms.unwrap_or(0.0).abs() * 0.05;
+
+        Some(if change.abs() <= threshold {
+            Trend::Stable
+        } else if slope > 0.0 {
+            Trend::Slower
+        } else {
+            Trend::Faster
+        })
+    }
+}
+
+/// Load run counts from the default history database
+///
+/// Returns an empty map if history is missing or unreadable, so callers fall
+/// back to alphabetical order.
+pub fn load_frequency_map() -> AHashMap<String, u64> {
+    HistoryStorage::new()
+        .and_then(|storage| storage.frequency_map())
+        .unwrap_or_default()
+}
+
+/// Sort command names by run count (descending), then alphabetically
+pub fn sort_by_frequency<T>(items: &mut [(&String, T)], frequency: &AHashMap<String, u64>) {
+    items.sort_by(|(a, _), (b, _)| {
+        let count_a = frequency.get(*a).copied().unwrap_or(0);
+        let count_b = frequency.get(*b).copied().unwrap_or(0);
+        count_b.cmp(&count_a).then_with(|| a.cmp(b))
+    });
+}
+
+/// Parse a `--since`/`--until` value into a point in time
+///
+/// Accepts relative durations counted backward from `now` (`30m`, `2h`, `3d`,
+/// `1w`) and ISO 8601 dates or date-times (`2024-01-15`,
+/// `2024-01-15T10:00:00`, `2024-01-15T10:00:00+09:00`). Values without a
+/// timezone are interpreted as UTC, matching how history times are displayed.
+pub fn parse_time_bound(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let input = input.trim();
+
+    if let Some(age) = parse_relative(input)? {
+        return now
+            .checked_sub_signed(age)
+            .with_context(|| format!("Duration out of range: {}", input));
+    }
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(time) = chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S") {
+        return Ok(time.and_utc());
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
+    }
+
+    anyhow::bail!(
+        "Invalid time '{}': expected a duration like 2h, 3d, 1w or a date like 2024-01-15",
+        input
+    )
+}
+
+/// Parse a history age such as `30d` or `12h` (`s`, `m`, `h`, `d` or `w`)
+pub fn parse_age(input: &str) -> Result<chrono::Duration> {
+    let input = input.trim();
+    parse_relative(input)?
+        .filter(|age| *age >= chrono::Duration::zero())
+        .with_context(|| {
+            format!(
+                "Invalid age '{}': expected a duration like 12h, 30d or 4w",
+                input
+            )
+        })
+}
+
+/// `<amount><unit>` durations; `None` when the input is not of that form
+fn parse_relative(input: &str) -> Result<Option<chrono::Duration>> {
+    let Some(unit) = input.chars().last().filter(char::is_ascii_alphabetic) else {
+        return Ok(None);
+    };
+    let Ok(amount) = input[..input.len() - 1].parse::<i64>() else {
+        return Ok(None);
+    };
+
+    let duration = match unit {
+        's' => chrono::Duration::try_seconds(amount),
+        'm' => chrono::Duration::try_minutes(amount),
+        'h' => chrono::Duration::try_hours(amount),
+        'd' => chrono::Duration::try_days(amount),
+        'w' => chrono::Duration::try_weeks(amount),
+        _ => anyhow::bail!(
+            "Invalid duration unit '{}' in '{}' (use s, m, h, d or w)",
+            unit,
+            input
+        ),
+    };
+    duration
+        .map(Some)
+        .with_context(|| format!("Duration out of range: {}", input))
+}
+
+/// Format a duration in milliseconds for display
+pub fn format_duration_ms(ms: Option<f64>) -> String {
+    match ms {
+        Some(ms) if ms < 1000.0 => format!("{:.0}ms", ms),
+        Some(ms) => format!("{:.2}s", ms / 1000.0),
+        None => "N/A".to_string(),
+    }
+}
+
+/// Percentile of sorted values using linear interpolation between closest ranks
+fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    match sorted.len() {
+        0 => None,
+        1 => Some(sorted[0]),
+        len => {
+            let rank = (p / 100.0) * (len - 1) as f64;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            let weight = rank - lower as f64;
+            Some(sorted[lower] + (sorted[upper] - sorted[lower]) * weight)
         }
     }
 }
 
+/// Least-squares slope of values against their index
+///
+/// Returns `None` for fewer than two values.
+fn linear_trend(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+
+    let (num, den) = values
+        .iter()
+        .enumerate()
+        .fold((0.0, 0.0), |(num, den), (i, &y)| {
+            let dx = i as f64 - mean_x;
+            (num + dx * (y - mean_y), den + dx * dx)
+        });
+
+    Some(num / den)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -510,6 +948,10 @@ mod tests {
             success,
             working_dir: Some("/tmp".to_string()),
             environment: Some(r#"{"PATH": "/usr/bin"}"#.to_string()),
+            confirm_skipped: false,
+            skipped: false,
+            hostname: String::new(),
+            cmdrun_version: String::new(),
         }
     }
 
@@ -532,6 +974,77 @@ mod tests {
         assert!(retrieved.success);
     }
 
+    #[test]
+    fn test_confirm_skipped_roundtrip() {
+        let mut storage = create_test_storage();
+        let mut entry = create_test_entry("deploy", true);
+        entry.confirm_skipped = true;
+
+        let id = storage.add(&entry).unwrap();
+        let retrieved = storage.get_by_id(id).unwrap().unwrap();
+        assert!(retrieved.confirm_skipped);
+    }
+
+    #[test]
+    fn test_migrates_schema_without_confirm_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("old_history.db");
+
+        // Schema as created by earlier versions
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute(
+            "CREATE TABLE command_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                command TEXT NOT NULL,
+                args TEXT,
+                start_time INTEGER NOT NULL,
+                duration_ms INTEGER,
+                exit_code INTEGER,
+                success BOOLEAN NOT NULL,
+                working_dir TEXT,
+                environment TEXT,
+                created_at INTEGER DEFAULT (strftime('%s', 'now') * 1000)
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO command_history (command, start_time, success) VALUES ('old', 1, 1)",
+            [],
+        )
+        .unwrap();
+        drop(conn);
+
+        let storage = HistoryStorage::with_path(&db_path).unwrap();
+        let last = storage.get_last().unwrap().unwrap();
+        assert_eq!(last.command, "old");
+        assert!(!last.confirm_skipped);
+        assert!(!last.skipped);
+        assert_eq!(last.hostname, "");
+        assert_eq!(last.cmdrun_version, "");
+    }
+
+    #[test]
+    fn test_deserializes_entries_without_host_fields() {
+        // JSON exported by earlier versions
+        let json = r#"{
+            "id": 1,
+            "command": "build",
+            "args": null,
+            "start_time": 1700000000000,
+            "duration_ms": 120,
+            "exit_code": 0,
+            "success": true,
+            "working_dir": "/tmp",
+            "environment": null
+        }"#;
+
+        let entry: HistoryEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.command, "build");
+        assert_eq!(entry.hostname, "");
+        assert_eq!(entry.cmdrun_version, "");
+    }
+
     #[test]
     fn test_get_last() {
         let mut storage = create_test_storage();
@@ -584,6 +1097,47 @@ mod tests {
         assert!(list.is_empty());
     }
 
+    #[test]
+    fn test_frequency_map() {
+        let mut storage = create_test_storage();
+        assert!(storage.frequency_map().unwrap().is_empty());
+
+        storage.add(&create_test_entry("build", true)).unwrap();
+        storage.add(&create_test_entry("build", false)).unwrap();
+        storage.add(&create_test_entry("test", true)).unwrap();
+
+        let frequency = storage.frequency_map().unwrap();
+        assert_eq!(frequency["build"], 2);
+        assert_eq!(frequency["test"], 1);
+        assert_eq!(frequency.len(), 2);
+    }
+
+    #[test]
+    fn test_sort_by_frequency() {
+        let names: Vec<String> = ["lint", "build", "test", "deploy"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // No history: alphabetical
+        let mut items: Vec<(&String, ())> = names.iter().map(|n| (n, ())).collect();
+        sort_by_frequency(&mut items, &AHashMap::new());
+        let order: Vec<&str> = items.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(order, vec!["build", "deploy", "lint", "test"]);
+
+        // Most-run first, ties broken alphabetically
+        let frequency: AHashMap<String, u64> = [
+            ("test".to_string(), 5),
+            ("lint".to_string(), 2),
+            ("deploy".to_string(), 2),
+        ]
+        .into_iter()
+        .collect();
+        sort_by_frequency(&mut items, &frequency);
+        let order: Vec<&str> = items.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(order, vec!["test", "deploy", "lint", "build"]);
+    }
+
     #[test]
     fn test_stats() {
         let mut storage = create_test_storage();
@@ -598,4 +1152,308 @@ mod tests {
         assert_eq!(stats.failed, 1);
         assert!((stats.success_rate() - 66.67).abs() < 0.1);
     }
+
+    #[test]
+    fn test_command_breakdown() {
+        let mut storage = create_test_storage();
+
+        for (command, duration_ms, success) in [
+            ("build", 1_000, true),
+            ("build", 3_000, false),
+            ("test", 500, true),
+            ("build", 2_000, true),
+            ("test", 700, true),
+        ] {
+            storage
+                .add(&HistoryEntry {
+                    duration_ms: Some(duration_ms),
+                    ..create_test_entry(command, success)
+                })
+                .unwrap();
+        }
+        // A run without a recorded duration still counts
+        storage
+            .add(&HistoryEntry {
+                duration_ms: None,
+                ..create_test_entry("lint", false)
+            })
+            .unwrap();
+
+        let rows = storage.get_command_breakdown().unwrap();
+        let names: Vec<&str> = rows.iter().map(|r| r.command.as_str()).collect();
+        assert_eq!(names, vec!["build", "lint", "test"]);
+
+        let build = &rows[0];
+        assert_eq!((build.runs, build.successful, build.failed), (3, 2, 1));
+        assert_eq!(build.avg_duration_ms, Some(2_000.0));
+        assert_eq!(build.total_duration_ms, 6_000.0);
+        assert!((build.success_rate() - 66.67).abs() < 0.1);
+
+        let lint = &rows[1];
+        assert_eq!((lint.runs, lint.failed), (1, 1));
+        assert_eq!(lint.avg_duration_ms, None);
+        assert_eq!(lint.total_duration_ms, 0.0);
+
+        let test = &rows[2];
+        assert_eq!((test.runs, test.failed), (2, 0));
+        assert_eq!(test.total_duration_ms, 1_200.0);
+    }
+
+    fn create_timed_entry(command: &str, start_time: i64, duration_ms: i64) -> HistoryEntry {
+        HistoryEntry {
+            start_time,
+            duration_ms: Some(duration_ms),
+            ..create_test_entry(command, true)
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values: Vec<f64> = (1..=100).map(|v| v as f64).collect();
+        assert!((percentile(&values, 50.0).unwrap() - 50.5).abs() < 1e-9);
+        assert!((percentile(&values, 90.0).unwrap() - 90.1).abs() < 1e-9);
+        assert!((percentile(&values, 99.0).unwrap() - 99.01).abs() < 1e-9);
+        assert_eq!(percentile(&[42.0], 99.0), Some(42.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_linear_trend() {
+        assert!((linear_trend(&[10.0, 20.0, 30.0, 40.0]).unwrap() - 10.0).abs() < 1e-9);
+        assert!(linear_trend(&[40.0, 30.0, 20.0]).unwrap() < 0.0);
+        assert_eq!(linear_trend(&[5.0, 5.0, 5.0]), Some(0.0));
+        assert_eq!(linear_trend(&[5.0]), None);
+    }
+
+    #[test]
+    fn test_stats_percentiles_and_trend() {
+        let mut storage = create_test_storage();
+
+        // Durations 100, 200, ..., 1000 recorded in chronological order
+        for i in 1..=10 {
+            storage
+                .add(&create_timed_entry("build", 1_000 + i, i * 100))
+                .unwrap();
+        }
+        // A different command that must not affect filtered stats
+        storage
+            .add(&create_timed_entry("test", 5_000, 10_000))
+            .unwrap();
+
+        let stats = storage.get_stats_filtered(Some("build"), 20).unwrap();
+        assert_eq!(stats.total, 10);
+        assert!((stats.p50_duration_ms.unwrap() - 550.0).abs() < 1e-9);
+        assert!((stats.p90_duration_ms.unwrap() - 910.0).abs() < 1e-9);
+        assert!((stats.p99_duration_ms.unwrap() - 991.0).abs() < 1e-9);
+        assert!(stats.trend_ms_per_run.unwrap() > 0.0);
+        assert_eq!(stats.trend_window, 10);
+        assert_eq!(stats.trend(), Some(Trend::Slower));
+
+        // Window limited to the 3 most recent runs
+        let stats = storage.get_stats_filtered(Some("build"), 3).unwrap();
+        assert_eq!(stats.trend_window, 3);
+        assert!((stats.trend_ms_per_run.unwrap() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_stable_and_empty_trend() {
+        let mut storage = create_test_storage();
+        assert!(storage.get_stats().unwrap().trend().is_none());
+
+        for i in 0..5 {
+            storage
+                .add(&create_timed_entry("steady", 1_000 + i, 1_000))
+                .unwrap();
+        }
+
+        let stats = storage.get_stats().unwrap();
+        assert_eq!(stats.p50_duration_ms, Some(1_000.0));
+        assert_eq!(stats.trend(), Some(Trend::Stable));
+    }
+
+    #[test]
+    fn test_parse_time_bound_relative() {
+        let now = DateTime::parse_from_rfc3339("2024-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let at = |s: &str| parse_time_bound(s, now).unwrap().to_rfc3339();
+
+        assert_eq!(at("30m"), "2024-03-10T11:30:00+00:00");
+        assert_eq!(at("2h"), "2024-03-10T10:00:00+00:00");
+        assert_eq!(at("3d"), "2024-03-07T12:00:00+00:00");
+        assert_eq!(at("1w"), "2024-03-03T12:00:00+00:00");
+
+        assert!(parse_time_bound("2y", now).is_err());
+        assert!(parse_time_bound("h", now).is_err());
+        assert!(parse_time_bound("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn test_parse_time_bound_iso() {
+        let now = Utc::now();
+        let at = |s: &str| parse_time_bound(s, now).unwrap().to_rfc3339();
+
+        assert_eq!(at("2024-01-15"), "2024-01-15T00:00:00+00:00");
+        assert_eq!(at("2024-01-15T10:30:00"), "2024-01-15T10:30:00+00:00");
+        assert_eq!(at("2024-01-15T10:30:00+09:00"), "2024-01-15T01:30:00+00:00");
+        assert!(parse_time_bound("2024-13-01", now).is_err());
+    }
+
+    #[test]
+    fn test_list_filtered_by_time_range() {
+        let mut storage = create_test_storage();
+        for (name, start) in [
+            ("old", 1_000),
+            ("edge", 2_000),
+            ("mid", 3_000),
+            ("new", 4_000),
+        ] {
+            storage.add(&create_timed_entry(name, start, 10)).unwrap();
+        }
+        let names = |entries: Vec<HistoryEntry>| -> Vec<String> {
+            entries.into_iter().map(|e| e.command).collect()
+        };
+
+        // Both bounds are inclusive
+        let filter = HistoryFilter {
+            since: Some(2_000),
+            until: Some(3_000),
+            failed_only: false,
+        };
+        assert_eq!(
+            names(storage.list_filtered(&filter, None, None).unwrap()),
+            vec!["mid", "edge"]
+        );
+
+        // Limit and offset apply after filtering
+        let filter = HistoryFilter {
+            since: Some(2_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            names(storage.list_filtered(&filter, Some(1), Some(1)).unwrap()),
+            vec!["mid"]
+        );
+    }
+
+    #[test]
+    fn test_list_filtered_combines_failed_and_time() {
+        let mut storage = create_test_storage();
+        storage
+            .add(&create_timed_entry("ok-new", 4_000, 10))
+            .unwrap();
+        let mut failed_old = create_timed_entry("failed-old", 1_000, 10);
+        failed_old.success = false;
+        storage.add(&failed_old).unwrap();
+        let mut failed_new = create_timed_entry("failed-new", 3_000, 10);
+        failed_new.success = false;
+        storage.add(&failed_new).unwrap();
+
+        let filter = HistoryFilter {
+            since: Some(2_000),
+            until: None,
+            failed_only: true,
+        };
+        let entries = storage.list_filtered(&filter, None, None).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command, "failed-new");
+    }
+
+    fn names_of(entries: Vec<HistoryEntry>) -> Vec<String> {
+        entries.into_iter().map(|e| e.command).collect()
+    }
+
+    fn at(ms: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(ms).unwrap()
+    }
+
+    fn storage_with_entries(starts: &[(&str, i64)]) -> HistoryStorage {
+        let mut storage = create_test_storage();
+        for (name, start) in starts {
+            storage.add(&create_timed_entry(name, *start, 10)).unwrap();
+        }
+        storage
+    }
+
+    #[test]
+    fn test_prune_by_count_keeps_newest() {
+        let mut storage = storage_with_entries(&[
+            ("second", 2_000),
+            ("first", 1_000),
+            ("fourth", 4_000),
+            ("third", 3_000),
+        ]);
+
+        assert_eq!(storage.prune(Some(2), None).unwrap(), 2);
+        assert_eq!(
+            names_of(storage.list(None, None).unwrap()),
+            vec!["fourth", "third"]
+        );
+
+        // Already within the cap
+        assert_eq!(storage.prune(Some(5), None).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_prune_by_age_drops_old_entries() {
+        let mut storage = storage_with_entries(&[("old", 1_000), ("edge", 2_000), ("new", 3_000)]);
+
+        assert_eq!(storage.prune(None, Some(at(2_000))).unwrap(), 1);
+        assert_eq!(
+            names_of(storage.list(None, None).unwrap()),
+            vec!["new", "edge"]
+        );
+    }
+
+    #[test]
+    fn test_prune_by_count_and_age() {
+        // Age removes two, then the cap trims the rest to the newest two
+        let mut storage = storage_with_entries(&[
+            ("a", 1_000),
+            ("b", 2_000),
+            ("c", 3_000),
+            ("d", 4_000),
+            ("e", 5_000),
+        ]);
+        assert_eq!(storage.prune(Some(2), Some(at(3_000))).unwrap(), 3);
+        assert_eq!(names_of(storage.list(None, None).unwrap()), vec!["e", "d"]);
+
+        // A cap larger than what survives the age cut removes nothing more
+        let mut storage = storage_with_entries(&[("a", 1_000), ("b", 2_000), ("c", 3_000)]);
+        assert_eq!(storage.prune(Some(10), Some(at(2_500))).unwrap(), 2);
+        assert_eq!(names_of(storage.list(None, None).unwrap()), vec!["c"]);
+    }
+
+    #[test]
+    fn test_add_auto_prunes_by_count_and_age() {
+        let now = Utc::now().timestamp_millis();
+        let mut storage = create_test_storage();
+        storage.set_max_entries(3);
+        storage.set_max_age(Some(chrono::Duration::days(7)));
+
+        storage
+            .add(&create_timed_entry("ancient", now - 30 * 86_400_000, 10))
+            .unwrap();
+        assert!(storage.list(None, None).unwrap().is_empty());
+
+        for (i, name) in ["one", "two", "three", "four"].iter().enumerate() {
+            storage
+                .add(&create_timed_entry(name, now - 1_000 + i as i64, 10))
+                .unwrap();
+        }
+        assert_eq!(
+            names_of(storage.list(None, None).unwrap()),
+            vec!["four", "three", "two"]
+        );
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30d").unwrap(), chrono::Duration::days(30));
+        assert_eq!(parse_age(" 12h ").unwrap(), chrono::Duration::hours(12));
+        assert!(parse_age("2024-01-15").is_err());
+        assert!(parse_age("-1d").is_err());
+        assert!(parse_age("3y").is_err());
+    }
 }
@@ -38,6 +38,9 @@ pub enum MessageKey {
     PromptEnterNumber,
     PromptSelectTemplate,
     PromptSelectLanguage,
+    PromptConfirmRunCommand,
+    ErrorConfirmationRequiresTerminal,
+    WarningConfirmationSkipped,
 
     // ====== 選択肢 ======
     OptionYesAdd,
@@ -45,6 +48,8 @@ pub enum MessageKey {
     OptionCancel,
     OptionEnglish,
     OptionJapanese,
+    OptionSpanish,
+    OptionGerman,
 
     // ====== プレビュー・ラベル ======
     LabelPreview,
@@ -109,6 +114,7 @@ pub enum MessageKey {
     ErrorCannotSetEnvVariable,
     ErrorNoSuitableUnixShell,
     ErrorNoSuitableWindowsShell,
+    ErrorShellNotFound,
     ErrorTemplateAlreadyExists,
     ErrorTemplateNotFound,
     ErrorCannotRemoveBuiltinTemplate,
@@ -243,6 +249,7 @@ pub enum MessageKey {
     // ====== Edit コマンド ======
     EditParallelExecution,
     EditConfirmBeforeExecution,
+    EditNoChanges,
 
     // ====== Graph コマンド ======
     GraphSavedTo,
@@ -308,1213 +315,1912 @@ pub enum MessageKey {
 }
 
 /// メッセージの取得
+///
+/// 翻訳が存在しないキーは英語のメッセージにフォールバックする
 pub fn get_message(key: MessageKey, language: Language) -> &'static str {
+    let translated = match language {
+        Language::English => None,
+        Language::Japanese => japanese(key),
+        Language::ChineseSimplified => chinese_simplified(key),
+        Language::ChineseTraditional => chinese_traditional(key),
+        Language::Spanish => spanish(key),
+        Language::German => german(key),
+    };
+
+    translated.unwrap_or_else(|| english(key))
+}
+
+/// 英語メッセージ（フォールバック先のため全キーを網羅する）
+fn english(key: MessageKey) -> &'static str {
     use MessageKey::*;
 
-    match language {
-        Language::English => match key {
-            // ====== 実行状態 ======
-            Running => "Running",
-            Completed => "Completed",
-            Error => "Error",
-            Warning => "Warning",
-            Success => "Success",
-
-            // ====== CRUD操作 ======
-            CommandAdded => "Command added successfully",
-            CommandRemoved => "Command removed successfully",
-            CommandUpdated => "Command updated successfully",
-
-            // ====== 検証 ======
-            Validating => "Validating configuration",
-            ConfigValid => "Configuration is valid",
-            ValidationFailed => "Validation failed",
-            ValidatingConfiguration => "Validating configuration...",
-            ConfigurationIsValid => "Configuration is valid",
-
-            // ====== 対話プロンプト ======
-            PromptCommandId => "Command ID:",
-            PromptCommand => "Command:",
-            PromptDescription => "Description:",
-            PromptCategory => "Category",
-            PromptTags => "Tags (comma-separated)",
-            PromptConfirm => "Are you sure?",
-            PromptSelectCommand => "Select command to edit",
-            PromptWhatToDo => "What would you like to do?",
-            PromptEnterNumber => "Enter number",
-            PromptSelectTemplate => "Select a template",
-            PromptSelectLanguage => "Select your preferred language",
-
-            // ====== 選択肢 ======
-            OptionYesAdd => "Yes, add this command",
-            OptionNoEdit => "No, edit again",
-            OptionCancel => "Cancel",
-            OptionEnglish => "English",
-            OptionJapanese => "日本語 (Japanese)",
-
-            // ====== プレビュー・ラベル ======
-            LabelPreview => "Preview",
-            LabelId => "ID",
-            LabelCommand => "Command",
-            LabelDescription => "Description",
-            LabelCategory => "Category",
-            LabelTags => "Tags",
-            LabelCurrentSettings => "Current settings",
-            LabelDependencies => "Dependencies",
-            LabelPlatforms => "Platforms",
-            LabelWorkingDirectory => "Working directory",
-            LabelEnvironmentVariables => "Environment variables",
-            LabelExecutionSettings => "Execution settings",
-            LabelParallel => "Parallel",
-            LabelConfirm => "Confirm",
-            LabelTimeout => "Timeout",
-            LabelCommandDetails => "Command details",
-            LabelConfiguration => "Configuration",
-            LabelLanguage => "language",
-            LabelShell => "shell",
-            LabelStrictMode => "strict_mode",
-            LabelBackupCreated => "Backup created",
-            LabelYes => "yes",
-            LabelNo => "no",
-
-            // ====== 警告メッセージ ======
-            WarningShellBuiltinNoEffect => "⚠ This shell builtin command runs in a subprocess and won't affect the current shell",
-            HintShellFunction => "💡 Hint: Use shell functions for directory navigation",
-            HintCdCommand => "   Add to ~/.cmdrun/shell-functions.sh:",
-
-            // ====== コマンド連結ヒント ======
-            HintCommandChainingAlternatives => "💡 Hint: Use one of these alternatives:",
-            HintCommandArrayRecommended => "   1. Use command array (recommended for security):\n      cmd = [\"cd /path\", \"git diff\"]",
-            HintEnableChainingForCommand => "   2. Enable chaining for this command (use with caution):\n      allow_chaining = true",
-            HintEnableChainingGlobally => "   3. Enable chaining globally (not recommended):\n      [config]\n      allow_command_chaining = true",
-
-            // ====== エラーメッセージ ======
-            ErrorEmptyCommandId => "Command ID cannot be empty",
-            ErrorEmptyCommand => "Command cannot be empty",
-            ErrorEmptyDescription => "Description cannot be empty",
-            ErrorCommandNotFound => "Command not found",
-            ErrorCommandExists => "Command already exists",
-            ErrorConfigNotFound => "Configuration file not found",
-            ErrorInvalidConfig => "Invalid configuration",
-            ErrorInvalidSelection => "Invalid selection",
-            ErrorSelectionOutOfRange => "Selection out of range",
-            ErrorNoCommandsAvailable => "No commands available",
-            ErrorUnknownTemplate => "Unknown template",
-            ErrorFileAlreadyExists => "Configuration file already exists",
-            ErrorCircularDependency => "Circular dependency detected",
-            ErrorValidationFailed => "Validation failed",
-            ErrorCommandFailed => "Command failed",
-            ErrorUnknownConfigKey => "Unknown configuration key",
-            ErrorAliasTargetNotFound => "Alias target not found",
-            ErrorInvalidLanguage => "Invalid language. Valid options: english, japanese, chinese_simplified, chinese_traditional",
-            ErrorNoConfigFileFound => "No configuration file found. Run 'cmdrun init' to create one.",
-            ErrorCannotDetermineConfigDir => "Cannot determine config directory",
-            ErrorCommandExecutionFailed => "Command failed with exit code",
-            ErrorLocalConfigNotFound => "Local configuration file not found",
-            ErrorNoConfigFilesSpecified => "No configuration files specified",
-            ErrorCannotSetEnvVariable => "Cannot set environment variable in default environment",
-            ErrorNoSuitableUnixShell => "No suitable Unix shell found",
-            ErrorNoSuitableWindowsShell => "No suitable Windows shell found",
-            ErrorTemplateAlreadyExists => "Template already exists",
-            ErrorTemplateNotFound => "Template not found",
-            ErrorCannotRemoveBuiltinTemplate => "Cannot remove built-in template",
-            ErrorFileNotFound => "File not found",
-            ErrorFailedToAccessCommandsTable => "Failed to access commands table",
-            ErrorSecurityValidationFailed => "Security validation failed",
-            ErrorEditorNotFound => "Editor not found",
-            ErrorFailedToOpenEditor => "Failed to open editor",
-            ErrorCannotDetermineShell => "Cannot determine shell",
-            ErrorInvalidConfigValue => "Invalid configuration value",
-            ErrorFailedToSaveConfiguration => "Failed to save configuration",
-            ErrorCannotDetermineHomeDir => "Could not determine home directory",
-            ErrorFailedToParseTemplate => "Failed to parse template",
-            ErrorHistoryEntryNotFound => "History entry not found",
-            ErrorFailedToDetermineLocalDataDir => "Failed to determine local data directory",
-            ErrorFailedToInitializeLogger => "Failed to initialize logger",
-            ErrorFailedToReadConfig => "Failed to read config",
-            ErrorFailedToParseConfig => "Failed to parse config",
-            ErrorInvalidConfigPath => "Invalid config path",
-            ErrorFailedToAcquireReadLock => "Failed to acquire read lock",
-            ErrorFailedToAcquireWriteLock => "Failed to acquire write lock",
-            ErrorFailedToInitializePlugin => "Failed to initialize plugin",
-            ErrorFailedToUnloadPlugin => "Failed to unload plugin",
-            ErrorPluginSymbolNotFound => "Plugin symbol not found",
-            ErrorFailedToLoadLibrary => "Failed to load library",
-            ErrorTemplateNameEmpty => "Template name cannot be empty",
-            ErrorTemplateDescriptionEmpty => "Template description cannot be empty",
-            ErrorTemplateNoCommands => "Template must contain at least one command",
-            ErrorCommandIdInvalidChars => "Command ID contains invalid characters",
-            ErrorCommandFailedWithCode => "Command failed with exit code",
-            ErrorNoTemplatesAvailable => "No templates available",
-
-            // ====== ヘルプテキスト ======
-            HelpAddCommand => "Add a new command to the configuration",
-            HelpRemoveCommand => "Remove a command from the configuration",
-            HelpEditCommand => "Edit an existing command",
-            HelpListCommands => "List all available commands",
-            HelpRunCommand => "Run a command",
-            HelpValidateConfig => "Validate configuration file",
-            HelpSearchCommand => "Search commands by keyword",
-            HelpInfoCommand => "Show detailed command information",
-            HelpConfigCommand => "Manage configuration settings",
-            HelpWatchCommand => "Watch files and run command on changes",
-            HelpInitCommand => "Initialize a new configuration file",
-
-            // ====== List コマンド ======
-            ListNoCommandsDefined => "No commands defined",
-            ListAvailableCommands => "Available commands",
-            ListCommandCount => "commands defined",
-            ListAliasCount => "aliases defined",
-
-            // ====== Run コマンド ======
-            RunRunningCommand => "Running",
-            RunWithParallelDependencies => "with parallel dependencies",
-            RunExecutionPlan => "Execution plan",
-            RunGroup => "Group",
-            RunAllCommandsCompleted => "All commands completed in",
-            RunCompletedIn => "Completed in",
-            RunCommandFailedWithCode => "Command failed with exit code",
-
-            // ====== Search コマンド ======
-            SearchSearchingFor => "Searching for",
-            SearchNoCommandsMatching => "No commands matching",
-            SearchFound => "Found",
-            SearchMatchedIn => "Matched in",
-            SearchUseInfoToSeeDetails => "Use cmdrun info <command> to see details",
-
-            // ====== Info コマンド ======
-            InfoSelectCommandToView => "Select command to view details",
-            InfoBasicInformation => "Basic information",
-            InfoCommandSpecification => "Command specification",
-            InfoExecutionSettings => "Execution settings",
-            InfoPlatformSupport => "Platform support",
-            InfoConfigurationPaths => "Configuration paths",
-            InfoGlobalConfigPath => "Global config",
-            InfoLocalConfigPath => "Local config",
-            InfoActualWorkingDirectory => "Actual working directory",
-            InfoExecutionStatistics => "Execution Statistics",
-            InfoTotalExecutions => "Total executions",
-            InfoSuccessfulRuns => "Successful runs",
-            InfoFailedRuns => "Failed runs",
-            InfoLastRun => "Last run",
-            InfoAverageDuration => "Average duration",
-
-            // ====== Config コマンド ======
-            ConfigSet => "Set",
-            ConfigShowingConfiguration => "Showing configuration",
-
-            // ====== Validate コマンド ======
-            ValidateLoadedConfigFrom => "Loaded configuration from",
-            ValidateCheckingCircularDependencies => "Checking for circular dependencies...",
-            ValidateNoCircularDependenciesFor => "No circular dependencies for",
-            ValidateValidatingCommands => "Validating commands",
-            ValidateValidatingAliases => "Validating aliases",
-            ValidateBuildingDependencyGraph => "Building dependency graph...",
-            ValidateDependencyGraphBuilt => "Dependency graph built successfully",
-            ValidateExecutionOrder => "Execution order",
-            ValidateErrors => "Errors",
-            ValidateWarnings => "Warnings",
-            ValidateInformation => "Information",
-            ValidateFailedWithErrors => "Configuration validation failed with",
-            ValidateCommandsDefined => "commands defined",
-            ValidateAliasesDefined => "aliases defined",
-
-            // ====== Init コマンド ======
-            InitCreated => "Created",
-            InitUsing => "Using",
-            InitNextSteps => "Next steps",
-            InitStep1EditFile => "Edit {0} to define your commands",
-            InitStep2ListCommands => "Run cmdrun list to list available commands",
-            InitStep3RunCommand => "Run cmdrun run <name> to execute a command",
-            InitExampleCommands => "Example commands",
-            InitTemplateDescription => "template",
-            InitLanguageSet => "Language set to",
-
-            // ====== Watch コマンド ======
-            WatchConfiguration => "Watch Configuration",
-            WatchCommand => "Command",
-            WatchWatching => "Watching",
-            WatchPatterns => "Patterns",
-            WatchExclude => "Exclude",
-            WatchDebounce => "Debounce",
-            WatchModeStarted => "Watch mode started. Press Ctrl+C to stop.",
-            WatchPresCtrlCToStop => "Press Ctrl+C to stop",
-            WatchModeStoppedByUser => "Watch mode stopped by user",
-
-            // ====== Remove コマンド ======
-            RemoveRemovalTarget => "Removal target",
-            RemoveType => "Type",
-            RemovePlatformSpecific => "Platform-specific",
-
-            // ====== Edit コマンド ======
-            EditParallelExecution => "Parallel execution",
-            EditConfirmBeforeExecution => "Confirm before execution",
-
-            // ====== Graph コマンド ======
-            GraphSavedTo => "Graph saved to",
-            GraphRenderWith => "Render with",
-            GraphViewAt => "View at",
-
-            // ====== Env コマンド ======
-            EnvCurrent => "Current environment",
-            EnvAvailableEnvironments => "Available environments",
-            EnvSwitchedTo => "Switched to environment",
-            EnvCreated => "Created environment",
-            EnvVariableSet => "Set variable",
-            EnvEnvironment => "Environment",
-            EnvDescription => "Description",
-            EnvConfigFile => "Config file",
-            EnvEnvironmentVariables => "Environment variables",
-            EnvErrorNotFound => "Environment not found",
-            EnvErrorAlreadyExists => "Environment already exists",
-            EnvErrorCannotSetDefault => "Cannot set variables for 'default' environment",
-
-            // ====== Typo検出 ======
-            TypoUnknownCommand => "Unknown command",
-            TypoDidYouMean => "Did you mean one of these?",
-            TypoSuggestions => "Suggestions",
-            TypoRunHelp => "Run 'cmdrun --help' for available commands",
-
-            // ====== その他 ======
-            AddingCommand => "Adding command",
-            RemovingCommand => "Removing command",
-            UpdatingCommand => "Updating command",
-            OpeningEditor => "Opening editor",
-            SearchResults => "Search results",
-            NoCommandsFound => "No commands found",
-            Cancelled => "Cancelled",
-            LoadingConfiguration => "Loading configuration",
-            CreatingBackup => "Creating backup",
-            MatchingCommands => "matching command(s)",
-            Template => "template",
-
-            // ====== History コマンド ======
-            HistoryNoEntriesFound => "No history entries found",
-            HistoryNoCommandsMatching => "No commands matching",
-            HistoryExitCode => "Exit code:",
-            HistoryWorkingDir => "Working dir:",
-            HistoryTotalCommands => "Total commands:",
-
-            // ====== Template コマンド ======
-            TemplateNoTemplatesAvailable => "No templates available",
-            TemplateUserTemplates => "User templates:",
-
-            // ====== Plugin コマンド ======
-            PluginNoPluginsInstalled => "No plugins installed",
-            PluginMinimumCmdrunVersion => "Minimum cmdrun version:",
-
-            // ====== Env コマンド表示 ======
-            EnvCurrentEnvironmentLabel => "Current environment:",
-            EnvAvailableEnvironmentsLabel => "Available environments:",
-            EnvConfigurationFiles => "Configuration files",
-            EnvBaseConfig => "Base config",
-
-            // ====== Completion コマンド ======
-            CompletionInstallationInstructions => "Installation instructions:",
-        },
-        Language::Japanese => match key {
-            // ====== 実行状態 ======
-            Running => "実行中",
-            Completed => "完了",
-            Error => "エラー",
-            Warning => "警告",
-            Success => "成功",
-
-            // ====== CRUD操作 ======
-            CommandAdded => "コマンドを追加しました",
-            CommandRemoved => "コマンドを削除しました",
-            CommandUpdated => "コマンドを更新しました",
-
-            // ====== 検証 ======
-            Validating => "設定を検証中",
-            ConfigValid => "設定は有効です",
-            ValidationFailed => "検証に失敗しました",
-            ValidatingConfiguration => "設定を検証中...",
-            ConfigurationIsValid => "設定は有効です",
-
-            // ====== 対話プロンプト ======
-            PromptCommandId => "コマンドID:",
-            PromptCommand => "コマンド：",
-            PromptDescription => "説明：",
-            PromptCategory => "カテゴリ",
-            PromptTags => "タグ（カンマ区切り）",
-            PromptConfirm => "よろしいですか？",
-            PromptSelectCommand => "編集するコマンドを選択",
-            PromptWhatToDo => "どうしますか？",
-            PromptEnterNumber => "番号を入力",
-            PromptSelectTemplate => "テンプレートを選択",
-            PromptSelectLanguage => "言語を選択してください",
-
-            // ====== 選択肢 ======
-            OptionYesAdd => "はい、このコマンドを追加",
-            OptionNoEdit => "いいえ、再編集",
-            OptionCancel => "キャンセル",
-            OptionEnglish => "English (英語)",
-            OptionJapanese => "日本語",
-
-            // ====== プレビュー・ラベル ======
-            LabelPreview => "プレビュー",
-            LabelId => "ID",
-            LabelCommand => "コマンド",
-            LabelDescription => "説明",
-            LabelCategory => "カテゴリ",
-            LabelTags => "タグ",
-            LabelCurrentSettings => "現在の設定",
-            LabelDependencies => "依存関係",
-            LabelPlatforms => "プラットフォーム",
-            LabelWorkingDirectory => "作業ディレクトリ",
-            LabelEnvironmentVariables => "環境変数",
-            LabelExecutionSettings => "実行設定",
-            LabelParallel => "並列実行",
-            LabelConfirm => "実行前確認",
-            LabelTimeout => "タイムアウト",
-            LabelCommandDetails => "コマンド詳細",
-            LabelConfiguration => "設定",
-            LabelLanguage => "言語",
-            LabelShell => "シェル",
-            LabelStrictMode => "厳格モード",
-            LabelBackupCreated => "バックアップを作成しました",
-            LabelYes => "はい",
-            LabelNo => "いいえ",
-
-            // ====== 警告メッセージ ======
-            WarningShellBuiltinNoEffect => "⚠ このシェルビルトインコマンドはサブプロセスで実行されるため、現在のシェルには影響しません",
-            HintShellFunction => "💡 ヒント: ディレクトリ移動にはシェル関数を使ってください",
-            HintCdCommand => "   ~/.cmdrun/shell-functions.sh に追加:",
-
-            // ====== コマンド連結ヒント ======
-            HintCommandChainingAlternatives => "💡 ヒント: 次のいずれかの代替方法を使用してください：",
-            HintCommandArrayRecommended => "   1. コマンド配列を使用（セキュリティ上推奨）:\n      cmd = [\"cd /path\", \"git diff\"]",
-            HintEnableChainingForCommand => "   2. このコマンドのみ連結を許可（注意して使用）:\n      allow_chaining = true",
-            HintEnableChainingGlobally => "   3. グローバルで連結を許可（非推奨）:\n      [config]\n      allow_command_chaining = true",
-
-            // ====== エラーメッセージ ======
-            ErrorEmptyCommandId => "コマンドIDは空にできません",
-            ErrorEmptyCommand => "コマンドは空にできません",
-            ErrorEmptyDescription => "説明は空にできません",
-            ErrorCommandNotFound => "コマンドが見つかりません",
-            ErrorCommandExists => "コマンドは既に存在します",
-            ErrorConfigNotFound => "設定ファイルが見つかりません",
-            ErrorInvalidConfig => "無効な設定です",
-            ErrorInvalidSelection => "無効な選択です",
-            ErrorSelectionOutOfRange => "選択が範囲外です",
-            ErrorNoCommandsAvailable => "利用可能なコマンドがありません",
-            ErrorUnknownTemplate => "不明なテンプレートです",
-            ErrorFileAlreadyExists => "設定ファイルは既に存在します",
-            ErrorCircularDependency => "循環依存が検出されました",
-            ErrorValidationFailed => "検証に失敗しました",
-            ErrorCommandFailed => "コマンドが失敗しました",
-            ErrorUnknownConfigKey => "不明な設定キーです",
-            ErrorAliasTargetNotFound => "エイリアス先が見つかりません",
-            ErrorInvalidLanguage => "無効な言語です。有効な選択肢: english, japanese, chinese_simplified, chinese_traditional",
-            ErrorNoConfigFileFound => "設定ファイルが見つかりません。'cmdrun init' を実行して作成してください。",
-            ErrorCannotDetermineConfigDir => "設定ディレクトリを特定できません",
-            ErrorCommandExecutionFailed => "コマンドが失敗しました（終了コード",
-            ErrorLocalConfigNotFound => "ローカル設定ファイルが見つかりません",
-            ErrorNoConfigFilesSpecified => "設定ファイルが指定されていません",
-            ErrorCannotSetEnvVariable => "デフォルト環境では環境変数を設定できません",
-            ErrorNoSuitableUnixShell => "適切なUnixシェルが見つかりません",
-            ErrorNoSuitableWindowsShell => "適切なWindowsシェルが見つかりません",
-            ErrorTemplateAlreadyExists => "テンプレートは既に存在します",
-            ErrorTemplateNotFound => "テンプレートが見つかりません",
-            ErrorCannotRemoveBuiltinTemplate => "組み込みテンプレートは削除できません",
-            ErrorFileNotFound => "ファイルが見つかりません",
-            ErrorFailedToAccessCommandsTable => "コマンドテーブルへのアクセスに失敗しました",
-            ErrorSecurityValidationFailed => "セキュリティ検証に失敗しました",
-            ErrorEditorNotFound => "エディタが見つかりません",
-            ErrorFailedToOpenEditor => "エディタを開けませんでした",
-            ErrorCannotDetermineShell => "シェルを特定できません",
-            ErrorInvalidConfigValue => "無効な設定値です",
-            ErrorFailedToSaveConfiguration => "設定の保存に失敗しました",
-            ErrorCannotDetermineHomeDir => "ホームディレクトリを特定できません",
-            ErrorFailedToParseTemplate => "テンプレートの解析に失敗しました",
-            ErrorHistoryEntryNotFound => "履歴エントリが見つかりません",
-            ErrorFailedToDetermineLocalDataDir => "ローカルデータディレクトリの特定に失敗しました",
-            ErrorFailedToInitializeLogger => "ロガーの初期化に失敗しました",
-            ErrorFailedToReadConfig => "設定ファイルの読み込みに失敗しました",
-            ErrorFailedToParseConfig => "設定ファイルの解析に失敗しました",
-            ErrorInvalidConfigPath => "無効な設定ファイルパスです",
-            ErrorFailedToAcquireReadLock => "読み取りロックの取得に失敗しました",
-            ErrorFailedToAcquireWriteLock => "書き込みロックの取得に失敗しました",
-            ErrorFailedToInitializePlugin => "プラグインの初期化に失敗しました",
-            ErrorFailedToUnloadPlugin => "プラグインのアンロードに失敗しました",
-            ErrorPluginSymbolNotFound => "プラグインシンボルが見つかりません",
-            ErrorFailedToLoadLibrary => "ライブラリの読み込みに失敗しました",
-            ErrorTemplateNameEmpty => "テンプレート名は空にできません",
-            ErrorTemplateDescriptionEmpty => "テンプレートの説明は空にできません",
-            ErrorTemplateNoCommands => "テンプレートには少なくとも1つのコマンドが必要です",
-            ErrorCommandIdInvalidChars => "コマンドIDに無効な文字が含まれています",
-            ErrorCommandFailedWithCode => "コマンドが失敗しました（終了コード",
-            ErrorNoTemplatesAvailable => "利用可能なテンプレートがありません",
-
-            // ====== ヘルプテキスト ======
-            HelpAddCommand => "設定に新しいコマンドを追加",
-            HelpRemoveCommand => "設定からコマンドを削除",
-            HelpEditCommand => "既存のコマンドを編集",
-            HelpListCommands => "利用可能なコマンド一覧を表示",
-            HelpRunCommand => "コマンドを実行",
-            HelpValidateConfig => "設定ファイルを検証",
-            HelpSearchCommand => "キーワードでコマンドを検索",
-            HelpInfoCommand => "コマンドの詳細情報を表示",
-            HelpConfigCommand => "設定を管理",
-            HelpWatchCommand => "ファイルを監視して変更時にコマンドを実行",
-            HelpInitCommand => "新しい設定ファイルを初期化",
-
-            // ====== List コマンド ======
-            ListNoCommandsDefined => "コマンドが定義されていません",
-            ListAvailableCommands => "利用可能なコマンド",
-            ListCommandCount => "個のコマンドが定義されています",
-            ListAliasCount => "個のエイリアスが定義されています",
-
-            // ====== Run コマンド ======
-            RunRunningCommand => "実行中",
-            RunWithParallelDependencies => "（並列依存関係あり）",
-            RunExecutionPlan => "実行計画",
-            RunGroup => "グループ",
-            RunAllCommandsCompleted => "すべてのコマンドが完了しました",
-            RunCompletedIn => "完了時間",
-            RunCommandFailedWithCode => "コマンドが失敗しました（終了コード",
-
-            // ====== Search コマンド ======
-            SearchSearchingFor => "検索中",
-            SearchNoCommandsMatching => "一致するコマンドが見つかりません",
-            SearchFound => "見つかりました",
-            SearchMatchedIn => "一致箇所",
-            SearchUseInfoToSeeDetails => "詳細を見るには cmdrun info <コマンド> を使用してください",
-
-            // ====== Info コマンド ======
-            InfoSelectCommandToView => "詳細を表示するコマンドを選択",
-            InfoBasicInformation => "基本情報",
-            InfoCommandSpecification => "コマンド仕様",
-            InfoExecutionSettings => "実行設定",
-            InfoPlatformSupport => "プラットフォームサポート",
-            InfoConfigurationPaths => "設定ファイルパス",
-            InfoGlobalConfigPath => "グローバル設定",
-            InfoLocalConfigPath => "ローカル設定",
-            InfoActualWorkingDirectory => "実際の作業ディレクトリ",
-            InfoExecutionStatistics => "実行統計",
-            InfoTotalExecutions => "総実行回数",
-            InfoSuccessfulRuns => "成功回数",
-            InfoFailedRuns => "失敗回数",
-            InfoLastRun => "最終実行",
-            InfoAverageDuration => "平均実行時間",
-
-            // ====== Config コマンド ======
-            ConfigSet => "設定しました",
-            ConfigShowingConfiguration => "設定を表示中",
-
-            // ====== Validate コマンド ======
-            ValidateLoadedConfigFrom => "設定を読み込みました",
-            ValidateCheckingCircularDependencies => "循環依存を確認中...",
-            ValidateNoCircularDependenciesFor => "循環依存はありません",
-            ValidateValidatingCommands => "コマンドを検証中",
-            ValidateValidatingAliases => "エイリアスを検証中",
-            ValidateBuildingDependencyGraph => "依存関係グラフを構築中...",
-            ValidateDependencyGraphBuilt => "依存関係グラフを構築しました",
-            ValidateExecutionOrder => "実行順序",
-            ValidateErrors => "エラー",
-            ValidateWarnings => "警告",
-            ValidateInformation => "情報",
-            ValidateFailedWithErrors => "設定の検証に失敗しました（エラー数",
-            ValidateCommandsDefined => "個のコマンドが定義されています",
-            ValidateAliasesDefined => "個のエイリアスが定義されています",
-
-            // ====== Init コマンド ======
-            InitCreated => "作成しました",
-            InitUsing => "使用中",
-            InitNextSteps => "次のステップ",
-            InitStep1EditFile => "{0} を編集してコマンドを定義",
-            InitStep2ListCommands => "cmdrun list で利用可能なコマンド一覧を表示",
-            InitStep3RunCommand => "cmdrun run <名前> でコマンドを実行",
-            InitExampleCommands => "コマンド例",
-            InitTemplateDescription => "テンプレート",
-            InitLanguageSet => "言語を設定しました",
-
-            // ====== Watch コマンド ======
-            WatchConfiguration => "監視設定",
-            WatchCommand => "コマンド",
-            WatchWatching => "監視中",
-            WatchPatterns => "パターン",
-            WatchExclude => "除外",
-            WatchDebounce => "デバウンス",
-            WatchModeStarted => "監視モードを開始しました。Ctrl+C で停止します。",
-            WatchPresCtrlCToStop => "Ctrl+C で停止",
-            WatchModeStoppedByUser => "監視モードをユーザーが停止しました",
-
-            // ====== Remove コマンド ======
-            RemoveRemovalTarget => "削除対象",
-            RemoveType => "タイプ",
-            RemovePlatformSpecific => "プラットフォーム固有",
-
-            // ====== Edit コマンド ======
-            EditParallelExecution => "並列実行",
-            EditConfirmBeforeExecution => "実行前確認",
-
-            // ====== Graph コマンド ======
-            GraphSavedTo => "グラフを保存しました",
-            GraphRenderWith => "レンダリング",
-            GraphViewAt => "表示",
-
-            // ====== Env コマンド ======
-            EnvCurrent => "現在の環境",
-            EnvAvailableEnvironments => "利用可能な環境",
-            EnvSwitchedTo => "環境を切り替えました",
-            EnvCreated => "環境を作成しました",
-            EnvVariableSet => "変数を設定しました",
-            EnvEnvironment => "環境",
-            EnvDescription => "説明",
-            EnvConfigFile => "設定ファイル",
-            EnvEnvironmentVariables => "環境変数",
-            EnvErrorNotFound => "環境が見つかりません",
-            EnvErrorAlreadyExists => "環境は既に存在します",
-            EnvErrorCannotSetDefault => "デフォルト環境には変数を設定できません",
-
-            // ====== Typo検出 ======
-            TypoUnknownCommand => "不明なコマンド",
-            TypoDidYouMean => "もしかして:",
-            TypoSuggestions => "候補",
-            TypoRunHelp => "'cmdrun --help' で利用可能なコマンドを確認できます",
-
-            // ====== その他 ======
-            AddingCommand => "コマンドを追加中",
-            RemovingCommand => "コマンドを削除中",
-            UpdatingCommand => "コマンドを更新中",
-            OpeningEditor => "エディタを起動中",
-            SearchResults => "検索結果",
-            NoCommandsFound => "コマンドが見つかりません",
-            Cancelled => "キャンセルしました",
-            LoadingConfiguration => "設定を読み込み中",
-            CreatingBackup => "バックアップを作成中",
-            MatchingCommands => "件の一致するコマンド",
-            Template => "テンプレート",
-
-            // ====== History コマンド ======
-            HistoryNoEntriesFound => "履歴エントリが見つかりません",
-            HistoryNoCommandsMatching => "一致するコマンドがありません",
-            HistoryExitCode => "終了コード:",
-            HistoryWorkingDir => "作業ディレクトリ:",
-            HistoryTotalCommands => "総コマンド数:",
-
-            // ====== Template コマンド ======
-            TemplateNoTemplatesAvailable => "利用可能なテンプレートがありません",
-            TemplateUserTemplates => "ユーザーテンプレート:",
-
-            // ====== Plugin コマンド ======
-            PluginNoPluginsInstalled => "インストール済みプラグインがありません",
-            PluginMinimumCmdrunVersion => "最小cmdrunバージョン:",
-
-            // ====== Env コマンド表示 ======
-            EnvCurrentEnvironmentLabel => "現在の環境:",
-            EnvAvailableEnvironmentsLabel => "利用可能な環境:",
-            EnvConfigurationFiles => "設定ファイル",
-            EnvBaseConfig => "ベース設定",
-
-            // ====== Completion コマンド ======
-            CompletionInstallationInstructions => "インストール手順:",
-        },
-        Language::ChineseSimplified => match key {
-            // ====== 实行状态 ======
-            Running => "运行中",
-            Completed => "已完成",
-            Error => "错误",
-            Warning => "警告",
-            Success => "成功",
-
-            // ====== CRUD操作 ======
-            CommandAdded => "成功添加命令",
-            CommandRemoved => "成功删除命令",
-            CommandUpdated => "成功更新命令",
-
-            // ====== 验证 ======
-            Validating => "正在验证配置",
-            ConfigValid => "配置有效",
-            ValidationFailed => "验证失败",
-            ValidatingConfiguration => "正在验证配置...",
-            ConfigurationIsValid => "配置有效",
-
-            // ====== 交互提示 ======
-            PromptCommandId => "命令ID：",
-            PromptCommand => "命令：",
-            PromptDescription => "描述：",
-            PromptCategory => "分类",
-            PromptTags => "标签（逗号分隔）",
-            PromptConfirm => "确定吗？",
-            PromptSelectCommand => "选择要编辑的命令",
-            PromptWhatToDo => "您想做什么？",
-            PromptEnterNumber => "输入数字",
-            PromptSelectTemplate => "选择模板",
-            PromptSelectLanguage => "选择首选语言",
-
-            // ====== 选项 ======
-            OptionYesAdd => "是，添加此命令",
-            OptionNoEdit => "否，重新编辑",
-            OptionCancel => "取消",
-            OptionEnglish => "English (英语)",
-            OptionJapanese => "日本語 (日语)",
-
-            // ====== 预览·标签 ======
-            LabelPreview => "预览",
-            LabelId => "ID",
-            LabelCommand => "命令",
-            LabelDescription => "描述",
-            LabelCategory => "分类",
-            LabelTags => "标签",
-            LabelCurrentSettings => "当前设置",
-            LabelDependencies => "依赖关系",
-            LabelPlatforms => "平台",
-            LabelWorkingDirectory => "工作目录",
-            LabelEnvironmentVariables => "环境变量",
-            LabelExecutionSettings => "执行设置",
-            LabelParallel => "并行执行",
-            LabelConfirm => "执行前确认",
-            LabelTimeout => "超时",
-            LabelCommandDetails => "命令详情",
-            LabelConfiguration => "配置",
-            LabelLanguage => "语言",
-            LabelShell => "shell",
-            LabelStrictMode => "严格模式",
-            LabelBackupCreated => "已创建备份",
-            LabelYes => "是",
-            LabelNo => "否",
-
-            // ====== 警告消息 ======
-            WarningShellBuiltinNoEffect => "⚠ 此shell内置命令在子进程中运行，不会影响当前shell",
-            HintShellFunction => "💡 提示：使用shell函数进行目录导航",
-            HintCdCommand => "   添加到 ~/.cmdrun/shell-functions.sh：",
-
-            // ====== 命令链接提示 ======
-            HintCommandChainingAlternatives => "💡 提示：使用以下替代方法之一：",
-            HintCommandArrayRecommended => "   1. 使用命令数组（推荐安全）:\n      cmd = [\"cd /path\", \"git diff\"]",
-            HintEnableChainingForCommand => "   2. 仅为此命令启用链接（谨慎使用）:\n      allow_chaining = true",
-            HintEnableChainingGlobally => "   3. 全局启用链接（不推荐）:\n      [config]\n      allow_command_chaining = true",
-
-            // ====== 错误消息 ======
-            ErrorEmptyCommandId => "命令ID不能为空",
-            ErrorEmptyCommand => "命令不能为空",
-            ErrorEmptyDescription => "描述不能为空",
-            ErrorCommandNotFound => "找不到命令",
-            ErrorCommandExists => "命令已存在",
-            ErrorConfigNotFound => "找不到配置文件",
-            ErrorInvalidConfig => "无效配置",
-            ErrorInvalidSelection => "无效选择",
-            ErrorSelectionOutOfRange => "选择超出范围",
-            ErrorNoCommandsAvailable => "没有可用命令",
-            ErrorUnknownTemplate => "未知模板",
-            ErrorFileAlreadyExists => "配置文件已存在",
-            ErrorCircularDependency => "检测到循环依赖",
-            ErrorValidationFailed => "验证失败",
-            ErrorCommandFailed => "命令执行失败",
-            ErrorUnknownConfigKey => "未知配置键",
-            ErrorAliasTargetNotFound => "找不到别名目标",
-            ErrorInvalidLanguage => "无效语言。有效选项: english, japanese, chinese_simplified, chinese_traditional",
-            ErrorNoConfigFileFound => "找不到配置文件。运行 'cmdrun init' 创建配置文件。",
-            ErrorCannotDetermineConfigDir => "无法确定配置目录",
-            ErrorCommandExecutionFailed => "命令执行失败，退出代码",
-            ErrorLocalConfigNotFound => "找不到本地配置文件",
-            ErrorNoConfigFilesSpecified => "未指定配置文件",
-            ErrorCannotSetEnvVariable => "无法在默认环境中设置环境变量",
-            ErrorNoSuitableUnixShell => "找不到合适的Unix shell",
-            ErrorNoSuitableWindowsShell => "找不到合适的Windows shell",
-            ErrorTemplateAlreadyExists => "模板已存在",
-            ErrorTemplateNotFound => "找不到模板",
-            ErrorCannotRemoveBuiltinTemplate => "无法删除内置模板",
-            ErrorFileNotFound => "找不到文件",
-            ErrorFailedToAccessCommandsTable => "访问命令表失败",
-            ErrorSecurityValidationFailed => "安全验证失败",
-            ErrorEditorNotFound => "找不到编辑器",
-            ErrorFailedToOpenEditor => "无法打开编辑器",
-            ErrorCannotDetermineShell => "无法确定Shell",
-            ErrorInvalidConfigValue => "配置值无效",
-            ErrorFailedToSaveConfiguration => "保存配置失败",
-            ErrorCannotDetermineHomeDir => "无法确定主目录",
-            ErrorFailedToParseTemplate => "模板解析失败",
-            ErrorHistoryEntryNotFound => "找不到历史记录条目",
-            ErrorFailedToDetermineLocalDataDir => "无法确定本地数据目录",
-            ErrorFailedToInitializeLogger => "日志初始化失败",
-            ErrorFailedToReadConfig => "配置文件读取失败",
-            ErrorFailedToParseConfig => "配置文件解析失败",
-            ErrorInvalidConfigPath => "配置文件路径无效",
-            ErrorFailedToAcquireReadLock => "获取读锁失败",
-            ErrorFailedToAcquireWriteLock => "获取写锁失败",
-            ErrorFailedToInitializePlugin => "插件初始化失败",
-            ErrorFailedToUnloadPlugin => "插件卸载失败",
-            ErrorPluginSymbolNotFound => "找不到插件符号",
-            ErrorFailedToLoadLibrary => "库加载失败",
-            ErrorTemplateNameEmpty => "模板名称不能为空",
-            ErrorTemplateDescriptionEmpty => "模板描述不能为空",
-            ErrorTemplateNoCommands => "模板必须包含至少一个命令",
-            ErrorCommandIdInvalidChars => "命令ID包含无效字符",
-            ErrorCommandFailedWithCode => "命令执行失败，退出代码",
-            ErrorNoTemplatesAvailable => "没有可用模板",
-
-            // ====== 帮助文本 ======
-            HelpAddCommand => "向配置中添加新命令",
-            HelpRemoveCommand => "从配置中删除命令",
-            HelpEditCommand => "编辑现有命令",
-            HelpListCommands => "列出所有可用命令",
-            HelpRunCommand => "运行命令",
-            HelpValidateConfig => "验证配置文件",
-            HelpSearchCommand => "按关键字搜索命令",
-            HelpInfoCommand => "显示命令详细信息",
-            HelpConfigCommand => "管理配置设置",
-            HelpWatchCommand => "监视文件并在更改时运行命令",
-            HelpInitCommand => "初始化新配置文件",
-
-            // ====== List 命令 ======
-            ListNoCommandsDefined => "未定义命令",
-            ListAvailableCommands => "可用命令",
-            ListCommandCount => "个已定义命令",
-            ListAliasCount => "个已定义别名",
-
-            // ====== Run 命令 ======
-            RunRunningCommand => "运行中",
-            RunWithParallelDependencies => "（含并行依赖）",
-            RunExecutionPlan => "执行计划",
-            RunGroup => "组",
-            RunAllCommandsCompleted => "所有命令已完成",
-            RunCompletedIn => "完成时间",
-            RunCommandFailedWithCode => "命令执行失败，退出代码",
-
-            // ====== Search 命令 ======
-            SearchSearchingFor => "搜索中",
-            SearchNoCommandsMatching => "没有匹配的命令",
-            SearchFound => "找到",
-            SearchMatchedIn => "匹配位置",
-            SearchUseInfoToSeeDetails => "使用 cmdrun info <命令> 查看详情",
-
-            // ====== Info 命令 ======
-            InfoSelectCommandToView => "选择要查看详情的命令",
-            InfoBasicInformation => "基本信息",
-            InfoCommandSpecification => "命令规范",
-            InfoExecutionSettings => "执行设置",
-            InfoPlatformSupport => "平台支持",
-            InfoConfigurationPaths => "配置文件路径",
-            InfoGlobalConfigPath => "全局配置",
-            InfoLocalConfigPath => "本地配置",
-            InfoActualWorkingDirectory => "实际工作目录",
-            InfoExecutionStatistics => "执行统计",
-            InfoTotalExecutions => "总执行次数",
-            InfoSuccessfulRuns => "成功次数",
-            InfoFailedRuns => "失败次数",
-            InfoLastRun => "最后执行",
-            InfoAverageDuration => "平均执行时间",
-
-            // ====== Config 命令 ======
-            ConfigSet => "已设置",
-            ConfigShowingConfiguration => "显示配置",
-
-            // ====== Validate 命令 ======
-            ValidateLoadedConfigFrom => "已加载配置文件",
-            ValidateCheckingCircularDependencies => "正在检查循环依赖...",
-            ValidateNoCircularDependenciesFor => "无循环依赖",
-            ValidateValidatingCommands => "正在验证命令",
-            ValidateValidatingAliases => "正在验证别名",
-            ValidateBuildingDependencyGraph => "正在构建依赖关系图...",
-            ValidateDependencyGraphBuilt => "依赖关系图构建成功",
-            ValidateExecutionOrder => "执行顺序",
-            ValidateErrors => "错误",
-            ValidateWarnings => "警告",
-            ValidateInformation => "信息",
-            ValidateFailedWithErrors => "配置验证失败，错误数",
-            ValidateCommandsDefined => "个已定义命令",
-            ValidateAliasesDefined => "个已定义别名",
-
-            // ====== Init 命令 ======
-            InitCreated => "已创建",
-            InitUsing => "使用中",
-            InitNextSteps => "下一步",
-            InitStep1EditFile => "编辑 {0} 来定义您的命令",
-            InitStep2ListCommands => "运行 cmdrun list 列出可用命令",
-            InitStep3RunCommand => "运行 cmdrun run <名称> 执行命令",
-            InitExampleCommands => "示例命令",
-            InitTemplateDescription => "模板",
-            InitLanguageSet => "语言已设置为",
-
-            // ====== Watch 命令 ======
-            WatchConfiguration => "监视配置",
-            WatchCommand => "命令",
-            WatchWatching => "监视中",
-            WatchPatterns => "模式",
-            WatchExclude => "排除",
-            WatchDebounce => "防抖",
-            WatchModeStarted => "监视模式已启动。按 Ctrl+C 停止。",
-            WatchPresCtrlCToStop => "按 Ctrl+C 停止",
-            WatchModeStoppedByUser => "用户已停止监视模式",
-
-            // ====== Remove 命令 ======
-            RemoveRemovalTarget => "删除目标",
-            RemoveType => "类型",
-            RemovePlatformSpecific => "平台特定",
-
-            // ====== Edit 命令 ======
-            EditParallelExecution => "并行执行",
-            EditConfirmBeforeExecution => "执行前确认",
-
-            // ====== Graph 命令 ======
-            GraphSavedTo => "图表已保存至",
-            GraphRenderWith => "渲染工具",
-            GraphViewAt => "查看位置",
-
-            // ====== Env 命令 ======
-            EnvCurrent => "当前环境",
-            EnvAvailableEnvironments => "可用环境",
-            EnvSwitchedTo => "已切换到环境",
-            EnvCreated => "已创建环境",
-            EnvVariableSet => "已设置变量",
-            EnvEnvironment => "环境",
-            EnvDescription => "描述",
-            EnvConfigFile => "配置文件",
-            EnvEnvironmentVariables => "环境变量",
-            EnvErrorNotFound => "找不到环境",
-            EnvErrorAlreadyExists => "环境已存在",
-            EnvErrorCannotSetDefault => "无法为'default'环境设置变量",
-
-            // ====== Typo检测 ======
-            TypoUnknownCommand => "未知命令",
-            TypoDidYouMean => "您是否想输入:",
-            TypoSuggestions => "建议",
-            TypoRunHelp => "运行 'cmdrun --help' 查看可用命令",
-
-            // ====== 其他 ======
-            AddingCommand => "正在添加命令",
-            RemovingCommand => "正在删除命令",
-            UpdatingCommand => "正在更新命令",
-            OpeningEditor => "正在打开编辑器",
-            SearchResults => "搜索结果",
-            NoCommandsFound => "找不到命令",
-            Cancelled => "已取消",
-            LoadingConfiguration => "正在加载配置",
-            CreatingBackup => "正在创建备份",
-            MatchingCommands => "个匹配命令",
-            Template => "模板",
-
-            // ====== History 命令 ======
-            HistoryNoEntriesFound => "未找到历史记录",
-            HistoryNoCommandsMatching => "没有匹配的命令",
-            HistoryExitCode => "退出代码:",
-            HistoryWorkingDir => "工作目录:",
-            HistoryTotalCommands => "总命令数:",
-
-            // ====== Template 命令 ======
-            TemplateNoTemplatesAvailable => "没有可用模板",
-            TemplateUserTemplates => "用户模板:",
-
-            // ====== Plugin 命令 ======
-            PluginNoPluginsInstalled => "未安装插件",
-            PluginMinimumCmdrunVersion => "最低cmdrun版本:",
-
-            // ====== Env 命令显示 ======
-            EnvCurrentEnvironmentLabel => "当前环境:",
-            EnvAvailableEnvironmentsLabel => "可用环境:",
-            EnvConfigurationFiles => "配置文件",
-            EnvBaseConfig => "基础配置",
-
-            // ====== Completion 命令 ======
-            CompletionInstallationInstructions => "安装说明:",
-        },
-        Language::ChineseTraditional => match key {
-            // ====== 執行狀態 ======
-            Running => "執行中",
-            Completed => "已完成",
-            Error => "錯誤",
-            Warning => "警告",
-            Success => "成功",
-
-            // ====== CRUD操作 ======
-            CommandAdded => "成功新增命令",
-            CommandRemoved => "成功刪除命令",
-            CommandUpdated => "成功更新命令",
-
-            // ====== 驗證 ======
-            Validating => "正在驗證配置",
-            ConfigValid => "配置有效",
-            ValidationFailed => "驗證失敗",
-            ValidatingConfiguration => "正在驗證配置...",
-            ConfigurationIsValid => "配置有效",
-
-            // ====== 互動提示 ======
-            PromptCommandId => "命令ID：",
-            PromptCommand => "命令：",
-            PromptDescription => "描述：",
-            PromptCategory => "分類",
-            PromptTags => "標籤（逗號分隔）",
-            PromptConfirm => "您確定嗎？",
-            PromptSelectCommand => "選擇要編輯的命令",
-            PromptWhatToDo => "您想做什麼？",
-            PromptEnterNumber => "輸入數字",
-            PromptSelectTemplate => "選擇範本",
-            PromptSelectLanguage => "選擇偏好語言",
-
-            // ====== 選項 ======
-            OptionYesAdd => "是，新增此命令",
-            OptionNoEdit => "否，重新編輯",
-            OptionCancel => "取消",
-            OptionEnglish => "English (英語)",
-            OptionJapanese => "日本語 (日語)",
-
-            // ====== 預覽·標籤 ======
-            LabelPreview => "預覽",
-            LabelId => "ID",
-            LabelCommand => "命令",
-            LabelDescription => "描述",
-            LabelCategory => "分類",
-            LabelTags => "標籤",
-            LabelCurrentSettings => "目前設定",
-            LabelDependencies => "相依性",
-            LabelPlatforms => "平台",
-            LabelWorkingDirectory => "工作目錄",
-            LabelEnvironmentVariables => "環境變數",
-            LabelExecutionSettings => "執行設定",
-            LabelParallel => "並行執行",
-            LabelConfirm => "執行前確認",
-            LabelTimeout => "逾時",
-            LabelCommandDetails => "命令詳情",
-            LabelConfiguration => "配置",
-            LabelLanguage => "語言",
-            LabelShell => "shell",
-            LabelStrictMode => "嚴格模式",
-            LabelBackupCreated => "已建立備份",
-            LabelYes => "是",
-            LabelNo => "否",
-
-            // ====== 警告訊息 ======
-            WarningShellBuiltinNoEffect => "⚠ 此shell內建命令在子處理序中執行，不會影響目前shell",
-            HintShellFunction => "💡 提示：使用shell函式進行目錄導覽",
-            HintCdCommand => "   新增至 ~/.cmdrun/shell-functions.sh：",
-
-            // ====== 命令鏈接提示 ======
-            HintCommandChainingAlternatives => "💡 提示：使用以下替代方法之一：",
-            HintCommandArrayRecommended => "   1. 使用命令陣列（建議安全）:\n      cmd = [\"cd /path\", \"git diff\"]",
-            HintEnableChainingForCommand => "   2. 僅為此命令啟用鏈接（謹慎使用）:\n      allow_chaining = true",
-            HintEnableChainingGlobally => "   3. 全域啟用鏈接（不建議）:\n      [config]\n      allow_command_chaining = true",
-
-            // ====== 錯誤訊息 ======
-            ErrorEmptyCommandId => "命令ID不能為空",
-            ErrorEmptyCommand => "命令不能為空",
-            ErrorEmptyDescription => "描述不能為空",
-            ErrorCommandNotFound => "找不到命令",
-            ErrorCommandExists => "命令已存在",
-            ErrorConfigNotFound => "找不到配置檔案",
-            ErrorInvalidConfig => "無效配置",
-            ErrorInvalidSelection => "無效選擇",
-            ErrorSelectionOutOfRange => "選擇超出範圍",
-            ErrorNoCommandsAvailable => "沒有可用命令",
-            ErrorUnknownTemplate => "未知範本",
-            ErrorFileAlreadyExists => "配置檔案已存在",
-            ErrorCircularDependency => "偵測到循環相依",
-            ErrorValidationFailed => "驗證失敗",
-            ErrorCommandFailed => "命令執行失敗",
-            ErrorUnknownConfigKey => "未知配置鍵",
-            ErrorAliasTargetNotFound => "找不到別名目標",
-            ErrorInvalidLanguage => "無效語言。有效選項: english, japanese, chinese_simplified, chinese_traditional",
-            ErrorNoConfigFileFound => "找不到配置檔案。執行 'cmdrun init' 建立配置檔案。",
-            ErrorCannotDetermineConfigDir => "無法確定配置目錄",
-            ErrorCommandExecutionFailed => "命令執行失敗，結束代碼",
-            ErrorLocalConfigNotFound => "找不到本地配置檔案",
-            ErrorNoConfigFilesSpecified => "未指定配置檔案",
-            ErrorCannotSetEnvVariable => "無法在預設環境中設定環境變數",
-            ErrorNoSuitableUnixShell => "找不到合適的Unix shell",
-            ErrorNoSuitableWindowsShell => "找不到合適的Windows shell",
-            ErrorTemplateAlreadyExists => "範本已存在",
-            ErrorTemplateNotFound => "找不到範本",
-            ErrorCannotRemoveBuiltinTemplate => "無法刪除內建範本",
-            ErrorFileNotFound => "找不到檔案",
-            ErrorFailedToAccessCommandsTable => "存取命令表失敗",
-            ErrorSecurityValidationFailed => "安全驗證失敗",
-            ErrorEditorNotFound => "找不到編輯器",
-            ErrorFailedToOpenEditor => "無法開啟編輯器",
-            ErrorCannotDetermineShell => "無法確定Shell",
-            ErrorInvalidConfigValue => "設定值無效",
-            ErrorFailedToSaveConfiguration => "儲存設定失敗",
-            ErrorCannotDetermineHomeDir => "無法確定主目錄",
-            ErrorFailedToParseTemplate => "範本解析失敗",
-            ErrorHistoryEntryNotFound => "找不到歷史記錄條目",
-            ErrorFailedToDetermineLocalDataDir => "無法確定本機資料目錄",
-            ErrorFailedToInitializeLogger => "日誌初始化失敗",
-            ErrorFailedToReadConfig => "配置檔案讀取失敗",
-            ErrorFailedToParseConfig => "配置檔案解析失敗",
-            ErrorInvalidConfigPath => "配置檔案路徑無效",
-            ErrorFailedToAcquireReadLock => "取得讀取鎖失敗",
-            ErrorFailedToAcquireWriteLock => "取得寫入鎖失敗",
-            ErrorFailedToInitializePlugin => "外掛程式初始化失敗",
-            ErrorFailedToUnloadPlugin => "外掛程式卸載失敗",
-            ErrorPluginSymbolNotFound => "找不到外掛程式符號",
-            ErrorFailedToLoadLibrary => "程式庫載入失敗",
-            ErrorTemplateNameEmpty => "範本名稱不能為空",
-            ErrorTemplateDescriptionEmpty => "範本描述不能為空",
-            ErrorTemplateNoCommands => "範本必須包含至少一個命令",
-            ErrorCommandIdInvalidChars => "命令ID包含無效字元",
-            ErrorCommandFailedWithCode => "命令執行失敗，結束代碼",
-            ErrorNoTemplatesAvailable => "沒有可用範本",
-
-            // ====== 說明文字 ======
-            HelpAddCommand => "向配置中新增命令",
-            HelpRemoveCommand => "從配置中刪除命令",
-            HelpEditCommand => "編輯現有命令",
-            HelpListCommands => "列出所有可用命令",
-            HelpRunCommand => "執行命令",
-            HelpValidateConfig => "驗證配置檔案",
-            HelpSearchCommand => "按關鍵字搜尋命令",
-            HelpInfoCommand => "顯示命令詳細資訊",
-            HelpConfigCommand => "管理配置設定",
-            HelpWatchCommand => "監視檔案並在變更時執行命令",
-            HelpInitCommand => "初始化新配置檔案",
-
-            // ====== List 命令 ======
-            ListNoCommandsDefined => "未定義命令",
-            ListAvailableCommands => "可用命令",
-            ListCommandCount => "個已定義命令",
-            ListAliasCount => "個已定義別名",
-
-            // ====== Run 命令 ======
-            RunRunningCommand => "執行中",
-            RunWithParallelDependencies => "（含並行相依）",
-            RunExecutionPlan => "執行計畫",
-            RunGroup => "群組",
-            RunAllCommandsCompleted => "所有命令已完成",
-            RunCompletedIn => "完成時間",
-            RunCommandFailedWithCode => "命令執行失敗，結束代碼",
-
-            // ====== Search 命令 ======
-            SearchSearchingFor => "搜尋中",
-            SearchNoCommandsMatching => "沒有符合的命令",
-            SearchFound => "找到",
-            SearchMatchedIn => "符合位置",
-            SearchUseInfoToSeeDetails => "使用 cmdrun info <命令> 檢視詳情",
-
-            // ====== Info 命令 ======
-            InfoSelectCommandToView => "選擇要檢視詳情的命令",
-            InfoBasicInformation => "基本資訊",
-            InfoCommandSpecification => "命令規範",
-            InfoExecutionSettings => "執行設定",
-            InfoPlatformSupport => "平台支援",
-            InfoConfigurationPaths => "配置檔案路徑",
-            InfoGlobalConfigPath => "全域配置",
-            InfoLocalConfigPath => "本機配置",
-            InfoActualWorkingDirectory => "實際工作目錄",
-            InfoExecutionStatistics => "執行統計",
-            InfoTotalExecutions => "總執行次數",
-            InfoSuccessfulRuns => "成功次數",
-            InfoFailedRuns => "失敗次數",
-            InfoLastRun => "最後執行",
-            InfoAverageDuration => "平均執行時間",
-
-            // ====== Config 命令 ======
-            ConfigSet => "已設定",
-            ConfigShowingConfiguration => "顯示配置",
-
-            // ====== Validate 命令 ======
-            ValidateLoadedConfigFrom => "已載入配置檔案",
-            ValidateCheckingCircularDependencies => "正在檢查循環相依...",
-            ValidateNoCircularDependenciesFor => "無循環相依",
-            ValidateValidatingCommands => "正在驗證命令",
-            ValidateValidatingAliases => "正在驗證別名",
-            ValidateBuildingDependencyGraph => "正在建立相依性圖...",
-            ValidateDependencyGraphBuilt => "相依性圖建立成功",
-            ValidateExecutionOrder => "執行順序",
-            ValidateErrors => "錯誤",
-            ValidateWarnings => "警告",
-            ValidateInformation => "資訊",
-            ValidateFailedWithErrors => "配置驗證失敗，錯誤數",
-            ValidateCommandsDefined => "個已定義命令",
-            ValidateAliasesDefined => "個已定義別名",
-
-            // ====== Init 命令 ======
-            InitCreated => "已建立",
-            InitUsing => "使用中",
-            InitNextSteps => "下一步",
-            InitStep1EditFile => "編輯 {0} 來定義您的命令",
-            InitStep2ListCommands => "執行 cmdrun list 列出可用命令",
-            InitStep3RunCommand => "執行 cmdrun run <名稱> 執行命令",
-            InitExampleCommands => "範例命令",
-            InitTemplateDescription => "範本",
-            InitLanguageSet => "語言已設定為",
-
-            // ====== Watch 命令 ======
-            WatchConfiguration => "監視配置",
-            WatchCommand => "命令",
-            WatchWatching => "監視中",
-            WatchPatterns => "模式",
-            WatchExclude => "排除",
-            WatchDebounce => "防抖",
-            WatchModeStarted => "監視模式已啟動。按 Ctrl+C 停止。",
-            WatchPresCtrlCToStop => "按 Ctrl+C 停止",
-            WatchModeStoppedByUser => "使用者已停止監視模式",
-
-            // ====== Remove 命令 ======
-            RemoveRemovalTarget => "刪除目標",
-            RemoveType => "類型",
-            RemovePlatformSpecific => "平台特定",
-
-            // ====== Edit 命令 ======
-            EditParallelExecution => "並行執行",
-            EditConfirmBeforeExecution => "執行前確認",
-
-            // ====== Graph 命令 ======
-            GraphSavedTo => "圖表已儲存至",
-            GraphRenderWith => "算繪工具",
-            GraphViewAt => "檢視位置",
-
-            // ====== Env 命令 ======
-            EnvCurrent => "目前環境",
-            EnvAvailableEnvironments => "可用環境",
-            EnvSwitchedTo => "已切換至環境",
-            EnvCreated => "已建立環境",
-            EnvVariableSet => "已設定變數",
-            EnvEnvironment => "環境",
-            EnvDescription => "描述",
-            EnvConfigFile => "配置檔案",
-            EnvEnvironmentVariables => "環境變數",
-            EnvErrorNotFound => "找不到環境",
-            EnvErrorAlreadyExists => "環境已存在",
-            EnvErrorCannotSetDefault => "無法為'default'環境設定變數",
-
-            // ====== Typo檢測 ======
-            TypoUnknownCommand => "未知命令",
-            TypoDidYouMean => "您是否想輸入:",
-            TypoSuggestions => "建議",
-            TypoRunHelp => "執行 'cmdrun --help' 檢視可用命令",
-
-            // ====== 其他 ======
-            AddingCommand => "正在新增命令",
-            RemovingCommand => "正在刪除命令",
-            UpdatingCommand => "正在更新命令",
-            OpeningEditor => "正在開啟編輯器",
-            SearchResults => "搜尋結果",
-            NoCommandsFound => "找不到命令",
-            Cancelled => "已取消",
-            LoadingConfiguration => "正在載入配置",
-            CreatingBackup => "正在建立備份",
-            MatchingCommands => "個符合命令",
-            Template => "範本",
-
-            // ====== History 命令 ======
-            HistoryNoEntriesFound => "未找到歷史記錄",
-            HistoryNoCommandsMatching => "沒有符合的命令",
-            HistoryExitCode => "結束代碼:",
-            HistoryWorkingDir => "工作目錄:",
-            HistoryTotalCommands => "總命令數:",
-
-            // ====== Template 命令 ======
-            TemplateNoTemplatesAvailable => "沒有可用範本",
-            TemplateUserTemplates => "使用者範本:",
-
-            // ====== Plugin 命令 ======
-            PluginNoPluginsInstalled => "未安裝外掛程式",
-            PluginMinimumCmdrunVersion => "最低cmdrun版本:",
-
-            // ====== Env 命令顯示 ======
-            EnvCurrentEnvironmentLabel => "目前環境:",
-            EnvAvailableEnvironmentsLabel => "可用環境:",
-            EnvConfigurationFiles => "配置檔案",
-            EnvBaseConfig => "基礎配置",
-
-            // ====== Completion 命令 ======
-            CompletionInstallationInstructions => "安裝說明:",
-        },
+    match key {
+        // ====== 実行状態 ======
+        Running => "Running",
+        Completed => "Completed",
+        Error => "Error",
+        Warning => "Warning",
+        Success => "Success",
+
+        // ====== CRUD操作 ======
+        CommandAdded => "Command added successfully",
+        CommandRemoved => "Command removed successfully",
+        CommandUpdated => "Command updated successfully",
+
+        // ====== 検証 ======
+        Validating => "Validating configuration",
+        ConfigValid => "Configuration is valid",
+        ValidationFailed => "Validation failed",
+        ValidatingConfiguration => "Validating configuration...",
+        ConfigurationIsValid => "Configuration is valid",
+
+        // ====== 対話プロンプト ======
+        PromptCommandId => "Command ID:",
+        PromptCommand => "Command:",
+        PromptDescription => "Description:",
+        PromptCategory => "Category",
+        PromptTags => "Tags (comma-separated)",
+        PromptConfirm => "Are you sure?",
+        PromptSelectCommand => "Select command to edit",
+        PromptWhatToDo => "What would you like to do?",
+        PromptEnterNumber => "Enter number",
+        PromptSelectTemplate => "Select a template",
+        PromptSelectLanguage => "Select your preferred language",
+        PromptConfirmRunCommand => "Run command '{0}'?",
+        ErrorConfirmationRequiresTerminal => "Command '{0}' requires confirmation but stdin is not a terminal (use --yes to confirm)",
+        WarningConfirmationSkipped => "Skipping confirmation for '{0}' (--yes)",
+
+        // ====== 選択肢 ======
+        OptionYesAdd => "Yes, add this command",
+        OptionNoEdit => "No, edit again",
+        OptionCancel => "Cancel",
+        OptionEnglish => "English",
+        OptionJapanese => "日本語 (Japanese)",
+        OptionSpanish => "Español (Spanish)",
+        OptionGerman => "Deutsch (German)",
+
+        // ====== プレビュー・ラベル ======
+        LabelPreview => "Preview",
+        LabelId => "ID",
+        LabelCommand => "Command",
+        LabelDescription => "Description",
+        LabelCategory => "Category",
+        LabelTags => "Tags",
+        LabelCurrentSettings => "Current settings",
+        LabelDependencies => "Dependencies",
+        LabelPlatforms => "Platforms",
+        LabelWorkingDirectory => "Working directory",
+        LabelEnvironmentVariables => "Environment variables",
+        LabelExecutionSettings => "Execution settings",
+        LabelParallel => "Parallel",
+        LabelConfirm => "Confirm",
+        LabelTimeout => "Timeout",
+        LabelCommandDetails => "Command details",
+        LabelConfiguration => "Configuration",
+        LabelLanguage => "language",
+        LabelShell => "shell",
+        LabelStrictMode => "strict_mode",
+        LabelBackupCreated => "Backup created",
+        LabelYes => "yes",
+        LabelNo => "no",
+
+        // ====== 警告メッセージ ======
+        WarningShellBuiltinNoEffect => "⚠ This shell builtin command runs in a subprocess and won't affect the current shell",
+        HintShellFunction => "💡 Hint: Use shell functions for directory navigation",
+        HintCdCommand => "   Add to ~/.cmdrun/shell-functions.sh:",
+
+        // ====== コマンド連結ヒント ======
+        HintCommandChainingAlternatives => "💡 Hint: Use one of these alternatives:",
+        HintCommandArrayRecommended => "   1. Use command array (recommended for security):\n      cmd = [\"cd /path\", \"git diff\"]",
+        HintEnableChainingForCommand => "   2. Enable chaining for this command (use with caution):\n      allow_chaining = true",
+        HintEnableChainingGlobally => "   3. Enable chaining globally (not recommended):\n      [config]\n      allow_command_chaining = true",
+
+        // ====== エラーメッセージ ======
+        ErrorEmptyCommandId => "Command ID cannot be empty",
+        ErrorEmptyCommand => "Command cannot be empty",
+        ErrorEmptyDescription => "Description cannot be empty",
+        ErrorCommandNotFound => "Command not found",
+        ErrorCommandExists => "Command already exists",
+        ErrorConfigNotFound => "Configuration file not found",
+        ErrorInvalidConfig => "Invalid configuration",
+        ErrorInvalidSelection => "Invalid selection",
+        ErrorSelectionOutOfRange => "Selection out of range",
+        ErrorNoCommandsAvailable => "No commands available",
+        ErrorUnknownTemplate => "Unknown template",
+        ErrorFileAlreadyExists => "Configuration file already exists",
+        ErrorCircularDependency => "Circular dependency detected",
+        ErrorValidationFailed => "Validation failed",
+        ErrorCommandFailed => "Command failed",
+        ErrorUnknownConfigKey => "Unknown configuration key",
+        ErrorAliasTargetNotFound => "Alias target not found",
+        ErrorInvalidLanguage => "Invalid language. Valid options: english, japanese, chinese_simplified, chinese_traditional, spanish, german",
+        ErrorNoConfigFileFound => "No configuration file found. Run 'cmdrun init' to create one.",
+        ErrorCannotDetermineConfigDir => "Cannot determine config directory",
+        ErrorCommandExecutionFailed => "Command failed with exit code",
+        ErrorLocalConfigNotFound => "Local configuration file not found",
+        ErrorNoConfigFilesSpecified => "No configuration files specified",
+        ErrorCannotSetEnvVariable => "Cannot set environment variable in default environment",
+        ErrorNoSuitableUnixShell => "No suitable Unix shell found",
+        ErrorNoSuitableWindowsShell => "No suitable Windows shell found",
+        ErrorShellNotFound => "Shell not found",
+        ErrorTemplateAlreadyExists => "Template already exists",
+        ErrorTemplateNotFound => "Template not found",
+        ErrorCannotRemoveBuiltinTemplate => "Cannot remove built-in template",
+        ErrorFileNotFound => "File not found",
+        ErrorFailedToAccessCommandsTable => "Failed to access commands table",
+        ErrorSecurityValidationFailed => "Security validation failed",
+        ErrorEditorNotFound => "Editor not found",
+        ErrorFailedToOpenEditor => "Failed to open editor",
+        ErrorCannotDetermineShell => "Cannot determine shell",
+        ErrorInvalidConfigValue => "Invalid configuration value",
+        ErrorFailedToSaveConfiguration => "Failed to save configuration",
+        ErrorCannotDetermineHomeDir => "Could not determine home directory",
+        ErrorFailedToParseTemplate => "Failed to parse template",
+        ErrorHistoryEntryNotFound => "History entry not found",
+        ErrorFailedToDetermineLocalDataDir => "Failed to determine local data directory",
+        ErrorFailedToInitializeLogger => "Failed to initialize logger",
+        ErrorFailedToReadConfig => "Failed to read config",
+        ErrorFailedToParseConfig => "Failed to parse config",
+        ErrorInvalidConfigPath => "Invalid config path",
+        ErrorFailedToAcquireReadLock => "Failed to acquire read lock",
+        ErrorFailedToAcquireWriteLock => "Failed to acquire write lock",
+        ErrorFailedToInitializePlugin => "Failed to initialize plugin",
+        ErrorFailedToUnloadPlugin => "Failed to unload plugin",
+        ErrorPluginSymbolNotFound => "Plugin symbol not found",
+        ErrorFailedToLoadLibrary => "Failed to load library",
+        ErrorTemplateNameEmpty => "Template name cannot be empty",
+        ErrorTemplateDescriptionEmpty => "Template description cannot be empty",
+        ErrorTemplateNoCommands => "Template must contain at least one command",
+        ErrorCommandIdInvalidChars => "Command ID contains invalid characters",
+        ErrorCommandFailedWithCode => "Command failed with exit code",
+        ErrorNoTemplatesAvailable => "No templates available",
+
+        // ====== ヘルプテキスト ======
+        HelpAddCommand => "Add a new command to the configuration",
+        HelpRemoveCommand => "Remove a command from the configuration",
+        HelpEditCommand => "Edit an existing command",
+        HelpListCommands => "List all available commands",
+        HelpRunCommand => "Run a command",
+        HelpValidateConfig => "Validate configuration file",
+        HelpSearchCommand => "Search commands by keyword",
+        HelpInfoCommand => "Show detailed command information",
+        HelpConfigCommand => "Manage configuration settings",
+        HelpWatchCommand => "Watch files and run command on changes",
+        HelpInitCommand => "Initialize a new configuration file",
+
+        // ====== List コマンド ======
+        ListNoCommandsDefined => "No commands defined",
+        ListAvailableCommands => "Available commands",
+        ListCommandCount => "commands defined",
+        ListAliasCount => "aliases defined",
+
+        // ====== Run コマンド ======
+        RunRunningCommand => "Running",
+        RunWithParallelDependencies => "with parallel dependencies",
+        RunExecutionPlan => "Execution plan",
+        RunGroup => "Group",
+        RunAllCommandsCompleted => "All commands completed in",
+        RunCompletedIn => "Completed in",
+        RunCommandFailedWithCode => "Command failed with exit code",
+
+        // ====== Search コマンド ======
+        SearchSearchingFor => "Searching for",
+        SearchNoCommandsMatching => "No commands matching",
+        SearchFound => "Found",
+        SearchMatchedIn => "Matched in",
+        SearchUseInfoToSeeDetails => "Use cmdrun info <command> to see details",
+
+        // ====== Info コマンド ======
+        InfoSelectCommandToView => "Select command to view details",
+        InfoBasicInformation => "Basic information",
+        InfoCommandSpecification => "Command specification",
+        InfoExecutionSettings => "Execution settings",
+        InfoPlatformSupport => "Platform support",
+        InfoConfigurationPaths => "Configuration paths",
+        InfoGlobalConfigPath => "Global config",
+        InfoLocalConfigPath => "Local config",
+        InfoActualWorkingDirectory => "Actual working directory",
+        InfoExecutionStatistics => "Execution Statistics",
+        InfoTotalExecutions => "Total executions",
+        InfoSuccessfulRuns => "Successful runs",
+        InfoFailedRuns => "Failed runs",
+        InfoLastRun => "Last run",
+        InfoAverageDuration => "Average duration",
+
+        // ====== Config コマンド ======
+        ConfigSet => "Set",
+        ConfigShowingConfiguration => "Showing configuration",
+
+        // ====== Validate コマンド ======
+        ValidateLoadedConfigFrom => "Loaded configuration from",
+        ValidateCheckingCircularDependencies => "Checking for circular dependencies...",
+        ValidateNoCircularDependenciesFor => "No circular dependencies for",
+        ValidateValidatingCommands => "Validating commands",
+        ValidateValidatingAliases => "Validating aliases",
+        ValidateBuildingDependencyGraph => "Building dependency graph...",
+        ValidateDependencyGraphBuilt => "Dependency graph built successfully",
+        ValidateExecutionOrder => "Execution order",
+        ValidateErrors => "Errors",
+        ValidateWarnings => "Warnings",
+        ValidateInformation => "Information",
+        ValidateFailedWithErrors => "Configuration validation failed with",
+        ValidateCommandsDefined => "commands defined",
+        ValidateAliasesDefined => "aliases defined",
+
+        // ====== Init コマンド ======
+        InitCreated => "Created",
+        InitUsing => "Using",
+        InitNextSteps => "Next steps",
+        InitStep1EditFile => "Edit {0} to define your commands",
+        InitStep2ListCommands => "Run cmdrun list to list available commands",
+        InitStep3RunCommand => "Run cmdrun run <name> to execute a command",
+        InitExampleCommands => "Example commands",
+        InitTemplateDescription => "template",
+        InitLanguageSet => "Language set to",
+
+        // ====== Watch コマンド ======
+        WatchConfiguration => "Watch Configuration",
+        WatchCommand => "Command",
+        WatchWatching => "Watching",
+        WatchPatterns => "Patterns",
+        WatchExclude => "Exclude",
+        WatchDebounce => "Debounce",
+        WatchModeStarted => "Watch mode started. Press Ctrl+C to stop.",
+        WatchPresCtrlCToStop => "Press Ctrl+C to stop",
+        WatchModeStoppedByUser => "Watch mode stopped by user",
+
+        // ====== Remove コマンド ======
+        RemoveRemovalTarget => "Removal target",
+        RemoveType => "Type",
+        RemovePlatformSpecific => "Platform-specific",
+
+        // ====== Edit コマンド ======
+        EditParallelExecution => "Parallel execution",
+        EditConfirmBeforeExecution => "Confirm before execution",
+        EditNoChanges => "No changes",
+
+        // ====== Graph コマンド ======
+        GraphSavedTo => "Graph saved to",
+        GraphRenderWith => "Render with",
+        GraphViewAt => "View at",
+
+        // ====== Env コマンド ======
+        EnvCurrent => "Current environment",
+        EnvAvailableEnvironments => "Available environments",
+        EnvSwitchedTo => "Switched to environment",
+        EnvCreated => "Created environment",
+        EnvVariableSet => "Set variable",
+        EnvEnvironment => "Environment",
+        EnvDescription => "Description",
+        EnvConfigFile => "Config file",
+        EnvEnvironmentVariables => "Environment variables",
+        EnvErrorNotFound => "Environment not found",
+        EnvErrorAlreadyExists => "Environment already exists",
+        EnvErrorCannotSetDefault => "Cannot set variables for 'default' environment",
+
+        // ====== Typo検出 ======
+        TypoUnknownCommand => "Unknown command",
+        TypoDidYouMean => "Did you mean one of these?",
+        TypoSuggestions => "Suggestions",
+        TypoRunHelp => "Run 'cmdrun --help' for available commands",
+
+        // ====== その他 ======
+        AddingCommand => "Adding command",
+        RemovingCommand => "Removing command",
+        UpdatingCommand => "Updating command",
+        OpeningEditor => "Opening editor",
+        SearchResults => "Search results",
+        NoCommandsFound => "No commands found",
+        Cancelled => "Cancelled",
+        LoadingConfiguration => "Loading configuration",
+        CreatingBackup => "Creating backup",
+        MatchingCommands => "matching command(s)",
+        Template => "template",
+
+        // ====== History コマンド ======
+        HistoryNoEntriesFound => "No history entries found",
+        HistoryNoCommandsMatching => "No commands matching",
+        HistoryExitCode => "Exit code:",
+        HistoryWorkingDir => "Working dir:",
+        HistoryTotalCommands => "Total commands:",
+
+        // ====== Template コマンド ======
+        TemplateNoTemplatesAvailable => "No templates available",
+        TemplateUserTemplates => "User templates:",
+
+        // ====== Plugin コマンド ======
+        PluginNoPluginsInstalled => "No plugins installed",
+        PluginMinimumCmdrunVersion => "Minimum cmdrun version:",
+
+        // ====== Env コマンド表示 ======
+        EnvCurrentEnvironmentLabel => "Current environment:",
+        EnvAvailableEnvironmentsLabel => "Available environments:",
+        EnvConfigurationFiles => "Configuration files",
+        EnvBaseConfig => "Base config",
+
+        // ====== Completion コマンド ======
+        CompletionInstallationInstructions => "Installation instructions:",
     }
 }
 
+/// 日本語のメッセージ（未翻訳のキーは `None`）
+fn japanese(key: MessageKey) -> Option<&'static str> {
+    use MessageKey::*;
+
+    let message = match key {
+        // ====== 実行状態 ======
+        Running => "実行中",
+        Completed => "完了",
+        Error => "エラー",
+        Warning => "警告",
+        Success => "成功",
+
+        // ====== CRUD操作 ======
+        CommandAdded => "コマンドを追加しました",
+        CommandRemoved => "コマンドを削除しました",
+        CommandUpdated => "コマンドを更新しました",
+
+        // ====== 検証 ======
+        Validating => "設定を検証中",
+        ConfigValid => "設定は有効です",
+        ValidationFailed => "検証に失敗しました",
+        ValidatingConfiguration => "設定を検証中...",
+        ConfigurationIsValid => "設定は有効です",
+
+        // ====== 対話プロンプト ======
+        PromptCommandId => "コマンドID:",
+        PromptCommand => "コマンド：",
+        PromptDescription => "説明：",
+        PromptCategory => "カテゴリ",
+        PromptTags => "タグ（カンマ区切り）",
+        PromptConfirm => "よろしいですか？",
+        PromptSelectCommand => "編集するコマンドを選択",
+        PromptWhatToDo => "どうしますか？",
+        PromptEnterNumber => "番号を入力",
+        PromptSelectTemplate => "テンプレートを選択",
+        PromptSelectLanguage => "言語を選択してください",
+        PromptConfirmRunCommand => "コマンド '{0}' を実行しますか？",
+        ErrorConfirmationRequiresTerminal => "コマンド '{0}' は確認が必要ですが、標準入力が端末ではありません（--yes で確認をスキップできます）",
+        WarningConfirmationSkipped => "'{0}' の確認をスキップします（--yes）",
+
+        // ====== 選択肢 ======
+        OptionYesAdd => "はい、このコマンドを追加",
+        OptionNoEdit => "いいえ、再編集",
+        OptionCancel => "キャンセル",
+        OptionEnglish => "English (英語)",
+        OptionJapanese => "日本語",
+        OptionSpanish => "Español (スペイン語)",
+        OptionGerman => "Deutsch (ドイツ語)",
+
+        // ====== プレビュー・ラベル ======
+        LabelPreview => "プレビュー",
+        LabelCommand => "コマンド",
+        LabelDescription => "説明",
+        LabelCategory => "カテゴリ",
+        LabelTags => "タグ",
+        LabelCurrentSettings => "現在の設定",
+        LabelDependencies => "依存関係",
+        LabelPlatforms => "プラットフォーム",
+        LabelWorkingDirectory => "作業ディレクトリ",
+        LabelEnvironmentVariables => "環境変数",
+        LabelExecutionSettings => "実行設定",
+        LabelParallel => "並列実行",
+        LabelConfirm => "実行前確認",
+        LabelTimeout => "タイムアウト",
+        LabelCommandDetails => "コマンド詳細",
+        LabelConfiguration => "設定",
+        LabelLanguage => "言語",
+        LabelShell => "シェル",
+        LabelStrictMode => "厳格モード",
+        LabelBackupCreated => "バックアップを作成しました",
+        LabelYes => "はい",
+        LabelNo => "いいえ",
+
+        // ====== 警告メッセージ ======
+        WarningShellBuiltinNoEffect => "⚠ このシェルビルトインコマンドはサブプロセスで実行されるため、現在のシェルには影響しません",
+        HintShellFunction => "💡 ヒント: ディレクトリ移動にはシェル関数を使ってください",
+        HintCdCommand => "   ~/.cmdrun/shell-functions.sh に追加:",
+
+        // ====== コマンド連結ヒント ======
+        HintCommandChainingAlternatives => "💡 ヒント: 次のいずれかの代替方法を使用してください：",
+        HintCommandArrayRecommended => "   1. コマンド配列を使用（セキュリティ上推奨）:\n      cmd = [\"cd /path\", \"git diff\"]",
+        HintEnableChainingForCommand => "   2. このコマンドのみ連結を許可（注意して使用）:\n      allow_chaining = true",
+        HintEnableChainingGlobally => "   3. グローバルで連結を許可（非推奨）:\n      [config]\n      allow_command_chaining = true",
+
+        // ====== エラーメッセージ ======
+        ErrorEmptyCommandId => "コマンドIDは空にできません",
+        ErrorEmptyCommand => "コマンドは空にできません",
+        ErrorEmptyDescription => "説明は空にできません",
+        ErrorCommandNotFound => "コマンドが見つかりません",
+        ErrorCommandExists => "コマンドは既に存在します",
+        ErrorConfigNotFound => "設定ファイルが見つかりません",
+        ErrorInvalidConfig => "無効な設定です",
+        ErrorInvalidSelection => "無効な選択です",
+        ErrorSelectionOutOfRange => "選択が範囲外です",
+        ErrorNoCommandsAvailable => "利用可能なコマンドがありません",
+        ErrorUnknownTemplate => "不明なテンプレートです",
+        ErrorFileAlreadyExists => "設定ファイルは既に存在します",
+        ErrorCircularDependency => "循環依存が検出されました",
+        ErrorValidationFailed => "検証に失敗しました",
+        ErrorCommandFailed => "コマンドが失敗しました",
+        ErrorUnknownConfigKey => "不明な設定キーです",
+        ErrorAliasTargetNotFound => "エイリアス先が見つかりません",
+        ErrorInvalidLanguage => "無効な言語です。有効な選択肢: english, japanese, chinese_simplified, chinese_traditional, spanish, german",
+        ErrorNoConfigFileFound => "設定ファイルが見つかりません。'cmdrun init' を実行して作成してください。",
+        ErrorCannotDetermineConfigDir => "設定ディレクトリを特定できません",
+        ErrorCommandExecutionFailed => "コマンドが失敗しました（終了コード",
+        ErrorLocalConfigNotFound => "ローカル設定ファイルが見つかりません",
+        ErrorNoConfigFilesSpecified => "設定ファイルが指定されていません",
+        ErrorCannotSetEnvVariable => "デフォルト環境では環境変数を設定できません",
+        ErrorNoSuitableUnixShell => "適切なUnixシェルが見つかりません",
+        ErrorNoSuitableWindowsShell => "適切なWindowsシェルが見つかりません",
+        ErrorShellNotFound => "シェルが見つかりません",
+        ErrorTemplateAlreadyExists => "テンプレートは既に存在します",
+        ErrorTemplateNotFound => "テンプレートが見つかりません",
+        ErrorCannotRemoveBuiltinTemplate => "組み込みテンプレートは削除できません",
+        ErrorFileNotFound => "ファイルが見つかりません",
+        ErrorFailedToAccessCommandsTable => "コマンドテーブルへのアクセスに失敗しました",
+        ErrorSecurityValidationFailed => "セキュリティ検証に失敗しました",
+        ErrorEditorNotFound => "エディタが見つかりません",
+        ErrorFailedToOpenEditor => "エディタを開けませんでした",
+        ErrorCannotDetermineShell => "シェルを特定できません",
+        ErrorInvalidConfigValue => "無効な設定値です",
+        ErrorFailedToSaveConfiguration => "設定の保存に失敗しました",
+        ErrorCannotDetermineHomeDir => "ホームディレクトリを特定できません",
+        ErrorFailedToParseTemplate => "テンプレートの解析に失敗しました",
+        ErrorHistoryEntryNotFound => "履歴エントリが見つかりません",
+        ErrorFailedToDetermineLocalDataDir => "ローカルデータディレクトリの特定に失敗しました",
+        ErrorFailedToInitializeLogger => "ロガーの初期化に失敗しました",
+        ErrorFailedToReadConfig => "設定ファイルの読み込みに失敗しました",
+        ErrorFailedToParseConfig => "設定ファイルの解析に失敗しました",
+        ErrorInvalidConfigPath => "無効な設定ファイルパスです",
+        ErrorFailedToAcquireReadLock => "読み取りロックの取得に失敗しました",
+        ErrorFailedToAcquireWriteLock => "書き込みロックの取得に失敗しました",
+        ErrorFailedToInitializePlugin => "プラグインの初期化に失敗しました",
+        ErrorFailedToUnloadPlugin => "プラグインのアンロードに失敗しました",
+        ErrorPluginSymbolNotFound => "プラグインシンボルが見つかりません",
+        ErrorFailedToLoadLibrary => "ライブラリの読み込みに失敗しました",
+        ErrorTemplateNameEmpty => "テンプレート名は空にできません",
+        ErrorTemplateDescriptionEmpty => "テンプレートの説明は空にできません",
+        ErrorTemplateNoCommands => "テンプレートには少なくとも1つのコマンドが必要です",
+        ErrorCommandIdInvalidChars => "コマンドIDに無効な文字が含まれています",
+        ErrorCommandFailedWithCode => "コマンドが失敗しました（終了コード",
+        ErrorNoTemplatesAvailable => "利用可能なテンプレートがありません",
+
+        // ====== ヘルプテキスト ======
+        HelpAddCommand => "設定に新しいコマンドを追加",
+        HelpRemoveCommand => "設定からコマンドを削除",
+        HelpEditCommand => "既存のコマンドを編集",
+        HelpListCommands => "利用可能なコマンド一覧を表示",
+        HelpRunCommand => "コマンドを実行",
+        HelpValidateConfig => "設定ファイルを検証",
+        HelpSearchCommand => "キーワードでコマンドを検索",
+        HelpInfoCommand => "コマンドの詳細情報を表示",
+        HelpConfigCommand => "設定を管理",
+        HelpWatchCommand => "ファイルを監視して変更時にコマンドを実行",
+        HelpInitCommand => "新しい設定ファイルを初期化",
+
+        // ====== List コマンド ======
+        ListNoCommandsDefined => "コマンドが定義されていません",
+        ListAvailableCommands => "利用可能なコマンド",
+        ListCommandCount => "個のコマンドが定義されています",
+        ListAliasCount => "個のエイリアスが定義されています",
+
+        // ====== Run コマンド ======
+        RunRunningCommand => "実行中",
+        RunWithParallelDependencies => "（並列依存関係あり）",
+        RunExecutionPlan => "実行計画",
+        RunGroup => "グループ",
+        RunAllCommandsCompleted => "すべてのコマンドが完了しました",
+        RunCompletedIn => "完了時間",
+        RunCommandFailedWithCode => "コマンドが失敗しました（終了コード",
+
+        // ====== Search コマンド ======
+        SearchSearchingFor => "検索中",
+        SearchNoCommandsMatching => "一致するコマンドが見つかりません",
+        SearchFound => "見つかりました",
+        SearchMatchedIn => "一致箇所",
+        SearchUseInfoToSeeDetails => "詳細を見るには cmdrun info <コマンド> を使用してください",
+
+        // ====== Info コマンド ======
+        InfoSelectCommandToView => "詳細を表示するコマンドを選択",
+        InfoBasicInformation => "基本情報",
+        InfoCommandSpecification => "コマンド仕様",
+        InfoExecutionSettings => "実行設定",
+        InfoPlatformSupport => "プラットフォームサポート",
+        InfoConfigurationPaths => "設定ファイルパス",
+        InfoGlobalConfigPath => "グローバル設定",
+        InfoLocalConfigPath => "ローカル設定",
+        InfoActualWorkingDirectory => "実際の作業ディレクトリ",
+        InfoExecutionStatistics => "実行統計",
+        InfoTotalExecutions => "総実行回数",
+        InfoSuccessfulRuns => "成功回数",
+        InfoFailedRuns => "失敗回数",
+        InfoLastRun => "最終実行",
+        InfoAverageDuration => "平均実行時間",
+
+        // ====== Config コマンド ======
+        ConfigSet => "設定しました",
+        ConfigShowingConfiguration => "設定を表示中",
+
+        // ====== Validate コマンド ======
+        ValidateLoadedConfigFrom => "設定を読み込みました",
+        ValidateCheckingCircularDependencies => "循環依存を確認中...",
+        ValidateNoCircularDependenciesFor => "循環依存はありません",
+        ValidateValidatingCommands => "コマンドを検証中",
+        ValidateValidatingAliases => "エイリアスを検証中",
+        ValidateBuildingDependencyGraph => "依存関係グラフを構築中...",
+        ValidateDependencyGraphBuilt => "依存関係グラフを構築しました",
+        ValidateExecutionOrder => "実行順序",
+        ValidateErrors => "エラー",
+        ValidateWarnings => "警告",
+        ValidateInformation => "情報",
+        ValidateFailedWithErrors => "設定の検証に失敗しました（エラー数",
+        ValidateCommandsDefined => "個のコマンドが定義されています",
+        ValidateAliasesDefined => "個のエイリアスが定義されています",
+
+        // ====== Init コマンド ======
+        InitCreated => "作成しました",
+        InitUsing => "使用中",
+        InitNextSteps => "次のステップ",
+        InitStep1EditFile => "{0} を編集してコマンドを定義",
+        InitStep2ListCommands => "cmdrun list で利用可能なコマンド一覧を表示",
+        InitStep3RunCommand => "cmdrun run <名前> でコマンドを実行",
+        InitExampleCommands => "コマンド例",
+        InitTemplateDescription => "テンプレート",
+        InitLanguageSet => "言語を設定しました",
+
+        // ====== Watch コマンド ======
+        WatchConfiguration => "監視設定",
+        WatchCommand => "コマンド",
+        WatchWatching => "監視中",
+        WatchPatterns => "パターン",
+        WatchExclude => "除外",
+        WatchDebounce => "デバウンス",
+        WatchModeStarted => "監視モードを開始しました。Ctrl+C で停止します。",
+        WatchPresCtrlCToStop => "Ctrl+C で停止",
+        WatchModeStoppedByUser => "監視モードをユーザーが停止しました",
+
+        // ====== Remove コマンド ======
+        RemoveRemovalTarget => "削除対象",
+        RemoveType => "タイプ",
+        RemovePlatformSpecific => "プラットフォーム固有",
+
+        // ====== Edit コマンド ======
+        EditParallelExecution => "並列実行",
+        EditConfirmBeforeExecution => "実行前確認",
+        EditNoChanges => "変更はありません",
+
+        // ====== Graph コマンド ======
+        GraphSavedTo => "グラフを保存しました",
+        GraphRenderWith => "レンダリング",
+        GraphViewAt => "表示",
+
+        // ====== Env コマンド ======
+        EnvCurrent => "現在の環境",
+        EnvAvailableEnvironments => "利用可能な環境",
+        EnvSwitchedTo => "環境を切り替えました",
+        EnvCreated => "環境を作成しました",
+        EnvVariableSet => "変数を設定しました",
+        EnvEnvironment => "環境",
+        EnvDescription => "説明",
+        EnvConfigFile => "設定ファイル",
+        EnvEnvironmentVariables => "環境変数",
+        EnvErrorNotFound => "環境が見つかりません",
+        EnvErrorAlreadyExists => "環境は既に存在します",
+        EnvErrorCannotSetDefault => "デフォルト環境には変数を設定できません",
+
+        // ====== Typo検出 ======
+        TypoUnknownCommand => "不明なコマンド",
+        TypoDidYouMean => "もしかして:",
+        TypoSuggestions => "候補",
+        TypoRunHelp => "'cmdrun --help' で利用可能なコマンドを確認できます",
+
+        // ====== その他 ======
+        AddingCommand => "コマンドを追加中",
+        RemovingCommand => "コマンドを削除中",
+        UpdatingCommand => "コマンドを更新中",
+        OpeningEditor => "エディタを起動中",
+        SearchResults => "検索結果",
+        NoCommandsFound => "コマンドが見つかりません",
+        Cancelled => "キャンセルしました",
+        LoadingConfiguration => "設定を読み込み中",
+        CreatingBackup => "バックアップを作成中",
+        MatchingCommands => "件の一致するコマンド",
+        Template => "テンプレート",
+
+        // ====== History コマンド ======
+        HistoryNoEntriesFound => "履歴エントリが見つかりません",
+        HistoryNoCommandsMatching => "一致するコマンドがありません",
+        HistoryExitCode => "終了コード:",
+        HistoryWorkingDir => "作業ディレクトリ:",
+        HistoryTotalCommands => "総コマンド数:",
+
+        // ====== Template コマンド ======
+        TemplateNoTemplatesAvailable => "利用可能なテンプレートがありません",
+        TemplateUserTemplates => "ユーザーテンプレート:",
+
+        // ====== Plugin コマンド ======
+        PluginNoPluginsInstalled => "インストール済みプラグインがありません",
+        PluginMinimumCmdrunVersion => "最小cmdrunバージョン:",
+
+        // ====== Env コマンド表示 ======
+        EnvCurrentEnvironmentLabel => "現在の環境:",
+        EnvAvailableEnvironmentsLabel => "利用可能な環境:",
+        EnvConfigurationFiles => "設定ファイル",
+        EnvBaseConfig => "ベース設定",
+
+        // ====== Completion コマンド ======
+        CompletionInstallationInstructions => "インストール手順:",
+
+        // 未翻訳のキーは英語にフォールバック
+        _ => return None,
+    };
+
+    Some(message)
+}
+
+/// 简体中文のメッセージ（未翻訳のキーは `None`）
+fn chinese_simplified(key: MessageKey) -> Option<&'static str> {
+    use MessageKey::*;
+
+    let message = match key {
+        // ====== 实行状态 ======
+        Running => "运行中",
+        Completed => "已完成",
+        Error => "错误",
+        Warning => "警告",
+        Success => "成功",
+
+        // ====== CRUD操作 ======
+        CommandAdded => "成功添加命令",
+        CommandRemoved => "成功删除命令",
+        CommandUpdated => "成功更新命令",
+
+        // ====== 验证 ======
+        Validating => "正在验证配置",
+        ConfigValid => "配置有效",
+        ValidationFailed => "验证失败",
+        ValidatingConfiguration => "正在验证配置...",
+        ConfigurationIsValid => "配置有效",
+
+        // ====== 交互提示 ======
+        PromptCommandId => "命令ID：",
+        PromptCommand => "命令：",
+        PromptDescription => "描述：",
+        PromptCategory => "分类",
+        PromptTags => "标签（逗号分隔）",
+        PromptConfirm => "确定吗？",
+        PromptSelectCommand => "选择要编辑的命令",
+        PromptWhatToDo => "您想做什么？",
+        PromptEnterNumber => "输入数字",
+        PromptSelectTemplate => "选择模板",
+        PromptSelectLanguage => "选择首选语言",
+        PromptConfirmRunCommand => "运行命令 '{0}'？",
+        ErrorConfirmationRequiresTerminal => "命令 '{0}' 需要确认，但标准输入不是终端（使用 --yes 确认）",
+        WarningConfirmationSkipped => "跳过 '{0}' 的确认（--yes）",
+
+        // ====== 选项 ======
+        OptionYesAdd => "是，添加此命令",
+        OptionNoEdit => "否，重新编辑",
+        OptionCancel => "取消",
+        OptionEnglish => "English (英语)",
+        OptionJapanese => "日本語 (日语)",
+        OptionSpanish => "Español (西班牙语)",
+        OptionGerman => "Deutsch (德语)",
+
+        // ====== 预览·标签 ======
+        LabelPreview => "预览",
+        LabelCommand => "命令",
+        LabelDescription => "描述",
+        LabelCategory => "分类",
+        LabelTags => "标签",
+        LabelCurrentSettings => "当前设置",
+        LabelDependencies => "依赖关系",
+        LabelPlatforms => "平台",
+        LabelWorkingDirectory => "工作目录",
+        LabelEnvironmentVariables => "环境变量",
+        LabelExecutionSettings => "执行设置",
+        LabelParallel => "并行执行",
+        LabelConfirm => "执行前确认",
+        LabelTimeout => "超时",
+        LabelCommandDetails => "命令详情",
+        LabelConfiguration => "配置",
+        LabelLanguage => "语言",
+        LabelStrictMode => "严格模式",
+        LabelBackupCreated => "已创建备份",
+        LabelYes => "是",
+        LabelNo => "否",
+
+        // ====== 警告消息 ======
+        WarningShellBuiltinNoEffect => "⚠ 此shell内置命令在子进程中运行，不会影响当前shell",
+        HintShellFunction => "💡 提示：使用shell函数进行目录导航",
+        HintCdCommand => "   添加到 ~/.cmdrun/shell-functions.sh：",
+
+        // ====== 命令链接提示 ======
+        HintCommandChainingAlternatives => "💡 提示：使用以下替代方法之一：",
+        HintCommandArrayRecommended => "   1. 使用命令数组（推荐安全）:\n      cmd = [\"cd /path\", \"git diff\"]",
+        HintEnableChainingForCommand => "   2. 仅为此命令启用链接（谨慎使用）:\n      allow_chaining = true",
+        HintEnableChainingGlobally => "   3. 全局启用链接（不推荐）:\n      [config]\n      allow_command_chaining = true",
+
+        // ====== 错误消息 ======
+        ErrorEmptyCommandId => "命令ID不能为空",
+        ErrorEmptyCommand => "命令不能为空",
+        ErrorEmptyDescription => "描述不能为空",
+        ErrorCommandNotFound => "找不到命令",
+        ErrorCommandExists => "命令已存在",
+        ErrorConfigNotFound => "找不到配置文件",
+        ErrorInvalidConfig => "无效配置",
+        ErrorInvalidSelection => "无效选择",
+        ErrorSelectionOutOfRange => "选择超出范围",
+        ErrorNoCommandsAvailable => "没有可用命令",
+        ErrorUnknownTemplate => "未知模板",
+        ErrorFileAlreadyExists => "配置文件已存在",
+        ErrorCircularDependency => "检测到循环依赖",
+        ErrorValidationFailed => "验证失败",
+        ErrorCommandFailed => "命令执行失败",
+        ErrorUnknownConfigKey => "未知配置键",
+        ErrorAliasTargetNotFound => "找不到别名目标",
+        ErrorInvalidLanguage => "无效语言。有效选项: english, japanese, chinese_simplified, chinese_traditional, spanish, german",
+        ErrorNoConfigFileFound => "找不到配置文件。运行 'cmdrun init' 创建配置文件。",
+        ErrorCannotDetermineConfigDir => "无法确定配置目录",
+        ErrorCommandExecutionFailed => "命令执行失败，退出代码",
+        ErrorLocalConfigNotFound => "找不到本地配置文件",
+        ErrorNoConfigFilesSpecified => "未指定配置文件",
+        ErrorCannotSetEnvVariable => "无法在默认环境中设置环境变量",
+        ErrorNoSuitableUnixShell => "找不到合适的Unix shell",
+        ErrorNoSuitableWindowsShell => "找不到合适的Windows shell",
+        ErrorShellNotFound => "找不到shell",
+        ErrorTemplateAlreadyExists => "模板已存在",
+        ErrorTemplateNotFound => "找不到模板",
+        ErrorCannotRemoveBuiltinTemplate => "无法删除内置模板",
+        ErrorFileNotFound => "找不到文件",
+        ErrorFailedToAccessCommandsTable => "访问命令表失败",
+        ErrorSecurityValidationFailed => "安全验证失败",
+        ErrorEditorNotFound => "找不到编辑器",
+        ErrorFailedToOpenEditor => "无法打开编辑器",
+        ErrorCannotDetermineShell => "无法确定Shell",
+        ErrorInvalidConfigValue => "配置值无效",
+        ErrorFailedToSaveConfiguration => "保存配置失败",
+        ErrorCannotDetermineHomeDir => "无法确定主目录",
+        ErrorFailedToParseTemplate => "模板解析失败",
+        ErrorHistoryEntryNotFound => "找不到历史记录条目",
+        ErrorFailedToDetermineLocalDataDir => "无法确定本地数据目录",
+        ErrorFailedToInitializeLogger => "日志初始化失败",
+        ErrorFailedToReadConfig => "配置文件读取失败",
+        ErrorFailedToParseConfig => "配置文件解析失败",
+        ErrorInvalidConfigPath => "配置文件路径无效",
+        ErrorFailedToAcquireReadLock => "获取读锁失败",
+        ErrorFailedToAcquireWriteLock => "获取写锁失败",
+        ErrorFailedToInitializePlugin => "插件初始化失败",
+        ErrorFailedToUnloadPlugin => "插件卸载失败",
+        ErrorPluginSymbolNotFound => "找不到插件符号",
+        ErrorFailedToLoadLibrary => "库加载失败",
+        ErrorTemplateNameEmpty => "模板名称不能为空",
+        ErrorTemplateDescriptionEmpty => "模板描述不能为空",
+        ErrorTemplateNoCommands => "模板必须包含至少一个命令",
+        ErrorCommandIdInvalidChars => "命令ID包含无效字符",
+        ErrorCommandFailedWithCode => "命令执行失败，退出代码",
+        ErrorNoTemplatesAvailable => "没有可用模板",
+
+        // ====== 帮助文本 ======
+        HelpAddCommand => "向配置中添加新命令",
+        HelpRemoveCommand => "从配置中删除命令",
+        HelpEditCommand => "编辑现有命令",
+        HelpListCommands => "列出所有可用命令",
+        HelpRunCommand => "运行命令",
+        HelpValidateConfig => "验证配置文件",
+        HelpSearchCommand => "按关键字搜索命令",
+        HelpInfoCommand => "显示命令详细信息",
+        HelpConfigCommand => "管理配置设置",
+        HelpWatchCommand => "监视文件并在更改时运行命令",
+        HelpInitCommand => "初始化新配置文件",
+
+        // ====== List 命令 ======
+        ListNoCommandsDefined => "未定义命令",
+        ListAvailableCommands => "可用命令",
+        ListCommandCount => "个已定义命令",
+        ListAliasCount => "个已定义别名",
+
+        // ====== Run 命令 ======
+        RunRunningCommand => "运行中",
+        RunWithParallelDependencies => "（含并行依赖）",
+        RunExecutionPlan => "执行计划",
+        RunGroup => "组",
+        RunAllCommandsCompleted => "所有命令已完成",
+        RunCompletedIn => "完成时间",
+        RunCommandFailedWithCode => "命令执行失败，退出代码",
+
+        // ====== Search 命令 ======
+        SearchSearchingFor => "搜索中",
+        SearchNoCommandsMatching => "没有匹配的命令",
+        SearchFound => "找到",
+        SearchMatchedIn => "匹配位置",
+        SearchUseInfoToSeeDetails => "使用 cmdrun info <命令> 查看详情",
+
+        // ====== Info 命令 ======
+        InfoSelectCommandToView => "选择要查看详情的命令",
+        InfoBasicInformation => "基本信息",
+        InfoCommandSpecification => "命令规范",
+        InfoExecutionSettings => "执行设置",
+        InfoPlatformSupport => "平台支持",
+        InfoConfigurationPaths => "配置文件路径",
+        InfoGlobalConfigPath => "全局配置",
+        InfoLocalConfigPath => "本地配置",
+        InfoActualWorkingDirectory => "实际工作目录",
+        InfoExecutionStatistics => "执行统计",
+        InfoTotalExecutions => "总执行次数",
+        InfoSuccessfulRuns => "成功次数",
+        InfoFailedRuns => "失败次数",
+        InfoLastRun => "最后执行",
+        InfoAverageDuration => "平均执行时间",
+
+        // ====== Config 命令 ======
+        ConfigSet => "已设置",
+        ConfigShowingConfiguration => "显示配置",
+
+        // ====== Validate 命令 ======
+        ValidateLoadedConfigFrom => "已加载配置文件",
+        ValidateCheckingCircularDependencies => "正在检查循环依赖...",
+        ValidateNoCircularDependenciesFor => "无循环依赖",
+        ValidateValidatingCommands => "正在验证命令",
+        ValidateValidatingAliases => "正在验证别名",
+        ValidateBuildingDependencyGraph => "正在构建依赖关系图...",
+        ValidateDependencyGraphBuilt => "依赖关系图构建成功",
+        ValidateExecutionOrder => "执行顺序",
+        ValidateErrors => "错误",
+        ValidateWarnings => "警告",
+        ValidateInformation => "信息",
+        ValidateFailedWithErrors => "配置验证失败，错误数",
+        ValidateCommandsDefined => "个已定义命令",
+        ValidateAliasesDefined => "个已定义别名",
+
+        // ====== Init 命令 ======
+        InitCreated => "已创建",
+        InitUsing => "使用中",
+        InitNextSteps => "下一步",
+        InitStep1EditFile => "编辑 {0} 来定义您的命令",
+        InitStep2ListCommands => "运行 cmdrun list 列出可用命令",
+        InitStep3RunCommand => "运行 cmdrun run <名称> 执行命令",
+        InitExampleCommands => "示例命令",
+        InitTemplateDescription => "模板",
+        InitLanguageSet => "语言已设置为",
+
+        // ====== Watch 命令 ======
+        WatchConfiguration => "监视配置",
+        WatchCommand => "命令",
+        WatchWatching => "监视中",
+        WatchPatterns => "模式",
+        WatchExclude => "排除",
+        WatchDebounce => "防抖",
+        WatchModeStarted => "监视模式已启动。按 Ctrl+C 停止。",
+        WatchPresCtrlCToStop => "按 Ctrl+C 停止",
+        WatchModeStoppedByUser => "用户已停止监视模式",
+
+        // ====== Remove 命令 ======
+        RemoveRemovalTarget => "删除目标",
+        RemoveType => "类型",
+        RemovePlatformSpecific => "平台特定",
+
+        // ====== Edit 命令 ======
+        EditParallelExecution => "并行执行",
+        EditConfirmBeforeExecution => "执行前确认",
+        EditNoChanges => "没有更改",
+
+        // ====== Graph 命令 ======
+        GraphSavedTo => "图表已保存至",
+        GraphRenderWith => "渲染工具",
+        GraphViewAt => "查看位置",
+
+        // ====== Env 命令 ======
+        EnvCurrent => "当前环境",
+        EnvAvailableEnvironments => "可用环境",
+        EnvSwitchedTo => "已切换到环境",
+        EnvCreated => "已创建环境",
+        EnvVariableSet => "已设置变量",
+        EnvEnvironment => "环境",
+        EnvDescription => "描述",
+        EnvConfigFile => "配置文件",
+        EnvEnvironmentVariables => "环境变量",
+        EnvErrorNotFound => "找不到环境",
+        EnvErrorAlreadyExists => "环境已存在",
+        EnvErrorCannotSetDefault => "无法为'default'环境设置变量",
+
+        // ====== Typo检测 ======
+        TypoUnknownCommand => "未知命令",
+        TypoDidYouMean => "您是否想输入:",
+        TypoSuggestions => "建议",
+        TypoRunHelp => "运行 'cmdrun --help' 查看可用命令",
+
+        // ====== 其他 ======
+        AddingCommand => "正在添加命令",
+        RemovingCommand => "正在删除命令",
+        UpdatingCommand => "正在更新命令",
+        OpeningEditor => "正在打开编辑器",
+        SearchResults => "搜索结果",
+        NoCommandsFound => "找不到命令",
+        Cancelled => "已取消",
+        LoadingConfiguration => "正在加载配置",
+        CreatingBackup => "正在创建备份",
+        MatchingCommands => "个匹配命令",
+        Template => "模板",
+
+        // ====== History 命令 ======
+        HistoryNoEntriesFound => "未找到历史记录",
+        HistoryNoCommandsMatching => "没有匹配的命令",
+        HistoryExitCode => "退出代码:",
+        HistoryWorkingDir => "工作目录:",
+        HistoryTotalCommands => "总命令数:",
+
+        // ====== Template 命令 ======
+        TemplateNoTemplatesAvailable => "没有可用模板",
+        TemplateUserTemplates => "用户模板:",
+
+        // ====== Plugin 命令 ======
+        PluginNoPluginsInstalled => "未安装插件",
+        PluginMinimumCmdrunVersion => "最低cmdrun版本:",
+
+        // ====== Env 命令显示 ======
+        EnvCurrentEnvironmentLabel => "当前环境:",
+        EnvAvailableEnvironmentsLabel => "可用环境:",
+        EnvConfigurationFiles => "配置文件",
+        EnvBaseConfig => "基础配置",
+
+        // ====== Completion 命令 ======
+        CompletionInstallationInstructions => "安装说明:",
+
+        // 未翻訳のキーは英語にフォールバック
+        _ => return None,
+    };
+
+    Some(message)
+}
+
+/// 繁體中文のメッセージ（未翻訳のキーは `None`）
+fn chinese_traditional(key: MessageKey) -> Option<&'static str> {
+    use MessageKey::*;
+
+    let message = match key {
+        // ====== 執行狀態 ======
+        Running => "執行中",
+        Completed => "已完成",
+        Error => "錯誤",
+        Warning => "警告",
+        Success => "成功",
+
+        // ====== CRUD操作 ======
+        CommandAdded => "成功新增命令",
+        CommandRemoved => "成功刪除命令",
+        CommandUpdated => "成功更新命令",
+
+        // ====== 驗證 ======
+        Validating => "正在驗證配置",
+        ConfigValid => "配置有效",
+        ValidationFailed => "驗證失敗",
+        ValidatingConfiguration => "正在驗證配置...",
+        ConfigurationIsValid => "配置有效",
+
+        // ====== 互動提示 ======
+        PromptCommandId => "命令ID：",
+        PromptCommand => "命令：",
+        PromptDescription => "描述：",
+        PromptCategory => "分類",
+        PromptTags => "標籤（逗號分隔）",
+        PromptConfirm => "您確定嗎？",
+        PromptSelectCommand => "選擇要編輯的命令",
+        PromptWhatToDo => "您想做什麼？",
+        PromptEnterNumber => "輸入數字",
+        PromptSelectTemplate => "選擇範本",
+        PromptSelectLanguage => "選擇偏好語言",
+        PromptConfirmRunCommand => "執行命令 '{0}'？",
+        ErrorConfirmationRequiresTerminal => "命令 '{0}' 需要確認，但標準輸入不是終端（使用 --yes 確認）",
+        WarningConfirmationSkipped => "略過 '{0}' 的確認（--yes）",
+
+        // ====== 選項 ======
+        OptionYesAdd => "是，新增此命令",
+        OptionNoEdit => "否，重新編輯",
+        OptionCancel => "取消",
+        OptionEnglish => "English (英語)",
+        OptionJapanese => "日本語 (日語)",
+        OptionSpanish => "Español (西班牙語)",
+        OptionGerman => "Deutsch (德語)",
+
+        // ====== 預覽·標籤 ======
+        LabelPreview => "預覽",
+        LabelCommand => "命令",
+        LabelDescription => "描述",
+        LabelCategory => "分類",
+        LabelTags => "標籤",
+        LabelCurrentSettings => "目前設定",
+        LabelDependencies => "相依性",
+        LabelPlatforms => "平台",
+        LabelWorkingDirectory => "工作目錄",
+        LabelEnvironmentVariables => "環境變數",
+        LabelExecutionSettings => "執行設定",
+        LabelParallel => "並行執行",
+        LabelConfirm => "執行前確認",
+        LabelTimeout => "逾時",
+        LabelCommandDetails => "命令詳情",
+        LabelConfiguration => "配置",
+        LabelLanguage => "語言",
+        LabelStrictMode => "嚴格模式",
+        LabelBackupCreated => "已建立備份",
+        LabelYes => "是",
+        LabelNo => "否",
+
+        // ====== 警告訊息 ======
+        WarningShellBuiltinNoEffect => "⚠ 此shell內建命令在子處理序中執行，不會影響目前shell",
+        HintShellFunction => "💡 提示：使用shell函式進行目錄導覽",
+        HintCdCommand => "   新增至 ~/.cmdrun/shell-functions.sh：",
+
+        // ====== 命令鏈接提示 ======
+        HintCommandChainingAlternatives => "💡 提示：使用以下替代方法之一：",
+        HintCommandArrayRecommended => "   1. 使用命令陣列（建議安全）:\n      cmd = [\"cd /path\", \"git diff\"]",
+        HintEnableChainingForCommand => "   2. 僅為此命令啟用鏈接（謹慎使用）:\n      allow_chaining = true",
+        HintEnableChainingGlobally => "   3. 全域啟用鏈接（不建議）:\n      [config]\n      allow_command_chaining = true",
+
+        // ====== 錯誤訊息 ======
+        ErrorEmptyCommandId => "命令ID不能為空",
+        ErrorEmptyCommand => "命令不能為空",
+        ErrorEmptyDescription => "描述不能為空",
+        ErrorCommandNotFound => "找不到命令",
+        ErrorCommandExists => "命令已存在",
+        ErrorConfigNotFound => "找不到配置檔案",
+        ErrorInvalidConfig => "無效配置",
+        ErrorInvalidSelection => "無效選擇",
+        ErrorSelectionOutOfRange => "選擇超出範圍",
+        ErrorNoCommandsAvailable => "沒有可用命令",
+        ErrorUnknownTemplate => "未知範本",
+        ErrorFileAlreadyExists => "配置檔案已存在",
+        ErrorCircularDependency => "偵測到循環相依",
+        ErrorValidationFailed => "驗證失敗",
+        ErrorCommandFailed => "命令執行失敗",
+        ErrorUnknownConfigKey => "未知配置鍵",
+        ErrorAliasTargetNotFound => "找不到別名目標",
+        ErrorInvalidLanguage => "無效語言。有效選項: english, japanese, chinese_simplified, chinese_traditional, spanish, german",
+        ErrorNoConfigFileFound => "找不到配置檔案。執行 'cmdrun init' 建立配置檔案。",
+        ErrorCannotDetermineConfigDir => "無法確定配置目錄",
+        ErrorCommandExecutionFailed => "命令執行失敗，結束代碼",
+        ErrorLocalConfigNotFound => "找不到本地配置檔案",
+        ErrorNoConfigFilesSpecified => "未指定配置檔案",
+        ErrorCannotSetEnvVariable => "無法在預設環境中設定環境變數",
+        ErrorNoSuitableUnixShell => "找不到合適的Unix shell",
+        ErrorNoSuitableWindowsShell => "找不到合適的Windows shell",
+        ErrorShellNotFound => "找不到shell",
+        ErrorTemplateAlreadyExists => "範本已存在",
+        ErrorTemplateNotFound => "找不到範本",
+        ErrorCannotRemoveBuiltinTemplate => "無法刪除內建範本",
+        ErrorFileNotFound => "找不到檔案",
+        ErrorFailedToAccessCommandsTable => "存取命令表失敗",
+        ErrorSecurityValidationFailed => "安全驗證失敗",
+        ErrorEditorNotFound => "找不到編輯器",
+        ErrorFailedToOpenEditor => "無法開啟編輯器",
+        ErrorCannotDetermineShell => "無法確定Shell",
+        ErrorInvalidConfigValue => "設定值無效",
+        ErrorFailedToSaveConfiguration => "儲存設定失敗",
+        ErrorCannotDetermineHomeDir => "無法確定主目錄",
+        ErrorFailedToParseTemplate => "範本解析失敗",
+        ErrorHistoryEntryNotFound => "找不到歷史記錄條目",
+        ErrorFailedToDetermineLocalDataDir => "無法確定本機資料目錄",
+        ErrorFailedToInitializeLogger => "日誌初始化失敗",
+        ErrorFailedToReadConfig => "配置檔案讀取失敗",
+        ErrorFailedToParseConfig => "配置檔案解析失敗",
+        ErrorInvalidConfigPath => "配置檔案路徑無效",
+        ErrorFailedToAcquireReadLock => "取得讀取鎖失敗",
+        ErrorFailedToAcquireWriteLock => "取得寫入鎖失敗",
+        ErrorFailedToInitializePlugin => "外掛程式初始化失敗",
+        ErrorFailedToUnloadPlugin => "外掛程式卸載失敗",
+        ErrorPluginSymbolNotFound => "找不到外掛程式符號",
+        ErrorFailedToLoadLibrary => "程式庫載入失敗",
+        ErrorTemplateNameEmpty => "範本名稱不能為空",
+        ErrorTemplateDescriptionEmpty => "範本描述不能為空",
+        ErrorTemplateNoCommands => "範本必須包含至少一個命令",
+        ErrorCommandIdInvalidChars => "命令ID包含無效字元",
+        ErrorCommandFailedWithCode => "命令執行失敗，結束代碼",
+        ErrorNoTemplatesAvailable => "沒有可用範本",
+
+        // ====== 說明文字 ======
+        HelpAddCommand => "向配置中新增命令",
+        HelpRemoveCommand => "從配置中刪除命令",
+        HelpEditCommand => "編輯現有命令",
+        HelpListCommands => "列出所有可用命令",
+        HelpRunCommand => "執行命令",
+        HelpValidateConfig => "驗證配置檔案",
+        HelpSearchCommand => "按關鍵字搜尋命令",
+        HelpInfoCommand => "顯示命令詳細資訊",
+        HelpConfigCommand => "管理配置設定",
+        HelpWatchCommand => "監視檔案並在變更時執行命令",
+        HelpInitCommand => "初始化新配置檔案",
+
+        // ====== List 命令 ======
+        ListNoCommandsDefined => "未定義命令",
+        ListAvailableCommands => "可用命令",
+        ListCommandCount => "個已定義命令",
+        ListAliasCount => "個已定義別名",
+
+        // ====== Run 命令 ======
+        RunRunningCommand => "執行中",
+        RunWithParallelDependencies => "（含並行相依）",
+        RunExecutionPlan => "執行計畫",
+        RunGroup => "群組",
+        RunAllCommandsCompleted => "所有命令已完成",
+        RunCompletedIn => "完成時間",
+        RunCommandFailedWithCode => "命令執行失敗，結束代碼",
+
+        // ====== Search 命令 ======
+        SearchSearchingFor => "搜尋中",
+        SearchNoCommandsMatching => "沒有符合的命令",
+        SearchFound => "找到",
+        SearchMatchedIn => "符合位置",
+        SearchUseInfoToSeeDetails => "使用 cmdrun info <命令> 檢視詳情",
+
+        // ====== Info 命令 ======
+        InfoSelectCommandToView => "選擇要檢視詳情的命令",
+        InfoBasicInformation => "基本資訊",
+        InfoCommandSpecification => "命令規範",
+        InfoExecutionSettings => "執行設定",
+        InfoPlatformSupport => "平台支援",
+        InfoConfigurationPaths => "配置檔案路徑",
+        InfoGlobalConfigPath => "全域配置",
+        InfoLocalConfigPath => "本機配置",
+        InfoActualWorkingDirectory => "實際工作目錄",
+        InfoExecutionStatistics => "執行統計",
+        InfoTotalExecutions => "總執行次數",
+        InfoSuccessfulRuns => "成功次數",
+        InfoFailedRuns => "失敗次數",
+        InfoLastRun => "最後執行",
+        InfoAverageDuration => "平均執行時間",
+
+        // ====== Config 命令 ======
+        ConfigSet => "已設定",
+        ConfigShowingConfiguration => "顯示配置",
+
+        // ====== Validate 命令 ======
+        ValidateLoadedConfigFrom => "已載入配置檔案",
+        ValidateCheckingCircularDependencies => "正在檢查循環相依...",
+        ValidateNoCircularDependenciesFor => "無循環相依",
+        ValidateValidatingCommands => "正在驗證命令",
+        ValidateValidatingAliases => "正在驗證別名",
+        ValidateBuildingDependencyGraph => "正在建立相依性圖...",
+        ValidateDependencyGraphBuilt => "相依性圖建立成功",
+        ValidateExecutionOrder => "執行順序",
+        ValidateErrors => "錯誤",
+        ValidateWarnings => "警告",
+        ValidateInformation => "資訊",
+        ValidateFailedWithErrors => "配置驗證失敗，錯誤數",
+        ValidateCommandsDefined => "個已定義命令",
+        ValidateAliasesDefined => "個已定義別名",
+
+        // ====== Init 命令 ======
+        InitCreated => "已建立",
+        InitUsing => "使用中",
+        InitNextSteps => "下一步",
+        InitStep1EditFile => "編輯 {0} 來定義您的命令",
+        InitStep2ListCommands => "執行 cmdrun list 列出可用命令",
+        InitStep3RunCommand => "執行 cmdrun run <名稱> 執行命令",
+        InitExampleCommands => "範例命令",
+        InitTemplateDescription => "範本",
+        InitLanguageSet => "語言已設定為",
+
+        // ====== Watch 命令 ======
+        WatchConfiguration => "監視配置",
+        WatchCommand => "命令",
+        WatchWatching => "監視中",
+        WatchPatterns => "模式",
+        WatchExclude => "排除",
+        WatchDebounce => "防抖",
+        WatchModeStarted => "監視模式已啟動。按 Ctrl+C 停止。",
+        WatchPresCtrlCToStop => "按 Ctrl+C 停止",
+        WatchModeStoppedByUser => "使用者已停止監視模式",
+
+        // ====== Remove 命令 ======
+        RemoveRemovalTarget => "刪除目標",
+        RemoveType => "類型",
+        RemovePlatformSpecific => "平台特定",
+
+        // ====== Edit 命令 ======
+        EditParallelExecution => "並行執行",
+        EditConfirmBeforeExecution => "執行前確認",
+        EditNoChanges => "沒有變更",
+
+        // ====== Graph 命令 ======
+        GraphSavedTo => "圖表已儲存至",
+        GraphRenderWith => "算繪工具",
+        GraphViewAt => "檢視位置",
+
+        // ====== Env 命令 ======
+        EnvCurrent => "目前環境",
+        EnvAvailableEnvironments => "可用環境",
+        EnvSwitchedTo => "已切換至環境",
+        EnvCreated => "已建立環境",
+        EnvVariableSet => "已設定變數",
+        EnvEnvironment => "環境",
+        EnvDescription => "描述",
+        EnvConfigFile => "配置檔案",
+        EnvEnvironmentVariables => "環境變數",
+        EnvErrorNotFound => "找不到環境",
+        EnvErrorAlreadyExists => "環境已存在",
+        EnvErrorCannotSetDefault => "無法為'default'環境設定變數",
+
+        // ====== Typo檢測 ======
+        TypoUnknownCommand => "未知命令",
+        TypoDidYouMean => "您是否想輸入:",
+        TypoSuggestions => "建議",
+        TypoRunHelp => "執行 'cmdrun --help' 檢視可用命令",
+
+        // ====== 其他 ======
+        AddingCommand => "正在新增命令",
+        RemovingCommand => "正在刪除命令",
+        UpdatingCommand => "正在更新命令",
+        OpeningEditor => "正在開啟編輯器",
+        SearchResults => "搜尋結果",
+        NoCommandsFound => "找不到命令",
+        Cancelled => "已取消",
+        LoadingConfiguration => "正在載入配置",
+        CreatingBackup => "正在建立備份",
+        MatchingCommands => "個符合命令",
+        Template => "範本",
+
+        // ====== History 命令 ======
+        HistoryNoEntriesFound => "未找到歷史記錄",
+        HistoryNoCommandsMatching => "沒有符合的命令",
+        HistoryExitCode => "結束代碼:",
+        HistoryWorkingDir => "工作目錄:",
+        HistoryTotalCommands => "總命令數:",
+
+        // ====== Template 命令 ======
+        TemplateNoTemplatesAvailable => "沒有可用範本",
+        TemplateUserTemplates => "使用者範本:",
+
+        // ====== Plugin 命令 ======
+        PluginNoPluginsInstalled => "未安裝外掛程式",
+        PluginMinimumCmdrunVersion => "最低cmdrun版本:",
+
+        // ====== Env 命令顯示 ======
+        EnvCurrentEnvironmentLabel => "目前環境:",
+        EnvAvailableEnvironmentsLabel => "可用環境:",
+        EnvConfigurationFiles => "配置檔案",
+        EnvBaseConfig => "基礎配置",
+
+        // ====== Completion 命令 ======
+        CompletionInstallationInstructions => "安裝說明:",
+
+        // 未翻訳のキーは英語にフォールバック
+        _ => return None,
+    };
+
+    Some(message)
+}
+
+/// スペイン語のメッセージ（未翻訳のキーは `None`）
+fn spanish(key: MessageKey) -> Option<&'static str> {
+    use MessageKey::*;
+
+    let message = match key {
+        // ====== 実行状態 ======
+        Running => "Ejecutando",
+        Completed => "Completado",
+        Warning => "Advertencia",
+        Success => "Éxito",
+
+        // ====== CRUD操作 ======
+        CommandAdded => "Comando añadido correctamente",
+        CommandRemoved => "Comando eliminado correctamente",
+        CommandUpdated => "Comando actualizado correctamente",
+
+        // ====== 検証 ======
+        Validating => "Validando la configuración",
+        ConfigValid => "La configuración es válida",
+        ValidationFailed => "La validación ha fallado",
+        ValidatingConfiguration => "Validando la configuración...",
+        ConfigurationIsValid => "La configuración es válida",
+
+        // ====== 対話プロンプト ======
+        PromptCommandId => "ID del comando:",
+        PromptCommand => "Comando:",
+        PromptDescription => "Descripción:",
+        PromptCategory => "Categoría",
+        PromptTags => "Etiquetas (separadas por comas)",
+        PromptConfirm => "¿Está seguro?",
+        PromptSelectCommand => "Seleccione el comando a editar",
+        PromptWhatToDo => "¿Qué desea hacer?",
+        PromptEnterNumber => "Introduzca un número",
+        PromptSelectTemplate => "Seleccione una plantilla",
+        PromptSelectLanguage => "Seleccione su idioma preferido",
+        PromptConfirmRunCommand => "¿Ejecutar el comando '{0}'?",
+        ErrorConfirmationRequiresTerminal => "El comando '{0}' requiere confirmación, pero stdin no es una terminal (use --yes para confirmar)",
+        WarningConfirmationSkipped => "Omitiendo la confirmación de '{0}' (--yes)",
+
+        // ====== 選択肢 ======
+        OptionYesAdd => "Sí, añadir este comando",
+        OptionNoEdit => "No, volver a editar",
+        OptionCancel => "Cancelar",
+        OptionEnglish => "English (inglés)",
+        OptionJapanese => "日本語 (japonés)",
+        OptionSpanish => "Español",
+        OptionGerman => "Deutsch (alemán)",
+
+        // ====== プレビュー・ラベル ======
+        LabelPreview => "Vista previa",
+        LabelCommand => "Comando",
+        LabelDescription => "Descripción",
+        LabelCategory => "Categoría",
+        LabelTags => "Etiquetas",
+        LabelCurrentSettings => "Configuración actual",
+        LabelDependencies => "Dependencias",
+        LabelPlatforms => "Plataformas",
+        LabelWorkingDirectory => "Directorio de trabajo",
+        LabelEnvironmentVariables => "Variables de entorno",
+        LabelExecutionSettings => "Ajustes de ejecución",
+        LabelParallel => "Paralelo",
+        LabelConfirm => "Confirmar",
+        LabelTimeout => "Tiempo límite",
+        LabelCommandDetails => "Detalles del comando",
+        LabelConfiguration => "Configuración",
+        LabelLanguage => "idioma",
+        LabelStrictMode => "modo estricto",
+        LabelBackupCreated => "Copia de seguridad creada",
+        LabelYes => "sí",
+
+        // ====== 警告メッセージ ======
+        WarningShellBuiltinNoEffect => "⚠ Este comando interno del shell se ejecuta en un subproceso y no afectará al shell actual",
+        HintShellFunction => "💡 Sugerencia: use funciones de shell para navegar entre directorios",
+        HintCdCommand => "   Añada a ~/.cmdrun/shell-functions.sh:",
+
+        // ====== コマンド連結ヒント ======
+        HintCommandChainingAlternatives => "💡 Sugerencia: use una de estas alternativas:",
+        HintCommandArrayRecommended => "   1. Use un array de comandos (recomendado por seguridad):\n      cmd = [\"cd /path\", \"git diff\"]",
+        HintEnableChainingForCommand => "   2. Habilite el encadenamiento para este comando (con precaución):\n      allow_chaining = true",
+        HintEnableChainingGlobally => "   3. Habilite el encadenamiento globalmente (no recomendado):\n      [config]\n      allow_command_chaining = true",
+
+        // ====== エラーメッセージ ======
+        ErrorEmptyCommandId => "El ID del comando no puede estar vacío",
+        ErrorEmptyCommand => "El comando no puede estar vacío",
+        ErrorEmptyDescription => "La descripción no puede estar vacía",
+        ErrorCommandNotFound => "Comando no encontrado",
+        ErrorCommandExists => "El comando ya existe",
+        ErrorConfigNotFound => "Archivo de configuración no encontrado",
+        ErrorInvalidConfig => "Configuración no válida",
+        ErrorInvalidSelection => "Selección no válida",
+        ErrorSelectionOutOfRange => "Selección fuera de rango",
+        ErrorNoCommandsAvailable => "No hay comandos disponibles",
+        ErrorUnknownTemplate => "Plantilla desconocida",
+        ErrorFileAlreadyExists => "El archivo de configuración ya existe",
+        ErrorCircularDependency => "Se detectó una dependencia circular",
+        ErrorValidationFailed => "La validación ha fallado",
+        ErrorCommandFailed => "El comando ha fallado",
+        ErrorUnknownConfigKey => "Clave de configuración desconocida",
+        ErrorAliasTargetNotFound => "No se encontró el destino del alias",
+        ErrorInvalidLanguage => "Idioma no válido. Opciones válidas: english, japanese, chinese_simplified, chinese_traditional, spanish, german",
+        ErrorNoConfigFileFound => "No se encontró ningún archivo de configuración. Ejecute 'cmdrun init' para crear uno.",
+        ErrorCannotDetermineConfigDir => "No se puede determinar el directorio de configuración",
+        ErrorCommandExecutionFailed => "El comando falló con el código de salida",
+        ErrorLocalConfigNotFound => "Archivo de configuración local no encontrado",
+        ErrorNoConfigFilesSpecified => "No se especificaron archivos de configuración",
+        ErrorCannotSetEnvVariable => "No se pueden definir variables en el entorno predeterminado",
+        ErrorNoSuitableUnixShell => "No se encontró un shell de Unix adecuado",
+        ErrorNoSuitableWindowsShell => "No se encontró un shell de Windows adecuado",
+        ErrorShellNotFound => "No se encontró la shell",
+        ErrorTemplateAlreadyExists => "La plantilla ya existe",
+        ErrorTemplateNotFound => "Plantilla no encontrada",
+        ErrorCannotRemoveBuiltinTemplate => "No se puede eliminar una plantilla integrada",
+        ErrorFileNotFound => "Archivo no encontrado",
+        ErrorFailedToAccessCommandsTable => "No se pudo acceder a la tabla de comandos",
+        ErrorSecurityValidationFailed => "La validación de seguridad ha fallado",
+        ErrorEditorNotFound => "Editor no encontrado",
+        ErrorFailedToOpenEditor => "No se pudo abrir el editor",
+        ErrorCannotDetermineShell => "No se puede determinar el shell",
+        ErrorInvalidConfigValue => "Valor de configuración no válido",
+        ErrorFailedToSaveConfiguration => "No se pudo guardar la configuración",
+        ErrorCannotDetermineHomeDir => "No se pudo determinar el directorio personal",
+        ErrorFailedToParseTemplate => "No se pudo analizar la plantilla",
+        ErrorHistoryEntryNotFound => "Entrada del historial no encontrada",
+        ErrorFailedToDetermineLocalDataDir => "No se pudo determinar el directorio de datos local",
+        ErrorFailedToInitializeLogger => "No se pudo inicializar el registro",
+        ErrorFailedToReadConfig => "No se pudo leer la configuración",
+        ErrorFailedToParseConfig => "No se pudo analizar la configuración",
+        ErrorInvalidConfigPath => "Ruta de configuración no válida",
+        ErrorFailedToAcquireReadLock => "No se pudo obtener el bloqueo de lectura",
+        ErrorFailedToAcquireWriteLock => "No se pudo obtener el bloqueo de escritura",
+        ErrorFailedToInitializePlugin => "No se pudo inicializar el plugin",
+        ErrorFailedToUnloadPlugin => "No se pudo descargar el plugin",
+        ErrorPluginSymbolNotFound => "Símbolo del plugin no encontrado",
+        ErrorFailedToLoadLibrary => "No se pudo cargar la biblioteca",
+        ErrorTemplateNameEmpty => "El nombre de la plantilla no puede estar vacío",
+        ErrorTemplateDescriptionEmpty => "La descripción de la plantilla no puede estar vacía",
+        ErrorTemplateNoCommands => "La plantilla debe contener al menos un comando",
+        ErrorCommandIdInvalidChars => "El ID del comando contiene caracteres no válidos",
+        ErrorCommandFailedWithCode => "El comando falló con el código de salida",
+        ErrorNoTemplatesAvailable => "No hay plantillas disponibles",
+
+        // ====== ヘルプテキスト ======
+        HelpAddCommand => "Añadir un nuevo comando a la configuración",
+        HelpRemoveCommand => "Eliminar un comando de la configuración",
+        HelpEditCommand => "Editar un comando existente",
+        HelpListCommands => "Listar todos los comandos disponibles",
+        HelpRunCommand => "Ejecutar un comando",
+        HelpValidateConfig => "Validar el archivo de configuración",
+        HelpSearchCommand => "Buscar comandos por palabra clave",
+        HelpInfoCommand => "Mostrar información detallada del comando",
+        HelpConfigCommand => "Gestionar los ajustes de configuración",
+        HelpWatchCommand => "Vigilar archivos y ejecutar un comando al cambiar",
+        HelpInitCommand => "Inicializar un nuevo archivo de configuración",
+
+        // ====== List コマンド ======
+        ListNoCommandsDefined => "No hay comandos definidos",
+        ListAvailableCommands => "Comandos disponibles",
+        ListCommandCount => "comandos definidos",
+        ListAliasCount => "alias definidos",
+
+        // ====== Run コマンド ======
+        RunRunningCommand => "Ejecutando",
+        RunWithParallelDependencies => "con dependencias en paralelo",
+        RunExecutionPlan => "Plan de ejecución",
+        RunGroup => "Grupo",
+        RunAllCommandsCompleted => "Todos los comandos completados en",
+        RunCompletedIn => "Completado en",
+        RunCommandFailedWithCode => "El comando falló con el código de salida",
+
+        // ====== Search コマンド ======
+        SearchSearchingFor => "Buscando",
+        SearchNoCommandsMatching => "Ningún comando coincide con",
+        SearchFound => "Encontrados",
+        SearchMatchedIn => "Coincidencia en",
+        SearchUseInfoToSeeDetails => "Use cmdrun info <comando> para ver los detalles",
+
+        // ====== Info コマンド ======
+        InfoSelectCommandToView => "Seleccione el comando para ver los detalles",
+        InfoBasicInformation => "Información básica",
+        InfoCommandSpecification => "Especificación del comando",
+        InfoExecutionSettings => "Ajustes de ejecución",
+        InfoPlatformSupport => "Plataformas compatibles",
+        InfoConfigurationPaths => "Rutas de configuración",
+        InfoGlobalConfigPath => "Configuración global",
+        InfoLocalConfigPath => "Configuración local",
+        InfoActualWorkingDirectory => "Directorio de trabajo real",
+        InfoExecutionStatistics => "Estadísticas de ejecución",
+        InfoTotalExecutions => "Ejecuciones totales",
+        InfoSuccessfulRuns => "Ejecuciones correctas",
+        InfoFailedRuns => "Ejecuciones fallidas",
+        InfoLastRun => "Última ejecución",
+        InfoAverageDuration => "Duración media",
+
+        // ====== Config コマンド ======
+        ConfigSet => "Establecido",
+        ConfigShowingConfiguration => "Mostrando la configuración",
+
+        // ====== Validate コマンド ======
+        ValidateLoadedConfigFrom => "Configuración cargada desde",
+        ValidateCheckingCircularDependencies => "Comprobando dependencias circulares...",
+        ValidateNoCircularDependenciesFor => "Sin dependencias circulares para",
+        ValidateValidatingCommands => "Validando comandos",
+        ValidateValidatingAliases => "Validando alias",
+        ValidateBuildingDependencyGraph => "Construyendo el grafo de dependencias...",
+        ValidateDependencyGraphBuilt => "Grafo de dependencias construido correctamente",
+        ValidateExecutionOrder => "Orden de ejecución",
+        ValidateErrors => "Errores",
+        ValidateWarnings => "Advertencias",
+        ValidateInformation => "Información",
+        ValidateFailedWithErrors => "La validación de la configuración falló con",
+        ValidateCommandsDefined => "comandos definidos",
+        ValidateAliasesDefined => "alias definidos",
+
+        // ====== Init コマンド ======
+        InitCreated => "Creado",
+        InitUsing => "Usando",
+        InitNextSteps => "Próximos pasos",
+        InitStep1EditFile => "Edite {0} para definir sus comandos",
+        InitStep2ListCommands => "Ejecute cmdrun list para listar los comandos disponibles",
+        InitStep3RunCommand => "Ejecute cmdrun run <nombre> para ejecutar un comando",
+        InitExampleCommands => "Comandos de ejemplo",
+        InitTemplateDescription => "plantilla",
+        InitLanguageSet => "Idioma establecido en",
+
+        // ====== Watch コマンド ======
+        WatchConfiguration => "Configuración de vigilancia",
+        WatchCommand => "Comando",
+        WatchWatching => "Vigilando",
+        WatchPatterns => "Patrones",
+        WatchExclude => "Excluir",
+        WatchDebounce => "Retardo",
+        WatchModeStarted => "Modo de vigilancia iniciado. Pulse Ctrl+C para detener.",
+        WatchPresCtrlCToStop => "Pulse Ctrl+C para detener",
+        WatchModeStoppedByUser => "Modo de vigilancia detenido por el usuario",
+
+        // ====== Remove コマンド ======
+        RemoveRemovalTarget => "Objetivo a eliminar",
+        RemoveType => "Tipo",
+        RemovePlatformSpecific => "Específico de plataforma",
+
+        // ====== Edit コマンド ======
+        EditParallelExecution => "Ejecución en paralelo",
+        EditConfirmBeforeExecution => "Confirmar antes de ejecutar",
+        EditNoChanges => "Sin cambios",
+
+        // ====== Graph コマンド ======
+        GraphSavedTo => "Grafo guardado en",
+        GraphRenderWith => "Renderizar con",
+        GraphViewAt => "Ver en",
+
+        // ====== Env コマンド ======
+        EnvCurrent => "Entorno actual",
+        EnvAvailableEnvironments => "Entornos disponibles",
+        EnvSwitchedTo => "Cambiado al entorno",
+        EnvCreated => "Entorno creado",
+        EnvVariableSet => "Variable establecida",
+        EnvEnvironment => "Entorno",
+        EnvDescription => "Descripción",
+        EnvConfigFile => "Archivo de configuración",
+        EnvEnvironmentVariables => "Variables de entorno",
+        EnvErrorNotFound => "Entorno no encontrado",
+        EnvErrorAlreadyExists => "El entorno ya existe",
+        EnvErrorCannotSetDefault => "No se pueden definir variables para el entorno 'default'",
+
+        // ====== Typo検出 ======
+        TypoUnknownCommand => "Comando desconocido",
+        TypoDidYouMean => "¿Quiso decir alguno de estos?",
+        TypoSuggestions => "Sugerencias",
+        TypoRunHelp => "Ejecute 'cmdrun --help' para ver los comandos disponibles",
+
+        // ====== その他 ======
+        AddingCommand => "Añadiendo comando",
+        RemovingCommand => "Eliminando comando",
+        UpdatingCommand => "Actualizando comando",
+        OpeningEditor => "Abriendo el editor",
+        SearchResults => "Resultados de búsqueda",
+        NoCommandsFound => "No se encontraron comandos",
+        Cancelled => "Cancelado",
+        LoadingConfiguration => "Cargando la configuración",
+        CreatingBackup => "Creando copia de seguridad",
+        MatchingCommands => "comando(s) coincidente(s)",
+        Template => "plantilla",
+
+        // ====== History コマンド ======
+        HistoryNoEntriesFound => "No se encontraron entradas en el historial",
+        HistoryNoCommandsMatching => "Ningún comando coincide con",
+        HistoryExitCode => "Código de salida:",
+        HistoryWorkingDir => "Directorio de trabajo:",
+        HistoryTotalCommands => "Comandos totales:",
+
+        // ====== Template コマンド ======
+        TemplateNoTemplatesAvailable => "No hay plantillas disponibles",
+        TemplateUserTemplates => "Plantillas de usuario:",
+
+        // ====== Plugin コマンド ======
+        PluginNoPluginsInstalled => "No hay plugins instalados",
+        PluginMinimumCmdrunVersion => "Versión mínima de cmdrun:",
+
+        // ====== Env コマンド表示 ======
+        EnvCurrentEnvironmentLabel => "Entorno actual:",
+        EnvAvailableEnvironmentsLabel => "Entornos disponibles:",
+        EnvConfigurationFiles => "Archivos de configuración",
+        EnvBaseConfig => "Configuración base",
+
+        // ====== Completion コマンド ======
+        CompletionInstallationInstructions => "Instrucciones de instalación:",
+
+        // 未翻訳のキーは英語にフォールバック
+        _ => return None,
+    };
+
+    Some(message)
+}
+
+/// ドイツ語のメッセージ（未翻訳のキーは `None`）
+fn german(key: MessageKey) -> Option<&'static str> {
+    use MessageKey::*;
+
+    let message = match key {
+        // ====== 実行状態 ======
+        Running => "Wird ausgeführt",
+        Completed => "Abgeschlossen",
+        Error => "Fehler",
+        Warning => "Warnung",
+        Success => "Erfolg",
+
+        // ====== CRUD操作 ======
+        CommandAdded => "Befehl erfolgreich hinzugefügt",
+        CommandRemoved => "Befehl erfolgreich entfernt",
+        CommandUpdated => "Befehl erfolgreich aktualisiert",
+
+        // ====== 検証 ======
+        Validating => "Konfiguration wird überprüft",
+        ConfigValid => "Konfiguration ist gültig",
+        ValidationFailed => "Überprüfung fehlgeschlagen",
+        ValidatingConfiguration => "Konfiguration wird überprüft...",
+        ConfigurationIsValid => "Konfiguration ist gültig",
+
+        // ====== 対話プロンプト ======
+        PromptCommandId => "Befehls-ID:",
+        PromptCommand => "Befehl:",
+        PromptDescription => "Beschreibung:",
+        PromptCategory => "Kategorie",
+        PromptTags => "Tags (durch Kommas getrennt)",
+        PromptConfirm => "Sind Sie sicher?",
+        PromptSelectCommand => "Zu bearbeitenden Befehl auswählen",
+        PromptWhatToDo => "Was möchten Sie tun?",
+        PromptEnterNumber => "Nummer eingeben",
+        PromptSelectTemplate => "Vorlage auswählen",
+        PromptSelectLanguage => "Bevorzugte Sprache auswählen",
+        PromptConfirmRunCommand => "Befehl '{0}' ausführen?",
+        ErrorConfirmationRequiresTerminal => "Befehl '{0}' erfordert eine Bestätigung, aber stdin ist kein Terminal (--yes zum Bestätigen verwenden)",
+        WarningConfirmationSkipped => "Bestätigung für '{0}' übersprungen (--yes)",
+
+        // ====== 選択肢 ======
+        OptionYesAdd => "Ja, diesen Befehl hinzufügen",
+        OptionNoEdit => "Nein, erneut bearbeiten",
+        OptionCancel => "Abbrechen",
+        OptionEnglish => "English (Englisch)",
+        OptionJapanese => "日本語 (Japanisch)",
+        OptionSpanish => "Español (Spanisch)",
+        OptionGerman => "Deutsch",
+
+        // ====== プレビュー・ラベル ======
+        LabelPreview => "Vorschau",
+        LabelCommand => "Befehl",
+        LabelDescription => "Beschreibung",
+        LabelCategory => "Kategorie",
+        LabelCurrentSettings => "Aktuelle Einstellungen",
+        LabelDependencies => "Abhängigkeiten",
+        LabelPlatforms => "Plattformen",
+        LabelWorkingDirectory => "Arbeitsverzeichnis",
+        LabelEnvironmentVariables => "Umgebungsvariablen",
+        LabelExecutionSettings => "Ausführungseinstellungen",
+        LabelConfirm => "Bestätigen",
+        LabelTimeout => "Zeitlimit",
+        LabelCommandDetails => "Befehlsdetails",
+        LabelConfiguration => "Konfiguration",
+        LabelLanguage => "Sprache",
+        LabelShell => "Shell",
+        LabelStrictMode => "Strikter Modus",
+        LabelBackupCreated => "Sicherung erstellt",
+        LabelYes => "ja",
+        LabelNo => "nein",
+
+        // ====== 警告メッセージ ======
+        WarningShellBuiltinNoEffect => "⚠ Dieser Shell-Builtin-Befehl läuft in einem Unterprozess und wirkt sich nicht auf die aktuelle Shell aus",
+        HintShellFunction => "💡 Tipp: Verwenden Sie Shell-Funktionen zum Wechseln von Verzeichnissen",
+        HintCdCommand => "   Zu ~/.cmdrun/shell-functions.sh hinzufügen:",
+
+        // ====== コマンド連結ヒント ======
+        HintCommandChainingAlternatives => "💡 Tipp: Verwenden Sie eine dieser Alternativen:",
+        HintCommandArrayRecommended => "   1. Befehls-Array verwenden (aus Sicherheitsgründen empfohlen):\n      cmd = [\"cd /path\", \"git diff\"]",
+        HintEnableChainingForCommand => "   2. Verkettung für diesen Befehl aktivieren (mit Vorsicht verwenden):\n      allow_chaining = true",
+        HintEnableChainingGlobally => "   3. Verkettung global aktivieren (nicht empfohlen):\n      [config]\n      allow_command_chaining = true",
+
+        // ====== エラーメッセージ ======
+        ErrorEmptyCommandId => "Befehls-ID darf nicht leer sein",
+        ErrorEmptyCommand => "Befehl darf nicht leer sein",
+        ErrorEmptyDescription => "Beschreibung darf nicht leer sein",
+        ErrorCommandNotFound => "Befehl nicht gefunden",
+        ErrorCommandExists => "Befehl existiert bereits",
+        ErrorConfigNotFound => "Konfigurationsdatei nicht gefunden",
+        ErrorInvalidConfig => "Ungültige Konfiguration",
+        ErrorInvalidSelection => "Ungültige Auswahl",
+        ErrorSelectionOutOfRange => "Auswahl außerhalb des gültigen Bereichs",
+        ErrorNoCommandsAvailable => "Keine Befehle verfügbar",
+        ErrorUnknownTemplate => "Unbekannte Vorlage",
+        ErrorFileAlreadyExists => "Konfigurationsdatei existiert bereits",
+        ErrorCircularDependency => "Zirkuläre Abhängigkeit erkannt",
+        ErrorValidationFailed => "Überprüfung fehlgeschlagen",
+        ErrorCommandFailed => "Befehl fehlgeschlagen",
+        ErrorUnknownConfigKey => "Unbekannter Konfigurationsschlüssel",
+        ErrorAliasTargetNotFound => "Alias-Ziel nicht gefunden",
+        ErrorInvalidLanguage => "Ungültige Sprache. Gültige Optionen: english, japanese, chinese_simplified, chinese_traditional, spanish, german",
+        ErrorNoConfigFileFound => "Keine Konfigurationsdatei gefunden. Führen Sie 'cmdrun init' aus, um eine zu erstellen.",
+        ErrorCannotDetermineConfigDir => "Konfigurationsverzeichnis kann nicht ermittelt werden",
+        ErrorCommandExecutionFailed => "Befehl fehlgeschlagen mit Exit-Code",
+        ErrorLocalConfigNotFound => "Lokale Konfigurationsdatei nicht gefunden",
+        ErrorNoConfigFilesSpecified => "Keine Konfigurationsdateien angegeben",
+        ErrorCannotSetEnvVariable => "In der Standardumgebung können keine Umgebungsvariablen gesetzt werden",
+        ErrorNoSuitableUnixShell => "Keine geeignete Unix-Shell gefunden",
+        ErrorNoSuitableWindowsShell => "Keine geeignete Windows-Shell gefunden",
+        ErrorShellNotFound => "Shell nicht gefunden",
+        ErrorTemplateAlreadyExists => "Vorlage existiert bereits",
+        ErrorTemplateNotFound => "Vorlage nicht gefunden",
+        ErrorCannotRemoveBuiltinTemplate => "Integrierte Vorlage kann nicht entfernt werden",
+        ErrorFileNotFound => "Datei nicht gefunden",
+        ErrorFailedToAccessCommandsTable => "Zugriff auf die Befehlstabelle fehlgeschlagen",
+        ErrorSecurityValidationFailed => "Sicherheitsprüfung fehlgeschlagen",
+        ErrorEditorNotFound => "Editor nicht gefunden",
+        ErrorFailedToOpenEditor => "Editor konnte nicht geöffnet werden",
+        ErrorCannotDetermineShell => "Shell kann nicht ermittelt werden",
+        ErrorInvalidConfigValue => "Ungültiger Konfigurationswert",
+        ErrorFailedToSaveConfiguration => "Konfiguration konnte nicht gespeichert werden",
+        ErrorCannotDetermineHomeDir => "Home-Verzeichnis konnte nicht ermittelt werden",
+        ErrorFailedToParseTemplate => "Vorlage konnte nicht geparst werden",
+        ErrorHistoryEntryNotFound => "Verlaufseintrag nicht gefunden",
+        ErrorFailedToDetermineLocalDataDir => "Lokales Datenverzeichnis konnte nicht ermittelt werden",
+        ErrorFailedToInitializeLogger => "Logger konnte nicht initialisiert werden",
+        ErrorFailedToReadConfig => "Konfiguration konnte nicht gelesen werden",
+        ErrorFailedToParseConfig => "Konfiguration konnte nicht geparst werden",
+        ErrorInvalidConfigPath => "Ungültiger Konfigurationspfad",
+        ErrorFailedToAcquireReadLock => "Lesesperre konnte nicht erlangt werden",
+        ErrorFailedToAcquireWriteLock => "Schreibsperre konnte nicht erlangt werden",
+        ErrorFailedToInitializePlugin => "Plugin konnte nicht initialisiert werden",
+        ErrorFailedToUnloadPlugin => "Plugin konnte nicht entladen werden",
+        ErrorPluginSymbolNotFound => "Plugin-Symbol nicht gefunden",
+        ErrorFailedToLoadLibrary => "Bibliothek konnte nicht geladen werden",
+        ErrorTemplateNameEmpty => "Vorlagenname darf nicht leer sein",
+        ErrorTemplateDescriptionEmpty => "Vorlagenbeschreibung darf nicht leer sein",
+        ErrorTemplateNoCommands => "Vorlage muss mindestens einen Befehl enthalten",
+        ErrorCommandIdInvalidChars => "Befehls-ID enthält ungültige Zeichen",
+        ErrorCommandFailedWithCode => "Befehl fehlgeschlagen mit Exit-Code",
+        ErrorNoTemplatesAvailable => "Keine Vorlagen verfügbar",
+
+        // ====== ヘルプテキスト ======
+        HelpAddCommand => "Neuen Befehl zur Konfiguration hinzufügen",
+        HelpRemoveCommand => "Befehl aus der Konfiguration entfernen",
+        HelpEditCommand => "Vorhandenen Befehl bearbeiten",
+        HelpListCommands => "Alle verfügbaren Befehle auflisten",
+        HelpRunCommand => "Befehl ausführen",
+        HelpValidateConfig => "Konfigurationsdatei überprüfen",
+        HelpSearchCommand => "Befehle nach Stichwort suchen",
+        HelpInfoCommand => "Detaillierte Befehlsinformationen anzeigen",
+        HelpConfigCommand => "Konfigurationseinstellungen verwalten",
+        HelpWatchCommand => "Dateien überwachen und bei Änderungen einen Befehl ausführen",
+        HelpInitCommand => "Neue Konfigurationsdatei initialisieren",
+
+        // ====== List コマンド ======
+        ListNoCommandsDefined => "Keine Befehle definiert",
+        ListAvailableCommands => "Verfügbare Befehle",
+        ListCommandCount => "Befehle definiert",
+        ListAliasCount => "Aliase definiert",
+
+        // ====== Run コマンド ======
+        RunRunningCommand => "Wird ausgeführt",
+        RunWithParallelDependencies => "mit parallelen Abhängigkeiten",
+        RunExecutionPlan => "Ausführungsplan",
+        RunGroup => "Gruppe",
+        RunAllCommandsCompleted => "Alle Befehle abgeschlossen in",
+        RunCompletedIn => "Abgeschlossen in",
+        RunCommandFailedWithCode => "Befehl fehlgeschlagen mit Exit-Code",
+
+        // ====== Search コマンド ======
+        SearchSearchingFor => "Suche nach",
+        SearchNoCommandsMatching => "Keine Befehle gefunden für",
+        SearchFound => "Gefunden",
+        SearchMatchedIn => "Treffer in",
+        SearchUseInfoToSeeDetails => "Verwenden Sie cmdrun info <Befehl>, um Details anzuzeigen",
+
+        // ====== Info コマンド ======
+        InfoSelectCommandToView => "Befehl auswählen, um Details anzuzeigen",
+        InfoBasicInformation => "Grundlegende Informationen",
+        InfoCommandSpecification => "Befehlsspezifikation",
+        InfoExecutionSettings => "Ausführungseinstellungen",
+        InfoPlatformSupport => "Plattformunterstützung",
+        InfoConfigurationPaths => "Konfigurationspfade",
+        InfoGlobalConfigPath => "Globale Konfiguration",
+        InfoLocalConfigPath => "Lokale Konfiguration",
+        InfoActualWorkingDirectory => "Tatsächliches Arbeitsverzeichnis",
+        InfoExecutionStatistics => "Ausführungsstatistik",
+        InfoTotalExecutions => "Ausführungen gesamt",
+        InfoSuccessfulRuns => "Erfolgreiche Ausführungen",
+        InfoFailedRuns => "Fehlgeschlagene Ausführungen",
+        InfoLastRun => "Letzte Ausführung",
+        InfoAverageDuration => "Durchschnittliche Dauer",
+
+        // ====== Config コマンド ======
+        ConfigSet => "Gesetzt",
+        ConfigShowingConfiguration => "Konfiguration wird angezeigt",
+
+        // ====== Validate コマンド ======
+        ValidateLoadedConfigFrom => "Konfiguration geladen aus",
+        ValidateCheckingCircularDependencies => "Suche nach zirkulären Abhängigkeiten...",
+        ValidateNoCircularDependenciesFor => "Keine zirkulären Abhängigkeiten für",
+        ValidateValidatingCommands => "Befehle werden überprüft",
+        ValidateValidatingAliases => "Aliase werden überprüft",
+        ValidateBuildingDependencyGraph => "Abhängigkeitsgraph wird erstellt...",
+        ValidateDependencyGraphBuilt => "Abhängigkeitsgraph erfolgreich erstellt",
+        ValidateExecutionOrder => "Ausführungsreihenfolge",
+        ValidateErrors => "Fehler",
+        ValidateWarnings => "Warnungen",
+        ValidateInformation => "Informationen",
+        ValidateFailedWithErrors => "Konfigurationsprüfung fehlgeschlagen mit",
+        ValidateCommandsDefined => "Befehle definiert",
+        ValidateAliasesDefined => "Aliase definiert",
+
+        // ====== Init コマンド ======
+        InitCreated => "Erstellt",
+        InitUsing => "Verwende",
+        InitNextSteps => "Nächste Schritte",
+        InitStep1EditFile => "Bearbeiten Sie {0}, um Ihre Befehle zu definieren",
+        InitStep2ListCommands => "Führen Sie cmdrun list aus, um verfügbare Befehle aufzulisten",
+        InitStep3RunCommand => "Führen Sie cmdrun run <Name> aus, um einen Befehl auszuführen",
+        InitExampleCommands => "Beispielbefehle",
+        InitTemplateDescription => "Vorlage",
+        InitLanguageSet => "Sprache eingestellt auf",
+
+        // ====== Watch コマンド ======
+        WatchConfiguration => "Überwachungskonfiguration",
+        WatchCommand => "Befehl",
+        WatchWatching => "Überwacht",
+        WatchPatterns => "Muster",
+        WatchExclude => "Ausschließen",
+        WatchDebounce => "Entprellung",
+        WatchModeStarted => "Überwachungsmodus gestartet. Mit Strg+C beenden.",
+        WatchPresCtrlCToStop => "Mit Strg+C beenden",
+        WatchModeStoppedByUser => "Überwachungsmodus vom Benutzer beendet",
+
+        // ====== Remove コマンド ======
+        RemoveRemovalTarget => "Zu entfernendes Ziel",
+        RemoveType => "Typ",
+        RemovePlatformSpecific => "Plattformspezifisch",
+
+        // ====== Edit コマンド ======
+        EditParallelExecution => "Parallele Ausführung",
+        EditConfirmBeforeExecution => "Vor der Ausführung bestätigen",
+        EditNoChanges => "Keine Änderungen",
+
+        // ====== Graph コマンド ======
+        GraphSavedTo => "Graph gespeichert unter",
+        GraphRenderWith => "Rendern mit",
+        GraphViewAt => "Anzeigen unter",
+
+        // ====== Env コマンド ======
+        EnvCurrent => "Aktuelle Umgebung",
+        EnvAvailableEnvironments => "Verfügbare Umgebungen",
+        EnvSwitchedTo => "Gewechselt zu Umgebung",
+        EnvCreated => "Umgebung erstellt",
+        EnvVariableSet => "Variable gesetzt",
+        EnvEnvironment => "Umgebung",
+        EnvDescription => "Beschreibung",
+        EnvConfigFile => "Konfigurationsdatei",
+        EnvEnvironmentVariables => "Umgebungsvariablen",
+        EnvErrorNotFound => "Umgebung nicht gefunden",
+        EnvErrorAlreadyExists => "Umgebung existiert bereits",
+        EnvErrorCannotSetDefault => "Für die Umgebung 'default' können keine Variablen gesetzt werden",
+
+        // ====== Typo検出 ======
+        TypoUnknownCommand => "Unbekannter Befehl",
+        TypoDidYouMean => "Meinten Sie einen dieser Befehle?",
+        TypoSuggestions => "Vorschläge",
+        TypoRunHelp => "Führen Sie 'cmdrun --help' aus, um verfügbare Befehle anzuzeigen",
+
+        // ====== その他 ======
+        AddingCommand => "Befehl wird hinzugefügt",
+        RemovingCommand => "Befehl wird entfernt",
+        UpdatingCommand => "Befehl wird aktualisiert",
+        OpeningEditor => "Editor wird geöffnet",
+        SearchResults => "Suchergebnisse",
+        NoCommandsFound => "Keine Befehle gefunden",
+        Cancelled => "Abgebrochen",
+        LoadingConfiguration => "Konfiguration wird geladen",
+        CreatingBackup => "Sicherung wird erstellt",
+        MatchingCommands => "passende(r) Befehl(e)",
+        Template => "Vorlage",
+
+        // ====== History コマンド ======
+        HistoryNoEntriesFound => "Keine Verlaufseinträge gefunden",
+        HistoryNoCommandsMatching => "Keine Befehle gefunden für",
+        HistoryExitCode => "Exit-Code:",
+        HistoryWorkingDir => "Arbeitsverzeichnis:",
+        HistoryTotalCommands => "Befehle gesamt:",
+
+        // ====== Template コマンド ======
+        TemplateNoTemplatesAvailable => "Keine Vorlagen verfügbar",
+        TemplateUserTemplates => "Benutzervorlagen:",
+
+        // ====== Plugin コマンド ======
+        PluginNoPluginsInstalled => "Keine Plugins installiert",
+        PluginMinimumCmdrunVersion => "Minimale cmdrun-Version:",
+
+        // ====== Env コマンド表示 ======
+        EnvCurrentEnvironmentLabel => "Aktuelle Umgebung:",
+        EnvAvailableEnvironmentsLabel => "Verfügbare Umgebungen:",
+        EnvConfigurationFiles => "Konfigurationsdateien",
+        EnvBaseConfig => "Basiskonfiguration",
+
+        // ====== Completion コマンド ======
+        CompletionInstallationInstructions => "Installationsanweisungen:",
+
+        // 未翻訳のキーは英語にフォールバック
+        _ => return None,
+    };
+
+    Some(message)
+}
+
 /// フォーマット付きメッセージの取得
 pub fn format_message(key: MessageKey, language: Language, args: &[&str]) -> String {
     let template = get_message(key, language);
@@ -1556,6 +2262,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_message_spanish() {
+        assert_eq!(
+            get_message(MessageKey::Running, Language::Spanish),
+            "Ejecutando"
+        );
+        assert_eq!(
+            get_message(MessageKey::ListAvailableCommands, Language::Spanish),
+            "Comandos disponibles"
+        );
+        assert_eq!(
+            format_message(
+                MessageKey::InitStep1EditFile,
+                Language::Spanish,
+                &["commands.toml"]
+            ),
+            "Edite commands.toml para definir sus comandos"
+        );
+    }
+
+    #[test]
+    fn test_get_message_german() {
+        assert_eq!(
+            get_message(MessageKey::Running, Language::German),
+            "Wird ausgeführt"
+        );
+        assert_eq!(
+            get_message(MessageKey::ListAvailableCommands, Language::German),
+            "Verfügbare Befehle"
+        );
+        assert_eq!(
+            format_message(
+                MessageKey::InitStep1EditFile,
+                Language::German,
+                &["commands.toml"]
+            ),
+            "Bearbeiten Sie commands.toml, um Ihre Befehle zu definieren"
+        );
+    }
+
+    #[test]
+    fn test_invalid_language_lists_all_options() {
+        for language in [
+            Language::English,
+            Language::Japanese,
+            Language::ChineseSimplified,
+            Language::ChineseTraditional,
+            Language::Spanish,
+            Language::German,
+        ] {
+            let message = get_message(MessageKey::ErrorInvalidLanguage, language);
+            assert!(message.contains("spanish"), "{:?}", language);
+            assert!(message.contains("german"), "{:?}", language);
+        }
+    }
+
+    #[test]
+    fn test_untranslated_key_falls_back_to_english() {
+        // "ID" は英語と同じ表記のためドイツ語側では定義していない
+        assert_eq!(german(MessageKey::LabelId), None);
+        assert_eq!(
+            get_message(MessageKey::LabelId, Language::German),
+            english(MessageKey::LabelId)
+        );
+
+        // 翻訳済みのキーはそのまま返る
+        assert_eq!(german(MessageKey::Running), Some("Wird ausgeführt"));
+    }
+
     #[test]
     fn test_format_message() {
         let result = format_message(
@@ -2,18 +2,27 @@
 //!
 //! A modern replacement for package.json scripts and Makefiles.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 #[cfg(feature = "plugin-system")]
 use cmdrun::cli::PluginAction;
 use cmdrun::cli::{
-    Cli, ColorChoice, Commands, ConfigAction, EnvAction, GraphFormat, HistoryAction, TemplateAction,
+    Cli, ColorChoice, Commands, ConfigAction, EnvAction, GraphFormat, HistoryAction, ProfileFormat,
+    TemplateAction,
 };
-use cmdrun::command::dependency::DependencyGraph;
-use cmdrun::command::executor::{CommandExecutor, ExecutionContext};
+use cmdrun::command::dependency::{DependencyGraph, ExecutionGroup};
+use cmdrun::command::executor::{CommandExecutor, ExecutionContext, InterruptHandle};
 use cmdrun::command::graph_visualizer::GraphVisualizer;
-use cmdrun::config::loader::ConfigLoader;
-use cmdrun::platform::shell::detect_shell;
+use cmdrun::command::hooks::HookRunner;
+use cmdrun::config::loader::{self, ConfigLoader};
+use cmdrun::error::ExecutionError;
+use cmdrun::output::capture::OutputLog;
+use cmdrun::output::formatter::{name_column_width, terminal_width, OutputFormatter};
+use cmdrun::output::manifest::{RunManifest, RunOutcome};
+use cmdrun::output::progress::{ParallelProgress, ProgressMode};
+use cmdrun::output::{notify, status};
+use cmdrun::platform::shell::{detect_shell, find_shell};
+use cmdrun::security::EnvRedactor;
 use colored::*;
 use std::fs;
 use std::process;
@@ -25,16 +34,39 @@ async fn main() {
 
     // Configure color output (must be done before any colored output)
     configure_color_output(cli.color);
+    status::set_quiet(cli.quiet);
+    loader::set_walk_parents(!cli.no_walk);
 
-    // Initialize logging (skip for CompletionList to avoid polluting shell completion)
-    if !matches!(cli.command, Commands::CompletionList) {
-        init_logging(cli.verbose, cli.color);
+    // Initialize logging (skip for completion helpers to avoid polluting shell completion)
+    if !matches!(
+        cli.command,
+        Commands::CompletionList
+            | Commands::CompleteArgs { .. }
+            | Commands::CompletionTags
+            | Commands::CompletionCategories
+    ) {
+        let log_file = match &cli.command {
+            Commands::Run { log_file, .. } => log_file.as_deref(),
+            _ => None,
+        };
+        init_logging(cli.verbose, cli.quiet, cli.color, log_file);
     }
 
+    let error_json = cli.error_json;
+    let command_name = match &cli.command {
+        Commands::Run { name, .. } => Some(name.clone()),
+        _ => None,
+    };
+
     // Run command
     if let Err(e) = run(cli).await {
-        eprintln!("{} {}", "Error:".red().bold(), e);
-        process::exit(1);
+        if error_json {
+            let report = cmdrun::error::ErrorReport::new(&e, command_name.as_deref());
+            eprintln!("{}", report.to_json());
+        } else {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+        }
+        process::exit(cmdrun::error::process_exit_code(&e));
     }
 }
 
@@ -64,33 +96,136 @@ fn configure_color_output(color_choice: ColorChoice) {
 /// Main execution flow
 async fn run(cli: Cli) -> Result<()> {
     // Extract config path and global flag before matching on command
-    let config_path = cli.config.clone();
+    let config_paths = cli.config.clone();
+    // Commands that edit the config write to the last `--config` file
+    let config_path = config_paths.last().cloned();
     let global_only = cli.global;
+    let allow_unsafe = cli.allow_unsafe;
 
     match cli.command {
         Commands::Run {
             name,
             args,
             parallel,
+            sequential,
+            merge_stderr,
+            yes,
+            log_file: _,
+            log,
+            capture,
+            cwd,
+            shell,
+            print_command,
+            dry_run,
+            jobs,
+            watch,
+            profile,
+            profile_format,
+            timeout,
+            env,
+            notify,
+        } => {
+            if watch {
+                run_watch(name, args, global_only, config_paths).await?;
+            } else {
+                let options = RunOptions {
+                    parallel: match (parallel, sequential) {
+                        (true, _) => Some(true),
+                        (_, true) => Some(false),
+                        _ => None,
+                    },
+                    allow_unsafe,
+                    merge_stderr,
+                    yes,
+                    cwd,
+                    shell,
+                    log,
+                    capture,
+                    print_command,
+                    dry_run,
+                    jobs,
+                    profile: profile.then_some(profile_format),
+                    timeout,
+                    env,
+                    notify,
+                };
+                run_command(&name, args, options, global_only, config_paths).await?;
+            }
+        }
+        Commands::Exec { command, timeout } => {
+            exec_command(command, timeout, allow_unsafe, global_only, config_paths).await?;
+        }
+        Commands::Bench {
+            name,
+            runs,
+            warmup,
+            ignore_failures,
         } => {
-            run_command(&name, args, parallel, global_only, config_path).await?;
+            let options = cmdrun::commands::BenchOptions {
+                runs,
+                warmup,
+                ignore_failures,
+            };
+            cmdrun::commands::handle_bench(&name, options, global_only, config_paths).await?;
         }
-        Commands::List { verbose } => {
-            list_commands(verbose, global_only, config_path).await?;
+        Commands::List {
+            verbose,
+            grouped,
+            category,
+            no_truncate,
+            json_schema,
+        } => {
+            if json_schema {
+                let schema = cmdrun::config::schema_json::config_json_schema();
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+            } else {
+                list_commands(
+                    verbose,
+                    grouped,
+                    category,
+                    no_truncate,
+                    global_only,
+                    config_paths,
+                )
+                .await?;
+            }
         }
         Commands::Init {
             template,
             interactive,
+            from,
             output,
-        } => {
-            cmdrun::commands::handle_init(template, interactive, output).await?;
-        }
+        } => match from {
+            Some(source) => cmdrun::commands::handle_init_from(source, output).await?,
+            None => cmdrun::commands::handle_init(template, interactive, output).await?,
+        },
         Commands::Validate {
             path,
             verbose,
             check_cycles,
+            graph_only,
+            strict_schema,
+            strict,
+            fail_on_warning,
+            explain,
         } => {
-            cmdrun::commands::handle_validate(path, verbose, check_cycles).await?;
+            cmdrun::commands::handle_validate(
+                path.map(|path| vec![path]).unwrap_or(config_paths),
+                verbose,
+                check_cycles,
+                graph_only,
+                strict_schema,
+                strict,
+                fail_on_warning,
+                explain,
+            )
+            .await?;
+        }
+        Commands::Doctor => {
+            cmdrun::commands::handle_doctor(global_only, config_paths).await?;
+        }
+        Commands::Audit => {
+            cmdrun::commands::handle_audit(global_only, config_paths).await?;
         }
         Commands::Graph {
             command,
@@ -104,7 +239,7 @@ async fn run(cli: Cli) -> Result<()> {
                 output,
                 show_groups,
                 global_only,
-                config_path,
+                config_paths,
             )
             .await?;
         }
@@ -125,27 +260,54 @@ async fn run(cli: Cli) -> Result<()> {
             cmdrun::commands::handle_add(id, command, description, category, tags, config_path)
                 .await?;
         }
-        Commands::Open => {
-            cmdrun::commands::handle_open(config_path).await?;
+        Commands::Open { editor } => {
+            cmdrun::commands::handle_open(config_path, editor).await?;
         }
         Commands::Edit { id } => {
             cmdrun::commands::handle_edit(id, config_path).await?;
         }
-        Commands::Info { id } => {
-            cmdrun::commands::handle_info(id, global_only, config_path).await?;
+        Commands::Info { id, format } => {
+            cmdrun::commands::handle_info(id, format, global_only, config_paths).await?;
+        }
+        Commands::Which { id } => {
+            cmdrun::commands::handle_which(&id, global_only, config_paths).await?;
+        }
+        Commands::Search {
+            keywords,
+            any,
+            fields,
+        } => {
+            cmdrun::commands::handle_search(&keywords, any, &fields, global_only, config_paths)
+                .await?;
+        }
+        Commands::Docs { output } => {
+            cmdrun::commands::handle_docs(output, global_only, config_paths).await?;
         }
-        Commands::Search { keyword } => {
-            cmdrun::commands::handle_search(keyword, global_only, config_path).await?;
+        Commands::Export { format, output } => {
+            cmdrun::commands::handle_export(format, output, global_only, config_paths).await?;
         }
         Commands::CompletionList => {
-            list_completion(global_only, config_path).await?;
+            list_completion(global_only, config_paths).await?;
+        }
+        Commands::CompleteArgs { name, position } => {
+            list_arg_completion(&name, position, global_only, config_paths).await?;
+        }
+        Commands::CompletionTags => {
+            list_tag_completion(global_only, config_paths).await?;
+        }
+        Commands::CompletionCategories => {
+            list_category_completion(global_only, config_paths).await?;
         }
         Commands::Config { action } => match action {
             ConfigAction::Get { key } => {
                 cmdrun::commands::handle_get(&key, config_path).await?;
             }
-            ConfigAction::Set { key, value } => {
-                cmdrun::commands::handle_set(&key, &value, config_path).await?;
+            ConfigAction::Set { key, value, append } => {
+                if append {
+                    cmdrun::commands::handle_append(&key, &value, config_path).await?;
+                } else {
+                    cmdrun::commands::handle_set(&key, &value, config_path).await?;
+                }
             }
             ConfigAction::Show => {
                 cmdrun::commands::handle_show(config_path).await?;
@@ -161,17 +323,16 @@ async fn run(cli: Cli) -> Result<()> {
             ignore_gitignore,
             no_recursive,
         } => {
-            cmdrun::commands::handle_watch(
-                command,
-                args,
+            let options = cmdrun::commands::WatchOptions {
                 paths,
                 patterns,
                 exclude,
-                debounce,
+                debounce_ms: debounce,
                 ignore_gitignore,
                 no_recursive,
-            )
-            .await?;
+            };
+            cmdrun::commands::handle_watch(command, args, options, global_only, config_paths)
+                .await?;
         }
         Commands::Env { action } => match action {
             EnvAction::Use { name } => {
@@ -195,20 +356,37 @@ async fn run(cli: Cli) -> Result<()> {
                 use cmdrun::config::Language;
                 cmdrun::commands::handle_env_info(name, Language::English).await?;
             }
+            EnvAction::Diff { a, b } => {
+                use cmdrun::config::Language;
+                cmdrun::commands::handle_env_diff(
+                    a,
+                    b,
+                    Language::English,
+                    global_only,
+                    config_paths,
+                )
+                .await?;
+            }
         },
         Commands::History { action } => match action {
             HistoryAction::List {
                 limit,
                 offset,
                 failed,
+                since,
+                until,
                 stats,
+                verbose,
             } => {
                 use cmdrun::config::Language;
                 cmdrun::commands::handle_history(
                     Some(limit),
                     offset,
                     failed,
+                    since.as_deref(),
+                    until.as_deref(),
                     stats,
+                    verbose,
                     Language::English,
                 )
                 .await?;
@@ -220,6 +398,12 @@ async fn run(cli: Cli) -> Result<()> {
             HistoryAction::Clear { force } => {
                 cmdrun::commands::handle_history_clear(force).await?;
             }
+            HistoryAction::Prune {
+                max_entries,
+                older_than,
+            } => {
+                cmdrun::commands::handle_history_prune(max_entries, older_than.as_deref()).await?;
+            }
             HistoryAction::Export {
                 format,
                 output,
@@ -228,13 +412,27 @@ async fn run(cli: Cli) -> Result<()> {
                 let export_format = match format {
                     cmdrun::cli::ExportFormat::Json => cmdrun::commands::ExportFormat::Json,
                     cmdrun::cli::ExportFormat::Csv => cmdrun::commands::ExportFormat::Csv,
+                    cmdrun::cli::ExportFormat::Yaml => cmdrun::commands::ExportFormat::Yaml,
+                    cmdrun::cli::ExportFormat::Toml => cmdrun::commands::ExportFormat::Toml,
                 };
                 cmdrun::commands::handle_history_export(export_format, output, limit).await?;
             }
-            HistoryAction::Stats => {
+            HistoryAction::Stats {
+                command,
+                last,
+                json,
+                by_command,
+                sort,
+                top,
+            } => {
                 use cmdrun::config::Language;
-                cmdrun::commands::handle_history(None, None, false, true, Language::English)
-                    .await?;
+                if by_command {
+                    cmdrun::commands::handle_history_breakdown(sort, top, json, Language::English)
+                        .await?;
+                } else {
+                    cmdrun::commands::handle_history_stats(command, last, json, Language::English)
+                        .await?;
+                }
             }
         },
         Commands::Retry { id } => {
@@ -244,8 +442,14 @@ async fn run(cli: Cli) -> Result<()> {
             TemplateAction::Add { name } => {
                 cmdrun::commands::handle_template_add(name, config_path).await?;
             }
-            TemplateAction::Use { name, output } => {
-                cmdrun::commands::handle_template_use(name, output).await?;
+            TemplateAction::Use {
+                name,
+                output,
+                set,
+                merge,
+                overwrite,
+            } => {
+                cmdrun::commands::handle_template_use(name, output, set, merge, overwrite).await?;
             }
             TemplateAction::List { verbose } => {
                 use cmdrun::config::Language;
@@ -257,25 +461,54 @@ async fn run(cli: Cli) -> Result<()> {
             TemplateAction::Export { name, output } => {
                 cmdrun::commands::handle_template_export(name, output).await?;
             }
-            TemplateAction::Import { file } => {
-                cmdrun::commands::handle_template_import(file).await?;
+            TemplateAction::Import {
+                file,
+                url,
+                git,
+                path,
+                yes,
+            } => {
+                use cmdrun::template::RemoteSource;
+                let remote = match (url, git, path) {
+                    (Some(url), _, _) => Some(RemoteSource::Url(url)),
+                    (None, Some(repo), Some(path)) => Some(RemoteSource::Git { repo, path }),
+                    _ => None,
+                };
+                match (remote, file) {
+                    (Some(source), _) => {
+                        cmdrun::commands::handle_template_import_remote(source, yes).await?;
+                    }
+                    (None, Some(file)) => {
+                        cmdrun::commands::handle_template_import(file).await?;
+                    }
+                    (None, None) => unreachable!("clap requires a file, --url, or --git"),
+                }
+            }
+            TemplateAction::Validate { file } => {
+                cmdrun::commands::handle_template_validate(file).await?;
             }
         },
         #[cfg(feature = "plugin-system")]
         Commands::Plugin { action } => match action {
-            PluginAction::List { enabled, verbose } => {
+            PluginAction::List {
+                enabled,
+                verbose,
+                json,
+            } => {
                 use cmdrun::config::Language;
                 cmdrun::commands::handle_plugin_list(
                     enabled,
                     verbose,
-                    config_path,
+                    json,
+                    config_paths,
                     Language::English,
                 )
                 .await?;
             }
             PluginAction::Info { name } => {
                 use cmdrun::config::Language;
-                cmdrun::commands::handle_plugin_info(&name, config_path, Language::English).await?;
+                cmdrun::commands::handle_plugin_info(&name, config_paths, Language::English)
+                    .await?;
             }
             PluginAction::Enable { name } => {
                 cmdrun::commands::handle_plugin_enable(&name, config_path).await?;
@@ -283,316 +516,1226 @@ async fn run(cli: Cli) -> Result<()> {
             PluginAction::Disable { name } => {
                 cmdrun::commands::handle_plugin_disable(&name, config_path).await?;
             }
+            PluginAction::Install { source } => {
+                cmdrun::commands::handle_plugin_install(&source, config_path).await?;
+            }
         },
+        #[cfg(feature = "plugin-system")]
+        Commands::External(argv) => {
+            run_plugin_command(argv, global_only, config_paths).await?;
+        }
     }
 
     Ok(())
 }
 
+/// Per-invocation options for `cmdrun run`
+#[derive(Debug, Default)]
+struct RunOptions {
+    /// `Some(true)` for `--parallel`, `Some(false)` for `--sequential`;
+    /// `None` follows the command's `parallel` setting
+    parallel: Option<bool>,
+    /// Skip command security validation
+    allow_unsafe: bool,
+    /// Merge stderr into stdout
+    merge_stderr: bool,
+    /// Auto-confirm commands marked `confirm = true`
+    yes: bool,
+    /// Working directory override (`--cwd`)
+    cwd: Option<std::path::PathBuf>,
+    /// Shell override (`--shell`)
+    shell: Option<String>,
+    /// Output capture file (`--log`)
+    log: Option<std::path::PathBuf>,
+    /// Run manifest file (`--capture`)
+    capture: Option<std::path::PathBuf>,
+    /// Print resolved command lines before executing (`--print-command`)
+    print_command: bool,
+    /// Print resolved command lines without executing (`--dry-run`)
+    dry_run: bool,
+    /// Concurrency limit for parallel groups (`--jobs`)
+    jobs: Option<usize>,
+    /// Timing report format (`--profile`, `--output`)
+    profile: Option<ProfileFormat>,
+    /// Timeout override (`--timeout`)
+    timeout: Option<std::time::Duration>,
+    /// Environment overrides (`--env KEY=VALUE`)
+    env: Vec<(String, String)>,
+    /// Desktop notification when the run finishes (`--notify`)
+    notify: bool,
+}
+
+impl RunOptions {
+    /// Resolve how dependencies run: the flags override `command.parallel`
+    fn execution_mode(&self, command: &cmdrun::config::schema::Command) -> ExecutionMode {
+        match self.parallel {
+            Some(true) => ExecutionMode::Parallel,
+            Some(false) => ExecutionMode::Sequential,
+            None if command.parallel => ExecutionMode::Parallel,
+            None => ExecutionMode::Direct,
+        }
+    }
+}
+
+/// How `cmdrun run` executes a command's dependencies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExecutionMode {
+    /// Only the command itself; dependencies are not resolved
+    Direct,
+    /// Dependency groups in order, commands within a group concurrently
+    Parallel,
+    /// Dependency groups in order, one command at a time
+    Sequential,
+}
+
+/// The literal shell input of every command in the plan, in execution order
+fn resolve_planned_commands(
+    executor: &CommandExecutor,
+    config: &cmdrun::config::schema::CommandsConfig,
+    name: &str,
+    mode: ExecutionMode,
+    redactor: &EnvRedactor,
+) -> Result<Vec<String>> {
+    let mut lines = Vec::new();
+    for cmd_name in planned_command_names(config, name, mode)? {
+        if let Some(cmd) = config.commands.get(cmd_name) {
+            lines.extend(executor.resolve_printable_commands(cmd, redactor)?);
+        }
+    }
+    Ok(lines)
+}
+
+/// The shell each command in the plan runs with, keyed by command name
+fn resolve_planned_shells(
+    executor: &CommandExecutor,
+    config: &cmdrun::config::schema::CommandsConfig,
+    name: &str,
+    mode: ExecutionMode,
+) -> Result<std::collections::BTreeMap<String, String>> {
+    let mut shells = std::collections::BTreeMap::new();
+    for cmd_name in planned_command_names(config, name, mode)? {
+        if let Some(cmd) = config.commands.get(cmd_name) {
+            shells.insert(cmd_name.to_string(), executor.resolve_shell(cmd)?);
+        }
+    }
+    Ok(shells)
+}
+
+/// Names of the commands `run` executes, dependencies first
+fn planned_command_names<'a>(
+    config: &'a cmdrun::config::schema::CommandsConfig,
+    name: &'a str,
+    mode: ExecutionMode,
+) -> Result<Vec<&'a str>> {
+    if mode == ExecutionMode::Direct {
+        return Ok(vec![name]);
+    }
+    Ok(DependencyGraph::new(config)
+        .resolve(name)?
+        .iter()
+        .flat_map(|group| group.commands.iter().copied())
+        .collect())
+}
+
+/// `run --watch`: delegate to the watch handler using the command's `watch` spec
+async fn run_watch(
+    name: String,
+    args: Vec<String>,
+    global_only: bool,
+    config_paths: Vec<std::path::PathBuf>,
+) -> Result<()> {
+    let mut config = load_run_config(global_only, config_paths.clone()).await?;
+    // Plugins may add commands or modify their watch spec
+    cmdrun::plugin::PluginManager::load_for_config(&mut config)?;
+    let (name, args) = resolve_alias(&name, args, &config)?;
+
+    let Some(command) = config.commands.get(&name) else {
+        let available_commands: Vec<&str> = config.commands.keys().map(|s| s.as_str()).collect();
+        return Err(unknown_command_error(&name, &available_commands, &config));
+    };
+    if command.watch.is_none() {
+        anyhow::bail!(
+            "Command '{name}' has no watch configuration.\n\n\
+             Add a watch section to commands.toml, for example:\n\n\
+             [commands.{name}.watch]\n\
+             paths = [\"src\"]\n\
+             patterns = [\"**/*.rs\"]\n\n\
+             or pass paths explicitly: cmdrun watch {name} --path src"
+        );
+    }
+
+    cmdrun::commands::handle_watch(
+        name,
+        args,
+        cmdrun::commands::WatchOptions::default(),
+        global_only,
+        config_paths,
+    )
+    .await
+}
+
 /// Run a command
 async fn run_command(
     name: &str,
     args: Vec<String>,
-    parallel: bool,
+    options: RunOptions,
     global_only: bool,
-    config_path: Option<std::path::PathBuf>,
+    config_paths: Vec<std::path::PathBuf>,
 ) -> Result<()> {
     // Initialize history recorder
     let storage = cmdrun::history::HistoryStorage::new()?;
     let mut recorder = cmdrun::history::HistoryRecorder::with_storage(storage);
 
-    // Load configuration (with environment support)
-    let config_loader = if let Some(path) = config_path {
-        ConfigLoader::with_path(path)?
-    } else if global_only {
-        ConfigLoader::global_only()
-    } else {
-        ConfigLoader::new()
-    };
+    let mut config = load_run_config(global_only, config_paths).await?;
 
-    // Try to load config (with environment), fallback to global-only if no local config
-    let config = match config_loader.load_with_environment().await {
-        Ok(cfg) => cfg,
-        Err(_) => {
-            // If local config not found, try loading from global config
-            if let Some(global_dir) = dirs::config_dir() {
-                let global_path = global_dir.join("cmdrun").join("commands.toml");
-                if global_path.exists() {
-                    ConfigLoader::with_path(global_path)?.load().await?
-                } else {
-                    // Use default language (English) for error message when config not found
-                    use cmdrun::config::Language;
-                    use cmdrun::i18n::{get_message, MessageKey};
-                    anyhow::bail!(
-                        "{}",
-                        get_message(MessageKey::ErrorNoConfigFileFound, Language::English)
-                    );
-                }
-            } else {
-                use cmdrun::config::Language;
-                use cmdrun::i18n::{get_message, MessageKey};
-                anyhow::bail!(
-                    "{}",
-                    get_message(MessageKey::ErrorCannotDetermineConfigDir, Language::English)
-                );
+    // Apply configured redaction patterns before anything is recorded
+    recorder
+        .set_redact_patterns(&config.config.redact_patterns)
+        .context("Invalid redact_patterns in [config]")?;
+    recorder
+        .set_retention(
+            config.config.history_max_entries,
+            config.config.history_max_age.as_deref(),
+        )
+        .context("Invalid history_max_age in [config]")?;
+
+    // Load plugins and let them modify the config before command resolution
+    #[cfg_attr(not(feature = "plugin-system"), allow(unused_variables))]
+    let plugin_manager = cmdrun::plugin::PluginManager::load_for_config(&mut config)?;
+
+    // Resolve aliases, forwarding the alias' own arguments
+    let (name, args) = resolve_alias(name, args, &config)?;
+    let name = name.as_str();
+
+    // --env overrides every command's env (dependencies included), except
+    // variables that hold positional arguments
+    let is_positional = |key: &str| {
+        key.parse::<usize>()
+            .is_ok_and(|idx| idx >= 1 && idx <= args.len())
+    };
+    for command in config.commands.values_mut() {
+        for (key, value) in &options.env {
+            if !is_positional(key) {
+                command.env.insert(key.clone(), value.clone());
             }
         }
-    };
+    }
 
     // Find command
     let command = match config.commands.get(name) {
         Some(cmd) => cmd,
         None => {
-            // Typo detection if command not found
-            if config.config.typo_detection {
-                use cmdrun::i18n::{get_message, MessageKey};
-                use cmdrun::utils::typo_detector::{TypoDetector, TypoDetectorConfig};
-
-                let detector = TypoDetector::with_config(TypoDetectorConfig {
-                    threshold: config.config.typo_threshold,
-                    max_suggestions: 5,
-                });
-
-                let available_commands: Vec<&str> =
-                    config.commands.keys().map(|s| s.as_str()).collect();
-                let suggestions = detector.suggest(name, &available_commands);
-
-                if !suggestions.is_empty() {
-                    let language = config.config.language;
-                    eprintln!(
-                        "{} '{}'",
-                        get_message(MessageKey::TypoUnknownCommand, language)
-                            .red()
-                            .bold(),
-                        name.bright_white()
-                    );
-                    eprintln!();
-                    eprintln!(
-                        "{} {}",
-                        "💡".bright_white(),
-                        get_message(MessageKey::TypoDidYouMean, language)
-                    );
-                    for (suggestion, distance) in suggestions {
-                        eprintln!(
-                            "  {} {} {}",
-                            "→".cyan(),
-                            suggestion.green().bold(),
-                            format!("(distance: {})", distance).dimmed()
-                        );
-                    }
-                    eprintln!();
-                    eprintln!(
-                        "{} {}",
-                        "ℹ".bright_white(),
-                        get_message(MessageKey::TypoRunHelp, language).dimmed()
-                    );
-                    anyhow::bail!(
-                        "{}",
-                        get_message(MessageKey::ErrorCommandNotFound, language)
-                    );
-                }
-            }
-
-            use cmdrun::config::Language;
-            use cmdrun::i18n::{get_message, MessageKey};
-            return Err(anyhow::anyhow!(
-                "{}",
-                get_message(MessageKey::ErrorCommandNotFound, Language::English)
-            ));
+            let available_commands: Vec<&str> =
+                config.commands.keys().map(|s| s.as_str()).collect();
+            return Err(unknown_command_error(name, &available_commands, &config));
         }
     };
+    let mode = options.execution_mode(command);
 
     // Create execution context with positional arguments
-    let mut env = config.config.env.clone();
+    //
+    // Precedence (lowest first): host env < global env_file < global env
+    // < command env_file < command env < --env < positional args. env_file values are
+    // read at execution time and never stored in the config; command env_file and env
+    // are applied by the executor.
+    let mut env = config.config.resolved_env()?;
+    env.extend(options.env.iter().cloned());
 
     // Add positional arguments as environment variables: 1, 2, 3, ...
     for (idx, arg) in args.iter().enumerate() {
         env.insert((idx + 1).to_string(), arg.clone());
     }
 
-    let ctx = ExecutionContext {
-        working_dir: config.config.working_dir.clone(),
-        env: env.clone(),
-        shell: detect_shell()
+    // --shell overrides the detected shell and the config setting for every
+    // command in the plan (they all share this context)
+    let shell = match &options.shell {
+        Some(shell) => find_shell(shell, config.config.language)?
+            .display()
+            .to_string(),
+        None => detect_shell()
             .map(|s| s.name)
             .unwrap_or_else(|_| config.config.shell.clone()),
-        timeout: command.timeout.or(Some(config.config.timeout)),
+    };
+
+    // --cwd replaces the configured working directory for the whole plan
+    let working_dir = match &options.cwd {
+        Some(dir) => {
+            let dir = std::env::current_dir()
+                .context("Failed to get current directory")?
+                .join(dir);
+            if !dir.is_dir() {
+                return Err(ExecutionError::WorkingDirNotFound {
+                    command: name.to_string(),
+                    path: dir.display().to_string(),
+                }
+                .into());
+            }
+            dir
+        }
+        None => config.config.working_dir.clone(),
+    };
+
+    let manifest_working_dir = working_dir.clone();
+    let ctx = ExecutionContext {
+        working_dir,
+        env: env.clone(),
+        shell,
+        timeout: options
+            .timeout
+            .or(command.timeout)
+            .or(Some(config.config.timeout)),
         strict: config.config.strict_mode,
         echo: true,
         color: true,
         language: config.config.language,
         allow_command_chaining: config.config.allow_command_chaining,
         allow_subshells: config.config.allow_subshells,
+        command_allowlist: config.config.command_allowlist.clone(),
+        allow_unsafe: options.allow_unsafe,
+        merge_stderr: options.merge_stderr || config.config.merge_stderr,
+        kill_grace_ms: config.config.kill_grace_ms,
+        max_parallel: options.jobs.or(config.config.max_parallel),
     };
 
-    let executor = CommandExecutor::new(ctx);
+    let hooks = HookRunner::new(&config, &ctx);
+    let mut executor = CommandExecutor::new(ctx)
+        .with_commands(config.commands.clone())
+        .with_args(args.clone());
 
-    // 並列実行が指定されている場合、依存関係を解決して並列実行
-    if parallel || command.parallel {
-        println!(
-            "{} {} (with parallel dependencies)",
-            "Running:".cyan().bold(),
-            command.description.bright_white()
-        );
+    // --print-command / --dry-run: show the literal shell input, secrets redacted
+    if options.print_command || options.dry_run {
+        let redactor = EnvRedactor::new(&config.config.redact_patterns)
+            .context("Invalid redact_patterns in [config]")?;
+
+        if options.dry_run {
+            for line in resolve_planned_commands(&executor, &config, name, mode, &redactor)? {
+                println!("{}", line);
+            }
+            return Ok(());
+        }
+
+        executor = executor.with_print_commands(redactor);
+    }
+
+    // --capture: everything known before the run; the result is added at the end
+    let manifest = match &options.capture {
+        Some(_) => {
+            let redactor = EnvRedactor::new(&config.config.redact_patterns)
+                .context("Invalid redact_patterns in [config]")?;
+            let mut manifest_env = env.clone();
+            let file_vars = command
+                .env_file_vars()
+                .with_context(|| format!("Invalid env_file (command '{}')", name))?;
+            for (key, value) in file_vars.iter().chain(&command.env) {
+                if !is_positional(key) {
+                    manifest_env.insert(key.clone(), value.clone());
+                }
+            }
+            let dir = match &command.working_dir {
+                Some(dir) => manifest_working_dir.join(dir),
+                None => manifest_working_dir,
+            };
+            Some(
+                RunManifest::new(
+                    name,
+                    &args,
+                    resolve_planned_commands(&executor, &config, name, mode, &redactor)?,
+                    &executor.resolve_shell(command)?,
+                    dir,
+                    redactor.redact(&manifest_env),
+                )
+                .with_command_shells(resolve_planned_shells(&executor, &config, name, mode)?),
+            )
+        }
+        None => None,
+    };
+
+    // --log: tee every command's output to the file
+    // --capture: also keep the output in memory for the manifest
+    let output_log = match (&options.log, options.capture.is_some()) {
+        (Some(path), capture) => {
+            let log = OutputLog::open(path)
+                .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+            Some(if capture { log.with_capture() } else { log })
+        }
+        (None, true) => Some(OutputLog::in_memory()),
+        (None, false) => None,
+    };
+    if let Some(log) = &output_log {
+        log.write_header(name, &args);
+        executor = executor.with_output_log(log.clone());
+    }
+
+    // Plugins may skip or block the command before anything runs; the same
+    // context is handed to the post/error hooks once it has finished
+    #[cfg(feature = "plugin-system")]
+    let mut plugin_hooks = None;
+    #[cfg(feature = "plugin-system")]
+    if let Some(plugin_manager) = plugin_manager.as_ref().filter(|m| m.enabled_count() > 0) {
+        let mut plugin_context = executor.plugin_context(name, command, &args)?;
+        if let cmdrun::plugin::PreExecuteOutcome::Skipped { plugin, reason } =
+            plugin_manager.execute_pre_hooks(&mut plugin_context)?
+        {
+            status::eprint(&format!(
+                "{} Command '{}' skipped by {} plugin{}",
+                "⏭".yellow().bold(),
+                name,
+                plugin,
+                if reason.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", reason)
+                }
+            ));
+            if let Err(e) = recorder.record_skipped(name, &args, &env) {
+                eprintln!("Warning: Failed to record command history: {}", e);
+            }
+            return Ok(());
+        }
+        plugin_hooks = Some((plugin_manager, plugin_context));
+    }
 
-        let start = std::time::Instant::now();
+    hooks.before_all().await?;
 
-        // 依存関係グラフを構築
-        let dep_graph = DependencyGraph::new(&config);
+    // Ctrl+C で実行中の子プロセスを終了させる
+    let interrupt = executor.interrupt_handle();
+    let interrupt_listener = spawn_interrupt_listener(interrupt.clone());
+    let started = std::time::Instant::now();
+
+    // after_all はコマンドが失敗しても実行する
+    let outcome: Result<()> = async {
+        // 並列・逐次実行が指定されている場合、依存関係を解決してグループ単位で実行
+        if mode != ExecutionMode::Direct {
+            let kind = if mode == ExecutionMode::Parallel {
+                "parallel"
+            } else {
+                "sequential"
+            };
+            status::print(&format!(
+                "{} {} (with {} dependencies)",
+                "Running:".cyan().bold(),
+                command.description.bright_white(),
+                kind
+            ));
 
-        // 循環依存チェック
-        dep_graph.check_cycles()?;
+            let start = std::time::Instant::now();
+            tracing::debug!("Running command '{}' with {} dependencies", name, kind);
 
-        // 実行グループを解決
-        let groups = dep_graph.resolve(name)?;
+            // 依存関係グラフを構築
+            let dep_graph = DependencyGraph::new(&config);
 
-        println!(
-            "{} Execution plan: {} groups",
-            "📋".bright_white(),
-            groups.len()
-        );
+            // 循環依存チェック
+            dep_graph.check_cycles()?;
 
-        // 各グループを順次実行（グループ内は並列）
-        for (idx, group) in groups.iter().enumerate() {
-            println!(
-                "{} Group {}/{} ({} commands)",
-                "▶".blue().bold(),
-                idx + 1,
-                groups.len(),
-                group.commands.len()
-            );
+            // 実行グループを解決（逐次実行では 1 グループ 1 コマンドに分割）
+            let mut groups = dep_graph.resolve(name)?;
+            if mode == ExecutionMode::Sequential {
+                groups = groups
+                    .into_iter()
+                    .flat_map(|group| group.commands)
+                    .map(|cmd_name| ExecutionGroup {
+                        commands: vec![cmd_name],
+                    })
+                    .collect();
+            }
 
-            // グループ内のコマンドを取得
-            let commands: Vec<_> = group
-                .commands
+            // 実行前にすべての確認を済ませる（途中で拒否されて中途半端に実行されないように）
+            let names: Vec<&str> = groups
                 .iter()
-                .filter_map(|cmd_name| config.commands.get(*cmd_name))
+                .flat_map(|group| group.commands.iter().copied())
                 .collect();
+            let skipped = confirm_commands(&names, &config, options.yes)?;
+            recorder.set_confirm_skipped(skipped);
+
+            status::print(&format!(
+                "{} Execution plan: {} groups",
+                "📋".bright_white(),
+                groups.len()
+            ));
 
-            // 並列実行
-            let results = executor.execute_parallel(&commands).await?;
+            // --profile 用の実行時間
+            let mut timings: Vec<CommandTiming> = Vec::new();
 
-            // 結果チェックと履歴記録
-            for (cmd_idx, result) in results.iter().enumerate() {
-                let cmd_name = group.commands[cmd_idx];
-                let duration_ms = result.duration.as_millis() as i64;
+            // 各グループを順次実行（グループ内は並列）
+            for (idx, group) in groups.iter().enumerate() {
+                status::print(&format!(
+                    "{} Group {}/{} ({} commands)",
+                    "▶".blue().bold(),
+                    idx + 1,
+                    groups.len(),
+                    group.commands.len()
+                ));
 
-                // 各コマンドの履歴を記録
-                if let Err(e) = recorder.record(
-                    cmd_name,
+                // グループ内のコマンドを取得
+                let (names, commands): (Vec<&str>, Vec<_>) = group
+                    .commands
+                    .iter()
+                    .filter_map(|cmd_name| {
+                        config.commands.get(*cmd_name).map(|cmd| (*cmd_name, cmd))
+                    })
+                    .unzip();
+
+                // 並列実行（after_each は失敗時も実行）
+                hooks.before_each(&group.commands).await?;
+                let results = if commands.len() > 1 && !status::is_quiet() {
+                    // 実行中のコマンドを進捗表示（端末でなければ完了ごとに 1 行）
+                    let progress = ParallelProgress::start(commands.len(), ProgressMode::detect());
+                    let results = executor
+                        .execute_parallel_with_progress(&commands, &names, &progress)
+                        .await;
+                    progress.finish();
+                    results
+                } else {
+                    executor.execute_parallel(&commands).await
+                };
+                let after_each = hooks.after_each(&group.commands).await;
+                let results = results?;
+                // Ctrl+C で終了したコマンドは失敗ではなく中断として扱う
+                if interrupt.is_interrupted() {
+                    return Err(ExecutionError::Interrupted.into());
+                }
+
+                // 結果チェックと履歴記録
+                for (cmd_idx, result) in results.iter().enumerate() {
+                    let cmd_name = group.commands[cmd_idx];
+                    let duration_ms = result.duration.as_millis() as i64;
+                    timings.push(CommandTiming {
+                        name: cmd_name,
+                        duration: result.duration,
+                        parallel: group.commands.len() > 1,
+                    });
+
+                    // 各コマンドの履歴を記録（条件が偽でスキップされたものは区別する）
+                    let recorded = if result.skipped {
+                        recorder.record_skipped(cmd_name, &args, &env)
+                    } else {
+                        recorder.record(
+                            cmd_name,
+                            &args,
+                            &env,
+                            duration_ms,
+                            result.exit_code,
+                            result.success,
+                        )
+                    };
+                    if let Err(e) = recorded {
+                        eprintln!("Warning: Failed to record command history: {}", e);
+                    }
+
+                    if !result.success {
+                        // Record failure state before bailing
+                        let _id = recorder.record(
+                            name,
+                            &args,
+                            &env,
+                            result.duration.as_millis() as i64,
+                            result.exit_code,
+                            false,
+                        );
+                        return Err(command_failed(
+                            cmd_name,
+                            result.exit_code,
+                            config.config.language,
+                        ));
+                    }
+                }
+                after_each?;
+            }
+
+            let total_duration = start.elapsed();
+            let duration_ms = total_duration.as_millis() as i64;
+
+            // メインコマンドの履歴を記録（すべて成功した場合）
+            if let Err(e) = recorder.record(name, &args, &env, duration_ms, 0, true) {
+                eprintln!("Warning: Failed to record main command history: {}", e);
+            }
+
+            status::print(&format!(
+                "{} All commands completed in {:.2}s",
+                "✓".green().bold(),
+                total_duration.as_secs_f64()
+            ));
+
+            if let Some(format) = options.profile {
+                let durations = timings.iter().map(|t| (t.name, t.duration)).collect();
+                let critical_path = dep_graph.critical_path(name, &durations);
+                print_profile(&mut timings, &critical_path, total_duration, format);
+            }
+        } else {
+            // 逐次実行（従来の動作）
+            status::print(&format!(
+                "{} {}",
+                "Running:".cyan().bold(),
+                command.description.bright_white()
+            ));
+
+            tracing::debug!("Running command '{}'", name);
+
+            let skipped = confirm_commands(&[name], &config, options.yes)?;
+            recorder.set_confirm_skipped(skipped);
+
+            // Execute and always record history (even on failure)
+            hooks.before_each(&[name]).await?;
+            let result = executor.execute(command).await;
+            let after_each = hooks.after_each(&[name]).await;
+            let result = match result {
+                Ok(r) => r,
+                Err(e) => {
+                    tracing::debug!("Command '{}' failed: {}", name, e);
+                    // Record failed execution in history before returning error
+                    // (中断は後でまとめて記録する)
+                    if !interrupt.is_interrupted() {
+                        let _ = recorder.record(name, &args, &env, 0, 1, false);
+                    }
+                    return Err(e.into());
+                }
+            };
+            if interrupt.is_interrupted() {
+                return Err(ExecutionError::Interrupted.into());
+            }
+
+            tracing::debug!(
+                "Command '{}' finished with exit code {} in {:?}",
+                name,
+                result.exit_code,
+                result.duration
+            );
+
+            let duration_ms = result.duration.as_millis() as i64;
+
+            // 履歴を記録（条件が偽でスキップされた場合は区別する）
+            let recorded = if result.skipped {
+                recorder.record_skipped(name, &args, &env)
+            } else {
+                recorder.record(
+                    name,
                     &args,
                     &env,
                     duration_ms,
                     result.exit_code,
                     result.success,
-                ) {
-                    eprintln!("Warning: Failed to record command history: {}", e);
-                }
+                )
+            };
+            if let Err(e) = recorded {
+                eprintln!("Warning: Failed to record command history: {}", e);
+            }
 
-                if !result.success {
-                    // Record failure state before bailing
-                    let _id = recorder.record(
-                        name,
-                        &args,
-                        &env,
-                        result.duration.as_millis() as i64,
-                        result.exit_code,
-                        false,
-                    );
-                    use cmdrun::i18n::{get_message, MessageKey};
-                    anyhow::bail!(
-                        "{} {}",
-                        get_message(
-                            MessageKey::ErrorCommandExecutionFailed,
-                            config.config.language
-                        ),
-                        result.exit_code
-                    );
+            if !result.success {
+                return Err(command_failed(
+                    name,
+                    result.exit_code,
+                    config.config.language,
+                ));
+            }
+            // スキップ時は executor 側で既に通知済み
+            if !result.skipped {
+                status::print(&format!(
+                    "{} Completed in {:.2}s",
+                    "✓".green().bold(),
+                    result.duration.as_secs_f64()
+                ));
+            }
+            after_each?;
+
+            if let Some(format) = options.profile {
+                let mut timings = vec![CommandTiming {
+                    name,
+                    duration: result.duration,
+                    parallel: false,
+                }];
+                print_profile(&mut timings, &[name], result.duration, format);
+            }
+        }
+
+        Ok(())
+    }
+    .await;
+    interrupt_listener.abort();
+
+    let exit_code = outcome
+        .as_ref()
+        .err()
+        .map_or(0, cmdrun::error::process_exit_code);
+
+    #[cfg(feature = "plugin-system")]
+    let finish_hooks = match &plugin_hooks {
+        Some((plugin_manager, plugin_context)) => plugin_manager.execute_finish_hooks(
+            plugin_context,
+            &outcome,
+            exit_code,
+            started.elapsed(),
+        ),
+        None => Ok(()),
+    };
+    if interrupt.is_interrupted() {
+        eprintln!("{} Interrupted", "⏹".yellow().bold());
+        let duration_ms = started.elapsed().as_millis() as i64;
+        if let Err(e) = recorder.record_interrupted(name, &args, &env, duration_ms) {
+            eprintln!("Warning: Failed to record command history: {}", e);
+        }
+    } else if options.notify || config.config.notify_on_completion {
+        notify::notify_completion(
+            &notify::DesktopNotifier,
+            name,
+            outcome.is_ok(),
+            exit_code,
+            started.elapsed(),
+            config.config.notify_threshold_ms,
+        );
+    }
+
+    if let (Some(manifest), Some(path)) = (manifest, &options.capture) {
+        let output = output_log
+            .as_ref()
+            .map(OutputLog::captured_lines)
+            .unwrap_or_default();
+        let outcome = RunOutcome {
+            exit_code,
+            duration: started.elapsed(),
+            interrupted: interrupt.is_interrupted(),
+        };
+        if let Err(e) = manifest.finish(outcome, output).write_to(path) {
+            eprintln!("Warning: Failed to write run manifest: {:#}", e);
+        }
+    }
+
+    let after_all = hooks.after_all().await;
+    if let Some(log) = &output_log {
+        if let Err(e) = log.flush() {
+            eprintln!(
+                "Warning: Failed to flush log file {}: {}",
+                log.path().unwrap_or(std::path::Path::new("")).display(),
+                e
+            );
+        }
+    }
+    outcome?;
+    #[cfg(feature = "plugin-system")]
+    finish_hooks?;
+    after_all?;
+
+    Ok(())
+}
+
+/// 最初の SIGINT（Ctrl+C）で `handle` を中断状態にするタスクを起動
+///
+/// シグナルハンドラは呼び出し時点で登録されるため、戻った後の Ctrl+C で
+/// cmdrun 自体が子プロセスを残して終了することはない
+fn spawn_interrupt_listener(handle: InterruptHandle) -> tokio::task::JoinHandle<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::interrupt()) {
+            Ok(mut sigint) => tokio::spawn(async move {
+                if sigint.recv().await.is_some() {
+                    handle.interrupt();
                 }
+            }),
+            Err(e) => {
+                tracing::debug!("Failed to install SIGINT handler: {}", e);
+                tokio::spawn(async {})
             }
         }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                handle.interrupt();
+            }
+        })
+    }
+}
 
-        let total_duration = start.elapsed();
-        let duration_ms = total_duration.as_millis() as i64;
+/// Wall-clock time of one executed command, for `run --profile`
+struct CommandTiming<'a> {
+    name: &'a str,
+    duration: std::time::Duration,
+    /// Ran in a parallel group together with other commands
+    parallel: bool,
+}
 
-        // メインコマンドの履歴を記録（すべて成功した場合）
-        if let Err(e) = recorder.record(name, &args, &env, duration_ms, 0, true) {
-            eprintln!("Warning: Failed to record main command history: {}", e);
+/// Print the `run --profile` report, slowest command first
+fn print_profile(
+    timings: &mut [CommandTiming],
+    critical_path: &[&str],
+    total: std::time::Duration,
+    format: ProfileFormat,
+) {
+    timings.sort_by(|a, b| b.duration.cmp(&a.duration).then(a.name.cmp(b.name)));
+    let critical_duration: std::time::Duration = timings
+        .iter()
+        .filter(|t| critical_path.contains(&t.name))
+        .map(|t| t.duration)
+        .sum();
+
+    match format {
+        ProfileFormat::Json => {
+            let report = serde_json::json!({
+                "total_ms": total.as_millis() as u64,
+                "commands": timings
+                    .iter()
+                    .map(|t| serde_json::json!({
+                        "name": t.name,
+                        "duration_ms": t.duration.as_millis() as u64,
+                        "parallel": t.parallel,
+                    }))
+                    .collect::<Vec<_>>(),
+                "critical_path": critical_path,
+                "critical_path_ms": critical_duration.as_millis() as u64,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).unwrap_or_default()
+            );
         }
+        ProfileFormat::Text => {
+            let width = timings
+                .iter()
+                .map(|t| t.name.chars().count())
+                .chain(std::iter::once("COMMAND".len()))
+                .max()
+                .unwrap_or(0);
 
-        println!(
-            "{} All commands completed in {:.2}s",
+            println!();
+            println!("{}", "Profile:".cyan().bold());
+            println!("  {:<width$}  {:>9}  PARALLEL", "COMMAND", "DURATION");
+            for timing in timings.iter() {
+                println!(
+                    "  {:<width$}  {:>8.2}s  {}",
+                    timing.name,
+                    timing.duration.as_secs_f64(),
+                    if timing.parallel { "yes" } else { "no" }
+                );
+            }
+            println!(
+                "{} ({:.2}s): {}",
+                "Critical path".cyan().bold(),
+                critical_duration.as_secs_f64(),
+                critical_path.join(" → ")
+            );
+        }
+    }
+}
+
+/// Run an ad-hoc command through the same pipeline as `cmdrun run`
+///
+/// The command is recorded in history as `exec:<program>`.
+async fn exec_command(
+    raw: Vec<String>,
+    timeout: Option<std::time::Duration>,
+    allow_unsafe: bool,
+    global_only: bool,
+    config_paths: Vec<std::path::PathBuf>,
+) -> Result<()> {
+    use cmdrun::config::schema::{Command, CommandSpec};
+
+    let storage = cmdrun::history::HistoryStorage::new()?;
+    let mut recorder = cmdrun::history::HistoryRecorder::with_storage(storage);
+
+    let config = load_run_config(global_only, config_paths).await?;
+    recorder
+        .set_redact_patterns(&config.config.redact_patterns)
+        .context("Invalid redact_patterns in [config]")?;
+    recorder
+        .set_retention(
+            config.config.history_max_entries,
+            config.config.history_max_age.as_deref(),
+        )
+        .context("Invalid history_max_age in [config]")?;
+
+    // A single argument is a shell command line; several are argv and keep their quoting
+    let cmd = if raw.len() == 1 {
+        raw[0].clone()
+    } else {
+        shell_words::join(&raw)
+    };
+    let program = cmd.split_whitespace().next().unwrap_or_default();
+    let name = format!("exec:{}", program);
+
+    let command = Command {
+        description: cmd.clone(),
+        cmd: CommandSpec::Single(cmd.clone()),
+        env: ahash::AHashMap::new(),
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        deps: vec![],
+        platform: vec![],
+        tags: vec![],
+        timeout,
+        parallel: false,
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    let env = config.config.resolved_env()?;
+    let ctx = ExecutionContext {
+        working_dir: config.config.working_dir.clone(),
+        env: env.clone(),
+        shell: detect_shell()
+            .map(|s| s.name)
+            .unwrap_or_else(|_| config.config.shell.clone()),
+        timeout: timeout.or(Some(config.config.timeout)),
+        strict: config.config.strict_mode,
+        echo: true,
+        color: true,
+        language: config.config.language,
+        allow_command_chaining: config.config.allow_command_chaining,
+        allow_subshells: config.config.allow_subshells,
+        command_allowlist: config.config.command_allowlist.clone(),
+        allow_unsafe,
+        merge_stderr: config.config.merge_stderr,
+        kill_grace_ms: config.config.kill_grace_ms,
+        max_parallel: config.config.max_parallel,
+    };
+
+    let executor = CommandExecutor::new(ctx);
+
+    status::print(&format!(
+        "{} {}",
+        "Running:".cyan().bold(),
+        cmd.bright_white()
+    ));
+    tracing::debug!("Running ad-hoc command '{}'", cmd);
+
+    // Record the raw command as args so history shows what was executed
+    let result = match executor.execute(&command).await {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::debug!("Ad-hoc command '{}' failed: {}", cmd, e);
+            let _ = recorder.record(&name, &raw, &env, 0, 1, false);
+            return Err(e.into());
+        }
+    };
+
+    let duration_ms = result.duration.as_millis() as i64;
+    if let Err(e) = recorder.record(
+        &name,
+        &raw,
+        &env,
+        duration_ms,
+        result.exit_code,
+        result.success,
+    ) {
+        eprintln!("Warning: Failed to record command history: {}", e);
+    }
+
+    if result.success {
+        status::print(&format!(
+            "{} Completed in {:.2}s",
             "✓".green().bold(),
-            total_duration.as_secs_f64()
-        );
+            result.duration.as_secs_f64()
+        ));
+        Ok(())
     } else {
-        // 逐次実行（従来の動作）
-        println!(
-            "{} {}",
-            "Running:".cyan().bold(),
-            command.description.bright_white()
-        );
+        Err(command_failed(
+            &name,
+            result.exit_code,
+            config.config.language,
+        ))
+    }
+}
 
-        // Execute and always record history (even on failure)
-        let result = match executor.execute(command).await {
-            Ok(r) => r,
-            Err(e) => {
-                // Record failed execution in history before returning error
-                let _ = recorder.record(name, &args, &env, 0, 1, false);
-                return Err(e.into());
+/// Load configuration for `run`/`exec` (with environment support)
+///
+/// Falls back to the global config when no local config is found.
+async fn load_run_config(
+    global_only: bool,
+    config_paths: Vec<std::path::PathBuf>,
+) -> Result<cmdrun::config::schema::CommandsConfig> {
+    // Load configuration (with environment support)
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+
+    // Try to load config (with environment), fallback to global-only if no local config
+    let config = match config_loader.load_with_environment().await {
+        Ok(cfg) => cfg,
+        // A config exists but failed to load (parse error, missing env_file, ...)
+        Err(e) if config_loader.has_local_config().await => return Err(e),
+        Err(_) => {
+            // If local config not found, try loading from global config
+            if let Some(global_dir) = dirs::config_dir() {
+                let global_path = global_dir.join("cmdrun").join("commands.toml");
+                if global_path.exists() {
+                    ConfigLoader::with_path(global_path)?.load().await?
+                } else {
+                    // Use default language (English) for error message when config not found
+                    use cmdrun::config::Language;
+                    use cmdrun::i18n::{get_message, MessageKey};
+                    return Err(cmdrun::error::config_not_found(
+                        global_path,
+                        get_message(MessageKey::ErrorNoConfigFileFound, Language::English),
+                    ));
+                }
+            } else {
+                use cmdrun::config::Language;
+                use cmdrun::i18n::{get_message, MessageKey};
+                anyhow::bail!(
+                    "{}",
+                    get_message(MessageKey::ErrorCannotDetermineConfigDir, Language::English)
+                );
             }
-        };
+        }
+    };
+
+    Ok(config)
+}
 
-        let duration_ms = result.duration.as_millis() as i64;
+/// Ask for confirmation of every command marked `confirm = true`
+///
+/// Returns whether any confirmation was skipped with `--yes`.
+fn confirm_commands(
+    names: &[&str],
+    config: &cmdrun::config::schema::CommandsConfig,
+    yes: bool,
+) -> Result<bool> {
+    use cmdrun::command::confirm::{confirm_command, ConfirmMode, ConfirmOutcome};
 
-        // 履歴を記録
-        if let Err(e) = recorder.record(
-            name,
-            &args,
-            &env,
-            duration_ms,
-            result.exit_code,
-            result.success,
-        ) {
-            eprintln!("Warning: Failed to record command history: {}", e);
+    let mode = ConfirmMode::detect(yes);
+    let mut skipped = false;
+    for name in names {
+        if let Some(command) = config.commands.get(*name) {
+            let outcome = confirm_command(name, command, mode, config.config.language)?;
+            skipped |= outcome == ConfirmOutcome::Skipped;
         }
+    }
+    Ok(skipped)
+}
 
-        if result.success {
-            println!(
-                "{} Completed in {:.2}s",
-                "✓".green().bold(),
-                result.duration.as_secs_f64()
+/// Build the error for an unknown command, printing typo suggestions if enabled
+fn unknown_command_error(
+    name: &str,
+    available_commands: &[&str],
+    config: &cmdrun::config::schema::CommandsConfig,
+) -> anyhow::Error {
+    // Typo detection if command not found
+    if config.config.typo_detection {
+        use cmdrun::i18n::{get_message, MessageKey};
+        use cmdrun::utils::typo_detector::{TypoDetector, TypoDetectorConfig};
+
+        let detector = TypoDetector::with_config(TypoDetectorConfig {
+            threshold: config.config.typo_threshold,
+            max_suggestions: 5,
+        });
+
+        let suggestions = detector.suggest(name, available_commands);
+
+        if !suggestions.is_empty() {
+            let language = config.config.language;
+            eprintln!(
+                "{} '{}'",
+                get_message(MessageKey::TypoUnknownCommand, language)
+                    .red()
+                    .bold(),
+                name.bright_white()
             );
-        } else {
-            use cmdrun::i18n::{get_message, MessageKey};
-            anyhow::bail!(
+            eprintln!();
+            eprintln!(
+                "{} {}",
+                "💡".bright_white(),
+                get_message(MessageKey::TypoDidYouMean, language)
+            );
+            for (suggestion, distance) in suggestions {
+                eprintln!(
+                    "  {} {} {}",
+                    "→".cyan(),
+                    suggestion.green().bold(),
+                    format!("(distance: {})", distance).dimmed()
+                );
+            }
+            eprintln!();
+            eprintln!(
                 "{} {}",
-                get_message(
-                    MessageKey::ErrorCommandExecutionFailed,
-                    config.config.language
-                ),
-                result.exit_code
+                "ℹ".bright_white(),
+                get_message(MessageKey::TypoRunHelp, language).dimmed()
             );
+            return command_not_found(name)
+                .context(get_message(MessageKey::ErrorCommandNotFound, language));
         }
     }
 
+    use cmdrun::config::Language;
+    use cmdrun::i18n::{get_message, MessageKey};
+    command_not_found(name).context(get_message(
+        MessageKey::ErrorCommandNotFound,
+        Language::English,
+    ))
+}
+
+/// Typed command failure carrying the localized "failed with exit code" message
+fn command_failed(name: &str, code: i32, language: cmdrun::config::Language) -> anyhow::Error {
+    use cmdrun::error::{CmdrunError, ExecutionError};
+    use cmdrun::i18n::{get_message, MessageKey};
+
+    anyhow::Error::new(CmdrunError::from(ExecutionError::CommandFailed {
+        command: name.to_string(),
+        code,
+    }))
+    .context(format!(
+        "{} {}",
+        get_message(MessageKey::ErrorCommandExecutionFailed, language),
+        code
+    ))
+}
+
+/// Typed command-not-found error (the caller adds the localized message)
+fn command_not_found(name: &str) -> anyhow::Error {
+    use cmdrun::error::{CmdrunError, ConfigError};
+
+    anyhow::Error::new(CmdrunError::from(ConfigError::CommandNotFound(
+        name.to_string(),
+    )))
+}
+
+/// Resolve `name` through `[aliases]`, expanding the alias' arguments and placeholders
+///
+/// Defined commands take precedence over aliases of the same name.
+fn resolve_alias(
+    name: &str,
+    args: Vec<String>,
+    config: &cmdrun::config::CommandsConfig,
+) -> Result<(String, Vec<String>)> {
+    use cmdrun::config::schema::AliasTarget;
+
+    let target = match config.aliases.get(name) {
+        Some(target) if !config.commands.contains_key(name) => target,
+        _ => return Ok((name.to_string(), args)),
+    };
+
+    let target = AliasTarget::parse(target)
+        .map_err(|e| anyhow::anyhow!("Invalid alias '{}': {}", name, e))?;
+    let args = target
+        .expand(&args)
+        .map_err(|e| anyhow::anyhow!("Alias '{}' {}", name, e))?;
+    tracing::debug!("Alias '{}' -> '{}' {:?}", name, target.command, args);
+
+    Ok((target.command, args))
+}
+
+/// Run a custom subcommand provided by a plugin
+#[cfg(feature = "plugin-system")]
+async fn run_plugin_command(
+    argv: Vec<String>,
+    global_only: bool,
+    config_paths: Vec<std::path::PathBuf>,
+) -> Result<()> {
+    use cmdrun::config::schema::{Command, CommandSpec};
+    use cmdrun::plugin::{PluginContext, PluginManager};
+
+    let (name, args) = match argv.split_first() {
+        Some((name, args)) => (name.clone(), args.to_vec()),
+        None => anyhow::bail!("No subcommand given"),
+    };
+
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
+    } else if global_only {
+        ConfigLoader::global_only()
+    } else {
+        ConfigLoader::new()
+    };
+    let mut config = config_loader.load_with_environment().await?;
+
+    let manager = PluginManager::load_for_config(&mut config)?;
+    let plugin_commands = manager
+        .as_ref()
+        .map(PluginManager::custom_commands)
+        .unwrap_or_default();
+    let Some(custom) = plugin_commands.iter().find(|c| c.name == name) else {
+        // Suggest built-in subcommands as well as plugin commands
+        use clap::CommandFactory;
+        let cli = Cli::command();
+        let available_commands: Vec<&str> = cli
+            .get_subcommands()
+            .filter(|sub| !sub.is_hide_set())
+            .map(|sub| sub.get_name())
+            .chain(plugin_commands.iter().map(|c| c.name.as_str()))
+            .collect();
+        return Err(unknown_command_error(&name, &available_commands, &config));
+    };
+
+    let context = PluginContext {
+        command_name: name.clone(),
+        command: Command {
+            description: custom.description.clone(),
+            cmd: CommandSpec::Single(name.clone()),
+            env: Default::default(),
+            env_file: None,
+            source_dir: None,
+            working_dir: None,
+            deps: vec![],
+            platform: vec![],
+            tags: vec![],
+            timeout: None,
+            parallel: false,
+            confirm: false,
+            confirm_message: None,
+            allow_chaining: None,
+            allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
+        },
+        command_spec: name.clone(),
+        args: args.clone(),
+        env: config.config.resolved_env()?,
+        working_dir: config.config.working_dir.display().to_string(),
+        data: Default::default(),
+    };
+
+    let result = custom.execute(&args, &context)?;
+
+    print!("{}", result.stdout);
+    eprint!("{}", result.stderr);
+
+    if result.exit_code != 0 {
+        return Err(command_failed(
+            &name,
+            result.exit_code,
+            config.config.language,
+        ));
+    }
+
     Ok(())
 }
 
 /// List available commands
 async fn list_commands(
     verbose: bool,
+    grouped: bool,
+    category: Option<String>,
+    no_truncate: bool,
     global: bool,
-    config_path: Option<std::path::PathBuf>,
+    config_paths: Vec<std::path::PathBuf>,
 ) -> Result<()> {
-    let config_loader = if let Some(path) = config_path {
-        ConfigLoader::with_path(path)?
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
     } else if global {
         ConfigLoader::global_only()
     } else {
         ConfigLoader::new()
     };
-    let config = config_loader.load_with_environment().await?;
+    let mut config = config_loader.load_with_environment().await?;
+    cmdrun::plugin::PluginManager::load_for_config(&mut config)?;
     let lang = config.config.language;
 
     if config.commands.is_empty() {
@@ -604,6 +1747,29 @@ async fn list_commands(
         return Ok(());
     }
 
+    let mut commands: Vec<_> = config
+        .commands
+        .iter()
+        .filter(|(_, cmd)| {
+            category
+                .as_deref()
+                .map_or(true, |c| category_label(cmd).eq_ignore_ascii_case(c))
+        })
+        .collect();
+    commands.sort_by_key(|(name, _)| *name);
+
+    if commands.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "No commands in category '{}'",
+                category.as_deref().unwrap_or_default()
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+
     println!(
         "{}",
         cmdrun::i18n::get_message(cmdrun::i18n::MessageKey::ListAvailableCommands, lang)
@@ -612,50 +1778,112 @@ async fn list_commands(
     );
     println!();
 
-    let mut commands: Vec<_> = config.commands.iter().collect();
-    commands.sort_by_key(|(name, _)| *name);
+    let layout = ListLayout {
+        formatter: OutputFormatter::new(),
+        name_width: name_column_width(commands.iter().map(|(name, _)| name.as_str())),
+        max_width: (!no_truncate).then(terminal_width),
+    };
 
+    if !grouped {
+        for (name, cmd) in commands {
+            print_list_entry(&layout, name, cmd, verbose, lang);
+        }
+        return Ok(());
+    }
+
+    // Group by category; uncategorized commands go last under "Other"
+    let mut groups: std::collections::BTreeMap<(bool, &str), Vec<_>> =
+        std::collections::BTreeMap::new();
     for (name, cmd) in commands {
-        if verbose {
-            println!("  {} - {}", name.green().bold(), cmd.description);
-            println!(
-                "    {}",
-                cmdrun::i18n::get_message(cmdrun::i18n::MessageKey::LabelCommand, lang).dimmed()
-            );
-            match &cmd.cmd {
-                cmdrun::config::schema::CommandSpec::Single(c) => {
-                    println!("      {}", c);
-                }
-                cmdrun::config::schema::CommandSpec::Multiple(cmds) => {
-                    for c in cmds {
-                        println!("      {}", c);
-                    }
-                }
-                cmdrun::config::schema::CommandSpec::Platform(_) => {
-                    println!("      {} Platform-specific", "[...]".dimmed());
-                }
-            }
-            if !cmd.deps.is_empty() {
-                println!(
-                    "    {} {:?}",
-                    cmdrun::i18n::get_message(cmdrun::i18n::MessageKey::LabelDependencies, lang)
-                        .dimmed(),
-                    cmd.deps
-                );
-            }
+        let label = category_label(cmd);
+        groups
+            .entry((label == "Other", label))
+            .or_default()
+            .push((name, cmd));
+    }
+
+    for ((_, label), entries) in groups {
+        println!("{}", label.yellow().bold());
+        for (name, cmd) in entries {
+            print_list_entry(&layout, name, cmd, verbose, lang);
+        }
+        if !verbose {
             println!();
-        } else {
-            println!("  {} - {}", name.green().bold(), cmd.description);
         }
     }
 
     Ok(())
 }
 
-/// List command names for shell completion (with descriptions)
-async fn list_completion(global_only: bool, config_path: Option<std::path::PathBuf>) -> Result<()> {
-    let config_loader = if let Some(path) = config_path {
-        ConfigLoader::with_path(path)?
+/// Category shown by `list --grouped` and matched by `list --category`
+fn category_label(cmd: &cmdrun::config::schema::Command) -> &str {
+    cmd.category.as_deref().unwrap_or("Other")
+}
+
+/// Column layout shared by every entry of one `list` call
+struct ListLayout {
+    formatter: OutputFormatter,
+    name_width: usize,
+    /// Truncate descriptions to this width (`None` with `--no-truncate`)
+    max_width: Option<usize>,
+}
+
+fn print_list_entry(
+    layout: &ListLayout,
+    name: &str,
+    cmd: &cmdrun::config::schema::Command,
+    verbose: bool,
+    lang: cmdrun::config::Language,
+) {
+    println!(
+        "{}",
+        layout
+            .formatter
+            .list_entry(name, &cmd.description, layout.name_width, layout.max_width)
+    );
+    if !verbose {
+        return;
+    }
+
+    println!(
+        "    {}",
+        cmdrun::i18n::get_message(cmdrun::i18n::MessageKey::LabelCommand, lang).dimmed()
+    );
+    match &cmd.cmd {
+        cmdrun::config::schema::CommandSpec::Single(c) => {
+            println!("      {}", c);
+        }
+        cmdrun::config::schema::CommandSpec::Multiple(cmds) => {
+            for c in cmds {
+                println!("      {}", c);
+            }
+        }
+        cmdrun::config::schema::CommandSpec::Platform(_) => {
+            println!("      {} Platform-specific", "[...]".dimmed());
+        }
+        cmdrun::config::schema::CommandSpec::Script { file } => {
+            println!("      {} {}", "[script]".dimmed(), file);
+        }
+    }
+    if !cmd.deps.is_empty() {
+        println!(
+            "    {} {:?}",
+            cmdrun::i18n::get_message(cmdrun::i18n::MessageKey::LabelDependencies, lang).dimmed(),
+            cmd.deps
+        );
+    }
+    println!();
+}
+
+/// Load the configuration for shell completion
+///
+/// Returns `None` (complete nothing) when no usable config is found.
+async fn load_completion_config(
+    global_only: bool,
+    config_paths: Vec<std::path::PathBuf>,
+) -> Result<Option<cmdrun::config::schema::CommandsConfig>> {
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
     } else if global_only {
         ConfigLoader::global_only()
     } else {
@@ -672,23 +1900,99 @@ async fn list_completion(global_only: bool, config_path: Option<std::path::PathB
                 if global_path.exists() {
                     match ConfigLoader::with_path(global_path)?.load().await {
                         Ok(cfg) => cfg,
-                        Err(_) => return Ok(()), // Global config invalid, return empty
+                        Err(_) => return Ok(None), // Global config invalid, return empty
                     }
                 } else {
-                    return Ok(()); // No global config, return empty
+                    return Ok(None); // No global config, return empty
                 }
             } else {
-                return Ok(()); // Can't determine config directory, return empty
+                return Ok(None); // Can't determine config directory, return empty
             }
         }
     };
 
+    Ok(Some(config))
+}
+
+/// List command names for shell completion (with descriptions)
+async fn list_completion(global_only: bool, config_paths: Vec<std::path::PathBuf>) -> Result<()> {
+    let Some(config) = load_completion_config(global_only, config_paths).await? else {
+        return Ok(());
+    };
+
     // Output command names with descriptions (format: "name:description")
-    // This format is compatible with bash/zsh completion systems
+    // Colons in either part are escaped as `\:`, so the separator is the first
+    // unescaped colon (zsh `_describe` convention; the other scripts unescape)
     for (name, cmd) in config.commands.iter() {
-        // Escape colons in description to avoid parsing issues
-        let desc = cmd.description.replace(':', "\\:");
-        println!("{}:{}", name, desc);
+        println!(
+            "{}:{}",
+            name.replace(':', "\\:"),
+            cmd.description.replace(':', "\\:")
+        );
+    }
+
+    Ok(())
+}
+
+/// List `arg_completions` candidates for one positional argument (1-based)
+async fn list_arg_completion(
+    name: &str,
+    position: usize,
+    global_only: bool,
+    config_paths: Vec<std::path::PathBuf>,
+) -> Result<()> {
+    let Some(config) = load_completion_config(global_only, config_paths).await? else {
+        return Ok(());
+    };
+
+    let choices = config
+        .commands
+        .get(name)
+        .and_then(|cmd| cmd.arg_completions.get(position.checked_sub(1)?));
+    for choice in choices.into_iter().flatten() {
+        println!("{}", choice);
+    }
+
+    Ok(())
+}
+
+/// List the distinct tags across all commands (sorted, one per line)
+async fn list_tag_completion(
+    global_only: bool,
+    config_paths: Vec<std::path::PathBuf>,
+) -> Result<()> {
+    let Some(config) = load_completion_config(global_only, config_paths).await? else {
+        return Ok(());
+    };
+
+    let tags: std::collections::BTreeSet<&str> = config
+        .commands
+        .values()
+        .flat_map(|cmd| cmd.tags.iter().map(String::as_str))
+        .collect();
+    for tag in tags {
+        println!("{}", tag);
+    }
+
+    Ok(())
+}
+
+/// List the distinct categories across all commands (sorted, one per line)
+async fn list_category_completion(
+    global_only: bool,
+    config_paths: Vec<std::path::PathBuf>,
+) -> Result<()> {
+    let Some(config) = load_completion_config(global_only, config_paths).await? else {
+        return Ok(());
+    };
+
+    let categories: std::collections::BTreeSet<&str> = config
+        .commands
+        .values()
+        .filter_map(|cmd| cmd.category.as_deref())
+        .collect();
+    for category in categories {
+        println!("{}", category);
     }
 
     Ok(())
@@ -701,10 +2005,10 @@ async fn show_dependency_graph(
     output_path: Option<std::path::PathBuf>,
     show_groups: bool,
     global_only: bool,
-    config_path: Option<std::path::PathBuf>,
+    config_paths: Vec<std::path::PathBuf>,
 ) -> Result<()> {
-    let config_loader = if let Some(path) = config_path {
-        ConfigLoader::with_path(path)?
+    let config_loader = if !config_paths.is_empty() {
+        ConfigLoader::with_paths(config_paths)?
     } else if global_only {
         ConfigLoader::global_only()
     } else {
@@ -752,11 +2056,20 @@ async fn show_dependency_graph(
 }
 
 /// Initialize logging
-fn init_logging(verbose: u8, color_choice: ColorChoice) {
+fn init_logging(
+    verbose: u8,
+    quiet: bool,
+    color_choice: ColorChoice,
+    log_file: Option<&std::path::Path>,
+) {
     use std::io::IsTerminal;
+    use tracing_subscriber::filter::{EnvFilter, LevelFilter};
     use tracing_subscriber::fmt::format::FmtSpan;
+    use tracing_subscriber::prelude::*;
 
+    // --quiet hides info logs unless -v is given as well
     let level = match verbose {
+        0 if quiet => "warn",
         0 => "info",
         1 => "debug",
         _ => "trace",
@@ -772,10 +2085,29 @@ fn init_logging(verbose: u8, color_choice: ColorChoice) {
         }
     };
 
-    let result = tracing_subscriber::fmt()
-        .with_env_filter(level)
+    let console_layer = tracing_subscriber::fmt::layer()
         .with_span_events(FmtSpan::CLOSE)
         .with_ansi(use_colors)
+        .with_filter(EnvFilter::new(level));
+
+    // --log-file: capture everything at trace level, independent of the console
+    let file_layer = log_file.and_then(|path| match fs::File::create(path) {
+        Ok(file) => Some(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_span_events(FmtSpan::CLOSE)
+                .with_writer(std::sync::Mutex::new(file))
+                .with_filter(LevelFilter::TRACE),
+        ),
+        Err(e) => {
+            eprintln!("Warning: Failed to open log file {}: {}", path.display(), e);
+            None
+        }
+    });
+
+    let result = tracing_subscriber::registry()
+        .with(console_layer)
+        .with(file_layer)
         .try_init();
 
     if let Err(e) = result {
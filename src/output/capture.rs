@@ -0,0 +1,180 @@
+//! Output capture
+//!
+//! `cmdrun run --log <path>` 用に、コマンドの stdout/stderr をファイルへ追記する。
+//! 各行は書き込み直後にフラッシュするため、コマンドが失敗して途中終了しても
+//! それまでの出力はファイルに残る。ANSIエスケープシーケンスは除去して書き込む。
+//! `--capture` 用に出力行をメモリにも保持できる。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+/// ANSIエスケープシーケンス（CSI / OSC）
+static ANSI_ESCAPE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]")
+        .expect("valid ANSI escape regex")
+});
+
+/// ANSIエスケープシーケンスを除去
+pub fn strip_ansi(text: &str) -> String {
+    ANSI_ESCAPE.replace_all(text, "").into_owned()
+}
+
+/// 出力キャプチャ先のログファイル
+///
+/// 並列実行時も含め、stdout/stderr の読み取りタスク間で共有する。
+/// 書き込みに失敗した場合は警告を出して以降の書き込みを停止する
+#[derive(Debug, Clone)]
+pub struct OutputLog {
+    /// ログファイルのパス（メモリのみの場合は `None`）
+    path: Option<Arc<PathBuf>>,
+    file: Arc<Mutex<Option<File>>>,
+    /// メモリに保持した出力行（`with_capture` 指定時のみ）
+    captured: Option<Arc<Mutex<Vec<String>>>>,
+}
+
+impl OutputLog {
+    /// ログファイルを追記モードで開く（存在しなければ作成）
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            path: Some(Arc::new(path.to_path_buf())),
+            file: Arc::new(Mutex::new(Some(file))),
+            captured: None,
+        })
+    }
+
+    /// ファイルに書き込まず、出力行をメモリにのみ保持する
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            file: Arc::new(Mutex::new(None)),
+            captured: None,
+        }
+        .with_capture()
+    }
+
+    /// 出力行をメモリにも保持する（`captured_lines` で取得）
+    pub fn with_capture(mut self) -> Self {
+        self.captured = Some(Arc::new(Mutex::new(Vec::new())));
+        self
+    }
+
+    /// ログファイルのパス
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref().map(PathBuf::as_path)
+    }
+
+    /// メモリに保持した出力行（ヘッダーは含まない）
+    pub fn captured_lines(&self) -> Vec<String> {
+        match &self.captured {
+            Some(lines) => lines.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            None => Vec::new(),
+        }
+    }
+
+    /// 実行ヘッダーを書き込む（タイムスタンプ・コマンド名・引数）
+    pub fn write_header(&self, command: &str, args: &[String]) {
+        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%:z");
+        let mut header = format!("=== {} cmdrun run {}", timestamp, command);
+        for arg in args {
+            header.push(' ');
+            header.push_str(arg);
+        }
+        header.push_str(" ===");
+        self.write_raw(&strip_ansi(&header));
+    }
+
+    /// 出力を1行書き込む（ANSIエスケープは除去）
+    pub fn write_line(&self, line: &str) {
+        let line = strip_ansi(line);
+        if let Some(lines) = &self.captured {
+            lines
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(line.clone());
+        }
+        self.write_raw(&line);
+    }
+
+    fn write_raw(&self, line: &str) {
+        let mut guard = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(file) = guard.as_mut() {
+            if let Err(e) = writeln!(file, "{}", line).and_then(|_| file.flush()) {
+                warn!(
+                    "Failed to write to log file '{}', disabling it: {}",
+                    self.path().unwrap_or(Path::new("")).display(),
+                    e
+                );
+                *guard = None;
+            }
+        }
+    }
+
+    /// バッファをフラッシュ
+    pub fn flush(&self) -> io::Result<()> {
+        let mut guard = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        match guard.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\x1b[1;31merror\x1b[0m: bad"), "error: bad");
+        assert_eq!(strip_ansi("\x1b]0;title\x07plain"), "plain");
+        assert_eq!(strip_ansi("\x1b[2K\x1b[?25lprogress"), "progress");
+        assert_eq!(strip_ansi("no escapes"), "no escapes");
+    }
+
+    #[test]
+    fn test_output_log_appends() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("logs").join("run.log");
+
+        let log = OutputLog::open(&path).unwrap();
+        log.write_header("build", &["--release".to_string()]);
+        log.write_line("\x1b[32mCompiling\x1b[0m cmdrun");
+        drop(log);
+
+        let log = OutputLog::open(&path).unwrap();
+        log.write_header("test", &[]);
+        log.write_line("ok");
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("=== "));
+        assert!(lines[0].ends_with(" cmdrun run build --release ==="));
+        assert_eq!(lines[1], "Compiling cmdrun");
+        assert!(lines[2].ends_with(" cmdrun run test ==="));
+        assert_eq!(lines[3], "ok");
+    }
+
+    #[test]
+    fn test_output_log_captures_lines() {
+        let log = OutputLog::in_memory();
+        log.write_header("build", &[]);
+        log.write_line("\x1b[32mCompiling\x1b[0m cmdrun");
+        log.clone().write_line("Finished");
+
+        assert!(log.path().is_none());
+        assert_eq!(log.captured_lines(), vec!["Compiling cmdrun", "Finished"]);
+    }
+}
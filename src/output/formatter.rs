@@ -3,6 +3,14 @@
 //! カラー出力とメッセージフォーマット機能
 
 use colored::*;
+use std::borrow::Cow;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// 端末幅を取得できない場合（パイプ出力など）の表示幅
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// 切り詰め時に説明として最低限確保する表示幅
+const MIN_DESCRIPTION_WIDTH: usize = 10;
 
 /// 出力フォーマッター
 #[derive(Debug, Clone)]
@@ -137,6 +145,39 @@ impl OutputFormatter {
         }
     }
 
+    /// コマンド一覧の 1 行（`list` 用）
+    ///
+    /// 名前を `name_width` に揃えて説明を同じ列から表示する。
+    /// `max_width` を指定すると、行がその表示幅に収まるよう説明を `…` で切り詰める
+    pub fn list_entry(
+        &self,
+        name: &str,
+        description: &str,
+        name_width: usize,
+        max_width: Option<usize>,
+    ) -> String {
+        let styled_name = if self.colors_enabled {
+            name.green().bold().to_string()
+        } else {
+            name.to_string()
+        };
+        if description.is_empty() {
+            return format!("  {}", styled_name);
+        }
+
+        let padding = " ".repeat(name_width.saturating_sub(name.width()));
+        let description = match max_width {
+            Some(max_width) => {
+                let available = max_width
+                    .saturating_sub(2 + name_width + 2)
+                    .max(MIN_DESCRIPTION_WIDTH);
+                truncate_to_width(description, available)
+            }
+            None => Cow::Borrowed(description),
+        };
+        format!("  {}{}  {}", styled_name, padding, description)
+    }
+
     /// コマンド出力（stdout）
     pub fn command_output(&self, output: &str) -> String {
         if output.is_empty() {
@@ -189,6 +230,49 @@ impl Default for OutputFormatter {
     }
 }
 
+/// 標準出力の端末幅（端末でない場合は [`DEFAULT_TERMINAL_WIDTH`]）
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(width), _)| width as usize)
+        .filter(|width| *width > 0)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+/// 名前の列幅（最も長い名前の表示幅）
+pub fn name_column_width<'a>(names: impl IntoIterator<Item = &'a str>) -> usize {
+    names
+        .into_iter()
+        .map(UnicodeWidthStr::width)
+        .max()
+        .unwrap_or(0)
+}
+
+/// 表示幅が `max_width` を超える場合に末尾を `…` で切り詰める
+///
+/// 全角文字は幅 2 として数える
+pub fn truncate_to_width(text: &str, max_width: usize) -> Cow<'_, str> {
+    if text.width() <= max_width {
+        return Cow::Borrowed(text);
+    }
+
+    // `…` の分（幅 1）を残す
+    let budget = max_width.saturating_sub(1);
+    let mut width = 0;
+    let mut truncated = String::new();
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if width + char_width > budget {
+            break;
+        }
+        width += char_width;
+        truncated.push(c);
+    }
+    if max_width > 0 {
+        truncated.push('…');
+    }
+    Cow::Owned(truncated)
+}
+
 /// 簡易フォーマット関数（グローバル）
 /// 成功メッセージをフォーマット
 pub fn format_success(message: &str) -> String {
@@ -268,6 +352,62 @@ mod tests {
         assert!(item.contains("build"));
     }
 
+    fn plain() -> OutputFormatter {
+        OutputFormatter {
+            colors_enabled: false,
+            verbose: false,
+        }
+    }
+
+    #[test]
+    fn test_name_column_width() {
+        assert_eq!(name_column_width(["build", "test", "docker-clean"]), 12);
+        assert_eq!(name_column_width(["ビルド", "test"]), 6);
+        assert_eq!(name_column_width(std::iter::empty()), 0);
+    }
+
+    #[test]
+    fn test_list_entries_align_descriptions() {
+        let formatter = plain();
+        let width = name_column_width(["build", "docker-clean"]);
+
+        let build = formatter.list_entry("build", "Build project", width, None);
+        let clean = formatter.list_entry("docker-clean", "Remove images", width, None);
+        assert_eq!(build, "  build         Build project");
+        assert_eq!(clean, "  docker-clean  Remove images");
+        assert_eq!(build.find("Build"), clean.find("Remove"));
+
+        assert_eq!(formatter.list_entry("bare", "", width, None), "  bare");
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("exactly10!", 10), "exactly10!");
+        assert_eq!(truncate_to_width("a bit too long", 10), "a bit too…");
+        // 全角文字は幅 2
+        assert_eq!(truncate_to_width("開発サーバーを起動", 7), "開発サ…");
+        assert_eq!(truncate_to_width("abc", 0), "");
+    }
+
+    #[test]
+    fn test_list_entry_truncates_to_terminal_width() {
+        let formatter = plain();
+        let description = "x".repeat(100);
+
+        let line = formatter.list_entry("build", &description, 5, Some(40));
+        assert_eq!(line.width(), 40);
+        assert!(line.ends_with('…'));
+
+        // 切り詰めなし
+        let line = formatter.list_entry("build", &description, 5, None);
+        assert!(line.ends_with(&description));
+
+        // 極端に狭い端末でも説明の最低幅は確保する
+        let line = formatter.list_entry("build", &description, 5, Some(5));
+        assert_eq!(line.width(), 2 + 5 + 2 + MIN_DESCRIPTION_WIDTH);
+    }
+
     #[test]
     fn test_progress() {
         let formatter = OutputFormatter::new().with_colors(false);
@@ -0,0 +1,189 @@
+//! 実行マニフェスト
+//!
+//! `cmdrun run --capture <path>` 用に、実行を再現するための情報（解決済みのコマンド行・
+//! 環境変数・シェル・プラットフォーム・cmdrun のバージョン）と結果（終了コード・
+//! 所要時間・出力）を JSON で保存する。`redact_patterns` に一致する環境変数は
+//! `***REDACTED***` として書き込む
+
+use crate::security::secrets::RedactedValue;
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// マニフェスト形式のバージョン（フィールドを変更したら上げる）
+pub const MANIFEST_VERSION: u32 = 2;
+
+/// 1回の `cmdrun run` の記録
+#[derive(Debug, Clone, Serialize)]
+pub struct RunManifest {
+    pub manifest_version: u32,
+    pub cmdrun_version: String,
+    /// 実行したコマンド名（エイリアス解決後）
+    pub command: String,
+    pub args: Vec<String>,
+    /// シェルに渡したコマンド行（依存関係を含む実行順、機密値は伏せ字）
+    pub resolved_commands: Vec<String>,
+    /// 実行したコマンドのシェル（コマンド別の `shell` 指定を反映）
+    pub shell: String,
+    /// 実行順に含まれる各コマンドのシェル（コマンド名 → シェル）
+    pub command_shells: BTreeMap<String, String>,
+    pub platform: Platform,
+    pub working_dir: PathBuf,
+    /// 環境変数（グローバル env・`--env`・コマンド env・位置引数、キー順）
+    pub env: BTreeMap<String, RedactedValue>,
+    /// 開始時刻（RFC 3339）
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub exit_code: i32,
+    pub success: bool,
+    /// Ctrl+C で中断された場合は true
+    pub interrupted: bool,
+    /// stdout/stderr の各行（ANSIエスケープ除去済み）
+    pub output: Vec<String>,
+}
+
+/// 実行したプラットフォーム
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Platform {
+    pub os: String,
+    pub arch: String,
+}
+
+impl Platform {
+    /// 現在のプラットフォーム
+    pub fn current() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+        }
+    }
+}
+
+/// 実行結果
+#[derive(Debug, Clone, Copy)]
+pub struct RunOutcome {
+    pub exit_code: i32,
+    pub duration: Duration,
+    pub interrupted: bool,
+}
+
+impl RunManifest {
+    /// 実行前に決まる情報からマニフェストを作成（結果は `finish` で設定）
+    pub fn new(
+        command: &str,
+        args: &[String],
+        resolved_commands: Vec<String>,
+        shell: &str,
+        working_dir: PathBuf,
+        env: BTreeMap<String, RedactedValue>,
+    ) -> Self {
+        Self {
+            manifest_version: MANIFEST_VERSION,
+            cmdrun_version: env!("CARGO_PKG_VERSION").to_string(),
+            command: command.to_string(),
+            args: args.to_vec(),
+            resolved_commands,
+            shell: shell.to_string(),
+            command_shells: BTreeMap::new(),
+            platform: Platform::current(),
+            working_dir,
+            env,
+            started_at: Local::now().to_rfc3339(),
+            duration_ms: 0,
+            exit_code: 0,
+            success: false,
+            interrupted: false,
+            output: Vec::new(),
+        }
+    }
+
+    /// 依存関係を含む各コマンドのシェルを設定
+    pub fn with_command_shells(mut self, shells: BTreeMap<String, String>) -> Self {
+        self.command_shells = shells;
+        self
+    }
+
+    /// 実行結果と出力を設定
+    pub fn finish(mut self, outcome: RunOutcome, output: Vec<String>) -> Self {
+        self.duration_ms = outcome.duration.as_millis() as u64;
+        self.exit_code = outcome.exit_code;
+        self.success = outcome.exit_code == 0 && !outcome.interrupted;
+        self.interrupted = outcome.interrupted;
+        self.output = output;
+        self
+    }
+
+    /// JSON（整形済み）でファイルに書き込む
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize manifest")?;
+        std::fs::write(path, json + "\n")
+            .with_context(|| format!("Failed to write manifest: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::secrets::EnvRedactor;
+    use ahash::AHashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let env: AHashMap<String, String> = [
+            ("API_TOKEN".to_string(), "s3cret".to_string()),
+            ("MODE".to_string(), "release".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let redactor = EnvRedactor::new(["*_TOKEN"]).unwrap();
+
+        let manifest = RunManifest::new(
+            "deploy",
+            &["prod".to_string()],
+            vec!["deploy.sh --token ***REDACTED***".to_string()],
+            "bash",
+            PathBuf::from("/srv/app"),
+            redactor.redact(&env),
+        )
+        .with_command_shells(BTreeMap::from([("deploy".to_string(), "bash".to_string())]))
+        .finish(
+            RunOutcome {
+                exit_code: 3,
+                duration: Duration::from_millis(1_250),
+                interrupted: false,
+            },
+            vec!["deploying".to_string()],
+        );
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("runs/deploy.json");
+        manifest.write_to(&path).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("s3cret"));
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["manifest_version"], MANIFEST_VERSION);
+        assert_eq!(value["cmdrun_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(value["command"], "deploy");
+        assert_eq!(value["args"][0], "prod");
+        assert_eq!(value["shell"], "bash");
+        assert_eq!(value["command_shells"]["deploy"], "bash");
+        assert_eq!(value["platform"]["os"], std::env::consts::OS);
+        assert_eq!(value["env"]["API_TOKEN"], "***REDACTED***");
+        assert_eq!(value["env"]["MODE"], "release");
+        assert_eq!(value["exit_code"], 3);
+        assert_eq!(value["success"], false);
+        assert_eq!(value["duration_ms"], 1_250);
+        assert_eq!(value["output"][0], "deploying");
+        assert!(value["started_at"].is_string());
+    }
+}
@@ -1,2 +1,7 @@
+pub mod capture;
 pub mod formatter;
 pub mod logger;
+pub mod manifest;
+pub mod notify;
+pub mod progress;
+pub mod status;
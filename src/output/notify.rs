@@ -0,0 +1,160 @@
+//! コマンド完了時のデスクトップ通知
+//!
+//! `[config] notify_on_completion` または `run --notify` で有効になる。実際の送信は
+//! `notifications` 機能（notify-rust）が有効な場合のみで、通知を送れない環境では
+//! 何も表示せずに無視する
+
+use std::time::Duration;
+
+/// 通知の内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// タイトル（`cmdrun: build succeeded`）
+    pub summary: String,
+    /// 本文（所要時間と終了コード）
+    pub body: String,
+}
+
+impl Notification {
+    /// コマンド完了の通知を作成
+    ///
+    /// `exit_code` は失敗時のみ本文に含める
+    pub fn completion(name: &str, success: bool, exit_code: i32, duration: Duration) -> Self {
+        let elapsed = format_elapsed(duration);
+        if success {
+            Self {
+                summary: format!("cmdrun: {} succeeded", name),
+                body: format!("Completed in {}", elapsed),
+            }
+        } else {
+            Self {
+                summary: format!("cmdrun: {} failed", name),
+                body: format!("Exit code {} after {}", exit_code, elapsed),
+            }
+        }
+    }
+}
+
+/// 通知の送信先
+pub trait NotificationSender {
+    fn send(&self, notification: &Notification) -> Result<(), String>;
+}
+
+/// デスクトップ通知
+///
+/// `notifications` 機能が無効な場合は常に送信失敗になる
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DesktopNotifier;
+
+impl NotificationSender for DesktopNotifier {
+    #[cfg(feature = "notifications")]
+    fn send(&self, notification: &Notification) -> Result<(), String> {
+        notify_rust::Notification::new()
+            .appname("cmdrun")
+            .summary(&notification.summary)
+            .body(&notification.body)
+            .show()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    #[cfg(not(feature = "notifications"))]
+    fn send(&self, _notification: &Notification) -> Result<(), String> {
+        Err("cmdrun was built without the `notifications` feature".to_string())
+    }
+}
+
+/// 実行時間が `threshold_ms` 以上なら完了を通知
+///
+/// 送信に失敗してもエラーにはしない（デバッグログのみ）。送信を試みた通知を返す
+pub fn notify_completion(
+    sender: &dyn NotificationSender,
+    name: &str,
+    success: bool,
+    exit_code: i32,
+    duration: Duration,
+    threshold_ms: u64,
+) -> Option<Notification> {
+    if duration < Duration::from_millis(threshold_ms) {
+        tracing::debug!(
+            "Not notifying: '{}' finished in {:?} (threshold {}ms)",
+            name,
+            duration,
+            threshold_ms
+        );
+        return None;
+    }
+
+    let notification = Notification::completion(name, success, exit_code, duration);
+    if let Err(e) = sender.send(&notification) {
+        tracing::debug!("Desktop notification unavailable: {}", e);
+    }
+    Some(notification)
+}
+
+/// 所要時間を `1m 05s` / `12.3s` の形式で表示
+fn format_elapsed(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// 送信された通知を記録する送信先
+    #[derive(Default)]
+    struct RecordingSender {
+        sent: RefCell<Vec<Notification>>,
+    }
+
+    impl NotificationSender for RecordingSender {
+        fn send(&self, notification: &Notification) -> Result<(), String> {
+            self.sent.borrow_mut().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_notify_success_and_failure() {
+        let sender = RecordingSender::default();
+
+        notify_completion(&sender, "build", true, 0, Duration::from_secs(125), 1_000);
+        notify_completion(
+            &sender,
+            "test",
+            false,
+            101,
+            Duration::from_millis(12_340),
+            1_000,
+        );
+
+        assert_eq!(
+            *sender.sent.borrow(),
+            vec![
+                Notification {
+                    summary: "cmdrun: build succeeded".to_string(),
+                    body: "Completed in 2m 05s".to_string(),
+                },
+                Notification {
+                    summary: "cmdrun: test failed".to_string(),
+                    body: "Exit code 101 after 12.3s".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_notify_skips_runs_below_threshold() {
+        let sender = RecordingSender::default();
+
+        let sent = notify_completion(&sender, "fmt", true, 0, Duration::from_millis(900), 1_000);
+        assert!(sent.is_none());
+        assert!(sender.sent.borrow().is_empty());
+    }
+}
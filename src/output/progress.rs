@@ -0,0 +1,402 @@
+//! 並列実行の進捗表示
+//!
+//! 並列グループの実行中、実行中の各コマンドをスピナーと経過時間付きで stderr に表示する。
+//! 表示ブロックは一定間隔で再描画し、コマンドの出力行は [`print_line`] /
+//! [`eprint_line`] 経由でブロックの上に書き出す（表示が崩れないように）。
+//! 端末でない場合や色なし（`--color never` / `NO_COLOR`）の場合は制御コードを使わず、
+//! コマンドの完了ごとに 1 行を出力する
+
+use crate::output::formatter::{terminal_width, truncate_to_width};
+use colored::*;
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// スピナーのフレーム
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// 再描画の間隔
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 表示中の進捗ブロック（出力行をブロックの上に書き出すために共有）
+static ACTIVE: Mutex<Option<Arc<Shared>>> = Mutex::new(None);
+
+/// 進捗の表示方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// スピナーと経過時間を再描画する
+    Live,
+    /// 完了ごとに 1 行を出力する（制御コードなし）
+    Plain,
+}
+
+impl ProgressMode {
+    /// 実行環境から表示方法を決定
+    pub fn detect() -> Self {
+        Self::from_env(
+            io::stderr().is_terminal(),
+            colored::control::SHOULD_COLORIZE.should_colorize(),
+        )
+    }
+
+    /// stderr が端末で、かつ色付き出力が有効な場合のみ [`ProgressMode::Live`]
+    pub fn from_env(stderr_is_terminal: bool, colorize: bool) -> Self {
+        if stderr_is_terminal && colorize {
+            Self::Live
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+/// コマンドの実行結果（進捗表示用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressOutcome {
+    /// 成功
+    Succeeded,
+    /// 終了コード付きで失敗
+    Failed(i32),
+    /// 実行条件が偽でスキップ
+    Skipped,
+    /// 起動失敗・タイムアウト・中断など
+    Aborted,
+}
+
+/// 並列グループの進捗表示
+///
+/// クローンは同じ表示を共有する（並列タスクから更新するため）
+#[derive(Clone)]
+pub struct ParallelProgress {
+    shared: Arc<Shared>,
+    ticker: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+struct Shared {
+    mode: ProgressMode,
+    state: Mutex<State>,
+}
+
+struct State {
+    total: usize,
+    done: usize,
+    /// 実行中のコマンド（開始順）
+    running: Vec<(String, Instant)>,
+    frame: usize,
+    /// 現在描画されているブロックの行数
+    drawn_lines: usize,
+    out: Box<dyn Write + Send>,
+}
+
+impl ParallelProgress {
+    /// 進捗表示を開始（stderr に出力）
+    ///
+    /// [`ProgressMode::Live`] の場合は tokio ランタイム上で再描画タスクを起動する
+    pub fn start(total: usize, mode: ProgressMode) -> Self {
+        Self::with_writer(total, mode, Box::new(io::stderr()))
+    }
+
+    /// 出力先を指定して進捗表示を開始
+    pub fn with_writer(total: usize, mode: ProgressMode, out: Box<dyn Write + Send>) -> Self {
+        let shared = Arc::new(Shared {
+            mode,
+            state: Mutex::new(State {
+                total,
+                done: 0,
+                running: Vec::new(),
+                frame: 0,
+                drawn_lines: 0,
+                out,
+            }),
+        });
+
+        let ticker = (mode == ProgressMode::Live).then(|| {
+            *ACTIVE.lock().unwrap() = Some(Arc::clone(&shared));
+            let shared = Arc::clone(&shared);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(TICK_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    let mut state = shared.state.lock().unwrap();
+                    state.frame = state.frame.wrapping_add(1);
+                    state.redraw();
+                }
+            })
+        });
+
+        Self {
+            shared,
+            ticker: Arc::new(Mutex::new(ticker)),
+        }
+    }
+
+    /// コマンドの開始を記録
+    pub fn command_started(&self, name: &str) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.running.push((name.to_string(), Instant::now()));
+        if self.shared.mode == ProgressMode::Live {
+            state.redraw();
+        }
+    }
+
+    /// コマンドの完了を記録し、完了行を出力
+    pub fn command_finished(&self, name: &str, outcome: ProgressOutcome) {
+        let mut state = self.shared.state.lock().unwrap();
+        let elapsed = state
+            .running
+            .iter()
+            .position(|(running, _)| running == name)
+            .map(|idx| state.running.remove(idx).1.elapsed())
+            .unwrap_or_default();
+        state.done += 1;
+
+        let line = completion_line(
+            name,
+            outcome,
+            elapsed,
+            state.done,
+            state.total,
+            self.shared.mode == ProgressMode::Live,
+        );
+        match self.shared.mode {
+            ProgressMode::Live => {
+                state.clear();
+                let _ = writeln!(state.out, "{}", line);
+                state.redraw();
+            }
+            ProgressMode::Plain => {
+                let _ = writeln!(state.out, "{}", line);
+                let _ = state.out.flush();
+            }
+        }
+    }
+
+    /// 進捗表示を終了（表示ブロックを消去）
+    pub fn finish(&self) {
+        if let Some(ticker) = self.ticker.lock().unwrap().take() {
+            ticker.abort();
+        }
+
+        let mut active = ACTIVE.lock().unwrap();
+        if active
+            .as_ref()
+            .is_some_and(|shared| Arc::ptr_eq(shared, &self.shared))
+        {
+            *active = None;
+        }
+        drop(active);
+
+        self.shared.state.lock().unwrap().clear();
+    }
+}
+
+impl State {
+    /// 表示ブロックを消去（カーソルはブロックの先頭行の行頭に戻る）
+    fn clear(&mut self) {
+        if self.drawn_lines == 0 {
+            return;
+        }
+        let mut erase = String::from("\r\x1b[2K");
+        for _ in 1..self.drawn_lines {
+            erase.push_str("\x1b[1A\x1b[2K");
+        }
+        let _ = write!(self.out, "{}", erase);
+        let _ = self.out.flush();
+        self.drawn_lines = 0;
+    }
+
+    /// 表示ブロックを描き直す（行が折り返さないよう端末幅で切り詰める）
+    fn redraw(&mut self) {
+        self.clear();
+        let lines = status_lines(
+            &self.running,
+            self.done,
+            self.total,
+            self.frame,
+            Instant::now(),
+            terminal_width().saturating_sub(1),
+        );
+        let block: Vec<String> = lines.iter().map(|line| line.dimmed().to_string()).collect();
+        let _ = write!(self.out, "{}", block.join("\n"));
+        let _ = self.out.flush();
+        self.drawn_lines = lines.len();
+    }
+}
+
+/// 実行中ブロックの各行（先頭は全体の進捗）
+fn status_lines(
+    running: &[(String, Instant)],
+    done: usize,
+    total: usize,
+    frame: usize,
+    now: Instant,
+    max_width: usize,
+) -> Vec<String> {
+    let spinner = SPINNER_FRAMES[frame % SPINNER_FRAMES.len()];
+    let mut lines = vec![format!(
+        "{}/{} completed, {} running",
+        done,
+        total,
+        running.len()
+    )];
+    for (name, started) in running {
+        let elapsed = now.saturating_duration_since(*started);
+        lines.push(format!(
+            "  {} {} ({:.1}s)",
+            spinner,
+            name,
+            elapsed.as_secs_f64()
+        ));
+    }
+    lines
+        .iter()
+        .map(|line| truncate_to_width(line, max_width).into_owned())
+        .collect()
+}
+
+/// 完了行（`✓ [1/3] build (1.20s)`）
+fn completion_line(
+    name: &str,
+    outcome: ProgressOutcome,
+    elapsed: Duration,
+    done: usize,
+    total: usize,
+    color: bool,
+) -> String {
+    let (symbol, detail) = match outcome {
+        ProgressOutcome::Succeeded => ("✓", String::new()),
+        ProgressOutcome::Failed(code) => ("✗", format!("exit code {}, ", code)),
+        ProgressOutcome::Skipped => ("⏭", "skipped, ".to_string()),
+        ProgressOutcome::Aborted => ("✗", "aborted, ".to_string()),
+    };
+    let symbol = match outcome {
+        _ if !color => symbol.to_string(),
+        ProgressOutcome::Succeeded => symbol.green().bold().to_string(),
+        ProgressOutcome::Skipped => symbol.yellow().bold().to_string(),
+        ProgressOutcome::Failed(_) | ProgressOutcome::Aborted => symbol.red().bold().to_string(),
+    };
+
+    format!(
+        "  {} [{}/{}] {} ({}{:.2}s)",
+        symbol,
+        done,
+        total,
+        name,
+        detail,
+        elapsed.as_secs_f64()
+    )
+}
+
+/// 標準出力に 1 行を書き出す（進捗表示中はブロックの上に出力）
+pub fn print_line(line: &str) {
+    write_line(line, false);
+}
+
+/// 標準エラー出力に 1 行を書き出す（進捗表示中はブロックの上に出力）
+pub fn eprint_line(line: &str) {
+    write_line(line, true);
+}
+
+fn write_line(line: &str, to_stderr: bool) {
+    let active = ACTIVE.lock().unwrap().clone();
+    let Some(shared) = active else {
+        if to_stderr {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+        return;
+    };
+
+    let mut state = shared.state.lock().unwrap();
+    state.clear();
+    if to_stderr {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+        let _ = io::stdout().flush();
+    }
+    state.redraw();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// テスト用の共有バッファ
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_mode_from_env() {
+        assert_eq!(ProgressMode::from_env(true, true), ProgressMode::Live);
+        assert_eq!(ProgressMode::from_env(false, true), ProgressMode::Plain);
+        assert_eq!(ProgressMode::from_env(true, false), ProgressMode::Plain);
+    }
+
+    #[test]
+    fn test_plain_mode_prints_one_line_per_completion_without_control_codes() {
+        let buffer = Buffer::default();
+        let progress =
+            ParallelProgress::with_writer(3, ProgressMode::Plain, Box::new(buffer.clone()));
+
+        progress.command_started("build");
+        progress.command_started("lint");
+        progress.command_started("check");
+        progress.command_finished("lint", ProgressOutcome::Succeeded);
+        progress.command_finished("build", ProgressOutcome::Failed(2));
+        progress.command_finished("check", ProgressOutcome::Skipped);
+        progress.finish();
+
+        let output = buffer.contents();
+        assert!(!output.contains('\x1b'), "{:?}", output);
+        assert!(!output.contains('\r'), "{:?}", output);
+        assert!(!SPINNER_FRAMES.iter().any(|f| output.contains(*f)));
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3, "{:?}", output);
+        assert!(lines[0].starts_with("  ✓ [1/3] lint ("), "{}", lines[0]);
+        assert!(
+            lines[1].starts_with("  ✗ [2/3] build (exit code 2, "),
+            "{}",
+            lines[1]
+        );
+        assert!(
+            lines[2].starts_with("  ⏭ [3/3] check (skipped, "),
+            "{}",
+            lines[2]
+        );
+    }
+
+    #[test]
+    fn test_status_lines_show_each_running_command() {
+        let now = Instant::now();
+        let started = now - Duration::from_millis(1500);
+        let running = vec![
+            ("build".to_string(), started),
+            ("a-very-long-command-name".to_string(), now),
+        ];
+
+        let lines = status_lines(&running, 1, 3, 0, now, 20);
+
+        assert_eq!(lines[0], "1/3 completed, 2 ru…");
+        assert_eq!(lines[1], "  ⠋ build (1.5s)");
+        assert_eq!(lines[2], "  ⠋ a-very-long-com…");
+    }
+}
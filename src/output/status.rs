@@ -0,0 +1,34 @@
+//! cmdrun 自身の状態表示（`Running:` / `Completed in` など）
+//!
+//! `--quiet` 指定時は表示しない。コマンド自体の stdout/stderr やエラー表示、
+//! 警告には影響しない
+
+use crate::output::progress;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// `--quiet` が指定されているか
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// 状態表示の抑制を設定（`--quiet`）
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// 状態表示が抑制されているか
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// 状態を標準出力に 1 行表示
+pub fn print(line: &str) {
+    if !is_quiet() {
+        progress::print_line(line);
+    }
+}
+
+/// 状態を標準エラー出力に 1 行表示
+pub fn eprint(line: &str) {
+    if !is_quiet() {
+        progress::eprint_line(line);
+    }
+}
@@ -41,6 +41,11 @@ impl ShellInfo {
 
     /// コマンドライン引数を取得（シェル実行用）
     pub fn get_command_args(&self, script: &str) -> Vec<String> {
+        // elvish / xonsh はWindowsでも `-c` を使う
+        if uses_dash_c(&self.name) {
+            return vec!["-c".to_string(), script.to_string()];
+        }
+
         match self.platform {
             Platform::Unix => vec!["-c".to_string(), script.to_string()],
             Platform::Windows => {
@@ -61,6 +66,32 @@ impl ShellInfo {
     }
 }
 
+/// 検出対象のUnixシェル（`SHELL` 未設定時に親プロセス名と照合する）
+const KNOWN_UNIX_SHELLS: &[&str] = &[
+    "bash", "zsh", "fish", "sh", "dash", "ksh", "elvish", "xonsh",
+];
+
+/// プラットフォームに関係なく `-c` でスクリプトを受け取るシェル
+const CROSS_PLATFORM_SHELLS: &[&str] = &["elvish", "xonsh"];
+
+/// シェル名またはパスから、`-c` を使うクロスプラットフォームシェルか判定
+///
+/// `C:\tools\elvish.exe` のようなパスも受け付ける
+pub fn uses_dash_c(shell: &str) -> bool {
+    let name = shell_name(shell);
+    CROSS_PLATFORM_SHELLS.contains(&name.as_str())
+}
+
+/// パスや拡張子、ログインシェルの `-` を除いたシェル名（小文字）
+fn shell_name(shell: &str) -> String {
+    let base = shell.rsplit(['/', '\\']).next().unwrap_or(shell);
+    let name = base.trim_start_matches('-').to_ascii_lowercase();
+    match name.strip_suffix(".exe") {
+        Some(stem) => stem.to_string(),
+        None => name,
+    }
+}
+
 /// 現在のプラットフォームのデフォルトシェルを検出
 pub fn detect_shell() -> Result<ShellInfo> {
     if cfg!(target_os = "windows") {
@@ -86,25 +117,32 @@ pub fn get_shell_by_name(name: &str) -> Result<ShellInfo> {
     Ok(ShellInfo::new(name.to_string(), path, platform))
 }
 
+/// シェル名またはパスを実行可能ファイルのパスに解決（`--shell` 用）
+///
+/// 見つからない場合は `language` でローカライズしたエラーを返す
+pub fn find_shell(name_or_path: &str, language: Language) -> Result<PathBuf> {
+    which::which(name_or_path).map_err(|_| {
+        anyhow::anyhow!(
+            "{}: {}",
+            get_message(MessageKey::ErrorShellNotFound, language),
+            name_or_path
+        )
+    })
+}
+
 /// Unix系のシェルを検出
 fn detect_unix_shell() -> Result<ShellInfo> {
     // 優先順位:
     // 1. SHELL環境変数
-    // 2. よく使われるシェルの順（bash, zsh, fish, sh）
-
-    // 環境変数 SHELL をチェック
-    if let Ok(shell_path) = env::var("SHELL") {
-        let path = PathBuf::from(&shell_path);
-        if path.exists() {
-            let name = path
-                .file_name()
-                .and_then(|s| s.to_str())
-                .unwrap_or("sh")
-                .to_string();
-
-            debug!("Using shell from SHELL env: {}", shell_path);
-            return Ok(ShellInfo::new(name, path, Platform::Unix));
-        }
+    // 2. 親プロセス（既知のシェルの場合のみ）
+    // 3. よく使われるシェルの順（bash, zsh, fish, sh）
+
+    if let Some(shell) = shell_from_env(env::var("SHELL").ok()) {
+        return Ok(shell);
+    }
+
+    if let Some(shell) = shell_from_parent_process(parent_process_name()) {
+        return Ok(shell);
     }
 
     // よく使われるシェルを順番に試す
@@ -123,6 +161,66 @@ fn detect_unix_shell() -> Result<ShellInfo> {
     )
 }
 
+/// `SHELL` 環境変数の値からシェル情報を作成（パスが存在する場合のみ）
+fn shell_from_env(shell_var: Option<String>) -> Option<ShellInfo> {
+    let shell_path = shell_var?;
+    let path = PathBuf::from(&shell_path);
+    if !path.exists() {
+        return None;
+    }
+
+    let name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .map(shell_name)
+        .unwrap_or_else(|| "sh".to_string());
+
+    debug!("Using shell from SHELL env: {}", shell_path);
+    Some(ShellInfo::new(name, path, Platform::Unix))
+}
+
+/// 親プロセス名が既知のシェルならシェル情報を作成
+fn shell_from_parent_process(process_name: Option<String>) -> Option<ShellInfo> {
+    let name = shell_name(&process_name?);
+    if !KNOWN_UNIX_SHELLS.contains(&name.as_str()) {
+        return None;
+    }
+
+    let path = which::which(&name).ok()?;
+    debug!(
+        "Using shell from parent process: {} at {}",
+        name,
+        path.display()
+    );
+    Some(ShellInfo::new(name, path, Platform::Unix))
+}
+
+/// 親プロセスの実行ファイル名を取得
+///
+/// Linux は `/proc/<ppid>/comm`、それ以外は `ps` を使う
+#[cfg(unix)]
+fn parent_process_name() -> Option<String> {
+    let ppid = std::os::unix::process::parent_id();
+
+    if let Ok(comm) = std::fs::read_to_string(format!("/proc/{}/comm", ppid)) {
+        return Some(comm.trim().to_string()).filter(|s| !s.is_empty());
+    }
+
+    let output = std::process::Command::new("ps")
+        .args(["-o", "comm=", "-p", &ppid.to_string()])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|s| !s.is_empty())
+}
+
+#[cfg(not(unix))]
+fn parent_process_name() -> Option<String> {
+    None
+}
+
 /// Windowsのシェルを検出
 fn detect_windows_shell() -> Result<ShellInfo> {
     // 優先順位:
@@ -161,12 +259,17 @@ pub fn detect_shell_features(shell: &ShellInfo) -> ShellFeatures {
     let mut features = ShellFeatures::default();
 
     match shell.name.as_str() {
-        "bash" | "zsh" | "fish" => {
+        "bash" | "zsh" | "fish" | "xonsh" => {
             features.supports_pipes = true;
             features.supports_redirects = true;
             features.supports_background = true;
             features.supports_job_control = true;
         }
+        "elvish" => {
+            // elvish は `&` によるバックグラウンド実行・ジョブ制御をサポートしない
+            features.supports_pipes = true;
+            features.supports_redirects = true;
+        }
         "sh" => {
             features.supports_pipes = true;
             features.supports_redirects = true;
@@ -255,6 +358,75 @@ mod tests {
         assert_eq!(args, vec!["/C", "echo hello"]);
     }
 
+    #[test]
+    fn test_find_shell() {
+        #[cfg(unix)]
+        assert!(find_shell("sh", Language::English).is_ok());
+
+        let err = find_shell("/nonexistent/bin/nosuchshell", Language::Japanese).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "シェルが見つかりません: /nonexistent/bin/nosuchshell"
+        );
+    }
+
+    #[test]
+    fn test_shell_from_env_elvish() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let elvish = temp_dir.path().join("elvish");
+        std::fs::write(&elvish, "").unwrap();
+
+        let shell = shell_from_env(Some(elvish.to_string_lossy().into_owned())).unwrap();
+        assert_eq!(shell.name, "elvish");
+        assert_eq!(shell.path, elvish);
+        assert_eq!(
+            shell.get_command_args("echo hello"),
+            vec!["-c", "echo hello"]
+        );
+    }
+
+    #[test]
+    fn test_shell_from_env_missing_path() {
+        assert!(shell_from_env(Some("/nonexistent/bin/elvish".to_string())).is_none());
+        assert!(shell_from_env(None).is_none());
+    }
+
+    #[test]
+    fn test_shell_from_parent_process() {
+        // 既知のシェル以外（make など）は無視する
+        assert!(shell_from_parent_process(Some("make".to_string())).is_none());
+        assert!(shell_from_parent_process(None).is_none());
+
+        #[cfg(unix)]
+        {
+            // ログインシェルは `-sh` のように表示される
+            let shell = shell_from_parent_process(Some("-sh".to_string())).unwrap();
+            assert_eq!(shell.name, "sh");
+        }
+    }
+
+    #[test]
+    fn test_shell_command_args_cross_platform_shells() {
+        for name in ["elvish", "xonsh"] {
+            for platform in [Platform::Unix, Platform::Windows] {
+                let shell = ShellInfo::new(name.to_string(), PathBuf::from(name), platform);
+                assert_eq!(
+                    shell.get_command_args("echo hello"),
+                    vec!["-c", "echo hello"]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_uses_dash_c() {
+        assert!(uses_dash_c("elvish"));
+        assert!(uses_dash_c("/usr/local/bin/xonsh"));
+        assert!(uses_dash_c(r"C:\tools\Elvish.exe"));
+        assert!(!uses_dash_c("bash"));
+        assert!(!uses_dash_c("pwsh"));
+    }
+
     #[test]
     fn test_detect_shell_features() {
         let bash_shell = ShellInfo::new(
@@ -2,12 +2,13 @@
 //!
 //! Defines the Plugin trait and related types for cmdrun's plugin system.
 
-use crate::config::schema::Command;
+use crate::config::schema::{Command, CommandsConfig};
 use crate::error::Result;
 use ahash::AHashMap;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::fmt;
+use std::sync::Arc;
 
 /// Plugin metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +86,12 @@ pub struct PluginContext {
     /// Command configuration
     pub command: Command,
 
+    /// Resolved shell command (variables expanded, steps separated by newlines)
+    pub command_spec: String,
+
+    /// Arguments passed on the command line
+    pub args: Vec<String>,
+
     /// Environment variables
     pub env: AHashMap<String, String>,
 
@@ -95,6 +102,82 @@ pub struct PluginContext {
     pub data: AHashMap<String, String>,
 }
 
+/// Decision returned by [`Plugin::pre_execute`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreExecuteDecision {
+    /// Run the command
+    Continue,
+
+    /// Do not run the command; it is reported and recorded as skipped
+    Skip(String),
+
+    /// Do not run the command; the run fails with the reason
+    Abort(String),
+}
+
+/// Combined outcome of the pre-execution hooks of all plugins
+///
+/// An `Abort` decision is returned as [`ExecutionError::BlockedByPlugin`](crate::error::ExecutionError::BlockedByPlugin)
+/// instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreExecuteOutcome {
+    /// Every plugin let the command run
+    Continue,
+
+    /// A plugin skipped the command
+    Skipped { plugin: String, reason: String },
+}
+
+/// Custom command handler
+///
+/// Receives the arguments passed after the command name and the execution
+/// context, and returns the command result.
+pub type CustomCommandHandler =
+    Arc<dyn Fn(&[String], &PluginContext) -> Result<CommandResult> + Send + Sync>;
+
+/// Custom subcommand contributed by a plugin
+///
+/// Registered commands are dispatched as `cmdrun <name> [args...]`.
+#[derive(Clone)]
+pub struct CustomCommand {
+    /// Command name (used as the subcommand)
+    pub name: String,
+
+    /// Command description
+    pub description: String,
+
+    /// Command handler
+    pub handler: CustomCommandHandler,
+}
+
+impl CustomCommand {
+    /// Create a new custom command
+    pub fn new<F>(name: impl Into<String>, description: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(&[String], &PluginContext) -> Result<CommandResult> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            handler: Arc::new(handler),
+        }
+    }
+
+    /// Invoke the command handler
+    pub fn execute(&self, args: &[String], context: &PluginContext) -> Result<CommandResult> {
+        (self.handler)(args, context)
+    }
+}
+
+impl fmt::Debug for CustomCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomCommand")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Plugin hook phase
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HookPhase {
@@ -149,11 +232,12 @@ pub trait Plugin: Send + Sync {
     ///
     /// # Returns
     ///
-    /// - `Ok(true)` - Continue with execution
-    /// - `Ok(false)` - Skip execution (command will be marked as successful)
-    /// - `Err(_)` - Abort execution with error
-    fn pre_execute(&self, _context: &mut PluginContext) -> Result<bool> {
-        Ok(true)
+    /// - `Ok(PreExecuteDecision::Continue)` - Continue with execution
+    /// - `Ok(PreExecuteDecision::Skip(reason))` - Skip execution (recorded as skipped)
+    /// - `Ok(PreExecuteDecision::Abort(reason))` - Refuse to run the command (run fails)
+    /// - `Err(_)` - The hook itself failed
+    fn pre_execute(&self, _context: &mut PluginContext) -> Result<PreExecuteDecision> {
+        Ok(PreExecuteDecision::Continue)
     }
 
     /// Post-execution hook
@@ -179,6 +263,24 @@ pub trait Plugin: Send + Sync {
         AHashMap::new()
     }
 
+    /// Modify the loaded configuration
+    ///
+    /// Called once after the configuration is loaded and before command
+    /// resolution, only when the plugin declares the `config_modification`
+    /// capability. Use this to inject environment variables, add computed
+    /// commands, or override settings for all commands.
+    fn modify_config(&self, _config: &mut CommandsConfig) -> Result<()> {
+        Ok(())
+    }
+
+    /// Get custom subcommands provided by this plugin
+    ///
+    /// Only consulted when the plugin declares the `custom_commands`
+    /// capability. Each command is dispatched as `cmdrun <name> [args...]`.
+    fn commands(&self) -> Vec<CustomCommand> {
+        Vec::new()
+    }
+
     /// Handle custom command execution
     ///
     /// Called when a custom command provided by this plugin is executed.
@@ -215,6 +317,11 @@ pub trait Plugin: Send + Sync {
 #[macro_export]
 macro_rules! declare_plugin {
     ($plugin_type:ty) => {
+        /// Plugin API version this plugin was built against
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        pub static _plugin_api_version: u32 = $crate::plugin::PLUGIN_API_VERSION;
+
         #[no_mangle]
         pub extern "C" fn _plugin_create() -> *mut dyn $crate::plugin::api::Plugin {
             let plugin = <$plugin_type>::default();
@@ -284,6 +391,56 @@ mod tests {
         assert!(!caps.config_modification);
     }
 
+    #[test]
+    fn test_custom_command_execute() {
+        let command = CustomCommand::new("greet", "Say hello", |args, context| {
+            Ok(CommandResult {
+                exit_code: 0,
+                stdout: format!("{} {}", context.command_name, args.join(" ")),
+                stderr: String::new(),
+                duration_ms: 0,
+                metadata: AHashMap::new(),
+            })
+        });
+
+        let context = PluginContext {
+            command_name: "greet".to_string(),
+            command: Command {
+                description: "Say hello".to_string(),
+                cmd: crate::config::schema::CommandSpec::Single("greet".to_string()),
+                env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
+                working_dir: None,
+                deps: vec![],
+                platform: vec![],
+                tags: vec![],
+                timeout: None,
+                parallel: false,
+                confirm: false,
+                confirm_message: None,
+                allow_chaining: None,
+                allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
+            },
+            command_spec: "greet".to_string(),
+            args: vec!["world".to_string()],
+            env: AHashMap::new(),
+            working_dir: ".".to_string(),
+            data: AHashMap::new(),
+        };
+
+        let result = command.execute(&["world".to_string()], &context).unwrap();
+        assert_eq!(result.stdout, "greet world");
+        assert!(TestPlugin.commands().is_empty());
+    }
+
     #[test]
     fn test_hook_phase_display() {
         assert_eq!(HookPhase::PreExecute.to_string(), "pre_execute");
@@ -3,7 +3,9 @@
 //! Dynamically loads plugin libraries at runtime.
 
 #[cfg(feature = "plugin-system")]
-use super::api::Plugin;
+use super::api::{Plugin, PluginMetadata};
+#[cfg(feature = "plugin-system")]
+use super::PLUGIN_API_VERSION;
 #[cfg(feature = "plugin-system")]
 use crate::config::Language;
 #[cfg(feature = "plugin-system")]
@@ -15,6 +17,8 @@ use libloading::{Library, Symbol};
 #[cfg(feature = "plugin-system")]
 use std::path::{Path, PathBuf};
 #[cfg(feature = "plugin-system")]
+use std::sync::Arc;
+#[cfg(feature = "plugin-system")]
 use tracing::{debug, info, warn};
 
 #[cfg(feature = "plugin-system")]
@@ -36,8 +40,8 @@ pub struct PluginLoader {
 
 #[cfg(feature = "plugin-system")]
 struct LoadedLibrary {
-    #[allow(dead_code)]
-    library: Library,
+    /// Shared with the registry and hook threads, which keep it mapped after unload
+    library: Arc<Library>,
     path: PathBuf,
 }
 
@@ -89,6 +93,9 @@ impl PluginLoader {
             ))
         })?;
 
+        // Refuse plugins built against a different API version before calling into them
+        Self::check_api_version(path, Self::read_api_version(&library))?;
+
         // Get the plugin creation function
         let create: Symbol<PluginCreate> = library.get(b"_plugin_create").map_err(|e| {
             CmdrunError::PluginLoad(format!(
@@ -108,9 +115,12 @@ impl PluginLoader {
 
         let plugin = Box::from_raw(plugin_ptr);
 
+        // Check the plugin's minimum cmdrun version requirement
+        Self::check_min_cmdrun_version(&plugin.metadata())?;
+
         // Store the library to keep it loaded
         self.libraries.push(LoadedLibrary {
-            library,
+            library: Arc::new(library),
             path: path.to_path_buf(),
         });
 
@@ -118,19 +128,108 @@ impl PluginLoader {
         Ok(plugin)
     }
 
+    /// Shared handle to the library loaded from `path`
+    ///
+    /// Holding the handle keeps the library mapped even after [`unload_all`](Self::unload_all).
+    pub fn library_handle(&self, path: &Path) -> Option<Arc<Library>> {
+        self.libraries
+            .iter()
+            .rev()
+            .find(|lib| lib.path == path)
+            .map(|lib| Arc::clone(&lib.library))
+    }
+
     /// Unload all plugins
     ///
+    /// A library whose handle is still held elsewhere (e.g. by a hook thread that
+    /// timed out) stays mapped until the last handle is dropped.
+    ///
     /// # Safety
     ///
     /// Plugins must be properly dropped before calling this.
     pub unsafe fn unload_all(&mut self) {
         for lib in &self.libraries {
-            debug!("Unloading plugin library: {}", lib.path.display());
+            if Arc::strong_count(&lib.library) > 1 {
+                debug!(
+                    "Plugin library still in use, deferring unload: {}",
+                    lib.path.display()
+                );
+            } else {
+                debug!("Unloading plugin library: {}", lib.path.display());
+            }
         }
         self.libraries.clear();
         info!("All plugin libraries unloaded");
     }
 
+    /// Read the exported plugin API version from a library
+    ///
+    /// Returns `None` if the library does not export `_plugin_api_version`
+    /// (e.g. it was built before version checking was introduced).
+    unsafe fn read_api_version(library: &Library) -> Option<u32> {
+        library
+            .get::<*const u32>(b"_plugin_api_version")
+            .ok()
+            .map(|symbol| **symbol)
+    }
+
+    /// Check that a plugin was built against the host's plugin API version
+    pub fn check_api_version(path: &Path, found: Option<u32>) -> Result<()> {
+        let plugin = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+
+        match found {
+            Some(version) if version == PLUGIN_API_VERSION => Ok(()),
+            Some(version) => Err(CmdrunError::PluginError {
+                plugin,
+                message: format!(
+                    "Plugin API version mismatch: expected {}, found {}. Rebuild the plugin against this version of cmdrun",
+                    PLUGIN_API_VERSION, version
+                ),
+            }),
+            None => Err(CmdrunError::PluginError {
+                plugin,
+                message: format!(
+                    "Plugin does not export _plugin_api_version (expected {}). Rebuild the plugin with declare_plugin!",
+                    PLUGIN_API_VERSION
+                ),
+            }),
+        }
+    }
+
+    /// Check a plugin's `min_cmdrun_version` against the running cmdrun version
+    pub fn check_min_cmdrun_version(metadata: &PluginMetadata) -> Result<()> {
+        Self::check_min_version_against(metadata, env!("CARGO_PKG_VERSION"))
+    }
+
+    /// Check a plugin's `min_cmdrun_version` against a given cmdrun version
+    fn check_min_version_against(metadata: &PluginMetadata, current: &str) -> Result<()> {
+        let Some(required) = &metadata.min_cmdrun_version else {
+            return Ok(());
+        };
+
+        let parse_error = |e: semver::Error| CmdrunError::PluginError {
+            plugin: metadata.name.clone(),
+            message: format!("Invalid min_cmdrun_version '{}': {}", required, e),
+        };
+        let required_version = semver::Version::parse(required).map_err(parse_error)?;
+        let current_version = semver::Version::parse(current).map_err(parse_error)?;
+
+        if current_version < required_version {
+            return Err(CmdrunError::PluginError {
+                plugin: metadata.name.clone(),
+                message: format!(
+                    "Plugin requires cmdrun {} or newer (running {})",
+                    required_version, current_version
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
     /// Get list of loaded library paths
     pub fn loaded_libraries(&self) -> Vec<PathBuf> {
         self.libraries.iter().map(|l| l.path.clone()).collect()
@@ -172,6 +271,8 @@ impl PluginLoader {
             if !has_destroy {
                 warn!("Plugin missing optional symbol: _plugin_destroy");
             }
+
+            Self::check_api_version(path, Self::read_api_version(&library))?;
         }
 
         info!("Plugin validation passed: {}", path.display());
@@ -260,6 +361,75 @@ mod tests {
         assert!(result2.is_err());
     }
 
+    fn metadata_with_min_version(min_version: Option<&str>) -> PluginMetadata {
+        PluginMetadata {
+            name: "test".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Test plugin".to_string(),
+            authors: vec![],
+            license: None,
+            homepage: None,
+            min_cmdrun_version: min_version.map(String::from),
+            capabilities: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_check_api_version() {
+        let path = Path::new("/plugins/libtest.so");
+        assert!(PluginLoader::check_api_version(path, Some(PLUGIN_API_VERSION)).is_ok());
+
+        let err = PluginLoader::check_api_version(path, Some(PLUGIN_API_VERSION + 1))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("libtest"));
+        assert!(err.contains(&format!("expected {}", PLUGIN_API_VERSION)));
+        assert!(err.contains(&format!("found {}", PLUGIN_API_VERSION + 1)));
+
+        assert!(PluginLoader::check_api_version(path, None).is_err());
+    }
+
+    #[test]
+    fn test_min_cmdrun_version_satisfied() {
+        assert!(PluginLoader::check_min_cmdrun_version(&metadata_with_min_version(None)).is_ok());
+        assert!(
+            PluginLoader::check_min_cmdrun_version(&metadata_with_min_version(Some(env!(
+                "CARGO_PKG_VERSION"
+            ))))
+            .is_ok()
+        );
+        assert!(PluginLoader::check_min_version_against(
+            &metadata_with_min_version(Some("1.0.0")),
+            "1.2.3"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_min_cmdrun_version_too_new_rejected() {
+        let err =
+            PluginLoader::check_min_cmdrun_version(&metadata_with_min_version(Some("999.0.0")))
+                .unwrap_err()
+                .to_string();
+        assert!(err.contains("requires cmdrun 999.0.0"));
+
+        assert!(PluginLoader::check_min_version_against(
+            &metadata_with_min_version(Some("1.1.0")),
+            "1.0.9"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_min_cmdrun_version_invalid() {
+        assert!(
+            PluginLoader::check_min_cmdrun_version(&metadata_with_min_version(Some(
+                "not-a-version"
+            )))
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_validate_invalid_extension() {
         // Test validation with invalid file extension
@@ -5,11 +5,13 @@
 use ahash::AHashMap;
 
 #[cfg(feature = "plugin-system")]
-use super::api::{CommandResult, HookPhase, PluginContext, PluginMetadata};
+use super::api::{CommandResult, CustomCommand, PluginContext, PluginMetadata, PreExecuteOutcome};
 #[cfg(feature = "plugin-system")]
 use super::loader::PluginLoader;
 #[cfg(feature = "plugin-system")]
-use super::registry::PluginRegistry;
+use super::registry::{LibraryHandle, PluginRegistry};
+#[cfg(feature = "plugin-system")]
+use crate::config::schema::CommandsConfig;
 #[cfg(feature = "plugin-system")]
 use crate::error::{CmdrunError, Result};
 #[cfg(feature = "plugin-system")]
@@ -17,6 +19,8 @@ use std::path::Path;
 #[cfg(feature = "plugin-system")]
 use std::sync::Arc;
 #[cfg(feature = "plugin-system")]
+use std::time::Duration;
+#[cfg(feature = "plugin-system")]
 use tracing::{debug, info, warn};
 
 /// Plugin configuration from TOML
@@ -32,6 +36,22 @@ pub struct PluginConfig {
     /// Plugin-specific configuration
     #[serde(default)]
     pub config: AHashMap<String, String>,
+
+    /// Maximum duration of a single hook call in seconds (default: 5)
+    #[serde(default)]
+    pub hook_timeout: Option<u64>,
+
+    /// Load and hook order (lower first, default: 0)
+    #[serde(default)]
+    pub priority: i32,
+
+    /// Plugins that must be loaded before this one, regardless of priority
+    #[serde(default)]
+    pub after: Vec<String>,
+
+    /// Only run hooks for commands with one of these tags (empty: every command)
+    #[serde(default)]
+    pub target_tags: Vec<String>,
 }
 
 fn default_true() -> bool {
@@ -60,6 +80,23 @@ impl PluginManager {
         }
     }
 
+    /// Load the enabled plugins configured in `config` and apply their config
+    /// modifications
+    ///
+    /// Shared by every command that reads the project's commands (`run`,
+    /// `list`, `info`, `validate`), so they all see the config `run` executes.
+    /// Returns `None` without building a manager when no plugin is enabled.
+    pub fn load_for_config(config: &mut CommandsConfig) -> Result<Option<Self>> {
+        if !config.plugins.plugins.values().any(|plugin| plugin.enabled) {
+            return Ok(None);
+        }
+
+        let mut manager = Self::new();
+        manager.load_plugins(&config.plugins.plugins)?;
+        manager.apply_config_modifications(config)?;
+        Ok(Some(manager))
+    }
+
     /// Load and register a plugin from a library file
     ///
     /// # Arguments
@@ -92,8 +129,12 @@ impl PluginManager {
                 message: format!("Failed to initialize plugin: {}", e),
             })?;
 
-        // Register the plugin
-        self.registry.register(plugin)?;
+        // Register the plugin, sharing its library with the registry's hook threads
+        let library = self
+            .loader
+            .library_handle(path)
+            .map(|library| library as LibraryHandle);
+        self.registry.register_with_library(plugin, library)?;
 
         info!("Plugin loaded and registered successfully");
         Ok(())
@@ -101,11 +142,15 @@ impl PluginManager {
 
     /// Load multiple plugins from configuration
     ///
+    /// Plugins are loaded in [`load_order`], which is also the order their
+    /// hooks run in.
+    ///
     /// # Arguments
     ///
     /// * `plugins` - Map of plugin name to plugin configuration
     pub fn load_plugins(&mut self, plugins: &AHashMap<String, PluginConfig>) -> Result<()> {
-        for (name, plugin_config) in plugins {
+        for name in load_order(plugins)? {
+            let plugin_config = &plugins[name];
             debug!("Loading plugin: {}", name);
 
             match self.load_plugin(&plugin_config.path, &plugin_config.config) {
@@ -113,6 +158,14 @@ impl PluginManager {
                     if !plugin_config.enabled {
                         self.registry.disable(name)?;
                     }
+                    if let Some(secs) = plugin_config.hook_timeout {
+                        self.registry
+                            .set_hook_timeout(name, Duration::from_secs(secs))?;
+                    }
+                    if !plugin_config.target_tags.is_empty() {
+                        self.registry
+                            .set_target_tags(name, plugin_config.target_tags.clone())?;
+                    }
                 }
                 Err(e) => {
                     warn!("Failed to load plugin {}: {}", name, e);
@@ -126,66 +179,88 @@ impl PluginManager {
 
     /// Execute pre-execution hooks
     ///
-    /// # Returns
+    /// Each hook runs under its plugin's hook timeout. A hook that times out
+    /// is skipped and treated as allowing execution to continue.
     ///
-    /// Returns `Ok(true)` if execution should continue, `Ok(false)` if execution
-    /// should be skipped.
-    pub fn execute_pre_hooks(&self, context: &mut PluginContext) -> Result<bool> {
+    /// # Errors
+    ///
+    /// Returns [`ExecutionError::BlockedByPlugin`](crate::error::ExecutionError::BlockedByPlugin)
+    /// if a plugin aborts the command.
+    pub fn execute_pre_hooks(&self, context: &mut PluginContext) -> Result<PreExecuteOutcome> {
         debug!("Executing pre-execution hooks");
-        self.registry.execute_hook(HookPhase::PreExecute, context)
+        self.registry.execute_pre_hooks(context)
     }
 
     /// Execute post-execution hooks
     pub fn execute_post_hooks(
         &self,
-        _context: &PluginContext,
-        _result: &mut CommandResult,
+        context: &PluginContext,
+        result: &mut CommandResult,
     ) -> Result<()> {
         debug!("Executing post-execution hooks");
-
-        let plugins = self.registry.list();
-        for metadata in plugins {
-            if !self.registry.is_enabled(&metadata.name) {
-                continue;
-            }
-
-            if !metadata.capabilities.post_execute {
-                continue;
-            }
-
-            // Note: This is a simplified implementation
-            // In a real implementation, we would need access to the plugin instance
-            debug!(
-                "Would execute post_execute hook for plugin: {}",
-                metadata.name
-            );
-        }
-
-        Ok(())
+        self.registry.execute_post_hooks(context, result)
     }
 
     /// Execute error hooks
-    pub fn execute_error_hooks(
-        &self,
-        _context: &PluginContext,
-        _error: &CmdrunError,
-    ) -> Result<()> {
+    pub fn execute_error_hooks(&self, context: &PluginContext, error: &CmdrunError) -> Result<()> {
         debug!("Executing error hooks");
+        self.registry.execute_error_hooks(context, error)
+    }
 
-        let plugins = self.registry.list();
-        for metadata in plugins {
-            if !self.registry.is_enabled(&metadata.name) {
-                continue;
+    /// Execute the hooks for a finished command
+    ///
+    /// Post-execution hooks run when `outcome` is `Ok`, error hooks otherwise.
+    /// A failing error hook is only logged so the command's own error is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a post-execution hook fails.
+    pub fn execute_finish_hooks(
+        &self,
+        context: &PluginContext,
+        outcome: &anyhow::Result<()>,
+        exit_code: i32,
+        duration: Duration,
+    ) -> Result<()> {
+        match outcome {
+            Ok(()) => {
+                let mut result = CommandResult {
+                    exit_code,
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    duration_ms: duration.as_millis() as u64,
+                    metadata: AHashMap::new(),
+                };
+                self.execute_post_hooks(context, &mut result)
             }
-
-            if !metadata.capabilities.on_error {
-                continue;
+            Err(e) => {
+                let hooked = match e.downcast_ref::<CmdrunError>() {
+                    Some(error) => self.execute_error_hooks(context, error),
+                    None => self.execute_error_hooks(
+                        context,
+                        &CmdrunError::Other(anyhow::anyhow!("{:#}", e)),
+                    ),
+                };
+                if let Err(hook_error) = hooked {
+                    warn!("Plugin error hook failed: {}", hook_error);
+                }
+                Ok(())
             }
-
-            debug!("Would execute on_error hook for plugin: {}", metadata.name);
         }
+    }
 
-        Ok(())
+    /// Apply configuration modifications from enabled plugins
+    ///
+    /// Must be called once after loading the configuration and before
+    /// resolving the command to run.
+    pub fn apply_config_modifications(&self, config: &mut CommandsConfig) -> Result<()> {
+        debug!("Applying plugin config modifications");
+        self.registry.modify_config(config)
+    }
+
+    /// List custom commands provided by enabled plugins
+    pub fn custom_commands(&self) -> Vec<CustomCommand> {
+        self.registry.custom_commands()
     }
 
     /// Get plugin metadata
@@ -198,6 +273,11 @@ impl PluginManager {
         self.registry.list()
     }
 
+    /// List all loaded plugins with their enabled state
+    pub fn list_plugins_with_status(&self) -> Vec<(PluginMetadata, bool)> {
+        self.registry.list_with_status()
+    }
+
     /// Enable a plugin
     pub fn enable_plugin(&self, name: &str) -> Result<()> {
         self.registry.enable(name)
@@ -243,6 +323,75 @@ impl PluginManager {
     }
 }
 
+/// Resolve the order plugins are loaded in
+///
+/// Plugins listed in `after` come first; otherwise lower `priority` wins and
+/// ties are broken by name. `after` entries naming unconfigured plugins are
+/// ignored.
+///
+/// # Errors
+///
+/// Returns error if `after` constraints form a cycle.
+#[cfg(feature = "plugin-system")]
+pub fn load_order(plugins: &AHashMap<String, PluginConfig>) -> Result<Vec<&String>> {
+    let mut remaining: Vec<&String> = plugins.keys().collect();
+    remaining.sort_by_key(|name| (plugins[*name].priority, *name));
+
+    for (name, config) in plugins {
+        for dep in &config.after {
+            if !plugins.contains_key(dep) {
+                warn!("Plugin {} is ordered after unknown plugin {}", name, dep);
+            }
+        }
+    }
+
+    let mut order: Vec<&String> = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let pending = |name: &String| remaining.contains(&name);
+        let ready = remaining
+            .iter()
+            .position(|name| !plugins[*name].after.iter().any(pending));
+
+        match ready {
+            Some(index) => order.push(remaining.remove(index)),
+            None => return Err(ordering_cycle_error(plugins, &remaining)),
+        }
+    }
+
+    Ok(order)
+}
+
+/// Build the error for `after` constraints that form a cycle
+///
+/// Every plugin in `remaining` waits for another one in `remaining`, so
+/// following those constraints from any of them ends in a cycle.
+#[cfg(feature = "plugin-system")]
+fn ordering_cycle_error(
+    plugins: &AHashMap<String, PluginConfig>,
+    remaining: &[&String],
+) -> CmdrunError {
+    let mut path: Vec<&String> = vec![remaining[0]];
+    loop {
+        let current = path[path.len() - 1];
+        let next = plugins[current]
+            .after
+            .iter()
+            .find(|dep| remaining.contains(dep))
+            .expect("every remaining plugin waits for another one");
+
+        if let Some(start) = path.iter().position(|name| *name == next) {
+            let mut cycle: Vec<String> =
+                path[start..].iter().map(|name| name.to_string()).collect();
+            cycle.push(next.clone());
+            return CmdrunError::PluginError {
+                plugin: next.clone(),
+                message: format!("Circular plugin ordering: {}", cycle.join(" → ")),
+            };
+        }
+        path.push(next);
+    }
+}
+
 #[cfg(feature = "plugin-system")]
 impl Default for PluginManager {
     fn default() -> Self {
@@ -269,12 +418,25 @@ impl PluginManager {
         Self
     }
 
+    pub fn load_for_config(
+        _config: &mut crate::config::schema::CommandsConfig,
+    ) -> Result<Option<Self>, crate::error::CmdrunError> {
+        Ok(None)
+    }
+
     pub fn load_plugins(
         &mut self,
         _plugins: &ahash::AHashMap<String, PluginConfig>,
     ) -> Result<(), crate::error::CmdrunError> {
         Ok(())
     }
+
+    pub fn apply_config_modifications(
+        &self,
+        _config: &mut crate::config::schema::CommandsConfig,
+    ) -> Result<(), crate::error::CmdrunError> {
+        Ok(())
+    }
 }
 
 #[cfg(not(feature = "plugin-system"))]
@@ -287,6 +449,7 @@ impl Default for PluginManager {
 #[cfg(all(test, feature = "plugin-system"))]
 mod tests {
     use super::*;
+    use crate::plugin::PreExecuteDecision;
 
     #[test]
     fn test_plugin_manager_creation() {
@@ -295,10 +458,702 @@ mod tests {
         assert_eq!(manager.enabled_count(), 0);
     }
 
+    #[test]
+    fn test_load_for_config_without_enabled_plugins() {
+        let mut config = CommandsConfig::default();
+        assert!(PluginManager::load_for_config(&mut config)
+            .unwrap()
+            .is_none());
+
+        // A disabled plugin is never loaded, even if its library is missing
+        let mut config: CommandsConfig = toml::from_str(
+            r#"
+[plugins.off]
+path = "/nonexistent/libcmdrun_off.so"
+enabled = false
+"#,
+        )
+        .unwrap();
+        assert!(PluginManager::load_for_config(&mut config)
+            .unwrap()
+            .is_none());
+    }
+
+    struct CiPlugin {
+        name: String,
+        declares_capability: bool,
+    }
+
+    impl crate::plugin::Plugin for CiPlugin {
+        fn metadata(&self) -> PluginMetadata {
+            PluginMetadata {
+                name: self.name.clone(),
+                version: "1.0.0".to_string(),
+                description: "Injects CI=true".to_string(),
+                authors: vec![],
+                license: None,
+                homepage: None,
+                min_cmdrun_version: None,
+                capabilities: crate::plugin::PluginCapabilities {
+                    config_modification: self.declares_capability,
+                    ..Default::default()
+                },
+            }
+        }
+
+        fn on_load(&mut self, _config: &AHashMap<String, String>) -> Result<()> {
+            Ok(())
+        }
+
+        fn modify_config(&self, config: &mut CommandsConfig) -> Result<()> {
+            config
+                .config
+                .env
+                .insert("CI".to_string(), format!("set-by-{}", self.name));
+            Ok(())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn test_config_modification_injects_env() {
+        use crate::command::executor::{CommandExecutor, ExecutionContext};
+        use crate::config::schema::{Command, CommandSpec};
+
+        let manager = PluginManager::new();
+        manager
+            .registry()
+            .register(Box::new(CiPlugin {
+                name: "ci".to_string(),
+                declares_capability: true,
+            }))
+            .unwrap();
+
+        let mut config = CommandsConfig::default();
+        manager.apply_config_modifications(&mut config).unwrap();
+        assert_eq!(config.config.env.get("CI").unwrap(), "set-by-ci");
+
+        let executor = CommandExecutor::new(ExecutionContext {
+            env: config.config.env.clone(),
+            echo: false,
+            ..Default::default()
+        });
+        let command = Command {
+            description: "print CI".to_string(),
+            cmd: CommandSpec::Single("echo ${CI}".to_string()),
+            env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
+            working_dir: None,
+            deps: vec![],
+            platform: vec![],
+            tags: vec![],
+            timeout: None,
+            parallel: false,
+            confirm: false,
+            confirm_message: None,
+            allow_chaining: None,
+            allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
+        };
+        let result = executor.execute(&command).await.unwrap();
+        assert!(result.stdout.contains("set-by-ci"));
+    }
+
+    #[test]
+    fn test_config_modification_requires_capability() {
+        let manager = PluginManager::new();
+        manager
+            .registry()
+            .register(Box::new(CiPlugin {
+                name: "undeclared".to_string(),
+                declares_capability: false,
+            }))
+            .unwrap();
+
+        let mut config = CommandsConfig::default();
+        manager.apply_config_modifications(&mut config).unwrap();
+        assert!(config.config.env.get("CI").is_none());
+    }
+
+    #[test]
+    fn test_config_modification_deterministic_order() {
+        let manager = PluginManager::new();
+        for name in ["b", "a", "c"] {
+            manager
+                .registry()
+                .register(Box::new(CiPlugin {
+                    name: name.to_string(),
+                    declares_capability: true,
+                }))
+                .unwrap();
+        }
+
+        let mut config = CommandsConfig::default();
+        manager.apply_config_modifications(&mut config).unwrap();
+        // Plugins run in load order, so the last one registered wins
+        assert_eq!(config.config.env.get("CI").unwrap(), "set-by-c");
+    }
+
+    /// Plugin whose hooks sleep for `delay` before marking the context/result
+    struct SlowPlugin {
+        name: String,
+        delay: std::time::Duration,
+    }
+
+    impl crate::plugin::Plugin for SlowPlugin {
+        fn metadata(&self) -> PluginMetadata {
+            PluginMetadata {
+                name: self.name.clone(),
+                version: "1.0.0".to_string(),
+                description: "Sleeps in hooks".to_string(),
+                authors: vec![],
+                license: None,
+                homepage: None,
+                min_cmdrun_version: None,
+                capabilities: crate::plugin::PluginCapabilities {
+                    pre_execute: true,
+                    post_execute: true,
+                    on_error: true,
+                    ..Default::default()
+                },
+            }
+        }
+
+        fn on_load(&mut self, _config: &AHashMap<String, String>) -> Result<()> {
+            Ok(())
+        }
+
+        fn pre_execute(&self, context: &mut PluginContext) -> Result<PreExecuteDecision> {
+            std::thread::sleep(self.delay);
+            context.data.insert(self.name.clone(), "pre".to_string());
+            Ok(PreExecuteDecision::Skip("slow".to_string()))
+        }
+
+        fn post_execute(&self, _context: &PluginContext, result: &mut CommandResult) -> Result<()> {
+            std::thread::sleep(self.delay);
+            result
+                .metadata
+                .insert(self.name.clone(), "post".to_string());
+            Ok(())
+        }
+
+        fn on_error(&self, _context: &PluginContext, _error: &CmdrunError) -> Result<()> {
+            std::thread::sleep(self.delay);
+            Ok(())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    fn slow_manager(delay_ms: u64, timeout_ms: u64) -> PluginManager {
+        let manager = PluginManager::new();
+        manager
+            .registry()
+            .register(Box::new(SlowPlugin {
+                name: "slow".to_string(),
+                delay: Duration::from_millis(delay_ms),
+            }))
+            .unwrap();
+        manager
+            .registry()
+            .set_hook_timeout("slow", Duration::from_millis(timeout_ms))
+            .unwrap();
+        manager
+    }
+
+    fn hook_context() -> PluginContext {
+        use crate::config::schema::{Command, CommandSpec};
+
+        PluginContext {
+            command_name: "test".to_string(),
+            command: Command {
+                description: "test".to_string(),
+                cmd: CommandSpec::Single("true".to_string()),
+                env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
+                working_dir: None,
+                deps: vec![],
+                platform: vec![],
+                tags: vec![],
+                timeout: None,
+                parallel: false,
+                confirm: false,
+                confirm_message: None,
+                allow_chaining: None,
+                allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
+            },
+            command_spec: "true".to_string(),
+            args: vec![],
+            env: AHashMap::new(),
+            working_dir: ".".to_string(),
+            data: AHashMap::new(),
+        }
+    }
+
+    fn hook_result() -> CommandResult {
+        CommandResult {
+            exit_code: 0,
+            stdout: String::new(),
+            stderr: String::new(),
+            duration_ms: 0,
+            metadata: AHashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_pre_hook_within_timeout() {
+        let manager = slow_manager(0, 5_000);
+        let mut context = hook_context();
+
+        assert_eq!(
+            manager.execute_pre_hooks(&mut context).unwrap(),
+            PreExecuteOutcome::Skipped {
+                plugin: "slow".to_string(),
+                reason: "slow".to_string()
+            }
+        );
+        assert_eq!(context.data.get("slow").unwrap(), "pre");
+    }
+
+    #[test]
+    fn test_pre_hook_timeout_continues_execution() {
+        let manager = slow_manager(2_000, 50);
+        let mut context = hook_context();
+
+        let start = std::time::Instant::now();
+        let outcome = manager.execute_pre_hooks(&mut context).unwrap();
+
+        assert_eq!(
+            outcome,
+            PreExecuteOutcome::Continue,
+            "timed-out pre_execute must not skip"
+        );
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(context.data.is_empty());
+    }
+
+    #[test]
+    fn test_post_hook_timeout_skips_plugin() {
+        let manager = slow_manager(2_000, 50);
+        let mut result = hook_result();
+
+        let start = std::time::Instant::now();
+        manager
+            .execute_post_hooks(&hook_context(), &mut result)
+            .unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(result.metadata.is_empty());
+
+        let manager = slow_manager(0, 5_000);
+        manager
+            .execute_post_hooks(&hook_context(), &mut result)
+            .unwrap();
+        assert_eq!(result.metadata.get("slow").unwrap(), "post");
+    }
+
+    #[test]
+    fn test_error_hook_timeout() {
+        let manager = slow_manager(2_000, 50);
+        let error = CmdrunError::PluginLoad("boom".to_string());
+
+        let start = std::time::Instant::now();
+        manager
+            .execute_error_hooks(&hook_context(), &error)
+            .unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_unload_all_keeps_library_while_timed_out_hook_runs() {
+        let mut manager = PluginManager::new();
+        let library: LibraryHandle = Arc::new("libslow.so");
+        let mapped = Arc::downgrade(&library);
+        manager
+            .registry()
+            .register_with_library(
+                Box::new(SlowPlugin {
+                    name: "slow".to_string(),
+                    delay: Duration::from_millis(500),
+                }),
+                Some(library),
+            )
+            .unwrap();
+        manager
+            .registry()
+            .set_hook_timeout("slow", Duration::from_millis(20))
+            .unwrap();
+
+        let mut context = hook_context();
+        manager.execute_pre_hooks(&mut context).unwrap();
+        manager.unload_all().unwrap();
+
+        // The hook thread is still inside plugin code
+        assert!(mapped.upgrade().is_some(), "library unloaded under a hook");
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while mapped.upgrade().is_some() && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(mapped.upgrade().is_none(), "library never released");
+    }
+
     #[test]
     fn test_list_empty_plugins() {
         let manager = PluginManager::new();
         let plugins = manager.list_plugins();
         assert_eq!(plugins.len(), 0);
     }
+
+    /// Plugin that appends its name to the context/result in every hook
+    struct OrderPlugin {
+        name: String,
+    }
+
+    impl crate::plugin::Plugin for OrderPlugin {
+        fn metadata(&self) -> PluginMetadata {
+            PluginMetadata {
+                name: self.name.clone(),
+                version: "1.0.0".to_string(),
+                description: "Records hook order".to_string(),
+                authors: vec![],
+                license: None,
+                homepage: None,
+                min_cmdrun_version: None,
+                capabilities: crate::plugin::PluginCapabilities {
+                    pre_execute: true,
+                    post_execute: true,
+                    ..Default::default()
+                },
+            }
+        }
+
+        fn on_load(&mut self, _config: &AHashMap<String, String>) -> Result<()> {
+            Ok(())
+        }
+
+        fn pre_execute(&self, context: &mut PluginContext) -> Result<PreExecuteDecision> {
+            context
+                .data
+                .entry("order".to_string())
+                .or_default()
+                .push_str(&self.name);
+            Ok(PreExecuteDecision::Continue)
+        }
+
+        fn post_execute(&self, _context: &PluginContext, result: &mut CommandResult) -> Result<()> {
+            result
+                .metadata
+                .entry("order".to_string())
+                .or_default()
+                .push_str(&self.name);
+            Ok(())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    fn plugin_configs(toml: &str) -> AHashMap<String, PluginConfig> {
+        toml::from_str::<CommandsConfig>(toml)
+            .unwrap()
+            .plugins
+            .plugins
+    }
+
+    /// Register `OrderPlugin`s in load order and return the pre/post hook order
+    fn hook_order(plugins: &AHashMap<String, PluginConfig>) -> (String, String) {
+        let manager = PluginManager::new();
+        for name in load_order(plugins).unwrap() {
+            manager
+                .registry()
+                .register(Box::new(OrderPlugin { name: name.clone() }))
+                .unwrap();
+        }
+
+        let mut context = hook_context();
+        assert_eq!(
+            manager.execute_pre_hooks(&mut context).unwrap(),
+            PreExecuteOutcome::Continue
+        );
+        let mut result = hook_result();
+        manager.execute_post_hooks(&context, &mut result).unwrap();
+
+        (
+            context.data.remove("order").unwrap(),
+            result.metadata.remove("order").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_hook_order_follows_priority() {
+        let plugins = plugin_configs(
+            r#"
+[plugins.a]
+path = "a.so"
+priority = 10
+
+[plugins.b]
+path = "b.so"
+priority = -5
+
+[plugins.c]
+path = "c.so"
+
+[plugins.d]
+path = "d.so"
+"#,
+        );
+
+        // Lower priority first, ties by name; post_execute unwinds in reverse
+        assert_eq!(
+            hook_order(&plugins),
+            ("bcda".to_string(), "adcb".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hook_order_follows_after_constraints() {
+        let plugins = plugin_configs(
+            r#"
+[plugins.logger]
+path = "logger.so"
+priority = 10
+
+[plugins.metrics]
+path = "metrics.so"
+priority = -10
+after = ["logger", "uninstalled"]
+
+[plugins.notify]
+path = "notify.so"
+"#,
+        );
+
+        let order: Vec<&String> = load_order(&plugins).unwrap();
+        assert_eq!(order, vec!["notify", "logger", "metrics"]);
+        assert_eq!(
+            hook_order(&plugins),
+            (
+                "notifyloggermetrics".to_string(),
+                "metricsloggernotify".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_load_order_detects_cycle() {
+        let plugins = plugin_configs(
+            r#"
+[plugins.a]
+path = "a.so"
+after = ["c"]
+
+[plugins.b]
+path = "b.so"
+after = ["a"]
+
+[plugins.c]
+path = "c.so"
+after = ["b"]
+
+[plugins.d]
+path = "d.so"
+"#,
+        );
+
+        let error = load_order(&plugins).unwrap_err().to_string();
+        assert!(error.contains("Circular plugin ordering"), "{}", error);
+        assert!(error.contains("a → c → b → a"), "{}", error);
+    }
+
+    /// Plugin whose pre_execute always returns `decision`
+    struct PolicyPlugin {
+        decision: PreExecuteDecision,
+    }
+
+    impl crate::plugin::Plugin for PolicyPlugin {
+        fn metadata(&self) -> PluginMetadata {
+            PluginMetadata {
+                name: "policy".to_string(),
+                version: "1.0.0".to_string(),
+                description: "Deploy window policy".to_string(),
+                authors: vec![],
+                license: None,
+                homepage: None,
+                min_cmdrun_version: None,
+                capabilities: crate::plugin::PluginCapabilities {
+                    pre_execute: true,
+                    ..Default::default()
+                },
+            }
+        }
+
+        fn on_load(&mut self, _config: &AHashMap<String, String>) -> Result<()> {
+            Ok(())
+        }
+
+        fn pre_execute(&self, _context: &mut PluginContext) -> Result<PreExecuteDecision> {
+            Ok(self.decision.clone())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    /// Register the policy plugin followed by a recording plugin
+    fn policy_manager(decision: PreExecuteDecision) -> PluginManager {
+        let manager = PluginManager::new();
+        manager
+            .registry()
+            .register(Box::new(PolicyPlugin { decision }))
+            .unwrap();
+        manager
+            .registry()
+            .register(Box::new(OrderPlugin {
+                name: "after".to_string(),
+            }))
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_pre_execute_continue_runs_remaining_hooks() {
+        let manager = policy_manager(PreExecuteDecision::Continue);
+        let mut context = hook_context();
+
+        assert_eq!(
+            manager.execute_pre_hooks(&mut context).unwrap(),
+            PreExecuteOutcome::Continue
+        );
+        assert_eq!(context.data.get("order").unwrap(), "after");
+    }
+
+    #[test]
+    fn test_pre_execute_skip_reports_plugin_and_reason() {
+        let manager = policy_manager(PreExecuteDecision::Skip("nothing changed".to_string()));
+        let mut context = hook_context();
+
+        assert_eq!(
+            manager.execute_pre_hooks(&mut context).unwrap(),
+            PreExecuteOutcome::Skipped {
+                plugin: "policy".to_string(),
+                reason: "nothing changed".to_string()
+            }
+        );
+        // Later plugins are not consulted once the command is skipped
+        assert!(context.data.get("order").is_none());
+    }
+
+    #[test]
+    fn test_pre_execute_abort_blocks_command() {
+        let manager = policy_manager(PreExecuteDecision::Abort(
+            "outside deploy window".to_string(),
+        ));
+        let mut context = hook_context();
+        context.command_name = "deploy".to_string();
+
+        let error = manager.execute_pre_hooks(&mut context).unwrap_err();
+        assert!(matches!(
+            error,
+            CmdrunError::Execution(crate::error::ExecutionError::BlockedByPlugin { .. })
+        ));
+        assert!(error
+            .to_string()
+            .contains("Command 'deploy' blocked by policy plugin: outside deploy window"));
+        assert_eq!(error.exit_code(), crate::error::exit_code::FAILURE);
+        assert!(context.data.get("order").is_none());
+    }
+
+    #[test]
+    fn test_target_tags_limit_hooks_to_tagged_commands() {
+        let manager = PluginManager::new();
+        for name in ["deploylog", "all"] {
+            manager
+                .registry()
+                .register(Box::new(OrderPlugin {
+                    name: name.to_string(),
+                }))
+                .unwrap();
+        }
+        manager
+            .registry()
+            .set_target_tags("deploylog", vec!["deploy".to_string()])
+            .unwrap();
+
+        let run = |tags: &[&str]| {
+            let mut context = hook_context();
+            context.command.tags = tags.iter().map(|t| t.to_string()).collect();
+            manager.execute_pre_hooks(&mut context).unwrap();
+            let mut result = hook_result();
+            manager.execute_post_hooks(&context, &mut result).unwrap();
+            (
+                context.data.remove("order").unwrap(),
+                result.metadata.remove("order").unwrap(),
+            )
+        };
+
+        // Any matching tag is enough
+        assert_eq!(
+            run(&["release", "deploy"]),
+            ("deploylogall".to_string(), "alldeploylog".to_string())
+        );
+        assert_eq!(run(&[]), ("all".to_string(), "all".to_string()));
+        assert_eq!(run(&["build"]), ("all".to_string(), "all".to_string()));
+    }
+
+    #[test]
+    fn test_plugin_config_target_tags_default_to_all_commands() {
+        let plugins = plugin_configs(
+            r#"
+[plugins.logger]
+path = "logger.so"
+
+[plugins.audit]
+path = "audit.so"
+target_tags = ["deploy"]
+"#,
+        );
+
+        assert!(plugins["logger"].target_tags.is_empty());
+        assert_eq!(plugins["audit"].target_tags, vec!["deploy"]);
+    }
 }
@@ -22,6 +22,10 @@
 //!     path: "/path/to/plugin.so".to_string(),
 //!     enabled: true,
 //!     config: AHashMap::new(),
+//!     hook_timeout: None,
+//!     priority: 0,
+//!     after: vec![],
+//!     target_tags: vec![],
 //! });
 //!
 //! manager.load_plugins(&plugins).unwrap();
@@ -39,7 +43,8 @@ pub mod registry;
 
 // Re-export commonly used types
 pub use api::{
-    CommandResult, HookPhase, Plugin, PluginCapabilities, PluginContext, PluginMetadata,
+    CommandResult, CustomCommand, CustomCommandHandler, HookPhase, Plugin, PluginCapabilities,
+    PluginContext, PluginMetadata, PreExecuteDecision, PreExecuteOutcome,
 };
 pub use manager::{PluginConfig, PluginManager};
 
@@ -54,7 +59,7 @@ pub const PLUGIN_SYSTEM_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// Plugin API version
 ///
 /// Increment this when making breaking changes to the Plugin trait.
-pub const PLUGIN_API_VERSION: u32 = 1;
+pub const PLUGIN_API_VERSION: u32 = 2;
 
 #[cfg(test)]
 mod tests {
@@ -62,6 +67,6 @@ mod tests {
 
     #[test]
     fn test_plugin_api_version() {
-        assert_eq!(PLUGIN_API_VERSION, 1);
+        assert_eq!(PLUGIN_API_VERSION, 2);
     }
 }
@@ -2,24 +2,64 @@
 //!
 //! Manages loaded plugins and their lifecycle.
 
-use super::api::{HookPhase, Plugin, PluginContext, PluginMetadata};
+use super::api::{
+    CommandResult, CustomCommand, HookPhase, Plugin, PluginContext, PluginMetadata,
+    PreExecuteDecision, PreExecuteOutcome,
+};
+use crate::config::schema::CommandsConfig;
 use crate::config::Language;
-use crate::error::{CmdrunError, Result};
+use crate::error::{CmdrunError, ExecutionError, Result};
 use crate::i18n::{get_message, MessageKey};
 use ahash::AHashMap;
+use std::any::Any;
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
+/// Default time a single plugin hook may run before it is skipped
+pub const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Handle keeping a plugin's dynamic library mapped
+///
+/// The library is unloaded once the registry and every hook thread have
+/// dropped their clone.
+pub type LibraryHandle = Arc<dyn Any + Send + Sync>;
+
 /// Plugin instance wrapper
 struct PluginInstance {
     /// Plugin implementation
-    plugin: Box<dyn Plugin>,
+    ///
+    /// Shared so hooks can run on a dedicated thread that may outlive a timeout.
+    plugin: Arc<dyn Plugin>,
+
+    /// Library the plugin code lives in (`None` for statically registered plugins)
+    ///
+    /// Declared after `plugin` so the plugin is dropped while its code is still mapped.
+    library: Option<LibraryHandle>,
 
     /// Plugin metadata cache
     metadata: PluginMetadata,
 
     /// Plugin enabled state
     enabled: bool,
+
+    /// Maximum duration of a single hook call
+    hook_timeout: Duration,
+
+    /// Registration sequence number; hooks run in this order
+    load_order: usize,
+
+    /// Command tags the hooks are limited to (empty: every command)
+    target_tags: Vec<String>,
+}
+
+impl PluginInstance {
+    /// Whether the plugin's hooks apply to a command with `tags`
+    fn targets(&self, tags: &[String]) -> bool {
+        self.target_tags.is_empty() || self.target_tags.iter().any(|tag| tags.contains(tag))
+    }
 }
 
 /// Plugin registry
@@ -47,7 +87,23 @@ impl PluginRegistry {
     /// # Errors
     ///
     /// Returns error if plugin with same name already exists.
-    pub fn register(&self, mut plugin: Box<dyn Plugin>) -> Result<()> {
+    pub fn register(&self, plugin: Box<dyn Plugin>) -> Result<()> {
+        self.register_with_library(plugin, None)
+    }
+
+    /// Register a plugin loaded from a dynamic library
+    ///
+    /// The registry and every hook thread hold a clone of `library`, so it stays
+    /// mapped while a timed-out hook is still running plugin code.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if plugin with same name already exists.
+    pub fn register_with_library(
+        &self,
+        mut plugin: Box<dyn Plugin>,
+        library: Option<LibraryHandle>,
+    ) -> Result<()> {
         let metadata = plugin.metadata();
         let name = metadata.name.clone();
 
@@ -95,12 +151,21 @@ impl PluginRegistry {
             ),
         })?;
 
+        let load_order = plugins
+            .values()
+            .map(|p| p.load_order + 1)
+            .max()
+            .unwrap_or(0);
         plugins.insert(
             name.clone(),
             PluginInstance {
-                plugin,
+                plugin: Arc::from(plugin),
+                library,
                 metadata,
                 enabled: true,
+                hook_timeout: DEFAULT_HOOK_TIMEOUT,
+                load_order,
+                target_tags: Vec::new(),
             },
         );
 
@@ -126,17 +191,22 @@ impl PluginRegistry {
         })?;
 
         if let Some(mut instance) = plugins.remove(name) {
-            instance
-                .plugin
-                .on_unload()
-                .map_err(|e| CmdrunError::PluginError {
-                    plugin: name.to_string(),
-                    message: format!(
-                        "{}: {}",
-                        get_message(MessageKey::ErrorFailedToUnloadPlugin, Language::English),
-                        e
-                    ),
-                })?;
+            let Some(plugin) = Arc::get_mut(&mut instance.plugin) else {
+                warn!(
+                    "Plugin {} still has a hook running, skipping on_unload",
+                    name
+                );
+                return Ok(());
+            };
+
+            plugin.on_unload().map_err(|e| CmdrunError::PluginError {
+                plugin: name.to_string(),
+                message: format!(
+                    "{}: {}",
+                    get_message(MessageKey::ErrorFailedToUnloadPlugin, Language::English),
+                    e
+                ),
+            })?;
             info!("Plugin unregistered successfully: {}", name);
             Ok(())
         } else {
@@ -193,6 +263,52 @@ impl PluginRegistry {
         }
     }
 
+    /// Set the maximum duration of a single hook call for a plugin
+    pub fn set_hook_timeout(&self, name: &str, timeout: Duration) -> Result<()> {
+        let mut plugins = self.plugins.write().map_err(|e| CmdrunError::PluginError {
+            plugin: name.to_string(),
+            message: format!(
+                "{}: {}",
+                get_message(MessageKey::ErrorFailedToAcquireWriteLock, Language::English),
+                e
+            ),
+        })?;
+
+        if let Some(instance) = plugins.get_mut(name) {
+            instance.hook_timeout = timeout;
+            Ok(())
+        } else {
+            Err(CmdrunError::PluginError {
+                plugin: name.to_string(),
+                message: "Plugin not found".to_string(),
+            })
+        }
+    }
+
+    /// Limit a plugin's hooks to commands with one of `tags`
+    ///
+    /// An empty list applies the hooks to every command.
+    pub fn set_target_tags(&self, name: &str, tags: Vec<String>) -> Result<()> {
+        let mut plugins = self.plugins.write().map_err(|e| CmdrunError::PluginError {
+            plugin: name.to_string(),
+            message: format!(
+                "{}: {}",
+                get_message(MessageKey::ErrorFailedToAcquireWriteLock, Language::English),
+                e
+            ),
+        })?;
+
+        if let Some(instance) = plugins.get_mut(name) {
+            instance.target_tags = tags;
+            Ok(())
+        } else {
+            Err(CmdrunError::PluginError {
+                plugin: name.to_string(),
+                message: "Plugin not found".to_string(),
+            })
+        }
+    }
+
     /// Check if a plugin is enabled
     pub fn is_enabled(&self, name: &str) -> bool {
         if let Ok(plugins) = self.plugins.read() {
@@ -211,26 +327,44 @@ impl PluginRegistry {
         }
     }
 
-    /// List all registered plugins
+    /// List all registered plugins in load order
     pub fn list(&self) -> Vec<PluginMetadata> {
         if let Ok(plugins) = self.plugins.read() {
-            plugins.values().map(|p| p.metadata.clone()).collect()
+            in_load_order(&plugins)
+                .into_iter()
+                .map(|(_, p)| p.metadata.clone())
+                .collect()
         } else {
             Vec::new()
         }
     }
 
-    /// Execute plugin hook
+    /// List all registered plugins with their enabled state, in load order
     ///
-    /// # Arguments
+    /// Read-only: plugins are not called and nothing is changed.
+    pub fn list_with_status(&self) -> Vec<(PluginMetadata, bool)> {
+        if let Ok(plugins) = self.plugins.read() {
+            in_load_order(&plugins)
+                .into_iter()
+                .map(|(_, p)| (p.metadata.clone(), p.enabled))
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Execute pre-execution hooks of enabled plugins
     ///
-    /// * `phase` - Hook phase to execute
-    /// * `context` - Plugin execution context
+    /// Plugins run in load order; plugins whose `target_tags` do not match the
+    /// command's tags are left out. The first plugin that skips or aborts the
+    /// command stops the remaining hooks. A hook that times out is skipped and
+    /// treated as allowing execution to continue.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// For PreExecute: Returns `Ok(true)` if execution should continue
-    pub fn execute_hook(&self, phase: HookPhase, context: &mut PluginContext) -> Result<bool> {
+    /// Returns [`ExecutionError::BlockedByPlugin`] if a plugin aborts the
+    /// command, or a plugin error if a hook fails.
+    pub fn execute_pre_hooks(&self, context: &mut PluginContext) -> Result<PreExecuteOutcome> {
         let plugins = self.plugins.read().map_err(|e| CmdrunError::PluginError {
             plugin: "registry".to_string(),
             message: format!(
@@ -240,46 +374,58 @@ impl PluginRegistry {
             ),
         })?;
 
-        let mut should_continue = true;
-
-        for (name, instance) in plugins.iter() {
-            if !instance.enabled {
+        for (name, instance) in in_load_order(&plugins) {
+            if !instance.enabled
+                || !instance.metadata.capabilities.pre_execute
+                || !instance.targets(&context.command.tags)
+            {
                 continue;
             }
 
-            // Check if plugin supports this hook
-            let supports_hook = match phase {
-                HookPhase::PreExecute => instance.metadata.capabilities.pre_execute,
-                HookPhase::PostExecute => instance.metadata.capabilities.post_execute,
-                HookPhase::OnError => instance.metadata.capabilities.on_error,
-            };
+            debug!("Executing pre_execute hook for plugin: {}", name);
+
+            let plugin = Arc::clone(&instance.plugin);
+            let mut hook_context = context.clone();
+            let outcome = run_hook(
+                name,
+                HookPhase::PreExecute,
+                instance.hook_timeout,
+                instance.library.clone(),
+                move || {
+                    let decision = plugin.pre_execute(&mut hook_context);
+                    (decision, hook_context)
+                },
+            );
 
-            if !supports_hook {
+            // A hung or crashed hook must not block the user's command
+            let Some((decision, hook_context)) = outcome else {
                 continue;
-            }
-
-            debug!("Executing {} hook for plugin: {}", phase, name);
-
-            let result = match phase {
-                HookPhase::PreExecute => instance.plugin.pre_execute(context),
-                HookPhase::PostExecute => {
-                    // For PostExecute, we need to get result from context
-                    // This will be handled by the manager
-                    Ok(true)
-                }
-                HookPhase::OnError => {
-                    // Error will be provided by the manager
-                    Ok(true)
-                }
             };
-
-            match result {
-                Ok(cont) => {
-                    if !cont {
-                        info!("Plugin {} requested to skip execution", name);
-                        should_continue = false;
-                        break;
+            *context = hook_context;
+
+            match decision {
+                Ok(PreExecuteDecision::Continue) => {}
+                Ok(PreExecuteDecision::Skip(reason)) => {
+                    info!(
+                        "Plugin {} skipped {}: {}",
+                        name, context.command_name, reason
+                    );
+                    return Ok(PreExecuteOutcome::Skipped {
+                        plugin: name.clone(),
+                        reason,
+                    });
+                }
+                Ok(PreExecuteDecision::Abort(reason)) => {
+                    info!(
+                        "Plugin {} blocked {}: {}",
+                        name, context.command_name, reason
+                    );
+                    return Err(ExecutionError::BlockedByPlugin {
+                        command: context.command_name.clone(),
+                        plugin: name.clone(),
+                        reason,
                     }
+                    .into());
                 }
                 Err(e) => {
                     warn!("Plugin {} hook failed: {}", name, e);
@@ -291,7 +437,191 @@ impl PluginRegistry {
             }
         }
 
-        Ok(should_continue)
+        Ok(PreExecuteOutcome::Continue)
+    }
+
+    /// Execute post-execution hooks of enabled plugins
+    ///
+    /// Plugins run in reverse load order and may modify `result`. A hook that
+    /// times out is skipped and its changes are discarded.
+    pub fn execute_post_hooks(
+        &self,
+        context: &PluginContext,
+        result: &mut CommandResult,
+    ) -> Result<()> {
+        let plugins = self.plugins.read().map_err(|e| CmdrunError::PluginError {
+            plugin: "registry".to_string(),
+            message: format!(
+                "{}: {}",
+                get_message(MessageKey::ErrorFailedToAcquireReadLock, Language::English),
+                e
+            ),
+        })?;
+
+        // Reverse load order, so the first plugin wraps all others
+        for (name, instance) in in_load_order(&plugins).into_iter().rev() {
+            if !instance.enabled
+                || !instance.metadata.capabilities.post_execute
+                || !instance.targets(&context.command.tags)
+            {
+                continue;
+            }
+
+            debug!("Executing post_execute hook for plugin: {}", name);
+
+            let plugin = Arc::clone(&instance.plugin);
+            let hook_context = context.clone();
+            let mut hook_result = result.clone();
+            let outcome = run_hook(
+                name,
+                HookPhase::PostExecute,
+                instance.hook_timeout,
+                instance.library.clone(),
+                move || {
+                    let outcome = plugin.post_execute(&hook_context, &mut hook_result);
+                    (outcome, hook_result)
+                },
+            );
+
+            match outcome {
+                Some((Ok(()), hook_result)) => *result = hook_result,
+                Some((Err(e), _)) => {
+                    warn!("Plugin {} hook failed: {}", name, e);
+                    return Err(CmdrunError::PluginError {
+                        plugin: name.clone(),
+                        message: format!("Hook execution failed: {}", e),
+                    });
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Execute error hooks of enabled plugins in reverse load order
+    ///
+    /// `CmdrunError` is not `Clone`, so hooks receive a copy carrying the
+    /// original error message. Hook failures are logged and ignored since an
+    /// error is already being reported.
+    pub fn execute_error_hooks(&self, context: &PluginContext, error: &CmdrunError) -> Result<()> {
+        let plugins = self.plugins.read().map_err(|e| CmdrunError::PluginError {
+            plugin: "registry".to_string(),
+            message: format!(
+                "{}: {}",
+                get_message(MessageKey::ErrorFailedToAcquireReadLock, Language::English),
+                e
+            ),
+        })?;
+
+        let message = error.to_string();
+
+        // Reverse load order, like post-execution hooks
+        for (name, instance) in in_load_order(&plugins).into_iter().rev() {
+            if !instance.enabled
+                || !instance.metadata.capabilities.on_error
+                || !instance.targets(&context.command.tags)
+            {
+                continue;
+            }
+
+            debug!("Executing on_error hook for plugin: {}", name);
+
+            let plugin = Arc::clone(&instance.plugin);
+            let hook_context = context.clone();
+            let hook_error = CmdrunError::Other(anyhow::anyhow!("{}", message));
+            let outcome = run_hook(
+                name,
+                HookPhase::OnError,
+                instance.hook_timeout,
+                instance.library.clone(),
+                move || plugin.on_error(&hook_context, &hook_error),
+            );
+
+            if let Some(Err(e)) = outcome {
+                warn!("Plugin {} hook failed: {}", name, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply configuration modifications from enabled plugins
+    ///
+    /// Plugins are invoked in load order so the result is deterministic. Only
+    /// plugins declaring the `config_modification` capability are called.
+    pub fn modify_config(&self, config: &mut CommandsConfig) -> Result<()> {
+        let plugins = self.plugins.read().map_err(|e| CmdrunError::PluginError {
+            plugin: "registry".to_string(),
+            message: format!(
+                "{}: {}",
+                get_message(MessageKey::ErrorFailedToAcquireReadLock, Language::English),
+                e
+            ),
+        })?;
+
+        for (name, instance) in in_load_order(&plugins) {
+            if !instance.enabled || !instance.metadata.capabilities.config_modification {
+                continue;
+            }
+
+            debug!("Applying config modifications from plugin: {}", name);
+            let before = config.clone();
+
+            instance
+                .plugin
+                .modify_config(config)
+                .map_err(|e| CmdrunError::PluginError {
+                    plugin: name.clone(),
+                    message: format!("Config modification failed: {}", e),
+                })?;
+
+            log_config_changes(name, &before, config);
+        }
+
+        Ok(())
+    }
+
+    /// Collect custom commands from enabled plugins
+    ///
+    /// Only plugins declaring the `custom_commands` capability are consulted.
+    /// Commands are returned sorted by name; when two plugins provide the same
+    /// name, the plugin loaded first wins.
+    pub fn custom_commands(&self) -> Vec<CustomCommand> {
+        let plugins = match self.plugins.read() {
+            Ok(plugins) => plugins,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut commands: AHashMap<String, CustomCommand> = AHashMap::new();
+        for (name, instance) in in_load_order(&plugins) {
+            if !instance.enabled || !instance.metadata.capabilities.custom_commands {
+                continue;
+            }
+
+            for command in instance.plugin.commands() {
+                if commands.contains_key(&command.name) {
+                    warn!(
+                        "Plugin {} provides duplicate command '{}', ignoring",
+                        name, command.name
+                    );
+                    continue;
+                }
+                debug!("Plugin {} provides command: {}", name, command.name);
+                commands.insert(command.name.clone(), command);
+            }
+        }
+
+        let mut commands: Vec<CustomCommand> = commands.into_values().collect();
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        commands
+    }
+
+    /// Find a custom command by name
+    pub fn find_command(&self, name: &str) -> Option<CustomCommand> {
+        self.custom_commands()
+            .into_iter()
+            .find(|command| command.name == name)
     }
 
     /// Get count of registered plugins
@@ -327,8 +657,16 @@ impl PluginRegistry {
 
         for name in plugin_names {
             if let Some(mut instance) = plugins.remove(&name) {
-                if let Err(e) = instance.plugin.on_unload() {
-                    warn!("Failed to unload plugin {}: {}", name, e);
+                match Arc::get_mut(&mut instance.plugin) {
+                    Some(plugin) => {
+                        if let Err(e) = plugin.on_unload() {
+                            warn!("Failed to unload plugin {}: {}", name, e);
+                        }
+                    }
+                    None => warn!(
+                        "Plugin {} still has a hook running, skipping on_unload",
+                        name
+                    ),
                 }
             }
         }
@@ -338,6 +676,95 @@ impl PluginRegistry {
     }
 }
 
+/// Run a plugin hook on a dedicated thread, giving up after `timeout`
+///
+/// Returns `None` if the hook timed out or panicked. A timed-out hook keeps
+/// running in the background; its result is discarded. The thread owns a clone
+/// of `library` until the hook and its captures are dropped, so unloading the
+/// plugin cannot unmap code that is still executing.
+fn run_hook<T, F>(
+    name: &str,
+    phase: HookPhase,
+    timeout: Duration,
+    library: Option<LibraryHandle>,
+    hook: F,
+) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+
+    let spawned = thread::Builder::new()
+        .name(format!("plugin-{}-{}", name, phase))
+        .spawn(move || {
+            // Dropped last, after the hook's captures and result
+            let _library = library;
+            // The receiver is gone if the hook timed out
+            let _ = tx.send(hook());
+        });
+
+    if let Err(e) = spawned {
+        warn!(
+            "Failed to spawn thread for plugin {} {} hook: {}",
+            name, phase, e
+        );
+        return None;
+    }
+
+    match rx.recv_timeout(timeout) {
+        Ok(value) => Some(value),
+        Err(RecvTimeoutError::Timeout) => {
+            warn!(
+                "Plugin {} {} hook timed out after {:?}, skipping",
+                name, phase, timeout
+            );
+            None
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            warn!("Plugin {} {} hook panicked, skipping", name, phase);
+            None
+        }
+    }
+}
+
+/// Registered plugins sorted by registration sequence
+fn in_load_order(plugins: &AHashMap<String, PluginInstance>) -> Vec<(&String, &PluginInstance)> {
+    let mut ordered: Vec<(&String, &PluginInstance)> = plugins.iter().collect();
+    ordered.sort_by_key(|(_, instance)| instance.load_order);
+    ordered
+}
+
+/// Log configuration changes made by a plugin at debug level
+fn log_config_changes(plugin: &str, before: &CommandsConfig, after: &CommandsConfig) {
+    for (key, value) in &after.config.env {
+        if before.config.env.get(key) != Some(value) {
+            debug!("Plugin {} set env {}", plugin, key);
+        }
+    }
+    for key in before.config.env.keys() {
+        if !after.config.env.contains_key(key) {
+            debug!("Plugin {} removed env {}", plugin, key);
+        }
+    }
+    for name in after.commands.keys() {
+        if !before.commands.contains_key(name) {
+            debug!("Plugin {} added command: {}", plugin, name);
+        }
+    }
+    for name in before.commands.keys() {
+        if !after.commands.contains_key(name) {
+            debug!("Plugin {} removed command: {}", plugin, name);
+        }
+    }
+    if before.config.timeout != after.config.timeout {
+        debug!(
+            "Plugin {} changed timeout: {:?} -> {:?}",
+            plugin, before.config.timeout, after.config.timeout
+        );
+    }
+}
+
 impl Default for PluginRegistry {
     fn default() -> Self {
         Self::new()
@@ -386,8 +813,8 @@ mod tests {
             Ok(())
         }
 
-        fn pre_execute(&self, _context: &mut PluginContext) -> Result<bool> {
-            Ok(true)
+        fn pre_execute(&self, _context: &mut PluginContext) -> Result<PreExecuteDecision> {
+            Ok(PreExecuteDecision::Continue)
         }
 
         fn post_execute(
@@ -463,6 +890,93 @@ mod tests {
         assert_eq!(registry.count(), 0);
     }
 
+    struct CommandPlugin {
+        name: String,
+        declares_capability: bool,
+    }
+
+    impl Plugin for CommandPlugin {
+        fn metadata(&self) -> PluginMetadata {
+            PluginMetadata {
+                name: self.name.clone(),
+                version: "1.0.0".to_string(),
+                description: "Command plugin".to_string(),
+                authors: vec!["Test".to_string()],
+                license: None,
+                homepage: None,
+                min_cmdrun_version: None,
+                capabilities: PluginCapabilities {
+                    custom_commands: self.declares_capability,
+                    ..Default::default()
+                },
+            }
+        }
+
+        fn on_load(&mut self, _config: &AHashMap<String, String>) -> Result<()> {
+            Ok(())
+        }
+
+        fn commands(&self) -> Vec<CustomCommand> {
+            let plugin = self.name.clone();
+            vec![CustomCommand::new(
+                format!("{}-cmd", self.name),
+                "Test command",
+                move |args, _context| {
+                    Ok(CommandResult {
+                        exit_code: 0,
+                        stdout: format!("{}:{}", plugin, args.join(",")),
+                        stderr: String::new(),
+                        duration_ms: 0,
+                        metadata: AHashMap::new(),
+                    })
+                },
+            )]
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_custom_commands() {
+        let registry = PluginRegistry::new();
+        registry
+            .register(Box::new(CommandPlugin {
+                name: "b".to_string(),
+                declares_capability: true,
+            }))
+            .unwrap();
+        registry
+            .register(Box::new(CommandPlugin {
+                name: "a".to_string(),
+                declares_capability: true,
+            }))
+            .unwrap();
+        registry
+            .register(Box::new(CommandPlugin {
+                name: "silent".to_string(),
+                declares_capability: false,
+            }))
+            .unwrap();
+
+        let names: Vec<String> = registry
+            .custom_commands()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["a-cmd".to_string(), "b-cmd".to_string()]);
+        assert!(registry.find_command("silent-cmd").is_none());
+
+        registry.disable("a").unwrap();
+        assert!(registry.find_command("a-cmd").is_none());
+        assert!(registry.find_command("b-cmd").is_some());
+    }
+
     #[test]
     fn test_list_plugins() {
         let registry = PluginRegistry::new();
@@ -5,5 +5,7 @@
 pub mod secrets;
 pub mod validation;
 
-pub use secrets::{SensitiveEnv, SensitiveString};
-pub use validation::{CommandValidator, ValidationResult};
+pub use secrets::{
+    EnvRedactor, RedactedValue, SensitiveEnv, SensitiveString, DEFAULT_REDACT_PATTERNS, REDACTED,
+};
+pub use validation::{CommandValidator, ValidationResult, Violation, ViolationKind};
@@ -2,10 +2,29 @@
 //!
 //! secrecyクレートを活用した機密情報のマスキングと安全な取り扱い
 
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use secrecy::{ExposeSecret, SecretString};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::fmt;
 
+/// 伏せ字（履歴・エクスポートでの機密値の表示）
+pub const REDACTED: &str = "***REDACTED***";
+
+/// デフォルトの伏せ字対象キーパターン（glob形式、大文字小文字を区別しない）
+pub const DEFAULT_REDACT_PATTERNS: &[&str] = &[
+    "*TOKEN*",
+    "*SECRET*",
+    "*PASSWORD*",
+    "*PASSWD*",
+    "*PASSPHRASE*",
+    "*_PASS",
+    "*_KEY*",
+    "*APIKEY*",
+    "*AUTH*",
+    "*CREDENTIAL*",
+];
+
 /// 機密文字列型（ログ出力時に自動マスキング）
 #[derive(Clone)]
 pub struct SensitiveString(SecretString);
@@ -50,6 +69,15 @@ impl fmt::Display for SensitiveString {
     }
 }
 
+impl Serialize for SensitiveString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
 impl From<String> for SensitiveString {
     fn from(value: String) -> Self {
         Self::new(value)
@@ -151,6 +179,77 @@ impl Default for SensitiveEnv {
     }
 }
 
+/// 伏せ字処理後の環境変数値
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum RedactedValue {
+    /// そのまま記録する値
+    Plain(String),
+    /// 機密値（シリアライズ時は `***REDACTED***`）
+    Sensitive(SensitiveString),
+}
+
+impl fmt::Display for RedactedValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RedactedValue::Plain(value) => f.write_str(value),
+            RedactedValue::Sensitive(_) => f.write_str(REDACTED),
+        }
+    }
+}
+
+/// キー名のglobパターンで環境変数値を伏せ字にする
+#[derive(Debug, Clone)]
+pub struct EnvRedactor {
+    patterns: GlobSet,
+}
+
+impl EnvRedactor {
+    /// パターン一覧から作成（`*_TOKEN` のようなglob形式）
+    pub fn new<I, S>(patterns: I) -> Result<Self, globset::Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            builder.add(
+                GlobBuilder::new(pattern.as_ref())
+                    .case_insensitive(true)
+                    .build()?,
+            );
+        }
+        Ok(Self {
+            patterns: builder.build()?,
+        })
+    }
+
+    /// キーが伏せ字対象かチェック
+    pub fn is_redacted(&self, key: &str) -> bool {
+        self.patterns.is_match(key)
+    }
+
+    /// 環境変数マップを伏せ字処理（キー順）
+    pub fn redact(&self, env: &ahash::AHashMap<String, String>) -> BTreeMap<String, RedactedValue> {
+        env.iter()
+            .map(|(k, v)| {
+                let value = if self.is_redacted(k) {
+                    RedactedValue::Sensitive(SensitiveString::new(v.clone()))
+                } else {
+                    RedactedValue::Plain(v.clone())
+                };
+                (k.clone(), value)
+            })
+            .collect()
+    }
+}
+
+impl Default for EnvRedactor {
+    fn default() -> Self {
+        Self::new(DEFAULT_REDACT_PATTERNS).expect("default redact patterns are valid")
+    }
+}
+
 /// Serdeサポート用の機密文字列ラッパー
 #[derive(Clone, Serialize, Deserialize)]
 pub struct SecretField(#[serde(with = "secret_string")] String);
@@ -258,6 +357,60 @@ mod tests {
         assert_eq!(masked.get("USER_NAME").unwrap(), "alice");
     }
 
+    #[test]
+    fn test_env_redactor_patterns() {
+        let redactor = EnvRedactor::default();
+
+        assert!(redactor.is_redacted("GITHUB_TOKEN"));
+        assert!(redactor.is_redacted("aws_secret_access_key"));
+        assert!(redactor.is_redacted("DB_PASSWORD"));
+        assert!(redactor.is_redacted("STRIPE_API_KEY"));
+        assert!(!redactor.is_redacted("PATH"));
+        assert!(!redactor.is_redacted("KEYBOARD_LAYOUT"));
+    }
+
+    #[test]
+    fn test_env_redactor_covers_previous_sensitive_names() {
+        let redactor = EnvRedactor::default();
+        for key in [
+            "API_KEY",
+            "SECRET_TOKEN",
+            "PASSWORD",
+            "DB_PASS",
+            "PASSPHRASE",
+            "GPG_PASSPHRASE",
+            "SSH_KEY_PATH",
+            "AWS_SECRET_KEY_ID",
+            "AWS_ACCESS_KEY_ID",
+            "PRIVATE_KEY_FILE",
+            "MY_APIKEY",
+            "AUTH_HEADER",
+            "GOOGLE_CREDENTIALS",
+        ] {
+            assert!(redactor.is_redacted(key), "{} should be redacted", key);
+        }
+        assert!(!redactor.is_redacted("HOME"));
+
+        let custom = EnvRedactor::new(["MY_*"]).unwrap();
+        assert!(custom.is_redacted("my_value"));
+        assert!(!custom.is_redacted("GITHUB_TOKEN"));
+    }
+
+    #[test]
+    fn test_env_redactor_serialization() {
+        let mut env = ahash::AHashMap::new();
+        env.insert("GITHUB_TOKEN".to_string(), "ghp_abc123".to_string());
+        env.insert("HOME".to_string(), "/home/user".to_string());
+
+        let redacted = EnvRedactor::default().redact(&env);
+        let json = serde_json::to_string(&redacted).unwrap();
+        assert_eq!(
+            json,
+            r#"{"GITHUB_TOKEN":"***REDACTED***","HOME":"/home/user"}"#
+        );
+        assert_eq!(redacted["GITHUB_TOKEN"].to_string(), REDACTED);
+    }
+
     #[test]
     fn test_zeroize_on_drop() {
         // Zeroizeが正しく動作することを確認
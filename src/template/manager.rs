@@ -1,14 +1,23 @@
 //! Template manager for storing and retrieving user templates
 
+use ahash::AHashMap;
 use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::command::executor::{CommandExecutor, ExecutionContext};
+use crate::config::schema::{CommandSpec, CommandsConfig};
 use crate::config::Language;
 use crate::i18n::{get_message, MessageKey};
 use crate::template::builtin::BuiltinTemplate;
 use crate::template::schema::UserTemplate;
 
+/// Placeholder pattern: {{name}}
+static PLACEHOLDER_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{[[:space:]]*([A-Za-z_][A-Za-z0-9_]*)[[:space:]]*\}\}").unwrap());
+
 /// Template manager
 pub struct TemplateManager {
     /// Template directory path
@@ -192,27 +201,133 @@ impl TemplateManager {
 
     /// Import template from a file
     pub fn import(&self, file_path: &Path) -> Result<String> {
-        if !file_path.exists() {
-            anyhow::bail!(
-                "{}: {}",
-                get_message(MessageKey::ErrorFileNotFound, self.language),
-                file_path.display()
-            );
+        let template = self.parse_file(file_path)?;
+        self.install(&template)?;
+
+        Ok(template.template.name.clone())
+    }
+
+    /// Validate and save an already parsed template
+    ///
+    /// Applies the same checks as [`Self::check_file`].
+    pub fn install(&self, template: &UserTemplate) -> Result<()> {
+        let problems = self.problems(template);
+        if !problems.is_empty() {
+            anyhow::bail!("{}", problems.join("; "));
         }
 
-        let content = fs::read_to_string(file_path)
-            .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+        self.save(template)
+    }
 
-        let template: UserTemplate = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse template from {}", file_path.display()))?;
+    /// Check a template file without importing it
+    ///
+    /// Runs the same checks as [`Self::import`] and returns every problem
+    /// found. Read and parse failures are returned as an error.
+    pub fn check_file(&self, file_path: &Path) -> Result<(UserTemplate, Vec<String>)> {
+        let template = self.parse_file(file_path)?;
+        let problems = self.problems(&template);
+        Ok((template, problems))
+    }
 
-        // Validate template
-        template.validate()?;
+    /// Check template content fetched from `source` (e.g. a URL)
+    pub fn check_content(
+        &self,
+        content: &str,
+        source: &str,
+    ) -> Result<(UserTemplate, Vec<String>)> {
+        let template = parse_content(content, source)?;
+        let problems = self.problems(&template);
+        Ok((template, problems))
+    }
 
-        // Save template
-        self.save(&template)?;
+    /// Collect structural and security problems in a template
+    fn problems(&self, template: &UserTemplate) -> Vec<String> {
+        let mut problems = template.problems_with_language(self.language);
 
-        Ok(template.template.name.clone())
+        // A template must not relax the security settings it is vetted with
+        if let Some(config) = &template.config {
+            if config.allow_command_chaining {
+                problems.push("Template [config] enables allow_command_chaining".to_string());
+            }
+            if config.allow_subshells {
+                problems.push("Template [config] enables allow_subshells".to_string());
+            }
+            if !config.strict_mode {
+                problems.push("Template [config] disables strict_mode".to_string());
+            }
+            if config.command_allowlist.is_some() {
+                problems.push("Template [config] sets command_allowlist".to_string());
+            }
+        }
+
+        // Commands are checked the way the executor would check them with the
+        // default security settings, not the template's own
+        let executor = CommandExecutor::new(ExecutionContext::default());
+
+        let mut ids: Vec<&String> = template.commands.keys().collect();
+        ids.sort();
+        for id in ids {
+            let command = &template.commands[id];
+            if command.allow_chaining == Some(true) {
+                problems.push(format!("Command '{}' enables allow_chaining", id));
+            }
+            if command.allow_subshells == Some(true) {
+                problems.push(format!("Command '{}' enables allow_subshells", id));
+            }
+            let validator = executor.build_validator_for_command(command);
+            for line in command_lines(&command.cmd) {
+                let result = validator.validate(line);
+                if !result.is_safe() {
+                    problems.push(format!(
+                        "Command '{}' failed security validation: {} ({})",
+                        id,
+                        line,
+                        result.reasons()
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// Apply a template, filling in `{{placeholder}}` values
+    ///
+    /// Values in `values` take precedence. Remaining placeholders are prompted
+    /// for when `interactive` is set, otherwise they are reported as an error.
+    pub fn apply(
+        &self,
+        name: &str,
+        values: &AHashMap<String, String>,
+        interactive: bool,
+    ) -> Result<CommandsConfig> {
+        let template = self.load(name)?;
+
+        let mut values = values.clone();
+        let missing: Vec<String> = find_placeholders(&template)?
+            .into_iter()
+            .filter(|p| !values.contains_key(p))
+            .collect();
+
+        if !missing.is_empty() {
+            if !interactive {
+                anyhow::bail!(
+                    "Missing value for template placeholder(s): {} (use --set KEY=VALUE)",
+                    missing.join(", ")
+                );
+            }
+
+            for placeholder in missing {
+                let value = dialoguer::Input::<String>::with_theme(
+                    &dialoguer::theme::ColorfulTheme::default(),
+                )
+                .with_prompt(&placeholder)
+                .interact_text()?;
+                values.insert(placeholder, value);
+            }
+        }
+
+        Ok(substitute_placeholders(&template, &values)?.to_commands_config())
     }
 
     /// Check if template exists
@@ -243,6 +358,45 @@ impl TemplateManager {
 
         Ok(template)
     }
+
+    /// Read and parse a template file given by the user, without validating it
+    fn parse_file(&self, file_path: &Path) -> Result<UserTemplate> {
+        if !file_path.exists() {
+            anyhow::bail!(
+                "{}: {}",
+                get_message(MessageKey::ErrorFileNotFound, self.language),
+                file_path.display()
+            );
+        }
+
+        let content = fs::read_to_string(file_path)
+            .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+
+        parse_content(&content, &file_path.display().to_string())
+    }
+}
+
+/// Parse template TOML, naming `source` in the error
+fn parse_content(content: &str, source: &str) -> Result<UserTemplate> {
+    toml::from_str(content).with_context(|| format!("Failed to parse template from {}", source))
+}
+
+/// All command lines in a spec, including every platform variant
+fn command_lines(spec: &CommandSpec) -> Vec<&str> {
+    match spec {
+        CommandSpec::Single(cmd) => vec![cmd.as_str()],
+        CommandSpec::Multiple(cmds) => cmds.iter().map(String::as_str).collect(),
+        CommandSpec::Platform(platform_cmds) => [
+            &platform_cmds.unix,
+            &platform_cmds.linux,
+            &platform_cmds.macos,
+            &platform_cmds.windows,
+        ]
+        .into_iter()
+        .filter_map(|cmd| cmd.as_deref())
+        .collect(),
+        CommandSpec::Script { file } => vec![file.as_str()],
+    }
 }
 
 impl Default for TemplateManager {
@@ -251,6 +405,62 @@ impl Default for TemplateManager {
     }
 }
 
+/// Collect placeholder names used anywhere in a template (sorted, unique)
+pub fn find_placeholders(template: &UserTemplate) -> Result<Vec<String>> {
+    fn walk(value: &toml::Value, found: &mut Vec<String>) {
+        match value {
+            toml::Value::String(s) => {
+                for caps in PLACEHOLDER_PATTERN.captures_iter(s) {
+                    found.push(caps[1].to_string());
+                }
+            }
+            toml::Value::Array(items) => items.iter().for_each(|v| walk(v, found)),
+            toml::Value::Table(table) => table.values().for_each(|v| walk(v, found)),
+            _ => {}
+        }
+    }
+
+    let value = toml::Value::try_from(template).context("Failed to serialize template")?;
+    let mut found = Vec::new();
+    walk(&value, &mut found);
+    found.sort();
+    found.dedup();
+
+    Ok(found)
+}
+
+/// Replace `{{placeholder}}` occurrences in every string of a template
+///
+/// Placeholders without a value in `values` are left untouched.
+pub fn substitute_placeholders(
+    template: &UserTemplate,
+    values: &AHashMap<String, String>,
+) -> Result<UserTemplate> {
+    fn walk(value: &mut toml::Value, values: &AHashMap<String, String>) {
+        match value {
+            toml::Value::String(s) => {
+                let replaced = PLACEHOLDER_PATTERN.replace_all(s, |caps: &regex::Captures| {
+                    values
+                        .get(&caps[1])
+                        .cloned()
+                        .unwrap_or_else(|| caps[0].to_string())
+                });
+                *s = replaced.into_owned();
+            }
+            toml::Value::Array(items) => items.iter_mut().for_each(|v| walk(v, values)),
+            toml::Value::Table(table) => table.iter_mut().for_each(|(_, v)| walk(v, values)),
+            _ => {}
+        }
+    }
+
+    let mut value = toml::Value::try_from(template).context("Failed to serialize template")?;
+    walk(&mut value, values);
+
+    value
+        .try_into()
+        .context("Failed to rebuild template after placeholder substitution")
+}
+
 /// Template information
 #[derive(Debug, Clone)]
 pub struct TemplateInfo {
@@ -285,6 +495,8 @@ mod tests {
                 description: "Test command".to_string(),
                 cmd: CommandSpec::Single("echo test".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: Vec::new(),
                 platform: Vec::new(),
@@ -292,8 +504,16 @@ mod tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             },
         );
 
@@ -440,6 +660,187 @@ mod tests {
         assert!(manager.exists("importable"));
     }
 
+    fn write_template_file(temp_dir: &TempDir, content: &str) -> PathBuf {
+        // Keep the file outside the template directory
+        let dir = temp_dir.path().join("downloads");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("shared.toml");
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_check_file_valid_template() {
+        let (manager, temp_dir) = create_test_manager();
+        let path = write_template_file(
+            &temp_dir,
+            r#"
+[template]
+name = "shared"
+description = "Shared template"
+
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#,
+        );
+
+        let (template, problems) = manager.check_file(&path).unwrap();
+        assert_eq!(template.template.name, "shared");
+        assert!(problems.is_empty(), "unexpected problems: {:?}", problems);
+        // Checking must not import the template
+        assert!(!manager.exists("shared"));
+    }
+
+    #[test]
+    fn test_check_file_reports_all_problems() {
+        let (manager, temp_dir) = create_test_manager();
+        let path = write_template_file(
+            &temp_dir,
+            r#"
+[template]
+name = ""
+description = " "
+
+[commands."bad id"]
+description = "Invalid id"
+cmd = "echo ok"
+
+[commands.wipe]
+description = "Dangerous"
+cmd = "rm -rf /; echo done"
+"#,
+        );
+
+        let (_, problems) = manager.check_file(&path).unwrap();
+        assert_eq!(problems.len(), 4, "problems: {:?}", problems);
+        assert_eq!(problems[0], "Template name cannot be empty");
+        assert_eq!(problems[1], "Template description cannot be empty");
+        assert!(problems[2].contains("'bad id'"));
+        assert!(problems[3].starts_with("Command 'wipe' failed security validation"));
+    }
+
+    #[test]
+    fn test_check_file_no_commands() {
+        let (manager, temp_dir) = create_test_manager();
+        let path = write_template_file(
+            &temp_dir,
+            r#"
+[template]
+name = "empty"
+description = "No commands"
+"#,
+        );
+
+        let (_, problems) = manager.check_file(&path).unwrap();
+        assert_eq!(
+            problems,
+            vec!["Template must contain at least one command".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_check_file_reports_relaxed_security_settings() {
+        let (manager, temp_dir) = create_test_manager();
+        let path = write_template_file(
+            &temp_dir,
+            r#"
+[template]
+name = "chained"
+description = "Chained commands"
+
+[config]
+allow_command_chaining = true
+allow_subshells = true
+strict_mode = false
+command_allowlist = ["npm"]
+
+[commands.setup]
+description = "Setup"
+cmd = "npm install && npm run build"
+allow_chaining = true
+
+[commands.sub]
+description = "Subshell"
+cmd = "echo $(whoami)"
+"#,
+        );
+
+        let (_, problems) = manager.check_file(&path).unwrap();
+        assert_eq!(
+            problems[..5],
+            [
+                "Template [config] enables allow_command_chaining",
+                "Template [config] enables allow_subshells",
+                "Template [config] disables strict_mode",
+                "Template [config] sets command_allowlist",
+                "Command 'setup' enables allow_chaining",
+            ]
+        );
+        // The template's [config] does not relax validation of its commands
+        assert_eq!(problems.len(), 6, "problems: {:?}", problems);
+        assert!(problems[5].starts_with("Command 'sub' failed security validation"));
+    }
+
+    #[test]
+    fn test_check_file_parse_error() {
+        let (manager, temp_dir) = create_test_manager();
+        let path = write_template_file(&temp_dir, "not = [valid");
+        assert!(manager.check_file(&path).is_err());
+
+        let missing = temp_dir.path().join("missing.toml");
+        assert!(manager.check_file(&missing).is_err());
+    }
+
+    #[test]
+    fn test_check_content_and_install() {
+        let (manager, _temp_dir) = create_test_manager();
+        let (template, problems) = manager
+            .check_content(
+                r#"
+[template]
+name = "remote"
+description = "Remote template"
+
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#,
+                "https://example.com/remote.toml",
+            )
+            .unwrap();
+        assert!(problems.is_empty(), "unexpected problems: {:?}", problems);
+
+        manager.install(&template).unwrap();
+        assert!(manager.exists("remote"));
+
+        let err = manager
+            .check_content("<html></html>", "https://example.com/remote.toml")
+            .unwrap_err();
+        assert!(err.to_string().contains("https://example.com/remote.toml"));
+    }
+
+    #[test]
+    fn test_import_rejects_dangerous_command() {
+        let (manager, temp_dir) = create_test_manager();
+        let path = write_template_file(
+            &temp_dir,
+            r#"
+[template]
+name = "dangerous"
+description = "Dangerous template"
+
+[commands.wipe]
+description = "Wipe"
+cmd = "rm -rf /; echo done"
+"#,
+        );
+
+        let err = manager.import(&path).unwrap_err();
+        assert!(err.to_string().contains("failed security validation"));
+        assert!(!manager.exists("dangerous"));
+    }
+
     #[test]
     fn test_exists() {
         let (manager, _temp_dir) = create_test_manager();
@@ -462,4 +863,68 @@ mod tests {
         assert!(manager.exists("python-data"));
         assert!(manager.exists("react-app"));
     }
+
+    fn create_placeholder_template(name: &str) -> UserTemplate {
+        let mut template = create_test_template(name);
+        let command = template.commands.get_mut("test").unwrap();
+        command.description = "Build {{project_name}}".to_string();
+        command.cmd = CommandSpec::Single("cargo build -p {{ project_name }}".to_string());
+        command
+            .env
+            .insert("APP_NAME".to_string(), "{{project_name}}".to_string());
+        template
+    }
+
+    #[test]
+    fn test_find_placeholders() {
+        let template = create_placeholder_template("placeholders");
+        let placeholders = find_placeholders(&template).unwrap();
+        assert_eq!(placeholders, vec!["project_name".to_string()]);
+
+        let plain = create_test_template("plain");
+        assert!(find_placeholders(&plain).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_apply_with_set_values() {
+        let (manager, _temp_dir) = create_test_manager();
+        manager
+            .save(&create_placeholder_template("placeholders"))
+            .unwrap();
+
+        let mut values = AHashMap::new();
+        values.insert("project_name".to_string(), "foo".to_string());
+
+        let config = manager.apply("placeholders", &values, false).unwrap();
+        let command = config.commands.get("test").unwrap();
+        assert_eq!(command.description, "Build foo");
+        match &command.cmd {
+            CommandSpec::Single(cmd) => assert_eq!(cmd, "cargo build -p foo"),
+            other => panic!("unexpected command spec: {:?}", other),
+        }
+        assert_eq!(command.env.get("APP_NAME"), Some(&"foo".to_string()));
+    }
+
+    #[test]
+    fn test_apply_missing_placeholder_non_interactive() {
+        let (manager, _temp_dir) = create_test_manager();
+        manager
+            .save(&create_placeholder_template("placeholders"))
+            .unwrap();
+
+        let err = manager
+            .apply("placeholders", &AHashMap::new(), false)
+            .unwrap_err();
+        assert!(err.to_string().contains("project_name"));
+    }
+
+    #[test]
+    fn test_substitute_leaves_unknown_placeholders() {
+        let template = create_placeholder_template("placeholders");
+        let result = substitute_placeholders(&template, &AHashMap::new()).unwrap();
+        assert_eq!(
+            result.commands.get("test").unwrap().description,
+            "Build {{project_name}}"
+        );
+    }
 }
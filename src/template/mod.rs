@@ -4,8 +4,10 @@
 
 pub mod builtin;
 pub mod manager;
+pub mod remote;
 pub mod schema;
 
 pub use builtin::BuiltinTemplate;
 pub use manager::TemplateManager;
+pub use remote::RemoteSource;
 pub use schema::{TemplateMetadata, UserTemplate};
@@ -0,0 +1,379 @@
+//! Fetching templates from remote sources
+//!
+//! Templates can be downloaded over https or read from a git repository.
+//! Both sources require the `template-download` feature. Downloads are
+//! limited in size and time, and only UTF-8 text is accepted; the content is
+//! parsed and validated by [`TemplateManager`](crate::template::TemplateManager)
+//! before anything is installed.
+
+use anyhow::Result;
+use std::fmt;
+use std::time::Duration;
+
+/// Maximum accepted size of a remote template file (1 MiB)
+pub const MAX_TEMPLATE_SIZE: u64 = 1024 * 1024;
+
+/// Time limit for downloading or cloning a remote template
+pub const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where a remote template is fetched from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSource {
+    /// A template file served over https
+    Url(String),
+    /// A template file inside a git repository
+    Git {
+        /// Repository to clone
+        repo: String,
+        /// Path of the template file relative to the repository root
+        path: String,
+    },
+}
+
+impl RemoteSource {
+    /// Fetch the template file content
+    pub fn fetch(&self) -> Result<String> {
+        match self {
+            RemoteSource::Url(url) => fetch_url(url),
+            RemoteSource::Git { repo, path } => fetch_git(repo, path),
+        }
+    }
+}
+
+impl fmt::Display for RemoteSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteSource::Url(url) => f.write_str(url),
+            RemoteSource::Git { repo, path } => write!(f, "{} ({})", repo, path),
+        }
+    }
+}
+
+/// Download a template file over https
+#[cfg(feature = "template-download")]
+pub fn fetch_url(url: &str) -> Result<String> {
+    fetch_url_with_timeout(url, DOWNLOAD_TIMEOUT)
+}
+
+#[cfg(not(feature = "template-download"))]
+pub fn fetch_url(url: &str) -> Result<String> {
+    anyhow::bail!(
+        "Importing templates from a URL requires the `template-download` feature: {}",
+        url
+    )
+}
+
+#[cfg(feature = "template-download")]
+fn fetch_url_with_timeout(url: &str, timeout: Duration) -> Result<String> {
+    use std::io::Read;
+
+    check_url(url)?;
+
+    let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+    let response = agent
+        .get(url)
+        .call()
+        .map_err(|e| anyhow::anyhow!("Failed to download template: {}", e))?;
+
+    let content_type = response.content_type().to_ascii_lowercase();
+    if content_type.contains("html") || content_type.starts_with("image/") {
+        anyhow::bail!(
+            "Not a template file (server returned {}): {}",
+            content_type,
+            url
+        );
+    }
+
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_TEMPLATE_SIZE + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to download template: {}", e))?;
+
+    into_template_text(bytes, url)
+}
+
+/// Read a template file from a git repository
+///
+/// The repository is shallow-cloned into a temporary directory which is
+/// removed afterwards.
+#[cfg(feature = "template-download")]
+pub fn fetch_git(repo: &str, path: &str) -> Result<String> {
+    fetch_git_with_timeout(repo, path, DOWNLOAD_TIMEOUT)
+}
+
+#[cfg(not(feature = "template-download"))]
+pub fn fetch_git(repo: &str, _path: &str) -> Result<String> {
+    anyhow::bail!(
+        "Importing templates from git requires the `template-download` feature: {}",
+        repo
+    )
+}
+
+#[cfg(feature = "template-download")]
+fn fetch_git_with_timeout(repo: &str, path: &str, timeout: Duration) -> Result<String> {
+    use std::path::Component;
+    use std::process::{Command, Stdio};
+
+    let relative = std::path::Path::new(path);
+    if path.is_empty()
+        || relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        anyhow::bail!(
+            "Template path must be relative to the repository root: {}",
+            path
+        );
+    }
+
+    // git runs from the temp directory, since the process cwd may no longer exist;
+    // a relative local repository path is anchored to the cwd first
+    let source = match std::env::current_dir() {
+        Ok(cwd) if std::path::Path::new(repo).is_relative() && cwd.join(repo).exists() => {
+            cwd.join(repo).into_os_string()
+        }
+        _ => repo.into(),
+    };
+
+    let checkout = TempCheckout::new()?;
+    let mut child = Command::new("git")
+        .args(["clone", "--depth", "1", "--quiet", "--"])
+        .arg(source)
+        .arg(&checkout.0)
+        .current_dir(std::env::temp_dir())
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_ALLOW_PROTOCOL", "https:ssh:git:file")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run git: {}", e))?;
+
+    let deadline = std::time::Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if std::time::Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!(
+                "Timed out after {}s cloning {}",
+                timeout.as_secs_f64(),
+                repo
+            );
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    if !status.success() {
+        anyhow::bail!("Failed to clone git repository: {}", repo);
+    }
+
+    let file = checkout.0.join(relative);
+    let metadata = std::fs::symlink_metadata(&file)
+        .map_err(|_| anyhow::anyhow!("Template file not found in {}: {}", repo, path))?;
+    if !metadata.is_file() {
+        anyhow::bail!("Not a template file: {}", path);
+    }
+    if metadata.len() > MAX_TEMPLATE_SIZE {
+        anyhow::bail!(
+            "Template file exceeds the {} byte limit: {}",
+            MAX_TEMPLATE_SIZE,
+            path
+        );
+    }
+
+    into_template_text(std::fs::read(&file)?, path)
+}
+
+/// Reject plain http except for loopback hosts (used for local testing)
+#[cfg(feature = "template-download")]
+fn check_url(url: &str) -> Result<()> {
+    if url.starts_with("https://") {
+        return Ok(());
+    }
+
+    if let Some(rest) = url.strip_prefix("http://") {
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+        let host = match authority.strip_prefix('[') {
+            Some(bracketed) => bracketed.split(']').next().unwrap_or(""),
+            None => authority.split(':').next().unwrap_or(""),
+        };
+        if matches!(host, "localhost" | "127.0.0.1" | "::1") {
+            return Ok(());
+        }
+    }
+
+    anyhow::bail!("Template URLs must use https: {}", url)
+}
+
+/// Enforce the size limit and require UTF-8 text
+#[cfg(feature = "template-download")]
+fn into_template_text(bytes: Vec<u8>, source: &str) -> Result<String> {
+    if bytes.len() as u64 > MAX_TEMPLATE_SIZE {
+        anyhow::bail!(
+            "Template file exceeds the {} byte limit: {}",
+            MAX_TEMPLATE_SIZE,
+            source
+        );
+    }
+    if bytes.contains(&0) {
+        anyhow::bail!("Not a template file (binary content): {}", source);
+    }
+
+    String::from_utf8(bytes)
+        .map_err(|_| anyhow::anyhow!("Not a template file (not UTF-8 text): {}", source))
+}
+
+/// Temporary clone directory, removed on drop
+#[cfg(feature = "template-download")]
+struct TempCheckout(std::path::PathBuf);
+
+#[cfg(feature = "template-download")]
+impl TempCheckout {
+    fn new() -> Result<Self> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let dir =
+            std::env::temp_dir().join(format!("cmdrun-template-{}-{}", std::process::id(), nanos));
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self(dir))
+    }
+}
+
+#[cfg(feature = "template-download")]
+impl Drop for TempCheckout {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(all(test, feature = "template-download"))]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    const TEMPLATE: &str = r#"
+[template]
+name = "remote"
+description = "Remote template"
+
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#;
+
+    /// Serve a single canned HTTP response and return the URL to request
+    fn serve_once(content_type: &'static str, body: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let header = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    content_type,
+                    body.len()
+                );
+                let _ = stream.write_all(header.as_bytes());
+                let _ = stream.write_all(&body);
+            }
+        });
+        format!("http://{}/template.toml", addr)
+    }
+
+    #[test]
+    fn test_fetch_url_success() {
+        let url = serve_once("text/plain", TEMPLATE.as_bytes().to_vec());
+        assert_eq!(fetch_url(&url).unwrap(), TEMPLATE);
+    }
+
+    #[test]
+    fn test_fetch_url_rejects_html() {
+        let url = serve_once("text/html; charset=utf-8", b"<html></html>".to_vec());
+        let err = fetch_url(&url).unwrap_err();
+        assert!(err.to_string().contains("Not a template file"), "{}", err);
+    }
+
+    #[test]
+    fn test_fetch_url_rejects_binary() {
+        let url = serve_once("application/octet-stream", vec![0x7f, b'E', b'L', b'F', 0]);
+        let err = fetch_url(&url).unwrap_err();
+        assert!(err.to_string().contains("binary content"), "{}", err);
+    }
+
+    #[test]
+    fn test_fetch_url_enforces_size_limit() {
+        let body = vec![b'#'; MAX_TEMPLATE_SIZE as usize + 1];
+        let url = serve_once("text/plain", body);
+        let err = fetch_url(&url).unwrap_err();
+        assert!(err.to_string().contains("byte limit"), "{}", err);
+    }
+
+    #[test]
+    fn test_fetch_url_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/template.toml", listener.local_addr().unwrap());
+        // Accept the connection but never answer
+        let handle = std::thread::spawn(move || {
+            let _stream = listener.accept();
+            std::thread::sleep(Duration::from_secs(2));
+        });
+
+        let err = fetch_url_with_timeout(&url, Duration::from_millis(200)).unwrap_err();
+        assert!(err.to_string().contains("Failed to download"), "{}", err);
+        drop(handle);
+    }
+
+    #[test]
+    fn test_check_url_requires_https() {
+        assert!(check_url("https://example.com/t.toml").is_ok());
+        assert!(check_url("http://127.0.0.1:8080/t.toml").is_ok());
+        assert!(check_url("http://[::1]:8080/t.toml").is_ok());
+        assert!(check_url("http://example.com/t.toml").is_err());
+        assert!(check_url("http://localhost.example.com/t.toml").is_err());
+        assert!(check_url("file:///etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_fetch_git_rejects_path_outside_repository() {
+        for path in ["../secret.toml", "/etc/passwd", ""] {
+            let err = fetch_git("https://example.com/repo.git", path).unwrap_err();
+            assert!(err.to_string().contains("relative"), "{}: {}", path, err);
+        }
+    }
+
+    #[test]
+    fn test_fetch_git_local_repository() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        std::fs::create_dir(repo.path().join("templates")).unwrap();
+        std::fs::write(repo.path().join("templates/remote.toml"), TEMPLATE).unwrap();
+        git(&["init", "--quiet"]);
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "init"]);
+
+        // An absolute path works whatever the process cwd is (other tests change it)
+        let repo_url = repo.path().to_str().unwrap();
+        assert_eq!(
+            fetch_git(repo_url, "templates/remote.toml").unwrap(),
+            TEMPLATE
+        );
+        assert!(fetch_git(repo_url, "templates/missing.toml").is_err());
+    }
+}
@@ -57,48 +57,52 @@ impl UserTemplate {
     }
 
     /// Validate template structure with specific language
+    ///
+    /// Fails with the first problem reported by [`Self::problems_with_language`].
     pub fn validate_with_language(&self, language: Language) -> anyhow::Result<()> {
+        match self.problems_with_language(language).into_iter().next() {
+            Some(problem) => anyhow::bail!("{}", problem),
+            None => Ok(()),
+        }
+    }
+
+    /// Collect every structural problem in the template
+    pub fn problems_with_language(&self, language: Language) -> Vec<String> {
+        let mut problems = Vec::new();
+
         // Check name is not empty
         if self.template.name.trim().is_empty() {
-            anyhow::bail!(
-                "{}",
-                get_message(MessageKey::ErrorTemplateNameEmpty, language)
-            );
+            problems.push(get_message(MessageKey::ErrorTemplateNameEmpty, language).to_string());
         }
 
         // Check description is not empty
         if self.template.description.trim().is_empty() {
-            anyhow::bail!(
-                "{}",
-                get_message(MessageKey::ErrorTemplateDescriptionEmpty, language)
-            );
+            problems
+                .push(get_message(MessageKey::ErrorTemplateDescriptionEmpty, language).to_string());
         }
 
         // Check at least one command is defined
         if self.commands.is_empty() {
-            anyhow::bail!(
-                "{}",
-                get_message(MessageKey::ErrorTemplateNoCommands, language)
-            );
+            problems.push(get_message(MessageKey::ErrorTemplateNoCommands, language).to_string());
         }
 
         // Validate command IDs
-        for (id, _) in &self.commands {
+        let mut ids: Vec<&String> = self.commands.keys().collect();
+        ids.sort();
+        for id in ids {
             if id.trim().is_empty() {
-                anyhow::bail!("{}", get_message(MessageKey::ErrorEmptyCommandId, language));
-            }
-
-            // Check for invalid characters in ID
-            if id.contains(|c: char| c.is_whitespace() || c == '/' || c == '\\') {
-                anyhow::bail!(
+                problems.push(get_message(MessageKey::ErrorEmptyCommandId, language).to_string());
+            } else if id.contains(|c: char| c.is_whitespace() || c == '/' || c == '\\') {
+                // Check for invalid characters in ID
+                problems.push(format!(
                     "{}: '{}'",
                     get_message(MessageKey::ErrorCommandIdInvalidChars, language),
                     id
-                );
+                ));
             }
         }
 
-        Ok(())
+        problems
     }
 
     /// Convert template to CommandsConfig
@@ -178,6 +182,29 @@ mod tests {
         assert!(template.validate().is_err());
     }
 
+    #[test]
+    fn test_template_problems_reports_all() {
+        let mut template = create_valid_template();
+        template.template.name = " ".to_string();
+        template.template.description = String::new();
+        template
+            .commands
+            .insert("bad/id".to_string(), create_test_command());
+
+        let problems = template.problems_with_language(Language::English);
+        assert_eq!(
+            problems,
+            vec![
+                "Template name cannot be empty".to_string(),
+                "Template description cannot be empty".to_string(),
+                format!(
+                    "{}: 'bad/id'",
+                    get_message(MessageKey::ErrorCommandIdInvalidChars, Language::English)
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_to_commands_config() {
         let template = create_valid_template();
@@ -228,6 +255,8 @@ mod tests {
             description: "Test command".to_string(),
             cmd: CommandSpec::Single("echo test".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: Vec::new(),
             platform: Vec::new(),
@@ -235,8 +264,16 @@ mod tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         }
     }
 }
@@ -1,5 +1,6 @@
 //! Main file watcher implementation
 
+use ahash::AHashMap;
 use anyhow::{Context, Result};
 use notify::{EventKind, RecursiveMode};
 use notify_debouncer_full::{new_debouncer, DebounceEventResult};
@@ -94,6 +95,40 @@ impl WatchRunner {
         })
     }
 
+    /// Make other commands available to the executor (for `when` conditions)
+    pub fn with_commands(mut self, commands: AHashMap<String, Command>) -> Self {
+        if let ExecutionMode::Cmdrun {
+            command_name,
+            command_def,
+            executor,
+        } = self.execution_mode
+        {
+            self.execution_mode = ExecutionMode::Cmdrun {
+                command_name,
+                command_def,
+                executor: Box::new(executor.with_commands(commands)),
+            };
+        }
+        self
+    }
+
+    /// Set the positional arguments substituted for `{1}`, `{@}` and `{*}`
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        if let ExecutionMode::Cmdrun {
+            command_name,
+            command_def,
+            executor,
+        } = self.execution_mode
+        {
+            self.execution_mode = ExecutionMode::Cmdrun {
+                command_name,
+                command_def,
+                executor: Box::new(executor.with_args(args)),
+            };
+        }
+        self
+    }
+
     /// Start watching and executing commands
     pub async fn run(&mut self) -> Result<()> {
         // Validate watch paths for symlink security
@@ -287,6 +322,8 @@ mod tests {
             description: "Test command".to_string(),
             cmd: CommandSpec::Single("echo test".to_string()),
             env: Default::default(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -294,8 +331,16 @@ mod tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let exec_ctx = ExecutionContext {
@@ -309,6 +354,11 @@ mod tests {
             language: crate::config::Language::English,
             allow_command_chaining: false,
             allow_subshells: false,
+            command_allowlist: None,
+            allow_unsafe: false,
+            merge_stderr: false,
+            kill_grace_ms: crate::command::executor::DEFAULT_KILL_GRACE_MS,
+            max_parallel: None,
         };
 
         let runner = WatchRunner::new_with_cmdrun(
@@ -336,6 +386,8 @@ mod tests {
             description: "Test command".to_string(),
             cmd: CommandSpec::Single("echo test".to_string()),
             env: Default::default(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -343,8 +395,16 @@ mod tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let exec_ctx = ExecutionContext {
@@ -358,6 +418,11 @@ mod tests {
             language: crate::config::Language::English,
             allow_command_chaining: false,
             allow_subshells: false,
+            command_allowlist: None,
+            allow_unsafe: false,
+            merge_stderr: false,
+            kill_grace_ms: crate::command::executor::DEFAULT_KILL_GRACE_MS,
+            max_parallel: None,
         };
 
         let runner = WatchRunner::new_with_cmdrun(
@@ -391,6 +456,8 @@ mod tests {
             description: "Test command".to_string(),
             cmd: CommandSpec::Single("echo test".to_string()),
             env: Default::default(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -398,8 +465,16 @@ mod tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let exec_ctx = ExecutionContext {
@@ -413,6 +488,11 @@ mod tests {
             language: crate::config::Language::English,
             allow_command_chaining: false,
             allow_subshells: false,
+            command_allowlist: None,
+            allow_unsafe: false,
+            merge_stderr: false,
+            kill_grace_ms: crate::command::executor::DEFAULT_KILL_GRACE_MS,
+            max_parallel: None,
         };
 
         let cmdrun_runner = WatchRunner::new_with_cmdrun(
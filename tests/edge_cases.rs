@@ -220,6 +220,8 @@ mod edge_case_tests {
                     description: "Test".to_string(),
                     cmd: CommandSpec::Single("echo test".to_string()),
                     env: AHashMap::new(),
+                    env_file: None,
+                    source_dir: None,
                     working_dir: None,
                     deps: vec![],
                     platform: vec![],
@@ -227,8 +229,16 @@ mod edge_case_tests {
                     timeout: None,
                     parallel: false,
                     confirm: false,
+                    confirm_message: None,
                     allow_chaining: None,
                     allow_subshells: None,
+                    output_pipe: None,
+                    category: None,
+                    watch: None,
+                    when: None,
+                    when_shell: None,
+                    shell: None,
+                    arg_completions: vec![],
                 },
             );
 
@@ -253,6 +263,8 @@ mod edge_case_tests {
                     description: "Deploy".to_string(),
                     cmd: CommandSpec::Single("deploy.sh".to_string()),
                     env: AHashMap::new(),
+                    env_file: None,
+                    source_dir: None,
                     working_dir: None,
                     deps: vec![
                         "build".to_string(),
@@ -265,8 +277,16 @@ mod edge_case_tests {
                     timeout: None,
                     parallel: false,
                     confirm: false,
+                    confirm_message: None,
                     allow_chaining: None,
                     allow_subshells: None,
+                    output_pipe: None,
+                    category: None,
+                    watch: None,
+                    when: None,
+                    when_shell: None,
+                    shell: None,
+                    arg_completions: vec![],
                 },
             );
 
@@ -292,6 +312,8 @@ mod edge_case_tests {
                     description: long_desc.clone(),
                     cmd: CommandSpec::Single("echo test".to_string()),
                     env: AHashMap::new(),
+                    env_file: None,
+                    source_dir: None,
                     working_dir: None,
                     deps: vec![],
                     platform: vec![],
@@ -299,8 +321,16 @@ mod edge_case_tests {
                     timeout: None,
                     parallel: false,
                     confirm: false,
+                    confirm_message: None,
                     allow_chaining: None,
                     allow_subshells: None,
+                    output_pipe: None,
+                    category: None,
+                    watch: None,
+                    when: None,
+                    when_shell: None,
+                    shell: None,
+                    arg_completions: vec![],
                 },
             );
 
@@ -329,6 +359,8 @@ mod edge_case_tests {
                         description: "Test".to_string(),
                         cmd: CommandSpec::Single("echo test".to_string()),
                         env: AHashMap::new(),
+                        env_file: None,
+                        source_dir: None,
                         working_dir: None,
                         deps: vec![],
                         platform: vec![],
@@ -336,8 +368,16 @@ mod edge_case_tests {
                         timeout: None,
                         parallel: false,
                         confirm: false,
+                        confirm_message: None,
                         allow_chaining: None,
                         allow_subshells: None,
+                        output_pipe: None,
+                        category: None,
+                        watch: None,
+                        when: None,
+                        when_shell: None,
+                        shell: None,
+                        arg_completions: vec![],
                     },
                 );
 
@@ -396,6 +436,8 @@ mod edge_case_tests {
                         description: format!("Command {}", i),
                         cmd: CommandSpec::Single(format!("echo {}", i)),
                         env: AHashMap::new(),
+                        env_file: None,
+                        source_dir: None,
                         working_dir: None,
                         deps,
                         platform: vec![],
@@ -403,8 +445,16 @@ mod edge_case_tests {
                         timeout: None,
                         parallel: false,
                         confirm: false,
+                        confirm_message: None,
                         allow_chaining: None,
                         allow_subshells: None,
+                        output_pipe: None,
+                        category: None,
+                        watch: None,
+                        when: None,
+                        when_shell: None,
+                        shell: None,
+                        arg_completions: vec![],
                     },
                 );
             }
@@ -433,6 +483,8 @@ mod edge_case_tests {
                         description: format!("Command {}", i),
                         cmd: CommandSpec::Single(format!("echo {}", i)),
                         env: AHashMap::new(),
+                        env_file: None,
+                        source_dir: None,
                         working_dir: None,
                         deps: vec![],
                         platform: vec![],
@@ -440,8 +492,16 @@ mod edge_case_tests {
                         timeout: None,
                         parallel: false,
                         confirm: false,
+                        confirm_message: None,
                         allow_chaining: None,
                         allow_subshells: None,
+                        output_pipe: None,
+                        category: None,
+                        watch: None,
+                        when: None,
+                        when_shell: None,
+                        shell: None,
+                        arg_completions: vec![],
                     },
                 );
             }
@@ -0,0 +1,28 @@
+
+# Custom completion for 'cmdrun run' and 'cmdrun info' with descriptions
+# Convert "name:description" to "name<TAB>description" (split on the first unescaped colon)
+function __cmdrun_command_list
+    cmdrun completion-list 2>/dev/null | string replace -r '^((?:\\\\.|[^:\\\\])*):' '$1'\t | string replace -a '\\:' ':'
+end
+
+# True while the command name has not been given yet
+function __cmdrun_needs_command_name
+    set -l args (string match -v -- '-*' (commandline -opc))
+    test (count $args) -eq 2
+end
+
+# Choices for the argument being completed, from the command's arg_completions
+function __cmdrun_arg_choices
+    set -l args (string match -v -- '-*' (commandline -opc))
+    test (count $args) -ge 3; or return
+    cmdrun complete-args $args[3] (math (count $args) - 2) 2>/dev/null
+end
+
+complete -c cmdrun -n '__fish_seen_subcommand_from run r; and __cmdrun_needs_command_name' -f -a '(__cmdrun_command_list)'
+complete -c cmdrun -n '__fish_seen_subcommand_from info i; and __cmdrun_needs_command_name' -f -a '(__cmdrun_command_list)'
+complete -c cmdrun -n '__fish_seen_subcommand_from run r; and not __cmdrun_needs_command_name' -a '(__cmdrun_arg_choices)'
+
+# Tags and categories that exist in the config
+complete -c cmdrun -n '__fish_seen_subcommand_from add' -s t -l tags -x -a '(cmdrun completion-tags 2>/dev/null)'
+complete -c cmdrun -n '__fish_seen_subcommand_from add' -s C -l category -x -a '(cmdrun completion-categories 2>/dev/null)'
+complete -c cmdrun -n '__fish_seen_subcommand_from list ls' -l category -x -a '(cmdrun completion-categories 2>/dev/null)'
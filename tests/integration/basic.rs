@@ -3,6 +3,7 @@
 use cmdrun::command::executor::{CommandExecutor, ExecutionContext};
 use cmdrun::config::loader::ConfigLoader;
 use cmdrun::config::schema::{Command, CommandSpec};
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_simple_echo() {
@@ -13,10 +14,20 @@ async fn test_simple_echo() {
         description: "Test echo".to_string(),
         cmd: CommandSpec::Single("echo hello".to_string()),
         env: Default::default(),
+        env_file: None,
+        source_dir: None,
         deps: vec![],
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
         timeout: None,
         platform: vec![],
         working_dir: None,
@@ -38,10 +49,20 @@ async fn test_multiple_commands() {
         description: "Multiple commands".to_string(),
         cmd: CommandSpec::Multiple(vec!["echo first".to_string(), "echo second".to_string()]),
         env: Default::default(),
+        env_file: None,
+        source_dir: None,
         deps: vec![],
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
         timeout: None,
         platform: vec![],
         working_dir: None,
@@ -67,10 +88,20 @@ async fn test_command_with_env() {
             env.insert("TEST_VAR".to_string(), "test_value".to_string());
             env
         },
+        env_file: None,
+        source_dir: None,
         deps: vec![],
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
         timeout: None,
         platform: vec![],
         working_dir: None,
@@ -115,10 +146,20 @@ async fn test_command_exit_code() {
         description: "Failing command".to_string(),
         cmd: CommandSpec::Single("exit 42".to_string()),
         env: Default::default(),
+        env_file: None,
+        source_dir: None,
         deps: vec![],
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
         timeout: None,
         platform: vec![],
         working_dir: None,
@@ -145,7 +186,7 @@ async fn test_command_exit_code() {
 #[tokio::test]
 async fn test_timeout() {
     let ctx = ExecutionContext {
-        timeout: Some(1), // 1 second timeout
+        timeout: Some(Duration::from_secs(1)),
         ..Default::default()
     };
 
@@ -156,11 +197,21 @@ async fn test_timeout() {
         description: "Long running command".to_string(),
         cmd: CommandSpec::Single("sleep 5".to_string()),
         env: Default::default(),
+        env_file: None,
+        source_dir: None,
         deps: vec![],
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
-        timeout: Some(1),
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+        timeout: Some(Duration::from_secs(1)),
         platform: vec![],
         working_dir: None,
         tags: vec![],
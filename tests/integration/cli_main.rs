@@ -13,11 +13,12 @@ use std::process::Command;
 use tempfile::TempDir;
 
 /// Test helper to run cmdrun binary
+///
+/// Uses the binary Cargo built for this test run, so it has the same features.
 fn run_cmdrun(args: &[&str]) -> Result<std::process::Output, std::io::Error> {
-    let mut cmd_args = vec!["run", "--bin", "cmdrun", "--"];
-    cmd_args.extend_from_slice(args);
-
-    Command::new("cargo").args(&cmd_args).output()
+    Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(args)
+        .output()
 }
 
 /// Test helper to check exit code
@@ -97,12 +98,8 @@ fn test_exit_code_on_invalid_subcommand() {
 fn test_exit_code_on_missing_config() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
 
-    let output = Command::new("cargo")
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
         .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
             "--config",
             temp_dir.path().join("nonexistent.toml").to_str().unwrap(),
             "list",
@@ -130,16 +127,8 @@ cmd = "echo test"
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "--config",
-            config_path.to_str().unwrap(),
-            "list",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "list"])
         .output()
         .expect("Failed to run command");
 
@@ -165,12 +154,8 @@ cmd = "echo test"
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
         .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
             "--color",
             "never",
             "--config",
@@ -205,12 +190,8 @@ cmd = "echo test"
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
         .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
             "--color",
             "always",
             "--config",
@@ -242,16 +223,8 @@ fn test_list_with_empty_config() {
     // Create empty commands section
     std::fs::write(&config_path, "[commands]\n").expect("Failed to write config");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "--config",
-            config_path.to_str().unwrap(),
-            "list",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "list"])
         .output()
         .expect("Failed to run command");
 
@@ -280,16 +253,8 @@ cmd = "cargo test"
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "--config",
-            config_path.to_str().unwrap(),
-            "list",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "list"])
         .output()
         .expect("Failed to run command");
 
@@ -322,12 +287,8 @@ cmd = "cargo clean"
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
         .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
             "--config",
             config_path.to_str().unwrap(),
             "list",
@@ -362,20 +323,16 @@ cmd = "cargo build"
 [commands.test]
 description = "Run tests"
 cmd = "cargo test"
+
+[commands."docs:serve"]
+description = "Serve docs: local preview"
+cmd = "mdbook serve"
 "#,
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "--config",
-            config_path.to_str().unwrap(),
-            "completion-list",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "completion-list"])
         .output()
         .expect("Failed to run command");
 
@@ -385,6 +342,58 @@ cmd = "cargo test"
     // Format: "name:description"
     assert!(stdout.contains("build:Build the project"));
     assert!(stdout.contains("test:Run tests"));
+    // Colons in names and descriptions are escaped
+    assert!(stdout.contains("docs\\:serve:Serve docs\\: local preview"));
+}
+
+#[test]
+fn test_completion_tags_and_categories() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.build]
+description = "Build the project"
+cmd = "cargo build"
+tags = ["rust", "ci"]
+category = "build"
+
+[commands.test]
+description = "Run tests"
+cmd = "cargo test"
+tags = ["test", "ci"]
+category = "test"
+
+[commands.lint]
+description = "Lint"
+cmd = "cargo clippy"
+tags = ["rust"]
+category = "build"
+
+[commands.clean]
+description = "Clean"
+cmd = "cargo clean"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let run = |subcommand: &str| {
+        Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+            .args(["--config", config_path.to_str().unwrap(), subcommand])
+            .output()
+            .expect("Failed to run command")
+    };
+
+    // Deduplicated and sorted, one per line
+    let output = run("completion-tags");
+    assert_exit_code(&output, 0);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ci\nrust\ntest\n");
+
+    let output = run("completion-categories");
+    assert_exit_code(&output, 0);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "build\ntest\n");
 }
 
 #[test]
@@ -392,16 +401,8 @@ fn test_completion_list_with_no_config() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let nonexistent = temp_dir.path().join("nonexistent.toml");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "--config",
-            nonexistent.to_str().unwrap(),
-            "completion-list",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", nonexistent.to_str().unwrap(), "completion-list"])
         .output()
         .expect("Failed to run command");
 
@@ -432,12 +433,8 @@ cmd = "echo build"
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
         .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
             "--config",
             config_path.to_str().unwrap(),
             "run",
@@ -475,12 +472,8 @@ cmd = "echo test"
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
         .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
             "--config",
             config_path.to_str().unwrap(),
             "run",
@@ -496,6 +489,28 @@ cmd = "echo test"
     assert!(stderr.contains("build") || stderr.contains("Did you mean"));
 }
 
+#[test]
+fn test_unknown_subcommand_suggests_builtin() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.build]
+cmd = "echo build"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "lsit"])
+        .expect("Failed to run cmdrun");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Did you mean"), "stderr: {}", stderr);
+    assert!(stderr.contains("list"), "stderr: {}", stderr);
+}
+
 // ============================================================================
 // Verbose Flag Tests
 // ============================================================================
@@ -515,17 +530,8 @@ cmd = "echo test"
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "-v",
-            "--config",
-            config_path.to_str().unwrap(),
-            "list",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["-v", "--config", config_path.to_str().unwrap(), "list"])
         .output()
         .expect("Failed to run command");
 
@@ -547,156 +553,3438 @@ cmd = "echo test"
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "-vv",
-            "--config",
-            config_path.to_str().unwrap(),
-            "list",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["-vv", "--config", config_path.to_str().unwrap(), "list"])
         .output()
         .expect("Failed to run command");
 
     assert_exit_code(&output, 0);
 }
 
-// ============================================================================
-// Config Path Tests
-// ============================================================================
-
 #[test]
-fn test_custom_config_path() {
+fn test_run_log_file() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
-    let config_path = temp_dir.path().join("custom.toml");
+    let config_path = temp_dir.path().join("commands.toml");
+    let log_path = temp_dir.path().join("run.log");
 
     std::fs::write(
         &config_path,
         r#"
-[commands.custom]
-description = "Custom command"
-cmd = "echo custom"
+[commands.test]
+description = "Test"
+cmd = "echo test"
 "#,
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "--config",
-            config_path.to_str().unwrap(),
-            "list",
-        ])
-        .output()
-        .expect("Failed to run command");
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--log-file",
+        log_path.to_str().unwrap(),
+        "test",
+    ])
+    .expect("Failed to run command");
 
     assert_exit_code(&output, 0);
+    assert!(log_path.exists(), "Log file should be created");
+
+    // Debug-level lifecycle events go to the file, not the console
+    let log = std::fs::read_to_string(&log_path).expect("Failed to read log file");
+    assert!(log.contains("Running command 'test'"), "log: {}", log);
+    assert!(log.contains("Process started"), "log: {}", log);
+    assert!(log.contains("Process exited with code 0"), "log: {}", log);
+    assert!(log.contains("finished with exit code 0"), "log: {}", log);
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("custom"));
+    assert!(!stdout.contains("Process started"));
 }
 
 #[test]
-fn test_config_flag_with_invalid_path() {
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "--config",
-            "/this/path/definitely/does/not/exist.toml",
-            "list",
-        ])
-        .output()
+fn test_run_reports_missing_env_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.test]
+description = "Test"
+cmd = "echo test"
+env_file = "missing.env"
+
+[commands.other]
+description = "Other"
+cmd = "echo other"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "test"])
         .expect("Failed to run command");
 
-    assert!(!output.status.success());
-}
+    assert_ne!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("env_file not found"), "stderr: {}", stderr);
 
-// ============================================================================
-// Integration Tests (Multiple Flags)
-// ============================================================================
+    // Other commands are not affected by the missing file
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "other"])
+        .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+}
 
 #[test]
-fn test_multiple_flags_combination() {
+fn test_run_confirm_refuses_without_terminal() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let config_path = temp_dir.path().join("commands.toml");
+    let marker = temp_dir.path().join("ran");
 
     std::fs::write(
         &config_path,
-        r#"
-[commands.test]
-description = "Test"
-cmd = "echo test"
+        format!(
+            r#"
+[commands.deploy]
+description = "Deploy"
+cmd = "touch {}"
+confirm = true
+confirm_message = "Deploy to production?"
 "#,
+            marker.display()
+        ),
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "-v",
-            "--color",
-            "never",
-            "--config",
-            config_path.to_str().unwrap(),
-            "list",
-        ])
-        .output()
+    // stdin is not a terminal here, so the run must fail instead of waiting
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "deploy"])
         .expect("Failed to run command");
 
+    assert_ne!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--yes"), "stderr: {}", stderr);
+    assert!(!marker.exists(), "Command should not have run");
+}
+
+#[test]
+fn test_run_confirm_with_yes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    let marker = temp_dir.path().join("ran");
+
+    std::fs::write(
+        &config_path,
+        format!(
+            r#"
+[commands.deploy]
+description = "Deploy"
+cmd = "touch {}"
+confirm = true
+"#,
+            marker.display()
+        ),
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--yes",
+        "deploy",
+    ])
+    .expect("Failed to run command");
+
     assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Skipping confirmation"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(marker.exists(), "Command should have run");
 }
 
 // ============================================================================
-// NO_COLOR Environment Variable Tests
+// Alias Tests
 // ============================================================================
 
+/// Write a config whose `show` command echoes its first three positional args
+fn write_alias_config(temp_dir: &TempDir, aliases: &str) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            r#"
+[commands.show]
+description = "Show args"
+cmd = "echo ${{1:-none}} ${{2:-none}} ${{3:-none}}"
+
+[aliases]
+{}
+"#,
+            aliases
+        ),
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
 #[test]
-fn test_no_color_env_var() {
+fn test_run_alias_forwards_args() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_alias_config(&temp_dir, r#"s = "show""#);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "s",
+        "a",
+        "b",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("a b none"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_run_alias_injects_fixed_flags() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_alias_config(&temp_dir, r#"release = "show -- --release""#);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "release",
+        "x",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--release x none"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_run_alias_substitutes_placeholders() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_alias_config(&temp_dir, r#"one = "show -- --exact {1} --tail""#);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "one",
+        "foo",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--exact foo --tail"), "stdout: {}", stdout);
+
+    let missing = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "one"])
+        .expect("Failed to run command");
+
+    assert_ne!(missing.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&missing.stderr);
+    assert!(
+        stderr.contains("Alias 'one' expects at least 1 argument(s), got 0"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_alias_target_must_exist() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_alias_config(&temp_dir, r#"broken = "missing -- --flag""#);
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "show"])
+        .expect("Failed to run command");
+
+    assert_ne!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Alias target not found: broken -> missing"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_run_quiet_prints_only_command_output() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let config_path = temp_dir.path().join("commands.toml");
 
     std::fs::write(
         &config_path,
         r#"
-[commands.test]
-description = "Test"
-cmd = "echo test"
+[commands.prepare]
+cmd = "echo prepared"
+
+[commands.version]
+cmd = "echo 1.2.3"
+deps = ["prepare"]
 "#,
     )
     .expect("Failed to write config");
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "--config",
-            config_path.to_str().unwrap(),
-            "list",
-        ])
-        .env("NO_COLOR", "1") // Set NO_COLOR environment variable
-        .output()
-        .expect("Failed to run command");
+    for parallel in [false, true] {
+        let mut args = vec!["-q", "--config", config_path.to_str().unwrap(), "run"];
+        if parallel {
+            args.push("--parallel");
+        }
+        args.push("version");
 
-    assert_exit_code(&output, 0);
+        let output = run_cmdrun(&args).expect("Failed to run command");
 
-    // Should not contain colors when NO_COLOR is set
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(
-        !stdout.contains("\x1b["),
-        "Output should not contain ANSI codes when NO_COLOR is set"
+        assert_exit_code(&output, 0);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        // Dependencies are only resolved with --parallel
+        let expected = if parallel {
+            "prepared\n1.2.3\n"
+        } else {
+            "1.2.3\n"
+        };
+        assert_eq!(stdout, expected, "parallel: {}", parallel);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!stderr.contains("→"), "stderr: {}", stderr);
+    }
+}
+
+#[test]
+fn test_run_quiet_hides_parallel_banner() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.lint]
+cmd = "echo linted"
+
+[commands.test]
+cmd = "echo tested"
+
+[commands.check]
+cmd = "echo checked"
+deps = ["lint", "test"]
+"#,
+    )
+    .expect("Failed to write config");
+
+    let config = config_path.to_str().unwrap();
+    let output = run_cmdrun(&["--config", config, "run", "--parallel", "check"])
+        .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("2 commands in parallel"),
+        "stderr: {}",
+        stderr
+    );
+
+    let output = run_cmdrun(&["-q", "--config", config, "run", "--parallel", "check"])
+        .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("in parallel"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_run_quiet_still_reports_errors() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(&config_path, "[commands.fail]\ncmd = \"exit 3\"\n")
+        .expect("Failed to write config");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "-q",
+        "fail",
+    ])
+    .expect("Failed to run command");
+
+    assert_ne!(output.status.code(), Some(0));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Running:"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Error:"), "stderr: {}", stderr);
+}
+
+// ============================================================================
+// Env Diff Tests
+// ============================================================================
+
+#[test]
+fn test_env_diff_uses_config_redact_patterns() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        "[config]\nredact_patterns = [\"INTERNAL_*\"]\n\n[commands.noop]\ncmd = \"true\"\n",
+    )
+    .expect("Failed to write config");
+
+    let cmdrun = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+            .current_dir(temp_dir.path())
+            .args(args)
+            .output()
+            .expect("Failed to run command")
+    };
+    assert_exit_code(&cmdrun(&["env", "create", "dev"]), 0);
+    assert_exit_code(
+        &cmdrun(&["env", "set", "INTERNAL_URL", "hidden-value", "--env", "dev"]),
+        0,
+    );
+
+    let output = cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "env",
+        "diff",
+        "default",
+        "dev",
+    ]);
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+ INTERNAL_URL="), "stdout: {}", stdout);
+    assert!(!stdout.contains("hidden-value"), "stdout: {}", stdout);
+}
+
+// ============================================================================
+// Exec Tests
+// ============================================================================
+
+#[test]
+fn test_exec_uses_global_env() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[config.env]
+EXEC_GREETING = "hello-from-config"
+
+[commands.noop]
+cmd = "true"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "exec",
+        "--",
+        "echo",
+        "${EXEC_GREETING}",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("hello-from-config"), "stdout: {}", stdout);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_preserves_argument_quoting() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(&config_path, "[commands.noop]\ncmd = \"true\"\n")
+        .expect("Failed to write config");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "exec",
+        "--",
+        "printf",
+        "%s,",
+        "foo bar",
+        "baz",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("foo bar,baz,"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_exec_blocks_dangerous_command() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    let marker = temp_dir.path().join("ran");
+
+    std::fs::write(&config_path, "[commands.noop]\ncmd = \"true\"\n")
+        .expect("Failed to write config");
+
+    let chained = format!("echo ok; touch {}", marker.display());
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "exec",
+        "--",
+        &chained,
+    ])
+    .expect("Failed to run command");
+
+    assert_ne!(output.status.code(), Some(0));
+    assert!(!marker.exists(), "Chained command should have been blocked");
+}
+
+// ============================================================================
+// Bench Tests
+// ============================================================================
+
+#[test]
+fn test_bench_runs_warmup_and_measured_runs() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    let runs_dir = temp_dir.path().join("runs");
+    std::fs::create_dir(&runs_dir).expect("Failed to create runs dir");
+
+    // Every run leaves one new file behind
+    std::fs::write(
+        &config_path,
+        format!(
+            "[commands.tick]\ncmd = \"mktemp {}/run.XXXXXX\"\n",
+            runs_dir.display()
+        ),
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "bench",
+        "tick",
+        "--runs",
+        "3",
+        "--warmup",
+        "1",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Runs:"), "stdout: {}", stdout);
+
+    let runs = std::fs::read_dir(&runs_dir)
+        .expect("Failed to read runs dir")
+        .count();
+    assert_eq!(runs, 4, "3 measured runs plus 1 warmup");
+}
+
+// ============================================================================
+// Search Tests
+// ============================================================================
+
+#[test]
+fn test_search_excluding_keyword_without_separator() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.up]
+description = "Start services"
+cmd = "docker compose up"
+
+[commands.image]
+description = "Build image"
+cmd = "docker build ."
+"#,
+    )
+    .expect("Failed to write config");
+    let config = config_path.to_str().unwrap();
+
+    for args in [
+        vec!["--config", config, "search", "docker", "-compose"],
+        vec![
+            "--config", config, "search", "--in", "cmd", "docker", "-compose",
+        ],
+    ] {
+        let output = run_cmdrun(&args).expect("Failed to run command");
+        assert_exit_code(&output, 0);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("image"), "stdout: {}", stdout);
+        assert!(!stdout.contains("up -"), "stdout: {}", stdout);
+    }
+
+    // Options after the keywords are reported instead of searched for
+    let output = run_cmdrun(&["--config", config, "search", "docker", "--any"])
+        .expect("Failed to run command");
+    assert_ne!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--any"), "stderr: {}", stderr);
+}
+
+// ============================================================================
+// Which Tests
+// ============================================================================
+
+#[test]
+fn test_which_reports_local_definition() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"[commands.lint]
+cmd = "cargo clippy"
+
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "which", "build"])
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(&format!("{}:4 [commands.build]", config_path.display())),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("cargo build"), "stdout: {}", stdout);
+
+    let missing = run_cmdrun(&["--config", config_path.to_str().unwrap(), "which", "buidl"])
+        .expect("Failed to run command");
+    assert_exit_code(&missing, 3);
+    let stderr = String::from_utf8_lossy(&missing.stderr);
+    assert!(
+        stderr.contains("did you mean: build?"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+// ============================================================================
+// Validate Tests
+// ============================================================================
+
+#[test]
+fn test_validate_graph_only_reports_cycle() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands]
+a = { description = "A", cmd = "echo a", deps = ["b"] }
+b = { description = "B", cmd = "echo b", deps = ["a"] }
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&[
+        "validate",
+        "--path",
+        config_path.to_str().unwrap(),
+        "--graph-only",
+    ])
+    .expect("Failed to run command");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Circular dependency detected"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("a → b → a") || stdout.contains("b → a → b"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_validate_graph_only_reports_missing_dependency() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands]
+build = { description = "Build", cmd = "cargo build", deps = ["missing"] }
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&[
+        "validate",
+        "--path",
+        config_path.to_str().unwrap(),
+        "--graph-only",
+    ])
+    .expect("Failed to run command");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Command not found: missing (referenced by build)"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_validate_graph_only_skips_content_validation() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    // The empty command fails full validation but the graph itself is valid
+    std::fs::write(
+        &config_path,
+        r#"
+[aliases]
+t = "test"
+
+[commands]
+test = { description = "Test", cmd = "" }
+build = { description = "Build", cmd = "cargo build", deps = ["t"] }
+"#,
+    )
+    .expect("Failed to write config");
+
+    let full = run_cmdrun(&["validate", "--path", config_path.to_str().unwrap()])
+        .expect("Failed to run command");
+    assert!(!full.status.success());
+
+    let start = std::time::Instant::now();
+    let output = run_cmdrun(&[
+        "validate",
+        "--path",
+        config_path.to_str().unwrap(),
+        "--graph-only",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    assert!(start.elapsed() < std::time::Duration::from_secs(30));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("valid"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_validate_strict_schema_reports_unknown_key() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+timout = 30
+"#,
+    )
+    .expect("Failed to write config");
+
+    // Unknown keys are ignored without --strict-schema
+    let lenient = run_cmdrun(&["validate", "--path", config_path.to_str().unwrap()])
+        .expect("Failed to run command");
+    assert_exit_code(&lenient, 0);
+
+    let output = run_cmdrun(&[
+        "validate",
+        "--path",
+        config_path.to_str().unwrap(),
+        "--strict-schema",
+    ])
+    .expect("Failed to run command");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Unknown key 'timout' in [commands.build] (did you mean 'timeout'?)"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_validate_reports_duplicate_commands_across_environment_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let project = temp_dir.path();
+    let config_path = project.join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#,
+    )
+    .expect("Failed to write config");
+    std::fs::write(
+        project.join("commands.dev.toml"),
+        r#"
+[commands.build]
+description = "Build (dev)"
+cmd = "cargo build --profile dev"
+"#,
+    )
+    .expect("Failed to write environment config");
+    std::fs::create_dir_all(project.join(".cmdrun")).expect("Failed to create .cmdrun");
+    std::fs::write(
+        project.join(".cmdrun/config.toml"),
+        r#"
+[environment]
+current = "dev"
+
+[environment.environments.dev]
+description = "Development"
+"#,
+    )
+    .expect("Failed to write environment settings");
+
+    let run_validate = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+            .args(["validate", "--path", config_path.to_str().unwrap()])
+            .args(extra)
+            .current_dir(project)
+            .output()
+            .expect("Failed to run command")
+    };
+
+    // Non-fatal warning by default
+    let output = run_validate(&[]);
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let warning = stdout
+        .lines()
+        .find(|line| line.contains("Command 'build' is defined in multiple files"))
+        .unwrap_or_else(|| panic!("missing duplicate warning: {}", stdout));
+    assert!(warning.contains("commands.toml, "), "warning: {}", warning);
+    assert!(
+        warning.contains("commands.dev.toml (using ") && warning.ends_with("commands.dev.toml)"),
+        "warning: {}",
+        warning
+    );
+
+    // Error with --strict
+    let strict = run_validate(&["--strict"]);
+    assert!(!strict.status.success());
+}
+
+#[test]
+fn test_validate_fail_on_warning() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let home = temp_dir.path().join("home");
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir_all(home.join(".config/cmdrun")).expect("Failed to create home");
+    std::fs::create_dir_all(&project).expect("Failed to create project");
+
+    // The local `build` shadows the global one
+    std::fs::write(
+        home.join(".config/cmdrun/commands.toml"),
+        r#"
+[commands.build]
+description = "Build (global)"
+cmd = "make"
+"#,
+    )
+    .expect("Failed to write global config");
+    std::fs::write(
+        project.join("commands.toml"),
+        r#"
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#,
+    )
+    .expect("Failed to write local config");
+
+    let run_validate = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+            .arg("validate")
+            .args(extra)
+            .current_dir(&project)
+            .env("HOME", &home)
+            .env_remove("XDG_CONFIG_HOME")
+            .output()
+            .expect("Failed to run command")
+    };
+
+    // Warnings are non-fatal by default
+    let output = run_validate(&[]);
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Command 'build' is defined in multiple files"),
+        "stdout: {}",
+        stdout
+    );
+
+    let output = run_validate(&["--fail-on-warning"]);
+    assert_exit_code(&output, 1);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1 warning(s)"), "stdout: {}", stdout);
+}
+
+// ============================================================================
+// Config Path Tests
+// ============================================================================
+
+#[test]
+fn test_custom_config_path() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("custom.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.custom]
+description = "Custom command"
+cmd = "echo custom"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "list"])
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("custom"));
+}
+
+#[test]
+fn test_config_flag_with_invalid_path() {
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args([
+            "--config",
+            "/this/path/definitely/does/not/exist.toml",
+            "list",
+        ])
+        .output()
+        .expect("Failed to run command");
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_repeated_config_layers_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base_path = temp_dir.path().join("base.toml");
+    let local_path = temp_dir.path().join("local.toml");
+    std::fs::write(
+        &base_path,
+        r#"
+[config]
+shell = "bash"
+
+[config.env]
+GREETING = "hello"
+TARGET = "base"
+
+[commands.greet]
+description = "Shared greeting"
+cmd = "echo shared $GREETING $TARGET"
+
+[commands.shared-only]
+description = "Only in base"
+cmd = "echo base"
+"#,
+    )
+    .expect("Failed to write config");
+    std::fs::write(
+        &local_path,
+        r#"
+[config.env]
+TARGET = "local"
+
+[commands.greet]
+description = "Personal greeting"
+cmd = "echo personal $GREETING $TARGET"
+"#,
+    )
+    .expect("Failed to write config");
+    let base = base_path.to_str().unwrap();
+    let local = local_path.to_str().unwrap();
+
+    let output =
+        run_cmdrun(&["--config", base, "--config", local, "list"]).expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Personal greeting"), "{}", stdout);
+    assert!(stdout.contains("shared-only"), "{}", stdout);
+    assert!(!stdout.contains("Shared greeting"), "{}", stdout);
+
+    // Later file wins for the command, env is merged key by key
+    let output = run_cmdrun(&["--config", base, "-c", local, "-q", "run", "greet"])
+        .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "personal hello local"
+    );
+
+    // Reversing the order reverses the precedence
+    let output = run_cmdrun(&["-c", local, "-c", base, "-q", "run", "greet"])
+        .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "shared hello base"
+    );
+
+    let output =
+        run_cmdrun(&["-c", base, "-c", local, "which", "greet"]).expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("local.toml"), "{}", stdout);
+    assert!(stdout.contains("Overrides"), "{}", stdout);
+    assert!(stdout.contains("base.toml"), "{}", stdout);
+}
+
+// ============================================================================
+// Integration Tests (Multiple Flags)
+// ============================================================================
+
+#[test]
+fn test_multiple_flags_combination() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.test]
+description = "Test"
+cmd = "echo test"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args([
+            "-v",
+            "--color",
+            "never",
+            "--config",
+            config_path.to_str().unwrap(),
+            "list",
+        ])
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+}
+
+// ============================================================================
+// NO_COLOR Environment Variable Tests
+// ============================================================================
+
+#[test]
+fn test_no_color_env_var() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.test]
+description = "Test"
+cmd = "echo test"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "list"])
+        .env("NO_COLOR", "1") // Set NO_COLOR environment variable
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+
+    // Should not contain colors when NO_COLOR is set
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        !stdout.contains("\x1b["),
+        "Output should not contain ANSI codes when NO_COLOR is set"
+    );
+}
+
+// ============================================================================
+// Security Flag Tests
+// ============================================================================
+
+#[test]
+fn test_allow_unsafe_flag() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.chained]
+description = "Chained"
+cmd = "echo first; echo second"
+"#,
+    )
+    .expect("Failed to write config");
+
+    // Blocked by validation without the flag
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "chained"])
+        .expect("Failed to run command");
+    assert!(!output.status.success());
+
+    // Runs with a warning under --allow-unsafe
+    let output = run_cmdrun(&[
+        "--allow-unsafe",
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "chained",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.contains("second"));
+    assert!(stderr.contains("--allow-unsafe"));
+}
+
+#[test]
+fn test_allow_unsafe_keeps_command_allowlist() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[config]
+command_allowlist = ["echo"]
+
+[commands.chained]
+description = "Chained"
+cmd = "echo first; echo second"
+
+[commands.other]
+description = "Other"
+cmd = "echo first; printf second"
+"#,
+    )
+    .expect("Failed to write config");
+
+    // Only the injection checks are skipped
+    let output = run_cmdrun(&[
+        "--allow-unsafe",
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "chained",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("command_allowlist still applies"),
+        "stderr: {}",
+        stderr
+    );
+
+    let output = run_cmdrun(&[
+        "--allow-unsafe",
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "other",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 4);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("first"), "stdout: {}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("allowlist"), "stderr: {}", stderr);
+}
+
+// ============================================================================
+// Hook Tests
+// ============================================================================
+
+/// Write a config with global hooks around a passing and a failing command
+fn write_hooks_config(temp_dir: &TempDir, extra_config: &str) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            r#"
+[config]
+{}
+
+[commands.ok]
+description = "Succeeds"
+cmd = "echo main-ran"
+
+[commands.fail]
+description = "Fails"
+cmd = "false"
+
+[hooks]
+before_all = ["echo before-all-ran"]
+before_each = ["echo before-each:${{CMDRUN_COMMAND}}"]
+after_each = ["echo after-each:${{CMDRUN_COMMAND}}"]
+after_all = ["echo after-all-ran"]
+"#,
+            extra_config
+        ),
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
+#[test]
+fn test_run_hooks_order() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_hooks_config(&temp_dir, "");
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "ok"])
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let positions: Vec<usize> = [
+        "before-all-ran",
+        "before-each:ok",
+        "main-ran",
+        "after-each:ok",
+        "after-all-ran",
+    ]
+    .iter()
+    .map(|marker| {
+        stdout
+            .rfind(marker)
+            .unwrap_or_else(|| panic!("missing {}: {}", marker, stdout))
+    })
+    .collect();
+    assert!(
+        positions.windows(2).all(|w| w[0] < w[1]),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_run_after_hooks_run_on_failure() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_hooks_config(&temp_dir, "");
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "fail"])
+        .expect("Failed to run command");
+
+    assert_ne!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("after-each:fail"), "stdout: {}", stdout);
+    assert!(stdout.contains("after-all-ran"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_run_hooks_disabled() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_hooks_config(&temp_dir, "hooks_enabled = false");
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "ok"])
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("main-ran"));
+    assert!(!stdout.contains("before-all-ran"), "stdout: {}", stdout);
+    assert!(!stdout.contains("after-each"), "stdout: {}", stdout);
+}
+
+// ============================================================================
+// Template Tests
+// ============================================================================
+
+#[test]
+fn test_template_validate_valid_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let file = temp_dir.path().join("shared.toml");
+    std::fs::write(
+        &file,
+        r#"
+[template]
+name = "shared"
+description = "Shared template"
+
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#,
+    )
+    .expect("Failed to write template");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .env("HOME", temp_dir.path())
+        .args(["template", "validate", file.to_str().unwrap()])
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("is valid"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_template_validate_reports_all_problems() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let file = temp_dir.path().join("broken.toml");
+    std::fs::write(
+        &file,
+        r#"
+[template]
+name = ""
+description = ""
+"#,
+    )
+    .expect("Failed to write template");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .env("HOME", temp_dir.path())
+        .args(["template", "validate", file.to_str().unwrap()])
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 1);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("3 problem(s)"), "stdout: {}", stdout);
+    assert!(stdout.contains("Template name cannot be empty"));
+    assert!(stdout.contains("Template description cannot be empty"));
+    assert!(stdout.contains("Template must contain at least one command"));
+}
+
+#[cfg(feature = "template-download")]
+#[test]
+fn test_template_import_from_url() {
+    use std::io::{Read, Write};
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let body = r#"
+[template]
+name = "remote"
+description = "Remote template"
+
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+    let url = format!("http://{}/remote.toml", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        if let Ok((mut stream, _)) = listener.accept() {
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .env("HOME", temp_dir.path())
+        .args(["template", "import", "--url", &url, "--yes"])
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("cargo build"),
+        "preview missing: {}",
+        stdout
+    );
+    assert!(temp_dir
+        .path()
+        .join(".cmdrun/templates/remote.toml")
+        .exists());
+}
+
+#[cfg(not(feature = "template-download"))]
+#[test]
+fn test_template_import_url_requires_feature() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .env("HOME", temp_dir.path())
+        .args([
+            "template",
+            "import",
+            "--url",
+            "https://example.com/remote.toml",
+        ])
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("template-download"), "stderr: {}", stderr);
+}
+
+// ============================================================================
+// Shell Override Tests
+// ============================================================================
+
+fn write_shell_config(temp_dir: &TempDir) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.prepare]
+description = "Prepare"
+cmd = "echo prepared"
+
+[commands.build]
+description = "Build"
+cmd = "echo built"
+deps = ["prepare"]
+"#,
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_with_shell_override() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_shell_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--shell",
+        "sh",
+        "build",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("built"), "stdout: {}", stdout);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_shell_override_applies_to_dependencies() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_shell_config(&temp_dir);
+
+    // Wrapper shell that marks every invocation
+    let wrapper = temp_dir.path().join("marker-sh");
+    std::fs::write(
+        &wrapper,
+        "#!/bin/sh\necho via-marker-sh\nexec /bin/sh \"$@\"\n",
+    )
+    .expect("Failed to write wrapper shell");
+    std::fs::set_permissions(&wrapper, std::fs::Permissions::from_mode(0o755))
+        .expect("Failed to make wrapper executable");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--parallel",
+        "--shell",
+        wrapper.to_str().unwrap(),
+        "build",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout.matches("via-marker-sh").count(),
+        2,
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("prepared") && stdout.contains("built"));
+}
+
+#[test]
+fn test_run_with_missing_shell_fails() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_shell_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--shell",
+        "/nonexistent/bin/nosuchshell",
+        "build",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Shell not found: /nonexistent/bin/nosuchshell"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("prepared"));
+}
+
+// ============================================================================
+// Doctor Tests
+// ============================================================================
+
+#[test]
+fn test_doctor_passes_with_valid_setup() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    std::fs::write(
+        temp_dir.path().join("commands.toml"),
+        r#"
+[commands.build]
+description = "Build"
+cmd = "echo build"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .arg("doctor")
+        .output()
+        .expect("Failed to run command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_exit_code(&output, 0);
+    assert!(
+        stdout.contains("1 command(s) loaded from"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("0 failed"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_doctor_reports_missing_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .arg("doctor")
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 1);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let config_line = stdout
+        .lines()
+        .find(|line| line.contains("Configuration"))
+        .unwrap_or_else(|| panic!("no Configuration check in: {}", stdout));
+    assert!(config_line.contains('✗'), "line: {}", config_line);
+    assert!(config_line.contains("cmdrun init"), "line: {}", config_line);
+    // Other checks still run
+    assert!(stdout.contains("Shell"), "stdout: {}", stdout);
+    assert!(stdout.contains("1 failed"), "stdout: {}", stdout);
+}
+
+// ============================================================================
+// Audit Tests
+// ============================================================================
+
+#[test]
+fn test_audit_reports_blocked_commands_without_running_them() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    let marker = temp_dir.path().join("ran");
+
+    std::fs::write(
+        &config_path,
+        format!(
+            r#"
+[commands.build]
+description = "Build"
+cmd = "touch {marker}"
+
+[commands.deploy]
+description = "Deploy"
+cmd = "touch {marker} && ssh host restart"
+allow_chaining = true
+"#,
+            marker = marker.display()
+        ),
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "audit"])
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 1);
+    assert!(!marker.exists(), "audit must not execute commands");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Command contains command chaining: '&&'"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("allow_chaining = true"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("1 command(s) would be blocked in strict mode"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_audit_passes_clean_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.build]
+description = "Build"
+cmd = "cargo build"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "audit"])
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("No command would be blocked"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_placeholder_arguments_with_shell_syntax() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.say]
+description = "Say"
+cmd = "echo {1}"
+
+[commands.each]
+description = "Each"
+cmd = "echo {@}"
+"#,
+    )
+    .expect("Failed to write config");
+
+    // Quoted placeholder values are not rejected as chaining or pipes
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "say",
+        "a; b",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("a; b"), "stdout: {}", stdout);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "each",
+        "x | y",
+        "z",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("x | y z"), "stdout: {}", stdout);
+}
+
+// ============================================================================
+// Output Log Tests
+// ============================================================================
+
+#[cfg(unix)]
+#[test]
+fn test_run_log_captures_output() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.greet]
+description = "Greet"
+cmd = "echo hello-${1}"
+
+[commands.fail]
+description = "Fail"
+cmd = ["echo partial", "false"]
+"#,
+    )
+    .expect("Failed to write config");
+    let log_path = temp_dir.path().join("run.log");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--log",
+        log_path.to_str().unwrap(),
+        "greet",
+        "world",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("hello-world"), "stdout: {}", stdout);
+
+    // A failing run is appended to the same file
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--log",
+        log_path.to_str().unwrap(),
+        "fail",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 1);
+
+    let content = std::fs::read_to_string(&log_path).expect("Failed to read log");
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 4, "log: {}", content);
+    assert!(
+        lines[0].ends_with(" cmdrun run greet world ==="),
+        "{}",
+        lines[0]
+    );
+    assert_eq!(lines[1], "hello-world");
+    assert!(lines[2].ends_with(" cmdrun run fail ==="), "{}", lines[2]);
+    assert_eq!(lines[3], "partial");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_capture_writes_redacted_manifest() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[config.env]
+API_TOKEN = "super-secret"
+
+[commands.deploy]
+description = "Deploy"
+cmd = ["echo deploying-${1}", "exit 3"]
+env = { MODE = "release" }
+"#,
+    )
+    .expect("Failed to write config");
+    let manifest_path = temp_dir.path().join("runs/deploy.json");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--capture",
+        manifest_path.to_str().unwrap(),
+        "deploy",
+        "prod",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 3);
+
+    let content = std::fs::read_to_string(&manifest_path).expect("Failed to read manifest");
+    assert!(!content.contains("super-secret"), "manifest: {}", content);
+    let manifest: serde_json::Value = serde_json::from_str(&content).expect("Invalid JSON");
+
+    assert_eq!(manifest["manifest_version"], 2);
+    assert_eq!(manifest["cmdrun_version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(manifest["command"], "deploy");
+    assert_eq!(manifest["args"], serde_json::json!(["prod"]));
+    assert_eq!(
+        manifest["resolved_commands"],
+        serde_json::json!(["echo deploying-prod", "exit 3"])
+    );
+    assert!(manifest["shell"].is_string());
+    assert_eq!(manifest["platform"]["os"], std::env::consts::OS);
+    assert_eq!(manifest["platform"]["arch"], std::env::consts::ARCH);
+    assert_eq!(manifest["env"]["API_TOKEN"], "***REDACTED***");
+    assert_eq!(manifest["env"]["MODE"], "release");
+    assert_eq!(manifest["env"]["1"], "prod");
+    assert_eq!(manifest["exit_code"], 3);
+    assert_eq!(manifest["success"], false);
+    assert!(manifest["duration_ms"].is_u64());
+    assert_eq!(manifest["output"], serde_json::json!(["deploying-prod"]));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_capture_records_per_command_shell() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.prep]
+cmd = "echo prep"
+
+[commands.deploy]
+cmd = "echo deploy"
+shell = "sh"
+deps = ["prep"]
+"#,
+    )
+    .expect("Failed to write config");
+    let manifest_path = temp_dir.path().join("deploy.json");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--sequential",
+        "--shell",
+        "bash",
+        "--capture",
+        manifest_path.to_str().unwrap(),
+        "deploy",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+
+    let content = std::fs::read_to_string(&manifest_path).expect("Failed to read manifest");
+    let manifest: serde_json::Value = serde_json::from_str(&content).expect("Invalid JSON");
+
+    // The command's own `shell` wins over the context shell
+    assert_eq!(manifest["shell"], "sh");
+    assert_eq!(manifest["command_shells"]["deploy"], "sh");
+    assert!(
+        manifest["command_shells"]["prep"]
+            .as_str()
+            .is_some_and(|shell| shell.ends_with("bash")),
+        "manifest: {}",
+        content
+    );
+}
+
+// ============================================================================
+// Error JSON Tests
+// ============================================================================
+
+fn write_error_json_config(temp_dir: &TempDir) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.build]
+description = "Build"
+cmd = "exit 2"
+"#,
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
+/// Parse the structured error from the last line of stderr
+fn error_json(output: &std::process::Output) -> serde_json::Value {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = stderr.lines().last().unwrap_or("");
+    serde_json::from_str(line).unwrap_or_else(|e| panic!("{}: {}", e, stderr))
+}
+
+#[test]
+fn test_error_json_command_not_found() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_error_json_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "--error-json",
+        "run",
+        "no-such-command",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 3);
+    let error = error_json(&output);
+    assert_eq!(error["kind"], "CommandNotFound");
+    assert_eq!(error["command"], "no-such-command");
+    assert!(error["exit_code"].is_null());
+    assert!(error["error"].as_str().unwrap().contains("not found"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_error_json_execution_failure() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_error_json_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--error-json",
+        "build",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 2);
+    let error = error_json(&output);
+    assert_eq!(error["kind"], "CommandExecutionFailed");
+    assert_eq!(error["exit_code"], 2);
+    assert_eq!(error["command"], "build");
+    assert!(error["error"].as_str().unwrap().contains("exit code 2"));
+}
+
+// ============================================================================
+// Exit Code Tests
+// ============================================================================
+
+fn write_exit_code_config(temp_dir: &TempDir) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.fail]
+description = "Fail with 42"
+cmd = "exit 42"
+
+[commands.unsafe]
+description = "Rejected by validation"
+cmd = "echo $(whoami)"
+
+[commands.slow]
+description = "Times out"
+cmd = "sleep 5"
+timeout = 1
+"#,
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
+fn run_exit_code_command(temp_dir: &TempDir, name: &str) -> std::process::Output {
+    let config_path = write_exit_code_config(temp_dir);
+    run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", name])
+        .expect("Failed to run command")
+}
+
+#[test]
+fn test_exit_code_config_not_found() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    // Explicit --config path that does not exist
+    let output = run_cmdrun(&[
+        "--config",
+        temp_dir.path().join("missing.toml").to_str().unwrap(),
+        "list",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 2);
+
+    // No local config anywhere above the working directory
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["run", "build"])
+        .current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .env("XDG_CONFIG_HOME", temp_dir.path().join(".config"))
+        .output()
+        .expect("Failed to run command");
+    assert_exit_code(&output, 2);
+}
+
+#[test]
+fn test_exit_code_command_not_found() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output = run_exit_code_command(&temp_dir, "no-such-command");
+    assert_exit_code(&output, 3);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exit_code_validation_rejected() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output = run_exit_code_command(&temp_dir, "unsafe");
+    assert_exit_code(&output, 4);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("rejected by security validation"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exit_code_timeout() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output = run_exit_code_command(&temp_dir, "slow");
+    assert_exit_code(&output, 124);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exit_code_forwards_command_exit_code() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output = run_exit_code_command(&temp_dir, "fail");
+    assert_exit_code(&output, 42);
+}
+
+// ============================================================================
+// List Category Tests
+// ============================================================================
+
+fn run_list_category(args: &[&str]) -> String {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.test]
+description = "Run tests"
+cmd = "cargo test"
+category = "build"
+
+[commands.build]
+description = "Build project"
+cmd = "cargo build"
+category = "build"
+
+[commands.deploy]
+description = "Deploy"
+cmd = "echo deploy"
+category = "ops"
+
+[commands.clean]
+description = "Clean"
+cmd = "cargo clean"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let mut full_args = vec!["--config", config_path.to_str().unwrap(), "list"];
+    full_args.extend_from_slice(args);
+    let output = run_cmdrun(&full_args).expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn position(haystack: &str, needle: &str) -> usize {
+    haystack
+        .find(needle)
+        .unwrap_or_else(|| panic!("'{}' not found in:\n{}", needle, haystack))
+}
+
+#[test]
+fn test_list_grouped_by_category() {
+    let stdout = run_list_category(&["--grouped"]);
+
+    let headers: Vec<&str> = stdout
+        .lines()
+        .filter(|l| !l.is_empty() && !l.starts_with(' '))
+        .skip(1)
+        .collect();
+    assert_eq!(headers, vec!["build", "ops", "Other"], "{}", stdout);
+
+    assert!(position(&stdout, "  build ") < position(&stdout, "  test "));
+    assert!(position(&stdout, "\nbuild") < position(&stdout, "  build "));
+    assert!(position(&stdout, "  test ") < position(&stdout, "\nops"));
+    assert!(position(&stdout, "\nops") < position(&stdout, "  deploy "));
+    assert!(position(&stdout, "\nOther") < position(&stdout, "  clean "));
+}
+
+#[test]
+fn test_list_filter_by_category() {
+    let stdout = run_list_category(&["--category", "build"]);
+    assert!(stdout.contains("  build  Build project\n"), "{}", stdout);
+    assert!(stdout.contains("  test   Run tests\n"), "{}", stdout);
+    assert!(!stdout.contains("deploy"), "{}", stdout);
+    assert!(!stdout.contains("clean"), "{}", stdout);
+
+    let stdout = run_list_category(&["--category", "other"]);
+    assert!(stdout.contains("  clean  Clean\n"), "{}", stdout);
+    assert!(!stdout.contains("deploy"), "{}", stdout);
+
+    let stdout = run_list_category(&["--category", "missing"]);
+    assert!(
+        stdout.contains("No commands in category 'missing'"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_list_truncates_long_descriptions_unless_no_truncate() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    let long = "word ".repeat(40);
+    std::fs::write(
+        &config_path,
+        format!(
+            "[commands.a]\ndescription = \"Short\"\ncmd = \"true\"\n\n\
+             [commands.long-name]\ndescription = \"{}\"\ncmd = \"true\"\n",
+            long.trim_end()
+        ),
+    )
+    .expect("Failed to write config");
+    let config = config_path.to_str().unwrap();
+
+    // Not a terminal: descriptions are cut at the 80-column fallback
+    let output = run_cmdrun(&["--config", config, "list"]).expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("  a          Short\n"), "{}", stdout);
+    let line = stdout
+        .lines()
+        .find(|l| l.starts_with("  long-name  word"))
+        .unwrap_or_else(|| panic!("missing entry:\n{}", stdout));
+    assert!(line.ends_with('…'), "{}", line);
+    assert_eq!(line.chars().count(), 80, "{}", line);
+
+    let output =
+        run_cmdrun(&["--config", config, "list", "--no-truncate"]).expect("Failed to run command");
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(&format!("  long-name  {}\n", long.trim_end())),
+        "{}",
+        stdout
+    );
+}
+
+// ============================================================================
+// Print Command Tests
+// ============================================================================
+
+fn write_print_command_config(temp_dir: &TempDir) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[config.env]
+GREETING = "hello"
+API_TOKEN = "super-secret"
+
+[commands.greet]
+description = "Greet"
+cmd = "echo ${GREETING} ${1}"
+
+[commands.call]
+description = "Call the API"
+cmd = ["echo calling", "echo token=${API_TOKEN}"]
+"#,
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
+#[test]
+fn test_run_print_command_then_executes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_print_command_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "greet",
+        "--print-command",
+        "world",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    let printed = lines
+        .iter()
+        .position(|l| *l == "echo hello world")
+        .unwrap_or_else(|| panic!("resolved command not printed: {}", stdout));
+    // The command still runs after being printed
+    assert!(
+        lines[printed + 1..].contains(&"hello world"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_run_dry_run_prints_only_and_redacts() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_print_command_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "call",
+        "--dry-run",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Only the resolved lines are printed (plugin teardown logs may follow)
+    assert_eq!(
+        stdout.lines().take(2).collect::<Vec<_>>(),
+        vec!["echo calling", "echo token=***REDACTED***"]
+    );
+    // Nothing was executed
+    assert!(
+        !stdout.lines().any(|l| l == "calling"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains("super-secret"), "stdout: {}", stdout);
+}
+
+// ============================================================================
+// Run Watch Tests
+// ============================================================================
+
+fn write_run_watch_project(temp_dir: &TempDir) {
+    let project = temp_dir.path();
+    std::fs::create_dir(project.join("src")).expect("Failed to create src");
+    std::fs::write(project.join("record.sh"), "echo run >> runs.log\n")
+        .expect("Failed to write script");
+    std::fs::write(
+        project.join("commands.toml"),
+        r#"
+[commands.record]
+description = "Record a run"
+cmd = "sh record.sh"
+
+[commands.record.watch]
+paths = ["src"]
+patterns = ["**/*.txt"]
+debounce_ms = 100
+
+[commands.plain]
+description = "No watch spec"
+cmd = "echo plain"
+"#,
+    )
+    .expect("Failed to write config");
+}
+
+fn count_runs(project: &std::path::Path) -> usize {
+    std::fs::read_to_string(project.join("runs.log"))
+        .map(|s| s.lines().count())
+        .unwrap_or(0)
+}
+
+/// Poll until `runs.log` has at least `expected` lines
+fn wait_for_runs(project: &std::path::Path, expected: usize) -> usize {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while count_runs(project) < expected && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    count_runs(project)
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_watch_reruns_on_change() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_run_watch_project(&temp_dir);
+    let project = temp_dir.path();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .current_dir(project)
+        .env("HOME", project)
+        .env_remove("XDG_CONFIG_HOME")
+        .args(["run", "record", "--watch"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .expect("Failed to spawn cmdrun");
+
+    // Give the watcher time to start
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+    assert_eq!(count_runs(project), 0, "command ran before any change");
+
+    std::fs::write(project.join("src/input.txt"), "one").unwrap();
+    let first = wait_for_runs(project, 1);
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    std::fs::write(project.join("src/input.txt"), "two").unwrap();
+    let second = wait_for_runs(project, first + 1);
+
+    let _ = child.kill();
+    let _ = child.wait();
+
+    assert!(first >= 1, "command did not run after the first change");
+    assert!(
+        second > first,
+        "command did not re-run after the second change"
+    );
+}
+
+#[test]
+fn test_run_watch_without_spec_fails() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_run_watch_project(&temp_dir);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .args(["run", "plain", "--watch"])
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("has no watch configuration"), "{}", stderr);
+    assert!(stderr.contains("[commands.plain.watch]"), "{}", stderr);
+}
+
+// ============================================================================
+// Run Condition Tests
+// ============================================================================
+
+fn write_condition_config(temp_dir: &TempDir) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.yes]
+description = "Always succeeds"
+cmd = "exit 0"
+
+[commands.no]
+description = "Always fails"
+cmd = "exit 1"
+
+[commands.when-yes]
+description = "Guarded by yes"
+cmd = "echo guarded-ran"
+when = "yes"
+
+[commands.when-no]
+description = "Guarded by no"
+cmd = "echo guarded-ran"
+when = "no"
+
+[commands.shell-no]
+description = "Guarded by a shell test"
+cmd = "echo guarded-ran"
+when_shell = "exit 1"
+"#,
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
+fn run_condition_command(temp_dir: &TempDir, name: &str) -> std::process::Output {
+    let config_path = write_condition_config(temp_dir);
+    Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .args(["--config", config_path.to_str().unwrap(), "run", name])
+        .output()
+        .expect("Failed to run command")
+}
+
+#[test]
+fn test_run_condition_true_runs_command() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output = run_condition_command(&temp_dir, "when-yes");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.lines().any(|l| l == "guarded-ran"), "{}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("skipped (condition false)"), "{}", stderr);
+}
+
+#[test]
+fn test_run_condition_false_skips_command() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    for name in ["when-no", "shell-no"] {
+        let output = run_condition_command(&temp_dir, name);
+
+        assert_exit_code(&output, 0);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.lines().any(|l| l == "guarded-ran"), "{}", stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("skipped (condition false)"), "{}", stderr);
+    }
+}
+
+// ============================================================================
+// Init From Tests
+// ============================================================================
+
+#[test]
+fn test_init_from_package_json() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    std::fs::write(
+        temp_dir.path().join("package.json"),
+        r#"{"scripts": {"pretest": "echo setup", "test": "echo testing"}}"#,
+    )
+    .expect("Failed to write package.json");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .args(["init", "--from", "package-json"])
+        .output()
+        .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+
+    let content = std::fs::read_to_string(temp_dir.path().join("commands.toml"))
+        .expect("commands.toml not written");
+    assert!(content.contains("[commands.test]"), "{}", content);
+    assert!(content.contains(r#"deps = ["pretest"]"#), "{}", content);
+}
+
+// ============================================================================
+// Run Profile Tests
+// ============================================================================
+
+fn write_profile_config(temp_dir: &TempDir) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.fetch]
+description = "Fetch"
+cmd = "echo fetch"
+
+[commands.compile]
+description = "Compile"
+cmd = "echo compile"
+
+[commands.release]
+description = "Release"
+cmd = "echo release"
+deps = ["fetch", "compile"]
+"#,
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
+#[test]
+fn test_run_profile_json_lists_every_command() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_profile_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "release",
+        "--parallel",
+        "--profile",
+        "--output",
+        "json",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+
+    // The pretty-printed report is the block from "{" to "}"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| *l == "{")
+        .expect("no JSON report");
+    let end = lines
+        .iter()
+        .rposition(|l| *l == "}")
+        .expect("no JSON report");
+    let report: serde_json::Value =
+        serde_json::from_str(&lines[start..=end].join("\n")).expect("invalid JSON report");
+
+    let commands = report["commands"].as_array().unwrap();
+    let mut names: Vec<&str> = commands
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["compile", "fetch", "release"]);
+    for command in commands {
+        assert!(command["duration_ms"].is_u64(), "{}", command);
+        let expected_parallel = command["name"] != "release";
+        assert_eq!(command["parallel"], expected_parallel, "{}", command);
+    }
+
+    let critical_path = report["critical_path"].as_array().unwrap();
+    assert_eq!(critical_path.len(), 2);
+    assert_eq!(critical_path[1], "release");
+}
+
+#[test]
+fn test_run_profile_text_table() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_profile_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "fetch",
+        "--profile",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Profile:"), "{}", stdout);
+    assert!(
+        stdout
+            .lines()
+            .any(|l| l.trim_start().starts_with("fetch") && l.ends_with("s  no")),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("Critical path"), "{}", stdout);
+}
+
+// ============================================================================
+// Run Env Override Tests
+// ============================================================================
+
+fn write_env_override_config(temp_dir: &TempDir) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[config.env]
+GREETING = "global"
+
+[commands.dep]
+description = "Dependency"
+cmd = "echo dep:$GREETING"
+
+[commands.show]
+description = "Show env"
+cmd = "echo show:$GREETING:$TARGET"
+deps = ["dep"]
+env = { GREETING = "command", TARGET = "world" }
+"#,
+    )
+    .unwrap();
+    config_path
+}
+
+#[test]
+fn test_run_env_override_single() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_env_override_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "show",
+        "--env",
+        "GREETING=cli",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("show:cli:world"), "{}", stdout);
+}
+
+#[test]
+fn test_run_env_override_multiple_reaches_dependencies() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_env_override_config(&temp_dir);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "show",
+        "--parallel",
+        "--env",
+        "GREETING=cli",
+        "--env",
+        "TARGET=there",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("dep:cli"), "{}", stdout);
+    assert!(stdout.contains("show:cli:there"), "{}", stdout);
+}
+
+#[test]
+fn test_run_env_override_malformed_rejected() {
+    let output = run_cmdrun(&["run", "show", "--env", "noequals"]).expect("Failed to run");
+
+    assert_exit_code(&output, 2);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("expected KEY=VALUE, got 'noequals'"),
+        "{}",
+        stderr
+    );
+}
+
+// ============================================================================
+// Run Cwd Tests
+// ============================================================================
+
+#[cfg(unix)]
+#[test]
+fn test_run_cwd_overrides_working_dir_for_dependencies() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let project = temp_dir.path().join("project");
+    let work = project.join("work");
+    std::fs::create_dir_all(work.join("sub")).expect("Failed to create dirs");
+    std::fs::write(
+        project.join("commands.toml"),
+        r#"
+[commands.where]
+description = "Where"
+cmd = "pwd"
+
+[commands.nested]
+description = "Nested"
+cmd = "pwd"
+working_dir = "sub"
+
+[commands.all]
+description = "All"
+cmd = "pwd"
+deps = ["where", "nested"]
+"#,
+    )
+    .expect("Failed to write config");
+
+    // Relative to the process directory
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["run", "--parallel", "--cwd", "work", "all"])
+        .current_dir(&project)
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let dirs: Vec<&str> = stdout.lines().filter(|l| l.starts_with('/')).collect();
+    let work = work.canonicalize().unwrap().display().to_string();
+    let nested = format!("{}/sub", work);
+    assert_eq!(dirs.len(), 3, "stdout: {}", stdout);
+    assert_eq!(dirs.iter().filter(|d| **d == work).count(), 2, "{:?}", dirs);
+    assert!(dirs.contains(&nested.as_str()), "{:?}", dirs);
+}
+
+#[test]
+fn test_run_cwd_missing_directory() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    std::fs::write(
+        temp_dir.path().join("commands.toml"),
+        r#"
+[commands.where]
+description = "Where"
+cmd = "pwd"
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["run", "--cwd", "missing", "where"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to run command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Working directory not found"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Running:"));
+}
+
+// ============================================================================
+// Run Timeout Tests
+// ============================================================================
+
+#[test]
+fn test_run_timeout_flag_overrides_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[config]
+timeout = "1h"
+
+[commands.slow]
+description = "Slow"
+cmd = "sleep 5"
+timeout = 60
+"#,
+    )
+    .unwrap();
+
+    let started = std::time::Instant::now();
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "slow",
+        "--timeout",
+        "300ms",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 124);
+    assert!(
+        started.elapsed() < std::time::Duration::from_secs(5),
+        "--timeout was not applied"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("timed out after 300ms"), "{}", stderr);
+}
+
+#[test]
+fn test_run_timeout_flag_rejects_invalid_duration() {
+    let output = run_cmdrun(&["run", "anything", "--timeout", "soon"]).expect("Failed to run");
+
+    assert_exit_code(&output, 2);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid duration 'soon'"), "{}", stderr);
+}
+
+// ============================================================================
+// Run Interrupt Tests
+// ============================================================================
+
+#[cfg(target_os = "linux")]
+fn find_processes(argv: &[&str]) -> Vec<u32> {
+    let expected = argv.iter().fold(Vec::new(), |mut bytes, arg| {
+        bytes.extend_from_slice(arg.as_bytes());
+        bytes.push(0);
+        bytes
+    });
+    std::fs::read_dir("/proc")
+        .expect("Failed to read /proc")
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u32>().ok())
+        .filter(|pid| {
+            std::fs::read(format!("/proc/{}/cmdline", pid)).is_ok_and(|cmdline| cmdline == expected)
+        })
+        .collect()
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_run_interrupt_kills_child_and_exits_130() {
+    use std::time::{Duration, Instant};
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    // 他のテストと衝突しない引数
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.hang]
+description = "Hang"
+cmd = "sleep 4713; echo done"
+allow_chaining = true
+"#,
+    )
+    .unwrap();
+
+    // sleep is a grandchild of cmdrun (the shell forks it for the compound command)
+    let mut cmdrun = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "run", "hang"])
+        .env("HOME", temp_dir.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .expect("Failed to spawn cmdrun");
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while find_processes(&["sleep", "4713"]).is_empty() {
+        assert!(Instant::now() < deadline, "sleep was never started");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let status = Command::new("kill")
+        .args(["-INT", &cmdrun.id().to_string()])
+        .status()
+        .expect("Failed to run kill");
+    assert!(status.success());
+
+    let status = cmdrun.wait().expect("Failed to wait for cmdrun");
+    assert_eq!(status.code(), Some(130));
+    assert!(find_processes(&["sleep", "4713"]).is_empty());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_run_timeout_kills_grandchildren() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    // 他のテストと衝突しない引数
+    std::fs::write(
+        &config_path,
+        r#"
+[config]
+kill_grace_ms = 200
+
+[commands.hang]
+description = "Hang"
+cmd = "sleep 4714; echo done"
+allow_chaining = true
+timeout = 1
+"#,
+    )
+    .unwrap();
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "hang"])
+        .expect("Failed to run command");
+
+    assert_ne!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("timed out"), "stderr: {}", stderr);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("done"));
+    assert!(find_processes(&["sleep", "4714"]).is_empty());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_run_timeout_kills_grandchildren_after_leader_exits() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    // シェルは SIGTERM で終了するが、子の sleep は SIGTERM を無視する
+    std::fs::write(
+        &config_path,
+        r#"
+[config]
+kill_grace_ms = 300
+
+[commands.hang]
+description = "Hang"
+cmd = "trap '' TERM; sleep 4715 & trap - TERM; wait"
+allow_chaining = true
+timeout = 1
+"#,
+    )
+    .unwrap();
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "hang"])
+        .expect("Failed to run command");
+
+    assert_ne!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("timed out"), "stderr: {}", stderr);
+    assert!(find_processes(&["sleep", "4715"]).is_empty());
+}
+
+// ============================================================================
+// List JSON Schema Tests
+// ============================================================================
+
+#[test]
+fn test_list_json_schema_outputs_schema_without_loading_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(&config_path, "this is not toml").unwrap();
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "list",
+        "--json-schema",
+    ])
+    .expect("Failed to execute");
+    assert_exit_code(&output, 0);
+
+    let schema: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("schema is not valid JSON");
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    assert!(schema["properties"]["commands"].is_object());
+    assert!(schema["properties"]["config"].is_object());
+}
+
+// ============================================================================
+// Parallel Progress Tests
+// ============================================================================
+
+#[test]
+fn test_parallel_progress_falls_back_to_plain_lines_when_not_a_tty() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.lint]
+description = "Lint"
+cmd = "echo linted"
+
+[commands.check]
+description = "Check"
+cmd = "echo checked"
+
+[commands.all]
+description = "All"
+cmd = "echo done"
+deps = ["lint", "check"]
+"#,
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--parallel",
+        "all",
+    ])
+    .expect("Failed to run command");
+    assert_exit_code(&output, 0);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains('\x1b'), "stderr: {:?}", stderr);
+    assert!(!stderr.contains('\r'), "stderr: {:?}", stderr);
+    assert!(!stderr.contains('⠋'), "stderr: {:?}", stderr);
+
+    let completions: Vec<&str> = stderr
+        .lines()
+        .filter(|line| line.starts_with("  ✓ ["))
+        .collect();
+    assert_eq!(completions.len(), 2, "stderr: {}", stderr);
+    assert!(
+        completions[0].starts_with("  ✓ [1/2] "),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        completions[1].starts_with("  ✓ [2/2] "),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("] lint (") && stderr.contains("] check ("));
+}
+
+// ============================================================================
+// Execution Mode Tests
+// ============================================================================
+
+/// Config where `a` and `b` only succeed when they run at the same time:
+/// each marks itself started and waits (up to 5s) for the other
+fn write_rendezvous_config(temp_dir: &TempDir, parallel: bool) -> std::path::PathBuf {
+    let script = temp_dir.path().join("rendezvous.sh");
+    std::fs::write(
+        &script,
+        "touch \"$1\"\nfor i in $(seq 50); do [ -e \"$2\" ] && exit 0; sleep 0.1; done\nexit 1\n",
+    )
+    .expect("Failed to write script");
+    let dir = temp_dir.path().display();
+    let wait = |me: &str, other: &str| format!("sh {} {dir}/{me} {dir}/{other}", script.display());
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            r#"
+[commands.a]
+cmd = "{}"
+
+[commands.b]
+cmd = "{}"
+
+[commands.all]
+cmd = "echo done"
+deps = ["a", "b"]
+parallel = {}
+"#,
+            wait("a.started", "b.started"),
+            wait("b.started", "a.started"),
+            parallel
+        ),
+    )
+    .expect("Failed to write config");
+    config_path
+}
+
+#[test]
+#[cfg(unix)]
+fn test_run_sequential_overrides_parallel_command() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_rendezvous_config(&temp_dir, true);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--sequential",
+        "--jobs",
+        "2",
+        "all",
+    ])
+    .expect("Failed to run command");
+
+    // The first dependency runs alone, gives up waiting and fails the run
+    assert_ne!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("with sequential dependencies"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Execution plan: 3 groups"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        temp_dir.path().join("a.started").exists() != temp_dir.path().join("b.started").exists(),
+        "only one dependency should have started"
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn test_run_parallel_overrides_non_parallel_command() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_rendezvous_config(&temp_dir, false);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--parallel",
+        "--jobs",
+        "2",
+        "all",
+    ])
+    .expect("Failed to run command");
+
+    // Both dependencies must be running at the same time to succeed
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("done"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("Execution plan: 2 groups"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_run_parallel_and_sequential_conflict() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = write_rendezvous_config(&temp_dir, false);
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "--parallel",
+        "--sequential",
+        "all",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 2);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"), "stderr: {}", stderr);
+    assert!(!temp_dir.path().join("a.started").exists());
+}
+
+// ============================================================================
+// Config From Stdin Tests
+// ============================================================================
+
+/// Run cmdrun in an empty directory with `config` piped to stdin
+fn run_with_stdin_config(args: &[&str], config: &str) -> std::process::Output {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(args)
+        .current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to run command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(config.as_bytes())
+        .expect("Failed to write stdin");
+    child
+        .wait_with_output()
+        .expect("Failed to wait for command")
+}
+
+#[test]
+fn test_config_from_stdin_list() {
+    let output = run_with_stdin_config(
+        &["--config", "-", "list"],
+        r#"
+[commands.generated-build]
+description = "Generated build"
+cmd = "echo build"
+
+[commands.generated-test]
+description = "Generated test"
+cmd = "echo test"
+"#,
+    );
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("generated-build"), "stdout: {}", stdout);
+    assert!(stdout.contains("Generated test"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_config_from_stdin_run_and_parse_error() {
+    let output = run_with_stdin_config(
+        &["--config", "-", "run", "hello"],
+        r#"
+[commands.hello]
+description = "Hello"
+cmd = "echo hello-from-stdin"
+"#,
+    );
+    assert_exit_code(&output, 0);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("hello-from-stdin"));
+
+    let output = run_with_stdin_config(&["--config", "-", "list"], "not toml");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("stdin"), "stderr: {}", stderr);
+}
+
+// ============================================================================
+// Config Discovery Tests
+// ============================================================================
+
+fn run_in_dir(
+    dir: &std::path::Path,
+    home: &std::path::Path,
+    args: &[&str],
+) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(args)
+        .current_dir(dir)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .output()
+        .expect("Failed to run command")
+}
+
+#[test]
+fn test_config_discovered_from_nested_subdir() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let root = temp_dir.path().canonicalize().unwrap();
+    std::fs::write(
+        root.join("commands.toml"),
+        r#"
+[commands.where]
+description = "Print the working directory"
+cmd = "pwd"
+"#,
+    )
+    .expect("Failed to write config");
+    let nested = root.join("crates").join("app").join("src");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let output = run_in_dir(&nested, &root, &["--quiet", "run", "where"]);
+    assert_exit_code(&output, 0);
+    // The project directory is the working directory, not the subdirectory
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), root.display().to_string());
+
+    let output = run_in_dir(&nested, &root, &["--no-walk", "run", "where"]);
+    assert_exit_code(&output, 2);
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_config_discovered_in_dot_cmdrun_dir() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let root = temp_dir.path().canonicalize().unwrap();
+    std::fs::create_dir_all(root.join(".cmdrun")).unwrap();
+    std::fs::write(
+        root.join(".cmdrun").join("commands.toml"),
+        r#"
+[commands.where]
+description = "Print the working directory"
+cmd = "pwd"
+"#,
+    )
+    .expect("Failed to write config");
+    let nested = root.join("docs");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let output = run_in_dir(&nested, &root, &["--quiet", "run", "where"]);
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), root.display().to_string());
+
+    // --no-walk still finds the config in the current directory
+    let output = run_in_dir(&root, &root, &["--no-walk", "--quiet", "run", "where"]);
+    assert_exit_code(&output, 0);
+}
+
+// ============================================================================
+// Open Editor Tests
+// ============================================================================
+
+/// Fake editor that records the file it was asked to open in `marker`
+#[cfg(unix)]
+fn write_fake_editor(dir: &std::path::Path, name: &str, marker: &std::path::Path) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = dir.join(name);
+    std::fs::write(
+        &path,
+        format!("#!/bin/sh\necho \"$@\" > '{}'\n", marker.display()),
+    )
+    .expect("Failed to write fake editor");
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+        .expect("Failed to make fake editor executable");
+    path.to_str().unwrap().to_string()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_open_editor_precedence() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(&config_path, "[commands]\n").expect("Failed to write config");
+
+    let flag_marker = temp_dir.path().join("flag-opened");
+    let visual_marker = temp_dir.path().join("visual-opened");
+    let editor_marker = temp_dir.path().join("editor-opened");
+    let flag_editor = write_fake_editor(temp_dir.path(), "flag-editor", &flag_marker);
+    let visual_editor = write_fake_editor(temp_dir.path(), "visual-editor", &visual_marker);
+    let env_editor = write_fake_editor(temp_dir.path(), "env-editor", &editor_marker);
+
+    let open = |extra: &[&str], visual: Option<&str>| {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_cmdrun"));
+        cmd.args(["--config", config_path.to_str().unwrap(), "open"])
+            .args(extra)
+            .env("EDITOR", &env_editor)
+            .env_remove("VISUAL");
+        if let Some(visual) = visual {
+            cmd.env("VISUAL", visual);
+        }
+        cmd.output().expect("Failed to run command")
+    };
+
+    // --editor wins over $VISUAL and $EDITOR, and may carry arguments
+    let output = open(
+        &["--editor", &format!("{} --wait", flag_editor)],
+        Some(&visual_editor),
+    );
+    assert_exit_code(&output, 0);
+    let opened = std::fs::read_to_string(&flag_marker).expect("flag editor was not invoked");
+    assert_eq!(opened.trim(), format!("--wait {}", config_path.display()));
+    assert!(!visual_marker.exists() && !editor_marker.exists());
+
+    // $VISUAL wins over $EDITOR
+    let output = open(&[], Some(&visual_editor));
+    assert_exit_code(&output, 0);
+    assert!(visual_marker.exists());
+    assert!(!editor_marker.exists());
+
+    // $EDITOR is used when $VISUAL is unset
+    let output = open(&[], None);
+    assert_exit_code(&output, 0);
+    assert!(editor_marker.exists());
+}
+
+#[test]
+fn test_open_missing_editor_fails() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(&config_path, "[commands]\n").expect("Failed to write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "open",
+            "--editor",
+            "/nonexistent/bin/no-such-editor",
+        ])
+        .output()
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Editor not found: /nonexistent/bin/no-such-editor"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_script_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    std::fs::create_dir(temp_dir.path().join("scripts")).expect("Failed to create dir");
+    std::fs::write(
+        temp_dir.path().join("scripts/hello.sh"),
+        "# greet\necho from-script \"$1\"\n",
+    )
+    .expect("Failed to write script");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        "[commands.hello]\ndescription = \"Run a script\"\ncmd = { file = \"scripts/hello.sh\" }\n",
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "run",
+        "hello",
+        "world",
+    ])
+    .expect("Failed to run command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("from-script world"), "stdout: {}", stdout);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_script_file_keeps_stdin_for_the_script() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    std::fs::create_dir(temp_dir.path().join("scripts")).expect("Failed to create dir");
+    std::fs::write(
+        temp_dir.path().join("scripts/greet.sh"),
+        "read name\necho \"hello-$name from $(basename \"$0\")\"\n",
+    )
+    .expect("Failed to write script");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        "[commands.greet]\ncmd = { file = \"scripts/greet.sh\" }\nallow_subshells = true\n",
+    )
+    .expect("Failed to write config");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "run", "greet"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to run command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"world\n")
+        .expect("Failed to write stdin");
+    let output = child
+        .wait_with_output()
+        .expect("Failed to wait for command");
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("hello-world from greet.sh"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_run_missing_script_file_fails() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        "[commands.hello]\ndescription = \"Run a script\"\ncmd = { file = \"scripts/missing.sh\" }\n",
+    )
+    .expect("Failed to write config");
+
+    let output = run_cmdrun(&["--config", config_path.to_str().unwrap(), "run", "hello"])
+        .expect("Failed to run command");
+
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Failed to read script file") && stderr.contains("missing.sh"),
+        "stderr: {}",
+        stderr
     );
 }
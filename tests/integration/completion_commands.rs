@@ -14,8 +14,8 @@ use tempfile::TempDir;
 
 /// Test helper to run cmdrun completion command
 fn run_completion(shell: &str) -> Result<std::process::Output, std::io::Error> {
-    Command::new("cargo")
-        .args(["run", "--bin", "cmdrun", "--", "completion", shell])
+    Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["completion", shell])
         .output()
 }
 
@@ -142,13 +142,30 @@ fn test_elvish_completion_generation() {
     assert_contains(&output.stderr, "eval (cmdrun completion elvish)");
 }
 
+#[test]
+fn test_nushell_completion_generation() {
+    for shell in ["nushell", "nu"] {
+        let output = run_completion(shell).expect("Failed to run completion command");
+
+        assert!(output.status.success());
+
+        // Should generate extern definitions with a dynamic command-name completer
+        assert_contains(&output.stdout, "def \"nu-complete cmdrun commands\" []");
+        assert_contains(&output.stdout, "^cmdrun completion-list");
+        assert_contains(&output.stdout, "export extern \"cmdrun run\" [");
+
+        assert_installation_instructions(&output.stderr, shell);
+        assert_contains(&output.stderr, "save -f ~/.config/nushell/cmdrun.nu");
+    }
+}
+
 // ============================================================================
 // Cross-Shell Compatibility Tests
 // ============================================================================
 
 #[test]
 fn test_all_supported_shells_generate_successfully() {
-    let shells = ["bash", "zsh", "fish", "powershell", "elvish"];
+    let shells = ["bash", "zsh", "fish", "powershell", "elvish", "nushell"];
 
     for shell in &shells {
         let output = run_completion(shell)
@@ -173,15 +190,8 @@ fn test_all_supported_shells_generate_successfully() {
 
 #[test]
 fn test_unsupported_shell_returns_error() {
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "completion",
-            "invalid-shell",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["completion", "invalid-shell"])
         .output()
         .expect("Failed to run command");
 
@@ -237,16 +247,20 @@ fn test_fish_completion_supports_descriptions() {
 
     // Fish supports native descriptions with tab separator
     assert!(
-        stdout.contains("string replace ':' \\t"),
+        stdout.contains("'$1'\\t"),
         "Fish completion should convert description format (colon to tab)"
     );
+    assert!(
+        stdout.contains("string replace -a '\\\\:' ':'"),
+        "Fish completion should unescape colons"
+    );
 }
 
 #[test]
 fn test_completion_list_integration() {
     // First, check if completion-list command exists
-    let list_output = Command::new("cargo")
-        .args(["run", "--bin", "cmdrun", "--", "completion-list"])
+    let list_output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["completion-list"])
         .output()
         .expect("Failed to run completion-list command");
 
@@ -269,6 +283,44 @@ fn test_completion_list_integration() {
     }
 }
 
+#[test]
+fn test_complete_args_lists_configured_choices() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("commands.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+[commands.deploy]
+cmd = "echo deploy {1} {2}"
+arg_completions = [["staging", "prod"], ["web", "api"]]
+"#,
+    )
+    .expect("Failed to write config");
+
+    let complete = |name: &str, position: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+            .args(["--config"])
+            .arg(&config_path)
+            .args(["complete-args", name, position])
+            .output()
+            .expect("Failed to run complete-args");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    assert_eq!(complete("deploy", "1"), "staging\nprod\n");
+    assert_eq!(complete("deploy", "2"), "web\napi\n");
+    // No choices beyond the configured positions or for unknown commands
+    assert_eq!(complete("deploy", "3"), "");
+    assert_eq!(complete("missing", "1"), "");
+
+    // The bash, zsh and fish scripts ask for argument choices
+    for shell in &["bash", "zsh", "fish"] {
+        let output = run_completion(shell).expect("Failed to generate completion");
+        assert_contains(&output.stdout, "cmdrun complete-args");
+    }
+}
+
 // ============================================================================
 // Installation Instructions Tests
 // ============================================================================
@@ -408,12 +460,8 @@ fn test_completion_command_works_with_config_flag() {
     let config_path = temp_dir.path().join("commands.toml");
     std::fs::write(&config_path, "[commands]\n").expect("Failed to write config");
 
-    let output = Command::new("cargo")
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
         .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
             "--config",
             config_path.to_str().unwrap(),
             "completion",
@@ -429,17 +477,8 @@ fn test_completion_command_works_with_config_flag() {
 
 #[test]
 fn test_completion_command_with_color_flag() {
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--",
-            "--color",
-            "never",
-            "completion",
-            "bash",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--color", "never", "completion", "bash"])
         .output()
         .expect("Failed to run command");
 
@@ -476,8 +515,8 @@ fn test_completion_generation_is_fast() {
 
 #[test]
 fn test_completion_with_no_arguments() {
-    let output = Command::new("cargo")
-        .args(["run", "--bin", "cmdrun", "--", "completion"])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["completion"])
         .output()
         .expect("Failed to run command");
 
@@ -490,8 +529,8 @@ fn test_completion_with_no_arguments() {
 
 #[test]
 fn test_completion_help_message() {
-    let output = Command::new("cargo")
-        .args(["run", "--bin", "cmdrun", "--", "completion", "--help"])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["completion", "--help"])
         .output()
         .expect("Failed to run command");
 
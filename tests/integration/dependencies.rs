@@ -200,10 +200,20 @@ async fn test_missing_dependency() {
             description: "Invalid command".to_string(),
             cmd: CommandSpec::Single("echo test".to_string()),
             env: Default::default(),
+            env_file: None,
+            source_dir: None,
             deps: vec!["nonexistent".to_string()],
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
             timeout: None,
             platform: vec![],
             working_dir: None,
@@ -3,6 +3,7 @@
 //! 環境管理機能の統合テスト
 
 use cmdrun::config::environment::EnvironmentManager;
+use std::time::Duration;
 use tempfile::TempDir;
 use tokio::fs;
 
@@ -102,7 +103,7 @@ cmd = "echo dev"
     let merged = manager.load_config_for_environment("dev").await.unwrap();
 
     // Verify merge
-    assert_eq!(merged.config.timeout, 600); // dev overrides
+    assert_eq!(merged.config.timeout, Duration::from_secs(600)); // dev overrides
     assert_eq!(merged.commands.len(), 2); // base + dev commands
     assert!(merged.commands.contains_key("test"));
     assert!(merged.commands.contains_key("dev_test"));
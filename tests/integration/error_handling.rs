@@ -7,6 +7,7 @@ use cmdrun::command::executor::{CommandExecutor, ExecutionContext};
 use cmdrun::config::schema::{Command, CommandSpec};
 use cmdrun::config::Language;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_timeout_handling() {
@@ -20,11 +21,16 @@ async fn test_timeout_handling() {
         working_dir: PathBuf::from("."),
         env: AHashMap::new(),
         shell,
-        timeout: Some(1), // 1秒でタイムアウト
+        timeout: Some(Duration::from_secs(1)), // 1秒でタイムアウト
         strict: false,
         echo: false,
         color: false,
         language: Language::default(), allow_command_chaining: false, allow_subshells: false,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
+        command_allowlist: None,
     };
     let executor = CommandExecutor::new(ctx);
 
@@ -39,12 +45,22 @@ async fn test_timeout_handling() {
         cmd: CommandSpec::Single(sleep_command.to_string()),
         deps: vec![],
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         timeout: None, // グローバル設定のタイムアウトを使用
         parallel: false,
         tags: vec![],
         platform: vec![],
         confirm: false, allow_chaining: None, allow_subshells: None,
+        confirm_message: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd).await;
@@ -105,11 +121,16 @@ async fn test_command_not_found_error() {
         working_dir: PathBuf::from("."),
         env: AHashMap::new(),
         shell,
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: Language::default(), allow_command_chaining: false, allow_subshells: false,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
+        command_allowlist: None,
     };
     let executor = CommandExecutor::new(ctx);
 
@@ -118,12 +139,22 @@ async fn test_command_not_found_error() {
         cmd: CommandSpec::Single("nonexistent_command_12345_xyz".to_string()),
         deps: vec![],
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         timeout: None,
         parallel: false,
         tags: vec![],
         platform: vec![],
         confirm: false, allow_chaining: None, allow_subshells: None,
+        confirm_message: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd).await;
@@ -184,11 +215,16 @@ async fn test_invalid_working_directory() {
         working_dir: PathBuf::from("."),
         env: AHashMap::new(),
         shell,
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: Language::default(), allow_command_chaining: false, allow_subshells: false,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
+        command_allowlist: None,
     };
     let executor = CommandExecutor::new(ctx);
 
@@ -197,12 +233,22 @@ async fn test_invalid_working_directory() {
         cmd: CommandSpec::Single("echo test".to_string()),
         deps: vec![],
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: Some(PathBuf::from("/nonexistent/directory/path/12345")),
         timeout: None,
         parallel: false,
         tags: vec![],
         platform: vec![],
         confirm: false, allow_chaining: None, allow_subshells: None,
+        confirm_message: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd).await;
@@ -224,12 +270,22 @@ fn test_empty_command_id() {
         cmd: CommandSpec::Single("echo test".to_string()),
         deps: vec![],
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         timeout: None,
         parallel: false,
         tags: vec![],
         platform: vec![],
         confirm: false, allow_chaining: None, allow_subshells: None,
+        confirm_message: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     // コマンド自体は作成できるが、IDが空の場合の処理を確認
@@ -283,12 +339,22 @@ fn test_duplicate_command_definition() {
         cmd: CommandSpec::Single("echo test".to_string()),
         deps: vec![],
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         timeout: None,
         parallel: false,
         tags: vec![],
         platform: vec![],
         confirm: false, allow_chaining: None, allow_subshells: None,
+        confirm_message: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     // 同じIDで2回追加
@@ -311,11 +377,16 @@ async fn test_platform_specific_command_execution() {
         working_dir: PathBuf::from("."),
         env: AHashMap::new(),
         shell,
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: Language::default(), allow_command_chaining: false, allow_subshells: false,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
+        command_allowlist: None,
     };
     let executor = CommandExecutor::new(ctx);
 
@@ -330,12 +401,22 @@ async fn test_platform_specific_command_execution() {
         cmd: CommandSpec::Single(platform_cmd.to_string()),
         deps: vec![],
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         timeout: None,
         parallel: false,
         tags: vec![],
         platform: vec![],
         confirm: false, allow_chaining: None, allow_subshells: None,
+        confirm_message: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd).await;
@@ -14,6 +14,7 @@ use ahash::AHashMap;
 use cmdrun::command::executor::{CommandExecutor, ExecutionContext};
 use cmdrun::config::schema::{Command, CommandSpec, Platform};
 use std::path::PathBuf;
+use std::time::Duration;
 use tempfile::TempDir;
 
 // ============================================================================
@@ -30,13 +31,18 @@ async fn test_command_timeout() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(1), // 1 second timeout
+        timeout: Some(Duration::from_secs(1)), // 1 second timeout
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -52,6 +58,8 @@ async fn test_command_timeout() {
         description: "Long running command".to_string(),
         cmd: CommandSpec::Single(sleep_cmd.to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -59,8 +67,16 @@ async fn test_command_timeout() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -92,13 +108,18 @@ async fn test_command_timeout_with_custom_timeout() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(2), // 2 second context timeout
+        timeout: Some(Duration::from_secs(2)), // 2 second context timeout
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -110,6 +131,8 @@ async fn test_command_timeout_with_custom_timeout() {
         description: "Fast command".to_string(),
         cmd: CommandSpec::Single(fast_cmd.to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -117,8 +140,16 @@ async fn test_command_timeout_with_custom_timeout() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -148,6 +179,11 @@ async fn test_no_timeout_setting() {
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -156,6 +192,8 @@ async fn test_no_timeout_setting() {
         description: "Quick command".to_string(),
         cmd: CommandSpec::Single("echo no_timeout".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -163,8 +201,16 @@ async fn test_no_timeout_setting() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -187,13 +233,18 @@ async fn test_command_not_found() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -203,6 +254,8 @@ async fn test_command_not_found() {
         description: "Nonexistent command".to_string(),
         cmd: CommandSpec::Single("this-command-definitely-does-not-exist-12345".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -210,8 +263,16 @@ async fn test_command_not_found() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -264,13 +325,18 @@ async fn test_command_with_invalid_syntax() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -289,6 +355,8 @@ async fn test_command_with_invalid_syntax() {
         description: "Invalid syntax".to_string(),
         cmd: CommandSpec::Single(invalid_cmd.to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -296,8 +364,16 @@ async fn test_command_with_invalid_syntax() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -337,13 +413,18 @@ async fn test_execute_file_without_permission() {
         working_dir: PathBuf::from("."),
         env: AHashMap::new(),
         shell: "bash".to_string(),
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -352,6 +433,8 @@ async fn test_execute_file_without_permission() {
         description: "Execute without permission".to_string(),
         cmd: CommandSpec::Single(script_path.to_string_lossy().to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -359,8 +442,16 @@ async fn test_execute_file_without_permission() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -387,13 +478,18 @@ async fn test_invalid_working_directory() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -402,6 +498,8 @@ async fn test_invalid_working_directory() {
         description: "Command with invalid working dir".to_string(),
         cmd: CommandSpec::Single("echo test".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -409,8 +507,16 @@ async fn test_invalid_working_directory() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -426,47 +532,92 @@ async fn test_invalid_working_directory() {
 #[tokio::test]
 async fn test_command_specific_working_directory() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let package_dir = temp_dir.path().join("packages").join("app");
+    std::fs::create_dir_all(&package_dir).expect("Failed to create package dir");
 
     let ctx = ExecutionContext {
-        working_dir: PathBuf::from("."),
+        working_dir: temp_dir.path().to_path_buf(),
         env: AHashMap::new(),
         shell: if cfg!(windows) {
             "cmd".to_string()
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
 
-    // Command with valid working directory
-    let command = Command {
+    let print_cwd = |working_dir: Option<PathBuf>| Command {
         description: "Command with specific working dir".to_string(),
-        cmd: CommandSpec::Single("echo test".to_string()),
+        cmd: CommandSpec::Single(if cfg!(windows) { "cd" } else { "pwd" }.to_string()),
         env: AHashMap::new(),
-        working_dir: Some(temp_dir.path().to_path_buf()),
+        env_file: None,
+        source_dir: None,
+        working_dir,
         deps: vec![],
         platform: vec![],
         tags: vec![],
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
-    };
-
-    let result = executor.execute(&command).await;
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+    let ran_in =
+        |stdout: &str| std::fs::canonicalize(stdout.trim()).expect("Command should print its cwd");
+    let expected = std::fs::canonicalize(&package_dir).unwrap();
+
+    // Absolute path is used as-is
+    let result = executor
+        .execute(&print_cwd(Some(package_dir.clone())))
+        .await
+        .expect("Command should run in its working_dir");
+    assert_eq!(ran_in(&result.stdout), expected);
+
+    // Relative path is resolved against the context working dir
+    let result = executor
+        .execute(&print_cwd(Some(PathBuf::from("packages/app"))))
+        .await
+        .expect("Command should run in its relative working_dir");
+    assert_eq!(ran_in(&result.stdout), expected);
+
+    // Without working_dir the context working dir is used
+    let result = executor.execute(&print_cwd(None)).await.unwrap();
+    assert_eq!(
+        ran_in(&result.stdout),
+        std::fs::canonicalize(temp_dir.path()).unwrap()
+    );
 
-    // Should succeed (but current implementation doesn't use command.working_dir)
-    // This test documents the expected behavior
-    assert!(result.is_ok() || result.is_err()); // Just verify it doesn't panic
+    // Missing directory is reported before spawning
+    let err = executor
+        .execute(&print_cwd(Some(PathBuf::from("packages/missing"))))
+        .await
+        .expect_err("Missing working_dir should fail");
+    assert!(
+        err.to_string().contains("Working directory not found"),
+        "unexpected error: {}",
+        err
+    );
 }
 
 // ============================================================================
@@ -483,13 +634,18 @@ async fn test_dangerous_env_vars_warning() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -501,6 +657,8 @@ async fn test_dangerous_env_vars_warning() {
         description: "Command with dangerous env var".to_string(),
         cmd: CommandSpec::Single("echo test".to_string()),
         env: dangerous_env,
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -508,8 +666,16 @@ async fn test_dangerous_env_vars_warning() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     // Should execute but log warning (captured by tracing)
@@ -538,13 +704,18 @@ async fn test_command_with_nonzero_exit_code() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -556,6 +727,8 @@ async fn test_command_with_nonzero_exit_code() {
         description: "Command with exit code 1".to_string(),
         cmd: CommandSpec::Single(fail_cmd.to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -563,8 +736,16 @@ async fn test_command_with_nonzero_exit_code() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -594,13 +775,18 @@ async fn test_command_with_zero_exit_code() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -609,6 +795,8 @@ async fn test_command_with_zero_exit_code() {
         description: "Successful command".to_string(),
         cmd: CommandSpec::Single("echo success".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -616,8 +804,16 @@ async fn test_command_with_zero_exit_code() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -643,13 +839,18 @@ async fn test_parallel_execution_with_one_failure() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -658,6 +859,8 @@ async fn test_parallel_execution_with_one_failure() {
         description: "Success command".to_string(),
         cmd: CommandSpec::Single("echo success".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -665,14 +868,24 @@ async fn test_parallel_execution_with_one_failure() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let fail_cmd = Command {
         description: "Failing command".to_string(),
         cmd: CommandSpec::Single("exit 1".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -680,8 +893,16 @@ async fn test_parallel_execution_with_one_failure() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let commands = vec![&success_cmd, &fail_cmd];
@@ -705,13 +926,18 @@ async fn test_parallel_execution_empty_commands() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -738,13 +964,18 @@ async fn test_undefined_variable_in_strict_mode() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: true, // Strict mode
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -753,6 +984,8 @@ async fn test_undefined_variable_in_strict_mode() {
         description: "Command with undefined variable".to_string(),
         cmd: CommandSpec::Single("echo ${UNDEFINED_VARIABLE_12345}".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -760,8 +993,16 @@ async fn test_undefined_variable_in_strict_mode() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -784,13 +1025,18 @@ async fn test_defined_variable_interpolation() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: true,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     ctx.env
@@ -802,6 +1048,8 @@ async fn test_defined_variable_interpolation() {
         description: "Command with defined variable".to_string(),
         cmd: CommandSpec::Single("echo ${TEST_VAR}".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -809,8 +1057,16 @@ async fn test_defined_variable_interpolation() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -840,13 +1096,18 @@ async fn test_platform_mismatch() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -862,6 +1123,8 @@ async fn test_platform_mismatch() {
         description: "Platform-specific command".to_string(),
         cmd: CommandSpec::Single("echo test".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: wrong_platform,
@@ -869,8 +1132,16 @@ async fn test_platform_mismatch() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&command).await;
@@ -897,13 +1168,18 @@ async fn test_execute_parallel_actually_runs_commands() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -913,6 +1189,8 @@ async fn test_execute_parallel_actually_runs_commands() {
         description: "Test command 1".to_string(),
         cmd: CommandSpec::Single("echo test1".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -920,14 +1198,24 @@ async fn test_execute_parallel_actually_runs_commands() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let cmd2 = Command {
         description: "Test command 2".to_string(),
         cmd: CommandSpec::Single("echo test2".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -935,8 +1223,16 @@ async fn test_execute_parallel_actually_runs_commands() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let commands = vec![&cmd1, &cmd2];
@@ -985,13 +1281,18 @@ async fn test_execute_parallel_with_empty_list() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -1016,13 +1317,18 @@ async fn test_execute_parallel_with_failures() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -1031,6 +1337,8 @@ async fn test_execute_parallel_with_failures() {
         description: "Success command".to_string(),
         cmd: CommandSpec::Single("echo success".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1038,14 +1346,24 @@ async fn test_execute_parallel_with_failures() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let cmd_failure = Command {
         description: "Failure command".to_string(),
         cmd: CommandSpec::Single("exit 1".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1053,8 +1371,16 @@ async fn test_execute_parallel_with_failures() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let commands = vec![&cmd_success, &cmd_failure];
@@ -1085,13 +1411,18 @@ async fn test_dangerous_env_vars_warning_logic() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -1104,6 +1435,8 @@ async fn test_dangerous_env_vars_warning_logic() {
         description: "Dangerous command".to_string(),
         cmd: CommandSpec::Single("echo test".to_string()),
         env: env_with_danger,
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1111,8 +1444,16 @@ async fn test_dangerous_env_vars_warning_logic() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     // Should execute (with warning in logs) but not fail
@@ -1127,6 +1468,8 @@ async fn test_dangerous_env_vars_warning_logic() {
         description: "Safe command".to_string(),
         cmd: CommandSpec::Single("echo test".to_string()),
         env: AHashMap::new(), // No dangerous vars
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1134,8 +1477,16 @@ async fn test_dangerous_env_vars_warning_logic() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_safe).await;
@@ -1155,13 +1506,18 @@ async fn test_platform_support_check_logic() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -1171,6 +1527,8 @@ async fn test_platform_support_check_logic() {
         description: "No platform restriction".to_string(),
         cmd: CommandSpec::Single("echo test".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![], // Empty = all platforms
@@ -1178,8 +1536,16 @@ async fn test_platform_support_check_logic() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_no_platform).await;
@@ -1201,6 +1567,8 @@ async fn test_platform_support_check_logic() {
         description: "Current platform command".to_string(),
         cmd: CommandSpec::Single("echo test".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: current_platform,
@@ -1208,8 +1576,16 @@ async fn test_platform_support_check_logic() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_current_platform).await;
@@ -1229,6 +1605,8 @@ async fn test_platform_support_check_logic() {
         description: "Wrong platform command".to_string(),
         cmd: CommandSpec::Single("echo test".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: wrong_platform,
@@ -1236,8 +1614,16 @@ async fn test_platform_support_check_logic() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_wrong_platform).await;
@@ -1259,13 +1645,18 @@ async fn test_shell_detection_logic_powershell() {
             working_dir: PathBuf::from("."),
             env: AHashMap::new(),
             shell: "pwsh".to_string(), // Only "pwsh"
-            timeout: Some(30),
+            timeout: Some(Duration::from_secs(30)),
             strict: false,
             echo: false,
             color: false,
             language: cmdrun::config::Language::default(),
             allow_command_chaining: false,
             allow_subshells: false,
+            command_allowlist: None,
+            allow_unsafe: false,
+            merge_stderr: false,
+            kill_grace_ms: 2000,
+            max_parallel: None,
         };
 
         let executor_pwsh = CommandExecutor::new(ctx_pwsh);
@@ -1273,6 +1664,8 @@ async fn test_shell_detection_logic_powershell() {
             description: "PowerShell command".to_string(),
             cmd: CommandSpec::Single("Write-Host 'test'".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -1280,8 +1673,16 @@ async fn test_shell_detection_logic_powershell() {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor_pwsh.execute(&cmd).await;
@@ -1295,13 +1696,18 @@ async fn test_shell_detection_logic_powershell() {
             working_dir: PathBuf::from("."),
             env: AHashMap::new(),
             shell: "powershell".to_string(), // Only "powershell"
-            timeout: Some(30),
+            timeout: Some(Duration::from_secs(30)),
             strict: false,
             echo: false,
             color: false,
             language: cmdrun::config::Language::default(),
             allow_command_chaining: false,
             allow_subshells: false,
+            command_allowlist: None,
+            allow_unsafe: false,
+            merge_stderr: false,
+            kill_grace_ms: 2000,
+            max_parallel: None,
         };
 
         let executor_powershell = CommandExecutor::new(ctx_powershell);
@@ -1316,13 +1722,18 @@ async fn test_shell_detection_logic_powershell() {
             working_dir: PathBuf::from("."),
             env: AHashMap::new(),
             shell: "cmd".to_string(), // cmd.exe
-            timeout: Some(30),
+            timeout: Some(Duration::from_secs(30)),
             strict: false,
             echo: false,
             color: false,
             language: cmdrun::config::Language::default(),
             allow_command_chaining: false,
             allow_subshells: false,
+            command_allowlist: None,
+            allow_unsafe: false,
+            merge_stderr: false,
+            kill_grace_ms: 2000,
+            max_parallel: None,
         };
 
         let executor_cmd = CommandExecutor::new(ctx_cmd);
@@ -1330,6 +1741,8 @@ async fn test_shell_detection_logic_powershell() {
             description: "CMD command".to_string(),
             cmd: CommandSpec::Single("echo test".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -1337,8 +1750,16 @@ async fn test_shell_detection_logic_powershell() {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor_cmd.execute(&cmd_cmd).await;
@@ -1355,13 +1776,18 @@ async fn test_shell_detection_logic_powershell() {
             working_dir: PathBuf::from("."),
             env: AHashMap::new(),
             shell: "bash".to_string(),
-            timeout: Some(30),
+            timeout: Some(Duration::from_secs(30)),
             strict: false,
             echo: false,
             color: false,
             language: cmdrun::config::Language::default(),
             allow_command_chaining: false,
             allow_subshells: false,
+            command_allowlist: None,
+            allow_unsafe: false,
+            merge_stderr: false,
+            kill_grace_ms: 2000,
+            max_parallel: None,
         };
 
         let executor = CommandExecutor::new(ctx);
@@ -1369,6 +1795,8 @@ async fn test_shell_detection_logic_powershell() {
             description: "Bash command".to_string(),
             cmd: CommandSpec::Single("echo test".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -1376,8 +1804,16 @@ async fn test_shell_detection_logic_powershell() {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor.execute(&cmd).await;
@@ -1402,13 +1838,18 @@ async fn test_print_command_function_is_called() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: true, // Enable echo to trigger print_command
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -1417,6 +1858,8 @@ async fn test_print_command_function_is_called() {
         description: "Test command".to_string(),
         cmd: CommandSpec::Single("echo test_output_12345".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1424,8 +1867,16 @@ async fn test_print_command_function_is_called() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     // Execute command - print_command should be called internally
@@ -1454,13 +1905,18 @@ async fn test_is_cd_command_detection() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -1475,6 +1931,8 @@ async fn test_is_cd_command_detection() {
             "cd /tmp".to_string()
         }),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1482,8 +1940,16 @@ async fn test_is_cd_command_detection() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_cd_simple).await;
@@ -1501,6 +1967,8 @@ async fn test_is_cd_command_detection() {
             "cd /tmp | echo done".to_string()
         }),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1508,8 +1976,16 @@ async fn test_is_cd_command_detection() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_cd_pipe).await;
@@ -1526,6 +2002,8 @@ async fn test_is_cd_command_detection() {
             "cd /tmp > /dev/null".to_string()
         }),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1533,8 +2011,16 @@ async fn test_is_cd_command_detection() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_cd_redirect).await;
@@ -1546,6 +2032,8 @@ async fn test_is_cd_command_detection() {
         description: "Not a CD command".to_string(),
         cmd: CommandSpec::Single("echo test".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1553,8 +2041,16 @@ async fn test_is_cd_command_detection() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_not_cd).await;
@@ -1574,13 +2070,18 @@ async fn test_warn_shell_builtin_is_invoked() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -1595,6 +2096,8 @@ async fn test_warn_shell_builtin_is_invoked() {
             "cd /tmp".to_string()
         }),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1602,8 +2105,16 @@ async fn test_warn_shell_builtin_is_invoked() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_cd).await;
@@ -1625,6 +2136,8 @@ async fn test_warn_shell_builtin_is_invoked() {
             "export VAR=value".to_string()
         }),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1632,8 +2145,16 @@ async fn test_warn_shell_builtin_is_invoked() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_export).await;
@@ -1656,13 +2177,18 @@ async fn test_cd_command_case_insensitive() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -1674,6 +2200,8 @@ async fn test_cd_command_case_insensitive() {
         description: "Lowercase cd for case-insensitive test".to_string(),
         cmd: CommandSpec::Single("cd".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1681,8 +2209,16 @@ async fn test_cd_command_case_insensitive() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_cd_upper).await;
@@ -1695,6 +2231,8 @@ async fn test_cd_command_case_insensitive() {
         description: "Mixed case cd test".to_string(),
         cmd: CommandSpec::Single("cd".to_string()),
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         deps: vec![],
         platform: vec![],
@@ -1702,8 +2240,16 @@ async fn test_cd_command_case_insensitive() {
         timeout: None,
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd_cd_mixed).await;
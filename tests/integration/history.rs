@@ -80,11 +80,11 @@ fn test_history_recorder() {
     assert!(entry.success);
     assert_eq!(entry.duration_ms, Some(2500));
 
-    // Verify sensitive data is filtered
+    // Verify sensitive data is redacted
     if let Some(env_json) = entry.environment {
         let stored_env: HashMap<String, String> = serde_json::from_str(&env_json).unwrap();
         assert!(stored_env.contains_key("PATH"));
-        assert!(!stored_env.contains_key("API_KEY")); // Should be filtered
+        assert_eq!(stored_env["API_KEY"], "***REDACTED***"); // Should be redacted
     }
 }
 
@@ -126,6 +126,51 @@ fn test_history_export_csv() {
     assert_eq!(line_count, 3);
 }
 
+#[test]
+fn test_history_export_yaml_roundtrip() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut storage = HistoryStorage::with_path(temp_file.path()).unwrap();
+
+    storage.add(&create_test_entry("cmd1", true)).unwrap();
+    let mut minimal = create_test_entry("cmd2", false);
+    minimal.args = None;
+    minimal.exit_code = None;
+    storage.add(&minimal).unwrap();
+
+    let yaml = storage.export_yaml(None).unwrap();
+    let parsed: Vec<HistoryEntry> = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(parsed, storage.list(None, None).unwrap());
+
+    // Field order follows the struct definition so diffs stay stable
+    let first = yaml.lines().take(3).collect::<Vec<_>>();
+    assert!(first[0].starts_with("- id: "));
+    assert!(first[1].starts_with("  command: "));
+    assert!(first[2].starts_with("  args: "));
+}
+
+#[test]
+fn test_history_export_toml_roundtrip() {
+    #[derive(serde::Deserialize)]
+    struct TomlExport {
+        history: Vec<HistoryEntry>,
+    }
+
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut storage = HistoryStorage::with_path(temp_file.path()).unwrap();
+
+    storage.add(&create_test_entry("cmd1", true)).unwrap();
+    let mut minimal = create_test_entry("cmd2", false);
+    minimal.working_dir = None;
+    minimal.environment = None;
+    storage.add(&minimal).unwrap();
+
+    let toml_str = storage.export_toml(None).unwrap();
+    assert_eq!(toml_str.matches("[[history]]").count(), 2);
+
+    let parsed: TomlExport = toml::from_str(&toml_str).unwrap();
+    assert_eq!(parsed.history, storage.list(None, None).unwrap());
+}
+
 #[test]
 #[ignore] // TODO: Fix max_entries logic - assertion failure on cmd8 vs cmd9
 fn test_history_max_entries() {
@@ -192,9 +237,11 @@ fn test_sensitive_data_filtering() {
         // Safe keys should be present
         assert!(stored_env.contains_key("PATH"));
         assert!(stored_env.contains_key("HOME"));
-        // Sensitive keys should be filtered out
-        assert!(!stored_env.contains_key("API_KEY"));
-        assert!(!stored_env.contains_key("PASSWORD"));
+        // Sensitive values should be redacted
+        assert_eq!(stored_env["API_KEY"], "***REDACTED***");
+        assert_eq!(stored_env["PASSWORD"], "***REDACTED***");
+        assert!(!env_json.contains("super_secret"));
+        assert!(!env_json.contains("hunter2"));
     }
 }
 
@@ -212,5 +259,9 @@ fn create_test_entry(command: &str, success: bool) -> HistoryEntry {
         success,
         working_dir: Some("/tmp".to_string()),
         environment: Some(r#"{"PATH": "/usr/bin"}"#.to_string()),
+        confirm_skipped: false,
+        skipped: false,
+        hostname: String::new(),
+        cmdrun_version: String::new(),
     }
 }
@@ -5,7 +5,7 @@ use cmdrun::command::executor::{CommandExecutor, ExecutionContext};
 use cmdrun::config::schema::{Command, CommandSpec, CommandsConfig, GlobalConfig};
 use ahash::AHashMap;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// テスト用の設定を作成
 fn create_test_config() -> CommandsConfig {
@@ -18,6 +18,8 @@ fn create_test_config() -> CommandsConfig {
             description: "Fast command 1".to_string(),
             cmd: CommandSpec::Single("echo fast1".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -25,6 +27,14 @@ fn create_test_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false, allow_chaining: None, allow_subshells: None,
+            confirm_message: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -34,6 +44,8 @@ fn create_test_config() -> CommandsConfig {
             description: "Fast command 2".to_string(),
             cmd: CommandSpec::Single("echo fast2".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -41,6 +53,14 @@ fn create_test_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false, allow_chaining: None, allow_subshells: None,
+            confirm_message: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -50,6 +70,8 @@ fn create_test_config() -> CommandsConfig {
             description: "Fast command 3".to_string(),
             cmd: CommandSpec::Single("echo fast3".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -57,6 +79,14 @@ fn create_test_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false, allow_chaining: None, allow_subshells: None,
+            confirm_message: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -79,6 +109,8 @@ fn create_dependency_config() -> CommandsConfig {
             description: "Root command".to_string(),
             cmd: CommandSpec::Single("echo root".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec!["a".to_string(), "b".to_string()],
             platform: vec![],
@@ -86,6 +118,14 @@ fn create_dependency_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false, allow_chaining: None, allow_subshells: None,
+            confirm_message: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -96,6 +136,8 @@ fn create_dependency_config() -> CommandsConfig {
             description: "Command A".to_string(),
             cmd: CommandSpec::Single("echo a".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec!["base".to_string()],
             platform: vec![],
@@ -103,6 +145,14 @@ fn create_dependency_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false, allow_chaining: None, allow_subshells: None,
+            confirm_message: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -113,6 +163,8 @@ fn create_dependency_config() -> CommandsConfig {
             description: "Command B".to_string(),
             cmd: CommandSpec::Single("echo b".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec!["base".to_string()],
             platform: vec![],
@@ -120,6 +172,14 @@ fn create_dependency_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false, allow_chaining: None, allow_subshells: None,
+            confirm_message: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -130,6 +190,8 @@ fn create_dependency_config() -> CommandsConfig {
             description: "Base command".to_string(),
             cmd: CommandSpec::Single("echo base".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -137,6 +199,14 @@ fn create_dependency_config() -> CommandsConfig {
             timeout: None,
             parallel: false,
             confirm: false, allow_chaining: None, allow_subshells: None,
+            confirm_message: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -159,10 +229,15 @@ async fn test_parallel_execution() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(10),
+        timeout: Some(Duration::from_secs(10)),
         strict: true,
         echo: false,
         color: false,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
+        command_allowlist: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -229,10 +304,15 @@ async fn test_parallel_with_dependencies() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(10),
+        timeout: Some(Duration::from_secs(10)),
         strict: true,
         echo: false,
         color: false,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
+        command_allowlist: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -289,6 +369,8 @@ async fn test_parallel_error_handling() {
             description: "Failing command".to_string(),
             cmd: CommandSpec::Single("exit 1".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -296,6 +378,14 @@ async fn test_parallel_error_handling() {
             timeout: None,
             parallel: false,
             confirm: false, allow_chaining: None, allow_subshells: None,
+            confirm_message: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -307,10 +397,15 @@ async fn test_parallel_error_handling() {
         } else {
             "bash".to_string()
         },
-        timeout: Some(10),
+        timeout: Some(Duration::from_secs(10)),
         strict: true,
         echo: false,
         color: false,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
+        command_allowlist: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -22,14 +22,24 @@ fn create_command_with_platform(description: &str, platforms: Vec<Platform>) ->
         cmd: CommandSpec::Single("echo test".to_string()),
         working_dir: None,
         env: Default::default(),
+        env_file: None,
+        source_dir: None,
         timeout: None,
         deps: vec![],
         platform: platforms,
         tags: vec![],
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     }
 }
 
@@ -253,6 +263,8 @@ async fn test_platform_validation_before_execution() {
         cmd: CommandSpec::Single("this-command-does-not-exist".to_string()),
         working_dir: None,
         env: Default::default(),
+        env_file: None,
+        source_dir: None,
         timeout: None,
         deps: vec![],
         platform: if cfg!(target_os = "windows") {
@@ -263,8 +275,16 @@ async fn test_platform_validation_before_execution() {
         tags: vec![],
         parallel: false,
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&incompatible_cmd).await;
@@ -19,19 +19,11 @@ fn run_plugin_command_with_config(
     args: &[&str],
     config_path: &std::path::Path,
 ) -> Result<std::process::Output, std::io::Error> {
-    let mut cmd_args = vec![
-        "run",
-        "--bin",
-        "cmdrun",
-        "--features",
-        "plugin-system",
-        "--",
-        "--config",
-        config_path.to_str().unwrap(),
-    ];
-    cmd_args.extend_from_slice(args);
-
-    Command::new("cargo").args(&cmd_args).output()
+    Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .arg("--config")
+        .arg(config_path)
+        .args(args)
+        .output()
 }
 
 /// Test helper to run cmdrun plugin command (backward compatibility)
@@ -142,19 +134,8 @@ fn test_plugin_list_with_custom_config() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let config_path = create_test_config(&temp_dir);
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--features",
-            "plugin-system",
-            "--",
-            "--config",
-            config_path.to_str().unwrap(),
-            "plugin",
-            "list",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "plugin", "list"])
         .output()
         .expect("Failed to run command");
 
@@ -335,8 +316,8 @@ fn test_plugin_invalid_subcommand() {
 #[cfg(not(feature = "plugin-system"))]
 fn test_plugin_disabled_without_feature() {
     // This test verifies behavior when plugin-system feature is disabled
-    let output = Command::new("cargo")
-        .args(["run", "--bin", "cmdrun", "--", "plugin", "list"])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["plugin", "list"])
         .output()
         .expect("Failed to run command");
 
@@ -403,19 +384,8 @@ fn test_plugin_with_global_config_flag() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let config_path = create_test_config(&temp_dir);
 
-    let output = Command::new("cargo")
-        .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--features",
-            "plugin-system",
-            "--",
-            "--config",
-            config_path.to_str().unwrap(),
-            "plugin",
-            "list",
-        ])
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args(["--config", config_path.to_str().unwrap(), "plugin", "list"])
         .output()
         .expect("Failed to run command");
 
@@ -431,14 +401,8 @@ fn test_plugin_with_color_flag() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let config_path = create_test_config(&temp_dir);
 
-    let output = Command::new("cargo")
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
         .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--features",
-            "plugin-system",
-            "--",
             "--config",
             config_path.to_str().unwrap(),
             "--color",
@@ -461,14 +425,8 @@ fn test_plugin_with_verbose_flag() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let config_path = create_test_config(&temp_dir);
 
-    let output = Command::new("cargo")
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
         .args([
-            "run",
-            "--bin",
-            "cmdrun",
-            "--features",
-            "plugin-system",
-            "--",
             "--config",
             config_path.to_str().unwrap(),
             "-v",
@@ -564,6 +522,54 @@ fn test_all_plugin_subcommands_have_help() {
     }
 }
 
+// ============================================================================
+// Plugin Install Tests
+// ============================================================================
+
+#[test]
+#[cfg(feature = "plugin-system")]
+fn test_plugin_install_invalid_library_rolls_back() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = create_test_config(&temp_dir);
+    let original = std::fs::read_to_string(&config_path).unwrap();
+
+    let library = temp_dir.path().join("libbroken.so");
+    std::fs::write(&library, b"not a library").unwrap();
+    let config_home = temp_dir.path().join("config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cmdrun"))
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "plugin",
+            "install",
+            library.to_str().unwrap(),
+        ])
+        .env("XDG_CONFIG_HOME", &config_home)
+        .output()
+        .expect("Failed to run plugin install command");
+
+    assert!(
+        !output.status.success(),
+        "Installing a broken library should fail"
+    );
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+    assert!(
+        !config_home.join("cmdrun/plugins/libbroken.so").exists(),
+        "Copied library should be removed on failure"
+    );
+}
+
+#[test]
+#[cfg(feature = "plugin-system")]
+fn test_plugin_install_missing_source() {
+    let output = run_plugin_command(&["plugin", "install", "/nonexistent/libmissing.so"])
+        .expect("Failed to run plugin install command");
+
+    assert!(!output.status.success());
+    assert_stderr_contains(&output.stderr, "not found");
+}
+
 // ============================================================================
 // Compatibility Tests
 // ============================================================================
@@ -3,4 +3,5 @@
 
 mod test_add_commands;
 mod test_config_commands;
+mod test_plugin_hooks;
 mod test_search_commands;
@@ -1,6 +1,6 @@
 //! Direct library tests for config command module
 
-use cmdrun::commands::{handle_get, handle_set, handle_show};
+use cmdrun::commands::{handle_append, handle_get, handle_set, handle_show};
 use std::fs;
 use tempfile::tempdir;
 
@@ -54,3 +54,113 @@ language = "english"
     let result = handle_show(Some(config_path)).await;
     assert!(result.is_ok());
 }
+
+const NESTED_CONFIG: &str = r#"
+[config]
+shell = "bash"
+
+[commands.build]
+description = "Build"
+cmd = "cargo build" # release builds use `build:release`
+tags = ["rust"]
+"#;
+
+#[tokio::test]
+async fn test_config_set_nested_command_timeout() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("commands.toml");
+    fs::write(&config_path, NESTED_CONFIG).unwrap();
+
+    handle_set("commands.build.timeout", "600", Some(config_path.clone()))
+        .await
+        .unwrap();
+
+    let updated_content = fs::read_to_string(&config_path).unwrap();
+    let updated: toml::Value = toml::from_str(&updated_content).unwrap();
+    assert_eq!(
+        updated["commands"]["build"]["timeout"].as_integer(),
+        Some(600)
+    );
+    // Existing formatting and comments are preserved
+    assert!(updated_content.contains("cmd = \"cargo build\" # release builds use `build:release`"));
+}
+
+#[tokio::test]
+async fn test_config_set_env_var() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("commands.toml");
+    fs::write(&config_path, NESTED_CONFIG).unwrap();
+
+    handle_set("config.env.RUST_LOG", "debug", Some(config_path.clone()))
+        .await
+        .unwrap();
+
+    let updated: toml::Value = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert_eq!(updated["config"]["env"]["RUST_LOG"].as_str(), Some("debug"));
+}
+
+#[tokio::test]
+async fn test_config_append_to_list() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("commands.toml");
+    fs::write(&config_path, NESTED_CONFIG).unwrap();
+
+    handle_append("commands.build.tags", "ci", Some(config_path.clone()))
+        .await
+        .unwrap();
+
+    let updated: toml::Value = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    let tags: Vec<&str> = updated["commands"]["build"]["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(toml::Value::as_str)
+        .collect();
+    assert_eq!(tags, ["rust", "ci"]);
+
+    // Appending to a non-list value is rejected
+    let result = handle_append("commands.build.description", "x", Some(config_path)).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_config_set_nested_type_mismatch() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("commands.toml");
+    fs::write(&config_path, NESTED_CONFIG).unwrap();
+
+    let result = handle_set("commands.build.timeout", "soon", Some(config_path.clone())).await;
+
+    assert!(result.is_err());
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), NESTED_CONFIG);
+}
+
+#[tokio::test]
+async fn test_config_set_unknown_nested_key() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("commands.toml");
+    fs::write(&config_path, NESTED_CONFIG).unwrap();
+
+    for key in ["commands.build.bogus", "config.bogus", "config.env.A.B"] {
+        let result = handle_set(key, "1", Some(config_path.clone())).await;
+        let err = result.expect_err(key).to_string();
+        assert!(err.contains("Unknown configuration key"), "{}", err);
+    }
+}
+
+#[tokio::test]
+async fn test_config_set_cmd_runs_security_validation() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("commands.toml");
+    fs::write(&config_path, NESTED_CONFIG).unwrap();
+
+    let result = handle_set(
+        "commands.build.cmd",
+        "cargo build; rm -rf /",
+        Some(config_path.clone()),
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), NESTED_CONFIG);
+}
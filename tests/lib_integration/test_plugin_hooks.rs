@@ -0,0 +1,120 @@
+//! Integration tests for plugin hooks around a finished command
+
+#![cfg(feature = "plugin-system")]
+
+use ahash::AHashMap;
+use cmdrun::error::{CmdrunError, ExecutionError};
+use cmdrun::plugin::{
+    CommandResult, Plugin, PluginCapabilities, PluginContext, PluginManager, PluginMetadata,
+};
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records which finish hooks ran
+#[derive(Default)]
+struct RecordingPlugin {
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl Plugin for RecordingPlugin {
+    fn metadata(&self) -> PluginMetadata {
+        PluginMetadata {
+            name: "recorder".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Records hook calls".to_string(),
+            authors: vec![],
+            license: None,
+            homepage: None,
+            min_cmdrun_version: None,
+            capabilities: PluginCapabilities {
+                post_execute: true,
+                on_error: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn on_load(&mut self, _config: &AHashMap<String, String>) -> cmdrun::Result<()> {
+        Ok(())
+    }
+
+    fn post_execute(
+        &self,
+        context: &PluginContext,
+        result: &mut CommandResult,
+    ) -> cmdrun::Result<()> {
+        self.calls.lock().unwrap().push(format!(
+            "post:{}:{}",
+            context.command_name, result.exit_code
+        ));
+        Ok(())
+    }
+
+    fn on_error(&self, context: &PluginContext, error: &CmdrunError) -> cmdrun::Result<()> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("error:{}:{}", context.command_name, error));
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+fn context(name: &str) -> PluginContext {
+    PluginContext {
+        command_name: name.to_string(),
+        command: toml::from_str(r#"cmd = "true""#).unwrap(),
+        command_spec: "true".to_string(),
+        args: vec![],
+        env: Default::default(),
+        working_dir: ".".to_string(),
+        data: Default::default(),
+    }
+}
+
+fn manager_with_recorder() -> (PluginManager, Arc<Mutex<Vec<String>>>) {
+    let plugin = RecordingPlugin::default();
+    let calls = plugin.calls.clone();
+    let manager = PluginManager::new();
+    manager.registry().register(Box::new(plugin)).unwrap();
+    (manager, calls)
+}
+
+#[test]
+fn test_post_hooks_fire_after_success() {
+    let (manager, calls) = manager_with_recorder();
+
+    manager
+        .execute_finish_hooks(&context("build"), &Ok(()), 0, Duration::from_millis(5))
+        .unwrap();
+
+    assert_eq!(*calls.lock().unwrap(), vec!["post:build:0"]);
+}
+
+#[test]
+fn test_error_hooks_fire_after_failure() {
+    let (manager, calls) = manager_with_recorder();
+    let failure: anyhow::Result<()> = Err(anyhow::Error::new(CmdrunError::from(
+        ExecutionError::CommandFailed {
+            command: "test".to_string(),
+            code: 3,
+        },
+    ))
+    .context("Command failed with exit code 3"));
+
+    manager
+        .execute_finish_hooks(&context("test"), &failure, 3, Duration::ZERO)
+        .unwrap();
+
+    let calls = calls.lock().unwrap();
+    assert_eq!(calls.len(), 1, "calls: {:?}", calls);
+    assert!(calls[0].starts_with("error:test:"), "calls: {:?}", calls);
+}
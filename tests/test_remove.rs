@@ -1,6 +1,6 @@
 //! Integration test for the remove command
 
-use cmdrun::commands::handle_remove;
+use cmdrun::commands::{handle_add, handle_remove};
 use cmdrun::config::loader::ConfigLoader;
 use tempfile::TempDir;
 use tokio::fs::File;
@@ -126,7 +126,7 @@ tags = ["testing"]
             entry
                 .file_name()
                 .to_str()
-                .map(|s| s.contains("backup"))
+                .map(|s| s.ends_with(".bak"))
                 .unwrap_or(false)
         })
         .collect();
@@ -199,3 +199,69 @@ deps = ["test"]
         "Command 'build' should still exist"
     );
 }
+
+#[tokio::test]
+async fn test_add_and_remove_preserve_comments_and_formatting() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("commands.toml");
+
+    let toml_content = r#"# Project commands
+[config]
+shell = "bash" # keep bash
+
+# Build the project
+[commands.build]
+description = "Build project"
+cmd   = "cargo build"
+
+# Run the test suite
+[commands.test]
+description = "Run tests"
+cmd = "cargo test" # fast enough
+"#;
+
+    let mut file = File::create(&config_path).await.unwrap();
+    file.write_all(toml_content.as_bytes()).await.unwrap();
+
+    handle_add(
+        Some("lint".to_string()),
+        Some("cargo clippy".to_string()),
+        Some("Lint code".to_string()),
+        None,
+        None,
+        Some(config_path.clone()),
+    )
+    .await
+    .unwrap();
+
+    let after_add = tokio::fs::read_to_string(&config_path).await.unwrap();
+    assert_eq!(
+        after_add,
+        format!(
+            "{}\n[commands.lint]\ndescription = \"Lint code\"\ncmd = \"cargo clippy\"\n",
+            toml_content
+        )
+    );
+
+    handle_remove("build".to_string(), true, Some(config_path.clone()))
+        .await
+        .unwrap();
+
+    let after_remove = tokio::fs::read_to_string(&config_path).await.unwrap();
+    assert_eq!(
+        after_remove,
+        r#"# Project commands
+[config]
+shell = "bash" # keep bash
+
+# Run the test suite
+[commands.test]
+description = "Run tests"
+cmd = "cargo test" # fast enough
+
+[commands.lint]
+description = "Lint code"
+cmd = "cargo clippy"
+"#
+    );
+}
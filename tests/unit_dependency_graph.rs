@@ -13,14 +13,24 @@ fn create_test_config() -> CommandsConfig {
             cmd: CommandSpec::Single("echo test".to_string()),
             deps: vec![],
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             timeout: None,
             parallel: false,
             tags: vec![],
             platform: vec![],
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
     commands.insert(
@@ -30,14 +40,24 @@ fn create_test_config() -> CommandsConfig {
             cmd: CommandSpec::Single("echo build".to_string()),
             deps: vec!["test".to_string()],
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             timeout: None,
             parallel: false,
             tags: vec![],
             platform: vec![],
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -83,14 +103,24 @@ fn test_circular_dependency_detection() {
             cmd: CommandSpec::Single("echo a".to_string()),
             deps: vec!["b".to_string()],
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             timeout: None,
             parallel: false,
             tags: vec![],
             platform: vec![],
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
     commands.insert(
@@ -100,14 +130,24 @@ fn test_circular_dependency_detection() {
             cmd: CommandSpec::Single("echo b".to_string()),
             deps: vec!["a".to_string()],
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             timeout: None,
             parallel: false,
             tags: vec![],
             platform: vec![],
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -134,14 +174,24 @@ fn test_missing_dependency() {
             cmd: CommandSpec::Single("echo cmd".to_string()),
             deps: vec!["missing".to_string()],
             env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
             working_dir: None,
             timeout: None,
             parallel: false,
             tags: vec![],
             platform: vec![],
             confirm: false,
+            confirm_message: None,
             allow_chaining: None,
             allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
         },
     );
 
@@ -4,6 +4,7 @@ use ahash::AHashMap;
 use cmdrun::command::executor::{CommandExecutor, ExecutionContext};
 use cmdrun::config::schema::{Command, CommandSpec};
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_executor_creation() {
@@ -11,13 +12,18 @@ async fn test_executor_creation() {
         working_dir: PathBuf::from("."),
         env: AHashMap::new(),
         shell: "bash".to_string(),
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: true,
         echo: true,
         color: true,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let _executor = CommandExecutor::new(ctx);
@@ -36,13 +42,18 @@ async fn test_execute_simple_command() {
         working_dir: PathBuf::from("."),
         env: AHashMap::new(),
         shell,
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -51,14 +62,24 @@ async fn test_execute_simple_command() {
         cmd: CommandSpec::Single("echo test".to_string()),
         deps: vec![],
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         timeout: None,
         parallel: false,
         tags: vec![],
         platform: vec![],
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let result = executor.execute(&cmd).await;
@@ -77,13 +98,18 @@ async fn test_execute_parallel() {
         working_dir: PathBuf::from("."),
         env: AHashMap::new(),
         shell,
-        timeout: Some(30),
+        timeout: Some(Duration::from_secs(30)),
         strict: false,
         echo: false,
         color: false,
         language: cmdrun::config::Language::default(),
         allow_command_chaining: false,
         allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
     };
 
     let executor = CommandExecutor::new(ctx);
@@ -92,31 +118,757 @@ async fn test_execute_parallel() {
         cmd: CommandSpec::Single("echo test1".to_string()),
         deps: vec![],
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         timeout: None,
         parallel: false,
         tags: vec![],
         platform: vec![],
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
     let cmd2 = Command {
         description: "Test 2".to_string(),
         cmd: CommandSpec::Single("echo test2".to_string()),
         deps: vec![],
         env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
         working_dir: None,
         timeout: None,
         parallel: false,
         tags: vec![],
         platform: vec![],
         confirm: false,
+        confirm_message: None,
         allow_chaining: None,
         allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
     };
 
     let commands = vec![&cmd1, &cmd2];
     let result = executor.execute_parallel(&commands).await;
     assert!(result.is_ok());
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_merge_stderr_preserves_order() {
+    let ctx = ExecutionContext {
+        working_dir: PathBuf::from("."),
+        env: AHashMap::new(),
+        shell: "bash".to_string(),
+        timeout: Some(Duration::from_secs(30)),
+        strict: false,
+        echo: false,
+        color: false,
+        language: cmdrun::config::Language::default(),
+        allow_command_chaining: true,
+        allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: true,
+        kill_grace_ms: 2000,
+        max_parallel: None,
+    };
+
+    let executor = CommandExecutor::new(ctx);
+    let cmd = Command {
+        description: "Alternating output".to_string(),
+        cmd: CommandSpec::Single(
+            "echo out1; echo err1 >&2; echo out2; echo err2 >&2; echo out3".to_string(),
+        ),
+        deps: vec![],
+        env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        timeout: None,
+        parallel: false,
+        tags: vec![],
+        platform: vec![],
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    let result = executor.execute(&cmd).await.unwrap();
+    assert_eq!(result.stdout, "out1\nerr1\nout2\nerr2\nout3\n");
+    assert!(result.stderr.is_empty());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_output_pipe_fifo() {
+    use std::io::Read;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let fifo_path = temp_dir.path().join("output.fifo");
+    let status = std::process::Command::new("mkfifo")
+        .arg(&fifo_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // 読み手はFIFOを開き、書き手が閉じる（EOF）まで読み続ける
+    let reader_path = fifo_path.clone();
+    let reader = std::thread::spawn(move || {
+        let mut content = String::new();
+        std::fs::File::open(reader_path)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    });
+
+    let ctx = ExecutionContext {
+        shell: "sh".to_string(),
+        timeout: Some(Duration::from_secs(30)),
+        strict: false,
+        echo: false,
+        color: false,
+        ..Default::default()
+    };
+
+    let executor = CommandExecutor::new(ctx);
+    let cmd = Command {
+        description: "Output to FIFO".to_string(),
+        cmd: CommandSpec::Multiple(vec!["echo line1".to_string(), "echo line2".to_string()]),
+        deps: vec![],
+        env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        timeout: None,
+        parallel: false,
+        tags: vec![],
+        platform: vec![],
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: Some(fifo_path.to_string_lossy().into_owned()),
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    let result = executor.execute(&cmd).await.unwrap();
+    // 端末側の出力も維持される
+    assert_eq!(result.stdout, "line2\n");
+
+    assert_eq!(reader.join().unwrap(), "line1\nline2\n");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_output_pipe_without_reader_fails() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let fifo_path = temp_dir.path().join("output.fifo");
+    let status = std::process::Command::new("mkfifo")
+        .arg(&fifo_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let marker = temp_dir.path().join("ran");
+
+    // 読み手がいなければタイムアウトでエラーになり、コマンドは実行されない
+    let ctx = ExecutionContext {
+        shell: "sh".to_string(),
+        timeout: Some(Duration::from_millis(500)),
+        strict: false,
+        echo: false,
+        color: false,
+        ..Default::default()
+    };
+
+    let executor = CommandExecutor::new(ctx);
+    let cmd = Command {
+        description: "Output to FIFO".to_string(),
+        cmd: CommandSpec::Single(format!("touch {}", marker.display())),
+        deps: vec![],
+        env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        timeout: None,
+        parallel: false,
+        tags: vec![],
+        platform: vec![],
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: Some(fifo_path.to_string_lossy().into_owned()),
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    let start = std::time::Instant::now();
+    let err = executor.execute(&cmd).await.unwrap_err().to_string();
+    assert!(err.contains("no reader on output_pipe"), "{}", err);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(!marker.exists());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_output_log_matches_captured_output() {
+    use cmdrun::output::capture::OutputLog;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let log_path = temp_dir.path().join("run.log");
+
+    let ctx = ExecutionContext {
+        shell: "sh".to_string(),
+        timeout: Some(Duration::from_secs(30)),
+        strict: false,
+        echo: false,
+        color: false,
+        allow_command_chaining: true,
+        merge_stderr: true,
+        ..Default::default()
+    };
+
+    // `[` はメタ文字として拒否されるため、色付き出力はスクリプトで生成する
+    let script = temp_dir.path().join("colors.sh");
+    std::fs::write(
+        &script,
+        "printf '\\033[32mok\\033[0m\\n'\necho warn >&2\necho done\n",
+    )
+    .unwrap();
+
+    let log = OutputLog::open(&log_path).unwrap();
+    log.write_header("colors", &[]);
+    let executor = CommandExecutor::new(ctx).with_output_log(log);
+    let cmd = Command {
+        description: "Colored output".to_string(),
+        cmd: CommandSpec::Single(format!("sh {}", script.display())),
+        deps: vec![],
+        env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        timeout: None,
+        parallel: false,
+        tags: vec![],
+        platform: vec![],
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    let result = executor.execute(&cmd).await.unwrap();
+    assert_eq!(result.stdout, "\x1b[32mok\x1b[0m\nwarn\ndone\n");
+
+    // ヘッダーの後に、ANSIエスケープを除去した出力がそのまま続く
+    let content = std::fs::read_to_string(&log_path).unwrap();
+    let (header, body) = content.split_once('\n').unwrap();
+    assert!(header.ends_with(" cmdrun run colors ==="), "{}", header);
+    assert_eq!(body, cmdrun::output::capture::strip_ansi(&result.stdout));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_output_log_written_on_failure() {
+    use cmdrun::output::capture::OutputLog;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let log_path = temp_dir.path().join("run.log");
+
+    let ctx = ExecutionContext {
+        shell: "sh".to_string(),
+        timeout: Some(Duration::from_secs(30)),
+        strict: false,
+        echo: false,
+        color: false,
+        allow_command_chaining: true,
+        ..Default::default()
+    };
+
+    let executor = CommandExecutor::new(ctx).with_output_log(OutputLog::open(&log_path).unwrap());
+    let cmd = Command {
+        description: "Failing command".to_string(),
+        cmd: CommandSpec::Single("echo before; echo oops >&2; exit 3".to_string()),
+        deps: vec![],
+        env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        timeout: None,
+        parallel: false,
+        tags: vec![],
+        platform: vec![],
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    assert!(executor.execute(&cmd).await.is_err());
+
+    let content = std::fs::read_to_string(&log_path).unwrap();
+    assert!(content.contains("before\n"), "{}", content);
+    assert!(content.contains("oops\n"), "{}", content);
+}
+
+/// タイムアウトするスクリプトを実行し、(エラー, 経過時間, トラップの記録) を返す
+#[cfg(unix)]
+async fn run_timeout_script(
+    trap_action: &str,
+    kill_grace_ms: u64,
+) -> (String, std::time::Duration, String) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let marker = temp_dir.path().join("signals.log");
+    let script = temp_dir.path().join("trap.sh");
+    std::fs::write(
+        &script,
+        format!(
+            "trap 'echo TERM >> {marker}; {trap_action}' TERM\nwhile true; do sleep 0.05; done\n",
+            marker = marker.display(),
+        ),
+    )
+    .unwrap();
+
+    let ctx = ExecutionContext {
+        shell: "bash".to_string(),
+        timeout: Some(Duration::from_secs(1)),
+        strict: false,
+        echo: false,
+        color: false,
+        kill_grace_ms,
+        max_parallel: None,
+        ..Default::default()
+    };
+
+    let executor = CommandExecutor::new(ctx);
+    let cmd = Command {
+        description: "Trap SIGTERM".to_string(),
+        cmd: CommandSpec::Single(format!("bash {}", script.display())),
+        deps: vec![],
+        env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        timeout: None,
+        parallel: false,
+        tags: vec![],
+        platform: vec![],
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    let start = std::time::Instant::now();
+    let err = executor.execute(&cmd).await.unwrap_err();
+    let elapsed = start.elapsed();
+    let signals = std::fs::read_to_string(&marker).unwrap_or_default();
+    (err.to_string(), elapsed, signals)
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_timeout_sends_sigterm_first() {
+    // SIGTERM で後片付けして終了するプロセスは猶予期間を待たずに終わる
+    let (err, elapsed, signals) = run_timeout_script("exit 0", 5000).await;
+
+    assert!(err.contains("timed out"), "{}", err);
+    assert_eq!(signals, "TERM\n");
+    assert!(elapsed < std::time::Duration::from_secs(4), "{:?}", elapsed);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_timeout_kills_after_grace_period() {
+    // SIGTERM を無視するプロセスは猶予期間後に SIGKILL される
+    let (err, elapsed, signals) = run_timeout_script(":", 500).await;
+
+    assert!(err.contains("timed out"), "{}", err);
+    assert_eq!(signals, "TERM\n");
+    assert!(
+        elapsed >= std::time::Duration::from_millis(1500),
+        "{:?}",
+        elapsed
+    );
+    assert!(elapsed < std::time::Duration::from_secs(5), "{:?}", elapsed);
+}
+
+#[test]
+fn test_resolve_printable_commands_expands_and_redacts() {
+    use cmdrun::security::EnvRedactor;
+
+    let mut env = AHashMap::new();
+    env.insert("1".to_string(), "staging".to_string());
+    env.insert("REGION".to_string(), "eu-west-1".to_string());
+    env.insert("DEPLOY_TOKEN".to_string(), "s3cr3t-value".to_string());
+
+    let ctx = ExecutionContext {
+        env,
+        strict: false,
+        ..Default::default()
+    };
+    let executor = CommandExecutor::new(ctx);
+
+    let mut cmd_env = AHashMap::new();
+    cmd_env.insert("TARGET".to_string(), "web".to_string());
+    let cmd = Command {
+        description: "Deploy".to_string(),
+        cmd: CommandSpec::Multiple(vec![
+            "deploy --env ${1} --region ${REGION}".to_string(),
+            "notify ${TARGET} --token ${DEPLOY_TOKEN}".to_string(),
+        ]),
+        deps: vec![],
+        env: cmd_env,
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        timeout: None,
+        parallel: false,
+        tags: vec![],
+        platform: vec![],
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    let lines = executor
+        .resolve_printable_commands(&cmd, &EnvRedactor::default())
+        .unwrap();
+    assert_eq!(
+        lines,
+        vec![
+            "deploy --env staging --region eu-west-1".to_string(),
+            "notify web --token ***REDACTED***".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_plugin_context_has_resolved_command_and_args() {
+    let mut env = AHashMap::new();
+    env.insert("1".to_string(), "staging".to_string());
+    env.insert("DEPLOY_TOKEN".to_string(), "s3cr3t-value".to_string());
+
+    let ctx = ExecutionContext {
+        env,
+        strict: false,
+        working_dir: PathBuf::from("/tmp"),
+        ..Default::default()
+    };
+    let executor = CommandExecutor::new(ctx);
+
+    let mut cmd_env = AHashMap::new();
+    cmd_env.insert("TARGET".to_string(), "web".to_string());
+    let cmd = Command {
+        description: "Deploy".to_string(),
+        cmd: CommandSpec::Multiple(vec![
+            "deploy --env ${1}".to_string(),
+            "notify ${TARGET} --token ${DEPLOY_TOKEN}".to_string(),
+        ]),
+        deps: vec![],
+        env: cmd_env,
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        timeout: None,
+        parallel: false,
+        tags: vec![],
+        platform: vec![],
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        allow_subshells: None,
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: None,
+        arg_completions: vec![],
+    };
+
+    let args = vec!["staging".to_string()];
+    let context = executor.plugin_context("deploy", &cmd, &args).unwrap();
+
+    assert_eq!(context.command_name, "deploy");
+    // プラグインには実際に実行される内容を渡す（伏せ字にしない）
+    assert_eq!(
+        context.command_spec,
+        "deploy --env staging\nnotify web --token s3cr3t-value"
+    );
+    assert_eq!(context.args, args);
+    assert_eq!(context.env.get("TARGET").unwrap(), "web");
+    assert_eq!(context.working_dir, "/tmp");
+    assert!(context.data.is_empty());
+}
+
+/// 各コマンドが trace ファイルに開始・終了を記録する並列グループを実行
+#[cfg(unix)]
+async fn run_traced_group(
+    max_parallel: usize,
+) -> (Vec<cmdrun::command::executor::ExecutionResult>, Vec<String>) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let ctx = ExecutionContext {
+        working_dir: temp_dir.path().to_path_buf(),
+        shell: "sh".to_string(),
+        timeout: Some(Duration::from_secs(30)),
+        strict: false,
+        echo: false,
+        color: false,
+        allow_command_chaining: true,
+        max_parallel: Some(max_parallel),
+        ..Default::default()
+    };
+    let executor = CommandExecutor::new(ctx);
+
+    // 先頭のコマンドが最も遅く終わる
+    let commands: Vec<Command> = [("a", "0.4"), ("b", "0.1"), ("c", "0.1")]
+        .iter()
+        .map(|(id, delay)| Command {
+            description: format!("Task {}", id),
+            cmd: CommandSpec::Single(format!(
+                "echo start-{id} >> trace; sleep {delay}; echo end-{id} >> trace; echo result-{id}"
+            )),
+            deps: vec![],
+            env: AHashMap::new(),
+            env_file: None,
+            source_dir: None,
+            working_dir: None,
+            timeout: None,
+            parallel: false,
+            tags: vec![],
+            platform: vec![],
+            confirm: false,
+            confirm_message: None,
+            allow_chaining: None,
+            allow_subshells: None,
+            output_pipe: None,
+            category: None,
+            watch: None,
+            when: None,
+            when_shell: None,
+            shell: None,
+            arg_completions: vec![],
+        })
+        .collect();
+    let refs: Vec<&Command> = commands.iter().collect();
+
+    let results = executor.execute_parallel(&refs).await.unwrap();
+    let trace = std::fs::read_to_string(temp_dir.path().join("trace")).unwrap();
+    (results, trace.lines().map(String::from).collect())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_execute_parallel_jobs_one_serializes_group() {
+    let (results, trace) = run_traced_group(1).await;
+
+    // 各コマンドは前のコマンドの終了後に開始する
+    assert_eq!(trace.len(), 6, "{:?}", trace);
+    for pair in trace.chunks(2) {
+        let id = pair[0].strip_prefix("start-").expect("start marker");
+        assert_eq!(pair[1], format!("end-{}", id), "{:?}", trace);
+    }
+
+    let stdout: Vec<&str> = results.iter().map(|r| r.stdout.trim()).collect();
+    assert_eq!(stdout, vec!["result-a", "result-b", "result-c"]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_execute_parallel_results_in_input_order() {
+    let (results, trace) = run_traced_group(3).await;
+
+    // a は最後に終了するが、結果は入力順のまま
+    assert_eq!(
+        trace.last().map(String::as_str),
+        Some("end-a"),
+        "{:?}",
+        trace
+    );
+    let stdout: Vec<&str> = results.iter().map(|r| r.stdout.trim()).collect();
+    assert_eq!(stdout, vec!["result-a", "result-b", "result-c"]);
+}
+
+#[cfg(unix)]
+fn bash_context() -> ExecutionContext {
+    ExecutionContext {
+        working_dir: PathBuf::from("."),
+        env: AHashMap::new(),
+        shell: "bash".to_string(),
+        timeout: Some(Duration::from_secs(30)),
+        strict: false,
+        echo: false,
+        color: false,
+        language: cmdrun::config::Language::default(),
+        allow_command_chaining: false,
+        allow_subshells: false,
+        command_allowlist: None,
+        allow_unsafe: false,
+        merge_stderr: false,
+        kill_grace_ms: 2000,
+        max_parallel: None,
+    }
+}
+
+#[cfg(unix)]
+fn command_with_shell(cmd: &str, shell: Option<&str>) -> Command {
+    Command {
+        description: cmd.to_string(),
+        cmd: CommandSpec::Single(cmd.to_string()),
+        deps: vec![],
+        env: AHashMap::new(),
+        env_file: None,
+        source_dir: None,
+        working_dir: None,
+        timeout: None,
+        parallel: false,
+        tags: vec![],
+        platform: vec![],
+        confirm: false,
+        confirm_message: None,
+        allow_chaining: None,
+        // `print(...)` は括弧を含むため
+        allow_subshells: Some(true),
+        output_pipe: None,
+        category: None,
+        watch: None,
+        when: None,
+        when_shell: None,
+        shell: shell.map(String::from),
+        arg_completions: vec![],
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_command_shell_overrides_context_shell() {
+    if which::which("python3").is_err() {
+        return;
+    }
+
+    let executor = CommandExecutor::new(bash_context());
+
+    let python = command_with_shell("print(6 * 7)", Some("python3"));
+    let result = executor.execute(&python).await.unwrap();
+    assert_eq!(result.stdout.trim(), "42");
+
+    // 他のコマンドは引き続きコンテキストのシェル（bash）で実行される
+    let bash = command_with_shell("type -t cd", None);
+    let result = executor.execute(&bash).await.unwrap();
+    assert_eq!(result.stdout.trim(), "builtin");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_command_shell_not_found() {
+    let executor = CommandExecutor::new(bash_context());
+    let command = command_with_shell("echo hello", Some("cmdrun-no-such-shell"));
+
+    let err = executor.execute(&command).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Shell not found: cmdrun-no-such-shell"),
+        "{}",
+        err
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_arg_placeholders_are_shell_quoted() {
+    let args = vec!["a  b".to_string(), "$HOME".to_string()];
+    let executor = CommandExecutor::new(bash_context()).with_args(args);
+
+    // クォートされるため空白は保持され、`$HOME` も展開されない
+    let command = command_with_shell("echo {1}; echo {@}; echo {*}", None);
+    let command = Command {
+        allow_chaining: Some(true),
+        ..command
+    };
+    let result = executor.execute(&command).await.unwrap();
+    assert_eq!(result.stdout, "a  b\na  b $HOME\na  b $HOME\n");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_missing_arg_placeholder_in_strict_mode() {
+    let ctx = ExecutionContext {
+        strict: true,
+        ..bash_context()
+    };
+    let executor = CommandExecutor::new(ctx).with_args(vec!["one".to_string()]);
+
+    let err = executor
+        .execute(&command_with_shell("echo {1} {2}", None))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Missing positional argument {2}: 1 argument(s) given"),
+        "{}",
+        err
+    );
+}